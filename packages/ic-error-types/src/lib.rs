@@ -80,6 +80,7 @@ impl From<ErrorCode> for RejectCode {
             CertifiedStateUnavailable => SysTransient,
             CanisterInstallCodeRateLimited => SysTransient,
             CanisterHeapDeltaRateLimited => SysTransient,
+            CanisterCyclesBurnLimitExceeded => SysTransient,
             // Invalid destination errors.
             CanisterNotFound => DestinationInvalid,
             CanisterSnapshotNotFound => DestinationInvalid,
@@ -158,6 +159,7 @@ pub enum ErrorCode {
     CertifiedStateUnavailable = 208,
     CanisterInstallCodeRateLimited = 209,
     CanisterHeapDeltaRateLimited = 210,
+    CanisterCyclesBurnLimitExceeded = 211,
     // 3xx -- `RejectCode::DestinationInvalid`
     CanisterNotFound = 301,
     CanisterSnapshotNotFound = 305,
@@ -328,6 +330,7 @@ impl UserError {
             | ErrorCode::CanisterSnapshotNotFound
             | ErrorCode::CanisterSnapshotImmutable
             | ErrorCode::CanisterHeapDeltaRateLimited
+            | ErrorCode::CanisterCyclesBurnLimitExceeded
             | ErrorCode::CanisterWasmMemoryLimitExceeded
             | ErrorCode::DeadlineExpired
             | ErrorCode::ResponseDropped => false,
@@ -389,7 +392,7 @@ mod tests {
             ErrorCode::iter().map(|x| x as i32).collect::<Vec<i32>>(),
             [
                 101, 102,
-                201, 202, 203, 204, 205, 206, 207, 208, 209, 210,
+                201, 202, 203, 204, 205, 206, 207, 208, 209, 210, 211,
                 301, 305,
                 402, 403, 404, 405, 406, 407, 408, 409,
                 502, 503, 504, 505, 506, 507, 508, 509, 510, 511, 512, 513, 514,
@@ -886,6 +886,92 @@ impl From<CanisterHttpRequest> for RawCanisterHttpRequest {
     }
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct RawCallGraphRecording {
+    pub enabled: bool,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Eq, PartialEq, JsonSchema)]
+pub enum CallGraphMessageKind {
+    Request,
+    Response,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct RawCallGraphMessage {
+    pub subnet_id: RawSubnetId,
+    pub height: u64,
+    pub kind: CallGraphMessageKind,
+    pub caller: RawCanisterId,
+    pub callee: RawCanisterId,
+    pub method_name: Option<String>,
+    pub callback_id: u64,
+    pub payload_size_bytes: u64,
+    pub cycles: u128,
+    pub reject_code: Option<u64>,
+    pub deadline: Option<u32>,
+}
+
+/// An inter-canister message recorded by the call-graph recorder.
+#[derive(Clone, Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct CallGraphMessage {
+    /// The subnet whose canister sent the message.
+    pub subnet_id: Principal,
+    /// Height of the block on that subnet in which the message was routed.
+    pub height: u64,
+    pub kind: CallGraphMessageKind,
+    /// The canister that made the call.
+    pub caller: Principal,
+    /// The canister that was called.
+    pub callee: Principal,
+    /// The called method. Only known for responses whose request was recorded.
+    pub method_name: Option<String>,
+    pub callback_id: u64,
+    pub payload_size_bytes: u64,
+    /// Cycles attached to a request or refunded with a response.
+    pub cycles: u128,
+    /// The reject code of a reject response.
+    pub reject_code: Option<u64>,
+    /// The deadline of a best-effort message, in seconds since the Unix epoch.
+    pub deadline: Option<u32>,
+}
+
+impl From<RawCallGraphMessage> for CallGraphMessage {
+    fn from(raw_call_graph_message: RawCallGraphMessage) -> Self {
+        Self {
+            subnet_id: candid::Principal::from_slice(&raw_call_graph_message.subnet_id.subnet_id),
+            height: raw_call_graph_message.height,
+            kind: raw_call_graph_message.kind,
+            caller: raw_call_graph_message.caller.into(),
+            callee: raw_call_graph_message.callee.into(),
+            method_name: raw_call_graph_message.method_name,
+            callback_id: raw_call_graph_message.callback_id,
+            payload_size_bytes: raw_call_graph_message.payload_size_bytes,
+            cycles: raw_call_graph_message.cycles,
+            reject_code: raw_call_graph_message.reject_code,
+            deadline: raw_call_graph_message.deadline,
+        }
+    }
+}
+
+impl From<CallGraphMessage> for RawCallGraphMessage {
+    fn from(call_graph_message: CallGraphMessage) -> Self {
+        Self {
+            subnet_id: call_graph_message.subnet_id.into(),
+            height: call_graph_message.height,
+            kind: call_graph_message.kind,
+            caller: call_graph_message.caller.into(),
+            callee: call_graph_message.callee.into(),
+            method_name: call_graph_message.method_name,
+            callback_id: call_graph_message.callback_id,
+            payload_size_bytes: call_graph_message.payload_size_bytes,
+            cycles: call_graph_message.cycles,
+            reject_code: call_graph_message.reject_code,
+            deadline: call_graph_message.deadline,
+        }
+    }
+}
+
 #[derive(
     Clone, Serialize, Deserialize, Debug, Hash, Eq, PartialEq, Ord, PartialOrd, JsonSchema,
 )]
@@ -54,9 +54,9 @@
 ///
 use crate::{
     common::rest::{
-        BlobCompression, BlobId, CanisterHttpRequest, ExtendedSubnetConfigSet, HttpsConfig,
-        IcpFeatures, InstanceId, MockCanisterHttpResponse, RawEffectivePrincipal, RawMessageId,
-        SubnetId, SubnetKind, SubnetSpec, Topology,
+        BlobCompression, BlobId, CallGraphMessage, CanisterHttpRequest, ExtendedSubnetConfigSet,
+        HttpsConfig, IcpFeatures, InstanceId, MockCanisterHttpResponse, RawEffectivePrincipal,
+        RawMessageId, SubnetId, SubnetKind, SubnetSpec, Topology,
     },
     nonblocking::PocketIc as PocketIcAsync,
 };
@@ -1344,6 +1344,26 @@ impl PocketIc {
         runtime.block_on(async { self.pocket_ic.get_canister_http().await })
     }
 
+    /// Enables or disables recording of inter-canister messages on all subnets.
+    /// Enabling the recording discards any previously recorded messages.
+    /// Messages are recorded between rounds, so messages that are both sent and
+    /// consumed within a single round, e.g., calls between canisters on the same
+    /// subnet, may be missing.
+    #[instrument(skip(self), fields(instance_id=self.pocket_ic.instance_id))]
+    pub fn set_call_graph_recording(&self, enabled: bool) {
+        let runtime = self.runtime.clone();
+        runtime.block_on(async { self.pocket_ic.set_call_graph_recording(enabled).await })
+    }
+
+    /// Returns the inter-canister messages recorded since the recording was enabled
+    /// by `PocketIc::set_call_graph_recording`, grouped by the subnet of the sending
+    /// canister and ordered by the time they were recorded.
+    #[instrument(ret, skip(self), fields(instance_id=self.pocket_ic.instance_id))]
+    pub fn get_call_graph(&self) -> Vec<CallGraphMessage> {
+        let runtime = self.runtime.clone();
+        runtime.block_on(async { self.pocket_ic.get_call_graph().await })
+    }
+
     /// Mock a response to a pending canister HTTP outcall.
     #[instrument(ret, skip(self), fields(instance_id=self.pocket_ic.instance_id))]
     pub fn mock_canister_http_response(
@@ -1554,6 +1574,7 @@ pub enum ErrorCode {
     CertifiedStateUnavailable = 208,
     CanisterInstallCodeRateLimited = 209,
     CanisterHeapDeltaRateLimited = 210,
+    CanisterCyclesBurnLimitExceeded = 211,
     // 3xx -- `RejectCode::DestinationInvalid`
     CanisterNotFound = 301,
     CanisterSnapshotNotFound = 305,
@@ -1624,6 +1645,7 @@ impl TryFrom<u64> for ErrorCode {
             208 => Ok(ErrorCode::CertifiedStateUnavailable),
             209 => Ok(ErrorCode::CanisterInstallCodeRateLimited),
             210 => Ok(ErrorCode::CanisterHeapDeltaRateLimited),
+            211 => Ok(ErrorCode::CanisterCyclesBurnLimitExceeded),
             // 3xx -- `RejectCode::DestinationInvalid`
             301 => Ok(ErrorCode::CanisterNotFound),
             305 => Ok(ErrorCode::CanisterSnapshotNotFound),
@@ -1,11 +1,12 @@
 use crate::common::rest::{
-    ApiResponse, AutoProgressConfig, BlobCompression, BlobId, CanisterHttpRequest,
-    CreateHttpGatewayResponse, CreateInstanceResponse, ExtendedSubnetConfigSet, HttpGatewayBackend,
-    HttpGatewayConfig, HttpGatewayInfo, HttpsConfig, IcpFeatures, InstanceConfig, InstanceId,
-    MockCanisterHttpResponse, RawAddCycles, RawCanisterCall, RawCanisterHttpRequest, RawCanisterId,
-    RawCanisterResult, RawCycles, RawEffectivePrincipal, RawIngressStatusArgs, RawMessageId,
-    RawMockCanisterHttpResponse, RawPrincipalId, RawSetStableMemory, RawStableMemory, RawSubnetId,
-    RawTime, RawVerifyCanisterSigArg, SubnetId, TickConfigs, Topology,
+    ApiResponse, AutoProgressConfig, BlobCompression, BlobId, CallGraphMessage,
+    CanisterHttpRequest, CreateHttpGatewayResponse, CreateInstanceResponse,
+    ExtendedSubnetConfigSet, HttpGatewayBackend, HttpGatewayConfig, HttpGatewayInfo, HttpsConfig,
+    IcpFeatures, InstanceConfig, InstanceId, MockCanisterHttpResponse, RawAddCycles,
+    RawCallGraphMessage, RawCallGraphRecording, RawCanisterCall, RawCanisterHttpRequest,
+    RawCanisterId, RawCanisterResult, RawCycles, RawEffectivePrincipal, RawIngressStatusArgs,
+    RawMessageId, RawMockCanisterHttpResponse, RawPrincipalId, RawSetStableMemory, RawStableMemory,
+    RawSubnetId, RawTime, RawVerifyCanisterSigArg, SubnetId, TickConfigs, Topology,
 };
 #[cfg(windows)]
 use crate::wsl_path;
@@ -1648,6 +1649,28 @@ impl PocketIc {
         res.into_iter().map(|r| r.into()).collect()
     }
 
+    /// Enables or disables recording of inter-canister messages on all subnets.
+    /// Enabling the recording discards any previously recorded messages.
+    /// Messages are recorded between rounds, so messages that are both sent and
+    /// consumed within a single round, e.g., calls between canisters on the same
+    /// subnet, may be missing.
+    #[instrument(skip(self), fields(instance_id=self.instance_id))]
+    pub async fn set_call_graph_recording(&self, enabled: bool) {
+        let endpoint = "update/set_call_graph_recording";
+        self.post::<(), _>(endpoint, RawCallGraphRecording { enabled })
+            .await;
+    }
+
+    /// Returns the inter-canister messages recorded since the recording was enabled
+    /// by `PocketIc::set_call_graph_recording`, grouped by the subnet of the sending
+    /// canister and ordered by the time they were recorded.
+    #[instrument(ret, skip(self), fields(instance_id=self.instance_id))]
+    pub async fn get_call_graph(&self) -> Vec<CallGraphMessage> {
+        let endpoint = "read/get_call_graph";
+        let res: Vec<RawCallGraphMessage> = self.get(endpoint).await;
+        res.into_iter().map(|m| m.into()).collect()
+    }
+
     /// Mock a response to a pending canister HTTP outcall.
     #[instrument(ret, skip(self), fields(instance_id=self.instance_id))]
     pub async fn mock_canister_http_response(
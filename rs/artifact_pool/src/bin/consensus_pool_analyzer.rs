@@ -0,0 +1,68 @@
+use clap::{Arg, ArgAction, Command};
+use ic_artifact_pool::pool_analyzer::{analyze, PoolSnapshot};
+use ic_config::artifact_pool::ArtifactPoolConfig;
+use ic_logger::{LoggerImpl, ReplicaLogger};
+use std::path::{Path, PathBuf};
+
+fn main() {
+    let matches = Command::new("ic-consensus-pool-analyzer")
+        .version("0.1")
+        .about(
+            "Merges the consensus pools or pool backups of the nodes of a subnet and reports why it \
+            stalled",
+        )
+        .arg(
+            Arg::new("backup")
+                .long("backup")
+                .help(
+                    "Read consensus pool backups, i.e. <backup_dir>/<subnet_id>/<replica_version> \
+                    directories, instead of consensus pool directories",
+                )
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .help("Print the report as JSON")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("POOL")
+                .help(
+                    "Consensus pool (or backup) directory of a node, optionally labeled as \
+                    NAME=PATH",
+                )
+                .required(true)
+                .num_args(1..),
+        )
+        .get_matches();
+
+    let logger = LoggerImpl::new(&Default::default(), "consensus_pool_analyzer".to_string());
+    let log = ReplicaLogger::new(logger.root.clone().into());
+
+    let backup = matches.get_flag("backup");
+    let snapshots: Vec<_> = matches
+        .get_many::<String>("POOL")
+        .expect("Missing consensus pool directories")
+        .map(|arg| {
+            let (name, path) = arg.split_once('=').unwrap_or((arg, arg));
+            if backup {
+                PoolSnapshot::load_backup(name.to_string(), Path::new(path))
+                    .unwrap_or_else(|err| panic!("Failed to load backup {}: {}", path, err))
+            } else {
+                let config = ArtifactPoolConfig::new(PathBuf::from(path));
+                PoolSnapshot::load_pool(name.to_string(), config, log.clone())
+            }
+        })
+        .collect();
+
+    let report = analyze(&snapshots);
+    if matches.get_flag("json") {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("Failed to serialize the report")
+        );
+    } else {
+        print!("{}", report);
+    }
+}
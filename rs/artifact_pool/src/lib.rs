@@ -8,6 +8,7 @@ pub mod idkg_pool;
 pub mod ingress_pool;
 mod inmemory_pool;
 mod metrics;
+pub mod pool_analyzer;
 mod pool_common;
 #[cfg(test)]
 mod test_utils;
@@ -0,0 +1,989 @@
+//! Offline analysis of the consensus and certification pools of a subnet.
+//!
+//! When a subnet stalls, the persistent pools or the pool backups of its nodes
+//! are copied and loaded into [`PoolSnapshot`]s. [`analyze`] merges the snapshots into a single
+//! view of the subnet and reports where and why progress stopped: which
+//! heights each node reached, which nodes did not contribute shares, which
+//! notarization shares are missing for each block proposal, gaps in the random
+//! beacon chain, and problems with the DKG and IDKG payloads.
+use crate::{
+    certification_pool::CertificationPoolImpl,
+    consensus_pool::{MutablePoolSection, PoolSectionOps, UncachedConsensusPoolImpl},
+    inmemory_pool::InMemoryPoolSection,
+};
+use ic_config::artifact_pool::ArtifactPoolConfig;
+use ic_interfaces::consensus_pool::{
+    ConsensusPool, HeightIndexedPool, HeightRange, PoolSection, ValidatedConsensusArtifact,
+};
+use ic_logger::ReplicaLogger;
+use ic_metrics::MetricsRegistry;
+use ic_protobuf::types::v1 as pb;
+use ic_types::{
+    consensus::{
+        certification::{Certification, CertificationShare},
+        get_faults_tolerated,
+        idkg::KeyTranscriptCreation,
+        Block, BlockPayload, BlockProposal, CatchUpPackage, ConsensusMessage,
+        ConsensusMessageHashable, Finalization, HasHeight, Notarization, RandomBeacon,
+    },
+    crypto::{threshold_sig::ni_dkg::NiDkgTag, CryptoHashOf},
+    time::UNIX_EPOCH,
+    Height, NodeId, PrincipalId,
+};
+use prost::Message;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The maximum number of rounds above the finalized height that are analyzed.
+const MAX_ROUNDS: u64 = 10;
+
+/// The validated artifacts of the pools of a single node.
+pub struct PoolSnapshot {
+    /// Label of the pool in the report, e.g. the node id or the backup path.
+    pub name: String,
+    pub consensus: Vec<ConsensusMessage>,
+    pub certifications: Vec<Certification>,
+    pub certification_shares: Vec<CertificationShare>,
+}
+
+impl PoolSnapshot {
+    /// Load the validated section of the persistent consensus and
+    /// certification pools under the given config, in read-only mode.
+    pub fn load_pool(name: String, mut config: ArtifactPoolConfig, log: ReplicaLogger) -> Self {
+        config.persistent_pool_read_only = true;
+        let consensus_pool = UncachedConsensusPoolImpl::new(config.clone(), log.clone());
+        // The node id is only used for the unvalidated section, which is not read.
+        let node_id = NodeId::from(PrincipalId::new_node_test_id(0));
+        let certification_pool =
+            CertificationPoolImpl::new(node_id, config, log, MetricsRegistry::new());
+
+        let mut snapshot = Self::from_pool_section(name, consensus_pool.validated());
+        snapshot.certifications = certification_pool
+            .validated
+            .certifications()
+            .get_all()
+            .collect();
+        snapshot.certification_shares = certification_pool
+            .validated
+            .certification_shares()
+            .get_all()
+            .collect();
+        snapshot
+    }
+
+    /// Load the artifacts of a consensus pool backup, i.e. of the directory
+    /// `<backup_dir>/<subnet_id>/<replica_version>` written by [`crate::backup`],
+    /// or of one of its ancestors to merge the backups of several versions.
+    ///
+    /// Backups only contain the artifacts needed to recompute the state: catch-up
+    /// packages, random beacons, block proposals, notarizations and finalizations.
+    /// Unlike for a pool loaded with [`PoolSnapshot::load_pool`], the report thus
+    /// lacks everything derived from shares and certifications, e.g. the missing
+    /// notarization shares and the participation of the nodes.
+    pub fn load_backup(name: String, path: &Path) -> io::Result<Self> {
+        let mut files = Vec::new();
+        collect_files(path, &mut files)?;
+
+        let mut consensus = Vec::new();
+        for file in files {
+            let Some(file_name) = file.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            // Files are named after the artifact type. Random tapes are not needed
+            // for the analysis, and files renamed to `invalid_*` by the replay tool
+            // are skipped.
+            let message = if file_name.starts_with("catch_up_package") {
+                CatchUpPackage::try_from(&decode_file::<pb::CatchUpPackage>(&file)?)
+                    .map(ConsensusMessage::CatchUpPackage)
+            } else if file_name.starts_with("block_proposal") {
+                BlockProposal::try_from(decode_file::<pb::BlockProposal>(&file)?)
+                    .map(ConsensusMessage::BlockProposal)
+            } else if file_name.starts_with("notarization") {
+                Notarization::try_from(decode_file::<pb::Notarization>(&file)?)
+                    .map(ConsensusMessage::Notarization)
+            } else if file_name.starts_with("finalization") {
+                Finalization::try_from(decode_file::<pb::Finalization>(&file)?)
+                    .map(ConsensusMessage::Finalization)
+            } else if file_name.starts_with("random_beacon") {
+                RandomBeacon::try_from(decode_file::<pb::RandomBeacon>(&file)?)
+                    .map(ConsensusMessage::RandomBeacon)
+            } else {
+                continue;
+            };
+            consensus.push(message.map_err(|err| invalid_data(&file, err))?);
+        }
+
+        Ok(Self {
+            name,
+            consensus,
+            certifications: Vec::new(),
+            certification_shares: Vec::new(),
+        })
+    }
+
+    /// Collect the consensus artifacts relevant for the analysis from the
+    /// given pool section.
+    pub fn from_pool_section(
+        name: String,
+        section: &dyn PoolSection<ValidatedConsensusArtifact>,
+    ) -> Self {
+        fn collect<T: ConsensusMessageHashable>(
+            pool: &dyn HeightIndexedPool<T>,
+            messages: &mut Vec<ConsensusMessage>,
+        ) {
+            messages.extend(pool.get_all().map(|x| x.into_message()));
+        }
+
+        let mut consensus = Vec::new();
+        collect(section.random_beacon(), &mut consensus);
+        collect(section.block_proposal(), &mut consensus);
+        collect(section.notarization(), &mut consensus);
+        collect(section.finalization(), &mut consensus);
+        collect(section.random_beacon_share(), &mut consensus);
+        collect(section.notarization_share(), &mut consensus);
+        collect(section.finalization_share(), &mut consensus);
+        collect(section.catch_up_package(), &mut consensus);
+        Self {
+            name,
+            consensus,
+            certifications: Vec::new(),
+            certification_shares: Vec::new(),
+        }
+    }
+}
+
+/// Appends the paths of all files below the given directory to `files`.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn decode_file<T: Message + Default>(path: &Path) -> io::Result<T> {
+    let bytes = fs::read(path)?;
+    T::decode(bytes.as_slice()).map_err(|err| invalid_data(path, err))
+}
+
+fn invalid_data(path: &Path, err: impl fmt::Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Failed to decode {}: {}", path.display(), err),
+    )
+}
+
+/// The heights reached according to a (possibly merged) pool.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PoolHeights {
+    pub name: String,
+    pub catch_up_height: Option<Height>,
+    pub finalized_height: Option<Height>,
+    pub notarized_height: Option<Height>,
+    pub random_beacon_height: Option<Height>,
+    pub certified_height: Option<Height>,
+}
+
+impl PoolHeights {
+    fn new(
+        name: String,
+        section: &dyn PoolSection<ValidatedConsensusArtifact>,
+        certifications: &[Certification],
+    ) -> Self {
+        Self {
+            name,
+            catch_up_height: section.catch_up_package().max_height(),
+            finalized_height: section.finalization().max_height(),
+            notarized_height: section.notarization().max_height(),
+            random_beacon_height: section.random_beacon().max_height(),
+            certified_height: certifications.iter().map(|c| c.height).max(),
+        }
+    }
+}
+
+/// The highest heights at which a node signed each kind of artifact.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct NodeParticipation {
+    pub node_id: String,
+    pub last_block_proposal: Option<Height>,
+    pub last_random_beacon_share: Option<Height>,
+    pub last_notarization_share: Option<Height>,
+    pub last_finalization_share: Option<Height>,
+    pub last_certification_share: Option<Height>,
+}
+
+/// The notarization status of a block proposal, or of a block that received
+/// notarization shares but is itself missing from all pools.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ProposalAnalysis {
+    pub rank: Option<u64>,
+    pub block_hash: String,
+    pub proposer: Option<String>,
+    pub notarization_shares: usize,
+    pub missing_notarization_shares: Vec<String>,
+}
+
+/// The state of a single round above the finalized height.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct RoundAnalysis {
+    pub height: Height,
+    pub random_beacon: bool,
+    pub random_beacon_shares: usize,
+    pub notarized: bool,
+    pub finalization_shares: usize,
+    pub proposals: Vec<ProposalAnalysis>,
+}
+
+/// The result of analyzing the merged pools of a subnet.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PoolAnalysisReport {
+    /// The heights reached by every individual pool.
+    pub pools: Vec<PoolHeights>,
+    /// The heights reached by the merged pool.
+    pub merged: PoolHeights,
+    /// The subnet members according to the latest catch-up package, or all
+    /// signers found in the pools if there is none.
+    pub subnet_members: Vec<String>,
+    /// The number of shares required to notarize or finalize a block.
+    pub threshold: usize,
+    pub participation: Vec<NodeParticipation>,
+    /// Members that did not sign any random beacon, notarization or
+    /// finalization share above the merged finalized height.
+    pub non_participating_nodes: Vec<String>,
+    pub rounds: Vec<RoundAnalysis>,
+    /// Heights without a random beacon between the lowest and highest beacon.
+    pub random_beacon_gaps: Vec<Height>,
+    pub dkg_issues: Vec<String>,
+    pub idkg_issues: Vec<String>,
+}
+
+/// Merge the given snapshots and analyze why the subnet stopped making progress.
+pub fn analyze(snapshots: &[PoolSnapshot]) -> PoolAnalysisReport {
+    let pools = snapshots
+        .iter()
+        .map(|snapshot| {
+            let section = to_pool_section(snapshot.consensus.iter().cloned());
+            PoolHeights::new(snapshot.name.clone(), &section, &snapshot.certifications)
+        })
+        .collect();
+
+    let merged = to_pool_section(
+        snapshots
+            .iter()
+            .flat_map(|snapshot| snapshot.consensus.iter().cloned()),
+    );
+    let certifications: Vec<_> = snapshots
+        .iter()
+        .flat_map(|snapshot| snapshot.certifications.iter().cloned())
+        .collect();
+    let certification_shares: Vec<_> = snapshots
+        .iter()
+        .flat_map(|snapshot| snapshot.certification_shares.iter().cloned())
+        .collect();
+    let merged_heights = PoolHeights::new(String::from("merged"), &merged, &certifications);
+
+    let participation = participation(&merged, &certification_shares);
+    let subnet_members = subnet_members(&merged)
+        .unwrap_or_else(|| participation.keys().cloned().collect::<BTreeSet<_>>());
+    let threshold = subnet_members.len() - get_faults_tolerated(subnet_members.len());
+
+    let finalized_height = merged_heights
+        .finalized_height
+        .or(merged_heights.catch_up_height)
+        .unwrap_or_default();
+    let non_participating_nodes = subnet_members
+        .iter()
+        .filter(|node| {
+            participation.get(node).is_none_or(|p| {
+                [
+                    p.last_random_beacon_share,
+                    p.last_notarization_share,
+                    p.last_finalization_share,
+                ]
+                .iter()
+                .all(|height| height.is_none_or(|h| h <= finalized_height))
+            })
+        })
+        .map(|node| node.to_string())
+        .collect();
+
+    PoolAnalysisReport {
+        pools,
+        subnet_members: subnet_members.iter().map(|n| n.to_string()).collect(),
+        threshold,
+        participation: participation.into_values().collect(),
+        non_participating_nodes,
+        rounds: rounds(&merged, finalized_height, &subnet_members),
+        random_beacon_gaps: random_beacon_gaps(&merged),
+        dkg_issues: dkg_issues(&merged, finalized_height),
+        idkg_issues: idkg_issues(&merged, finalized_height),
+        merged: merged_heights,
+    }
+}
+
+fn to_pool_section(
+    messages: impl Iterator<Item = ConsensusMessage>,
+) -> InMemoryPoolSection<ValidatedConsensusArtifact> {
+    let mut ops = PoolSectionOps::new();
+    for msg in messages {
+        ops.insert(ValidatedConsensusArtifact {
+            msg,
+            timestamp: UNIX_EPOCH,
+        });
+    }
+    let mut section = InMemoryPoolSection::new();
+    section.mutate(ops);
+    section
+}
+
+fn update_max(slot: &mut Option<Height>, height: Height) {
+    *slot = Some(slot.map_or(height, |h| h.max(height)));
+}
+
+fn entry(
+    participation: &mut BTreeMap<NodeId, NodeParticipation>,
+    node_id: NodeId,
+) -> &mut NodeParticipation {
+    participation
+        .entry(node_id)
+        .or_insert_with(|| NodeParticipation {
+            node_id: node_id.to_string(),
+            ..Default::default()
+        })
+}
+
+fn participation(
+    section: &dyn PoolSection<ValidatedConsensusArtifact>,
+    certification_shares: &[CertificationShare],
+) -> BTreeMap<NodeId, NodeParticipation> {
+    let mut participation = BTreeMap::<NodeId, NodeParticipation>::new();
+    for proposal in section.block_proposal().get_all() {
+        update_max(
+            &mut entry(&mut participation, proposal.signature.signer).last_block_proposal,
+            proposal.height(),
+        );
+    }
+    for share in section.random_beacon_share().get_all() {
+        update_max(
+            &mut entry(&mut participation, share.signature.signer).last_random_beacon_share,
+            share.height(),
+        );
+    }
+    for share in section.notarization_share().get_all() {
+        update_max(
+            &mut entry(&mut participation, share.signature.signer).last_notarization_share,
+            share.height(),
+        );
+    }
+    for share in section.finalization_share().get_all() {
+        update_max(
+            &mut entry(&mut participation, share.signature.signer).last_finalization_share,
+            share.height(),
+        );
+    }
+    for share in certification_shares {
+        update_max(
+            &mut entry(&mut participation, share.signed.signature.signer).last_certification_share,
+            share.height,
+        );
+    }
+    participation
+}
+
+/// Returns the receivers of the current low threshold transcript of the
+/// highest catch-up package.
+fn subnet_members(
+    section: &dyn PoolSection<ValidatedConsensusArtifact>,
+) -> Option<BTreeSet<NodeId>> {
+    let cup = section.catch_up_package().get_highest().ok()?;
+    let block: &Block = cup.content.block.as_ref();
+    match block.payload.as_ref() {
+        BlockPayload::Summary(summary) => summary
+            .dkg
+            .current_transcript(&NiDkgTag::LowThreshold)
+            .map(|transcript| transcript.committee.get().clone()),
+        BlockPayload::Data(_) => None,
+    }
+}
+
+fn hash_to_string(hash: &CryptoHashOf<Block>) -> String {
+    hash.get_ref()
+        .0
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Returns the lowest height above `height` with an artifact in the given pool.
+fn lowest_height_above<T: HasHeight>(
+    pool: &dyn HeightIndexedPool<T>,
+    height: Height,
+) -> Option<Height> {
+    let max_height = pool.max_height()?;
+    pool.get_by_height_range(HeightRange::new(height.increment(), max_height))
+        .next()
+        .map(|artifact| artifact.height())
+}
+
+/// Returns the analysis of the rounds above the finalized height, starting
+/// at the first height with any artifacts of a round.
+fn rounds(
+    section: &dyn PoolSection<ValidatedConsensusArtifact>,
+    finalized_height: Height,
+    subnet_members: &BTreeSet<NodeId>,
+) -> Vec<RoundAnalysis> {
+    let Some(start) = [
+        lowest_height_above(section.random_beacon(), finalized_height),
+        lowest_height_above(section.random_beacon_share(), finalized_height),
+        lowest_height_above(section.block_proposal(), finalized_height),
+        lowest_height_above(section.notarization_share(), finalized_height),
+        lowest_height_above(section.notarization(), finalized_height),
+        lowest_height_above(section.finalization_share(), finalized_height),
+    ]
+    .into_iter()
+    .flatten()
+    .min() else {
+        return Vec::new();
+    };
+    let highest = [
+        section.random_beacon().max_height(),
+        section.random_beacon_share().max_height(),
+        section.block_proposal().max_height(),
+        section.notarization_share().max_height(),
+        section.notarization().max_height(),
+        section.finalization_share().max_height(),
+    ]
+    .into_iter()
+    .flatten()
+    .max()
+    .unwrap_or(start)
+    .min(start + Height::from(MAX_ROUNDS - 1));
+
+    (start.get()..=highest.get())
+        .map(Height::from)
+        .map(|height| {
+            let mut proposals = BTreeMap::new();
+            for proposal in section.block_proposal().get_by_height(height) {
+                let block: &Block = proposal.as_ref();
+                proposals.insert(
+                    proposal.content.get_hash().clone(),
+                    (Some(block.rank.0), Some(proposal.signature.signer)),
+                );
+            }
+            let mut signers = BTreeMap::<_, BTreeSet<NodeId>>::new();
+            for share in section.notarization_share().get_by_height(height) {
+                proposals
+                    .entry(share.content.block.clone())
+                    .or_insert((None, None));
+                signers
+                    .entry(share.content.block.clone())
+                    .or_default()
+                    .insert(share.signature.signer);
+            }
+
+            let mut proposals: Vec<_> = proposals
+                .into_iter()
+                .map(|(hash, (rank, proposer))| {
+                    let signers = signers.remove(&hash).unwrap_or_default();
+                    ProposalAnalysis {
+                        rank,
+                        block_hash: hash_to_string(&hash),
+                        proposer: proposer.map(|p| p.to_string()),
+                        notarization_shares: signers.len(),
+                        missing_notarization_shares: subnet_members
+                            .difference(&signers)
+                            .map(|node| node.to_string())
+                            .collect(),
+                    }
+                })
+                .collect();
+            proposals.sort_by_key(|p| p.rank.unwrap_or(u64::MAX));
+
+            RoundAnalysis {
+                height,
+                random_beacon: section
+                    .random_beacon()
+                    .get_by_height(height)
+                    .next()
+                    .is_some(),
+                random_beacon_shares: section.random_beacon_share().get_by_height(height).count(),
+                notarized: section
+                    .notarization()
+                    .get_by_height(height)
+                    .next()
+                    .is_some(),
+                finalization_shares: section.finalization_share().get_by_height(height).count(),
+                proposals,
+            }
+        })
+        .collect()
+}
+
+fn random_beacon_gaps(section: &dyn PoolSection<ValidatedConsensusArtifact>) -> Vec<Height> {
+    let Some(range) = section.random_beacon().height_range() else {
+        return Vec::new();
+    };
+    (range.min.get()..=range.max.get())
+        .map(Height::from)
+        .filter(|h| section.random_beacon().get_by_height(*h).next().is_none())
+        .collect()
+}
+
+/// Returns the finalized block at the given height, if both the finalization
+/// and the block proposal are in the pool.
+fn finalized_block(
+    section: &dyn PoolSection<ValidatedConsensusArtifact>,
+    height: Height,
+) -> Option<Block> {
+    let finalization = section.finalization().get_by_height(height).next()?;
+    section
+        .block_proposal()
+        .get_by_height(height)
+        .find(|proposal| proposal.content.get_hash() == &finalization.content.block)
+        .map(Block::from)
+}
+
+/// Returns the latest block with a summary payload, searching the finalized
+/// chain down to the catch-up package.
+fn latest_summary_block(
+    section: &dyn PoolSection<ValidatedConsensusArtifact>,
+    finalized_height: Height,
+) -> Option<Block> {
+    let cup = section.catch_up_package().get_highest().ok();
+    let cup_height = cup.as_ref().map(|cup| cup.height()).unwrap_or_default();
+    (cup_height.get() + 1..=finalized_height.get())
+        .rev()
+        .filter_map(|h| finalized_block(section, Height::from(h)))
+        .find(|block| block.payload.as_ref().is_summary())
+        .or_else(|| cup.map(|cup| cup.content.block.as_ref().clone()))
+}
+
+fn dkg_issues(
+    section: &dyn PoolSection<ValidatedConsensusArtifact>,
+    finalized_height: Height,
+) -> Vec<String> {
+    let Some(summary_block) = latest_summary_block(section, finalized_height) else {
+        return vec![String::from("No catch-up package or summary block found")];
+    };
+    let dkg = &summary_block.payload.as_ref().as_summary().dkg;
+    let mut issues = Vec::new();
+
+    for tag in dkg.current_transcripts().keys() {
+        if dkg.next_transcript(tag).is_none() {
+            issues.push(format!(
+                "Summary block at height {} has no next transcript for {:?}, \
+                the DKG of the previous interval did not complete",
+                dkg.height, tag
+            ));
+        }
+    }
+    for (dkg_id, _, result) in &dkg.transcripts_for_remote_subnets {
+        if let Err(err) = result {
+            issues.push(format!(
+                "Remote DKG {:?} for {:?} failed: {}",
+                dkg_id.dkg_tag, dkg_id.target_subnet, err
+            ));
+        }
+    }
+
+    // Count the dealers whose dealings were included in the finalized blocks
+    // of the current interval.
+    let last_height = finalized_height.min(dkg.get_next_start_height().decrement());
+    let mut dealers = BTreeMap::<_, BTreeSet<NodeId>>::new();
+    for h in dkg.height.get() + 1..=last_height.get() {
+        let Some(block) = finalized_block(section, Height::from(h)) else {
+            continue;
+        };
+        if let BlockPayload::Data(data) = block.payload.as_ref() {
+            for message in &data.dkg.messages {
+                dealers
+                    .entry(message.content.dkg_id.clone())
+                    .or_default()
+                    .insert(message.signature.signer);
+            }
+        }
+    }
+    for (dkg_id, config) in &dkg.configs {
+        let included = dealers.get(dkg_id).map_or(0, |dealers| dealers.len());
+        let required = config.collection_threshold().get() as usize;
+        if included < required {
+            issues.push(format!(
+                "DKG {:?} for {:?}: {} of {} required dealings included up to height {}",
+                dkg_id.dkg_tag, dkg_id.target_subnet, included, required, last_height
+            ));
+        }
+    }
+    issues
+}
+
+fn key_transcript_creation_state(state: &KeyTranscriptCreation) -> &'static str {
+    match state {
+        KeyTranscriptCreation::Begin => "Begin",
+        KeyTranscriptCreation::RandomTranscriptParams(_) => "RandomTranscriptParams",
+        KeyTranscriptCreation::ReshareOfMaskedParams(_) => "ReshareOfMaskedParams",
+        KeyTranscriptCreation::ReshareOfUnmaskedParams(_) => "ReshareOfUnmaskedParams",
+        KeyTranscriptCreation::XnetReshareOfUnmaskedParams(_) => "XnetReshareOfUnmaskedParams",
+        KeyTranscriptCreation::Created(_) => "Created",
+    }
+}
+
+fn idkg_issues(
+    section: &dyn PoolSection<ValidatedConsensusArtifact>,
+    finalized_height: Height,
+) -> Vec<String> {
+    let block = finalized_block(section, finalized_height).or_else(|| {
+        section
+            .catch_up_package()
+            .get_highest()
+            .ok()
+            .map(|cup| cup.content.block.as_ref().clone())
+    });
+    let Some(block) = block else {
+        return Vec::new();
+    };
+    let Some(idkg) = block.payload.as_ref().as_idkg() else {
+        return Vec::new();
+    };
+
+    let mut issues = Vec::new();
+    for (key_id, key_transcript) in &idkg.key_transcripts {
+        if key_transcript.current.is_none() {
+            issues.push(format!(
+                "Block at height {}: no current key transcript for {}, next transcript is in state {}",
+                block.height,
+                key_id,
+                key_transcript_creation_state(&key_transcript.next_in_creation)
+            ));
+        }
+    }
+    if !idkg.key_transcripts.is_empty() && idkg.available_pre_signatures.is_empty() {
+        issues.push(format!(
+            "Block at height {}: no available pre-signatures, {} in creation",
+            block.height,
+            idkg.pre_signatures_in_creation.len()
+        ));
+    }
+    issues
+}
+
+fn fmt_height(height: &Option<Height>) -> String {
+    height.map_or_else(|| String::from("-"), |h| h.to_string())
+}
+
+impl fmt::Display for PoolHeights {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: cup {}, finalized {}, notarized {}, random beacon {}, certified {}",
+            self.name,
+            fmt_height(&self.catch_up_height),
+            fmt_height(&self.finalized_height),
+            fmt_height(&self.notarized_height),
+            fmt_height(&self.random_beacon_height),
+            fmt_height(&self.certified_height),
+        )
+    }
+}
+
+impl fmt::Display for PoolAnalysisReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Heights per pool:")?;
+        for pool in &self.pools {
+            writeln!(f, "  {}", pool)?;
+        }
+        writeln!(f, "  {}", self.merged)?;
+
+        writeln!(
+            f,
+            "Subnet members: {} (threshold {})",
+            self.subnet_members.len(),
+            self.threshold
+        )?;
+        writeln!(f, "Last signed heights per node:")?;
+        for p in &self.participation {
+            writeln!(
+                f,
+                "  {}: proposal {}, beacon share {}, notarization share {}, \
+                finalization share {}, certification share {}",
+                p.node_id,
+                fmt_height(&p.last_block_proposal),
+                fmt_height(&p.last_random_beacon_share),
+                fmt_height(&p.last_notarization_share),
+                fmt_height(&p.last_finalization_share),
+                fmt_height(&p.last_certification_share),
+            )?;
+        }
+        writeln!(f, "Nodes not participating:")?;
+        for node in &self.non_participating_nodes {
+            writeln!(f, "  {}", node)?;
+        }
+
+        writeln!(f, "Rounds above the finalized height:")?;
+        for round in &self.rounds {
+            writeln!(
+                f,
+                "  Height {}: random beacon {}, {} beacon shares, notarized {}, {} finalization shares",
+                round.height,
+                round.random_beacon,
+                round.random_beacon_shares,
+                round.notarized,
+                round.finalization_shares,
+            )?;
+            for proposal in &round.proposals {
+                writeln!(
+                    f,
+                    "    rank {} block {} by {}: {}/{} notarization shares, missing: [{}]",
+                    proposal
+                        .rank
+                        .map_or_else(|| String::from("?"), |r| r.to_string()),
+                    proposal.block_hash,
+                    proposal.proposer.as_deref().unwrap_or("?"),
+                    proposal.notarization_shares,
+                    self.threshold,
+                    proposal.missing_notarization_shares.join(", "),
+                )?;
+            }
+        }
+
+        writeln!(f, "Random beacon gaps: {:?}", self.random_beacon_gaps)?;
+        writeln!(f, "DKG issues:")?;
+        for issue in &self.dkg_issues {
+            writeln!(f, "  {}", issue)?;
+        }
+        writeln!(f, "IDKG issues:")?;
+        for issue in &self.idkg_issues {
+            writeln!(f, "  {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backup::BackupArtifact;
+    use ic_test_artifact_pool::consensus_pool::TestConsensusPool;
+    use ic_test_utilities::{crypto::CryptoReturningOk, state_manager::FakeStateManager};
+    use ic_test_utilities_consensus::fake::*;
+    use ic_test_utilities_consensus::idkg::{
+        empty_idkg_payload, fake_ecdsa_idkg_master_public_key_id,
+    };
+    use ic_test_utilities_registry::{setup_registry, SubnetRecordBuilder};
+    use ic_test_utilities_time::FastForwardTimeSource;
+    use ic_test_utilities_types::ids::{node_test_id, subnet_test_id};
+    use ic_types::{
+        batch::BatchPayload,
+        consensus::{DataPayload, NotarizationShare, Payload},
+        crypto::crypto_hash,
+        SubnetId,
+    };
+    use std::sync::Arc;
+
+    fn test_pool(pool_config: ArtifactPoolConfig) -> (SubnetId, TestConsensusPool) {
+        let subnet_id = subnet_test_id(1);
+        let committee: Vec<_> = (0..4).map(node_test_id).collect();
+        let registry = setup_registry(
+            subnet_id,
+            vec![(
+                1,
+                SubnetRecordBuilder::from(&committee)
+                    .with_dkg_interval_length(19)
+                    .build(),
+            )],
+        );
+        let pool = TestConsensusPool::new(
+            node_test_id(0),
+            subnet_id,
+            pool_config,
+            FastForwardTimeSource::new(),
+            registry,
+            Arc::new(CryptoReturningOk::default()),
+            Arc::new(FakeStateManager::new()),
+            None,
+        );
+        (subnet_id, pool)
+    }
+
+    #[test]
+    fn test_analyze_stalled_round() {
+        ic_test_utilities::artifact_pool_config::with_test_pool_config(|pool_config| {
+            let (_, mut pool) = test_pool(pool_config);
+            assert_eq!(pool.advance_round_normal_operation_n(3), Height::from(3));
+
+            // Only nodes 0 and 1 notarize the block at height 4, and their
+            // shares are found in different pools.
+            pool.insert_validated(pool.make_next_beacon());
+            let block = pool.make_next_block();
+            pool.insert_validated(block.clone());
+            pool.insert_validated(NotarizationShare::fake(block.as_ref(), node_test_id(0)));
+            let local = PoolSnapshot::from_pool_section(String::from("node-0"), pool.validated());
+            let remote = PoolSnapshot {
+                name: String::from("node-1"),
+                consensus: vec![
+                    NotarizationShare::fake(block.as_ref(), node_test_id(1)).into_message()
+                ],
+                certifications: Vec::new(),
+                certification_shares: Vec::new(),
+            };
+
+            let report = analyze(&[local, remote]);
+
+            assert_eq!(report.pools[0].finalized_height, Some(Height::from(3)));
+            assert_eq!(report.pools[1].finalized_height, None);
+            assert_eq!(report.merged.finalized_height, Some(Height::from(3)));
+            assert_eq!(report.merged.random_beacon_height, Some(Height::from(4)));
+            assert_eq!(report.subnet_members.len(), 4);
+            assert_eq!(report.threshold, 3);
+
+            let missing = vec![node_test_id(2).to_string(), node_test_id(3).to_string()];
+            assert_eq!(report.non_participating_nodes, missing);
+
+            assert_eq!(report.rounds.len(), 1);
+            let round = &report.rounds[0];
+            assert_eq!(round.height, Height::from(4));
+            assert!(round.random_beacon);
+            assert!(!round.notarized);
+            assert_eq!(round.proposals.len(), 1);
+            assert_eq!(round.proposals[0].rank, Some(0));
+            assert_eq!(round.proposals[0].notarization_shares, 2);
+            assert_eq!(round.proposals[0].missing_notarization_shares, missing);
+
+            assert!(report.random_beacon_gaps.is_empty());
+            assert!(report.idkg_issues.is_empty());
+        })
+    }
+    #[test]
+    fn test_analyze_missing_dkg_summary() {
+        ic_test_utilities::artifact_pool_config::with_test_pool_config(|pool_config| {
+            let (_, mut pool) = test_pool(pool_config);
+            assert_eq!(pool.advance_round_normal_operation_n(3), Height::from(3));
+
+            // The catch-up package is missing, and there is no summary block in
+            // the finalized chain.
+            let mut local =
+                PoolSnapshot::from_pool_section(String::from("node-0"), pool.validated());
+            local
+                .consensus
+                .retain(|msg| !matches!(msg, ConsensusMessage::CatchUpPackage(_)));
+            // A notarization share two heights above the finalized height.
+            let block = Block::from_parent(pool.make_next_block().as_ref());
+            let remote = PoolSnapshot {
+                name: String::from("node-1"),
+                consensus: vec![NotarizationShare::fake(&block, node_test_id(1)).into_message()],
+                certifications: Vec::new(),
+                certification_shares: Vec::new(),
+            };
+
+            let report = analyze(&[local, remote]);
+
+            assert_eq!(report.merged.catch_up_height, None);
+            assert_eq!(report.merged.finalized_height, Some(Height::from(3)));
+            assert_eq!(
+                report.dkg_issues,
+                vec![String::from("No catch-up package or summary block found")]
+            );
+            assert!(report.idkg_issues.is_empty());
+
+            // The analysis starts at the first height with any artifacts.
+            assert_eq!(report.rounds.len(), 1);
+            assert_eq!(report.rounds[0].height, Height::from(5));
+            assert_eq!(report.rounds[0].proposals.len(), 1);
+            assert_eq!(report.rounds[0].proposals[0].rank, None);
+            assert_eq!(report.rounds[0].proposals[0].notarization_shares, 1);
+        })
+    }
+
+    #[test]
+    fn test_analyze_missing_idkg_key_transcript() {
+        ic_test_utilities::artifact_pool_config::with_test_pool_config(|pool_config| {
+            let (subnet_id, mut pool) = test_pool(pool_config);
+            assert_eq!(pool.advance_round_normal_operation_n(3), Height::from(3));
+
+            // The block at height 4 is finalized, but the key transcript was never
+            // created.
+            let mut block = Block::from(pool.make_next_block());
+            let dkg = block.payload.as_ref().as_data().dkg.clone();
+            block.payload = Payload::new(
+                crypto_hash,
+                BlockPayload::Data(DataPayload {
+                    batch: BatchPayload::default(),
+                    dkg,
+                    idkg: Some(empty_idkg_payload(subnet_id)),
+                }),
+            );
+            let proposal = BlockProposal::fake(block, node_test_id(0));
+            pool.insert_validated(pool.make_next_beacon());
+            pool.insert_validated(proposal.clone());
+            pool.notarize(&proposal);
+            pool.finalize(&proposal);
+
+            let report = analyze(&[PoolSnapshot::from_pool_section(
+                String::from("node-0"),
+                pool.validated(),
+            )]);
+
+            assert_eq!(report.merged.finalized_height, Some(Height::from(4)));
+            assert_eq!(
+                report.idkg_issues,
+                vec![
+                    format!(
+                        "Block at height 4: no current key transcript for {}, \
+                        next transcript is in state Begin",
+                        fake_ecdsa_idkg_master_public_key_id()
+                    ),
+                    String::from("Block at height 4: no available pre-signatures, 0 in creation"),
+                ]
+            );
+            // There are no artifacts above the finalized height.
+            assert!(report.rounds.is_empty());
+        })
+    }
+
+    #[test]
+    fn test_load_backup() {
+        ic_test_utilities::artifact_pool_config::with_test_pool_config(|pool_config| {
+            let (_, mut pool) = test_pool(pool_config);
+            assert_eq!(pool.advance_round_normal_operation_n(3), Height::from(3));
+
+            let snapshot =
+                PoolSnapshot::from_pool_section(String::from("node-0"), pool.validated());
+            let dir = tempfile::tempdir().unwrap();
+            let version_path = dir.path().join("subnet").join("version");
+            let mut backed_up = 0;
+            for msg in snapshot.consensus.iter().cloned() {
+                if let Ok(artifact) = BackupArtifact::try_from(msg) {
+                    artifact.write_to_disk(&version_path).unwrap();
+                    backed_up += 1;
+                }
+            }
+            // Files that were renamed by the replay tool are ignored.
+            std::fs::write(version_path.join("invalid_block_proposal.bin"), b"garbage").unwrap();
+
+            let backup = PoolSnapshot::load_backup(String::from("backup"), dir.path()).unwrap();
+            assert_eq!(backup.consensus.len(), backed_up);
+            assert!(backup
+                .consensus
+                .iter()
+                .all(|msg| snapshot.consensus.contains(msg)));
+
+            let report = analyze(&[backup]);
+            assert_eq!(report.merged.catch_up_height, Some(Height::from(0)));
+            assert_eq!(report.merged.finalized_height, Some(Height::from(3)));
+            assert_eq!(report.merged.notarized_height, Some(Height::from(3)));
+            assert_eq!(report.merged.random_beacon_height, Some(Height::from(3)));
+            // Shares are not backed up.
+            assert!(report
+                .participation
+                .iter()
+                .all(|p| p.last_notarization_share.is_none()));
+        })
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    collections::VecDeque,
     fmt,
     str::FromStr,
     sync::Arc,
@@ -69,11 +70,78 @@ impl fmt::Display for CheckError {
 const WINDOW_SIZE: usize = 10;
 type LatencyMovAvg = SumTreeSMA<f64, f64, WINDOW_SIZE>;
 
+// Certified height that doesn't advance for longer than this starts to lower the node's health score
+const CERT_STALENESS_GRACE: Duration = Duration::from_secs(10);
+// Republish the subnet if some node's health score has changed by more than this value
+const SCORE_CHANGE_THRESHOLD: f64 = 0.05;
+
 #[derive(Copy, Clone, PartialEq, Debug)]
 struct NodeState {
     healthy: bool,
     height: u64,
     avg_latency_secs: f64,
+    // Fraction of failed checks within the window
+    error_rate: f64,
+    latency_p50_secs: f64,
+    latency_p95_secs: f64,
+    // Time since the node's certified height has last advanced
+    cert_staleness_secs: f64,
+}
+
+// Returns the value at the given percentile (0..=1) using the nearest-rank method.
+// Returns f64::MAX if there are no samples.
+fn percentile(samples: &VecDeque<f64>, p: f64) -> f64 {
+    if samples.is_empty() {
+        return f64::MAX;
+    }
+
+    let mut sorted = samples.iter().copied().collect::<Vec<_>>();
+    sorted.sort_by(f64::total_cmp);
+
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+// Best (lowest) latency percentiles among the routable nodes of a subnet
+#[derive(Copy, Clone, Debug)]
+struct SubnetLatency {
+    p50_secs: f64,
+    p95_secs: f64,
+}
+
+// Calculates the node's health score in 0..=1 range, higher is better.
+// It is a product of the following factors, each in 0..=1 range:
+// - success rate of the recent health checks
+// - latency percentiles relative to the best ones observed in the subnet
+// - height lag relative to the subnet's median height and the max allowed lag
+// - certificate freshness i.e. how long ago the node's certified height has last advanced
+fn calc_health_score(
+    state: &NodeState,
+    median_height: u64,
+    max_height_lag: u64,
+    best: SubnetLatency,
+) -> f64 {
+    let success_factor = 1.0 - state.error_rate;
+
+    let latency_factor = 0.5 * (best.p50_secs / state.latency_p50_secs.max(f64::EPSILON))
+        + 0.5 * (best.p95_secs / state.latency_p95_secs.max(f64::EPSILON));
+
+    let height_lag = median_height.saturating_sub(state.height);
+    let height_factor = 1.0 - height_lag as f64 / (max_height_lag + 1) as f64;
+
+    let grace = CERT_STALENESS_GRACE.as_secs_f64();
+    let freshness_factor = if state.cert_staleness_secs <= grace {
+        1.0
+    } else {
+        grace / state.cert_staleness_secs
+    };
+
+    let score = success_factor * latency_factor * height_factor * freshness_factor;
+    if score.is_nan() {
+        return 0.0;
+    }
+
+    score.clamp(0.0, 1.0)
 }
 
 // Send node's state message to the SubnetActor after this number of health checks have passed.
@@ -91,6 +159,12 @@ struct NodeActor {
     checker: Arc<dyn Check>,
     state: Option<NodeState>,
     avg_mov_latency: LatencyMovAvg,
+    // Latencies of the last successful checks
+    latencies: VecDeque<f64>,
+    // Results of the last checks, true if the check has succeeded
+    results: VecDeque<bool>,
+    last_height: u64,
+    last_height_change: Instant,
     checks_counter: usize,
 }
 
@@ -110,10 +184,27 @@ impl NodeActor {
             checker,
             state: None,
             avg_mov_latency: LatencyMovAvg::new(),
+            latencies: VecDeque::with_capacity(WINDOW_SIZE),
+            results: VecDeque::with_capacity(WINDOW_SIZE),
+            last_height: 0,
+            last_height_change: Instant::now(),
             checks_counter: 0,
         }
     }
 
+    // Push the value into the window, evicting the oldest one if it's full
+    fn push_window<T>(window: &mut VecDeque<T>, value: T) {
+        if window.len() >= WINDOW_SIZE {
+            window.pop_front();
+        }
+        window.push_back(value);
+    }
+
+    fn error_rate(&self) -> f64 {
+        let failed = self.results.iter().filter(|&&x| !x).count();
+        failed as f64 / self.results.len().max(1) as f64
+    }
+
     // Perform the health check
     async fn check(&mut self) {
         self.checks_counter += 1;
@@ -127,19 +218,33 @@ impl NodeActor {
                 let current_avg = self.avg_mov_latency.get_average();
                 self.avg_mov_latency.add_sample(latency);
                 let latency_change = (latency - current_avg).abs() / current_avg;
+                Self::push_window(&mut self.latencies, latency);
+
+                if res.height > self.last_height {
+                    self.last_height = res.height;
+                    self.last_height_change = Instant::now();
+                }
+
                 (true, res.height, latency_change)
             }
             // Note: we don't add latency to the moving average in case of an error.
             Err(_) => (false, 0, 0.0),
         };
+        Self::push_window(&mut self.results, healthy);
 
-        // Note: initially we update only the health field. height and avg latency are updated conditionally.
+        // Note: initially we update only the health & error rate fields.
+        // Height, latencies and certificate staleness are updated conditionally.
         let mut new_state = self.state.unwrap_or_else(|| NodeState {
             healthy,
             height,
             avg_latency_secs: self.avg_mov_latency.get_average(),
+            error_rate: 0.0,
+            latency_p50_secs: percentile(&self.latencies, 0.5),
+            latency_p95_secs: percentile(&self.latencies, 0.95),
+            cert_staleness_secs: 0.0,
         });
         new_state.healthy = healthy;
+        new_state.error_rate = self.error_rate();
 
         // Update height and avg latency based on conditions.
         if self.checks_counter >= CHECKS_MSG_PERIODICITY
@@ -148,13 +253,16 @@ impl NodeActor {
             // reset the counter
             self.checks_counter = 0;
             new_state.avg_latency_secs = self.avg_mov_latency.get_average();
+            new_state.latency_p50_secs = percentile(&self.latencies, 0.5);
+            new_state.latency_p95_secs = percentile(&self.latencies, 0.95);
+            new_state.cert_staleness_secs = self.last_height_change.elapsed().as_secs_f64();
             new_state.height = height;
         }
 
         // Send the state down the line if either:
-        // - health has changed
+        // - health or error rate has changed
         // - conditionally updated height has changed
-        // - conditionally updated avg latency has changed
+        // - conditionally updated latencies or certificate staleness have changed
         if Some(new_state) != self.state {
             self.state = Some(new_state);
             // It can never fail in our case
@@ -242,17 +350,17 @@ impl SubnetActor {
         }
     }
 
-    fn calc_min_height(&self) -> u64 {
+    fn calc_median_height(&self) -> u64 {
         let mut heights = self
             .states
             .iter()
-            // calc_min_height is called only when all states are Some()
+            // calc_median_height is called only when all states are Some()
             .map(|x| x.as_ref().unwrap())
             .filter(|x| x.healthy)
             .map(|x| x.height)
             .collect::<Vec<_>>();
 
-        // Calculate the minimum block height requirement for given subnet
+        // Calculate the median block height for given subnet
         match heights.len() {
             0 => 0,
             _ => {
@@ -262,12 +370,25 @@ impl SubnetActor {
                 // We use the median because it's a good approximation of
                 // the "consensus" and keeps us resilient to malicious replicas
                 // sending an artificially high height to DoS the BNs
-                let median_height = (mid_height_0 + mid_height_1) / 2;
-                median_height.saturating_sub(self.max_height_lag)
+                (mid_height_0 + mid_height_1) / 2
             }
         }
     }
 
+    // Checks if the new set of nodes differs from the published one either
+    // by its composition or by some node's health score changing noticeably
+    fn nodes_changed(&self, nodes: &[Arc<Node>]) -> bool {
+        let Some(published) = &self.healthy_nodes else {
+            return true;
+        };
+
+        published.as_slice() != nodes
+            || published
+                .iter()
+                .zip(nodes)
+                .any(|(a, b)| (a.health_score - b.health_score).abs() > SCORE_CHANGE_THRESHOLD)
+    }
+
     // This remembers if we have passed the init state so that we don't have to iterate each time
     fn init_done(&mut self) -> bool {
         if !self.init_done {
@@ -283,11 +404,12 @@ impl SubnetActor {
             return;
         }
 
-        // Calc the minimum height
-        let min_height = self.calc_min_height();
+        // Calc the median & minimum height
+        let median_height = self.calc_median_height();
+        let min_height = median_height.saturating_sub(self.max_height_lag);
 
-        // Generate a list of healthy nodes
-        let nodes = self
+        // Generate a list of healthy nodes with their states
+        let healthy = self
             .states
             .iter()
             // All states are Some() - it's checked above
@@ -297,15 +419,33 @@ impl SubnetActor {
             .map(|(idx, state)| (self.subnet.nodes[idx].clone(), state))
             // Discard unhealthy & lagging behind
             .filter(|(_, state)| state.healthy && state.height >= min_height)
+            .collect::<Vec<_>>();
+
+        // Find the best latencies in the subnet to score the nodes relative to them
+        let best = healthy.iter().fold(
+            SubnetLatency {
+                p50_secs: f64::MAX,
+                p95_secs: f64::MAX,
+            },
+            |acc, (_, state)| SubnetLatency {
+                p50_secs: acc.p50_secs.min(state.latency_p50_secs),
+                p95_secs: acc.p95_secs.min(state.latency_p95_secs),
+            },
+        );
+
+        let nodes = healthy
+            .into_iter()
             .map(|(node, state)| {
                 let mut node = (*node).clone();
                 node.avg_latency_secs = state.avg_latency_secs;
+                node.health_score =
+                    calc_health_score(state, median_height, self.max_height_lag, best);
                 Arc::new(node)
             })
             .collect::<Vec<_>>();
 
-        // See if the healthy nodes set changed
-        if self.healthy_nodes.is_none() || &nodes != self.healthy_nodes.as_ref().unwrap() {
+        // See if the healthy nodes set or their scores have changed
+        if self.nodes_changed(&nodes) {
             self.healthy_nodes = Some(nodes.clone());
 
             // Publish the new subnet
@@ -705,7 +845,7 @@ pub(crate) mod test {
     use crate::{
         persist::{Persister, Routes},
         snapshot::{node_test_id, subnet_test_id, CanisterRange, Node, RegistrySnapshot, Subnet},
-        test_utils::valid_tls_certificate_and_validation_time,
+        test_utils::{test_node_signing_key, valid_tls_certificate_and_validation_time},
     };
 
     const NODE_ID_OFFSET: u64 = 1000;
@@ -739,8 +879,9 @@ pub(crate) mod test {
 
             let mut nodes = Vec::new();
             for j in 0..nodes_per_subnet {
+                let id = node_test_id(NODE_ID_OFFSET + offset + i * 100 + j).get().0;
                 let node = Node {
-                    id: node_test_id(NODE_ID_OFFSET + offset + i * 100 + j).get().0,
+                    id,
                     subnet_id,
                     subnet_type: SubnetType::Application,
                     addr: IpAddr::V4(Ipv4Addr::new(192, 168, i as u8, j as u8)),
@@ -748,7 +889,12 @@ pub(crate) mod test {
                     tls_certificate: valid_tls_certificate_and_validation_time()
                         .0
                         .certificate_der,
+                    signing_public_key: test_node_signing_key(id.as_slice())
+                        .public_key()
+                        .serialize_raw()
+                        .to_vec(),
                     avg_latency_secs: f64::MAX,
+                    health_score: 0.0,
                 };
                 let node = Arc::new(node);
 
@@ -791,6 +937,69 @@ pub(crate) mod test {
         }
     }
 
+    fn node_state(
+        error_rate: f64,
+        latency_secs: f64,
+        height: u64,
+        staleness_secs: f64,
+    ) -> NodeState {
+        NodeState {
+            healthy: true,
+            height,
+            avg_latency_secs: latency_secs,
+            error_rate,
+            latency_p50_secs: latency_secs,
+            latency_p95_secs: latency_secs * 2.0,
+            cert_staleness_secs: staleness_secs,
+        }
+    }
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(percentile(&VecDeque::new(), 0.5), f64::MAX);
+
+        let samples = (1..=10).rev().map(|x| x as f64).collect::<VecDeque<_>>();
+        assert_eq!(percentile(&samples, 0.0), 1.0);
+        assert_eq!(percentile(&samples, 0.5), 5.0);
+        assert_eq!(percentile(&samples, 0.95), 10.0);
+        assert_eq!(percentile(&samples, 1.0), 10.0);
+    }
+
+    #[test]
+    fn test_calc_health_score() {
+        let best = SubnetLatency {
+            p50_secs: 0.1,
+            p95_secs: 0.2,
+        };
+
+        // Perfect node
+        let perfect = calc_health_score(&node_state(0.0, 0.1, 1000, 1.0), 1000, 50, best);
+        assert_eq!(perfect, 1.0);
+
+        // Each of the components lowers the score
+        let erroring = calc_health_score(&node_state(0.3, 0.1, 1000, 1.0), 1000, 50, best);
+        let slow = calc_health_score(&node_state(0.0, 0.4, 1000, 1.0), 1000, 50, best);
+        let lagging = calc_health_score(&node_state(0.0, 0.1, 975, 1.0), 1000, 50, best);
+        let stale = calc_health_score(&node_state(0.0, 0.1, 1000, 40.0), 1000, 50, best);
+
+        assert!((erroring - 0.7).abs() < 1e-9);
+        assert!((slow - 0.25).abs() < 1e-9);
+        assert!((lagging - (1.0 - 25.0 / 51.0)).abs() < 1e-9);
+        assert!((stale - 0.25).abs() < 1e-9);
+
+        // Components are combined
+        let bad = calc_health_score(&node_state(0.3, 0.4, 975, 40.0), 1000, 50, best);
+        assert!(bad < erroring.min(slow).min(lagging).min(stale));
+
+        // Node ahead of the median isn't rewarded
+        let ahead = calc_health_score(&node_state(0.0, 0.1, 1010, 1.0), 1000, 50, best);
+        assert_eq!(ahead, 1.0);
+
+        // Always failing node
+        let failing = calc_health_score(&node_state(1.0, 0.1, 1000, 1.0), 1000, 50, best);
+        assert_eq!(failing, 0.0);
+    }
+
     // Ensure that nodes that have failed healthcheck or lag behind are excluded
     #[tokio::test]
     async fn test_check_some_unhealthy() -> Result<(), Error> {
@@ -945,6 +1154,68 @@ pub(crate) mod test {
         Ok(())
     }
 
+    // Ensure that the published nodes carry health scores and the lagging ones are penalized
+    #[tokio::test]
+    async fn test_check_health_scores() -> Result<(), Error> {
+        let routes = Arc::new(ArcSwapOption::empty());
+        let persister = Arc::new(Persister::new(Arc::clone(&routes)));
+
+        let mut checker = MockCheck::new();
+        checker
+            .expect_check()
+            .withf(|x: &Node| [node_id(0), node_id(1)].contains(&x.id))
+            .returning(|_| Ok(check_result(1000)));
+
+        checker
+            .expect_check()
+            .withf(|x: &Node| x.id == node_id(2))
+            .returning(|_| Ok(check_result(995)));
+
+        let (channel_send, channel_recv) = watch::channel(None);
+        let runner = Runner::new(
+            10,
+            Duration::from_millis(100),
+            Duration::from_millis(1),
+            Arc::new(checker),
+            persister,
+            #[allow(clippy::disallowed_types)]
+            Mutex::new(channel_recv),
+        );
+        tokio::spawn(async move {
+            let _ = runner.run(CancellationToken::new()).await;
+        });
+
+        let snapshot = generate_custom_registry_snapshot(1, 3, 0);
+        channel_send.send(Some(Arc::new(snapshot))).unwrap();
+
+        // Wait until the routing table is published
+        for _ in 1..10 {
+            if routes.load().is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        let rt = routes.load_full().unwrap();
+        let nodes = &rt.subnets[0].nodes;
+        assert_eq!(nodes.len(), 3);
+
+        assert!(nodes.iter().all(|x| (0.0..=1.0).contains(&x.health_score)));
+        assert!(nodes
+            .windows(2)
+            .all(|x| x[0].health_score >= x[1].health_score));
+
+        // Node 2 lags 5 blocks behind the median with the max lag of 10
+        let score = nodes
+            .iter()
+            .find(|x| x.id == node_id(2))
+            .unwrap()
+            .health_score;
+        assert!(score <= 1.0 - 5.0 / 11.0 + 1e-9);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_runner() -> Result<(), Error> {
         let mut checker = MockCheck::new();
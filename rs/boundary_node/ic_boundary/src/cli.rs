@@ -55,6 +55,9 @@ pub struct Cli {
     #[command(flatten, next_help_heading = "Retries")]
     pub retry: Retry,
 
+    #[command(flatten, next_help_heading = "Query Agreement")]
+    pub query_agreement: QueryAgreement,
+
     #[command(flatten, next_help_heading = "Load")]
     pub load: Load,
 
@@ -367,6 +370,40 @@ pub struct Retry {
     /// Whether to use latency-based routing for /call
     #[clap(env, long, default_value = "false")]
     pub retry_disable_latency_routing: bool,
+
+    /// Enables hedging of read-only requests (query, read_state).
+    /// If the node has not responded within this percentile of its observed latencies (e.g. 0.95)
+    /// then the request is also sent to another node and the first valid response is used.
+    #[clap(env, long, value_parser = parse_hedge_percentile)]
+    pub retry_hedge_percentile: Option<f64>,
+
+    /// How long to wait before hedging while there are not enough latency observations for the node
+    #[clap(env, long, default_value = "500ms", value_parser = parse_duration)]
+    pub retry_hedge_default_delay: Duration,
+
+    /// Maximum fraction of read-only requests that can be hedged, limits the load amplification
+    #[clap(env, long, default_value = "0.05", value_parser = parse_hedge_budget)]
+    pub retry_hedge_budget: f64,
+}
+
+#[derive(Args)]
+pub struct QueryAgreement {
+    /// How many replicas of the subnet to send a query to when agreement mode applies.
+    /// The response is returned only if all of them agree.
+    /// Each response must carry a valid signature of the node it came from,
+    /// checked against the node signing key in the registry.
+    /// Should be in range [2..13], if the subnet has less healthy nodes - the query fails.
+    #[clap(env, long, default_value = "3", value_parser = clap::value_parser!(u8).range(2..14))]
+    pub query_agreement_replicas: u8,
+
+    /// Canisters for which all queries are executed in agreement mode
+    #[clap(env, long, value_delimiter = ',')]
+    pub query_agreement_canisters: Vec<Principal>,
+
+    /// Whether to honor the `x-ic-query-agreement` request header,
+    /// which enables agreement mode for individual queries
+    #[clap(env, long, default_value = "false")]
+    pub query_agreement_per_request: bool,
 }
 
 #[derive(Args)]
@@ -449,3 +486,21 @@ pub struct Misc {
 fn parse_crypto_config(arg: &str) -> Result<CryptoConfig, serde_json::Error> {
     serde_json::from_str(arg)
 }
+
+fn parse_hedge_percentile(arg: &str) -> Result<f64, String> {
+    let v: f64 = arg.parse().map_err(|e| format!("{e}"))?;
+    if v.is_nan() || v <= 0.0 || v > 1.0 {
+        return Err("must be in range (0, 1]".into());
+    }
+
+    Ok(v)
+}
+
+fn parse_hedge_budget(arg: &str) -> Result<f64, String> {
+    let v: f64 = arg.parse().map_err(|e| format!("{e}"))?;
+    if !v.is_finite() || v < 0.0 {
+        return Err("must be a non-negative number".into());
+    }
+
+    Ok(v)
+}
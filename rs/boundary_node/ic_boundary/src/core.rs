@@ -67,15 +67,19 @@ use crate::{
     http::{
         handlers::{self, logs_canister, LogsState},
         middleware::{
+            agreement::{
+                mark_query_agreement, query_agreement, QueryAgreementMetrics, QueryAgreementParams,
+            },
             cache::{cache_middleware, CacheState},
             cors::{self},
             geoip::{self},
+            hedge::{Hedging, HedgingMetrics},
             process::{self},
             retry::{retry_request, RetryParams},
             validate::{self, UUID_REGEX},
         },
-        PATH_CALL, PATH_CALL_V3, PATH_HEALTH, PATH_QUERY, PATH_READ_STATE, PATH_STATUS,
-        PATH_SUBNET_READ_STATE,
+        PATH_CALL, PATH_CALL_V3, PATH_HEALTH, PATH_HEALTH_NODES, PATH_QUERY, PATH_READ_STATE,
+        PATH_STATUS, PATH_SUBNET_READ_STATE,
     },
     metrics::{
         self, HttpMetricParams, HttpMetricParamsStatus, MetricParamsCheck, MetricParamsPersist,
@@ -926,9 +930,13 @@ pub fn setup_router(
             metrics::metrics_middleware_status,
         ));
 
-    let health_route = Router::new().route(PATH_HEALTH, {
-        get(handlers::health).with_state(health.clone())
-    });
+    let health_route = Router::new()
+        .route(PATH_HEALTH, {
+            get(handlers::health).with_state(health.clone())
+        })
+        .route(PATH_HEALTH_NODES, {
+            get(handlers::health_nodes).with_state(health.clone())
+        });
 
     let middleware_geoip = option_layer(cli.misc.geoip_db.as_ref().map(|x| {
         middleware::from_fn_with_state(
@@ -974,10 +982,35 @@ pub fn setup_router(
             retry_count: cli.retry.retry_count as usize,
             retry_update_call: cli.retry.retry_update_call,
             disable_latency_routing: cli.retry.retry_disable_latency_routing,
+            hedging: cli.retry.retry_hedge_percentile.map(|x| {
+                Arc::new(Hedging::new(
+                    x,
+                    cli.retry.retry_hedge_default_delay,
+                    cli.retry.retry_hedge_budget,
+                    HedgingMetrics::new(metrics_registry),
+                ))
+            }),
         },
         retry_request,
     );
 
+    let query_agreement_params = QueryAgreementParams {
+        replicas: cli.query_agreement.query_agreement_replicas as usize,
+        canisters: Arc::new(
+            cli.query_agreement
+                .query_agreement_canisters
+                .iter()
+                .copied()
+                .collect(),
+        ),
+        per_request: cli.query_agreement.query_agreement_per_request,
+        metrics: QueryAgreementMetrics::new(metrics_registry),
+    };
+    let middleware_mark_query_agreement =
+        middleware::from_fn_with_state(query_agreement_params.clone(), mark_query_agreement);
+    let middleware_query_agreement =
+        middleware::from_fn_with_state(query_agreement_params, query_agreement);
+
     // Load shedders
 
     // We need to map the generic response of a shedder to an Axum's Response
@@ -1055,10 +1088,13 @@ pub fn setup_router(
         .layer(common_service_layers.clone())
         .layer(middleware_subnet_lookup.clone())
         .layer(middleware_generic_limiter.clone())
+        // Must come before the cache so that agreement queries bypass it
+        .layer(middleware_mark_query_agreement)
         .layer(option_layer(cache_state.map(|x| {
             middleware::from_fn_with_state(x.clone(), cache_middleware)
         })))
-        .layer(middleware_retry.clone());
+        .layer(middleware_retry.clone())
+        .layer(middleware_query_agreement);
 
     let service_subnet_read = ServiceBuilder::new()
         .layer(middleware::from_fn(validate::validate_request))
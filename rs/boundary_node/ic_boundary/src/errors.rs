@@ -39,6 +39,7 @@ pub enum ErrorCause {
     ReplicaTLSErrorOther(String),
     ReplicaTLSErrorCert(String),
     ReplicaErrorOther(String),
+    QueryDisagreement(String),
     #[strum(serialize = "rate_limited_{0}")]
     RateLimited(RateLimitCause),
     #[strum(serialize = "internal_server_error")]
@@ -59,12 +60,13 @@ impl ErrorCause {
             Self::ReplicaTLSErrorOther(x) => Some(x.clone()),
             Self::ReplicaTLSErrorCert(x) => Some(x.clone()),
             Self::ReplicaErrorOther(x) => Some(x.clone()),
+            Self::QueryDisagreement(x) => Some(x.clone()),
             _ => None,
         }
     }
 
     pub fn retriable(&self) -> bool {
-        !matches!(self, Self::PayloadTooLarge(_))
+        !matches!(self, Self::PayloadTooLarge(_) | Self::QueryDisagreement(_))
     }
 
     pub fn to_client_facing_error(&self) -> ErrorClientFacing {
@@ -87,6 +89,7 @@ impl ErrorCause {
             Self::ReplicaTLSErrorOther(_) => ErrorClientFacing::ReplicaError,
             Self::ReplicaTLSErrorCert(_) => ErrorClientFacing::ReplicaError,
             Self::ReplicaErrorOther(_) => ErrorClientFacing::ReplicaError,
+            Self::QueryDisagreement(_) => ErrorClientFacing::QueryDisagreement,
             Self::Forbidden => ErrorClientFacing::Forbidden,
             Self::RateLimited(_) => ErrorClientFacing::RateLimited,
         }
@@ -115,6 +118,7 @@ pub enum ErrorClientFacing {
     Other,
     PayloadTooLarge(usize),
     Forbidden,
+    QueryDisagreement,
     RateLimited,
     ReplicaError,
     ServiceUnavailable,
@@ -134,6 +138,7 @@ impl ErrorClientFacing {
             Self::Other => StatusCode::INTERNAL_SERVER_ERROR,
             Self::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
             Self::Forbidden => StatusCode::FORBIDDEN,
+            Self::QueryDisagreement => StatusCode::BAD_GATEWAY,
             Self::RateLimited => StatusCode::TOO_MANY_REQUESTS,
             Self::ReplicaError => StatusCode::SERVICE_UNAVAILABLE,
             Self::ServiceUnavailable => StatusCode::SERVICE_UNAVAILABLE,
@@ -153,6 +158,7 @@ impl ErrorClientFacing {
             Self::Other => "Internal Server Error".to_string(),
             Self::PayloadTooLarge(x) => format!("Payload is too large: maximum body size is {x} bytes."),
             Self::Forbidden => "Request is forbidden according to currently active policy, it might work later.".to_string(),
+            Self::QueryDisagreement => "The replica nodes returned diverging responses to the query. Please try again later.".to_string(),
             Self::RateLimited => "Rate limit exceeded. Please slow down requests and try again later.".to_string(),
             Self::ReplicaError => "An unexpected error occurred while communicating with the upstream replica node. Please try again later.".to_string(),
             Self::ServiceUnavailable => "The API boundary node is temporarily unable to process the request. Please try again later.".to_string(),
@@ -15,7 +15,7 @@ use axum::{
     },
     http::StatusCode,
     response::IntoResponse,
-    Extension,
+    Extension, Json,
 };
 use bytes::Bytes;
 use candid::Principal;
@@ -199,6 +199,11 @@ pub async fn health(State(h): State<Arc<dyn Health>>) -> impl IntoResponse {
     }
 }
 
+// Handler: emits the health scores of the nodes currently published in the routing table
+pub async fn health_nodes(State(h): State<Arc<dyn Health>>) -> impl IntoResponse {
+    Json(h.node_health())
+}
+
 // Handler: processes IC status call
 pub async fn status(
     State((rk, h)): State<(Arc<dyn RootKey>, Arc<dyn Health>)>,
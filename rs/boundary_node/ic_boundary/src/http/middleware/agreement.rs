@@ -0,0 +1,516 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+};
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Extension,
+};
+use bytes::Bytes;
+use candid::Principal;
+use futures::future::join_all;
+use http::{response::Parts, HeaderName, StatusCode};
+use ic_types::{
+    crypto::Signable,
+    messages::{
+        HttpQueryContent, HttpQueryResponse, HttpRequestEnvelope, Query, QueryResponseHash,
+    },
+    Time,
+};
+use prometheus::{register_int_counter_vec_with_registry, IntCounterVec, Registry};
+use serde_cbor::Value;
+
+use crate::{
+    errors::{ApiError, ErrorCause},
+    persist::RouteSubnet,
+    routes::{RequestContext, RequestType},
+    snapshot::Node,
+};
+
+/// Header that enables agreement mode for a single query
+pub const X_IC_QUERY_AGREEMENT: HeaderName = HeaderName::from_static("x-ic-query-agreement");
+
+/// Request extension that marks a query to be executed in agreement mode.
+/// It is set early in the middleware chain so that the layers in front of the
+/// agreement middleware (e.g. the cache) can take it into account.
+#[derive(Clone, Copy, Debug)]
+pub struct QueryAgreementRequest;
+
+// Replicas do not produce responses larger than a few MBs, so this is plenty
+const MAX_RESPONSE_SIZE: usize = 8 * 1024 * 1024;
+
+const RESULT_AGREED: &str = "agreed";
+const RESULT_DISAGREED: &str = "disagreed";
+const RESULT_REPLICA_ERROR: &str = "replica_error";
+const RESULT_NOT_ENOUGH_NODES: &str = "not_enough_nodes";
+
+#[derive(Clone)]
+pub struct QueryAgreementMetrics {
+    pub results: IntCounterVec,
+}
+
+impl QueryAgreementMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            results: register_int_counter_vec_with_registry!(
+                "query_agreement_total",
+                "Counts the outcomes of queries executed in agreement mode",
+                &["result", "subnet_id"],
+                registry
+            )
+            .unwrap(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct QueryAgreementParams {
+    pub replicas: usize,
+    pub canisters: Arc<HashSet<Principal>>,
+    pub per_request: bool,
+    pub metrics: QueryAgreementMetrics,
+}
+
+impl QueryAgreementParams {
+    // Agreement mode applies to queries only, either to all queries of
+    // the configured canisters or to those that ask for it with a header
+    fn applies_to(&self, ctx: &RequestContext, request: &Request) -> bool {
+        if ctx.request_type != RequestType::Query {
+            return false;
+        }
+
+        if ctx.canister_id.is_some_and(|x| self.canisters.contains(&x)) {
+            return true;
+        }
+
+        self.per_request && request.headers().contains_key(X_IC_QUERY_AGREEMENT)
+    }
+
+    fn observe(&self, result: &str, subnet: &RouteSubnet) {
+        self.metrics
+            .results
+            .with_label_values(&[result, &subnet.id.to_string()])
+            .inc();
+    }
+}
+
+// Parses the query that the replicas sign their responses for
+fn parse_query(body: &[u8]) -> Result<Query, String> {
+    let envelope: HttpRequestEnvelope<HttpQueryContent> =
+        serde_cbor::from_slice(body).map_err(|e| format!("unable to decode query: {e}"))?;
+
+    let HttpQueryContent::Query { query } = envelope.content;
+    Query::try_from(query).map_err(|e| format!("unable to parse query: {e}"))
+}
+
+// Verifies the signature that `node` attached to its response to `query`
+// against the node's signing key from the registry
+fn verify_node_signature(
+    response: &HttpQueryResponse,
+    query: &Query,
+    signature: &BTreeMap<Value, Value>,
+    node: &Node,
+) -> Result<(), String> {
+    let Some(Value::Integer(timestamp)) = signature.get(&Value::Text("timestamp".into())) else {
+        return Err(format!("signature of node {} has no timestamp", node.id));
+    };
+    let timestamp = u64::try_from(*timestamp)
+        .map_err(|_| format!("signature of node {} has an invalid timestamp", node.id))?;
+
+    let Some(Value::Bytes(signature)) = signature.get(&Value::Text("signature".into())) else {
+        return Err(format!("signature entry of node {} is empty", node.id));
+    };
+
+    let public_key = ic_ed25519::PublicKey::deserialize_raw(&node.signing_public_key)
+        .map_err(|e| format!("invalid signing key of node {}: {e:?}", node.id))?;
+
+    let hash = QueryResponseHash::new(
+        response,
+        query,
+        Time::from_nanos_since_unix_epoch(timestamp),
+    );
+
+    public_key
+        .verify_signature(&hash.as_signed_bytes(), signature)
+        .map_err(|e| format!("invalid signature of node {}: {e:?}", node.id))
+}
+
+// Extracts the part of a signed query response that all replicas must agree on.
+// That is everything except for the node signatures, which differ per node.
+// The response must carry a valid signature of the node it was received from,
+// checked against the node's public key from the registry snapshot.
+fn response_content(body: &[u8], query: &Query, node: &Node) -> Result<Value, String> {
+    let value: Value = serde_cbor::from_slice(body)
+        .map_err(|e| format!("unable to decode response of node {}: {e}", node.id))?;
+
+    let Value::Map(mut map) = value else {
+        return Err(format!("response of node {} is not a map", node.id));
+    };
+
+    let identity = Value::Bytes(node.id.as_slice().to_vec());
+    let signature = match map.remove(&Value::Text("signatures".into())) {
+        Some(Value::Array(signatures)) => signatures.into_iter().find_map(|x| match x {
+            Value::Map(signature)
+                if signature.get(&Value::Text("identity".into())) == Some(&identity) =>
+            {
+                Some(signature)
+            }
+            _ => None,
+        }),
+        _ => None,
+    };
+
+    let Some(signature) = signature else {
+        return Err(format!(
+            "response has no signature entry of node {}",
+            node.id
+        ));
+    };
+
+    let content = Value::Map(map);
+    let response: HttpQueryResponse = serde_cbor::value::from_value(content.clone())
+        .map_err(|e| format!("unable to parse response of node {}: {e}", node.id))?;
+    verify_node_signature(&response, query, &signature, node)?;
+
+    Ok(content)
+}
+
+// Middleware that marks the queries that are to be executed in agreement mode.
+// Must run before the cache and retry middlewares.
+pub async fn mark_query_agreement(
+    State(params): State<QueryAgreementParams>,
+    Extension(ctx): Extension<Arc<RequestContext>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if params.applies_to(&ctx, &request) {
+        request.extensions_mut().insert(QueryAgreementRequest);
+    }
+
+    next.run(request).await
+}
+
+// Middleware that sends a query to several replicas of the subnet and returns
+// the response only if all of them agree on it
+pub async fn query_agreement(
+    State(params): State<QueryAgreementParams>,
+    Extension(subnet): Extension<Arc<RouteSubnet>>,
+    request: Request,
+    next: Next,
+) -> Result<impl IntoResponse, ApiError> {
+    if request
+        .extensions()
+        .get::<QueryAgreementRequest>()
+        .is_none()
+    {
+        return Ok(next.run(request).await);
+    }
+
+    let nodes = subnet.pick_random_nodes(params.replicas)?;
+    if nodes.len() < params.replicas {
+        params.observe(RESULT_NOT_ENOUGH_NODES, &subnet);
+        return Err(ErrorCause::NoHealthyNodes.into());
+    }
+
+    let (parts, body) = request.into_parts();
+    // We don't care for the max size since the body already buffered and checked before.
+    // And it cannot fail since it's already in-memory.
+    let body = to_bytes(body, usize::MAX).await.unwrap();
+
+    let query = parse_query(&body).map_err(ErrorCause::MalformedRequest)?;
+
+    let responses = join_all(nodes.iter().map(|node| {
+        let mut request = Request::from_parts(parts.clone(), Body::from(body.clone()));
+        request.extensions_mut().insert(node.clone());
+        next.clone().run(request)
+    }))
+    .await;
+
+    let mut agreed: Option<(Parts, Bytes, Value)> = None;
+    for (node, response) in nodes.iter().zip(responses) {
+        // Rejected queries are also returned with 200, so anything else
+        // is a failure to talk to the replica - pass it through as is
+        if response.status() != StatusCode::OK {
+            params.observe(RESULT_REPLICA_ERROR, &subnet);
+            return Ok(response);
+        }
+
+        let (parts, body) = response.into_parts();
+        let body = to_bytes(body, MAX_RESPONSE_SIZE)
+            .await
+            .map_err(|e| ErrorCause::ReplicaErrorOther(e.to_string()))?;
+
+        let content = response_content(&body, &query, node).map_err(|e| {
+            params.observe(RESULT_DISAGREED, &subnet);
+            ErrorCause::QueryDisagreement(e)
+        })?;
+
+        match &agreed {
+            None => agreed = Some((parts, body, content)),
+            Some((_, _, expected)) if expected == &content => {}
+            Some(_) => {
+                params.observe(RESULT_DISAGREED, &subnet);
+                return Err(ErrorCause::QueryDisagreement(format!(
+                    "node {} returned a diverging response",
+                    node.id
+                ))
+                .into());
+            }
+        }
+    }
+
+    params.observe(RESULT_AGREED, &subnet);
+
+    // At least one node was picked, so there's a response
+    let (parts, body, _) = agreed.unwrap();
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::{collections::BTreeMap, str::FromStr};
+
+    use anyhow::Error;
+    use axum::{middleware, routing::method_routing::post, Router};
+    use ic_bn_lib::principal;
+    use ic_types::{
+        messages::{Blob, HttpQueryResponseReply, HttpUserQuery},
+        CanisterId,
+    };
+    use tower::Service;
+
+    use crate::{
+        routes::test::{test_node, test_route_subnet},
+        test_utils::test_node_signing_key,
+    };
+
+    #[derive(Clone, Copy)]
+    enum Replicas {
+        Agree,
+        Diverge,
+        ForgeSignatures,
+    }
+
+    fn query_body() -> Vec<u8> {
+        let envelope = HttpRequestEnvelope::<HttpQueryContent> {
+            content: HttpQueryContent::Query {
+                query: HttpUserQuery {
+                    canister_id: Blob(principal!("f7crg-kabae").as_slice().to_vec()),
+                    method_name: "foo".into(),
+                    arg: Blob(vec![1, 2, 3, 4]),
+                    sender: Blob(Principal::anonymous().as_slice().to_vec()),
+                    nonce: None,
+                    ingress_expiry: 1,
+                },
+            },
+            sender_delegation: None,
+            sender_pubkey: None,
+            sender_sig: None,
+        };
+
+        serde_cbor::to_vec(&envelope).unwrap()
+    }
+
+    fn gen_request(request_type: RequestType, agreement: bool) -> Request<Body> {
+        let ctx = RequestContext {
+            request_type,
+            canister_id: Some(principal!("f7crg-kabae")),
+            sender: Some(principal!("f7crg-kabae")),
+            method_name: Some("foo".into()),
+            ingress_expiry: Some(1),
+            arg: Some(vec![1, 2, 3, 4]),
+            ..Default::default()
+        };
+
+        let mut req = Request::post("/").body(Body::from(query_body())).unwrap();
+        if agreement {
+            req.headers_mut()
+                .insert(X_IC_QUERY_AGREEMENT, "1".parse().unwrap());
+        }
+        req.extensions_mut().insert(Arc::new(ctx));
+        req.extensions_mut()
+            .insert(CanisterId::from_str("f7crg-kabae").unwrap());
+        req.extensions_mut().insert(Arc::new(test_route_subnet(4)));
+        // Normally inserted by the retry middleware
+        req.extensions_mut().insert(test_node(0));
+
+        req
+    }
+
+    // Signs the response with `key` in the name of `node`
+    fn signed_response(
+        reply: &[u8],
+        query: &Query,
+        node: &Node,
+        key: &ic_ed25519::PrivateKey,
+    ) -> Vec<u8> {
+        let timestamp = 1234;
+        let response = HttpQueryResponse::Replied {
+            reply: HttpQueryResponseReply {
+                arg: Blob(reply.to_vec()),
+            },
+        };
+        let hash = QueryResponseHash::new(
+            &response,
+            query,
+            Time::from_nanos_since_unix_epoch(timestamp),
+        );
+
+        let signature = Value::Map(BTreeMap::from([
+            (
+                Value::Text("timestamp".into()),
+                Value::Integer(timestamp.into()),
+            ),
+            (
+                Value::Text("signature".into()),
+                Value::Bytes(key.sign_message(&hash.as_signed_bytes()).to_vec()),
+            ),
+            (
+                Value::Text("identity".into()),
+                Value::Bytes(node.id.as_slice().to_vec()),
+            ),
+        ]));
+
+        let response = Value::Map(BTreeMap::from([
+            (Value::Text("status".into()), Value::Text("replied".into())),
+            (
+                Value::Text("reply".into()),
+                Value::Map(BTreeMap::from([(
+                    Value::Text("arg".into()),
+                    Value::Bytes(reply.to_vec()),
+                )])),
+            ),
+            (
+                Value::Text("signatures".into()),
+                Value::Array(vec![signature]),
+            ),
+        ]));
+
+        serde_cbor::to_vec(&response).unwrap()
+    }
+
+    // Replies with the node id if the replicas diverge, so that nodes disagree,
+    // and signs with a key that is not the node's if the signatures are forged
+    async fn handler(
+        State(replicas): State<Replicas>,
+        Extension(node): Extension<Arc<Node>>,
+        body: Bytes,
+    ) -> Response {
+        let query = parse_query(&body).unwrap();
+
+        let reply = match replicas {
+            Replicas::Diverge => node.id.as_slice().to_vec(),
+            Replicas::Agree | Replicas::ForgeSignatures => b"foobar".to_vec(),
+        };
+
+        let key = match replicas {
+            Replicas::ForgeSignatures => test_node_signing_key(b"forger"),
+            Replicas::Agree | Replicas::Diverge => test_node_signing_key(node.id.as_slice()),
+        };
+
+        signed_response(&reply, &query, &node, &key).into_response()
+    }
+
+    fn app(replicas: Replicas, canisters: HashSet<Principal>) -> Router {
+        let params = QueryAgreementParams {
+            replicas: 3,
+            canisters: Arc::new(canisters),
+            per_request: true,
+            metrics: QueryAgreementMetrics::new(&Registry::new()),
+        };
+
+        Router::new()
+            .route("/", post(handler).with_state(replicas))
+            .layer(middleware::from_fn_with_state(
+                params.clone(),
+                query_agreement,
+            ))
+            .layer(middleware::from_fn_with_state(params, mark_query_agreement))
+    }
+
+    #[test]
+    fn test_response_content_requires_node_signature() {
+        let query = parse_query(&query_body()).unwrap();
+        let node = test_node(1);
+        let other = test_node(2);
+        let key = test_node_signing_key(node.id.as_slice());
+
+        let body = signed_response(b"foobar", &query, &node, &key);
+        assert!(response_content(&body, &query, &node).is_ok());
+        assert!(response_content(&body, &query, &other).is_err());
+        assert!(response_content(b"foobar", &query, &node).is_err());
+
+        // Signed with the key of another node
+        let other_key = test_node_signing_key(other.id.as_slice());
+        let body = signed_response(b"foobar", &query, &node, &other_key);
+        assert!(response_content(&body, &query, &node)
+            .unwrap_err()
+            .contains("invalid signature"));
+
+        // Signed for another query
+        let mut other_query = query.clone();
+        other_query.method_name = "bar".into();
+        let body = signed_response(b"foobar", &other_query, &node, &key);
+        assert!(response_content(&body, &query, &node).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_query_agreement() -> Result<(), Error> {
+        // Replicas agree
+        let mut app_agree = app(Replicas::Agree, HashSet::new());
+        let res = app_agree
+            .call(gen_request(RequestType::Query, true))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        // Replicas disagree
+        let mut app_diverge = app(Replicas::Diverge, HashSet::new());
+        let res = app_diverge
+            .call(gen_request(RequestType::Query, true))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_GATEWAY);
+
+        // Replicas agree on the content, but the signatures are invalid
+        let mut app_forged = app(Replicas::ForgeSignatures, HashSet::new());
+        let res = app_forged
+            .call(gen_request(RequestType::Query, true))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_GATEWAY);
+
+        // Agreement mode is not requested, so only a single replica is asked
+        let res = app_diverge
+            .call(gen_request(RequestType::Query, false))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        // Agreement mode is not used for calls
+        let res = app_diverge
+            .call(gen_request(RequestType::Call, true))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        // Agreement mode is enabled for the canister
+        let mut app_diverge = app(
+            Replicas::Diverge,
+            HashSet::from([principal!("f7crg-kabae")]),
+        );
+        let res = app_diverge
+            .call(gen_request(RequestType::Query, false))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_GATEWAY);
+
+        Ok(())
+    }
+}
@@ -21,6 +21,7 @@ use tokio_util::sync::CancellationToken;
 use crate::{
     cli,
     errors::{ApiError, ErrorCause},
+    http::middleware::agreement::QueryAgreementRequest,
     routes::RequestContext,
 };
 
@@ -48,6 +49,7 @@ pub enum BypassReasonIC {
     IncorrectRequestType,
     Nonce,
     NonAnonymous,
+    QueryAgreement,
 }
 impl CustomBypassReason for BypassReasonIC {}
 
@@ -77,6 +79,9 @@ impl Bypasser for BypasserIC {
         } else if ctx.is_anonymous() == Some(false) && !self.cache_non_anonymous {
             // Bypass non-anonymous requests if not configured to cache them
             Some(BypassReasonIC::NonAnonymous)
+        } else if req.extensions().get::<QueryAgreementRequest>().is_some() {
+            // Queries in agreement mode must always be answered by the replicas
+            Some(BypassReasonIC::QueryAgreement)
         } else {
             None
         })
@@ -318,6 +323,20 @@ mod test {
         let body = String::from_utf8_lossy(&body);
         assert_eq!("a".repeat(DEFAULT_SIZE as usize), body);
 
+        // Check query agreement
+        let mut req = gen_request(CANISTER_1, false);
+        req.extensions_mut().insert(QueryAgreementRequest);
+        let res = app.call(req).await.unwrap();
+        let cs = res
+            .extensions()
+            .get::<CacheStatus<BypassReasonIC>>()
+            .cloned()
+            .unwrap();
+        assert_eq!(
+            cs,
+            CacheStatus::Bypass(CacheBypassReason::Custom(BypassReasonIC::QueryAgreement))
+        );
+
         // Check with nonce
         let req = gen_request(CANISTER_1, true);
         let res = app.call(req).await.unwrap();
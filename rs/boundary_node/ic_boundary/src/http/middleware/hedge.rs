@@ -0,0 +1,190 @@
+use std::{collections::VecDeque, sync::Mutex, time::Duration};
+
+use candid::Principal;
+use dashmap::DashMap;
+use prometheus::{register_int_counter_vec_with_registry, IntCounterVec, Registry};
+
+use crate::{routes::RequestType, snapshot::Node};
+
+// Number of most recent latencies kept per node
+const LATENCY_WINDOW: usize = 128;
+// Below this number of samples the node's latency distribution is not trusted
+const MIN_SAMPLES: usize = 16;
+// Max number of hedges that can be done in a burst
+const BUDGET_BURST: f64 = 10.0;
+
+pub const OUTCOME_PRIMARY: &str = "primary";
+pub const OUTCOME_HEDGE: &str = "hedge";
+pub const OUTCOME_NO_BUDGET: &str = "no_budget";
+
+#[derive(Clone)]
+pub struct HedgingMetrics {
+    pub hedges: IntCounterVec,
+}
+
+impl HedgingMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            hedges: register_int_counter_vec_with_registry!(
+                "hedge_total",
+                "Counts hedging attempts by outcome: which request won or if the budget was exhausted",
+                &["outcome"],
+                registry
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// Decides if and when a read-only request is sent to a second node.
+///
+/// The delay is a percentile of the latencies observed for the node that
+/// the request was sent to. To avoid load amplification only a fraction
+/// of the requests can be hedged, which is enforced with a token bucket.
+pub struct Hedging {
+    percentile: f64,
+    default_delay: Duration,
+    budget_ratio: f64,
+    budget: Mutex<f64>,
+    latencies: DashMap<Principal, VecDeque<Duration>>,
+    metrics: HedgingMetrics,
+}
+
+impl Hedging {
+    pub fn new(
+        percentile: f64,
+        default_delay: Duration,
+        budget_ratio: f64,
+        metrics: HedgingMetrics,
+    ) -> Self {
+        Self {
+            percentile: percentile.clamp(0.0, 1.0),
+            default_delay,
+            budget_ratio,
+            budget: Mutex::new(BUDGET_BURST),
+            latencies: DashMap::new(),
+            metrics,
+        }
+    }
+
+    /// Only requests without side effects can be sent to several nodes
+    pub fn applies_to(request_type: RequestType) -> bool {
+        matches!(
+            request_type,
+            RequestType::Query | RequestType::ReadState | RequestType::ReadStateSubnet
+        )
+    }
+
+    /// Records the latency of a successful request to the given node
+    pub fn record(&self, node: &Node, latency: Duration) {
+        let mut samples = self.latencies.entry(node.id).or_default();
+        if samples.len() == LATENCY_WINDOW {
+            samples.pop_front();
+        }
+        samples.push_back(latency);
+    }
+
+    /// Returns how long to wait for the given node before hedging
+    pub fn delay(&self, node: &Node) -> Duration {
+        let Some(samples) = self.latencies.get(&node.id) else {
+            return self.default_delay;
+        };
+
+        if samples.len() < MIN_SAMPLES {
+            return self.default_delay;
+        }
+
+        let mut sorted = samples.iter().copied().collect::<Vec<_>>();
+        sorted.sort_unstable();
+        let idx = ((sorted.len() - 1) as f64 * self.percentile).round() as usize;
+
+        sorted[idx]
+    }
+
+    /// Adds budget for hedging, should be called once per eligible request
+    pub fn deposit(&self) {
+        let mut budget = self.budget.lock().unwrap();
+        *budget = (*budget + self.budget_ratio).min(BUDGET_BURST);
+    }
+
+    /// Takes budget for a single hedge, returns false if it's exhausted
+    pub fn try_acquire(&self) -> bool {
+        let mut budget = self.budget.lock().unwrap();
+        if *budget < 1.0 {
+            self.metrics
+                .hedges
+                .with_label_values(&[OUTCOME_NO_BUDGET])
+                .inc();
+            return false;
+        }
+
+        *budget -= 1.0;
+        true
+    }
+
+    pub fn observe(&self, outcome: &str) {
+        self.metrics.hedges.with_label_values(&[outcome]).inc();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::routes::test::test_node;
+
+    fn hedging(budget_ratio: f64) -> Hedging {
+        Hedging::new(
+            0.9,
+            Duration::from_millis(500),
+            budget_ratio,
+            HedgingMetrics::new(&Registry::new()),
+        )
+    }
+
+    #[test]
+    fn test_delay() {
+        let h = hedging(0.1);
+        let node = test_node(0);
+
+        // Not enough samples
+        assert_eq!(h.delay(&node), Duration::from_millis(500));
+        for i in 1..MIN_SAMPLES as u64 {
+            h.record(&node, Duration::from_millis(i * 10));
+        }
+        assert_eq!(h.delay(&node), Duration::from_millis(500));
+
+        // 10ms..1000ms
+        for i in MIN_SAMPLES as u64..=100 {
+            h.record(&node, Duration::from_millis(i * 10));
+        }
+        assert_eq!(h.delay(&node), Duration::from_millis(900));
+
+        // Old samples are evicted
+        for _ in 0..LATENCY_WINDOW {
+            h.record(&node, Duration::from_millis(20));
+        }
+        assert_eq!(h.delay(&node), Duration::from_millis(20));
+
+        // Other nodes are not affected
+        assert_eq!(h.delay(&test_node(1)), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_budget() {
+        let h = hedging(0.5);
+
+        // Initial burst
+        for _ in 0..BUDGET_BURST as usize {
+            assert!(h.try_acquire());
+        }
+        assert!(!h.try_acquire());
+
+        // Two requests add enough budget for one hedge
+        h.deposit();
+        assert!(!h.try_acquire());
+        h.deposit();
+        assert!(h.try_acquire());
+        assert!(!h.try_acquire());
+    }
+}
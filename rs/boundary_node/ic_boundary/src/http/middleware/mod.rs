@@ -1,6 +1,8 @@
+pub(crate) mod agreement;
 pub(crate) mod cache;
 pub(crate) mod cors;
 pub(crate) mod geoip;
+pub(crate) mod hedge;
 pub(crate) mod process;
 pub(crate) mod retry;
 pub(crate) mod validate;
@@ -1,4 +1,9 @@
-use std::sync::Arc;
+use std::{
+    collections::VecDeque,
+    pin::pin,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use axum::{
     body::{to_bytes, Body},
@@ -7,8 +12,13 @@ use axum::{
     response::{IntoResponse, Response},
     Extension,
 };
-use http::StatusCode;
+use bytes::Bytes;
+use http::{request::Parts, StatusCode};
 
+use super::{
+    agreement::QueryAgreementRequest,
+    hedge::{Hedging, OUTCOME_HEDGE, OUTCOME_PRIMARY},
+};
 use crate::{
     errors::{ApiError, ErrorCause},
     persist::RouteSubnet,
@@ -21,6 +31,7 @@ pub struct RetryParams {
     pub retry_count: usize,
     pub retry_update_call: bool,
     pub disable_latency_routing: bool,
+    pub hedging: Option<Arc<Hedging>>,
 }
 
 #[derive(Clone)]
@@ -52,6 +63,77 @@ fn request_needs_retrying(response: &Response) -> bool {
     }
 }
 
+// Sends the request to the given node and measures how long it took
+async fn send_to_node(
+    next: Next,
+    parts: Parts,
+    body: Bytes,
+    node: Arc<Node>,
+) -> (Arc<Node>, Duration, Response) {
+    let mut request = Request::from_parts(parts, Body::from(body));
+    request.extensions_mut().insert(node.clone());
+
+    let start = Instant::now();
+    let response = next.run(request).await;
+    (node, start.elapsed(), response)
+}
+
+// Sends the request to the primary node and, if it does not respond in time,
+// also to the first spare node. The first response that does not need retrying wins.
+async fn send_hedged(
+    hedging: &Hedging,
+    next: &Next,
+    parts: &Parts,
+    body: &Bytes,
+    primary: Arc<Node>,
+    spare: &mut VecDeque<Arc<Node>>,
+) -> (Arc<Node>, Response) {
+    hedging.deposit();
+
+    let delay = hedging.delay(&primary);
+    let mut primary_fut = pin!(send_to_node(
+        next.clone(),
+        parts.clone(),
+        body.clone(),
+        primary
+    ));
+
+    let (node, latency, response) = tokio::select! {
+        r = &mut primary_fut => r,
+        _ = tokio::time::sleep(delay) => {
+            match spare.front().cloned() {
+                Some(node) if hedging.try_acquire() => {
+                    spare.pop_front();
+                    let mut hedge_fut =
+                        pin!(send_to_node(next.clone(), parts.clone(), body.clone(), node));
+
+                    tokio::select! {
+                        r = &mut primary_fut => {
+                            if request_needs_retrying(&r.2) { hedge_fut.await } else {
+                                hedging.observe(OUTCOME_PRIMARY);
+                                r
+                            }
+                        }
+                        r = &mut hedge_fut => {
+                            if request_needs_retrying(&r.2) { primary_fut.await } else {
+                                hedging.observe(OUTCOME_HEDGE);
+                                r
+                            }
+                        }
+                    }
+                }
+                _ => primary_fut.await,
+            }
+        }
+    };
+
+    if !request_needs_retrying(&response) {
+        hedging.record(&node, latency);
+    }
+
+    (node, response)
+}
+
 // Middleware that optionally retries the request according to the predefined conditions
 pub async fn retry_request(
     State(params): State<RetryParams>,
@@ -60,16 +142,36 @@ pub async fn retry_request(
     mut request: Request,
     next: Next,
 ) -> Result<impl IntoResponse, ApiError> {
-    // Select up to 1+retry_count nodes from the subnet if there are any
+    // Queries in agreement mode already fan out to several replicas below us,
+    // hedging them would multiply that load
+    let agreement = request
+        .extensions()
+        .get::<QueryAgreementRequest>()
+        .is_some();
+    let hedging = params
+        .hedging
+        .as_deref()
+        .filter(|_| Hedging::applies_to(ctx.request_type) && !agreement);
+
+    // Hedging needs at least one more node besides the primary one
+    let count = if hedging.is_some() {
+        1 + params.retry_count.max(1)
+    } else {
+        1 + params.retry_count
+    };
+
+    // Select up to `count` nodes from the subnet if there are any
     let nodes = if !params.disable_latency_routing && (ctx.request_type.is_call()) {
         let factor = subnet.fault_tolerance_factor() + 1;
-        subnet.pick_n_out_of_m_closest(1 + params.retry_count, factor)?
+        subnet.pick_n_out_of_m_closest(count, factor)?
     } else {
-        subnet.pick_random_nodes(1 + params.retry_count)?
+        subnet.pick_random_nodes(count)?
     };
 
     // Skip retrying in certain cases
-    if params.retry_count == 0 || (ctx.request_type.is_call() && !params.retry_update_call) {
+    if (params.retry_count == 0 && hedging.is_none())
+        || (ctx.request_type.is_call() && !params.retry_update_call)
+    {
         // Pick one node and pass the request down the stack
         // At this point there would be at least one node in the vector
         let node = nodes[0].clone();
@@ -91,10 +193,16 @@ pub async fn retry_request(
     // And it cannot fail since it's already in-memory.
     let body = to_bytes(body, usize::MAX).await.unwrap();
 
-    for node in nodes.into_iter() {
-        let mut request = Request::from_parts(parts.clone(), Body::from(body.clone()));
-        request.extensions_mut().insert(node.clone());
-        let mut response = next.clone().run(request).await;
+    let mut nodes = VecDeque::from(nodes);
+    while let Some(node) = nodes.pop_front() {
+        let (node, mut response) = match hedging {
+            Some(hedging) => send_hedged(hedging, &next, &parts, &body, node, &mut nodes).await,
+            None => {
+                let mut request = Request::from_parts(parts.clone(), Body::from(body.clone()));
+                request.extensions_mut().insert(node.clone());
+                (node, next.clone().run(request).await)
+            }
+        };
 
         // Stop if the request does not need retrying
         if !request_needs_retrying(&response) {
@@ -110,6 +218,11 @@ pub async fn retry_request(
         response_last = Some(response);
         node_last = Some(node);
         retry_result.retries += 1;
+
+        // Hedging might have picked an extra node, don't use it for retries
+        if retry_result.retries > params.retry_count {
+            break;
+        }
     }
 
     // Return the last response if all retries failed
@@ -126,7 +239,10 @@ mod test {
 
     use std::{
         str::FromStr,
-        sync::{Arc, RwLock},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, RwLock,
+        },
     };
 
     use anyhow::Error;
@@ -139,7 +255,10 @@ mod test {
     use ic_types::CanisterId;
     use tower::Service;
 
-    use crate::routes::{test::test_route_subnet, RequestType};
+    use crate::{
+        http::middleware::hedge::HedgingMetrics,
+        routes::{test::test_route_subnet, RequestType},
+    };
 
     struct TestState {
         failures: u8,
@@ -202,6 +321,7 @@ mod test {
                     retry_count: 3,
                     retry_update_call: false,
                     disable_latency_routing: true,
+                    hedging: None,
                 },
                 retry_request,
             ));
@@ -268,6 +388,7 @@ mod test {
                     retry_count: 3,
                     retry_update_call: true,
                     disable_latency_routing: true,
+                    hedging: None,
                 },
                 retry_request,
             ));
@@ -283,4 +404,66 @@ mod test {
 
         Ok(())
     }
+
+    // The first request hangs, the following ones are answered immediately
+    async fn slow_handler(State(calls): State<Arc<AtomicUsize>>) -> impl IntoResponse {
+        if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+            tokio::time::sleep(Duration::from_secs(2)).await;
+        }
+
+        "foobar".into_response()
+    }
+
+    #[tokio::test]
+    async fn test_hedging() -> Result<(), Error> {
+        // Time advances only as far as the pending timers require
+        tokio::time::pause();
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let hedging = Arc::new(Hedging::new(
+            0.9,
+            Duration::from_millis(50),
+            0.1,
+            HedgingMetrics::new(&prometheus::Registry::new()),
+        ));
+
+        let mut app = Router::new()
+            .route("/", post(slow_handler).with_state(Arc::clone(&calls)))
+            .layer(middleware::from_fn_with_state(
+                RetryParams {
+                    retry_count: 0,
+                    retry_update_call: false,
+                    disable_latency_routing: true,
+                    hedging: Some(hedging),
+                },
+                retry_request,
+            ));
+
+        // The hedged request wins
+        let start = tokio::time::Instant::now();
+        let res = app.call(gen_request(RequestType::Query)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Calls are never hedged
+        calls.store(0, Ordering::SeqCst);
+        let start = tokio::time::Instant::now();
+        let res = app.call(gen_request(RequestType::Call)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(start.elapsed() >= Duration::from_secs(2));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Queries in agreement mode are never hedged
+        calls.store(0, Ordering::SeqCst);
+        let mut req = gen_request(RequestType::Query);
+        req.extensions_mut().insert(QueryAgreementRequest);
+        let start = tokio::time::Instant::now();
+        let res = app.call(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(start.elapsed() >= Duration::from_secs(2));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        Ok(())
+    }
 }
@@ -13,6 +13,7 @@ pub const PATH_CALL_V3: &str = "/api/v3/canister/{canister_id}/call";
 pub const PATH_READ_STATE: &str = "/api/v2/canister/{canister_id}/read_state";
 pub const PATH_SUBNET_READ_STATE: &str = "/api/v2/subnet/{subnet_id}/read_state";
 pub const PATH_HEALTH: &str = "/health";
+pub const PATH_HEALTH_NODES: &str = "/health/nodes";
 
 // Try to categorize the error that we got from Reqwest call
 pub fn error_infer(e: &impl std::error::Error) -> ErrorCause {
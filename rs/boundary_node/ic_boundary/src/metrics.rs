@@ -22,10 +22,10 @@ use ic_bn_lib::{
 };
 use ic_bn_lib::{
     prometheus::{
-        proto::MetricFamily, register_histogram_vec_with_registry,
-        register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry,
-        register_int_gauge_with_registry, Encoder, HistogramOpts, HistogramVec, IntCounterVec,
-        IntGauge, IntGaugeVec, Registry, TextEncoder,
+        proto::MetricFamily, register_gauge_vec_with_registry,
+        register_histogram_vec_with_registry, register_int_counter_vec_with_registry,
+        register_int_gauge_vec_with_registry, register_int_gauge_with_registry, Encoder, GaugeVec,
+        HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Registry, TextEncoder,
     },
     pubsub::Broker,
 };
@@ -230,6 +230,7 @@ pub struct WithMetricsPersist<T>(pub T, pub MetricParamsPersist);
 pub struct MetricParamsPersist {
     pub ranges: IntGauge,
     pub nodes: IntGauge,
+    pub health_score: GaugeVec,
 }
 
 impl MetricParamsPersist {
@@ -250,6 +251,15 @@ impl MetricParamsPersist {
                 registry
             )
             .unwrap(),
+
+            // Health score of published nodes
+            health_score: register_gauge_vec_with_registry!(
+                format!("persist_node_health_score"),
+                format!("Health score of the nodes currently published, from 0 to 1"),
+                &[NODE_ID_LABEL, SUBNET_ID_LABEL, "addr"],
+                registry
+            )
+            .unwrap(),
         }
     }
 }
@@ -4,7 +4,7 @@ use arc_swap::ArcSwapOption;
 use async_trait::async_trait;
 use candid::Principal;
 use ethnum::u256;
-use rand::seq::SliceRandom;
+use rand::Rng;
 use tracing::{debug, error};
 
 use crate::{
@@ -54,13 +54,35 @@ pub struct RouteSubnet {
     pub nodes: Vec<Arc<Node>>,
 }
 
+// Minimal routing weight of a node so that even the low-scored nodes can be picked e.g. for retries
+const MIN_ROUTING_WEIGHT: f64 = 0.01;
+
+// Picks up to n nodes without replacement with the probability proportional to their health scores.
+// This uses the Efraimidis-Spirakis algorithm: each node gets a random key u^(1/weight) and the nodes
+// with the largest keys are picked. The nodes are returned ordered by their keys, so the first one
+// should be used as a primary and the rest for retries.
+fn pick_weighted(nodes: &[Arc<Node>], n: usize) -> Vec<Arc<Node>> {
+    let mut rng = rand::thread_rng();
+
+    let mut keyed = nodes
+        .iter()
+        .map(|node| {
+            let weight = node.health_score.max(MIN_ROUTING_WEIGHT);
+            (rng.gen::<f64>().powf(1.0 / weight), node)
+        })
+        .collect::<Vec<_>>();
+
+    keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+    keyed
+        .into_iter()
+        .take(n)
+        .map(|(_, node)| node.clone())
+        .collect()
+}
+
 impl RouteSubnet {
     pub fn pick_random_nodes(&self, n: usize) -> Result<Vec<Arc<Node>>, ErrorCause> {
-        let nodes = self
-            .nodes
-            .choose_multiple(&mut rand::thread_rng(), n)
-            .cloned()
-            .collect::<Vec<_>>();
+        let nodes = pick_weighted(&self.nodes, n);
 
         if nodes.is_empty() {
             return Err(ErrorCause::NoHealthyNodes);
@@ -79,14 +101,9 @@ impl RouteSubnet {
         n: usize,
         m: usize,
     ) -> Result<Vec<Arc<Node>>, ErrorCause> {
-        // nodes should already be sorted by latency after persist() invocation
+        // nodes should already be sorted by health score after persist() invocation
         let m = std::cmp::min(m, self.nodes.len());
-        let nodes = &self.nodes[0..m];
-
-        let picked_nodes = nodes
-            .choose_multiple(&mut rand::thread_rng(), n)
-            .map(Arc::clone)
-            .collect::<Vec<_>>();
+        let picked_nodes = pick_weighted(&self.nodes[0..m], n);
 
         if picked_nodes.is_empty() {
             return Err(ErrorCause::NoHealthyNodes);
@@ -175,8 +192,12 @@ impl Persist for Persister {
             .into_iter()
             .flat_map(|subnet| {
                 let mut nodes = subnet.nodes;
-                // Sort nodes by latency before publishing to avoid sorting on each retry_request() call.
-                nodes.sort_by(|a, b| a.avg_latency_secs.partial_cmp(&b.avg_latency_secs).unwrap());
+                // Sort nodes by health score (and then by latency) before publishing to avoid sorting on each retry_request() call.
+                nodes.sort_by(|a, b| {
+                    b.health_score
+                        .total_cmp(&a.health_score)
+                        .then(a.avg_latency_secs.total_cmp(&b.avg_latency_secs))
+                });
 
                 subnet.ranges.into_iter().map(move |range| {
                     Arc::new(RouteSubnet {
@@ -225,8 +246,27 @@ impl Persist for Persister {
 #[async_trait]
 impl<T: Persist> Persist for WithMetricsPersist<T> {
     fn persist(&self, subnets: Vec<Subnet>) -> PersistStatus {
+        let scores = subnets
+            .iter()
+            .flat_map(|x| x.nodes.iter())
+            .map(|x| {
+                (
+                    [
+                        x.id.to_string(),
+                        x.subnet_id.to_string(),
+                        x.addr.to_string(),
+                    ],
+                    x.health_score,
+                )
+            })
+            .collect::<Vec<_>>();
+
         let out = self.0.persist(subnets);
-        let MetricParamsPersist { nodes, ranges } = &self.1;
+        let MetricParamsPersist {
+            nodes,
+            ranges,
+            health_score,
+        } = &self.1;
 
         match out {
             PersistStatus::SkippedEmpty => {
@@ -237,6 +277,13 @@ impl<T: Persist> Persist for WithMetricsPersist<T> {
                 nodes.set(s.nodes_new as i64);
                 ranges.set(s.ranges_new as i64);
 
+                // Nodes that are not published anymore are dropped from the gauge
+                health_score.reset();
+                for (labels, score) in &scores {
+                    let labels = labels.each_ref().map(String::as_str);
+                    health_score.with_label_values(&labels).set(*score);
+                }
+
                 debug!(
                     action = "persist",
                     "Lookup table published: subnet ranges: {:?} -> {:?}, nodes: {:?} -> {:?}",
@@ -271,7 +318,7 @@ pub(crate) mod test {
 
     use crate::{
         snapshot::{node_test_id, CanisterRange, Node, Subnet},
-        test_utils::valid_tls_certificate_and_validation_time,
+        test_utils::{test_node_signing_key, valid_tls_certificate_and_validation_time},
     };
 
     // Converts string principal to a u256
@@ -318,8 +365,9 @@ pub(crate) mod test {
     }
 
     pub fn node(i: u64, subnet_id: Principal) -> Arc<Node> {
+        let id = node_test_id(1001 + i).get().0;
         Arc::new(Node {
-            id: node_test_id(1001 + i).get().0,
+            id,
             subnet_id,
             subnet_type: SubnetType::Application,
             addr: IpAddr::V4(Ipv4Addr::new(192, 168, 0, i as u8)),
@@ -327,7 +375,12 @@ pub(crate) mod test {
             tls_certificate: valid_tls_certificate_and_validation_time()
                 .0
                 .certificate_der,
+            signing_public_key: test_node_signing_key(id.as_slice())
+                .public_key()
+                .serialize_raw()
+                .to_vec(),
             avg_latency_secs: f64::MAX,
+            health_score: 0.0,
         })
     }
 
@@ -487,6 +540,63 @@ pub(crate) mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_pick_weighted() -> Result<(), Error> {
+        let subnet_id = principal!("f7crg-kabae");
+        let scored = |i, score| {
+            let mut node = (*node(i, subnet_id)).clone();
+            node.health_score = score;
+            Arc::new(node)
+        };
+
+        let subnet = Subnet {
+            id: subnet_id,
+            subnet_type: SubnetType::Application,
+            ranges: vec![CanisterRange {
+                start: principal!("f7crg-kabae"),
+                end: principal!("sxiki-5ygae-aq"),
+            }],
+            nodes: vec![scored(1, 0.0), scored(2, 0.5), scored(3, 1.0)],
+            replica_version: "7742d96ddd30aa6b607c9d2d4093a7b714f5b25b".to_string(),
+        };
+
+        let rt = Arc::new(ArcSwapOption::empty());
+        let persister = Persister::new(Arc::clone(&rt));
+        persister.persist(vec![subnet]);
+        let rt = rt.load_full().unwrap();
+        let subnet = rt.lookup_by_id(subnet_id).unwrap();
+
+        // Nodes are sorted by the score
+        let ids = subnet.nodes.iter().map(|x| x.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![
+                node(3, subnet_id).id,
+                node(2, subnet_id).id,
+                node(1, subnet_id).id
+            ]
+        );
+
+        // Only the best node is considered
+        for _ in 0..100 {
+            let nodes = subnet.pick_n_out_of_m_closest(1, 1)?;
+            assert_eq!(nodes[0].id, node(3, subnet_id).id);
+        }
+
+        // All nodes are returned, but the low-scored one is rarely the primary
+        let mut primary_worst = 0;
+        for _ in 0..1000 {
+            let nodes = subnet.pick_random_nodes(3)?;
+            assert_eq!(nodes.len(), 3);
+            if nodes[0].id == node(1, subnet_id).id {
+                primary_worst += 1;
+            }
+        }
+        assert!(primary_worst < 100);
+
+        Ok(())
+    }
+
     #[test]
     fn test_lookup() -> Result<(), Error> {
         let r = generate_test_routes(0);
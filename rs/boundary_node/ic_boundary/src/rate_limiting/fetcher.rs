@@ -267,7 +267,9 @@ mod test {
                     )),
                     methods_regex: Some(Regex::new("^foo|bar$").unwrap()),
                     request_types: None,
+                    caller_type: None,
                     ip_prefix_group: None,
+                    burst: None,
                     ip: None,
                     limit: v1::Action::Block,
                 },
@@ -278,7 +280,9 @@ mod test {
                     )),
                     methods_regex: Some(Regex::new("^baz|bax$").unwrap()),
                     request_types: None,
+                    caller_type: None,
                     ip_prefix_group: None,
+                    burst: None,
                     ip: None,
                     limit: v1::Action::Limit(1, Duration::from_secs(10)),
                 },
@@ -287,7 +291,9 @@ mod test {
                     subnet_id: None,
                     methods_regex: Some(Regex::new("^foo|bax$").unwrap()),
                     request_types: None,
+                    caller_type: None,
                     ip_prefix_group: None,
+                    burst: None,
                     ip: None,
                     limit: v1::Action::Limit(10, Duration::from_secs(60)),
                 }
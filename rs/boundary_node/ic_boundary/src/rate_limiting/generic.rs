@@ -27,7 +27,9 @@ use ic_bn_lib::prometheus::{
 use ic_bn_lib::{http::ConnInfo, tasks::Run};
 use ic_types::CanisterId;
 use ipnet::IpNet;
-use rate_limits_api::v1::{Action, IpPrefixes, RateLimitRule, RequestType as RequestTypeRule};
+use rate_limits_api::v1::{
+    Action, CallerType, IpPrefixes, RateLimitRule, RequestType as RequestTypeRule,
+};
 use ratelimit::Ratelimiter;
 use strum::{Display, IntoStaticStr};
 #[allow(clippy::disallowed_types)]
@@ -70,11 +72,23 @@ enum Decision {
     Limit,
 }
 
+// Determines the caller type from the request's sender, if there's one
+fn caller_type(ctx: &RequestContext) -> Option<CallerType> {
+    ctx.is_anonymous().map(|x| {
+        if x {
+            CallerType::Anonymous
+        } else {
+            CallerType::Authenticated
+        }
+    })
+}
+
 pub struct Context<'a> {
     subnet_id: Principal,
     canister_id: Option<Principal>,
     method: Option<&'a str>,
     request_type: RequestType,
+    caller_type: Option<CallerType>,
     ip: IpAddr,
 }
 
@@ -129,6 +143,12 @@ impl Bucket {
             }
         }
 
+        if let Some(v) = self.rule.caller_type {
+            if ctx.caller_type != Some(v) {
+                return None;
+            }
+        }
+
         if let Some(v) = self.rule.ip {
             if !v.contains(&ctx.ip) {
                 return None;
@@ -307,11 +327,12 @@ impl GenericLimiter {
             .into_iter()
             .enumerate()
             .map(|(idx, mut rule)| {
-                // Scale the rule limit accordingly
+                // Scale the rule limit & burst accordingly
                 if let Action::Limit(n, d) = rule.limit {
                     // Make sure the limit doesn't go below 1
                     let limit = (n / scale).max(1);
                     rule.limit = Action::Limit(limit, d);
+                    rule.burst = rule.burst.map(|x| (x / scale).max(1));
                 }
 
                 // Check if the same rule exists in the same position.
@@ -323,11 +344,14 @@ impl GenericLimiter {
                 }
 
                 let limiter = if let Action::Limit(limit, duration) = &rule.limit {
+                    // Token bucket capacity defaults to the limit
+                    let burst = rule.burst.unwrap_or(*limit);
+
                     Some(if let Some(v) = &rule.ip_prefix_group {
                         Limiter::Sharded(
                             Arc::new(ShardedRatelimiter::new(
                                 *limit,
-                                *limit,
+                                burst,
                                 *duration,
                                 self.opts.tti,
                                 self.opts.max_shards,
@@ -335,7 +359,7 @@ impl GenericLimiter {
                             *v,
                         )
                     } else {
-                        Limiter::Single(Arc::new(create_ratelimiter(*limit, *limit, *duration)))
+                        Limiter::Single(Arc::new(create_ratelimiter(*limit, burst, *duration)))
                     })
                 } else {
                     None
@@ -480,6 +504,7 @@ pub async fn middleware(
         canister_id: canister_id.map(|x| x.get().into()),
         method: ctx.method_name.as_deref(),
         request_type: ctx.request_type,
+        caller_type: caller_type(&ctx),
         ip: conn_info.remote_addr.ip(),
     };
 
@@ -526,6 +551,83 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_ratelimit_caller_type_and_burst() {
+        let ip1 = IpAddr::from_str("10.0.0.1").unwrap();
+        let subnet_id =
+            principal!("3hhby-wmtmw-umt4t-7ieyg-bbiig-xiylg-sblrt-voxgt-bqckd-a75bf-rqe");
+        let id1 = principal!("aaaaa-aa");
+        let id2 = principal!("5s2ji-faaaa-aaaaa-qaaaq-cai");
+
+        let rules = indoc! {"
+        - canister_id: aaaaa-aa
+          caller_type: anonymous
+          limit: block
+
+        - canister_id: 5s2ji-faaaa-aaaaa-qaaaq-cai
+          methods_regex: ^foo$
+          burst: 20
+          limit: 1/1h
+        "};
+
+        let rules: Vec<RateLimitRule> = serde_yaml::from_str(rules).unwrap();
+        let opts = Options {
+            tti: Duration::from_secs(10),
+            max_shards: 10000,
+            poll_interval: Duration::from_secs(30),
+            autoscale: true,
+        };
+
+        let (_, rx) = watch::channel(None);
+        let limiter = GenericLimiter::new_with_fetcher(
+            Arc::new(TestFetcher(rules.clone())),
+            opts,
+            rx,
+            &Registry::new(),
+        );
+        assert!(limiter.refresh().await.is_ok());
+
+        let ctx = |canister_id, method, caller_type| Context {
+            subnet_id,
+            canister_id: Some(canister_id),
+            method: Some(method),
+            request_type: RequestType::Call,
+            caller_type,
+            ip: ip1,
+        };
+
+        // Only anonymous callers are blocked
+        assert_eq!(
+            limiter.evaluate(ctx(id1, "foo", Some(CallerType::Anonymous))),
+            Decision::Block
+        );
+        assert_eq!(
+            limiter.evaluate(ctx(id1, "foo", Some(CallerType::Authenticated))),
+            Decision::Pass
+        );
+        // Requests without a sender don't match the caller type
+        assert_eq!(limiter.evaluate(ctx(id1, "foo", None)), Decision::Pass);
+
+        // The bucket holds 20 tokens even though it's refilled with 1 token per hour
+        for _ in 0..20 {
+            assert_eq!(
+                limiter.evaluate(ctx(id2, "foo", Some(CallerType::Authenticated))),
+                Decision::Pass
+            );
+        }
+        assert_eq!(
+            limiter.evaluate(ctx(id2, "foo", Some(CallerType::Anonymous))),
+            Decision::Limit
+        );
+
+        // Burst is scaled along with the limit
+        limiter.apply_rules(rules, 4);
+        for _ in 0..5 {
+            assert_eq!(limiter.evaluate(ctx(id2, "foo", None)), Decision::Pass);
+        }
+        assert_eq!(limiter.evaluate(ctx(id2, "foo", None)), Decision::Limit);
+    }
+
     #[tokio::test]
     async fn test_ratelimit() {
         let ip1 = IpAddr::from_str("10.0.0.1").unwrap();
@@ -606,6 +708,7 @@ mod test {
                     canister_id: Some(id1),
                     method: Some("foo"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Pass
@@ -620,6 +723,7 @@ mod test {
                     canister_id: Some(id1),
                     method: Some("bar"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Limit
@@ -664,6 +768,7 @@ mod test {
                     canister_id: Some(id0),
                     method: None,
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip_local4,
                 }),
                 Decision::Pass
@@ -676,6 +781,7 @@ mod test {
                     canister_id: Some(id0),
                     method: None,
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip_local6,
                 }),
                 Decision::Pass
@@ -691,6 +797,7 @@ mod test {
                     canister_id: Some(id1),
                     method: Some("foo"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Pass
@@ -705,6 +812,7 @@ mod test {
                     canister_id: Some(id1),
                     method: Some("bar"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Limit
@@ -721,6 +829,7 @@ mod test {
                     canister_id: Some(id2),
                     method: Some("foo"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Pass
@@ -735,6 +844,7 @@ mod test {
                     canister_id: Some(id2),
                     method: Some("bar"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Limit
@@ -748,6 +858,7 @@ mod test {
                     canister_id: Some(id2),
                     method: Some("lol"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Pass
@@ -760,6 +871,7 @@ mod test {
                     canister_id: Some(id2),
                     method: Some("rofl"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Pass
@@ -774,6 +886,7 @@ mod test {
                     canister_id: Some(id2),
                     method: Some("baz"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Block
@@ -789,6 +902,7 @@ mod test {
                     canister_id: Some(id3),
                     method: Some("rofl"),
                     request_type: RequestType::Call,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Pass
@@ -802,6 +916,7 @@ mod test {
                     canister_id: Some(id3),
                     method: Some("bar"),
                     request_type: RequestType::Call,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Limit
@@ -817,6 +932,7 @@ mod test {
                     canister_id: Some(id3),
                     method: Some("baz"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Pass
@@ -830,6 +946,7 @@ mod test {
                     canister_id: Some(id3),
                     method: Some("zob"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Limit
@@ -846,6 +963,7 @@ mod test {
                     canister_id: Some(id3),
                     method: None,
                     request_type: RequestType::ReadState,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Pass
@@ -859,6 +977,7 @@ mod test {
                     canister_id: Some(id3),
                     method: None,
                     request_type: RequestType::ReadState,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Limit
@@ -873,6 +992,7 @@ mod test {
                     canister_id: Some(id3),
                     method: None,
                     request_type: RequestType::ReadState,
+                    caller_type: None,
                     ip: ip2,
                 }),
                 Decision::Pass
@@ -886,6 +1006,7 @@ mod test {
                     canister_id: Some(id3),
                     method: None,
                     request_type: RequestType::ReadState,
+                    caller_type: None,
                     ip: ip2,
                 }),
                 Decision::Limit
@@ -903,6 +1024,7 @@ mod test {
                     canister_id: Some(id1),
                     method: Some("foo"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Pass
@@ -917,6 +1039,7 @@ mod test {
                     canister_id: Some(id1),
                     method: Some("bar"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Limit
@@ -934,6 +1057,7 @@ mod test {
                     canister_id: Some(id1),
                     method: Some("foo"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Pass
@@ -948,6 +1072,7 @@ mod test {
                     canister_id: Some(id1),
                     method: Some("bar"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Limit
@@ -964,6 +1089,7 @@ mod test {
                 canister_id: Some(id1),
                 method: Some("foo"),
                 request_type: RequestType::Query,
+                caller_type: None,
                 ip: ip1,
             }),
             Decision::Pass
@@ -977,6 +1103,7 @@ mod test {
                     canister_id: Some(id1),
                     method: Some("bar"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Limit
@@ -996,6 +1123,7 @@ mod test {
                     canister_id: Some(id1),
                     method: Some("foo"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Pass
@@ -1010,6 +1138,7 @@ mod test {
                     canister_id: Some(id1),
                     method: Some("bar"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Limit
@@ -1029,6 +1158,7 @@ mod test {
                     canister_id: Some(id1),
                     method: Some("foo"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Pass
@@ -1043,6 +1173,7 @@ mod test {
                     canister_id: Some(id1),
                     method: Some("bar"),
                     request_type: RequestType::Query,
+                    caller_type: None,
                     ip: ip1,
                 }),
                 Decision::Limit
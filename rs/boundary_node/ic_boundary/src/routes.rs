@@ -15,7 +15,7 @@ use candid::{CandidType, Principal};
 use ic_bn_lib::http::{proxy, Client as HttpClient};
 pub use ic_bn_lib::types::RequestType;
 use ic_types::{messages::ReplicaHealthStatus, CanisterId, SubnetId};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use url::Url;
 
 use crate::{
@@ -109,6 +109,17 @@ pub trait Lookup: Sync + Send {
 
 pub trait Health: Sync + Send {
     fn health(&self) -> ReplicaHealthStatus;
+    fn node_health(&self) -> Vec<NodeHealth>;
+}
+
+/// Health of a single node currently published in the routing table
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct NodeHealth {
+    pub node_id: String,
+    pub subnet_id: String,
+    pub addr: String,
+    pub health_score: f64,
+    pub avg_latency_secs: f64,
 }
 
 pub trait RootKey: Sync + Send {
@@ -223,6 +234,29 @@ impl Health for ProxyRouter {
             _ => ReplicaHealthStatus::Starting,
         }
     }
+
+    fn node_health(&self) -> Vec<NodeHealth> {
+        let Some(rt) = self.routing_table.load_full() else {
+            return vec![];
+        };
+
+        // Subnets can have several ranges, so use the map which has one entry per subnet
+        let mut nodes = rt
+            .subnet_map
+            .values()
+            .flat_map(|subnet| subnet.nodes.iter())
+            .map(|node| NodeHealth {
+                node_id: node.id.to_string(),
+                subnet_id: node.subnet_id.to_string(),
+                addr: node.addr.to_string(),
+                health_score: node.health_score,
+                avg_latency_secs: node.avg_latency_secs,
+            })
+            .collect::<Vec<_>>();
+
+        nodes.sort_by(|a, b| (&a.subnet_id, &a.node_id).cmp(&(&b.subnet_id, &b.node_id)));
+        nodes
+    }
 }
 
 // Middleware: looks up the target subnet in the routing table
@@ -282,8 +316,8 @@ pub(crate) mod test {
 
     use crate::{
         http::{
-            handlers::{health, status},
-            PATH_HEALTH, PATH_STATUS,
+            handlers::{health, health_nodes, status},
+            PATH_HEALTH, PATH_HEALTH_NODES, PATH_STATUS,
         },
         persist::{test::node, Persist, Persister},
         snapshot::{test::test_registry_snapshot, Node},
@@ -583,6 +617,51 @@ pub(crate) mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_health_nodes() -> Result<(), Error> {
+        let routing_table = Arc::new(ArcSwapOption::empty());
+        let registry_snapshot = Arc::new(ArcSwapOption::empty());
+
+        let persister = Persister::new(routing_table.clone());
+        let (snapshot, _, _) = test_registry_snapshot(5, 3);
+        registry_snapshot.store(Some(Arc::new(snapshot.clone())));
+
+        let http_client = Arc::new(TestHttpClient(1));
+        let proxy_router = Arc::new(ProxyRouter::new(
+            http_client,
+            routing_table,
+            registry_snapshot,
+            0.51,
+            0.6666,
+        ));
+
+        // Nothing is published yet
+        assert!(proxy_router.node_health().is_empty());
+
+        persister.persist(snapshot.subnets.clone());
+
+        let mut app = Router::new().route(
+            PATH_HEALTH_NODES,
+            get(health_nodes).with_state(proxy_router.clone() as Arc<dyn Health>),
+        );
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("http://localhost/health/nodes")
+            .body(Body::from(""))
+            .unwrap();
+
+        let resp = app.call(request).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX).await?;
+        let nodes: Vec<serde_json::Value> = serde_json::from_slice(&body)?;
+        assert_eq!(nodes.len(), 15);
+        assert!(nodes.iter().all(|x| x["health_score"].is_number()));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_all_call_types() -> Result<(), Error> {
         let (mut app, subnets) = setup_test_router(false, false, 10, 1, 1024, None);
@@ -23,7 +23,7 @@ use ic_registry_client_helpers::{
 };
 use ic_registry_replicator::RegistryReplicator;
 use ic_registry_subnet_type::SubnetType;
-use ic_types::{NodeId, PrincipalId, RegistryVersion, SubnetId};
+use ic_types::{crypto::KeyPurpose, NodeId, PrincipalId, RegistryVersion, SubnetId};
 use tokio::{select, sync::watch};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, warn};
@@ -44,7 +44,11 @@ pub struct Node {
     pub addr: IpAddr,
     pub port: u16,
     pub tls_certificate: Vec<u8>,
+    // Raw Ed25519 public key the node signs query responses with
+    pub signing_public_key: Vec<u8>,
     pub avg_latency_secs: f64,
+    // Continuous health score in 0..=1 range, higher is better
+    pub health_score: f64,
 }
 
 // Lightweight Eq, just compare principals
@@ -322,9 +326,16 @@ impl Snapshotter {
                         X509Certificate::from_der(cert.certificate_der.as_slice())
                             .context("Unable to parse TLS certificate")?;
 
+                        let signing_key = self
+                            .registry_client
+                            .get_crypto_key_for_node(node_id, KeyPurpose::NodeSigning, version)
+                            .context("failed to get node signing key")? // Result
+                            .context("node signing key not available")?; // Option
+
                         let node = Node {
                             // init to max, this value is updated with running health checks
                             avg_latency_secs: f64::MAX,
+                            health_score: 0.0,
                             id: node_id.as_ref().0,
                             subnet_id: subnet_id.as_ref().0,
                             subnet_type,
@@ -332,6 +343,7 @@ impl Snapshotter {
                                 .context("unable to parse IP address")?,
                             port: http_endpoint.port as u16, // Port is u16 anyway
                             tls_certificate: cert.certificate_der,
+                            signing_public_key: signing_key.key_value,
                         };
                         let node = Arc::new(node);
 
@@ -537,12 +549,14 @@ pub fn generate_stub_subnet(nodes: Vec<SocketAddr>) -> Subnet {
             Arc::new(Node {
                 // init to max, this value is updated with running health checks
                 avg_latency_secs: f64::MAX,
+                health_score: 0.0,
                 id: node_test_id(i as u64).get().0,
                 subnet_type: SubnetType::Application,
                 subnet_id,
                 addr: x.ip(),
                 port: x.port(),
                 tls_certificate: vec![],
+                signing_public_key: vec![],
             })
         })
         .collect::<Vec<_>>();
@@ -566,7 +580,8 @@ pub fn generate_stub_subnet(nodes: Vec<SocketAddr>) -> Subnet {
 pub(crate) mod test {
     use super::*;
     use crate::test_utils::{
-        create_fake_registry_client, valid_tls_certificate_and_validation_time,
+        create_fake_registry_client, test_node_signing_key,
+        valid_tls_certificate_and_validation_time,
     };
     use ic_registry_routing_table::CanisterIdRange;
 
@@ -624,6 +639,12 @@ pub(crate) mod test {
                     .0
                     .certificate_der,
             );
+            assert_eq!(
+                sn.nodes[0].signing_public_key,
+                test_node_signing_key(nodes[i].0.get().as_slice())
+                    .public_key()
+                    .serialize_raw(),
+            );
         }
 
         Ok(())
@@ -26,6 +26,23 @@ pub enum RequestType {
     ReadStateSubnet,
 }
 
+/// Class of the principal that has sent the request
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CallerType {
+    Anonymous,
+    Authenticated,
+}
+
+impl fmt::Display for CallerType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Anonymous => write!(f, "anonymous"),
+            Self::Authenticated => write!(f, "authenticated"),
+        }
+    }
+}
+
 /// Implement serde parser for Action
 struct ActionVisitor;
 impl de::Visitor<'_> for ActionVisitor {
@@ -141,7 +158,9 @@ impl std::fmt::Display for IpPrefixes {
     }
 }
 
-/// Defines the rate-limit rule to be stored in the canister
+/// Defines the rate-limit rule to be stored in the canister.
+/// The whole rule is stored in the canister as `rule_raw` and is thus
+/// kept confidential until the rule is disclosed.
 #[derive(Clone, Deserialize, Serialize, Debug, Default)]
 #[serde(remote = "Self")]
 pub struct RateLimitRule {
@@ -151,7 +170,14 @@ pub struct RateLimitRule {
     pub methods_regex: Option<Regex>,
     pub ip: Option<IpNet>,
     pub request_types: Option<Vec<RequestType>>,
+    /// Matches only the requests sent by the given class of principals
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caller_type: Option<CallerType>,
     pub ip_prefix_group: Option<IpPrefixes>,
+    /// Capacity of the token bucket, defaults to the count of the limit.
+    /// Like the limit itself it's shared across the boundary nodes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst: Option<u32>,
     pub limit: Action,
 }
 
@@ -161,10 +187,12 @@ impl PartialEq for RateLimitRule {
         self.methods_regex.as_ref().map(|x| x.as_str())
             == other.methods_regex.as_ref().map(|x| x.as_str())
             && self.request_types == other.request_types
+            && self.caller_type == other.caller_type
             && self.canister_id == other.canister_id
             && self.subnet_id == other.subnet_id
             && self.ip == other.ip
             && self.ip_prefix_group == other.ip_prefix_group
+            && self.burst == other.burst
             && self.limit == other.limit
     }
 }
@@ -183,10 +211,23 @@ impl<'de> Deserialize<'de> for RateLimitRule {
             ));
         }
 
+        if let Some(v) = this.burst {
+            if !matches!(this.limit, Action::Limit(_, _)) {
+                return Err(D::Error::custom(
+                    "burst only makes sense with 'limit' set to an actual ratelimit",
+                ));
+            }
+
+            if v == 0 {
+                return Err(D::Error::custom("burst should be > 0"));
+            }
+        }
+
         if this.canister_id.is_none()
             && this.subnet_id.is_none()
             && this.methods_regex.is_none()
             && this.request_types.is_none()
+            && this.caller_type.is_none()
             && this.ip.is_none()
         {
             return Err(D::Error::custom(
@@ -211,13 +252,15 @@ impl std::fmt::Display for RateLimitRule {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "CanisterID: {}, SubnetID: {}, Request Types: {:?}, Methods: {}, IP: {}, IP Prefix: {}, Limit: {}",
+            "CanisterID: {}, SubnetID: {}, Request Types: {:?}, Methods: {}, Caller: {}, IP: {}, IP Prefix: {}, Burst: {}, Limit: {}",
             format_option(&self.canister_id),
             format_option(&self.subnet_id),
             self.request_types,
             format_option(&self.methods_regex),
+            format_option(&self.caller_type),
             format_option(&self.ip),
             format_option(&self.ip_prefix_group),
+            format_option(&self.burst),
             self.limit,
         )
     }
@@ -296,11 +339,13 @@ mod test {
                 subnet_id: None,
                 methods_regex: Some(Regex::new("^.*$").unwrap()),
                 request_types: None,
+                caller_type: None,
                 ip: Some(IpNet::new_assert(
                     IpAddr::V4(Ipv4Addr::new(10, 1, 1, 0)),
                     24
                 )),
                 ip_prefix_group: Some(IpPrefixes { v4: 24, v6: 64 }),
+                burst: None,
                 limit: Action::Limit(100, Duration::from_secs(1)),
             }
         );
@@ -411,21 +456,25 @@ mod test {
                     subnet_id: None,
                     canister_id: Some(Principal::from_text("aaaaa-aa").unwrap()),
                     request_types: None,
+                    caller_type: None,
                     methods_regex: Some(Regex::new("^.*$").unwrap()),
                     ip: Some(IpNet::new_assert(
                         IpAddr::V6(Ipv6Addr::from_str("2001:db8::").unwrap()),
                         32
                     )),
                     ip_prefix_group: None,
+                    burst: None,
                     limit: Action::Limit(100, Duration::from_secs(1)),
                 },
                 RateLimitRule {
                     subnet_id: None,
                     canister_id: Some(Principal::from_text("5s2ji-faaaa-aaaaa-qaaaq-cai").unwrap()),
                     request_types: None,
+                    caller_type: None,
                     methods_regex: Some(Regex::new("^(foo|bar)$").unwrap()),
                     ip: None,
                     ip_prefix_group: None,
+                    burst: None,
                     limit: Action::Limit(60, Duration::from_secs(60)),
                 },
                 RateLimitRule {
@@ -437,45 +486,55 @@ mod test {
                     ),
                     canister_id: Some(Principal::from_text("5s2ji-faaaa-aaaaa-qaaaq-cai").unwrap()),
                     request_types: None,
+                    caller_type: None,
                     methods_regex: None,
                     ip: None,
                     ip_prefix_group: None,
+                    burst: None,
                     limit: Action::Limit(90, Duration::from_secs(60)),
                 },
                 RateLimitRule {
                     subnet_id: None,
                     canister_id: Some(Principal::from_text("5s2ji-faaaa-aaaaa-qaaaq-cai").unwrap()),
                     request_types: None,
+                    caller_type: None,
                     methods_regex: Some(Regex::new("^(foo|bar)$").unwrap()),
                     ip: None,
                     ip_prefix_group: None,
+                    burst: None,
                     limit: Action::Block,
                 },
                 RateLimitRule {
                     subnet_id: None,
                     canister_id: Some(Principal::from_text("5s2ji-faaaa-aaaaa-qaaaq-cai").unwrap()),
                     request_types: Some(vec![RequestType::Query]),
+                    caller_type: None,
                     methods_regex: Some(Regex::new("^(foo|bar)$").unwrap()),
                     ip: None,
                     ip_prefix_group: None,
+                    burst: None,
                     limit: Action::Block,
                 },
                 RateLimitRule {
                     subnet_id: None,
                     canister_id: Some(Principal::from_text("5s2ji-faaaa-aaaaa-qaaaq-cai").unwrap()),
                     request_types: Some(vec![RequestType::Call, RequestType::SyncCall]),
+                    caller_type: None,
                     methods_regex: None,
                     ip: None,
                     ip_prefix_group: None,
+                    burst: None,
                     limit: Action::Block,
                 },
                 RateLimitRule {
                     subnet_id: None,
                     canister_id: Some(Principal::from_text("5s2ji-faaaa-aaaaa-qaaaq-cai").unwrap()),
                     request_types: Some(vec![RequestType::Call, RequestType::SyncCall]),
+                    caller_type: None,
                     methods_regex: None,
                     ip: None,
                     ip_prefix_group: None,
+                    burst: None,
                     limit: Action::Pass,
                 },
             ],
@@ -550,4 +609,80 @@ mod test {
         let rules = serde_yaml::from_str::<Vec<RateLimitRule>>(rules);
         assert!(rules.unwrap_err().to_string().contains("request_type"));
     }
+
+    #[test]
+    fn test_rules_caller_type_and_burst() {
+        let rule_raw = indoc! {"
+        methods_regex: ^(foo|bar)$
+        caller_type: anonymous
+        burst: 500
+        limit: 100/1s
+        "};
+
+        let rule = RateLimitRule::from_bytes_yaml(rule_raw.as_bytes()).unwrap();
+        assert_eq!(
+            rule,
+            RateLimitRule {
+                methods_regex: Some(Regex::new("^(foo|bar)$").unwrap()),
+                caller_type: Some(CallerType::Anonymous),
+                burst: Some(500),
+                limit: Action::Limit(100, Duration::from_secs(1)),
+                ..Default::default()
+            }
+        );
+
+        // Roundtrip
+        let json = rule.to_bytes_json().unwrap();
+        assert_eq!(RateLimitRule::from_bytes_json(&json).unwrap(), rule);
+
+        // Rules without new fields are serialized as before
+        let rule = RateLimitRule {
+            canister_id: Some(Principal::from_text("aaaaa-aa").unwrap()),
+            limit: Action::Block,
+            ..Default::default()
+        };
+        let json = String::from_utf8(rule.to_bytes_json().unwrap()).unwrap();
+        assert!(!json.contains("caller_type"));
+        assert!(!json.contains("burst"));
+
+        // Caller type alone is a valid condition
+        let rule_raw = indoc! {"
+        caller_type: authenticated
+        limit: block
+        "};
+        let rule = RateLimitRule::from_bytes_yaml(rule_raw.as_bytes()).unwrap();
+        assert_eq!(rule.caller_type, Some(CallerType::Authenticated));
+
+        // Bad caller type
+        let rule_raw = indoc! {"
+        caller_type: nobody
+        limit: block
+        "};
+        assert!(RateLimitRule::from_bytes_yaml(rule_raw.as_bytes())
+            .unwrap_err()
+            .to_string()
+            .contains("caller_type"));
+
+        // Burst without an actual limit
+        let rule_raw = indoc! {"
+        canister_id: aaaaa-aa
+        burst: 10
+        limit: block
+        "};
+        assert!(RateLimitRule::from_bytes_yaml(rule_raw.as_bytes())
+            .unwrap_err()
+            .to_string()
+            .contains("burst only makes sense with"));
+
+        // Zero burst
+        let rule_raw = indoc! {"
+        canister_id: aaaaa-aa
+        burst: 0
+        limit: 10/1s
+        "};
+        assert!(RateLimitRule::from_bytes_yaml(rule_raw.as_bytes())
+            .unwrap_err()
+            .to_string()
+            .contains("burst should be > 0"));
+    }
 }
@@ -5,6 +5,7 @@ use ic_consensus_utils::{
 };
 use ic_interfaces::{
     certification::{CertificationPool, ChangeAction, Mutations, Verifier, VerifierError},
+    consensus::{BlockLifecycleStage, BlockLifecycleTracer},
     consensus_pool::ConsensusPoolCache,
     p2p::consensus::{Bouncer, BouncerFactory, BouncerValue, PoolMutationsProducer},
     validation::ValidationError,
@@ -48,6 +49,7 @@ pub struct CertifierImpl {
     /// The highest height that has been purged. Used to avoid redundant purging.
     highest_purged_height: RefCell<Height>,
     max_certified_height_tx: watch::Sender<Height>,
+    block_lifecycle_tracer: Arc<dyn BlockLifecycleTracer>,
     log: ReplicaLogger,
 }
 
@@ -144,11 +146,13 @@ impl<T: CertificationPool> PoolMutationsProducer<T> for CertifierImpl {
             .state_manager
             .list_state_hashes_to_certify()
             .into_iter()
-            .filter_map(
-                |(height, hash)| match certification_pool.certification_at_height(height) {
+            .filter_map(|(height, hash)| {
+                match certification_pool.certification_at_height(height) {
                     // if we have a valid certification, deliver it to the state manager and skip
                     // the pair
                     Some(certification) => {
+                        self.block_lifecycle_tracer
+                            .record(height, BlockLifecycleStage::Certified);
                         // TODO[NET-1711]: Remove deliver_state_certification(), and include them in the
                         // change set for the artifact processor to handle.
                         self.state_manager
@@ -168,8 +172,8 @@ impl<T: CertificationPool> PoolMutationsProducer<T> for CertifierImpl {
                     }
                     // return this pair to be signed by the current replica
                     _ => Some((height, hash)),
-                },
-            )
+                }
+            })
             .collect();
         trace!(
             &self.log,
@@ -267,6 +271,7 @@ impl CertifierImpl {
         metrics_registry: MetricsRegistry,
         log: ReplicaLogger,
         max_certified_height_tx: watch::Sender<Height>,
+        block_lifecycle_tracer: Arc<dyn BlockLifecycleTracer>,
     ) -> Self {
         let membership = Arc::new(Membership::new(
             consensus_pool_cache.clone(),
@@ -302,6 +307,7 @@ impl CertifierImpl {
             log,
             highest_purged_height: RefCell::new(Height::from(1)),
             max_certified_height_tx,
+            block_lifecycle_tracer,
         }
     }
 
@@ -593,9 +599,11 @@ mod tests {
     use super::*;
     use ic_artifact_pool::certification_pool::CertificationPoolImpl;
     use ic_consensus_mocks::{dependencies, Dependencies};
+    use ic_consensus_utils::block_lifecycle::BlockLifecycleTrace;
     use ic_interfaces::{
         certification::CertificationPool,
         p2p::consensus::{MutablePool, UnvalidatedArtifact},
+        time_source::SysTimeSource,
     };
     use ic_test_utilities_consensus::fake::*;
     use ic_test_utilities_logger::with_test_replica_logger;
@@ -718,6 +726,7 @@ mod tests {
                     metrics_registry.clone(),
                     log,
                     max_certified_height_tx,
+                    Arc::new(BlockLifecycleTrace::new(Arc::new(SysTimeSource::new()))),
                 );
                 let bouncer_factory = CertifierBouncer::new(&metrics_registry, pool.get_cache());
 
@@ -782,6 +791,7 @@ mod tests {
                     metrics_registry,
                     log,
                     max_certified_height_tx,
+                    Arc::new(BlockLifecycleTrace::new(Arc::new(SysTimeSource::new()))),
                 );
 
                 // generate a certifications for heights 1, 2 and 4
@@ -917,6 +927,7 @@ mod tests {
                     metrics_registry,
                     log,
                     max_certified_height_tx,
+                    Arc::new(BlockLifecycleTrace::new(Arc::new(SysTimeSource::new()))),
                 );
 
                 std::iter::empty()
@@ -996,6 +1007,7 @@ mod tests {
                     metrics_registry,
                     log,
                     max_certified_height_tx,
+                    Arc::new(BlockLifecycleTrace::new(Arc::new(SysTimeSource::new()))),
                 );
 
                 std::iter::empty()
@@ -1067,6 +1079,7 @@ mod tests {
                     metrics_registry,
                     log,
                     max_certified_height_tx,
+                    Arc::new(BlockLifecycleTrace::new(Arc::new(SysTimeSource::new()))),
                 );
 
                 let shares = certifier.sign(
@@ -1133,6 +1146,7 @@ mod tests {
                     metrics_registry.clone(),
                     log,
                     max_certified_height_tx,
+                    Arc::new(BlockLifecycleTrace::new(Arc::new(SysTimeSource::new()))),
                 );
                 let mut cert_pool = CertificationPoolImpl::new(
                     replica_config.node_id,
@@ -1199,6 +1213,7 @@ mod tests {
                     MetricsRegistry::new(),
                     log,
                     max_certified_height_tx,
+                    Arc::new(BlockLifecycleTrace::new(Arc::new(SysTimeSource::new()))),
                 );
 
                 let cert = if let CertificationMessage::Certification(cert) =
@@ -1260,6 +1275,7 @@ mod tests {
                     metrics_registry,
                     log,
                     max_certified_height_tx,
+                    Arc::new(BlockLifecycleTrace::new(Arc::new(SysTimeSource::new()))),
                 );
 
                 std::iter::empty()
@@ -1370,6 +1386,7 @@ mod tests {
                     metrics_registry,
                     log,
                     max_certified_height_tx,
+                    Arc::new(BlockLifecycleTrace::new(Arc::new(SysTimeSource::new()))),
                 );
 
                 // We expect deliver_state_certification() to be called 8 times since we call
@@ -35,6 +35,7 @@ use ic_consensus_utils::{
 };
 use ic_interfaces::{
     batch_payload::BatchPayloadBuilder,
+    consensus::{BlockLifecycleStage, BlockLifecycleTracer},
     consensus_pool::{
         ChangeAction, ConsensusPool, ConsensusPoolCache, Mutations, ValidatedConsensusArtifact,
     },
@@ -53,9 +54,12 @@ use ic_metrics::MetricsRegistry;
 use ic_registry_client_helpers::subnet::SubnetRegistry;
 use ic_replicated_state::ReplicatedState;
 use ic_types::{
-    artifact::ConsensusMessageId, consensus::ConsensusMessageHashable,
-    malicious_flags::MaliciousFlags, replica_config::ReplicaConfig,
-    replica_version::ReplicaVersion, Time,
+    artifact::ConsensusMessageId,
+    consensus::{ConsensusMessage, ConsensusMessageHashable, HasHeight},
+    malicious_flags::MaliciousFlags,
+    replica_config::ReplicaConfig,
+    replica_version::ReplicaVersion,
+    Time,
 };
 pub use metrics::ValidatorMetrics;
 use std::{
@@ -131,6 +135,7 @@ pub struct ConsensusImpl {
     registry_client: Arc<dyn RegistryClient>,
     state_manager: Arc<dyn StateManager<State = ReplicatedState>>,
     dkg_key_manager: Arc<Mutex<DkgKeyManager>>,
+    block_lifecycle_tracer: Arc<dyn BlockLifecycleTracer>,
     last_invoked: RefCell<BTreeMap<ConsensusSubcomponent, Time>>,
     schedule: RoundRobin,
     replica_config: ReplicaConfig,
@@ -162,6 +167,7 @@ impl ConsensusImpl {
         time_source: Arc<dyn TimeSource>,
         registry_poll_delay_duration_ms: u64,
         malicious_flags: MaliciousFlags,
+        block_lifecycle_tracer: Arc<dyn BlockLifecycleTracer>,
         metrics_registry: MetricsRegistry,
         logger: ReplicaLogger,
     ) -> Self {
@@ -207,6 +213,7 @@ impl ConsensusImpl {
                 membership.clone(),
                 crypto.clone(),
                 state_manager.clone(),
+                block_lifecycle_tracer.clone(),
                 metrics_registry.clone(),
                 logger.clone(),
             ),
@@ -217,6 +224,7 @@ impl ConsensusImpl {
                 crypto.clone(),
                 message_routing.clone(),
                 ingress_selector.clone(),
+                block_lifecycle_tracer.clone(),
                 logger.clone(),
                 metrics_registry.clone(),
             ),
@@ -252,6 +260,7 @@ impl ConsensusImpl {
                 idkg_pool.clone(),
                 state_manager.clone(),
                 stable_registry_version_age,
+                block_lifecycle_tracer.clone(),
                 metrics_registry.clone(),
                 logger.clone(),
             ),
@@ -264,6 +273,7 @@ impl ConsensusImpl {
                 state_manager.clone(),
                 message_routing.clone(),
                 dkg_pool,
+                block_lifecycle_tracer.clone(),
                 logger.clone(),
                 ValidatorMetrics::new(metrics_registry.clone()),
                 Arc::clone(&time_source),
@@ -289,6 +299,7 @@ impl ConsensusImpl {
             state_manager,
             malicious_flags,
             replica_config,
+            block_lifecycle_tracer,
             last_invoked: RefCell::new(last_invoked),
             schedule: RoundRobin::default(),
         }
@@ -332,6 +343,29 @@ impl ConsensusImpl {
         change_set
     }
 
+    /// Record the notarizations and finalizations that are added to the
+    /// validated pool by applying the given [`Mutations`], whether they were
+    /// aggregated locally or received and validated.
+    fn trace_block_lifecycle(&self, changeset: &Mutations) {
+        let tracer = &self.block_lifecycle_tracer;
+        for action in changeset {
+            let msg = match action {
+                ChangeAction::AddToValidated(artifact) => &artifact.msg,
+                ChangeAction::MoveToValidated(msg) => msg,
+                _ => continue,
+            };
+            match msg {
+                ConsensusMessage::Notarization(notarization) => {
+                    tracer.record(notarization.height(), BlockLifecycleStage::Notarized);
+                }
+                ConsensusMessage::Finalization(finalization) => {
+                    tracer.record(finalization.height(), BlockLifecycleStage::Finalized);
+                }
+                _ => {}
+            }
+        }
+    }
+
     /// check whether the subnet should halt because the subnet record in the
     /// latest registry version instructs the subnet to halt
     pub fn should_halt_by_subnet_record(&self) -> bool {
@@ -510,6 +544,7 @@ impl<T: ConsensusPool> PoolMutationsProducer<T> for ConsensusImpl {
         ];
 
         let changeset = self.schedule.call_next(&calls);
+        self.trace_block_lifecycle(&changeset);
 
         let settings = get_notarization_delay_settings(
             &self.log,
@@ -629,6 +664,7 @@ mod tests {
     use super::*;
     use ic_config::artifact_pool::ArtifactPoolConfig;
     use ic_consensus_mocks::{dependencies_with_subnet_params, Dependencies};
+    use ic_consensus_utils::block_lifecycle::BlockLifecycleTrace;
     use ic_https_outcalls_consensus::test_utils::FakeCanisterHttpPayloadBuilder;
     use ic_logger::replica_logger::no_op_logger;
     use ic_metrics::MetricsRegistry;
@@ -708,6 +744,7 @@ mod tests {
             time_source.clone(),
             0,
             MaliciousFlags::default(),
+            Arc::new(BlockLifecycleTrace::new(time_source.clone())),
             metrics_registry,
             no_op_logger(),
         );
@@ -19,6 +19,7 @@ use ic_error_types::RejectCode;
 use ic_https_outcalls_consensus::payload_builder::CanisterHttpPayloadBuilderImpl;
 use ic_interfaces::{
     batch_payload::IntoMessages,
+    consensus::{BlockLifecycleStage, BlockLifecycleTracer},
     messaging::{MessageRouting, MessageRoutingError},
 };
 use ic_interfaces_registry::RegistryClient;
@@ -46,6 +47,9 @@ use std::collections::BTreeMap;
 /// Deliver all finalized blocks from
 /// `message_routing.expected_batch_height` to `finalized_height` via
 /// `MessageRouting` and return the last delivered batch height.
+///
+/// If a `block_lifecycle_tracer` is given, the delivery of every delivered
+/// block is recorded in it.
 #[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn deliver_batches(
     message_routing: &dyn MessageRouting,
@@ -59,6 +63,7 @@ pub fn deliver_batches(
     // deliver all bathes up to the height `min(h, finalized_height)`.
     max_batch_height_to_deliver: Option<Height>,
     result_processor: Option<&dyn Fn(&Result<(), MessageRoutingError>, BlockStats, BatchStats)>,
+    block_lifecycle_tracer: Option<&dyn BlockLifecycleTracer>,
 ) -> Result<Height, MessageRoutingError> {
     let finalized_height = pool.get_finalized_height();
     // If `max_batch_height_to_deliver` is specified and smaller than
@@ -254,6 +259,9 @@ pub fn deliver_batches(
             warn!(every_n_seconds => 5, log, "Batch delivery failed: {:?}", err);
             return Err(err);
         }
+        if let Some(tracer) = block_lifecycle_tracer {
+            tracer.record(height, BlockLifecycleStage::Delivered);
+        }
         last_delivered_batch_height = height;
         height = height.increment();
     }
@@ -11,7 +11,10 @@ use ic_consensus_utils::{
     get_subnet_record, membership::Membership, pool_reader::PoolReader,
 };
 use ic_interfaces::{
-    consensus::PayloadBuilder, dkg::DkgPool, idkg::IDkgPool, time_source::TimeSource,
+    consensus::{BlockLifecycleStage, BlockLifecycleTracer, PayloadBuilder},
+    dkg::DkgPool,
+    idkg::IDkgPool,
+    time_source::TimeSource,
 };
 use ic_interfaces_registry::RegistryClient;
 use ic_interfaces_state_manager::StateManager;
@@ -70,6 +73,7 @@ pub struct BlockMaker {
     dkg_pool: Arc<RwLock<dyn DkgPool>>,
     idkg_pool: Arc<RwLock<dyn IDkgPool>>,
     pub(crate) state_manager: Arc<dyn StateManager<State = ReplicatedState>>,
+    block_lifecycle_tracer: Arc<dyn BlockLifecycleTracer>,
     metrics: BlockMakerMetrics,
     idkg_payload_metrics: IDkgPayloadMetrics,
     pub(crate) log: ReplicaLogger,
@@ -93,6 +97,7 @@ impl BlockMaker {
         idkg_pool: Arc<RwLock<dyn IDkgPool>>,
         state_manager: Arc<dyn StateManager<State = ReplicatedState>>,
         stable_registry_version_age: Duration,
+        block_lifecycle_tracer: Arc<dyn BlockLifecycleTracer>,
         metrics_registry: MetricsRegistry,
         log: ReplicaLogger,
     ) -> Self {
@@ -106,6 +111,7 @@ impl BlockMaker {
             dkg_pool,
             idkg_pool,
             state_manager,
+            block_lifecycle_tracer,
             log,
             metrics: BlockMakerMetrics::new(metrics_registry.clone()),
             idkg_payload_metrics: IDkgPayloadMetrics::new(metrics_registry),
@@ -138,8 +144,11 @@ impl BlockMaker {
                         Some(&self.metrics),
                     )
                 {
-                    self.propose_block(pool, rank, parent)
-                        .inspect(|block| self.log_block(block))
+                    self.propose_block(pool, rank, parent).inspect(|block| {
+                        self.log_block(block);
+                        self.block_lifecycle_tracer
+                            .record(height, BlockLifecycleStage::Proposed);
+                    })
                 } else {
                     None
                 }
@@ -602,6 +611,7 @@ pub(super) fn is_time_to_make_block(
 mod tests {
     use super::*;
     use ic_consensus_mocks::{dependencies_with_subnet_params, Dependencies, MockPayloadBuilder};
+    use ic_consensus_utils::block_lifecycle::BlockLifecycleTrace;
     use ic_interfaces::consensus_pool::ConsensusPool;
     use ic_logger::replica_logger::no_op_logger;
     use ic_metrics::MetricsRegistry;
@@ -672,6 +682,7 @@ mod tests {
                 idkg_pool.clone(),
                 state_manager.clone(),
                 Duration::from_millis(0),
+                Arc::new(BlockLifecycleTrace::new(time_source.clone())),
                 MetricsRegistry::new(),
                 no_op_logger(),
             );
@@ -742,6 +753,7 @@ mod tests {
                 subnet_id: replica_config.subnet_id,
             };
 
+            let block_lifecycle_tracer = Arc::new(BlockLifecycleTrace::new(time_source.clone()));
             let block_maker = BlockMaker::new(
                 Arc::clone(&time_source) as Arc<_>,
                 replica_config,
@@ -753,6 +765,7 @@ mod tests {
                 idkg_pool,
                 state_manager,
                 Duration::from_millis(0),
+                block_lifecycle_tracer.clone(),
                 MetricsRegistry::new(),
                 no_op_logger(),
             );
@@ -763,6 +776,7 @@ mod tests {
 
             // kick start another round
             assert!(run_block_maker().is_none());
+            assert!(block_lifecycle_tracer.records().is_empty());
 
             time_source.set_time(expected_time).unwrap();
             if let Some(proposal) = run_block_maker() {
@@ -770,6 +784,10 @@ mod tests {
             } else {
                 panic!("Expected a new block proposal");
             }
+            let records = block_lifecycle_tracer.records();
+            assert_eq!(records.len(), 1);
+            assert_eq!(records[0].height, next_height);
+            assert_eq!(records[0].proposed, Some(expected_time));
 
             // insert a rank 0 block for the current round
             let next_block = pool.make_next_block();
@@ -884,6 +902,7 @@ mod tests {
                 idkg_pool.clone(),
                 state_manager.clone(),
                 Duration::from_millis(0),
+                Arc::new(BlockLifecycleTrace::new(time_source.clone())),
                 MetricsRegistry::new(),
                 no_op_logger(),
             );
@@ -923,6 +942,7 @@ mod tests {
                 idkg_pool,
                 state_manager,
                 Duration::from_millis(0),
+                Arc::new(BlockLifecycleTrace::new(time_source.clone())),
                 MetricsRegistry::new(),
                 no_op_logger(),
             );
@@ -998,6 +1018,7 @@ mod tests {
                 idkg_pool,
                 state_manager,
                 Duration::from_millis(0),
+                Arc::new(BlockLifecycleTrace::new(time_source.clone())),
                 MetricsRegistry::new(),
                 no_op_logger(),
             );
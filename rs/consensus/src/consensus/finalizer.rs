@@ -24,6 +24,7 @@ use ic_consensus_utils::{
     crypto::ConsensusCrypto, membership::Membership, pool_reader::PoolReader,
 };
 use ic_interfaces::{
+    consensus::BlockLifecycleTracer,
     ingress_manager::IngressSelector,
     messaging::{MessageRouting, MessageRoutingError},
     time_source::system_time_now,
@@ -45,6 +46,7 @@ pub struct Finalizer {
     pub(crate) crypto: Arc<dyn ConsensusCrypto>,
     message_routing: Arc<dyn MessageRouting>,
     ingress_selector: Arc<dyn IngressSelector>,
+    block_lifecycle_tracer: Arc<dyn BlockLifecycleTracer>,
     pub(crate) log: ReplicaLogger,
     metrics: FinalizerMetrics,
     prev_finalized_height: RefCell<Height>,
@@ -60,6 +62,7 @@ impl Finalizer {
         crypto: Arc<dyn ConsensusCrypto>,
         message_routing: Arc<dyn MessageRouting>,
         ingress_selector: Arc<dyn IngressSelector>,
+        block_lifecycle_tracer: Arc<dyn BlockLifecycleTracer>,
         log: ReplicaLogger,
         metrics_registry: MetricsRegistry,
     ) -> Self {
@@ -70,6 +73,7 @@ impl Finalizer {
             crypto,
             message_routing,
             ingress_selector,
+            block_lifecycle_tracer,
             log,
             metrics: FinalizerMetrics::new(metrics_registry),
             prev_finalized_height: RefCell::new(Height::from(0)),
@@ -106,6 +110,7 @@ impl Finalizer {
             Some(&|result, block_stats, batch_stats| {
                 self.process_batch_delivery_result(result, block_stats, batch_stats)
             }),
+            Some(self.block_lifecycle_tracer.as_ref()),
         );
 
         // Try to finalize rounds from finalized_height + 1 up to (and including)
@@ -254,6 +259,8 @@ mod tests {
     //! Finalizer unit tests
     use super::*;
     use ic_consensus_mocks::{dependencies, dependencies_with_subnet_params, Dependencies};
+    use ic_consensus_utils::block_lifecycle::BlockLifecycleTrace;
+    use ic_interfaces::time_source::TimeSource;
     use ic_logger::replica_logger::no_op_logger;
     use ic_metrics::MetricsRegistry;
     use ic_test_utilities::{
@@ -277,6 +284,7 @@ mod tests {
                 membership,
                 registry,
                 crypto,
+                time_source,
                 ..
             } = dependencies(pool_config, 1);
             let message_routing = FakeMessageRouting::new();
@@ -288,6 +296,7 @@ mod tests {
 
             let message_routing = Arc::new(message_routing);
             let ingress_selector = Arc::new(FakeIngressSelector::new());
+            let block_lifecycle_tracer = Arc::new(BlockLifecycleTrace::new(time_source.clone()));
 
             let finalizer = Finalizer::new(
                 replica_config,
@@ -296,6 +305,7 @@ mod tests {
                 crypto,
                 message_routing.clone(),
                 ingress_selector,
+                block_lifecycle_tracer.clone(),
                 no_op_logger(),
                 MetricsRegistry::new(),
             );
@@ -313,6 +323,10 @@ mod tests {
             assert!(!b.is_empty());
             // First block, nothing to remove.
             assert!(shares.is_empty());
+            let records = block_lifecycle_tracer.records();
+            assert_eq!(records.len(), 1);
+            assert_eq!(records[0].height, Height::from(1));
+            assert_eq!(records[0].delivered, Some(time_source.get_relative_time()));
 
             // 3. When notarization exists, create a finalization share
             pool.insert_validated(pool.make_next_beacon());
@@ -354,6 +368,7 @@ mod tests {
                 membership,
                 registry,
                 crypto,
+                time_source,
                 ..
             } = dependencies_with_subnet_params(
                 pool_config,
@@ -386,6 +401,7 @@ mod tests {
                 crypto,
                 message_routing.clone(),
                 ingress_selector,
+                Arc::new(BlockLifecycleTrace::new(time_source.clone())),
                 no_op_logger(),
                 metrics_registry,
             );
@@ -33,7 +33,10 @@ use ic_consensus_utils::{
     membership::{Membership, MembershipError},
     pool_reader::PoolReader,
 };
-use ic_interfaces::time_source::TimeSource;
+use ic_interfaces::{
+    consensus::{BlockLifecycleStage, BlockLifecycleTracer},
+    time_source::TimeSource,
+};
 use ic_interfaces_state_manager::StateManager;
 use ic_logger::{error, trace, warn, ReplicaLogger};
 use ic_metrics::MetricsRegistry;
@@ -66,6 +69,7 @@ pub struct Notary {
     membership: Arc<Membership>,
     crypto: Arc<dyn ConsensusCrypto>,
     state_manager: Arc<dyn StateManager<State = ReplicatedState>>,
+    block_lifecycle_tracer: Arc<dyn BlockLifecycleTracer>,
     pub(crate) log: ReplicaLogger,
     metrics: NotaryMetrics,
 }
@@ -77,6 +81,7 @@ impl Notary {
         membership: Arc<Membership>,
         crypto: Arc<dyn ConsensusCrypto>,
         state_manager: Arc<dyn StateManager<State = ReplicatedState>>,
+        block_lifecycle_tracer: Arc<dyn BlockLifecycleTracer>,
         metrics_registry: MetricsRegistry,
         log: ReplicaLogger,
     ) -> Notary {
@@ -86,6 +91,7 @@ impl Notary {
             membership,
             crypto,
             state_manager,
+            block_lifecycle_tracer,
             log,
             metrics: NotaryMetrics::new(metrics_registry),
         }
@@ -108,6 +114,8 @@ impl Notary {
                     if !self.is_proposal_already_notarized_by_me(pool, &proposal) {
                         if let Some(s) = self.notarize_block(pool, &proposal.content) {
                             self.metrics.report_notarization(proposal.as_ref(), elapsed);
+                            self.block_lifecycle_tracer
+                                .record(height, BlockLifecycleStage::NotarizationShared);
                             notarization_shares.push(s);
                         }
                     }
@@ -383,6 +391,7 @@ mod tests {
     use super::*;
     use assert_matches::assert_matches;
     use ic_consensus_mocks::{dependencies_with_subnet_params, Dependencies};
+    use ic_consensus_utils::block_lifecycle::BlockLifecycleTrace;
     use ic_interfaces::{consensus_pool::ConsensusPool, time_source::TimeSource};
     use ic_logger::replica_logger::no_op_logger;
     use ic_metrics::MetricsRegistry;
@@ -433,6 +442,7 @@ mod tests {
             pool.insert_validated(block.clone());
 
             let metrics_registry = MetricsRegistry::new();
+            let block_lifecycle_tracer = Arc::new(BlockLifecycleTrace::new(time_source.clone()));
 
             let notary = Notary::new(
                 Arc::clone(&time_source) as Arc<_>,
@@ -440,6 +450,7 @@ mod tests {
                 membership.clone(),
                 crypto,
                 state_manager.clone(),
+                block_lifecycle_tracer.clone(),
                 metrics_registry,
                 no_op_logger(),
             );
@@ -449,6 +460,7 @@ mod tests {
                 notary.on_state_change(&reader)
             };
             assert!(run_notary(&pool).is_empty());
+            assert!(block_lifecycle_tracer.records().is_empty());
 
             // Time has expired for rank 0, do something
             time_source
@@ -472,6 +484,13 @@ mod tests {
                 }
                 _ => false,
             });
+            let records = block_lifecycle_tracer.records();
+            assert_eq!(records.len(), 1);
+            assert_eq!(records[0].height, Height::from(2));
+            assert_eq!(
+                records[0].notarization_shared,
+                Some(time_source.get_relative_time())
+            );
 
             // 2. Make sure we do not create a duplicate share
             assert!(run_notary(&pool).is_empty());
@@ -630,6 +649,7 @@ mod tests {
                 membership.clone(),
                 crypto,
                 state_manager.clone(),
+                Arc::new(BlockLifecycleTrace::new(time_source.clone())),
                 metrics_registry,
                 no_op_logger(),
             );
@@ -19,7 +19,10 @@ use ic_consensus_utils::{
 };
 use ic_interfaces::{
     batch_payload::ProposalContext,
-    consensus::{InvalidPayloadReason, PayloadBuilder, PayloadValidationFailure},
+    consensus::{
+        BlockLifecycleStage, BlockLifecycleTracer, InvalidPayloadReason, PayloadBuilder,
+        PayloadValidationFailure,
+    },
     consensus_pool::*,
     dkg::DkgPool,
     messaging::MessageRouting,
@@ -684,6 +687,7 @@ pub struct Validator {
     state_manager: Arc<dyn StateManager<State = ReplicatedState>>,
     message_routing: Arc<dyn MessageRouting>,
     dkg_pool: Arc<RwLock<dyn DkgPool>>,
+    block_lifecycle_tracer: Arc<dyn BlockLifecycleTracer>,
     log: ReplicaLogger,
     metrics: ValidatorMetrics,
     schedule: RoundRobin,
@@ -702,6 +706,7 @@ impl Validator {
         state_manager: Arc<dyn StateManager<State = ReplicatedState>>,
         message_routing: Arc<dyn MessageRouting>,
         dkg_pool: Arc<RwLock<dyn DkgPool>>,
+        block_lifecycle_tracer: Arc<dyn BlockLifecycleTracer>,
         log: ReplicaLogger,
         metrics: ValidatorMetrics,
         time_source: Arc<dyn TimeSource>,
@@ -715,6 +720,7 @@ impl Validator {
             state_manager,
             message_routing,
             dkg_pool,
+            block_lifecycle_tracer,
             log,
             metrics,
             schedule: RoundRobin::default(),
@@ -1143,6 +1149,8 @@ impl Validator {
             {
                 self.metrics.observe_data_payload(proposal);
                 self.metrics.observe_block(pool_reader, proposal);
+                self.block_lifecycle_tracer
+                    .record(proposal.height(), BlockLifecycleStage::Validated);
             }
         }
         self.metrics.observe_and_reset_dkg_time_per_validator_run();
@@ -1905,6 +1913,7 @@ pub mod test {
         dependencies_with_subnet_params, dependencies_with_subnet_records_with_raw_state_manager,
         Dependencies, RefMockPayloadBuilder,
     };
+    use ic_consensus_utils::block_lifecycle::BlockLifecycleTrace;
     use ic_interfaces::{
         messaging::XNetPayloadValidationFailure, p2p::consensus::MutablePool,
         time_source::TimeSource,
@@ -1977,6 +1986,7 @@ pub mod test {
         pub pool: TestConsensusPool,
         pub dkg_pool: Arc<RwLock<DkgPoolImpl>>,
         pub time_source: Arc<FastForwardTimeSource>,
+        pub block_lifecycle_tracer: Arc<BlockLifecycleTrace>,
         pub replica_config: ReplicaConfig,
     }
 
@@ -1984,6 +1994,8 @@ pub mod test {
         fn new(dependencies: Dependencies) -> Self {
             let payload_builder = Arc::new(RefMockPayloadBuilder::default());
             let message_routing = Arc::new(RefMockMessageRouting::default());
+            let block_lifecycle_tracer =
+                Arc::new(BlockLifecycleTrace::new(dependencies.time_source.clone()));
             let validator = Validator::new(
                 dependencies.replica_config.clone(),
                 dependencies.membership.clone(),
@@ -1993,6 +2005,7 @@ pub mod test {
                 dependencies.state_manager.clone(),
                 message_routing.clone(),
                 dependencies.dkg_pool.clone(),
+                block_lifecycle_tracer.clone(),
                 no_op_logger(),
                 ValidatorMetrics::new(MetricsRegistry::new()),
                 Arc::clone(&dependencies.time_source) as Arc<_>,
@@ -2009,6 +2022,7 @@ pub mod test {
                 pool: dependencies.pool,
                 dkg_pool: dependencies.dkg_pool,
                 time_source: dependencies.time_source,
+                block_lifecycle_tracer,
                 replica_config: dependencies.replica_config,
             }
         }
@@ -2520,6 +2534,7 @@ pub mod test {
                 registry_client,
                 mut pool,
                 time_source,
+                block_lifecycle_tracer,
                 replica_config,
                 ..
             } = setup_dependencies(pool_config, &committee);
@@ -2602,9 +2617,15 @@ pub mod test {
                     /*metrics=*/ None,
                 );
 
+            assert!(block_lifecycle_tracer.records().is_empty());
+
             time_source.set_time(parent.context.time + delay).unwrap();
             let valid_results = validator.on_state_change(&PoolReader::new(&pool));
             assert_block_valid(&valid_results, &block_proposal);
+            let records = block_lifecycle_tracer.records();
+            assert_eq!(records.len(), 1);
+            assert_eq!(records[0].height, test_block.height());
+            assert_eq!(records[0].validated, Some(time_source.get_relative_time()));
         });
     }
 
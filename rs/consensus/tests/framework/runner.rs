@@ -3,7 +3,8 @@ use ic_config::artifact_pool::ArtifactPoolConfig;
 use ic_consensus_certification::{CertificationCrypto, CertifierImpl};
 use ic_consensus_dkg::DkgKeyManager;
 use ic_consensus_utils::{
-    crypto::ConsensusCrypto, membership::Membership, pool_reader::PoolReader,
+    block_lifecycle::BlockLifecycleTrace, crypto::ConsensusCrypto, membership::Membership,
+    pool_reader::PoolReader,
 };
 use ic_interfaces::{consensus_pool::ConsensusPoolCache, time_source::TimeSource};
 use ic_logger::{info, warn, ReplicaLogger};
@@ -143,6 +144,7 @@ impl<'a> ConsensusRunner<'a> {
             pool_reader,
         )));
         let malicious_flags = MaliciousFlags::default();
+        let block_lifecycle_tracer = Arc::new(BlockLifecycleTrace::new(self.time.clone()));
         let consensus = ic_consensus::consensus::ConsensusImpl::new(
             deps.replica_config.clone(),
             Arc::clone(&deps.registry_client),
@@ -162,6 +164,7 @@ impl<'a> ConsensusRunner<'a> {
             Arc::clone(&self.time) as Arc<_>,
             0,
             malicious_flags.clone(),
+            block_lifecycle_tracer.clone(),
             deps.metrics_registry.clone(),
             replica_logger.clone(),
         );
@@ -195,6 +198,7 @@ impl<'a> ConsensusRunner<'a> {
             deps.metrics_registry.clone(),
             replica_logger.clone(),
             watch::channel(Height::from(0)).0,
+            block_lifecycle_tracer,
         );
         let now = self.time.get_relative_time();
         let in_queue: Queue<Input> = Default::default();
@@ -5,7 +5,7 @@ use crate::framework::ConsensusDriver;
 use ic_artifact_pool::{consensus_pool, dkg_pool, idkg_pool};
 use ic_consensus_certification::CertifierImpl;
 use ic_consensus_dkg::{get_dkg_summary_from_cup_contents, DkgKeyManager};
-use ic_consensus_utils::pool_reader::PoolReader;
+use ic_consensus_utils::{block_lifecycle::BlockLifecycleTrace, pool_reader::PoolReader};
 use ic_https_outcalls_consensus::test_utils::FakeCanisterHttpPayloadBuilder;
 use ic_interfaces_registry::RegistryClient;
 use ic_interfaces_state_manager::Labeled;
@@ -150,6 +150,7 @@ fn consensus_produces_expected_batches() {
         )));
 
         let (dummy_watcher, _) = watch::channel(Height::from(0));
+        let block_lifecycle_tracer = Arc::new(BlockLifecycleTrace::new(time_source.clone()));
 
         let consensus = ic_consensus::consensus::ConsensusImpl::new(
             replica_config.clone(),
@@ -170,6 +171,7 @@ fn consensus_produces_expected_batches() {
             Arc::clone(&time_source) as Arc<_>,
             0,
             MaliciousFlags::default(),
+            block_lifecycle_tracer.clone(),
             metrics_registry.clone(),
             no_op_logger(),
         );
@@ -201,6 +203,7 @@ fn consensus_produces_expected_batches() {
             metrics_registry.clone(),
             no_op_logger(),
             dummy_watcher,
+            block_lifecycle_tracer,
         );
 
         let driver = ConsensusDriver::new(
@@ -0,0 +1,122 @@
+//! A bounded, in-memory trace of the lifecycle of the blocks at the most recent
+//! heights, as observed by the local replica.
+use ic_interfaces::{
+    consensus::{BlockLifecycleRecord, BlockLifecycleStage, BlockLifecycleTracer},
+    time_source::TimeSource,
+};
+use ic_types::Height;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+/// The number of heights retained by default.
+pub const DEFAULT_BLOCK_LIFECYCLE_CAPACITY: usize = 500;
+
+/// A ring buffer of [`BlockLifecycleRecord`]s ordered by height. Once the
+/// buffer is full, the records of the lowest heights are evicted, and events
+/// for heights below the retained window are dropped.
+///
+/// All stages are timestamped with the relative time of the same
+/// [`TimeSource`], which should be the one injected into consensus.
+pub struct BlockLifecycleTrace {
+    capacity: usize,
+    time_source: Arc<dyn TimeSource>,
+    records: Mutex<VecDeque<BlockLifecycleRecord>>,
+}
+
+impl BlockLifecycleTrace {
+    /// Creates a trace retaining [`DEFAULT_BLOCK_LIFECYCLE_CAPACITY`] heights.
+    pub fn new(time_source: Arc<dyn TimeSource>) -> Self {
+        Self::with_capacity(DEFAULT_BLOCK_LIFECYCLE_CAPACITY, time_source)
+    }
+
+    pub fn with_capacity(capacity: usize, time_source: Arc<dyn TimeSource>) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            time_source,
+            records: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+}
+
+impl BlockLifecycleTracer for BlockLifecycleTrace {
+    fn record(&self, height: Height, stage: BlockLifecycleStage) {
+        let time = self.time_source.get_relative_time();
+        let mut records = self.records.lock().unwrap();
+        match records.binary_search_by_key(&height, |record| record.height) {
+            Ok(index) => records[index].set(stage, time),
+            Err(index) => {
+                if index == 0 && records.len() >= self.capacity {
+                    return;
+                }
+                let mut record = BlockLifecycleRecord::new(height);
+                record.set(stage, time);
+                records.insert(index, record);
+                while records.len() > self.capacity {
+                    records.pop_front();
+                }
+            }
+        }
+    }
+
+    fn records(&self) -> Vec<BlockLifecycleRecord> {
+        self.records.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_test_utilities_time::FastForwardTimeSource;
+    use ic_types::{time::UNIX_EPOCH, Time};
+    use std::time::Duration;
+
+    fn time(secs: u64) -> Time {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn test_keeps_first_time_per_stage() {
+        let time_source = FastForwardTimeSource::new();
+        let trace = BlockLifecycleTrace::with_capacity(10, time_source.clone());
+        let height = Height::from(1);
+        time_source.set_time(time(1)).unwrap();
+        trace.record(height, BlockLifecycleStage::Validated);
+        time_source.set_time(time(2)).unwrap();
+        trace.record(height, BlockLifecycleStage::Notarized);
+        time_source.set_time(time(3)).unwrap();
+        trace.record(height, BlockLifecycleStage::Notarized);
+        trace.record(height, BlockLifecycleStage::Executed);
+
+        let records = trace.records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].height, height);
+        assert_eq!(records[0].validated, Some(time(1)));
+        assert_eq!(records[0].first_seen(), Some(time(1)));
+        assert_eq!(records[0].notarized, Some(time(2)));
+        assert_eq!(records[0].executed, Some(time(3)));
+        assert_eq!(records[0].get(BlockLifecycleStage::Finalized), None);
+    }
+
+    #[test]
+    fn test_evicts_lowest_heights() {
+        let time_source = FastForwardTimeSource::new();
+        let trace = BlockLifecycleTrace::with_capacity(3, time_source.clone());
+        for h in [2, 1, 4, 3, 5] {
+            trace.record(Height::from(h), BlockLifecycleStage::NotarizationShared);
+        }
+        let heights: Vec<_> = trace.records().iter().map(|r| r.height.get()).collect();
+        assert_eq!(heights, vec![3, 4, 5]);
+
+        // Events below the retained window are dropped.
+        time_source.set_time(time(10)).unwrap();
+        trace.record(Height::from(1), BlockLifecycleStage::Certified);
+        let heights: Vec<_> = trace.records().iter().map(|r| r.height.get()).collect();
+        assert_eq!(heights, vec![3, 4, 5]);
+
+        // Late events for retained heights are still recorded.
+        trace.record(Height::from(3), BlockLifecycleStage::Certified);
+        assert_eq!(trace.records()[0].certified, Some(time(10)));
+    }
+}
@@ -15,7 +15,9 @@
 
 use ic_base_types::NumSeconds;
 use ic_config::subnet_config::CyclesAccountManagerConfig;
-use ic_interfaces::execution_environment::CanisterOutOfCyclesError;
+use ic_interfaces::execution_environment::{
+    CanisterOutOfCyclesError, CyclesBurnLimitExceededError,
+};
 use ic_logger::{error, info, ReplicaLogger};
 use ic_management_canister_types_private::Method;
 use ic_nns_constants::CYCLES_MINTING_CANISTER_ID;
@@ -28,7 +30,7 @@ use ic_types::{
     canister_http::MAX_CANISTER_HTTP_RESPONSE_BYTES,
     messages::{Request, Response, SignedIngressContent, MAX_INTER_CANISTER_PAYLOAD_IN_BYTES},
     CanisterId, ComputeAllocation, Cycles, MemoryAllocation, NumBytes, NumInstructions,
-    PrincipalId, SubnetId,
+    PrincipalId, SubnetId, Time,
 };
 use prometheus::IntCounter;
 use serde::{Deserialize, Serialize};
@@ -924,6 +926,29 @@ impl CyclesAccountManager {
         }
     }
 
+    /// Checks whether the canister has not yet consumed its `cycles_burn_limit`
+    /// within the cycles burn window that is current at `time`.
+    ///
+    /// Returns a `CyclesBurnLimitExceededError` if the limit has been reached.
+    pub fn check_cycles_burn_limit(
+        &self,
+        system_state: &SystemState,
+        time: Time,
+    ) -> Result<(), CyclesBurnLimitExceededError> {
+        let Some(limit) = system_state.cycles_burn_limit else {
+            return Ok(());
+        };
+        let burned = system_state.cycles_burned_in_window(time);
+        if burned >= limit {
+            return Err(CyclesBurnLimitExceededError {
+                canister_id: system_state.canister_id(),
+                burned,
+                limit,
+            });
+        }
+        Ok(())
+    }
+
     /// Subtracts and consumes the cycles. This call should be used when the
     /// cycles are not being sent somewhere else.
     pub fn consume_with_threshold(
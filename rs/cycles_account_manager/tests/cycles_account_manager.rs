@@ -1,7 +1,9 @@
 use ic_base_types::NumSeconds;
 use ic_config::subnet_config::CyclesAccountManagerConfig;
 use ic_cycles_account_manager::{IngressInductionCost, ResourceSaturation};
-use ic_interfaces::execution_environment::CanisterOutOfCyclesError;
+use ic_interfaces::execution_environment::{
+    CanisterOutOfCyclesError, CyclesBurnLimitExceededError,
+};
 use ic_limits::SMALL_APP_SUBNET_MAX_SIZE;
 use ic_logger::replica_logger::no_op_logger;
 use ic_management_canister_types_private::{CanisterIdRecord, Payload, IC_00};
@@ -9,7 +11,7 @@ use ic_registry_subnet_type::SubnetType;
 use ic_replicated_state::{
     canister_state::{execution_state::WasmExecutionMode, system_state::CyclesUseCase},
     testing::SystemStateTesting,
-    MessageMemoryUsage, SystemState,
+    MessageMemoryUsage, SystemState, CYCLES_BURN_LIMIT_GRANULARITY, CYCLES_BURN_LIMIT_WINDOW,
 };
 use ic_test_utilities::cycles_account_manager::CyclesAccountManagerBuilder;
 use ic_test_utilities_logger::with_test_replica_logger;
@@ -953,6 +955,86 @@ fn withdraw_cycles_for_transfer_checks_reserved_balance() {
     assert_eq!(Cycles::zero(), new_balance);
 }
 
+#[test]
+fn cycles_burn_limit_is_enforced_within_window() {
+    let cycles_account_manager = CyclesAccountManagerBuilder::new().build();
+    let mut system_state = SystemStateBuilder::new()
+        .initial_cycles(Cycles::new(1_000_000_000))
+        .build();
+    system_state.cycles_burn_limit = Some(Cycles::new(1_000));
+    let start = UNIX_EPOCH + Duration::from_secs(1);
+    system_state.update_cycles_burn_window(start);
+
+    assert!(cycles_account_manager
+        .check_cycles_burn_limit(&system_state, start)
+        .is_ok());
+
+    cycles_account_manager
+        .consume_with_threshold(
+            &mut system_state,
+            Cycles::new(1_000),
+            Cycles::zero(),
+            CyclesUseCase::Instructions,
+            false,
+        )
+        .unwrap();
+    assert_eq!(
+        system_state.cycles_burned_in_window(start),
+        Cycles::new(1_000)
+    );
+    assert_eq!(
+        cycles_account_manager.check_cycles_burn_limit(&system_state, start),
+        Err(CyclesBurnLimitExceededError {
+            canister_id: system_state.canister_id(),
+            burned: Cycles::new(1_000),
+            limit: Cycles::new(1_000),
+        })
+    );
+
+    // Refunds reduce the amount burned in the window.
+    system_state.add_cycles(Cycles::new(1), CyclesUseCase::Instructions);
+    assert!(cycles_account_manager
+        .check_cycles_burn_limit(&system_state, start)
+        .is_ok());
+
+    // A new sample is taken once per granularity interval.
+    let later = start + CYCLES_BURN_LIMIT_GRANULARITY;
+    system_state.update_cycles_burn_window(later);
+    cycles_account_manager
+        .consume_with_threshold(
+            &mut system_state,
+            Cycles::new(1),
+            Cycles::zero(),
+            CyclesUseCase::Instructions,
+            false,
+        )
+        .unwrap();
+    assert!(cycles_account_manager
+        .check_cycles_burn_limit(&system_state, later)
+        .is_err());
+
+    // The window is rolling: the cycles burned after `start` still count one
+    // window after `start`.
+    let window_end = start + CYCLES_BURN_LIMIT_WINDOW;
+    system_state.update_cycles_burn_window(window_end);
+    assert_eq!(
+        system_state.cycles_burned_in_window(window_end),
+        Cycles::new(1_000)
+    );
+
+    // Once the sample taken at `later` is at the start of the window, only the
+    // cycles burned after it count.
+    let next_window_end = later + CYCLES_BURN_LIMIT_WINDOW;
+    system_state.update_cycles_burn_window(next_window_end);
+    assert_eq!(
+        system_state.cycles_burned_in_window(next_window_end),
+        Cycles::new(1)
+    );
+    assert!(cycles_account_manager
+        .check_cycles_burn_limit(&system_state, next_window_end)
+        .is_ok());
+}
+
 #[test]
 fn freezing_threshold_uses_reserved_balance() {
     let cycles_account_manager = CyclesAccountManagerBuilder::new().build();
@@ -159,6 +159,7 @@ pub(crate) fn setup() -> (
         log.clone().into(),
         Arc::clone(&registry) as _,
         MaliciousFlags::default(),
+        None,
     );
 
     (
@@ -270,6 +270,7 @@ pub async fn run_drun(uo: DrunOptions) -> Result<(), String> {
         log.clone().into(),
         Arc::clone(&registry) as _,
         MaliciousFlags::default(),
+        None,
     );
 
     for parse_result in msg_stream {
@@ -42,7 +42,7 @@ pub const RESERVED_SYMBOLS: [&str; 6] = [
 
 /// System functions that can be exported by a canister
 #[doc(hidden)] // pub for usage in tests
-pub const WASM_VALID_SYSTEM_FUNCTIONS: [&str; 7] = [
+pub const WASM_VALID_SYSTEM_FUNCTIONS: [&str; 8] = [
     "canister_init",
     "canister_inspect_message",
     "canister_pre_upgrade",
@@ -50,6 +50,7 @@ pub const WASM_VALID_SYSTEM_FUNCTIONS: [&str; 7] = [
     "canister_heartbeat",
     "canister_global_timer",
     "canister_on_low_wasm_memory",
+    "canister_on_low_cycles",
 ];
 
 const WASM_FUNCTION_COMPLEXITY_LIMIT: Complexity = Complexity(1_000_000);
@@ -900,6 +901,13 @@ fn get_valid_exported_functions() -> HashMap<String, FunctionSignature> {
                 return_type: vec![],
             },
         ),
+        (
+            "canister_on_low_cycles",
+            FunctionSignature {
+                param_types: vec![],
+                return_type: vec![],
+            },
+        ),
     ];
 
     valid_exported_functions
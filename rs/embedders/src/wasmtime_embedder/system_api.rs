@@ -393,11 +393,13 @@ pub enum ApiType {
         message_accepted: bool,
     },
 
-    // For executing the `canister_heartbeat` or `canister_global_timer` or `canister_on_low_wasm_memory` methods
+    // For executing the `canister_heartbeat`, `canister_global_timer`, `canister_on_low_wasm_memory`,
+    // or `canister_on_low_cycles` methods
     SystemTask {
         caller: PrincipalId,
         /// System task to execute.
-        /// Only `canister_heartbeat`, `canister_global_timer`, and `canister_on_low_wasm_memory` are allowed.
+        /// Only `canister_heartbeat`, `canister_global_timer`, `canister_on_low_wasm_memory`,
+        /// and `canister_on_low_cycles` are allowed.
         system_task: SystemMethod,
         time: Time,
         call_context_id: CallContextId,
@@ -748,12 +750,13 @@ impl ApiType {
                 SystemMethod::CanisterHeartbeat => "heartbeat",
                 SystemMethod::CanisterGlobalTimer => "global timer",
                 SystemMethod::CanisterOnLowWasmMemory => "on low Wasm memory",
+                SystemMethod::CanisterOnLowCycles => "on low cycles",
                 SystemMethod::CanisterStart
                 | SystemMethod::CanisterInit
                 | SystemMethod::CanisterPreUpgrade
                 | SystemMethod::CanisterPostUpgrade
                 | SystemMethod::CanisterInspectMessage => {
-                    panic!("Only `canister_heartbeat`, `canister_global_timer`, and `canister_on_low_wasm_memory` are allowed.")
+                    panic!("Only `canister_heartbeat`, `canister_global_timer`, `canister_on_low_wasm_memory`, and `canister_on_low_cycles` are allowed.")
                 }
             },
             ApiType::Update { .. } => "update",
@@ -6,16 +6,17 @@ use ic_btc_interface::NetworkInRequest as BitcoinNetwork;
 use ic_error_types::UserError;
 use ic_management_canister_types_private::{
     BitcoinGetBalanceArgs, BitcoinGetBlockHeadersArgs, BitcoinGetCurrentFeePercentilesArgs,
-    BitcoinGetUtxosArgs, BitcoinSendTransactionArgs, CanisterIdRecord, CanisterInfoRequest,
-    ClearChunkStoreArgs, ComputeInitialIDkgDealingsArgs, DeleteCanisterSnapshotArgs,
-    ECDSAPublicKeyArgs, InstallChunkedCodeArgs, InstallCodeArgsV2, ListCanisterSnapshotArgs,
-    LoadCanisterSnapshotArgs, MasterPublicKeyId, Method as Ic00Method, NodeMetricsHistoryArgs,
-    Payload, ProvisionalTopUpCanisterArgs, ReadCanisterSnapshotDataArgs,
-    ReadCanisterSnapshotMetadataArgs, RenameCanisterArgs, ReshareChainKeyArgs,
-    SchnorrPublicKeyArgs, SignWithECDSAArgs, SignWithSchnorrArgs, StoredChunksArgs, SubnetInfoArgs,
-    TakeCanisterSnapshotArgs, UninstallCodeArgs, UpdateSettingsArgs,
-    UploadCanisterSnapshotDataArgs, UploadCanisterSnapshotMetadataArgs, UploadChunkArgs,
-    VetKdDeriveKeyArgs, VetKdPublicKeyArgs,
+    BitcoinGetUtxosArgs, BitcoinSendTransactionArgs, CancelScheduledManagementCallArgs,
+    CanisterIdRecord, CanisterInfoRequest, ClearChunkStoreArgs, ComputeInitialIDkgDealingsArgs,
+    DeleteCanisterSnapshotArgs, ECDSAPublicKeyArgs, InstallChunkedCodeArgs, InstallCodeArgsV2,
+    ListCanisterSnapshotArgs, ListScheduledManagementCallsArgs, LoadCanisterSnapshotArgs,
+    MasterPublicKeyId, Method as Ic00Method, NodeMetricsHistoryArgs, Payload,
+    ProvisionalTopUpCanisterArgs, ReadCanisterSnapshotDataArgs, ReadCanisterSnapshotMetadataArgs,
+    RenameCanisterArgs, ReshareChainKeyArgs, ScheduleManagementCallArgs,
+    ScheduledManagementCallStatusArgs, SchnorrPublicKeyArgs, SignWithECDSAArgs,
+    SignWithSchnorrArgs, StoredChunksArgs, SubnetInfoArgs, TakeCanisterSnapshotArgs,
+    UninstallCodeArgs, UpdateSettingsArgs, UploadCanisterSnapshotDataArgs,
+    UploadCanisterSnapshotMetadataArgs, UploadChunkArgs, VetKdDeriveKeyArgs, VetKdPublicKeyArgs,
 };
 use ic_replicated_state::NetworkTopology;
 use itertools::Itertools;
@@ -171,12 +172,12 @@ pub(super) fn resolve_destination(
             Ok(NodeMetricsHistoryArgs::decode(payload)?.subnet_id)
         }
         Ok(Ic00Method::SubnetInfo) => Ok(SubnetInfoArgs::decode(payload)?.subnet_id),
-        Ok(Ic00Method::FetchCanisterLogs) => {
+        Ok(method @ (Ic00Method::FetchCanisterLogs | Ic00Method::FetchCanisterHistory)) => {
             Err(ResolveDestinationError::UserError(UserError::new(
                 ic_error_types::ErrorCode::CanisterRejectedMessage,
                 format!(
                     "{} API is only accessible to end users in non-replicated mode",
-                    Ic00Method::FetchCanisterLogs
+                    method
                 ),
             )))
         }
@@ -344,6 +345,42 @@ pub(super) fn resolve_destination(
             let canister_id = args.get_canister_id();
             route_canister_id(canister_id, Ic00Method::RenameCanister, network_topology)
         }
+        Ok(Ic00Method::ScheduleManagementCall) => {
+            let args = ScheduleManagementCallArgs::decode(payload)?;
+            let canister_id = args.get_canister_id();
+            route_canister_id(
+                canister_id,
+                Ic00Method::ScheduleManagementCall,
+                network_topology,
+            )
+        }
+        Ok(Ic00Method::ListScheduledManagementCalls) => {
+            let args = ListScheduledManagementCallsArgs::decode(payload)?;
+            let canister_id = args.get_canister_id();
+            route_canister_id(
+                canister_id,
+                Ic00Method::ListScheduledManagementCalls,
+                network_topology,
+            )
+        }
+        Ok(Ic00Method::CancelScheduledManagementCall) => {
+            let args = CancelScheduledManagementCallArgs::decode(payload)?;
+            let canister_id = args.get_canister_id();
+            route_canister_id(
+                canister_id,
+                Ic00Method::CancelScheduledManagementCall,
+                network_topology,
+            )
+        }
+        Ok(Ic00Method::ScheduledManagementCallStatus) => {
+            let args = ScheduledManagementCallStatusArgs::decode(payload)?;
+            let canister_id = args.get_canister_id();
+            route_canister_id(
+                canister_id,
+                Ic00Method::ScheduledManagementCallStatus,
+                network_topology,
+            )
+        }
         Err(_) => Err(ResolveDestinationError::MethodNotFound(
             method_name.to_string(),
         )),
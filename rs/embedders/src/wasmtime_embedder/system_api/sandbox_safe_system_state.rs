@@ -278,6 +278,7 @@ impl SystemStateModifications {
             | Ok(Ic00Method::NodeMetricsHistory)
             | Ok(Ic00Method::SubnetInfo)
             | Ok(Ic00Method::FetchCanisterLogs)
+            | Ok(Ic00Method::FetchCanisterHistory)
             | Ok(Ic00Method::UploadChunk)
             | Ok(Ic00Method::StoredChunks)
             | Ok(Ic00Method::ClearChunkStore)
@@ -287,7 +288,11 @@ impl SystemStateModifications {
             | Ok(Ic00Method::ReadCanisterSnapshotMetadata)
             | Ok(Ic00Method::ReadCanisterSnapshotData)
             | Ok(Ic00Method::UploadCanisterSnapshotMetadata)
-            | Ok(Ic00Method::UploadCanisterSnapshotData) => Ok(None),
+            | Ok(Ic00Method::UploadCanisterSnapshotData)
+            | Ok(Ic00Method::ScheduleManagementCall)
+            | Ok(Ic00Method::ListScheduledManagementCalls)
+            | Ok(Ic00Method::CancelScheduledManagementCall)
+            | Ok(Ic00Method::ScheduledManagementCallStatus) => Ok(None),
             Err(_) => Err(UserError::new(
                 ErrorCode::CanisterMethodNotFound,
                 format!("Management canister has no method '{}'", msg.method_name),
@@ -342,6 +347,10 @@ impl SystemStateModifications {
             }
         }
 
+        // The cycles balance may have dropped below (or risen above) the
+        // threshold of the `OnLowCycles` hook during the execution.
+        system_state.update_on_low_cycles_hook_status();
+
         // Verify we don't accept more cycles than are available from call
         // context and update the call context balance.
         if let Some((context_id, call_context_balance_taken)) = self.call_context_balance_taken {
@@ -163,7 +163,10 @@ impl CanisterManager {
             | Ok(Ic00Method::NodeMetricsHistory)
             | Ok(Ic00Method::SubnetInfo)
             // `RenameCanister` can only be called from the NNS subnet.
-            | Ok(Ic00Method::RenameCanister) => Err(UserError::new(
+            | Ok(Ic00Method::RenameCanister)
+            // Users read the outcome of their scheduled calls from the ingress
+            // history instead.
+            | Ok(Ic00Method::ScheduledManagementCallStatus) => Err(UserError::new(
                 ErrorCode::CanisterRejectedMessage,
                 format!("Only canisters can call ic00 method {}", method_name),
             )),
@@ -189,7 +192,10 @@ impl CanisterManager {
             | Ok(Ic00Method::ReadCanisterSnapshotMetadata)
             | Ok(Ic00Method::ReadCanisterSnapshotData)
             | Ok(Ic00Method::UploadCanisterSnapshotMetadata)
-            | Ok(Ic00Method::UploadCanisterSnapshotData) => {
+            | Ok(Ic00Method::UploadCanisterSnapshotData)
+            | Ok(Ic00Method::ScheduleManagementCall)
+            | Ok(Ic00Method::ListScheduledManagementCalls)
+            | Ok(Ic00Method::CancelScheduledManagementCall) => {
                 match effective_canister_id {
                     Some(canister_id) => {
                         let canister = state.canister_state(&canister_id).ok_or_else(|| UserError::new(
@@ -214,13 +220,12 @@ impl CanisterManager {
                 }
             },
 
-            Ok(Ic00Method::FetchCanisterLogs) => Err(UserError::new(
-                ErrorCode::CanisterRejectedMessage,
-                format!(
-                    "{} API is only accessible in non-replicated mode",
-                    Ic00Method::FetchCanisterLogs
-                ),
-            )),
+            Ok(method @ (Ic00Method::FetchCanisterLogs | Ic00Method::FetchCanisterHistory)) => {
+                Err(UserError::new(
+                    ErrorCode::CanisterRejectedMessage,
+                    format!("{} API is only accessible in non-replicated mode", method),
+                ))
+            }
 
             Ok(Ic00Method::ProvisionalCreateCanisterWithCycles)
             | Ok(Ic00Method::BitcoinGetSuccessors)
@@ -304,6 +309,15 @@ impl CanisterManager {
         if let Some(wasm_memory_threshold) = settings.wasm_memory_threshold() {
             canister.system_state.wasm_memory_threshold = wasm_memory_threshold;
         }
+        if let Some(cycles_threshold) = settings.cycles_threshold() {
+            canister.system_state.cycles_threshold = cycles_threshold;
+        }
+        if let Some(cycles_burn_limit) = settings.cycles_burn_limit() {
+            // A limit of zero removes the limit, matching how it is reported
+            // in `definite_canister_settings`.
+            canister.system_state.cycles_burn_limit =
+                (cycles_burn_limit > Cycles::zero()).then_some(cycles_burn_limit);
+        }
         if let Some(limit) = settings.reserved_cycles_limit() {
             canister.system_state.set_reserved_balance_limit(limit);
         }
@@ -320,6 +334,11 @@ impl CanisterManager {
         if let Some(log_visibility) = settings.log_visibility() {
             canister.system_state.log_visibility = log_visibility.clone();
         }
+        if let Some(history_visibility) = settings.history_visibility() {
+            canister
+                .system_state
+                .set_canister_history_visibility(history_visibility);
+        }
         if let Some(wasm_memory_limit) = settings.wasm_memory_limit() {
             canister.system_state.wasm_memory_limit = Some(wasm_memory_limit);
         }
@@ -766,6 +785,7 @@ impl CanisterManager {
         sender: PrincipalId,
         canister: &mut CanisterState,
         subnet_size: usize,
+        time: Time,
     ) -> Result<CanisterStatusResultV2, CanisterManagerError> {
         // Skip the controller check if the canister itself is requesting its
         // own status, as the canister is considered in the same trust domain.
@@ -797,6 +817,10 @@ impl CanisterManager {
         let log_visibility = canister.system_state.log_visibility.clone();
         let wasm_memory_limit = canister.system_state.wasm_memory_limit;
         let wasm_memory_threshold = canister.system_state.wasm_memory_threshold;
+        let cycles_threshold = canister.system_state.cycles_threshold;
+        let cycles_burn_limit = canister.system_state.cycles_burn_limit;
+        let history_visibility = canister.system_state.canister_history_visibility();
+        let cycles_burned_in_window = canister.system_state.cycles_burned_in_window(time);
 
         Ok(CanisterStatusResultV2::new(
             canister.status(),
@@ -843,6 +867,10 @@ impl CanisterManager {
                 .egress_payload_size,
             wasm_memory_limit.map(|x| x.get()),
             wasm_memory_threshold.get(),
+            cycles_threshold.get(),
+            cycles_burn_limit.map(|x| x.get()),
+            history_visibility,
+            cycles_burned_in_window.get(),
         ))
     }
 
@@ -891,8 +919,19 @@ impl CanisterManager {
         let canister_to_delete = state.take_canister_state(&canister_id_to_delete).unwrap();
         state.delete_snapshots(canister_to_delete.canister_id());
 
+        // Drop the management calls scheduled for the canister. The cycles held
+        // for scheduled deposits to it are discarded along with its balance.
+        let held_cycles: Cycles = state
+            .metadata
+            .subnet_call_context_manager
+            .remove_scheduled_management_calls_for_canister(canister_id_to_delete)
+            .into_iter()
+            .map(|call| call.cycles)
+            .sum();
+
         // Leftover cycles in the balance are considered `consumed`.
-        let leftover_cycles = NominalCycles::from(canister_to_delete.system_state.balance());
+        let leftover_cycles =
+            NominalCycles::from(canister_to_delete.system_state.balance() + held_cycles);
         let consumed_cycles_by_canister_to_delete = leftover_cycles
             + canister_to_delete
                 .system_state
@@ -1271,9 +1271,10 @@ fn get_canister_status_of_stopped_canister() {
         let canister = get_stopped_canister(canister_id);
         state.put_canister_state(canister);
 
+        let time = state.time();
         let canister = state.canister_state_mut(&canister_id).unwrap();
         let status = canister_manager
-            .get_canister_status(sender, canister, SMALL_APP_SUBNET_MAX_SIZE)
+            .get_canister_status(sender, canister, SMALL_APP_SUBNET_MAX_SIZE, time)
             .unwrap()
             .status();
         assert_eq!(status, CanisterStatusType::Stopped);
@@ -1288,9 +1289,10 @@ fn get_canister_status_of_stopping_canister() {
         let canister = get_stopping_canister(canister_id);
         state.put_canister_state(canister);
 
+        let time = state.time();
         let canister = state.canister_state_mut(&canister_id).unwrap();
         let status = canister_manager
-            .get_canister_status(sender, canister, SMALL_APP_SUBNET_MAX_SIZE)
+            .get_canister_status(sender, canister, SMALL_APP_SUBNET_MAX_SIZE, time)
             .unwrap()
             .status();
         assert_eq!(status, CanisterStatusType::Stopping);
@@ -3,7 +3,9 @@ use ic_crypto_sha2::Sha256;
 use ic_cycles_account_manager::{CyclesAccountManager, ResourceSaturation};
 use ic_error_types::{ErrorCode, UserError};
 use ic_interfaces::execution_environment::SubnetAvailableMemory;
-use ic_management_canister_types_private::{CanisterSettingsArgs, LogVisibilityV2, HASH_LENGTH};
+use ic_management_canister_types_private::{
+    CanisterHistoryVisibility, CanisterSettingsArgs, LogVisibilityV2, HASH_LENGTH,
+};
 use ic_replicated_state::MessageMemoryUsage;
 use ic_types::{
     ComputeAllocation, Cycles, InvalidComputeAllocationError, InvalidMemoryAllocationError,
@@ -28,11 +30,17 @@ pub(crate) struct CanisterSettings {
     pub(crate) memory_allocation: Option<MemoryAllocation>,
     /// Threshold used for activation of canister_on_low_wasm_memory hook.
     pub(crate) wasm_memory_threshold: Option<NumBytes>,
+    /// Threshold used for activation of canister_on_low_cycles hook.
+    pub(crate) cycles_threshold: Option<Cycles>,
+    /// Maximum amount of cycles the canister may burn per cycles burn limit window.
+    pub(crate) cycles_burn_limit: Option<Cycles>,
     pub(crate) freezing_threshold: Option<NumSeconds>,
     pub(crate) reserved_cycles_limit: Option<Cycles>,
     pub(crate) log_visibility: Option<LogVisibilityV2>,
     pub(crate) wasm_memory_limit: Option<NumBytes>,
     pub(crate) environment_variables: Option<EnvironmentVariables>,
+    /// Who may fetch the extended canister history.
+    pub(crate) history_visibility: Option<CanisterHistoryVisibility>,
 }
 
 impl CanisterSettings {
@@ -41,22 +49,28 @@ impl CanisterSettings {
         compute_allocation: Option<ComputeAllocation>,
         memory_allocation: Option<MemoryAllocation>,
         wasm_memory_threshold: Option<NumBytes>,
+        cycles_threshold: Option<Cycles>,
+        cycles_burn_limit: Option<Cycles>,
         freezing_threshold: Option<NumSeconds>,
         reserved_cycles_limit: Option<Cycles>,
         log_visibility: Option<LogVisibilityV2>,
         wasm_memory_limit: Option<NumBytes>,
         environment_variables: Option<EnvironmentVariables>,
+        history_visibility: Option<CanisterHistoryVisibility>,
     ) -> Self {
         Self {
             controllers,
             compute_allocation,
             memory_allocation,
             wasm_memory_threshold,
+            cycles_threshold,
+            cycles_burn_limit,
             freezing_threshold,
             reserved_cycles_limit,
             log_visibility,
             wasm_memory_limit,
             environment_variables,
+            history_visibility,
         }
     }
 
@@ -76,6 +90,14 @@ impl CanisterSettings {
         self.wasm_memory_threshold
     }
 
+    pub fn cycles_threshold(&self) -> Option<Cycles> {
+        self.cycles_threshold
+    }
+
+    pub fn cycles_burn_limit(&self) -> Option<Cycles> {
+        self.cycles_burn_limit
+    }
+
     pub fn freezing_threshold(&self) -> Option<NumSeconds> {
         self.freezing_threshold
     }
@@ -95,6 +117,10 @@ impl CanisterSettings {
     pub fn environment_variables(&self) -> Option<&EnvironmentVariables> {
         self.environment_variables.as_ref()
     }
+
+    pub fn history_visibility(&self) -> Option<CanisterHistoryVisibility> {
+        self.history_visibility
+    }
 }
 
 impl TryFrom<CanisterSettingsArgs> for CanisterSettings {
@@ -158,6 +184,20 @@ impl TryFrom<CanisterSettingsArgs> for CanisterSettings {
             None => None,
         };
 
+        let cycles_threshold = match input.cycles_threshold {
+            Some(ct) => Some(Cycles::from(ct.0.to_u128().ok_or(
+                UpdateSettingsError::CyclesThresholdOutOfRange { provided: ct },
+            )?)),
+            None => None,
+        };
+
+        let cycles_burn_limit = match input.cycles_burn_limit {
+            Some(limit) => Some(Cycles::from(limit.0.to_u128().ok_or(
+                UpdateSettingsError::CyclesBurnLimitOutOfRange { provided: limit },
+            )?)),
+            None => None,
+        };
+
         let environment_variables = input.environment_variables.map(|env_vars| {
             EnvironmentVariables::new(env_vars.into_iter().map(|e| (e.name, e.value)).collect())
         });
@@ -169,11 +209,14 @@ impl TryFrom<CanisterSettingsArgs> for CanisterSettings {
             compute_allocation,
             memory_allocation,
             wasm_memory_threshold,
+            cycles_threshold,
+            cycles_burn_limit,
             freezing_threshold,
             reserved_cycles_limit,
             input.log_visibility,
             wasm_memory_limit,
             environment_variables,
+            input.history_visibility,
         ))
     }
 }
@@ -194,11 +237,14 @@ pub(crate) struct CanisterSettingsBuilder {
     compute_allocation: Option<ComputeAllocation>,
     memory_allocation: Option<MemoryAllocation>,
     wasm_memory_threshold: Option<NumBytes>,
+    cycles_threshold: Option<Cycles>,
+    cycles_burn_limit: Option<Cycles>,
     freezing_threshold: Option<NumSeconds>,
     reserved_cycles_limit: Option<Cycles>,
     log_visibility: Option<LogVisibilityV2>,
     wasm_memory_limit: Option<NumBytes>,
     environment_variables: Option<EnvironmentVariables>,
+    history_visibility: Option<CanisterHistoryVisibility>,
 }
 
 #[allow(dead_code)]
@@ -209,11 +255,14 @@ impl CanisterSettingsBuilder {
             compute_allocation: None,
             memory_allocation: None,
             wasm_memory_threshold: None,
+            cycles_threshold: None,
+            cycles_burn_limit: None,
             freezing_threshold: None,
             reserved_cycles_limit: None,
             log_visibility: None,
             wasm_memory_limit: None,
             environment_variables: None,
+            history_visibility: None,
         }
     }
 
@@ -223,11 +272,14 @@ impl CanisterSettingsBuilder {
             compute_allocation: self.compute_allocation,
             memory_allocation: self.memory_allocation,
             wasm_memory_threshold: self.wasm_memory_threshold,
+            cycles_threshold: self.cycles_threshold,
+            cycles_burn_limit: self.cycles_burn_limit,
             freezing_threshold: self.freezing_threshold,
             reserved_cycles_limit: self.reserved_cycles_limit,
             log_visibility: self.log_visibility,
             wasm_memory_limit: self.wasm_memory_limit,
             environment_variables: self.environment_variables,
+            history_visibility: self.history_visibility,
         }
     }
 
@@ -259,6 +311,20 @@ impl CanisterSettingsBuilder {
         }
     }
 
+    pub fn with_cycles_threshold(self, cycles_threshold: Cycles) -> Self {
+        Self {
+            cycles_threshold: Some(cycles_threshold),
+            ..self
+        }
+    }
+
+    pub fn with_cycles_burn_limit(self, cycles_burn_limit: Cycles) -> Self {
+        Self {
+            cycles_burn_limit: Some(cycles_burn_limit),
+            ..self
+        }
+    }
+
     pub fn with_freezing_threshold(self, freezing_threshold: NumSeconds) -> Self {
         Self {
             freezing_threshold: Some(freezing_threshold),
@@ -293,6 +359,13 @@ impl CanisterSettingsBuilder {
             ..self
         }
     }
+
+    pub fn with_history_visibility(self, history_visibility: CanisterHistoryVisibility) -> Self {
+        Self {
+            history_visibility: Some(history_visibility),
+            ..self
+        }
+    }
 }
 
 pub enum UpdateSettingsError {
@@ -302,6 +375,8 @@ pub enum UpdateSettingsError {
     ReservedCyclesLimitOutOfRange { provided: candid::Nat },
     WasmMemoryLimitOutOfRange { provided: candid::Nat },
     WasmMemoryThresholdOutOfRange { provided: candid::Nat },
+    CyclesThresholdOutOfRange { provided: candid::Nat },
+    CyclesBurnLimitOutOfRange { provided: candid::Nat },
 }
 
 impl From<UpdateSettingsError> for UserError {
@@ -351,6 +426,20 @@ impl From<UpdateSettingsError> for UserError {
                     provided
                 ),
             ),
+            UpdateSettingsError::CyclesThresholdOutOfRange { provided } => UserError::new(
+                ErrorCode::CanisterContractViolation,
+                format!(
+                    "Cycles threshold expected to be in the range of [0..2^128-1], got {}",
+                    provided
+                ),
+            ),
+            UpdateSettingsError::CyclesBurnLimitOutOfRange { provided } => UserError::new(
+                ErrorCode::CanisterContractViolation,
+                format!(
+                    "Cycles burn limit expected to be in the range of [0..2^128-1], got {}",
+                    provided
+                ),
+            ),
         }
     }
 }
@@ -372,12 +461,15 @@ pub(crate) struct ValidatedCanisterSettings {
     compute_allocation: Option<ComputeAllocation>,
     memory_allocation: Option<MemoryAllocation>,
     wasm_memory_threshold: Option<NumBytes>,
+    cycles_threshold: Option<Cycles>,
+    cycles_burn_limit: Option<Cycles>,
     freezing_threshold: Option<NumSeconds>,
     reserved_cycles_limit: Option<Cycles>,
     reservation_cycles: Cycles,
     log_visibility: Option<LogVisibilityV2>,
     wasm_memory_limit: Option<NumBytes>,
     environment_variables: Option<EnvironmentVariables>,
+    history_visibility: Option<CanisterHistoryVisibility>,
 }
 
 impl ValidatedCanisterSettings {
@@ -397,6 +489,14 @@ impl ValidatedCanisterSettings {
         self.wasm_memory_threshold
     }
 
+    pub fn cycles_threshold(&self) -> Option<Cycles> {
+        self.cycles_threshold
+    }
+
+    pub fn cycles_burn_limit(&self) -> Option<Cycles> {
+        self.cycles_burn_limit
+    }
+
     pub fn freezing_threshold(&self) -> Option<NumSeconds> {
         self.freezing_threshold
     }
@@ -420,6 +520,10 @@ impl ValidatedCanisterSettings {
     pub fn environment_variables(&self) -> Option<&EnvironmentVariables> {
         self.environment_variables.as_ref()
     }
+
+    pub fn history_visibility(&self) -> Option<CanisterHistoryVisibility> {
+        self.history_visibility
+    }
 }
 
 #[derive(Clone)]
@@ -647,11 +751,14 @@ pub(crate) fn validate_canister_settings(
         compute_allocation: settings.compute_allocation(),
         memory_allocation: settings.memory_allocation(),
         wasm_memory_threshold: settings.wasm_memory_threshold(),
+        cycles_threshold: settings.cycles_threshold(),
+        cycles_burn_limit: settings.cycles_burn_limit(),
         freezing_threshold: settings.freezing_threshold(),
         reserved_cycles_limit: settings.reserved_cycles_limit(),
         reservation_cycles,
         log_visibility: settings.log_visibility().cloned(),
         wasm_memory_limit: settings.wasm_memory_limit(),
         environment_variables: settings.environment_variables().cloned(),
+        history_visibility: settings.history_visibility(),
     })
 }
@@ -23,7 +23,7 @@ use ic_interfaces::execution_environment::{
 use ic_logger::{info, ReplicaLogger};
 use ic_replicated_state::{
     canister_state::execution_state::WasmExecutionMode, num_bytes_try_from, CallContextAction,
-    CallOrigin, CanisterState,
+    CallOrigin, CanisterState, ExecutionTask,
 };
 use ic_types::messages::{
     CallContextId, CanisterCall, CanisterCallOrTask, CanisterMessage, CanisterMessageOrTask,
@@ -37,6 +37,24 @@ use ic_wasm_types::WasmEngineError::FailedToApplySystemChanges;
 #[cfg(test)]
 mod tests;
 
+/// A hook task that is taken from the task queue but not executed, because
+/// the canister is frozen or over its cycles burn limit, must become `Ready`
+/// again so that it is executed once the canister can run it.
+///
+/// The hook is taken from the task queue with its status set to `Executed`.
+/// Because of the way the hook status `update` is implemented, we first need to
+/// remove it from the task queue (which calls `update(false)`) followed with
+/// `enqueue` (which calls `update(true)`) to ensure the desired behavior.
+fn rearm_hook(canister: &mut CanisterState, call_or_task: &CanisterCallOrTask) {
+    let task = match call_or_task {
+        CanisterCallOrTask::Task(CanisterTask::OnLowWasmMemory) => ExecutionTask::OnLowWasmMemory,
+        CanisterCallOrTask::Task(CanisterTask::OnLowCycles) => ExecutionTask::OnLowCycles,
+        _ => return,
+    };
+    canister.system_state.task_queue.remove(task.clone());
+    canister.system_state.task_queue.enqueue(task);
+}
+
 // Execute an inter-canister call message or a canister task.
 #[allow(clippy::too_many_arguments)]
 pub fn execute_call_or_task(
@@ -70,9 +88,29 @@ pub fn execute_call_or_task(
                     .as_ref()
                     .map_or(WasmExecutionMode::Wasm32, |es| es.wasm_execution_mode);
 
-                let prepaid_execution_cycles = match round
+                // Calls and tasks are rejected once the canister has burned its
+                // configured cycles burn limit within the rolling window. Tasks
+                // are included because a runaway heartbeat or timer is the most
+                // common way for a canister to drain its balance.
+                canister.system_state.update_cycles_burn_window(time);
+                if let Err(err) = round
                     .cycles_account_manager
-                    .prepay_execution_cycles(
+                    .check_cycles_burn_limit(&canister.system_state, time)
+                {
+                    rearm_hook(&mut canister, &call_or_task);
+                    return finish_call_with_error(
+                        UserError::new(ErrorCode::CanisterCyclesBurnLimitExceeded, err),
+                        canister,
+                        call_or_task,
+                        NumInstructions::from(0),
+                        round.time,
+                        execution_parameters.subnet_type,
+                        round.log,
+                    );
+                }
+
+                let prepaid_execution_cycles =
+                    match round.cycles_account_manager.prepay_execution_cycles(
                         &mut canister.system_state,
                         memory_usage,
                         message_memory_usage,
@@ -82,35 +120,20 @@ pub fn execute_call_or_task(
                         reveal_top_up,
                         wasm_execution_mode,
                     ) {
-                    Ok(cycles) => cycles,
-                    Err(err) => {
-                        if call_or_task == CanisterCallOrTask::Task(CanisterTask::OnLowWasmMemory) {
-                            //`OnLowWasmMemoryHook` is taken from task_queue (i.e. `OnLowWasmMemoryHookStatus` is `Executed`),
-                            // but it was not executed due to the freezing of the canister. To ensure that the hook is executed
-                            // when the canister is unfrozen we need to set `OnLowWasmMemoryHookStatus` to `Ready`. Because of
-                            // the way `OnLowWasmMemoryHookStatus::update` is implemented we first need to remove it from the
-                            // task_queue (which calls `OnLowWasmMemoryHookStatus::update(false)`) followed with `enqueue`
-                            // (which calls `OnLowWasmMemoryHookStatus::update(true)`) to ensure desired behavior.
-                            canister
-                                .system_state
-                                .task_queue
-                                .remove(ic_replicated_state::ExecutionTask::OnLowWasmMemory);
-                            canister
-                                .system_state
-                                .task_queue
-                                .enqueue(ic_replicated_state::ExecutionTask::OnLowWasmMemory);
+                        Ok(cycles) => cycles,
+                        Err(err) => {
+                            rearm_hook(&mut canister, &call_or_task);
+                            return finish_call_with_error(
+                                UserError::new(ErrorCode::CanisterOutOfCycles, err),
+                                canister,
+                                call_or_task,
+                                NumInstructions::from(0),
+                                round.time,
+                                execution_parameters.subnet_type,
+                                round.log,
+                            );
                         }
-                        return finish_call_with_error(
-                            UserError::new(ErrorCode::CanisterOutOfCycles, err),
-                            canister,
-                            call_or_task,
-                            NumInstructions::from(0),
-                            round.time,
-                            execution_parameters.subnet_type,
-                            round.log,
-                        );
-                    }
-                };
+                    };
                 (canister, prepaid_execution_cycles, false)
             }
         };
@@ -191,6 +214,11 @@ pub fn execute_call_or_task(
             time,
             helper.call_context_id(),
         ),
+        CanisterCallOrTask::Task(CanisterTask::OnLowCycles) => ApiType::system_task(
+            SystemMethod::CanisterOnLowCycles,
+            time,
+            helper.call_context_id(),
+        ),
     };
 
     let memory_usage = helper.canister().memory_usage();
@@ -400,7 +428,8 @@ impl CallOrTaskHelper {
             CanisterCallOrTask::Update(_)
             | CanisterCallOrTask::Query(_)
             | CanisterCallOrTask::Task(CanisterTask::Heartbeat)
-            | CanisterCallOrTask::Task(CanisterTask::OnLowWasmMemory) => {}
+            | CanisterCallOrTask::Task(CanisterTask::OnLowWasmMemory)
+            | CanisterCallOrTask::Task(CanisterTask::OnLowCycles) => {}
             CanisterCallOrTask::Task(CanisterTask::GlobalTimer) => {
                 // The global timer is one-off.
                 canister.system_state.global_timer = CanisterTimer::Inactive;
@@ -13,6 +13,7 @@ use crate::execution::install_code::{
 };
 use crate::execution_environment::{RoundContext, RoundLimits};
 use ic_base_types::PrincipalId;
+use ic_crypto_sha2::Sha256;
 use ic_embedders::{
     wasm_executor::{CanisterStateChanges, PausedWasmExecution, WasmExecutionResult},
     wasmtime_embedder::system_api::ApiType,
@@ -144,7 +145,13 @@ pub(crate) fn execute_install(
     helper.clear_log();
     helper.deactivate_global_timer();
     helper.bump_canister_version();
-    helper.add_canister_change(round.time, context.origin, context.mode, module_hash.into());
+    helper.add_canister_change(
+        round.time,
+        context.origin,
+        context.mode,
+        module_hash.into(),
+        Sha256::hash(&context.arg),
+    );
 
     // Stage 2: invoke the `start()` method of the Wasm module (if present).
     let method = WasmMethod::System(SystemMethod::CanisterStart);
@@ -15,7 +15,7 @@ use ic_interfaces::execution_environment::{
 };
 use ic_logger::{error, fatal, info, warn};
 use ic_management_canister_types_private::{
-    CanisterChangeDetails, CanisterChangeOrigin, CanisterInstallModeV2,
+    CanisterChangeDetails, CanisterChangeOrigin, CanisterInstallModeV2, HASH_LENGTH,
 };
 use ic_replicated_state::canister_state::system_state::ReservationError;
 use ic_replicated_state::metadata_state::subnet_call_context_manager::InstallCodeCallId;
@@ -84,6 +84,7 @@ pub(crate) enum InstallCodeStep {
         origin: CanisterChangeOrigin,
         mode: CanisterInstallModeV2,
         module_hash: WasmHash,
+        arg_hash: [u8; HASH_LENGTH],
     },
     HandleWasmExecution {
         canister_state_changes: CanisterStateChanges,
@@ -169,17 +170,22 @@ impl InstallCodeHelper {
         origin: CanisterChangeOrigin,
         mode: CanisterInstallModeV2,
         module_hash: WasmHash,
+        arg_hash: [u8; HASH_LENGTH],
     ) {
         self.steps.push(InstallCodeStep::AddCanisterChange {
             timestamp_nanos,
             origin: origin.clone(),
             mode,
             module_hash: module_hash.clone(),
+            arg_hash,
         });
         let details = CanisterChangeDetails::code_deployment(mode.into(), module_hash.to_slice());
-        self.canister
-            .system_state
-            .add_canister_change(timestamp_nanos, origin, details);
+        self.canister.system_state.add_code_deployment_change(
+            timestamp_nanos,
+            origin,
+            details,
+            arg_hash,
+        );
     }
 
     pub fn charge_for_large_wasm_assembly(&mut self, instructions: NumInstructions) {
@@ -767,8 +773,9 @@ impl InstallCodeHelper {
                 origin,
                 mode,
                 module_hash,
+                arg_hash,
             } => {
-                self.add_canister_change(timestamp_nanos, origin, mode, module_hash);
+                self.add_canister_change(timestamp_nanos, origin, mode, module_hash, arg_hash);
                 Ok(())
             }
             InstallCodeStep::HandleWasmExecution {
@@ -15,7 +15,7 @@ use ic_replicated_state::{CallOrigin, CanisterState, NetworkTopology};
 use ic_types::ingress::WasmResult;
 use ic_types::messages::{CallContextId, RequestMetadata};
 use ic_types::methods::{FuncRef, WasmMethod};
-use ic_types::{Cycles, NumInstructions, Time};
+use ic_types::{Cycles, NumInstructions, Time, UserId};
 use prometheus::IntCounter;
 
 // Execute non replicated query.
@@ -105,6 +105,28 @@ pub fn execute_non_replicated_query(
                 Some(call_context_id),
             )
         }
+        NonReplicatedQueryKind::SimulatedUpdate { caller } => {
+            preserve_changes = true;
+            let call_context_id = canister
+                .system_state
+                .new_call_context(
+                    CallOrigin::Query(UserId::from(caller)),
+                    Cycles::zero(),
+                    time,
+                    RequestMetadata::for_new_call_tree(time),
+                )
+                .unwrap();
+            (
+                ApiType::update(
+                    time,
+                    payload.to_vec(),
+                    Cycles::zero(),
+                    caller,
+                    call_context_id,
+                ),
+                Some(call_context_id),
+            )
+        }
     };
 
     // As we are executing the query in non-replicated mode, we can
@@ -14,6 +14,7 @@ use crate::execution::install_code::{
 };
 use crate::execution_environment::{RoundContext, RoundLimits};
 use ic_base_types::PrincipalId;
+use ic_crypto_sha2::Sha256;
 use ic_embedders::{
     wasm_executor::{CanisterStateChanges, PausedWasmExecution, WasmExecutionResult},
     wasmtime_embedder::system_api::ApiType,
@@ -337,7 +338,13 @@ fn upgrade_stage_2_and_3a_create_execution_state_and_call_start(
 
     helper.deactivate_global_timer();
     helper.bump_canister_version();
-    helper.add_canister_change(round.time, context.origin, context.mode, module_hash.into());
+    helper.add_canister_change(
+        round.time,
+        context.origin,
+        context.mode,
+        module_hash.into(),
+        Sha256::hash(&context.arg),
+    );
 
     // Stage 3: invoke the `start()` method (if present).
     let method = WasmMethod::System(SystemMethod::CanisterStart);
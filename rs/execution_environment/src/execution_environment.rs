@@ -33,22 +33,29 @@ use ic_interfaces::execution_environment::{
     ChainKeyData, ExecutionMode, IngressHistoryWriter, RegistryExecutionSettings,
     SubnetAvailableMemory,
 };
-use ic_limits::{LOG_CANISTER_OPERATION_CYCLES_THRESHOLD, SMALL_APP_SUBNET_MAX_SIZE};
+use ic_limits::{
+    LOG_CANISTER_OPERATION_CYCLES_THRESHOLD, MAX_INGRESS_TTL, SMALL_APP_SUBNET_MAX_SIZE,
+};
 use ic_logger::{error, info, warn, ReplicaLogger};
 use ic_management_canister_types_private::{
-    CanisterChangeOrigin, CanisterHttpRequestArgs, CanisterIdRecord, CanisterInfoRequest,
-    CanisterInfoResponse, CanisterStatusType, ClearChunkStoreArgs, ComputeInitialIDkgDealingsArgs,
-    CreateCanisterArgs, DeleteCanisterSnapshotArgs, ECDSAPublicKeyArgs, ECDSAPublicKeyResponse,
-    EmptyBlob, InstallChunkedCodeArgs, InstallCodeArgsV2, ListCanisterSnapshotArgs,
+    CancelScheduledManagementCallArgs, CanisterChangeOrigin, CanisterHttpRequestArgs,
+    CanisterIdRecord, CanisterInfoRequest, CanisterInfoResponse, CanisterStatusType,
+    ClearChunkStoreArgs, ComputeInitialIDkgDealingsArgs, CreateCanisterArgs,
+    DeleteCanisterSnapshotArgs, ECDSAPublicKeyArgs, ECDSAPublicKeyResponse, EmptyBlob,
+    InstallChunkedCodeArgs, InstallCodeArgsV2, ListCanisterSnapshotArgs,
+    ListScheduledManagementCallsArgs, ListScheduledManagementCallsResponse,
     LoadCanisterSnapshotArgs, MasterPublicKeyId, Method as Ic00Method, NodeMetricsHistoryArgs,
     Payload as Ic00Payload, ProvisionalCreateCanisterWithCyclesArgs, ProvisionalTopUpCanisterArgs,
     ReadCanisterSnapshotDataArgs, ReadCanisterSnapshotMetadataArgs, RenameCanisterArgs,
-    ReshareChainKeyArgs, SchnorrAlgorithm, SchnorrPublicKeyArgs, SchnorrPublicKeyResponse,
-    SetupInitialDKGArgs, SignWithECDSAArgs, SignWithSchnorrArgs, SignWithSchnorrAux,
-    StoredChunksArgs, SubnetInfoArgs, SubnetInfoResponse, TakeCanisterSnapshotArgs,
-    UninstallCodeArgs, UpdateSettingsArgs, UploadCanisterSnapshotDataArgs,
-    UploadCanisterSnapshotMetadataArgs, UploadCanisterSnapshotMetadataResponse, UploadChunkArgs,
-    VetKdDeriveKeyArgs, VetKdPublicKeyArgs, VetKdPublicKeyResult, IC_00,
+    ReshareChainKeyArgs, ScheduleManagementCallArgs, ScheduleManagementCallResult,
+    ScheduledManagementCallRecord, ScheduledManagementCallStatusArgs,
+    ScheduledManagementCallStatusResponse, SchnorrAlgorithm, SchnorrPublicKeyArgs,
+    SchnorrPublicKeyResponse, SetupInitialDKGArgs, SignWithECDSAArgs, SignWithSchnorrArgs,
+    SignWithSchnorrAux, StoredChunksArgs, SubnetInfoArgs, SubnetInfoResponse,
+    TakeCanisterSnapshotArgs, UninstallCodeArgs, UpdateSettingsArgs,
+    UploadCanisterSnapshotDataArgs, UploadCanisterSnapshotMetadataArgs,
+    UploadCanisterSnapshotMetadataResponse, UploadChunkArgs, VetKdDeriveKeyArgs,
+    VetKdPublicKeyArgs, VetKdPublicKeyResult, IC_00,
 };
 use ic_metrics::MetricsRegistry;
 use ic_registry_provisional_whitelist::ProvisionalWhitelist;
@@ -61,8 +68,9 @@ use ic_replicated_state::{
     },
     metadata_state::subnet_call_context_manager::{
         EcdsaArguments, InstallCodeCall, InstallCodeCallId, ReshareChainKeyContext,
-        SchnorrArguments, SetupInitialDkgContext, SignWithThresholdContext, StopCanisterCall,
-        SubnetCallContext, ThresholdArguments, VetKdArguments,
+        ScheduledManagementCall, ScheduledManagementCallId, SchnorrArguments,
+        SetupInitialDkgContext, SignWithThresholdContext, StopCanisterCall, SubnetCallContext,
+        ThresholdArguments, VetKdArguments,
     },
     page_map::PageAllocatorFileDescriptor,
     CanisterState, ExecutionTask, NetworkTopology, ReplicatedState,
@@ -77,14 +85,14 @@ use ic_types::{
     ingress::{IngressState, IngressStatus, WasmResult},
     messages::{
         extract_effective_canister_id, CanisterCall, CanisterCallOrTask, CanisterMessage,
-        CanisterMessageOrTask, CanisterTask, Payload, RejectContext, Request, Response,
+        CanisterMessageOrTask, CanisterTask, Ingress, Payload, RejectContext, Request, Response,
         SignedIngressContent, StopCanisterCallId, StopCanisterContext,
         MAX_INTER_CANISTER_PAYLOAD_IN_BYTES,
     },
     methods::SystemMethod,
     nominal_cycles::NominalCycles,
     CanisterId, Cycles, ExecutionRound, Height, NumBytes, NumInstructions, ReplicaVersion,
-    SubnetId, Time,
+    SubnetId, Time, UserId,
 };
 use ic_types::{messages::MessageId, methods::WasmMethod};
 use ic_utils_thread::deallocator_thread::{DeallocationSender, DeallocatorThread};
@@ -1537,16 +1545,15 @@ impl ExecutionEnvironment {
                 }
             },
 
-            Ok(Ic00Method::FetchCanisterLogs) => ExecuteSubnetMessageResult::Finished {
-                response: Err(UserError::new(
-                    ErrorCode::CanisterRejectedMessage,
-                    format!(
-                        "{} API is only accessible in non-replicated mode",
-                        Ic00Method::FetchCanisterLogs
-                    ),
-                )),
-                refund: msg.take_cycles(),
-            },
+            Ok(method @ (Ic00Method::FetchCanisterLogs | Ic00Method::FetchCanisterHistory)) => {
+                ExecuteSubnetMessageResult::Finished {
+                    response: Err(UserError::new(
+                        ErrorCode::CanisterRejectedMessage,
+                        format!("{} API is only accessible in non-replicated mode", method),
+                    )),
+                    refund: msg.take_cycles(),
+                }
+            }
 
             Ok(Ic00Method::TakeCanisterSnapshot) => match TakeCanisterSnapshotArgs::decode(payload)
             {
@@ -1753,6 +1760,80 @@ impl ExecutionEnvironment {
                 }
             }
 
+            Ok(Ic00Method::ScheduleManagementCall) => {
+                match ScheduleManagementCallArgs::decode(payload) {
+                    Err(err) => ExecuteSubnetMessageResult::Finished {
+                        response: Err(err),
+                        refund: msg.take_cycles(),
+                    },
+                    Ok(args) => {
+                        let canister_id = args.get_canister_id();
+                        let cycles = msg.take_cycles();
+                        match self.schedule_management_call(
+                            *msg.sender(),
+                            cycles,
+                            args,
+                            &mut state,
+                            round_limits,
+                            registry_settings.subnet_size,
+                        ) {
+                            Ok(res) => ExecuteSubnetMessageResult::Finished {
+                                response: Ok((res, Some(canister_id))),
+                                refund: Cycles::zero(),
+                            },
+                            Err(err) => ExecuteSubnetMessageResult::Finished {
+                                response: Err(err),
+                                refund: cycles,
+                            },
+                        }
+                    }
+                }
+            }
+
+            Ok(Ic00Method::ListScheduledManagementCalls) => {
+                let res = ListScheduledManagementCallsArgs::decode(payload).and_then(|args| {
+                    let canister_id = args.get_canister_id();
+                    self.list_scheduled_management_calls(*msg.sender(), args, &state)
+                        .map(|res| (res, Some(canister_id)))
+                });
+                ExecuteSubnetMessageResult::Finished {
+                    response: res,
+                    refund: msg.take_cycles(),
+                }
+            }
+
+            Ok(Ic00Method::CancelScheduledManagementCall) => {
+                let res = CancelScheduledManagementCallArgs::decode(payload).and_then(|args| {
+                    let canister_id = args.get_canister_id();
+                    self.cancel_scheduled_management_call(&msg, args, &mut state)
+                        .map(|held_cycles| (canister_id, held_cycles))
+                });
+                match res {
+                    Ok((canister_id, held_cycles)) => ExecuteSubnetMessageResult::Finished {
+                        response: Ok((EmptyBlob.encode(), Some(canister_id))),
+                        // The cycles held on behalf of the cancelled call are
+                        // returned to the caller.
+                        refund: msg.take_cycles() + held_cycles,
+                    },
+                    Err(err) => ExecuteSubnetMessageResult::Finished {
+                        response: Err(err),
+                        refund: msg.take_cycles(),
+                    },
+                }
+            }
+
+            Ok(Ic00Method::ScheduledManagementCallStatus) => {
+                let res = ScheduledManagementCallStatusArgs::decode(payload).map(|args| {
+                    let canister_id = args.get_canister_id();
+                    let res = self.scheduled_management_call_status(*msg.sender(), args, &state);
+                    (res, Some(canister_id))
+                });
+                ExecuteSubnetMessageResult::Finished {
+                    response: res,
+                    refund: msg.take_cycles(),
+                }
+            }
+
             Err(ParseError::VariantNotFound) => {
                 let res = Err(UserError::new(
                     ErrorCode::CanisterMethodNotFound,
@@ -1860,6 +1941,7 @@ impl ExecutionEnvironment {
                         if let Some(canister_id) = canister_id {
                             if let Some(canister_state) = state.canister_state_mut(canister_id) {
                                 canister_state.update_on_low_wasm_memory_hook_condition();
+                                canister_state.update_on_low_cycles_hook_condition();
                             }
                         }
                         Ok(res)
@@ -2225,6 +2307,7 @@ impl ExecutionEnvironment {
                 canister_state
                     .system_state
                     .add_cycles(cycles, CyclesUseCase::NonConsumed);
+                canister_state.update_on_low_cycles_hook_condition();
                 if cycles.get() > LOG_CANISTER_OPERATION_CYCLES_THRESHOLD {
                     info!(
                         self.log,
@@ -2249,10 +2332,11 @@ impl ExecutionEnvironment {
         state: &mut ReplicatedState,
         subnet_size: usize,
     ) -> Result<Vec<u8>, UserError> {
+        let time = state.time();
         let canister = get_canister_mut(canister_id, state)?;
 
         self.canister_manager
-            .get_canister_status(sender, canister, subnet_size)
+            .get_canister_status(sender, canister, subnet_size, time)
             .map(|status| status.encode())
             .map_err(|err| err.into())
     }
@@ -2625,6 +2709,424 @@ impl ExecutionEnvironment {
         result
     }
 
+    /// Schedules a management canister call targeting a canister controlled
+    /// by `sender` for execution by the system at the requested time.
+    ///
+    /// The target canister pays upfront for storing the call until it is due,
+    /// at the same rate as for its own memory. The fee is not refunded if the
+    /// call is cancelled. The stored call takes subnet memory until it is due.
+    fn schedule_management_call(
+        &self,
+        sender: PrincipalId,
+        cycles: Cycles,
+        args: ScheduleManagementCallArgs,
+        state: &mut ReplicatedState,
+        round_limits: &mut RoundLimits,
+        subnet_size: usize,
+    ) -> Result<Vec<u8>, UserError> {
+        let canister_id = args.get_canister_id();
+        validate_controller(get_canister(canister_id, state)?, &sender)?;
+
+        let method = Ic00Method::from_str(&args.method_name).ok();
+        let target = match method.and_then(|method| scheduled_call_target(method, &args.arg)) {
+            Some(target) => target?,
+            None => {
+                return Err(UserError::new(
+                    ErrorCode::InvalidManagementPayload,
+                    format!(
+                        "Management canister method '{}' cannot be scheduled.",
+                        args.method_name
+                    ),
+                ))
+            }
+        };
+        if target != canister_id {
+            return Err(UserError::new(
+                ErrorCode::InvalidManagementPayload,
+                format!(
+                    "The scheduled call targets canister {} but was scheduled for canister {}.",
+                    target, canister_id
+                ),
+            ));
+        }
+        if !cycles.is_zero() && method != Some(Ic00Method::DepositCycles) {
+            return Err(UserError::new(
+                ErrorCode::CanisterRejectedMessage,
+                format!(
+                    "Only scheduled {} calls can hold cycles.",
+                    Ic00Method::DepositCycles
+                ),
+            ));
+        }
+
+        let call_size = args.method_name.len() + args.arg.len();
+        if call_size > MAX_SCHEDULED_MANAGEMENT_CALL_SIZE_BYTES {
+            return Err(UserError::new(
+                ErrorCode::InvalidManagementPayload,
+                format!(
+                    "Scheduled calls can take at most {} bytes, got {}. Use {} to schedule installing larger modules.",
+                    MAX_SCHEDULED_MANAGEMENT_CALL_SIZE_BYTES,
+                    call_size,
+                    Ic00Method::InstallChunkedCode
+                ),
+            ));
+        }
+
+        let execute_at = Time::from_nanos_since_unix_epoch(args.execute_at_nanos);
+        if execute_at <= state.time() {
+            return Err(UserError::new(
+                ErrorCode::CanisterRejectedMessage,
+                format!(
+                    "Scheduled calls must be due in the future, got {} at time {}.",
+                    execute_at,
+                    state.time()
+                ),
+            ));
+        }
+        let delay = execute_at.saturating_duration_since(state.time());
+        if delay > MAX_SCHEDULED_MANAGEMENT_CALL_DELAY {
+            return Err(UserError::new(
+                ErrorCode::CanisterRejectedMessage,
+                format!(
+                    "Scheduled calls can be due at most {} seconds in the future, got {} seconds.",
+                    MAX_SCHEDULED_MANAGEMENT_CALL_DELAY.as_secs(),
+                    delay.as_secs()
+                ),
+            ));
+        }
+
+        if state
+            .metadata
+            .subnet_call_context_manager
+            .scheduled_management_calls_for_canister(canister_id)
+            .count()
+            >= MAX_SCHEDULED_MANAGEMENT_CALLS_PER_CANISTER
+        {
+            return Err(UserError::new(
+                ErrorCode::CanisterRejectedMessage,
+                format!(
+                    "Canister {} already has the maximum of {} scheduled management calls.",
+                    canister_id, MAX_SCHEDULED_MANAGEMENT_CALLS_PER_CANISTER
+                ),
+            ));
+        }
+
+        let call_size = NumBytes::new(call_size as u64);
+        round_limits
+            .subnet_available_memory
+            .try_decrement(call_size, NumBytes::new(0), NumBytes::new(0))
+            .map_err(|_| {
+                UserError::new(
+                    ErrorCode::SubnetOversubscribed,
+                    format!(
+                        "Scheduling the call requires {} of memory but only {} are available in the subnet.",
+                        call_size.display(),
+                        NumBytes::new(
+                            round_limits
+                                .subnet_available_memory
+                                .get_execution_memory()
+                                .max(0) as u64
+                        )
+                        .display(),
+                    ),
+                )
+            })?;
+
+        let storage_fee = self
+            .cycles_account_manager
+            .memory_cost(call_size, delay, subnet_size);
+        let canister = get_canister_mut(canister_id, state)?;
+        let memory_usage = canister.memory_usage();
+        let message_memory_usage = canister.message_memory_usage();
+        let compute_allocation = canister.scheduler_state.compute_allocation;
+        self.cycles_account_manager
+            .consume_cycles(
+                &mut canister.system_state,
+                memory_usage,
+                message_memory_usage,
+                compute_allocation,
+                storage_fee,
+                subnet_size,
+                CyclesUseCase::Memory,
+                true, // the sender is a controller
+            )
+            .map_err(|err| {
+                round_limits.subnet_available_memory.increment(
+                    call_size,
+                    NumBytes::new(0),
+                    NumBytes::new(0),
+                );
+                UserError::new(ErrorCode::CanisterOutOfCycles, err.to_string())
+            })?;
+
+        let call_id = state
+            .metadata
+            .subnet_call_context_manager
+            .push_scheduled_management_call(ScheduledManagementCall {
+                sender,
+                effective_canister_id: canister_id,
+                method_name: args.method_name,
+                method_payload: args.arg,
+                cycles,
+                execute_at,
+            });
+
+        Ok(ScheduleManagementCallResult {
+            call_id: call_id.get(),
+            request_id: ScheduledManagementCall::message_id(self.own_subnet_id, call_id)
+                .as_bytes()
+                .to_vec(),
+        }
+        .encode())
+    }
+
+    /// Lists the management calls scheduled for a canister controlled by `sender`.
+    fn list_scheduled_management_calls(
+        &self,
+        sender: PrincipalId,
+        args: ListScheduledManagementCallsArgs,
+        state: &ReplicatedState,
+    ) -> Result<Vec<u8>, UserError> {
+        let canister_id = args.get_canister_id();
+        validate_controller(get_canister(canister_id, state)?, &sender)?;
+
+        let calls = state
+            .metadata
+            .subnet_call_context_manager
+            .scheduled_management_calls_for_canister(canister_id)
+            .map(|(call_id, call)| ScheduledManagementCallRecord {
+                call_id: call_id.get(),
+                request_id: ScheduledManagementCall::message_id(self.own_subnet_id, *call_id)
+                    .as_bytes()
+                    .to_vec(),
+                sender: call.sender,
+                method_name: call.method_name.clone(),
+                execute_at_nanos: call.execute_at.as_nanos_since_unix_epoch(),
+                cycles: call.cycles.into(),
+            })
+            .collect();
+        Ok(ListScheduledManagementCallsResponse(calls).encode())
+    }
+
+    /// Cancels a management call scheduled for a canister controlled by the
+    /// caller and returns the cycles held on behalf of the call.
+    fn cancel_scheduled_management_call(
+        &self,
+        msg: &CanisterCall,
+        args: CancelScheduledManagementCallArgs,
+        state: &mut ReplicatedState,
+    ) -> Result<Cycles, UserError> {
+        let canister_id = args.get_canister_id();
+        validate_controller(get_canister(canister_id, state)?, msg.sender())?;
+
+        let call_id = ScheduledManagementCallId::new(args.get_call_id());
+        let manager = &mut state.metadata.subnet_call_context_manager;
+        match manager.get_scheduled_management_call(&call_id) {
+            Some(call) if call.effective_canister_id == canister_id => {
+                // Ingress messages cannot carry the held cycles back to the caller.
+                if !call.cycles.is_zero() && matches!(msg, CanisterCall::Ingress(_)) {
+                    return Err(UserError::new(
+                        ErrorCode::CanisterRejectedMessage,
+                        format!(
+                            "Scheduled call {} holds {} and can only be cancelled by a canister.",
+                            call_id, call.cycles
+                        ),
+                    ));
+                }
+            }
+            _ => {
+                return Err(UserError::new(
+                    ErrorCode::CanisterRejectedMessage,
+                    format!(
+                        "Scheduled call {} for canister {} not found.",
+                        call_id, canister_id
+                    ),
+                ))
+            }
+        }
+        Ok(manager
+            .remove_scheduled_management_call(call_id)
+            .map(|call| call.cycles)
+            .unwrap_or_default())
+    }
+
+    /// Returns the status of a management call scheduled by `sender`.
+    ///
+    /// Calls that are not due yet are looked up among the scheduled calls of
+    /// `canister_id`. The outcome of executed calls is taken from the ingress
+    /// history, which retains it for `MAX_INGRESS_TTL` after the call
+    /// completed. Only the principal that scheduled a call can retrieve its
+    /// status, also if it is no longer a controller of the canister; calls of
+    /// other principals are reported as unknown.
+    fn scheduled_management_call_status(
+        &self,
+        sender: PrincipalId,
+        args: ScheduledManagementCallStatusArgs,
+        state: &ReplicatedState,
+    ) -> Vec<u8> {
+        let canister_id = args.get_canister_id();
+        let call_id = ScheduledManagementCallId::new(args.get_call_id());
+
+        if let Some(call) = state
+            .metadata
+            .subnet_call_context_manager
+            .get_scheduled_management_call(&call_id)
+        {
+            let status = if call.effective_canister_id == canister_id && call.sender == sender {
+                ScheduledManagementCallStatusResponse::Scheduled
+            } else {
+                ScheduledManagementCallStatusResponse::Unknown
+            };
+            return status.encode();
+        }
+
+        let message_id = ScheduledManagementCall::message_id(self.own_subnet_id, call_id);
+        let status = match state.get_ingress_status(&message_id) {
+            IngressStatus::Known { user_id, state, .. } if user_id.get() == sender => match state {
+                IngressState::Received | IngressState::Processing => {
+                    ScheduledManagementCallStatusResponse::Processing
+                }
+                IngressState::Completed(WasmResult::Reply(reply)) => {
+                    ScheduledManagementCallStatusResponse::Replied(reply.clone())
+                }
+                IngressState::Completed(WasmResult::Reject(reject_message)) => {
+                    ScheduledManagementCallStatusResponse::Rejected {
+                        reject_code: RejectCode::CanisterReject as u32,
+                        reject_message: reject_message.clone(),
+                    }
+                }
+                IngressState::Failed(err) => ScheduledManagementCallStatusResponse::Rejected {
+                    reject_code: err.reject_code() as u32,
+                    reject_message: err.description().to_string(),
+                },
+                IngressState::Done => ScheduledManagementCallStatusResponse::Done,
+            },
+            IngressStatus::Known { .. } | IngressStatus::Unknown => {
+                ScheduledManagementCallStatusResponse::Unknown
+            }
+        };
+        status.encode()
+    }
+
+    /// Hands the scheduled management calls that are due over to execution.
+    ///
+    /// Each call is enqueued as an ingress message to the management canister
+    /// on behalf of the principal that scheduled it, so that its outcome is
+    /// recorded in the ingress history under the request ID returned when the
+    /// call was scheduled. Scheduled `deposit_cycles` calls, which need the
+    /// cycles held on their behalf, are executed right away instead.
+    ///
+    /// Calls scheduled by a canister are enqueued with the canister as the
+    /// source of the ingress message. Such messages are recorded with a
+    /// canister origin in the canister history, see
+    /// `CanisterCall::canister_change_origin`, and the scheduling canister
+    /// retrieves their outcome with `scheduled_management_call_status`.
+    pub fn enqueue_due_scheduled_management_calls(&self, state: &mut ReplicatedState) {
+        let time = state.time();
+        let due_calls = state
+            .metadata
+            .subnet_call_context_manager
+            .remove_due_scheduled_management_calls(time);
+
+        for (call_id, call) in due_calls {
+            let message_id = ScheduledManagementCall::message_id(self.own_subnet_id, call_id);
+            let user_id = UserId::from(call.sender);
+
+            if matches!(
+                Ic00Method::from_str(&call.method_name),
+                Ok(Ic00Method::DepositCycles)
+            ) {
+                let ingress_state = self.deposit_scheduled_cycles(&call, state);
+                self.ingress_history_writer.set_status(
+                    state,
+                    message_id,
+                    IngressStatus::Known {
+                        receiver: IC_00.get(),
+                        user_id,
+                        time,
+                        state: ingress_state,
+                    },
+                );
+                continue;
+            }
+
+            self.ingress_history_writer.set_status(
+                state,
+                message_id.clone(),
+                IngressStatus::Known {
+                    receiver: IC_00.get(),
+                    user_id,
+                    time,
+                    state: IngressState::Received,
+                },
+            );
+            let ingress = Ingress {
+                source: user_id,
+                receiver: IC_00,
+                effective_canister_id: Some(call.effective_canister_id),
+                method_name: call.method_name,
+                method_payload: call.method_payload,
+                message_id,
+                expiry_time: time + MAX_INGRESS_TTL,
+            };
+            if let Err(err) = state.push_ingress(ingress) {
+                error!(
+                    self.log,
+                    "[EXC-BUG] Failed to enqueue scheduled management call {}: {:?}", call_id, err
+                );
+            }
+        }
+    }
+
+    /// Deposits the cycles held on behalf of a scheduled `deposit_cycles`
+    /// call. If the target canister no longer exists, the cycles are returned
+    /// to the sender, provided it is a canister on this subnet. Otherwise, the
+    /// cycles are burned and accounted for like the cycles of a deleted canister.
+    fn deposit_scheduled_cycles(
+        &self,
+        call: &ScheduledManagementCall,
+        state: &mut ReplicatedState,
+    ) -> IngressState {
+        if let Some(canister) = state.canister_state_mut(&call.effective_canister_id) {
+            canister
+                .system_state
+                .add_cycles(call.cycles, CyclesUseCase::NonConsumed);
+            return IngressState::Completed(WasmResult::Reply(EmptyBlob.encode()));
+        }
+
+        let sender = CanisterId::unchecked_from_principal(call.sender);
+        match state.canister_state_mut(&sender) {
+            Some(sender) => sender
+                .system_state
+                .add_cycles(call.cycles, CyclesUseCase::NonConsumed),
+            None => {
+                warn!(
+                    self.log,
+                    "Burning {} held for a scheduled deposit to canister {} by {}.",
+                    call.cycles,
+                    call.effective_canister_id,
+                    call.sender
+                );
+                let burned_cycles = NominalCycles::from(call.cycles);
+                state
+                    .metadata
+                    .subnet_metrics
+                    .observe_consumed_cycles_with_use_case(
+                        CyclesUseCase::DeletedCanisters,
+                        burned_cycles,
+                    );
+                state
+                    .metadata
+                    .subnet_metrics
+                    .consumed_cycles_by_deleted_canisters += burned_cycles;
+            }
+        }
+        IngressState::Failed(UserError::new(
+            ErrorCode::CanisterNotFound,
+            format!("Canister {} not found.", call.effective_canister_id),
+        ))
+    }
+
     fn read_canister_snapshot_metadata(
         &self,
         sender: PrincipalId,
@@ -2921,6 +3423,18 @@ impl ExecutionEnvironment {
             }
         }
 
+        // Reject early if the canister already burned its cycles burn limit
+        // in the current window, the message would be rejected on execution.
+        if let Err(err) = self
+            .cycles_account_manager
+            .check_cycles_burn_limit(&canister_state.system_state, state.time())
+        {
+            return Err(UserError::new(
+                ErrorCode::CanisterCyclesBurnLimitExceeded,
+                err.to_string(),
+            ));
+        }
+
         // Composite queries are not allowed to be called in replicated mode.
         let method = WasmMethod::CompositeQuery(ingress.method_name().to_string());
         if canister_state.exports_method(&method) {
@@ -3645,13 +4159,16 @@ impl ExecutionEnvironment {
         let execution_duration = since.elapsed().as_secs_f64();
         match dts_result {
             DtsInstallCodeResult::Finished {
-                canister,
+                mut canister,
                 mut message,
                 call_id,
                 instructions_used,
                 result,
             } => {
                 let canister_id = canister.canister_id();
+                // Installation is charged for even if it fails, and a successful
+                // one may add or remove the `canister_on_low_cycles` export.
+                canister.update_on_low_cycles_hook_condition();
                 let result = match result {
                     Ok(result) => {
                         state.metadata.heap_delta_estimate += result.heap_delta;
@@ -3768,6 +4285,7 @@ impl ExecutionEnvironment {
             ExecutionTask::Heartbeat
             | ExecutionTask::GlobalTimer
             | ExecutionTask::OnLowWasmMemory
+            | ExecutionTask::OnLowCycles
             | ExecutionTask::PausedExecution { .. }
             | ExecutionTask::AbortedExecution { .. } => {
                 panic!(
@@ -3862,7 +4380,8 @@ impl ExecutionEnvironment {
             | ExecutionTask::AbortedInstallCode { .. }
             | ExecutionTask::Heartbeat
             | ExecutionTask::GlobalTimer
-            | ExecutionTask::OnLowWasmMemory => {
+            | ExecutionTask::OnLowWasmMemory
+            | ExecutionTask::OnLowCycles => {
                 unreachable!(
                     "Function abort_paused_execution_and_return_task is only called after
                     the paused task is returned from TaskQueue, hence no task other than PausedExecution
@@ -4249,6 +4768,17 @@ pub enum CompilationCostHandling {
 /// The expected speed up of deserializing a module compared to compiling it.
 const DESERIALIZATION_SPEED_UP_FACTOR: u64 = 100;
 
+/// The maximum number of management calls that can be scheduled for a single
+/// canister at any time.
+const MAX_SCHEDULED_MANAGEMENT_CALLS_PER_CANISTER: usize = 16;
+
+/// The maximum size of the method name and argument of a scheduled management
+/// call, which are stored in the replicated state until the call is due.
+const MAX_SCHEDULED_MANAGEMENT_CALL_SIZE_BYTES: usize = 64 * 1024;
+
+/// How far in the future a management call can be scheduled.
+const MAX_SCHEDULED_MANAGEMENT_CALL_DELAY: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
 impl CompilationCostHandling {
     /// Adjusts the compilation cost based on how it should be handled. Only public for use in tests.
     #[doc(hidden)]
@@ -4271,6 +4801,33 @@ pub(crate) fn subnet_memory_capacity(config: &ExecutionConfig) -> SubnetAvailabl
     )
 }
 
+/// Returns the canister targeted by a call to `method` with the given
+/// argument, or `None` if calls to `method` cannot be scheduled.
+fn scheduled_call_target(method: Ic00Method, arg: &[u8]) -> Option<Result<CanisterId, UserError>> {
+    let target = match method {
+        Ic00Method::StartCanister
+        | Ic00Method::StopCanister
+        | Ic00Method::DepositCycles
+        | Ic00Method::UninstallCode => {
+            CanisterIdRecord::decode(arg).map(|args| args.get_canister_id())
+        }
+        Ic00Method::UpdateSettings => {
+            UpdateSettingsArgs::decode(arg).map(|args| args.get_canister_id())
+        }
+        Ic00Method::InstallCode => {
+            InstallCodeArgsV2::decode(arg).map(|args| args.get_canister_id())
+        }
+        Ic00Method::InstallChunkedCode => {
+            InstallChunkedCodeArgs::decode(arg).map(|args| args.target_canister_id())
+        }
+        Ic00Method::TakeCanisterSnapshot => {
+            TakeCanisterSnapshotArgs::decode(arg).map(|args| args.get_canister_id())
+        }
+        _ => return None,
+    };
+    Some(target)
+}
+
 fn get_canister(
     canister_id: CanisterId,
     state: &ReplicatedState,
@@ -4420,6 +4977,10 @@ pub fn execute_canister(
                 let task = CanisterMessageOrTask::Task(CanisterTask::OnLowWasmMemory);
                 (task, None)
             }
+            ExecutionTask::OnLowCycles => {
+                let task = CanisterMessageOrTask::Task(CanisterTask::OnLowCycles);
+                (task, None)
+            }
             ExecutionTask::AbortedExecution {
                 input,
                 prepaid_execution_cycles,
@@ -1,15 +1,19 @@
 use candid::{Decode, Encode};
 use ic_base_types::{NumBytes, NumSeconds};
 use ic_error_types::{ErrorCode, RejectCode, UserError};
+use ic_interfaces::execution_environment::SubnetAvailableMemory;
 use ic_management_canister_types_private::{
-    self as ic00, BitcoinGetUtxosArgs, BitcoinNetwork, BoundedHttpHeaders, CanisterChange,
+    self as ic00, BitcoinGetUtxosArgs, BitcoinNetwork, BoundedHttpHeaders,
+    CancelScheduledManagementCallArgs, CanisterChange, CanisterChangeDetails, CanisterChangeOrigin,
     CanisterHttpRequestArgs, CanisterIdRecord, CanisterSettingsArgsBuilder, CanisterStatusResultV2,
     CanisterStatusType, ClearChunkStoreArgs, DerivationPath, EcdsaKeyId, EmptyBlob,
-    FetchCanisterLogsRequest, HttpMethod, LogVisibilityV2, MasterPublicKeyId, Method,
+    FetchCanisterLogsRequest, HttpMethod, ListScheduledManagementCallsArgs,
+    ListScheduledManagementCallsResponse, LogVisibilityV2, MasterPublicKeyId, Method,
     OnLowWasmMemoryHookStatus, Payload as Ic00Payload, ProvisionalCreateCanisterWithCyclesArgs,
-    ProvisionalTopUpCanisterArgs, SchnorrAlgorithm, SchnorrKeyId, TakeCanisterSnapshotArgs,
-    TransformContext, TransformFunc, UpdateSettingsArgs, UploadChunkArgs, VetKdCurve, VetKdKeyId,
-    IC_00,
+    ProvisionalTopUpCanisterArgs, ScheduleManagementCallArgs, ScheduleManagementCallResult,
+    ScheduledManagementCallStatusArgs, ScheduledManagementCallStatusResponse, SchnorrAlgorithm,
+    SchnorrKeyId, TakeCanisterSnapshotArgs, TransformContext, TransformFunc, UpdateSettingsArgs,
+    UploadChunkArgs, VetKdCurve, VetKdKeyId, IC_00,
 };
 use ic_registry_routing_table::{canister_id_into_u64, CanisterIdRange, RoutingTable};
 use ic_registry_subnet_type::SubnetType;
@@ -17,8 +21,10 @@ use ic_replicated_state::{
     canister_state::{
         system_state::CyclesUseCase, DEFAULT_QUEUE_CAPACITY, WASM_PAGE_SIZE_IN_BYTES,
     },
+    metadata_state::subnet_call_context_manager::ScheduledManagementCall,
     testing::{CanisterQueuesTesting, SystemStateTesting},
-    CanisterStatus, ReplicatedState, SystemState,
+    CanisterStatus, ReplicatedState, SystemState, CYCLES_BURN_LIMIT_GRANULARITY,
+    CYCLES_BURN_LIMIT_WINDOW,
 };
 use ic_test_utilities::assert_utils::assert_balance_equals;
 use ic_test_utilities_execution_environment::{
@@ -29,18 +35,18 @@ use ic_types::{
     canister_http::{CanisterHttpMethod, Transform},
     ingress::{IngressState, IngressStatus, WasmResult},
     messages::{
-        CallbackId, Payload, RejectContext, RequestOrResponse, Response, MAX_RESPONSE_COUNT_BYTES,
-        NO_DEADLINE,
+        CallbackId, CanisterTask, Payload, RejectContext, RequestOrResponse, Response,
+        MAX_RESPONSE_COUNT_BYTES, NO_DEADLINE,
     },
     nominal_cycles::NominalCycles,
     time::UNIX_EPOCH,
-    CanisterId, CountBytes, Cycles, PrincipalId, RegistryVersion,
+    CanisterId, CountBytes, Cycles, MessageId, PrincipalId, RegistryVersion, UserId,
 };
 use ic_types_test_utils::ids::{canister_test_id, node_test_id, subnet_test_id, user_test_id};
 use ic_universal_canister::{call_args, wasm, UNIVERSAL_CANISTER_WASM};
 use maplit::btreemap;
 use more_asserts::assert_gt;
-use std::mem::size_of;
+use std::{mem::size_of, time::Duration};
 
 #[cfg(test)]
 mod canister_task;
@@ -4001,3 +4007,430 @@ fn upload_and_clear_chunk_updates_hook_condition() {
         OnLowWasmMemoryHookStatus::ConditionNotSatisfied
     );
 }
+
+#[test]
+fn calls_are_rejected_once_cycles_burn_limit_is_reached() {
+    let mut test = ExecutionTestBuilder::new().build();
+    let canister_id = test.universal_canister().unwrap();
+    test.canister_update_cycles_burn_limit(canister_id, Cycles::new(1))
+        .unwrap();
+
+    // The first sample is taken when the first call is executed, so the
+    // cycles burned while installing the canister do not count.
+    let result = test
+        .ingress(canister_id, "update", wasm().reply().build())
+        .unwrap();
+    assert_eq!(result, WasmResult::Reply(vec![]));
+
+    let err = test
+        .ingress(canister_id, "update", wasm().reply().build())
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::CanisterCyclesBurnLimitExceeded);
+
+    // The cycles burned by the first call are still within the window.
+    test.advance_time(CYCLES_BURN_LIMIT_GRANULARITY);
+    let err = test
+        .ingress(canister_id, "update", wasm().reply().build())
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::CanisterCyclesBurnLimitExceeded);
+
+    // Once they have rolled out of the window, calls are accepted again.
+    test.advance_time(CYCLES_BURN_LIMIT_WINDOW);
+    let result = test
+        .ingress(canister_id, "update", wasm().reply().build())
+        .unwrap();
+    assert_eq!(result, WasmResult::Reply(vec![]));
+}
+
+#[test]
+fn tasks_are_rejected_once_cycles_burn_limit_is_reached() {
+    let mut test = ExecutionTestBuilder::new().build();
+    let canister_id = test.universal_canister().unwrap();
+    test.canister_update_cycles_burn_limit(canister_id, Cycles::new(1))
+        .unwrap();
+    let result = test
+        .ingress(canister_id, "update", wasm().reply().build())
+        .unwrap();
+    assert_eq!(result, WasmResult::Reply(vec![]));
+
+    // The timer is rejected before any cycles are prepaid for its execution.
+    let balance_before = test.canister_state(canister_id).system_state.balance();
+    test.canister_task(canister_id, CanisterTask::GlobalTimer);
+    assert_eq!(
+        test.canister_state(canister_id).system_state.balance(),
+        balance_before
+    );
+}
+
+fn schedule_management_call(
+    test: &mut ExecutionTest,
+    canister_id: CanisterId,
+    method: Method,
+    delay: Duration,
+) -> Result<ScheduleManagementCallResult, UserError> {
+    let execute_at = test.time() + delay;
+    let args = ScheduleManagementCallArgs::new(
+        canister_id,
+        method,
+        CanisterIdRecord::from(canister_id).encode(),
+        execute_at.as_nanos_since_unix_epoch(),
+    );
+    let result = test.subnet_message(Method::ScheduleManagementCall, args.encode())?;
+    Ok(ScheduleManagementCallResult::decode(&get_reply(Ok(result))).unwrap())
+}
+
+fn list_scheduled_management_calls(
+    test: &mut ExecutionTest,
+    canister_id: CanisterId,
+) -> ListScheduledManagementCallsResponse {
+    let args = ListScheduledManagementCallsArgs::new(canister_id);
+    let result = test.subnet_message(Method::ListScheduledManagementCalls, args.encode());
+    ListScheduledManagementCallsResponse::decode(&get_reply(result)).unwrap()
+}
+
+fn scheduled_management_call_status(
+    test: &mut ExecutionTest,
+    canister_id: CanisterId,
+    call_id: u64,
+) -> ScheduledManagementCallStatusResponse {
+    let args = ScheduledManagementCallStatusArgs::new(canister_id, call_id);
+    let result = test.subnet_message(Method::ScheduledManagementCallStatus, args.encode());
+    ScheduledManagementCallStatusResponse::decode(&get_reply(result)).unwrap()
+}
+
+#[test]
+fn scheduled_management_call_is_executed_once_due() {
+    let mut test = ExecutionTestBuilder::new().with_manual_execution().build();
+    let canister_id = test.universal_canister().unwrap();
+    test.stop_canister(canister_id);
+    test.process_stopping_canisters();
+
+    let scheduled = schedule_management_call(
+        &mut test,
+        canister_id,
+        Method::StartCanister,
+        Duration::from_secs(60),
+    )
+    .unwrap();
+    let calls = list_scheduled_management_calls(&mut test, canister_id).0;
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].call_id, scheduled.call_id);
+    assert_eq!(calls[0].method_name, Method::StartCanister.to_string());
+
+    // Nothing happens before the call is due.
+    test.enqueue_due_scheduled_management_calls();
+    assert!(!test.execute_subnet_message());
+
+    test.advance_time(Duration::from_secs(60));
+    test.enqueue_due_scheduled_management_calls();
+    assert!(test.execute_subnet_message());
+
+    let message_id = MessageId::try_from(&scheduled.request_id[..]).unwrap();
+    assert_eq!(
+        test.ingress_state(&message_id),
+        IngressState::Completed(WasmResult::Reply(EmptyBlob.encode()))
+    );
+    assert_eq!(
+        test.canister_state(canister_id).system_state.status(),
+        CanisterStatusType::Running
+    );
+    assert!(list_scheduled_management_calls(&mut test, canister_id)
+        .0
+        .is_empty());
+}
+
+#[test]
+fn scheduled_management_call_can_be_cancelled() {
+    let mut test = ExecutionTestBuilder::new().with_manual_execution().build();
+    let canister_id = test.universal_canister().unwrap();
+
+    let scheduled = schedule_management_call(
+        &mut test,
+        canister_id,
+        Method::StopCanister,
+        Duration::from_secs(60),
+    )
+    .unwrap();
+    let args = CancelScheduledManagementCallArgs::new(canister_id, scheduled.call_id);
+    test.subnet_message(Method::CancelScheduledManagementCall, args.encode())
+        .unwrap();
+    assert!(list_scheduled_management_calls(&mut test, canister_id)
+        .0
+        .is_empty());
+
+    // Cancelling the same call again fails.
+    let err = test
+        .subnet_message(Method::CancelScheduledManagementCall, args.encode())
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::CanisterRejectedMessage);
+
+    // The cancelled call is not executed once it would have been due.
+    test.advance_time(Duration::from_secs(60));
+    test.enqueue_due_scheduled_management_calls();
+    assert!(!test.execute_subnet_message());
+    assert_eq!(
+        test.canister_state(canister_id).system_state.status(),
+        CanisterStatusType::Running
+    );
+}
+
+#[test]
+fn schedule_management_call_rejects_invalid_requests() {
+    let mut test = ExecutionTestBuilder::new().with_manual_execution().build();
+    let canister_id = test.universal_canister().unwrap();
+
+    let err = schedule_management_call(
+        &mut test,
+        canister_id,
+        Method::CanisterStatus,
+        Duration::from_secs(60),
+    )
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::InvalidManagementPayload);
+
+    let err = schedule_management_call(
+        &mut test,
+        canister_id,
+        Method::StartCanister,
+        Duration::ZERO,
+    )
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::CanisterRejectedMessage);
+
+    let err = schedule_management_call(
+        &mut test,
+        canister_id,
+        Method::StartCanister,
+        Duration::from_secs(31 * 24 * 60 * 60),
+    )
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::CanisterRejectedMessage);
+
+    let args = ScheduleManagementCallArgs::new(
+        canister_id,
+        Method::StartCanister,
+        vec![0; 64 * 1024],
+        (test.time() + Duration::from_secs(60)).as_nanos_since_unix_epoch(),
+    );
+    let err = test
+        .subnet_message(Method::ScheduleManagementCall, args.encode())
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::InvalidManagementPayload);
+
+    test.set_user_id(user_test_id(42));
+    let err = schedule_management_call(
+        &mut test,
+        canister_id,
+        Method::StartCanister,
+        Duration::from_secs(60),
+    )
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::CanisterInvalidController);
+}
+
+#[test]
+fn scheduled_management_call_charges_storage_fee() {
+    let mut test = ExecutionTestBuilder::new().with_manual_execution().build();
+    let canister_id = test.universal_canister().unwrap();
+    let balance_before = test.canister_state(canister_id).system_state.balance();
+
+    schedule_management_call(
+        &mut test,
+        canister_id,
+        Method::StopCanister,
+        Duration::from_secs(24 * 60 * 60),
+    )
+    .unwrap();
+    assert!(test.canister_state(canister_id).system_state.balance() < balance_before);
+}
+
+#[test]
+fn scheduled_management_calls_are_dropped_on_canister_deletion() {
+    let mut test = ExecutionTestBuilder::new().with_manual_execution().build();
+    let canister_id = test.universal_canister().unwrap();
+
+    schedule_management_call(
+        &mut test,
+        canister_id,
+        Method::StartCanister,
+        Duration::from_secs(60),
+    )
+    .unwrap();
+    test.stop_canister(canister_id);
+    test.process_stopping_canisters();
+    test.delete_canister(canister_id).unwrap();
+
+    assert_eq!(
+        test.state()
+            .metadata
+            .subnet_call_context_manager
+            .scheduled_management_calls_for_canister(canister_id)
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn scheduled_management_call_status_reports_outcome_to_sender() {
+    let mut test = ExecutionTestBuilder::new().with_manual_execution().build();
+    let canister_id = test.universal_canister().unwrap();
+
+    let scheduled = schedule_management_call(
+        &mut test,
+        canister_id,
+        Method::StartCanister,
+        Duration::from_secs(60),
+    )
+    .unwrap();
+    assert_eq!(
+        scheduled_management_call_status(&mut test, canister_id, scheduled.call_id),
+        ScheduledManagementCallStatusResponse::Scheduled
+    );
+
+    test.advance_time(Duration::from_secs(60));
+    test.enqueue_due_scheduled_management_calls();
+    assert!(test.execute_subnet_message());
+    assert_eq!(
+        scheduled_management_call_status(&mut test, canister_id, scheduled.call_id),
+        ScheduledManagementCallStatusResponse::Replied(EmptyBlob.encode())
+    );
+
+    // Other principals cannot retrieve the outcome.
+    test.set_user_id(user_test_id(42));
+    assert_eq!(
+        scheduled_management_call_status(&mut test, canister_id, scheduled.call_id),
+        ScheduledManagementCallStatusResponse::Unknown
+    );
+}
+
+#[test]
+fn scheduled_management_call_status_reports_failures() {
+    let mut test = ExecutionTestBuilder::new().with_manual_execution().build();
+    let canister_id = test.universal_canister().unwrap();
+
+    let scheduled = schedule_management_call(
+        &mut test,
+        canister_id,
+        Method::UninstallCode,
+        Duration::from_secs(60),
+    )
+    .unwrap();
+    // The scheduled call fails once its sender is no longer a controller.
+    test.canister_update_controller(canister_id, vec![user_test_id(42).get()])
+        .unwrap();
+
+    test.advance_time(Duration::from_secs(60));
+    test.enqueue_due_scheduled_management_calls();
+    assert!(test.execute_subnet_message());
+    match scheduled_management_call_status(&mut test, canister_id, scheduled.call_id) {
+        ScheduledManagementCallStatusResponse::Rejected { reject_code, .. } => {
+            assert_eq!(reject_code, RejectCode::CanisterError as u32)
+        }
+        status => panic!("Unexpected status {:?}", status),
+    }
+}
+
+#[test]
+fn scheduled_management_call_of_canister_is_recorded_with_canister_origin() {
+    let mut test = ExecutionTestBuilder::new().with_manual_execution().build();
+    let controller = test.universal_canister().unwrap();
+    let canister_id = test.universal_canister().unwrap();
+    test.canister_update_controller(canister_id, vec![controller.get()])
+        .unwrap();
+
+    let execute_at = test.time() + Duration::from_secs(60);
+    let call_id = test
+        .state_mut()
+        .metadata
+        .subnet_call_context_manager
+        .push_scheduled_management_call(ScheduledManagementCall {
+            sender: controller.get(),
+            effective_canister_id: canister_id,
+            method_name: Method::UninstallCode.to_string(),
+            method_payload: CanisterIdRecord::from(canister_id).encode(),
+            cycles: Cycles::zero(),
+            execute_at,
+        });
+
+    test.advance_time(Duration::from_secs(60));
+    test.enqueue_due_scheduled_management_calls();
+    assert!(test.execute_subnet_message());
+
+    let system_state = &test.canister_state(canister_id).system_state;
+    let last_change = system_state
+        .get_canister_history()
+        .get_changes(1)
+        .next()
+        .unwrap();
+    assert_eq!(
+        **last_change,
+        CanisterChange::new(
+            test.time().as_nanos_since_unix_epoch(),
+            system_state.canister_version,
+            CanisterChangeOrigin::from_canister(controller.get(), None),
+            CanisterChangeDetails::CanisterCodeUninstall,
+        )
+    );
+
+    // The scheduling canister retrieves the outcome of the call.
+    test.set_user_id(UserId::from(controller.get()));
+    assert_eq!(
+        scheduled_management_call_status(&mut test, canister_id, call_id.get()),
+        ScheduledManagementCallStatusResponse::Replied(EmptyBlob.encode())
+    );
+}
+
+#[test]
+fn scheduled_management_calls_take_subnet_memory() {
+    let mut test = ExecutionTestBuilder::new().with_manual_execution().build();
+    let canister_id = test.universal_canister().unwrap();
+    let memory_taken_before = test.state().memory_taken().execution();
+    let available_memory_before = test.subnet_available_memory().get_execution_memory();
+
+    schedule_management_call(
+        &mut test,
+        canister_id,
+        Method::StopCanister,
+        Duration::from_secs(60),
+    )
+    .unwrap();
+    let call_size = (Method::StopCanister.to_string().len()
+        + CanisterIdRecord::from(canister_id).encode().len()) as u64;
+    assert_eq!(
+        test.state().memory_taken().execution(),
+        memory_taken_before + NumBytes::new(call_size)
+    );
+    assert_eq!(
+        test.subnet_available_memory().get_execution_memory(),
+        available_memory_before - call_size as i64
+    );
+
+    // The stored call no longer takes memory once it is due.
+    test.advance_time(Duration::from_secs(60));
+    test.enqueue_due_scheduled_management_calls();
+    assert_eq!(test.state().memory_taken().execution(), memory_taken_before);
+}
+
+#[test]
+fn schedule_management_call_fails_if_subnet_memory_is_exhausted() {
+    let mut test = ExecutionTestBuilder::new().with_manual_execution().build();
+    let canister_id = test.universal_canister().unwrap();
+    let balance_before = test.canister_state(canister_id).system_state.balance();
+    test.set_subnet_available_memory(SubnetAvailableMemory::new(10, ONE_GIB, ONE_GIB));
+
+    let err = schedule_management_call(
+        &mut test,
+        canister_id,
+        Method::StopCanister,
+        Duration::from_secs(60),
+    )
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::SubnetOversubscribed);
+    assert_eq!(
+        test.canister_state(canister_id).system_state.balance(),
+        balance_before
+    );
+    assert!(list_scheduled_management_calls(&mut test, canister_id)
+        .0
+        .is_empty());
+}
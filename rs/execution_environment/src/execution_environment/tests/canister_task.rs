@@ -3,9 +3,12 @@ use ic_base_types::NumSeconds;
 use ic_config::{execution_environment::Config as HypervisorConfig, subnet_config::SubnetConfig};
 use ic_error_types::RejectCode;
 use ic_management_canister_types_private::{
-    CanisterSettingsArgsBuilder, CanisterStatusType, OnLowWasmMemoryHookStatus,
+    CanisterIdRecord, CanisterUpgradeOptions, Method, Payload, WasmMemoryPersistence,
+};
+use ic_management_canister_types_private::{
+    CanisterSettingsArgsBuilder, CanisterStatusType, OnLowCyclesHookStatus,
+    OnLowWasmMemoryHookStatus,
 };
-use ic_management_canister_types_private::{CanisterUpgradeOptions, WasmMemoryPersistence};
 use ic_registry_subnet_type::SubnetType;
 use ic_replicated_state::canister_state::NextExecution;
 use ic_replicated_state::canister_state::WASM_PAGE_SIZE_IN_BYTES;
@@ -15,6 +18,7 @@ use ic_state_machine_tests::StateMachine;
 use ic_state_machine_tests::{StateMachineBuilder, StateMachineConfig, WasmResult};
 use ic_test_utilities_execution_environment::{wat_compilation_cost, ExecutionTestBuilder};
 use ic_test_utilities_metrics::fetch_int_counter_vec;
+use ic_test_utilities_types::ids::{canister_test_id, subnet_test_id};
 use ic_types::messages::CanisterTask;
 use ic_types::Cycles;
 use ic_types::{CanisterId, NumBytes};
@@ -1842,3 +1846,121 @@ fn low_wasm_memory_hook_is_run_when_memory_limit_is_exceeded() {
         OnLowWasmMemoryHookStatus::Executed
     );
 }
+
+#[test]
+fn on_low_cycles_is_executed_once() {
+    let mut test = ExecutionTestBuilder::new().with_manual_execution().build();
+
+    let wat = r#"(module
+            (func (export "canister_on_low_cycles")
+                (drop (memory.grow (i32.const 5)))
+            )
+            (memory 1 20)
+        )"#;
+    let canister_id = test.canister_from_wat(wat).unwrap();
+
+    let balance = test.canister_state(canister_id).system_state.balance();
+    test.canister_update_cycles_threshold(canister_id, balance + Cycles::new(1))
+        .unwrap();
+
+    // The balance is below the threshold, so the hook becomes ready.
+    assert_eq!(
+        test.canister_state(canister_id)
+            .system_state
+            .task_queue
+            .peek_on_low_cycles_hook_status(),
+        OnLowCyclesHookStatus::Ready
+    );
+
+    test.execute_slice(canister_id);
+    assert_eq!(
+        test.execution_state(canister_id).wasm_memory.size,
+        NumWasmPages::new(6)
+    );
+    assert_eq!(
+        test.canister_state(canister_id)
+            .system_state
+            .task_queue
+            .peek_on_low_cycles_hook_status(),
+        OnLowCyclesHookStatus::Executed
+    );
+
+    // The condition still holds, but the hook is not executed again.
+    test.execute_slice(canister_id);
+    assert_eq!(
+        test.execution_state(canister_id).wasm_memory.size,
+        NumWasmPages::new(6)
+    );
+}
+
+#[test]
+fn on_low_cycles_is_not_executed_if_balance_is_above_threshold() {
+    let mut test = ExecutionTestBuilder::new().with_manual_execution().build();
+
+    let wat = r#"(module
+            (func (export "canister_on_low_cycles")
+                (drop (memory.grow (i32.const 5)))
+            )
+            (memory 1 20)
+        )"#;
+    let canister_id = test.canister_from_wat(wat).unwrap();
+
+    test.canister_update_cycles_threshold(canister_id, Cycles::new(1))
+        .unwrap();
+
+    assert_eq!(
+        test.canister_state(canister_id)
+            .system_state
+            .task_queue
+            .peek_on_low_cycles_hook_status(),
+        OnLowCyclesHookStatus::ConditionNotSatisfied
+    );
+
+    test.execute_slice(canister_id);
+    assert_eq!(
+        test.execution_state(canister_id).wasm_memory.size,
+        NumWasmPages::new(1)
+    );
+}
+
+#[test]
+fn on_low_cycles_hook_condition_is_updated_after_deposit_cycles() {
+    let own_subnet = subnet_test_id(1);
+    let mut test = ExecutionTestBuilder::new()
+        .with_own_subnet_id(own_subnet)
+        .with_manual_execution()
+        .with_caller(own_subnet, canister_test_id(10))
+        .build();
+
+    let wat = r#"(module
+            (func (export "canister_on_low_cycles"))
+            (memory 1 20)
+        )"#;
+    let canister_id = test.canister_from_wat(wat).unwrap();
+
+    let balance = test.canister_state(canister_id).system_state.balance();
+    test.canister_update_cycles_threshold(canister_id, balance + Cycles::new(1_000))
+        .unwrap();
+    assert_eq!(
+        test.canister_state(canister_id)
+            .system_state
+            .task_queue
+            .peek_on_low_cycles_hook_status(),
+        OnLowCyclesHookStatus::Ready
+    );
+
+    // Depositing enough cycles lifts the balance above the threshold.
+    test.inject_call_to_ic00(
+        Method::DepositCycles,
+        CanisterIdRecord::from(canister_id).encode(),
+        Cycles::new(1_000_000_000_000),
+    );
+    test.execute_subnet_message();
+    assert_eq!(
+        test.canister_state(canister_id)
+            .system_state
+            .task_queue
+            .peek_on_low_cycles_hook_status(),
+        OnLowCyclesHookStatus::ConditionNotSatisfied
+    );
+}
@@ -298,6 +298,7 @@ impl ExecutionEnvironmentMetrics {
                     | ic00::Method::NodeMetricsHistory
                     | ic00::Method::SubnetInfo
                     | ic00::Method::FetchCanisterLogs
+                    | ic00::Method::FetchCanisterHistory
                     | ic00::Method::ProvisionalCreateCanisterWithCycles
                     | ic00::Method::ProvisionalTopUpCanister
                     | ic00::Method::UploadChunk
@@ -311,7 +312,11 @@ impl ExecutionEnvironmentMetrics {
                     | ic00::Method::ReadCanisterSnapshotData
                     | ic00::Method::UploadCanisterSnapshotMetadata
                     | ic00::Method::UploadCanisterSnapshotData
-                    | ic00::Method::RenameCanister => String::from("fast"),
+                    | ic00::Method::RenameCanister
+                    | ic00::Method::ScheduleManagementCall
+                    | ic00::Method::ListScheduledManagementCalls
+                    | ic00::Method::CancelScheduledManagementCall
+                    | ic00::Method::ScheduledManagementCallStatus => String::from("fast"),
 
                     // "Slow" management methods that might require several execution
                     // rounds to be completed, either due to using DTS or due to
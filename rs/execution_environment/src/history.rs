@@ -346,6 +346,7 @@ fn dashboard_label_value_from(code: ErrorCode) -> &'static str {
                 in the previous install_code messages"
         }
         CanisterHeapDeltaRateLimited => "Canister Heap Delta Rate Limited",
+        CanisterCyclesBurnLimitExceeded => "Canister Cycles Burn Limit Exceeded",
         // 3xx -- `RejectCode::DestinationInvalid`
         CanisterNotFound => "Canister Not Found",
         CanisterSnapshotNotFound => "Canister Snapshot Not Found",
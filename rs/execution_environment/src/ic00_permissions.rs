@@ -54,7 +54,11 @@ impl Ic00MethodPermissions {
             | Ic00Method::ReadCanisterSnapshotMetadata
             | Ic00Method::ReadCanisterSnapshotData
             | Ic00Method::UploadCanisterSnapshotMetadata
-            | Ic00Method::UploadCanisterSnapshotData => Self {
+            | Ic00Method::UploadCanisterSnapshotData
+            | Ic00Method::ScheduleManagementCall
+            | Ic00Method::ListScheduledManagementCalls
+            | Ic00Method::CancelScheduledManagementCall
+            | Ic00Method::ScheduledManagementCallStatus => Self {
                 method,
                 allow_remote_subnet_sender: true,
                 allow_only_nns_subnet_sender: false,
@@ -100,10 +104,11 @@ impl Ic00MethodPermissions {
                 does_not_run_on_aborted_canister: false,
                 installs_code: false,
             },
-            Ic00Method::FetchCanisterLogs => Self {
+            Ic00Method::FetchCanisterLogs | Ic00Method::FetchCanisterHistory => Self {
                 method,
-                // `FetchCanisterLogs` method is only allowed for messages sent by users,
-                // all inter-canister call permissions are irrelevant and therefore set to false.
+                // `FetchCanisterLogs` and `FetchCanisterHistory` methods are only allowed for
+                // messages sent by users, all inter-canister call permissions are irrelevant
+                // and therefore set to false.
                 allow_remote_subnet_sender: false,
                 allow_only_nns_subnet_sender: false,
                 counts_toward_round_limit: false,
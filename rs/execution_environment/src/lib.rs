@@ -70,12 +70,14 @@ pub enum QueryExecutionType {
 }
 
 /// This enum indicates whether execution of a non-replicated query
-/// should keep track of the state or not.
+/// should keep track of the state or not. `SimulatedUpdate` executes an
+/// update method on a throwaway copy of the canister to report its effects.
 #[doc(hidden)]
 #[derive(Clone, Eq, PartialEq)]
 pub enum NonReplicatedQueryKind {
     Stateful { call_origin: CallOrigin },
     Pure { caller: PrincipalId },
+    SimulatedUpdate { caller: PrincipalId },
 }
 
 // This struct holds public facing components that are created by Execution.
@@ -18,7 +18,7 @@ use ic_config::execution_environment::Config;
 use ic_config::flag_status::FlagStatus;
 use ic_crypto_tree_hash::{flatmap, Label, LabeledTree, LabeledTree::SubTree};
 use ic_cycles_account_manager::CyclesAccountManager;
-use ic_error_types::{ErrorCode, UserError};
+use ic_error_types::{ErrorCode, RejectCode, UserError};
 use ic_interfaces::execution_environment::{
     QueryExecutionError, QueryExecutionResponse, QueryExecutionService,
 };
@@ -32,7 +32,9 @@ use ic_types::batch::QueryStats;
 use ic_types::QueryStatsEpoch;
 use ic_types::{
     ingress::WasmResult,
-    messages::{Blob, Certificate, CertificateDelegation, Query},
+    messages::{
+        Blob, Certificate, CertificateDelegation, Query, MAX_INTER_CANISTER_PAYLOAD_IN_BYTES_U64,
+    },
     CanisterId, NumInstructions, PrincipalId,
 };
 use prometheus::{histogram_opts, labels, Histogram};
@@ -51,7 +53,11 @@ use tower::{util::BoxCloneService, Service};
 
 pub(crate) use self::query_scheduler::{QueryScheduler, QuerySchedulerFlag};
 use ic_management_canister_types_private::{
-    FetchCanisterLogsRequest, FetchCanisterLogsResponse, LogVisibilityV2, Payload, QueryMethod,
+    CanisterChangeDetails, CanisterHistoryEntry, CanisterHistoryModule, CanisterHistoryVisibility,
+    FetchCanisterHistoryRequest, FetchCanisterHistoryResponse, FetchCanisterLogsRequest,
+    FetchCanisterLogsResponse, LogVisibilityV2, Payload, QueryMethod, SimulateUpdateCallArgs,
+    SimulateUpdateCallResponse, SimulatedCallReject, SimulatedCallResult, SimulatedOutgoingCall,
+    HASH_LENGTH,
 };
 
 /// Convert an object into CBOR binary.
@@ -215,6 +221,36 @@ impl InternalHttpQueryHandler {
                     );
                     return result;
                 }
+                Ok(QueryMethod::FetchCanisterHistory) => {
+                    let since = Instant::now(); // Start logging execution time.
+                    let result = fetch_canister_history(
+                        query.source(),
+                        state.get_ref(),
+                        FetchCanisterHistoryRequest::decode(&query.method_payload)?,
+                    );
+                    self.metrics.observe_subnet_query_message(
+                        QueryMethod::FetchCanisterHistory,
+                        since.elapsed().as_secs_f64(),
+                        &result,
+                    );
+                    return result;
+                }
+                Ok(QueryMethod::SimulateUpdateCall) => {
+                    let since = Instant::now(); // Start logging execution time.
+                    let result = self.simulate_update_call(
+                        query.source(),
+                        state,
+                        data_certificate,
+                        SimulateUpdateCallArgs::decode(&query.method_payload)?,
+                        &measurement_scope,
+                    );
+                    self.metrics.observe_subnet_query_message(
+                        QueryMethod::SimulateUpdateCall,
+                        since.elapsed().as_secs_f64(),
+                        &result,
+                    );
+                    return result;
+                }
                 Err(_) => {
                     return Err(UserError::new(
                         ErrorCode::CanisterMethodNotFound,
@@ -247,6 +283,42 @@ impl InternalHttpQueryHandler {
             None
         };
 
+        let mut context = self.new_query_context(
+            // For composite queries, the set of evaluated canisters is not known in advance,
+            // so the whole state is needed to capture later the state of the call graph.
+            // The clone should not be expensive, as the state is `Labeled<Arc<ReplicatedState>>`.
+            state.clone(),
+            data_certificate,
+            query.receiver,
+            query_stats_collector,
+        );
+
+        let result = context.run(query, &self.metrics, &measurement_scope);
+        context.accumulate_transient_errors_from_result(result.as_ref());
+        context.observe_metrics(&self.metrics);
+
+        // Add the query execution result to the query cache (if the query caching is enabled).
+        // Query caching is disabled if the key is set to `None`.
+        if let Some(key) = cache_entry_key {
+            let state = state.get_ref().as_ref();
+            let counters = context.system_api_call_counters();
+            let stats = context.evaluated_canister_stats();
+            let errors = context.transient_errors();
+            self.query_cache
+                .push(key, &result, state, counters, stats, errors);
+        }
+        result
+    }
+
+    /// Creates the context in which a query to `root_canister_id`, along with
+    /// its outgoing query calls, is executed.
+    fn new_query_context<'a>(
+        &'a self,
+        state: Labeled<Arc<ReplicatedState>>,
+        data_certificate: Vec<u8>,
+        root_canister_id: CanisterId,
+        query_stats_collector: Option<&'a QueryStatsCollector>,
+    ) -> query_context::QueryContext<'a> {
         // Letting the canister grow arbitrarily when executing the
         // query is fine as we do not persist state modifications.
         let subnet_available_memory = subnet_memory_capacity(&self.config);
@@ -255,14 +327,11 @@ impl InternalHttpQueryHandler {
         // implicit bound anyway.
         let subnet_available_callbacks = self.config.subnet_callback_soft_limit as i64;
 
-        let mut context = query_context::QueryContext::new(
+        query_context::QueryContext::new(
             &self.log,
             self.hypervisor.as_ref(),
             self.own_subnet_type,
-            // For composite queries, the set of evaluated canisters is not known in advance,
-            // so the whole state is needed to capture later the state of the call graph.
-            // The clone should not be expensive, as the state is `Labeled<Arc<ReplicatedState>>`.
-            state.clone(),
+            state,
             data_certificate,
             subnet_available_memory,
             subnet_available_callbacks,
@@ -275,27 +344,67 @@ impl InternalHttpQueryHandler {
             self.config.max_query_call_walltime,
             self.config.instruction_overhead_per_query_call,
             self.config.composite_queries,
-            query.receiver,
+            root_canister_id,
             &self.metrics.query_critical_error,
             query_stats_collector,
             Arc::clone(&self.cycles_account_manager),
-        );
+        )
+    }
 
-        let result = context.run(query, &self.metrics, &measurement_scope);
-        context.accumulate_transient_errors_from_result(result.as_ref());
+    /// Executes an update method on a throwaway copy of the canister and
+    /// returns its reply, the instructions used, the cycles the canister would
+    /// be charged, and the outgoing calls, which are not sent.
+    fn simulate_update_call(
+        &self,
+        caller: PrincipalId,
+        state: Labeled<Arc<ReplicatedState>>,
+        data_certificate: Vec<u8>,
+        args: SimulateUpdateCallArgs,
+        measurement_scope: &MeasurementScope,
+    ) -> Result<WasmResult, UserError> {
+        let canister_id = args.get_canister_id();
+        let mut context = self.new_query_context(state, data_certificate, canister_id, None);
+        let simulated = context.simulate_update(
+            caller,
+            canister_id,
+            args.method_name,
+            &args.arg,
+            measurement_scope,
+        )?;
         context.observe_metrics(&self.metrics);
 
-        // Add the query execution result to the query cache (if the query caching is enabled).
-        // Query caching is disabled if the key is set to `None`.
-        if let Some(key) = cache_entry_key {
-            let state = state.get_ref().as_ref();
-            let counters = context.system_api_call_counters();
-            let stats = context.evaluated_canister_stats();
-            let errors = context.transient_errors();
-            self.query_cache
-                .push(key, &result, state, counters, stats, errors);
-        }
-        result
+        let result = match simulated.result {
+            Ok(Some(WasmResult::Reply(reply))) => Some(SimulatedCallResult::Reply(reply)),
+            Ok(Some(WasmResult::Reject(message))) => {
+                Some(SimulatedCallResult::Reject(SimulatedCallReject {
+                    reject_code: RejectCode::CanisterReject as u32,
+                    reject_message: message,
+                    error_code: ErrorCode::CanisterRejectedMessage.to_string(),
+                }))
+            }
+            Ok(None) => None,
+            Err(err) => Some(SimulatedCallResult::Reject(SimulatedCallReject {
+                reject_code: err.reject_code() as u32,
+                reject_message: err.description().to_string(),
+                error_code: err.code().to_string(),
+            })),
+        };
+        let response = SimulateUpdateCallResponse {
+            result,
+            instructions_used: simulated.instructions_used.get(),
+            cycles_charged: candid::Nat::from(simulated.cycles_charged.get()),
+            outgoing_calls: simulated
+                .outgoing_calls
+                .iter()
+                .map(|request| SimulatedOutgoingCall {
+                    callee: request.receiver.get(),
+                    method_name: request.method_name.clone(),
+                    payment: candid::Nat::from(request.payment.get()),
+                    arg: request.method_payload.clone(),
+                })
+                .collect(),
+        };
+        Ok(WasmResult::Reply(Encode!(&response).unwrap()))
     }
 }
 
@@ -339,6 +448,109 @@ fn fetch_canister_logs(
     Ok(WasmResult::Reply(Encode!(&response).unwrap()))
 }
 
+/// Upper bound on the total size of the Wasm modules returned by a single
+/// `fetch_canister_history` call. Modules that do not fit are returned
+/// without their bytes, starting with the oldest requested module.
+const MAX_FETCH_CANISTER_HISTORY_MODULES_BYTES: u64 = MAX_INTER_CANISTER_PAYLOAD_IN_BYTES_U64;
+
+/// Returns the canister history together with the install argument hashes and
+/// the modules of the most recent code deployments.
+///
+/// The canister history does not retain Wasm modules of its own, so that it
+/// does not grow the canister's memory usage by whole modules. A past module is
+/// only returned while it is still installed or stored in one of the canister's
+/// snapshots; otherwise only its hash is returned.
+fn fetch_canister_history(
+    sender: PrincipalId,
+    state: &ReplicatedState,
+    args: FetchCanisterHistoryRequest,
+) -> Result<WasmResult, UserError> {
+    let canister_id = args.get_canister_id();
+    let canister = state.canister_state(&canister_id).ok_or_else(|| {
+        UserError::new(
+            ErrorCode::CanisterNotFound,
+            format!("Canister {canister_id} not found"),
+        )
+    })?;
+
+    match canister.system_state.canister_history_visibility() {
+        CanisterHistoryVisibility::Public => Ok(()),
+        CanisterHistoryVisibility::Controllers if canister.controllers().contains(&sender) => {
+            Ok(())
+        }
+        CanisterHistoryVisibility::Controllers => Err(UserError::new(
+            ErrorCode::CanisterRejectedMessage,
+            format!(
+                "Caller {} is not allowed to query ic00 method {}",
+                sender,
+                QueryMethod::FetchCanisterHistory
+            ),
+        )),
+    }?;
+
+    let canister_history = canister.system_state.get_canister_history();
+    let changes: Vec<CanisterHistoryEntry> = canister_history
+        .get_changes_with_argument_hashes()
+        .map(|(change, arg_hash)| CanisterHistoryEntry {
+            change: (**change).clone(),
+            arg_hash: arg_hash.map(|hash| hash.to_vec()),
+        })
+        .collect();
+
+    // The only Wasm modules available are the installed module and the modules
+    // stored in the canister's snapshots, both of which are already paid for.
+    let snapshots = state.canister_snapshots.list_snapshots(canister_id);
+    let retained_module = |module_hash: [u8; HASH_LENGTH]| {
+        canister
+            .execution_state
+            .iter()
+            .map(|execution_state| &execution_state.wasm_binary.binary)
+            .chain(
+                snapshots
+                    .iter()
+                    .map(|(_, snapshot)| snapshot.canister_module()),
+            )
+            .find(|module| module.module_hash() == module_hash)
+    };
+
+    let num_requested_modules = args.num_requested_modules.unwrap_or(0) as usize;
+    let mut remaining_bytes = MAX_FETCH_CANISTER_HISTORY_MODULES_BYTES;
+    // Walk the code deployments from the most recent one, so that the most
+    // recent modules are the ones returned if not all of them fit.
+    let mut modules: Vec<CanisterHistoryModule> = changes
+        .iter()
+        .rev()
+        .filter_map(|entry| match entry.change.details() {
+            CanisterChangeDetails::CanisterCodeDeployment(record) => {
+                Some((entry.change.canister_version(), record.module_hash()))
+            }
+            _ => None,
+        })
+        .take(num_requested_modules)
+        .map(|(canister_version, module_hash)| {
+            let wasm_module = retained_module(module_hash)
+                .filter(|module| module.len() as u64 <= remaining_bytes)
+                .map(|module| {
+                    remaining_bytes -= module.len() as u64;
+                    module.as_slice().to_vec()
+                });
+            CanisterHistoryModule {
+                canister_version,
+                module_hash: module_hash.to_vec(),
+                wasm_module,
+            }
+        })
+        .collect();
+    modules.reverse();
+
+    let response = FetchCanisterHistoryResponse {
+        total_num_changes: canister_history.get_total_num_changes(),
+        changes,
+        modules,
+    };
+    Ok(WasmResult::Reply(Encode!(&response).unwrap()))
+}
+
 impl HttpQueryHandler {
     pub(crate) fn new_service(
         internal: Arc<InternalHttpQueryHandler>,
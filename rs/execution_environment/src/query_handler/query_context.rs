@@ -38,7 +38,7 @@ use ic_types::{
         RequestOrResponse, Response, NO_DEADLINE,
     },
     methods::{FuncRef, WasmClosure, WasmMethod},
-    CanisterId, Cycles, NumInstructions, NumMessages, NumSlices, Time,
+    CanisterId, Cycles, NumInstructions, NumMessages, NumSlices, PrincipalId, Time,
 };
 use prometheus::IntCounter;
 use std::{
@@ -65,6 +65,16 @@ pub(super) enum ExecutionResult {
     SystemError(UserError),
 }
 
+/// The effects of an update call executed by `QueryContext::simulate_update`.
+pub(super) struct SimulatedUpdate {
+    pub result: Result<Option<WasmResult>, UserError>,
+    pub instructions_used: NumInstructions,
+    /// The cycles the canister would be charged, excluding the cycles attached
+    /// to the outgoing calls.
+    pub cycles_charged: Cycles,
+    pub outgoing_calls: Vec<Arc<Request>>,
+}
+
 /// Returns either `WasmMethod::CompositeQuery` or `WasmMethod::Query` depending
 /// on whether the given method name is exported as a composite query or not.
 fn wasm_query_method(
@@ -301,6 +311,160 @@ impl<'a> QueryContext<'a> {
         result
     }
 
+    /// Executes the given update method on a throwaway copy of the canister
+    /// and returns its effects. The canister is charged as it would be for a
+    /// real ingress message, but the outgoing calls are not sent and no state
+    /// changes are persisted.
+    ///
+    /// Only controllers of the canister may simulate its update methods: the
+    /// method runs in replicated mode and its reply is returned to the caller,
+    /// but `canister_inspect_message` is not run, so the simulation must not be
+    /// available to callers the canister would not otherwise answer.
+    ///
+    /// The method runs under the query instruction limit rather than the
+    /// update one, so an update that needs more instructions fails with
+    /// `CanisterInstructionLimitExceeded` here even though it would complete
+    /// when executed for real.
+    ///
+    /// Note that the fees of the outgoing calls include the prepayment for
+    /// executing their responses, part of which would later be refunded.
+    pub(super) fn simulate_update(
+        &mut self,
+        caller: PrincipalId,
+        canister_id: CanisterId,
+        method_name: String,
+        method_payload: &[u8],
+        measurement_scope: &MeasurementScope,
+    ) -> Result<SimulatedUpdate, UserError> {
+        let mut canister = self
+            .state
+            .get_ref()
+            .get_active_canister(&canister_id)?
+            .clone();
+        if !canister.controllers().contains(&caller) {
+            return Err(UserError::new(
+                ErrorCode::CanisterInvalidController,
+                format!(
+                    "Only the controllers of canister {} can simulate its update calls. Sender's ID is {}.",
+                    canister_id, caller
+                ),
+            ));
+        }
+        let method = WasmMethod::Update(method_name.clone());
+        common::validate_canister(&canister)?;
+        validate_method(&method, &canister).map_err(|err| err.into_user_error(&canister_id))?;
+
+        let subnet_size = self
+            .network_topology
+            .get_subnet_size(&self.cycles_account_manager.get_subnet_id())
+            .unwrap_or(SMALL_APP_SUBNET_MAX_SIZE);
+        let wasm_execution_mode = canister
+            .execution_state
+            .as_ref()
+            .map_or(WasmExecutionMode::Wasm32, |state| state.wasm_execution_mode);
+        let instruction_limit = self.max_instructions_per_query.min(NumInstructions::new(
+            self.round_limits.instructions.get().max(0) as u64,
+        ));
+        let instruction_limits =
+            InstructionLimits::new(FlagStatus::Disabled, instruction_limit, instruction_limit);
+        let mut execution_parameters = self.execution_parameters(&canister, instruction_limits);
+        // The method must see the same System API as a committed update call.
+        execution_parameters.execution_mode = ExecutionMode::Replicated;
+
+        let ingress_induction_cost = self
+            .cycles_account_manager
+            .ingress_induction_cost_from_bytes(
+                NumBytes::from((method_payload.len() + method_name.len()) as u64),
+                subnet_size,
+            );
+        let memory_usage = canister.memory_usage();
+        let message_memory_usage = canister.message_memory_usage();
+        let compute_allocation = canister.compute_allocation();
+        // The caller is a controller, so it may see the top-up amount.
+        let reveal_top_up = true;
+        self.cycles_account_manager
+            .charge_ingress_induction_cost(
+                &mut canister,
+                memory_usage,
+                message_memory_usage,
+                compute_allocation,
+                ingress_induction_cost,
+                subnet_size,
+                reveal_top_up,
+            )
+            .and_then(|()| {
+                self.cycles_account_manager.prepay_execution_cycles(
+                    &mut canister.system_state,
+                    memory_usage,
+                    message_memory_usage,
+                    compute_allocation,
+                    instruction_limit,
+                    subnet_size,
+                    reveal_top_up,
+                    wasm_execution_mode,
+                )
+            })
+            .map_err(|err| UserError::new(ErrorCode::CanisterOutOfCycles, err))?;
+        let balance_before = canister.system_state.balance();
+
+        let (mut canister, instructions_left, result, call_context_id, system_api_call_counters) =
+            execute_non_replicated_query(
+                NonReplicatedQueryKind::SimulatedUpdate { caller },
+                method,
+                method_payload,
+                canister,
+                None,
+                self.state.get_ref().time(),
+                execution_parameters,
+                &self.network_topology,
+                self.hypervisor,
+                &mut self.round_limits,
+                self.query_critical_error,
+            );
+        self.add_system_api_call_counters(system_api_call_counters);
+        let instructions_used = instruction_limit - instructions_left;
+        measurement_scope.add(instructions_used, NumSlices::from(1), NumMessages::from(1));
+
+        // The output queues of the copy may still hold messages from before,
+        // so only the requests made from the simulated call context are kept.
+        let outgoing_messages: Vec<_> = canister.output_into_iter().collect();
+        let outgoing_calls: Vec<_> = match (
+            call_context_id,
+            canister.system_state.call_context_manager(),
+        ) {
+            (Some(call_context_id), Some(call_context_manager)) => outgoing_messages
+                .into_iter()
+                .filter_map(|msg| match msg {
+                    RequestOrResponse::Request(request) => call_context_manager
+                        .callback(request.sender_reply_callback)
+                        .filter(|callback| callback.call_context_id == call_context_id)
+                        .map(|_| request),
+                    RequestOrResponse::Response(_) => None,
+                })
+                .collect(),
+            _ => vec![],
+        };
+
+        let attached_cycles = outgoing_calls
+            .iter()
+            .fold(Cycles::zero(), |total, request| total + request.payment);
+        let call_fees = (balance_before - canister.system_state.balance()) - attached_cycles;
+        let cycles_charged = ingress_induction_cost
+            + self.cycles_account_manager.execution_cost(
+                instructions_used,
+                subnet_size,
+                wasm_execution_mode,
+            )
+            + call_fees;
+
+        Ok(SimulatedUpdate {
+            result,
+            instructions_used,
+            cycles_charged,
+            outgoing_calls,
+        })
+    }
+
     // A helper function that extracts the query calls of the given canister and
     // enqueues them onto the given deque.
     fn extract_query_requests(
@@ -1,14 +1,17 @@
 use crate::InternalHttpQueryHandler;
-use ic_base_types::{CanisterId, NumSeconds};
+use ic_base_types::{CanisterId, NumBytes, NumSeconds};
 use ic_config::execution_environment::INSTRUCTION_OVERHEAD_PER_QUERY_CALL;
 use ic_error_types::{ErrorCode, UserError};
+use ic_management_canister_types_private::{
+    Payload, SimulateUpdateCallArgs, SimulateUpdateCallResponse, SimulatedCallResult,
+};
 use ic_test_utilities::universal_canister::{call_args, wasm};
 use ic_test_utilities_execution_environment::{ExecutionTest, ExecutionTestBuilder};
 use ic_test_utilities_types::ids::user_test_id;
 use ic_types::{
     ingress::WasmResult,
     messages::{Query, QuerySource},
-    Cycles, NumInstructions,
+    Cycles, NumInstructions, UserId,
 };
 use std::sync::Arc;
 
@@ -1252,3 +1255,149 @@ fn query_call_exceeds_instructions_limit() {
             )
     );
 }
+
+fn simulate_update_call_as(
+    test: &ExecutionTest,
+    sender: UserId,
+    canister_id: CanisterId,
+    method_name: &str,
+    arg: Vec<u8>,
+) -> Result<WasmResult, UserError> {
+    let args = SimulateUpdateCallArgs::new(canister_id, method_name.to_string(), arg);
+    test.query(
+        Query {
+            source: QuerySource::User {
+                user_id: sender,
+                ingress_expiry: 0,
+                nonce: None,
+            },
+            receiver: CanisterId::ic_00(),
+            method_name: "simulate_update_call".to_string(),
+            method_payload: args.encode(),
+        },
+        Arc::new(test.state().clone()),
+        vec![],
+    )
+}
+
+fn simulate_update_call(
+    test: &mut ExecutionTest,
+    canister_id: CanisterId,
+    method_name: &str,
+    arg: Vec<u8>,
+) -> SimulateUpdateCallResponse {
+    // The canisters of the test are controlled by the test's user.
+    match simulate_update_call_as(test, test.user_id(), canister_id, method_name, arg).unwrap() {
+        WasmResult::Reply(bytes) => SimulateUpdateCallResponse::decode(&bytes).unwrap(),
+        WasmResult::Reject(reason) => panic!("simulate_update_call rejected: {}", reason),
+    }
+}
+
+#[test]
+fn simulate_update_call_returns_reply_and_cost() {
+    let mut test = ExecutionTestBuilder::new().build();
+    let canister = test.universal_canister_with_cycles(CYCLES_BALANCE).unwrap();
+    let balance_before = test.canister_state(canister).system_state.balance();
+
+    let arg = wasm().reply_data(b"pong").build();
+    let response = simulate_update_call(&mut test, canister, "update", arg.clone());
+
+    assert_eq!(
+        response.result,
+        Some(SimulatedCallResult::Reply(b"pong".to_vec()))
+    );
+    assert!(response.instructions_used > 0);
+    assert!(response.outgoing_calls.is_empty());
+    let ingress_induction_cost = test
+        .cycles_account_manager()
+        .ingress_induction_cost_from_bytes(
+            NumBytes::from((arg.len() + "update".len()) as u64),
+            test.subnet_size(),
+        );
+    let execution_cost = test.cycles_account_manager().execution_cost(
+        NumInstructions::from(response.instructions_used),
+        test.subnet_size(),
+        test.canister_wasm_execution_mode(canister),
+    );
+    assert_eq!(
+        response.cycles_charged,
+        candid::Nat::from((ingress_induction_cost + execution_cost).get())
+    );
+    // Nothing is charged for the simulation itself.
+    assert_eq!(
+        test.canister_state(canister).system_state.balance(),
+        balance_before
+    );
+}
+
+#[test]
+fn simulate_update_call_reports_outgoing_calls_without_sending_them() {
+    let mut test = ExecutionTestBuilder::new().build();
+    let canister_a = test.universal_canister_with_cycles(CYCLES_BALANCE).unwrap();
+    let canister_b = test.universal_canister_with_cycles(CYCLES_BALANCE).unwrap();
+    let balance_before = test.canister_state(canister_a).system_state.balance();
+
+    let arg = wasm()
+        .set_global_data(b"simulated")
+        .call_with_cycles(
+            canister_b,
+            "update",
+            call_args().other_side(wasm().reply_data(b"pong")),
+            Cycles::new(1_000),
+        )
+        .build();
+    let response = simulate_update_call(&mut test, canister_a, "update", arg);
+
+    // The call is still awaiting the response of canister B.
+    assert_eq!(response.result, None);
+    assert_eq!(response.outgoing_calls.len(), 1);
+    let call = &response.outgoing_calls[0];
+    assert_eq!(call.callee, canister_b.get());
+    assert_eq!(call.method_name, "update");
+    assert_eq!(call.payment, candid::Nat::from(1_000_u64));
+    // The fees of the outgoing call are included, but not its payment.
+    let execution_cost = test.cycles_account_manager().execution_cost(
+        NumInstructions::from(response.instructions_used),
+        test.subnet_size(),
+        test.canister_wasm_execution_mode(canister_a),
+    );
+    assert!(response.cycles_charged > candid::Nat::from(execution_cost.get()));
+
+    // Neither the state changes nor the outgoing call were committed.
+    assert_eq!(
+        test.canister_state(canister_a).system_state.balance(),
+        balance_before
+    );
+    assert!(!test.canister_state(canister_a).has_output());
+    let output = test.non_replicated_query(
+        canister_a,
+        "query",
+        wasm().get_global_data().append_and_reply().build(),
+    );
+    assert_eq!(output, Ok(WasmResult::Reply(vec![])));
+}
+
+#[test]
+fn simulate_update_call_rejects_unknown_method() {
+    let mut test = ExecutionTestBuilder::new().build();
+    let canister = test.universal_canister_with_cycles(CYCLES_BALANCE).unwrap();
+
+    let err =
+        simulate_update_call_as(&test, test.user_id(), canister, "unknown", vec![]).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::CanisterMethodNotFound);
+}
+
+#[test]
+fn simulate_update_call_rejects_non_controllers() {
+    let mut test = ExecutionTestBuilder::new().build();
+    let canister = test.universal_canister_with_cycles(CYCLES_BALANCE).unwrap();
+    let non_controller = user_test_id(42);
+    assert!(!test
+        .canister_state(canister)
+        .controllers()
+        .contains(&non_controller.get()));
+
+    let arg = wasm().reply_data(b"pong").build();
+    let err = simulate_update_call_as(&test, non_controller, canister, "update", arg).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::CanisterInvalidController);
+}
@@ -952,6 +952,14 @@ impl SchedulerImpl {
                         canister.canister_id()
                     );
                     self.metrics.num_canisters_uninstalled_out_of_cycles.inc();
+                } else {
+                    // Charging for resources is the main way the balance of an
+                    // idle canister decreases, so the `OnLowCycles` hook condition
+                    // is re-evaluated here.
+                    canister.update_on_low_cycles_hook_condition();
+                    // Sample the consumed cycles of idle canisters as well, so
+                    // that the cycles burn window keeps rolling between calls.
+                    canister.system_state.update_cycles_burn_window(state_time);
                 }
             }
         }
@@ -1300,6 +1308,8 @@ impl Scheduler for SchedulerImpl {
             {
                 let _timer = self.metrics.round_preparation_ingress.start_timer();
                 self.purge_expired_ingress_messages(&mut state, &mut canister_ingress_latencies);
+                self.exec_env
+                    .enqueue_due_scheduled_management_calls(&mut state);
             }
 
             // In the future, subnet messages might be executed in threads. In
@@ -2006,6 +2016,7 @@ fn observe_replicated_state_metrics(
             Some(&ExecutionTask::Heartbeat)
             | Some(&ExecutionTask::GlobalTimer)
             | Some(&ExecutionTask::OnLowWasmMemory)
+            | Some(&ExecutionTask::OnLowCycles)
             | None => {}
         }
         consumed_cycles_total += canister.system_state.canister_metrics.consumed_cycles;
@@ -2202,7 +2213,8 @@ fn can_execute_subnet_msg(
             None
             | Some(ExecutionTask::Heartbeat)
             | Some(ExecutionTask::GlobalTimer)
-            | Some(ExecutionTask::OnLowWasmMemory) => (false, false),
+            | Some(ExecutionTask::OnLowWasmMemory)
+            | Some(ExecutionTask::OnLowCycles) => (false, false),
             Some(ExecutionTask::PausedExecution { .. })
             | Some(ExecutionTask::PausedInstallCode(_)) => (true, false),
             Some(ExecutionTask::AbortedExecution { .. })
@@ -2282,6 +2294,7 @@ fn get_instructions_limits_for_subnet_message(
             | NodeMetricsHistory
             | SubnetInfo
             | FetchCanisterLogs
+            | FetchCanisterHistory
             | ProvisionalCreateCanisterWithCycles
             | ProvisionalTopUpCanister
             | UploadChunk
@@ -2295,7 +2308,11 @@ fn get_instructions_limits_for_subnet_message(
             | ReadCanisterSnapshotData
             | UploadCanisterSnapshotMetadata
             | UploadCanisterSnapshotData
-            | RenameCanister => default_limits,
+            | RenameCanister
+            | ScheduleManagementCall
+            | ListScheduledManagementCalls
+            | CancelScheduledManagementCall
+            | ScheduledManagementCallStatus => default_limits,
             InstallCode | InstallChunkedCode => InstructionLimits::new(
                 dts,
                 config.max_instructions_per_install_code,
@@ -10,9 +10,10 @@ use ic_error_types::{ErrorCode, UserError};
 use ic_execution_environment::EnvironmentVariables;
 use ic_management_canister_types_private::CanisterInstallMode::{Install, Reinstall, Upgrade};
 use ic_management_canister_types_private::{
-    self as ic00, CanisterChange, CanisterChangeDetails, CanisterChangeOrigin, CanisterIdRecord,
-    CanisterInfoRequest, CanisterInfoResponse, CreateCanisterArgs, InstallCodeArgs, Method,
-    Payload, ProvisionalCreateCanisterWithCyclesArgs, UpdateSettingsArgs,
+    self as ic00, CanisterChange, CanisterChangeDetails, CanisterChangeOrigin,
+    CanisterHistoryVisibility, CanisterIdRecord, CanisterInfoRequest, CanisterInfoResponse,
+    CreateCanisterArgs, FetchCanisterHistoryRequest, FetchCanisterHistoryResponse, InstallCodeArgs,
+    Method, Payload, ProvisionalCreateCanisterWithCyclesArgs, UpdateSettingsArgs,
 };
 use ic_registry_subnet_type::SubnetType;
 use ic_replicated_state::canister_state::system_state::{
@@ -1385,3 +1386,126 @@ fn canister_history_memory_usage_ignored_in_invariant_checks() {
         .description()
         .contains("Canister cannot grow its memory usage."));
 }
+
+fn fetch_canister_history(
+    env: &StateMachine,
+    sender: PrincipalId,
+    canister_id: CanisterId,
+    num_requested_modules: Option<u64>,
+) -> Result<FetchCanisterHistoryResponse, UserError> {
+    match env.query_as(
+        sender,
+        CanisterId::ic_00(),
+        "fetch_canister_history",
+        FetchCanisterHistoryRequest::new(canister_id, num_requested_modules).encode(),
+    )? {
+        WasmResult::Reply(bytes) => Ok(FetchCanisterHistoryResponse::decode(&bytes[..])
+            .expect("failed to decode fetch_canister_history response")),
+        WasmResult::Reject(reason) => panic!("fetch_canister_history rejected: {}", reason),
+    }
+}
+
+#[test]
+fn fetch_canister_history_returns_argument_hashes_and_retained_modules() {
+    let (env, test_canister, test_canister_sha256) =
+        test_setup(SubnetType::Application, std::time::SystemTime::now());
+    let controller = user_test_id(7).get();
+    let auditor = user_test_id(8).get();
+
+    let canister_id = env.create_canister_with_cycles(
+        None,
+        INITIAL_CYCLES_BALANCE,
+        Some(
+            CanisterSettingsArgsBuilder::new()
+                .with_controllers(vec![controller])
+                .build(),
+        ),
+    );
+    let install_arg = vec![1, 2, 3];
+    env.execute_ingress_as(
+        controller,
+        ic00::IC_00,
+        Method::InstallCode,
+        InstallCodeArgs::new(Install, canister_id, test_canister, install_arg.clone()).encode(),
+    )
+    .unwrap();
+    env.execute_ingress_as(
+        controller,
+        ic00::IC_00,
+        Method::InstallCode,
+        InstallCodeArgs::new(
+            Upgrade,
+            canister_id,
+            UNIVERSAL_CANISTER_WASM.to_vec(),
+            vec![],
+        )
+        .encode(),
+    )
+    .unwrap();
+
+    let history = fetch_canister_history(&env, controller, canister_id, Some(2)).unwrap();
+    assert_eq!(history.total_num_changes, 3);
+    assert_eq!(
+        history
+            .changes
+            .iter()
+            .map(|entry| entry.arg_hash.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            None,
+            Some(Sha256::hash(&install_arg).to_vec()),
+            Some(Sha256::hash(&[]).to_vec()),
+        ]
+    );
+    // Only the installed module is retained, the replaced one is not.
+    assert_eq!(history.modules.len(), 2);
+    assert_eq!(history.modules[0].canister_version, 1);
+    assert_eq!(
+        history.modules[0].module_hash,
+        test_canister_sha256.to_vec()
+    );
+    assert_eq!(history.modules[0].wasm_module, None);
+    assert_eq!(history.modules[1].canister_version, 2);
+    assert_eq!(
+        history.modules[1].module_hash,
+        UNIVERSAL_CANISTER_WASM_SHA256.to_vec()
+    );
+    assert_eq!(
+        history.modules[1].wasm_module,
+        Some(UNIVERSAL_CANISTER_WASM.to_vec())
+    );
+
+    // The extended history is only visible to controllers by default.
+    let err = fetch_canister_history(&env, auditor, canister_id, None).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::CanisterRejectedMessage);
+    let history_visibility = |env: &StateMachine| {
+        env.canister_status_as(controller, canister_id)
+            .unwrap()
+            .unwrap()
+            .settings()
+            .history_visibility()
+    };
+    assert_eq!(
+        history_visibility(&env),
+        CanisterHistoryVisibility::Controllers
+    );
+
+    env.execute_ingress_as(
+        controller,
+        ic00::IC_00,
+        Method::UpdateSettings,
+        UpdateSettingsArgs {
+            canister_id: canister_id.into(),
+            settings: CanisterSettingsArgsBuilder::new()
+                .with_history_visibility(CanisterHistoryVisibility::Public)
+                .build(),
+            sender_canister_version: None,
+        }
+        .encode(),
+    )
+    .unwrap();
+    assert_eq!(history_visibility(&env), CanisterHistoryVisibility::Public);
+    let history = fetch_canister_history(&env, auditor, canister_id, None).unwrap();
+    assert_eq!(history.changes.len(), 3);
+    assert!(history.modules.is_empty());
+}
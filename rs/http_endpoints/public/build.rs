@@ -27,6 +27,7 @@ struct Dashboard<'a> {{
     replicated_state: &'a ic_replicated_state::replicated_state::ReplicatedState,
     canisters: &'a Vec<&'a ic_replicated_state::CanisterState>,
     replica_version: ic_types::ReplicaVersion,
+    block_lifecycle: &'a Vec<ic_interfaces::consensus::BlockLifecycleRecord>,
 }}
     "#,
             std::fs::read_to_string("templates/dashboard.html").unwrap()
@@ -2,6 +2,7 @@
 pub mod call_v2;
 pub mod call_v3;
 mod ingress_watcher;
+pub mod request_status_stream;
 
 pub use ingress_watcher::{IngressWatcher, IngressWatcherHandle};
 
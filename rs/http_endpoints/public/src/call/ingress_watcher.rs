@@ -42,10 +42,16 @@ struct IngressWatcherSubscription {
 #[derive(Clone)]
 pub struct IngressWatcherHandle {
     subscriber_registration_tx: Sender<IngressWatcherSubscription>,
+    certified_height: watch::Receiver<Height>,
     metrics: HttpHandlerMetrics,
 }
 
 impl IngressWatcherHandle {
+    /// Returns a receiver for the certified height observed by the [`IngressWatcher`].
+    pub(crate) fn certified_height(&self) -> watch::Receiver<Height> {
+        self.certified_height.clone()
+    }
+
     /// Subscribes for the certification of an ingress message, and returns a [`IngressCertificationSubscriber`], that can be
     /// used to wait for a message to be certified.
     pub(crate) async fn subscribe_for_certification(
@@ -103,6 +109,12 @@ impl IngressCertificationSubscriber {
             .start_timer();
         self.certification_notifier.notified().await;
     }
+
+    /// Like [`Self::wait_for_certification`], but keeps the subscriber, and
+    /// with it the subscription, alive until it is dropped.
+    pub(crate) async fn certified(&self) {
+        self.certification_notifier.notified().await;
+    }
 }
 
 enum MessageExecutionStatus {
@@ -143,6 +155,7 @@ impl IngressWatcher {
         let (subscriber_registration_tx, subscriber_registration_rx) =
             channel::<IngressWatcherSubscription>(INGRESS_WATCHER_CHANNEL_SIZE);
 
+        let certified_height = certified_height_watcher.clone();
         let ingress_watcher = Self {
             log,
             metrics: metrics.clone(),
@@ -163,6 +176,7 @@ impl IngressWatcher {
         (
             IngressWatcherHandle {
                 subscriber_registration_tx,
+                certified_height,
                 metrics,
             },
             join_handle,
@@ -0,0 +1,688 @@
+//! Module that deals with requests to /api/v3/canister/.../request_status_stream.
+//!
+//! The endpoint takes a signed read state request whose paths are all of the
+//! form `["request_status", <message id>]`, and responds with a stream of
+//! server-sent events. Whenever the certified status of one of the messages
+//! changes, e.g. from `received` to `processing` to `replied`, a `status` event
+//! carrying a certificate for the new status is sent. The stream is closed once
+//! all messages reached a terminal status.
+//!
+//! Each event's data is the hex encoding of a CBOR map with the fields
+//! `request_id`, `status` and `certificate` (for `status` events), or
+//! `request_id` and `reject_message` (for `error` events). The statuses that
+//! change at the same height share one certificate, which witnesses all of
+//! them.
+//!
+//! As streams outlive the request handler, the number of open streams is
+//! bounded by the module itself, both in total and per sender.
+
+use super::ingress_watcher::{IngressCertificationSubscriber, IngressWatcherHandle};
+use crate::{
+    common::{build_validator, into_cbor, validation_error_to_http_error, Cbor, WithTimeout},
+    metrics::HttpHandlerMetrics,
+    HttpError,
+};
+use axum::{
+    body::Body,
+    extract::{DefaultBodyLimit, State},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    Router,
+};
+use futures::{future::select_all, stream};
+use http::Request;
+use hyper::StatusCode;
+use ic_crypto_interfaces_sig_verification::IngressSigVerifier;
+use ic_crypto_tree_hash::{sparse_labeled_tree_from_paths, Label, MixedHashTree, Path};
+use ic_interfaces::time_source::{SysTimeSource, TimeSource};
+use ic_interfaces_registry::RegistryClient;
+use ic_interfaces_state_manager::StateReader;
+use ic_logger::ReplicaLogger;
+use ic_registry_client_helpers::crypto::root_of_trust::RegistryRootOfTrustProvider;
+use ic_replicated_state::ReplicatedState;
+use ic_types::{
+    consensus::certification::Certification,
+    malicious_flags::MaliciousFlags,
+    messages::{
+        Blob, Certificate, CertificateDelegation, HttpReadStateContent, HttpRequest,
+        HttpRequestEnvelope, MessageId, ReadState, EXPECTED_MESSAGE_ID_LENGTH,
+    },
+    CanisterId, Height, UserId,
+};
+use ic_validator::{CanisterIdSet, HttpRequestVerifier};
+use serde_cbor::Value as CBOR;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    convert::{Infallible, TryFrom},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    sync::{watch, OwnedSemaphorePermit, Semaphore},
+    time::{timeout_at, Instant},
+};
+use tokio_util::time::FutureExt;
+use tower::{util::BoxCloneService, ServiceBuilder};
+
+/// The maximum number of messages whose status can be tracked by one stream.
+const MAX_MESSAGES_PER_STREAM: usize = 100;
+
+/// The maximum number of streams that can be open at the same time.
+const MAX_OPEN_STREAMS: usize = 1_000;
+
+/// The maximum number of streams a single sender can have open at the same
+/// time.
+const MAX_OPEN_STREAMS_PER_SENDER: usize = 10;
+
+/// The maximum time a stream stays open. Messages expire after at most five
+/// minutes, so all of them reached a terminal status or were pruned by then.
+const MAX_STREAM_DURATION: Duration = Duration::from_secs(6 * 60);
+
+/// The timeout duration used when creating a subscriber for the ingress message,
+/// by calling [`IngressWatcherHandle::subscribe_for_certification`].
+const SUBSCRIPTION_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The statuses after which the status of a message can no longer change.
+const TERMINAL_STATUSES: [&str; 3] = ["replied", "rejected", "done"];
+
+const STATUS_EVENT: &str = "status";
+const ERROR_EVENT: &str = "error";
+
+#[derive(Clone)]
+struct RequestStatusStreamState {
+    log: ReplicaLogger,
+    ingress_watcher_handle: IngressWatcherHandle,
+    metrics: HttpHandlerMetrics,
+    delegation_from_nns: watch::Receiver<Option<CertificateDelegation>>,
+    state_reader: Arc<dyn StateReader<State = ReplicatedState>>,
+    time_source: Arc<dyn TimeSource>,
+    validator: Arc<dyn HttpRequestVerifier<ReadState, RegistryRootOfTrustProvider>>,
+    registry_client: Arc<dyn RegistryClient>,
+    open_streams: OpenStreams,
+}
+
+/// Keeps track of the open streams, which outlive the requests that opened
+/// them and are thus not bounded by the concurrency limit of the router.
+#[derive(Clone)]
+struct OpenStreams {
+    permits: Arc<Semaphore>,
+    per_sender: Arc<Mutex<BTreeMap<UserId, usize>>>,
+}
+
+impl OpenStreams {
+    fn new() -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(MAX_OPEN_STREAMS)),
+            per_sender: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// Reserves a stream for `sender`, if neither the total nor the sender's
+    /// limit of open streams is reached. The stream is released when the
+    /// returned guard is dropped.
+    fn try_open(&self, sender: UserId) -> Option<OpenStreamGuard> {
+        let permit = Arc::clone(&self.permits).try_acquire_owned().ok()?;
+        let mut per_sender = self.per_sender.lock().unwrap();
+        let open = per_sender.entry(sender).or_default();
+        if *open >= MAX_OPEN_STREAMS_PER_SENDER {
+            return None;
+        }
+        *open += 1;
+        Some(OpenStreamGuard {
+            _permit: permit,
+            sender,
+            per_sender: Arc::clone(&self.per_sender),
+        })
+    }
+}
+
+struct OpenStreamGuard {
+    _permit: OwnedSemaphorePermit,
+    sender: UserId,
+    per_sender: Arc<Mutex<BTreeMap<UserId, usize>>>,
+}
+
+impl Drop for OpenStreamGuard {
+    fn drop(&mut self) {
+        let mut per_sender = self.per_sender.lock().unwrap();
+        if let Some(open) = per_sender.get_mut(&self.sender) {
+            *open -= 1;
+            if *open == 0 {
+                per_sender.remove(&self.sender);
+            }
+        }
+    }
+}
+
+pub(crate) fn route() -> &'static str {
+    "/api/v3/canister/{effective_canister_id}/request_status_stream"
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn new_router(
+    log: ReplicaLogger,
+    ingress_watcher_handle: IngressWatcherHandle,
+    metrics: HttpHandlerMetrics,
+    delegation_from_nns: watch::Receiver<Option<CertificateDelegation>>,
+    state_reader: Arc<dyn StateReader<State = ReplicatedState>>,
+    registry_client: Arc<dyn RegistryClient>,
+    ingress_verifier: Arc<dyn IngressSigVerifier + Send + Sync>,
+    malicious_flags: Option<MaliciousFlags>,
+) -> Router {
+    let state = RequestStatusStreamState {
+        log,
+        ingress_watcher_handle,
+        metrics,
+        delegation_from_nns,
+        state_reader,
+        time_source: Arc::new(SysTimeSource::new()),
+        validator: build_validator(ingress_verifier, malicious_flags),
+        registry_client,
+        open_streams: OpenStreams::new(),
+    };
+
+    Router::new().route_service(
+        route(),
+        axum::routing::post(request_status_stream)
+            .with_state(state)
+            .layer(ServiceBuilder::new().layer(DefaultBodyLimit::disable())),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn new_service(
+    log: ReplicaLogger,
+    ingress_watcher_handle: IngressWatcherHandle,
+    metrics: HttpHandlerMetrics,
+    delegation_from_nns: watch::Receiver<Option<CertificateDelegation>>,
+    state_reader: Arc<dyn StateReader<State = ReplicatedState>>,
+    registry_client: Arc<dyn RegistryClient>,
+    ingress_verifier: Arc<dyn IngressSigVerifier + Send + Sync>,
+    malicious_flags: Option<MaliciousFlags>,
+) -> BoxCloneService<Request<Body>, Response, Infallible> {
+    let router = new_router(
+        log,
+        ingress_watcher_handle,
+        metrics,
+        delegation_from_nns,
+        state_reader,
+        registry_client,
+        ingress_verifier,
+        malicious_flags,
+    );
+    BoxCloneService::new(router.into_service())
+}
+
+/// Handles a call to /api/v3/canister/../request_status_stream
+async fn request_status_stream(
+    // The effective canister id is only used by boundary nodes to route the
+    // request. As for read_state, request statuses are authorized by sender.
+    axum::extract::Path(_effective_canister_id): axum::extract::Path<CanisterId>,
+    State(RequestStatusStreamState {
+        log,
+        ingress_watcher_handle,
+        metrics,
+        delegation_from_nns,
+        state_reader,
+        time_source,
+        validator,
+        registry_client,
+        open_streams,
+    }): State<RequestStatusStreamState>,
+    WithTimeout(Cbor(request)): WithTimeout<Cbor<HttpRequestEnvelope<HttpReadStateContent>>>,
+) -> Response {
+    // Convert the message to a strongly-typed struct.
+    let request = match HttpRequest::<ReadState>::try_from(request) {
+        Ok(request) => request,
+        Err(e) => {
+            let status = StatusCode::BAD_REQUEST;
+            let text = format!("Malformed request: {:?}", e);
+            return (status, text).into_response();
+        }
+    };
+    let message_ids = match parse_message_ids(&request.content().paths) {
+        Ok(message_ids) => message_ids,
+        Err(HttpError { status, message }) => return (status, message).into_response(),
+    };
+
+    let registry_version = registry_client.get_latest_version();
+    let root_of_trust_provider =
+        RegistryRootOfTrustProvider::new(Arc::clone(&registry_client), registry_version);
+    // Since spawn blocking requires 'static we can't use any references
+    let request_c = request.clone();
+    let targets = match tokio::task::spawn_blocking(move || {
+        validator.validate_request(
+            &request_c,
+            time_source.get_relative_time(),
+            &root_of_trust_provider,
+        )
+    })
+    .await
+    {
+        Ok(Ok(targets)) => targets,
+        Ok(Err(err)) => {
+            let HttpError { status, message } = validation_error_to_http_error(&request, err, &log);
+            return (status, message).into_response();
+        }
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+
+    let Some(open_stream) = open_streams.try_open(request.sender()) else {
+        let status = StatusCode::TOO_MANY_REQUESTS;
+        let text = format!(
+            "Too many open request status streams. At most {} streams can be open per sender.",
+            MAX_OPEN_STREAMS_PER_SENDER
+        );
+        return (status, text).into_response();
+    };
+
+    // Keep the ingress watcher informed about the messages, so that we are
+    // notified as soon as they are certified. If a message is already tracked,
+    // e.g. by a synchronous call, we rely on the certified height alone.
+    let mut subscribers = BTreeMap::new();
+    for message_id in &message_ids {
+        if let Ok(Ok(subscriber)) = ingress_watcher_handle
+            .clone()
+            .subscribe_for_certification(message_id.clone())
+            .timeout(SUBSCRIPTION_TIMEOUT)
+            .await
+        {
+            subscribers.insert(message_id.clone(), subscriber);
+        }
+    }
+
+    let mut status_stream = RequestStatusStream {
+        user: request.sender(),
+        targets,
+        metrics,
+        delegation_from_nns,
+        state_reader,
+        statuses: message_ids
+            .into_iter()
+            .map(|message_id| (message_id, None))
+            .collect(),
+        subscribers,
+        certified_height: ingress_watcher_handle.certified_height(),
+        deadline: Instant::now() + MAX_STREAM_DURATION,
+        events: VecDeque::new(),
+        _open_stream: open_stream,
+    };
+    status_stream
+        .metrics
+        .request_status_stream_open_streams
+        .inc();
+
+    // Report the current statuses right away, failing the request if there is
+    // no certified state to report them from yet.
+    if !status_stream.poll_statuses().await {
+        let status = StatusCode::SERVICE_UNAVAILABLE;
+        let text = "Certified state is not available yet. Please try again...".to_string();
+        return (status, text).into_response();
+    }
+
+    let events = stream::unfold(status_stream, |mut status_stream| async move {
+        let event = status_stream.next_event().await?;
+        Some((Ok::<_, Infallible>(event), status_stream))
+    });
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// Extracts the message ids from the requested paths, all of which must be
+/// of the form `["request_status", <message id>]`.
+fn parse_message_ids(paths: &[Path]) -> Result<Vec<MessageId>, HttpError> {
+    let mut message_ids = Vec::new();
+    for path in paths {
+        let labels: Vec<&[u8]> = path.iter().map(|label| label.as_bytes()).collect();
+        let [b"request_status", request_id] = labels.as_slice() else {
+            return Err(HttpError {
+                status: StatusCode::NOT_FOUND,
+                message: "Invalid path requested. Only request_status paths of the form [\"request_status\", <request id>] can be streamed.".to_string(),
+            });
+        };
+        let message_id = MessageId::try_from(*request_id).map_err(|_| HttpError {
+            status: StatusCode::BAD_REQUEST,
+            message: format!(
+                "Invalid request id in paths. Maybe the request ID is not of {} bytes in length?!",
+                EXPECTED_MESSAGE_ID_LENGTH
+            ),
+        })?;
+        if !message_ids.contains(&message_id) {
+            message_ids.push(message_id);
+        }
+    }
+
+    if message_ids.is_empty() {
+        return Err(HttpError {
+            status: StatusCode::BAD_REQUEST,
+            message: "No request ids to stream the status of.".to_string(),
+        });
+    }
+    if message_ids.len() > MAX_MESSAGES_PER_STREAM {
+        return Err(HttpError {
+            status: StatusCode::BAD_REQUEST,
+            message: format!(
+                "The status of at most {} request ids can be streamed at once, got {}.",
+                MAX_MESSAGES_PER_STREAM,
+                message_ids.len()
+            ),
+        });
+    }
+    Ok(message_ids)
+}
+
+/// The changes of the statuses of the tracked messages, as read from one
+/// certified state.
+#[derive(Default)]
+struct MessageStatusUpdates {
+    /// The messages whose status changed, with their new status.
+    changed: Vec<(MessageId, String)>,
+    /// The messages the user is not authorized to read, with the reason.
+    unauthorized: Vec<(MessageId, &'static str)>,
+    /// A witness for the statuses of all the changed messages, if any.
+    certified: Option<(MixedHashTree, Certification)>,
+}
+
+struct RequestStatusStream {
+    user: UserId,
+    targets: CanisterIdSet,
+    metrics: HttpHandlerMetrics,
+    delegation_from_nns: watch::Receiver<Option<CertificateDelegation>>,
+    state_reader: Arc<dyn StateReader<State = ReplicatedState>>,
+    /// Maps the messages that did not reach a terminal status yet to their
+    /// last sent status.
+    statuses: BTreeMap<MessageId, Option<String>>,
+    /// Subscribers of the messages that are tracked by the ingress watcher.
+    subscribers: BTreeMap<MessageId, IngressCertificationSubscriber>,
+    certified_height: watch::Receiver<Height>,
+    deadline: Instant,
+    /// Events that are ready to be sent.
+    events: VecDeque<Event>,
+    /// Counts the stream as open until it is dropped.
+    _open_stream: OpenStreamGuard,
+}
+
+impl Drop for RequestStatusStream {
+    fn drop(&mut self) {
+        self.metrics.request_status_stream_open_streams.dec();
+    }
+}
+
+impl RequestStatusStream {
+    /// Returns the next event to send, waiting for new certifications if
+    /// needed, or `None` once the stream should be closed.
+    async fn next_event(&mut self) -> Option<Event> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Some(event);
+            }
+            if self.statuses.is_empty() {
+                return None;
+            }
+            if !timeout_at(self.deadline, self.wait_for_certification())
+                .await
+                .unwrap_or(false)
+            {
+                return None;
+            }
+            self.poll_statuses().await;
+        }
+    }
+
+    /// Waits until either a new height is certified or one of the subscribed
+    /// messages is certified. Returns `false` if no new heights will be
+    /// certified anymore.
+    async fn wait_for_certification(&mut self) -> bool {
+        let subscribers = &self.subscribers;
+        let any_certified = async move {
+            if subscribers.is_empty() {
+                std::future::pending::<()>().await;
+            }
+            select_all(
+                subscribers
+                    .values()
+                    .map(|subscriber| Box::pin(subscriber.certified())),
+            )
+            .await;
+        };
+
+        tokio::select! {
+            changed = self.certified_height.changed() => changed.is_ok(),
+            _ = any_certified => true,
+        }
+    }
+
+    /// Reads the statuses of the tracked messages from the latest certified
+    /// state and queues events for the ones that changed. Returns `false` if
+    /// no certified state is available.
+    async fn poll_statuses(&mut self) -> bool {
+        let state_reader = self.state_reader.clone();
+        let user = self.user;
+        let targets = self.targets.clone();
+        let last_statuses = self.statuses.clone();
+        let updates = match tokio::task::spawn_blocking(move || {
+            read_message_statuses(state_reader.as_ref(), &user, &targets, &last_statuses)
+        })
+        .await
+        {
+            Ok(Some(updates)) => updates,
+            Ok(None) | Err(_) => return false,
+        };
+
+        if let Some((tree, certification)) = updates.certified {
+            let signature = certification.signed.signature.signature.get().0;
+            let certificate = into_cbor(&Certificate {
+                tree,
+                signature: Blob(signature),
+                delegation: self.delegation_from_nns.borrow().clone(),
+            });
+            for (message_id, status) in updates.changed {
+                self.queue_event(
+                    STATUS_EVENT,
+                    &status,
+                    &message_id,
+                    [
+                        ("status", CBOR::Text(status.clone())),
+                        ("certificate", CBOR::Bytes(certificate.clone())),
+                    ],
+                );
+
+                if TERMINAL_STATUSES.contains(&status.as_str()) {
+                    self.untrack(&message_id);
+                } else if let Some(last_status) = self.statuses.get_mut(&message_id) {
+                    *last_status = Some(status);
+                }
+            }
+        }
+        for (message_id, reason) in updates.unauthorized {
+            self.queue_event(
+                ERROR_EVENT,
+                ERROR_EVENT,
+                &message_id,
+                [("reject_message", CBOR::Text(reason.to_string()))],
+            );
+            self.untrack(&message_id);
+        }
+        true
+    }
+
+    fn queue_event(
+        &mut self,
+        event: &str,
+        metric_label: &str,
+        message_id: &MessageId,
+        fields: impl IntoIterator<Item = (&'static str, CBOR)>,
+    ) {
+        let mut data = BTreeMap::from([(
+            CBOR::Text("request_id".to_string()),
+            CBOR::Bytes(message_id.as_bytes().to_vec()),
+        )]);
+        data.extend(
+            fields
+                .into_iter()
+                .map(|(field, value)| (CBOR::Text(field.to_string()), value)),
+        );
+
+        self.metrics
+            .request_status_stream_events_total
+            .with_label_values(&[metric_label])
+            .inc();
+        self.events.push_back(
+            Event::default()
+                .event(event)
+                .data(hex::encode(into_cbor(&CBOR::Map(data)))),
+        );
+    }
+
+    fn untrack(&mut self, message_id: &MessageId) {
+        self.statuses.remove(message_id);
+        self.subscribers.remove(message_id);
+    }
+}
+
+/// Reads the certified status of each message, after verifying that `user`
+/// is authorized to read it, and returns the ones that differ from
+/// `last_statuses`. A single witness for all of them is only built if any
+/// status changed. Returns `None` if no certified state is available.
+fn read_message_statuses(
+    state_reader: &dyn StateReader<State = ReplicatedState>,
+    user: &UserId,
+    targets: &CanisterIdSet,
+    last_statuses: &BTreeMap<MessageId, Option<String>>,
+) -> Option<MessageStatusUpdates> {
+    let certified_state_reader = state_reader.get_certified_state_snapshot()?;
+
+    let mut updates = MessageStatusUpdates::default();
+    for (message_id, last_status) in last_statuses {
+        // Verify that the request was signed by the same user, as read_state does.
+        let ingress_status = certified_state_reader
+            .get_state()
+            .get_ingress_status(message_id);
+        if ingress_status
+            .user_id()
+            .is_some_and(|ingress_user_id| ingress_user_id != *user)
+        {
+            updates.unauthorized.push((
+                message_id.clone(),
+                "The user tries to access Request ID not signed by the caller.",
+            ));
+            continue;
+        }
+        if ingress_status
+            .receiver()
+            .is_some_and(|receiver| !targets.contains(&receiver))
+        {
+            updates.unauthorized.push((
+                message_id.clone(),
+                "The user tries to access request IDs for canisters not belonging to sender delegation targets.",
+            ));
+            continue;
+        }
+
+        let status = ingress_status.as_str();
+        if last_status.as_deref() != Some(status) {
+            updates
+                .changed
+                .push((message_id.clone(), status.to_string()));
+        }
+    }
+    if updates.changed.is_empty() {
+        return Some(updates);
+    }
+
+    // We always add time path to comply with the IC spec.
+    let paths: Vec<_> = std::iter::once(Path::from(Label::from("time")))
+        .chain(updates.changed.iter().map(|(message_id, _)| {
+            Path::from(vec![
+                Label::from("request_status"),
+                Label::from(message_id.clone()),
+            ])
+        }))
+        .collect();
+    let labeled_tree =
+        sparse_labeled_tree_from_paths(&paths).expect("Paths are within length bound.");
+    updates.certified = Some(certified_state_reader.read_certified_state(&labeled_tree)?);
+    Some(updates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_types::PrincipalId;
+
+    fn request_status_path(message_id: &[u8]) -> Path {
+        Path::new(vec![Label::from("request_status"), message_id.into()])
+    }
+
+    #[test]
+    fn parse_message_ids_deduplicates_request_ids() {
+        let paths = vec![
+            request_status_path(&[1; 32]),
+            request_status_path(&[2; 32]),
+            request_status_path(&[1; 32]),
+        ];
+
+        assert_eq!(
+            parse_message_ids(&paths),
+            Ok(vec![
+                MessageId::try_from(&[1; 32][..]).unwrap(),
+                MessageId::try_from(&[2; 32][..]).unwrap(),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_message_ids_rejects_invalid_paths() {
+        let status = |paths: Vec<Path>| parse_message_ids(&paths).unwrap_err().status;
+
+        assert_eq!(status(vec![]), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            status(vec![Path::from(Label::from("time"))]),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            status(vec![Path::new(vec![
+                Label::from("request_status"),
+                [1; 32].into(),
+                Label::from("reply"),
+            ])]),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            status(vec![request_status_path(&[1; 31])]),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            status(
+                (0..=MAX_MESSAGES_PER_STREAM)
+                    .map(|i| request_status_path(&[i as u8; 32]))
+                    .collect()
+            ),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[test]
+    fn open_streams_are_limited_per_sender() {
+        let open_streams = OpenStreams::new();
+        let sender = UserId::from(PrincipalId::new_user_test_id(1));
+        let other_sender = UserId::from(PrincipalId::new_user_test_id(2));
+
+        let mut guards: Vec<_> = (0..MAX_OPEN_STREAMS_PER_SENDER)
+            .map(|_| open_streams.try_open(sender).unwrap())
+            .collect();
+        assert!(open_streams.try_open(sender).is_none());
+        assert!(open_streams.try_open(other_sender).is_some());
+
+        // Closing a stream allows the sender to open another one.
+        guards.pop();
+        assert!(open_streams.try_open(sender).is_some());
+
+        guards.clear();
+        assert!(open_streams.per_sender.lock().unwrap().is_empty());
+        assert_eq!(open_streams.permits.available_permits(), MAX_OPEN_STREAMS);
+    }
+}
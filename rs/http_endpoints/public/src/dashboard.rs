@@ -7,10 +7,11 @@ use askama::Template;
 use axum::{
     extract::State,
     response::{Html, IntoResponse},
-    Router,
+    Json, Router,
 };
 use hyper::StatusCode;
 use ic_config::http_handler::Config;
+use ic_interfaces::consensus::{BlockLifecycleRecord, BlockLifecycleStage, BlockLifecycleTracer};
 use ic_interfaces_state_manager::StateReader;
 use ic_registry_subnet_type::SubnetType;
 use ic_replicated_state::ReplicatedState;
@@ -24,6 +25,7 @@ pub(crate) struct DashboardService {
     config: Config,
     subnet_type: SubnetType,
     state_reader: Arc<dyn StateReader<State = ReplicatedState>>,
+    block_lifecycle_tracer: Option<Arc<dyn BlockLifecycleTracer>>,
 }
 
 impl DashboardService {
@@ -31,28 +33,73 @@ impl DashboardService {
         "/_/dashboard"
     }
 
+    pub(crate) fn block_lifecycle_route() -> &'static str {
+        "/_/dashboard/block_lifecycle"
+    }
+
     pub(crate) fn new_router(
         config: Config,
         subnet_type: SubnetType,
         state_reader: Arc<dyn StateReader<State = ReplicatedState>>,
+        block_lifecycle_tracer: Option<Arc<dyn BlockLifecycleTracer>>,
     ) -> Router {
         let state = DashboardService {
             config,
             subnet_type,
             state_reader,
+            block_lifecycle_tracer,
         };
-        Router::new().route(
-            DashboardService::route(),
-            axum::routing::get(dashboard).with_state(state),
-        )
+        Router::new()
+            .route(
+                DashboardService::route(),
+                axum::routing::get(dashboard).with_state(state.clone()),
+            )
+            .route(
+                DashboardService::block_lifecycle_route(),
+                axum::routing::get(block_lifecycle).with_state(state),
+            )
+    }
+}
+
+/// Returns the delay between the first local sighting of a block proposal for
+/// the record's height and the given stage, for display on the dashboard.
+fn lifecycle_delay(record: &BlockLifecycleRecord, stage: BlockLifecycleStage) -> String {
+    match (record.first_seen(), record.get(stage)) {
+        (Some(first_seen), Some(time)) => {
+            format!(
+                "{} ms",
+                time.saturating_duration_since(first_seen).as_millis()
+            )
+        }
+        (None, Some(_)) => String::from("?"),
+        (_, None) => String::from("-"),
     }
 }
 
+fn block_lifecycle_records(
+    block_lifecycle_tracer: &Option<Arc<dyn BlockLifecycleTracer>>,
+) -> Vec<BlockLifecycleRecord> {
+    block_lifecycle_tracer
+        .as_ref()
+        .map(|tracer| tracer.records())
+        .unwrap_or_default()
+}
+
+async fn block_lifecycle(
+    State(DashboardService {
+        block_lifecycle_tracer,
+        ..
+    }): State<DashboardService>,
+) -> Json<Vec<BlockLifecycleRecord>> {
+    Json(block_lifecycle_records(&block_lifecycle_tracer))
+}
+
 async fn dashboard(
     State(DashboardService {
         config,
         subnet_type,
         state_reader,
+        block_lifecycle_tracer,
     }): State<DashboardService>,
 ) -> impl IntoResponse {
     let labeled_state =
@@ -70,6 +117,9 @@ async fn dashboard(
     // See https://github.com/djc/askama/issues/333
     let canisters: Vec<&ic_replicated_state::CanisterState> =
         labeled_state.get_ref().canisters_iter().collect();
+    // Most recent heights first.
+    let mut block_lifecycle = block_lifecycle_records(&block_lifecycle_tracer);
+    block_lifecycle.reverse();
 
     let dashboard = Dashboard {
         subnet_type,
@@ -78,6 +128,7 @@ async fn dashboard(
         replicated_state: labeled_state.get_ref(),
         canisters: &canisters,
         replica_version: ReplicaVersion::default(),
+        block_lifecycle: &block_lifecycle,
     };
 
     match dashboard.render() {
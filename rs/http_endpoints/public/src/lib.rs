@@ -25,7 +25,10 @@ cfg_if::cfg_if! {
     }
 }
 
-pub use call::{call_v2, call_v3, IngressValidatorBuilder, IngressWatcher, IngressWatcherHandle};
+pub use call::{
+    call_v2, call_v3, request_status_stream, IngressValidatorBuilder, IngressWatcher,
+    IngressWatcherHandle,
+};
 pub use common::cors_layer;
 use common::CONTENT_TYPE_CBOR;
 use ic_http_endpoints_async_utils::start_tcp_listener;
@@ -64,6 +67,7 @@ use ic_config::http_handler::Config;
 use ic_crypto_interfaces_sig_verification::IngressSigVerifier;
 use ic_crypto_tls_interfaces::TlsConfig;
 use ic_interfaces::{
+    consensus::BlockLifecycleTracer,
     consensus_pool::ConsensusPoolCache,
     crypto::BasicSigner,
     execution_environment::{IngressFilterService, QueryExecutionService},
@@ -125,6 +129,7 @@ pub struct HttpError {
 struct HttpHandler {
     call_router: Router,
     call_v3_router: Router,
+    request_status_stream_router: Router,
     query_router: Router,
     catchup_router: Router,
     dashboard_router: Router,
@@ -265,6 +270,7 @@ pub fn start_server(
     tracing_handle: ReloadHandles,
     certified_height_watcher: watch::Receiver<Height>,
     completed_execution_messages_rx: Receiver<(MessageId, Height)>,
+    block_lifecycle_tracer: Option<Arc<dyn BlockLifecycleTracer>>,
     cancellation_token: CancellationToken,
 ) {
     info!(log, "Starting HTTP server...");
@@ -304,6 +310,17 @@ pub fn start_server(
     let call_router =
         call_v2::new_router(call_handler.clone(), Some(ingress_watcher_handle.clone()));
 
+    let request_status_stream_router = request_status_stream::new_router(
+        log.clone(),
+        ingress_watcher_handle.clone(),
+        metrics.clone(),
+        delegation_from_nns.clone(),
+        state_reader.clone(),
+        registry_client.clone(),
+        ingress_verifier.clone(),
+        Some(malicious_flags.clone()),
+    );
+
     let call_v3_router = call_v3::new_router(
         call_handler,
         ingress_watcher_handle,
@@ -316,6 +333,7 @@ pub fn start_server(
     let query_router = QueryServiceBuilder::builder(
         log.clone(),
         node_id,
+        subnet_id,
         query_signer,
         registry_client.clone(),
         ingress_verifier.clone(),
@@ -348,8 +366,12 @@ pub fn start_server(
         Arc::clone(&health_status),
         state_reader.clone(),
     );
-    let dashboard_router =
-        DashboardService::new_router(config.clone(), subnet_type, state_reader.clone());
+    let dashboard_router = DashboardService::new_router(
+        config.clone(),
+        subnet_type,
+        state_reader.clone(),
+        block_lifecycle_tracer,
+    );
     let catchup_router = CatchUpPackageService::new_router(consensus_pool_cache.clone());
 
     let pprof_home_router = PprofHomeService::new_router();
@@ -378,6 +400,7 @@ pub fn start_server(
     let http_handler = HttpHandler {
         call_router,
         call_v3_router,
+        request_status_stream_router,
         query_router,
         status_router,
         catchup_router,
@@ -567,6 +590,19 @@ fn make_router(
             ),
         )
         .merge(http_handler.call_v3_router)
+        // The concurrency limit only bounds the handlers, which return as soon
+        // as the stream is set up. The streams themselves are bounded by the
+        // request_status_stream module.
+        .merge(
+            http_handler.request_status_stream_router.layer(
+                ServiceBuilder::new()
+                    .layer(HandleErrorLayer::new(map_box_error_to_response))
+                    .load_shed()
+                    .layer(GlobalConcurrencyLimitLayer::new(
+                        config.max_read_state_concurrent_requests,
+                    )),
+            ),
+        )
         .merge(
             http_handler.query_router.layer(
                 ServiceBuilder::new()
@@ -784,6 +820,8 @@ pub(crate) mod tests {
         let http_handler = HttpHandler {
             call_router: Router::new().route(call_v2::route(), axum::routing::post(dummy)),
             call_v3_router: Router::new().route(call_v3::route(), axum::routing::post(dummy)),
+            request_status_stream_router: Router::new()
+                .route(request_status_stream::route(), axum::routing::post(dummy)),
             query_router: Router::new()
                 .route(QueryService::route(), axum::routing::post(dummy_cbor)),
             catchup_router: Router::new().route(
@@ -28,6 +28,8 @@ pub const CALL_V3_EARLY_RESPONSE_MESSAGE_ALREADY_IN_CERTIFIED_STATE: &str =
 pub const CALL_V3_STATUS_IS_NOT_LEAF: &str = "not_leaf";
 pub const CALL_V3_STATUS_IS_INVALID_UTF8: &str = "is_invalid_utf8";
 
+pub const LABEL_REQUEST_STATUS_STREAM_EVENT: &str = "event";
+
 /// Placeholder used when we can't determine the appropriate prometheus label.
 pub const LABEL_UNKNOWN: &str = "unknown";
 
@@ -71,6 +73,10 @@ pub struct HttpHandlerMetrics {
     // Call v3 handler metrics
     pub call_v3_early_response_trigger_total: IntCounterVec,
     pub call_v3_certificate_status_total: IntCounterVec,
+
+    // Request status stream metrics
+    pub request_status_stream_open_streams: IntGauge,
+    pub request_status_stream_events_total: IntCounterVec,
 }
 
 // There is a mismatch between the labels and the public spec.
@@ -202,6 +208,15 @@ impl HttpHandlerMetrics {
                 "The count of early response triggers for the /v3/.../call endpoint.",
                 &[LABEL_CALL_V3_EARLY_RESPONSE_TRIGGER],
             ),
+            request_status_stream_open_streams: metrics_registry.int_gauge(
+                "replica_http_request_status_stream_open_streams",
+                "The number of open streams of the /v3/.../request_status_stream endpoint.",
+            ),
+            request_status_stream_events_total: metrics_registry.int_counter_vec(
+                "replica_http_request_status_stream_events_total",
+                "The count of events sent by the /v3/.../request_status_stream endpoint. I.e. received, processing, replied, error, etc.",
+                &[LABEL_REQUEST_STATUS_STREAM_EVENT],
+            ),
         }
     }
 }
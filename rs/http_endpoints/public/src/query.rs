@@ -1,8 +1,9 @@
-//! Module that deals with requests to /api/v2/canister/.../query
+//! Module that deals with requests to /api/v2/canister/.../query and
+//! /api/v3/canister/.../batch_query
 
 use crate::{
     common::{build_validator, validation_error_to_http_error, Cbor, WithTimeout},
-    ReplicaHealthStatus,
+    HttpError, ReplicaHealthStatus,
 };
 
 use axum::{
@@ -12,6 +13,7 @@ use axum::{
     Router,
 };
 use crossbeam::atomic::AtomicCell;
+use futures::{stream, StreamExt};
 use http::Request;
 use hyper::StatusCode;
 use ic_crypto_interfaces_sig_verification::IngressSigVerifier;
@@ -23,7 +25,9 @@ use ic_interfaces::{
 };
 use ic_interfaces_registry::RegistryClient;
 use ic_logger::{error, ReplicaLogger};
-use ic_registry_client_helpers::crypto::root_of_trust::RegistryRootOfTrustProvider;
+use ic_registry_client_helpers::{
+    crypto::root_of_trust::RegistryRootOfTrustProvider, routing_table::RoutingTableRegistry,
+};
 use ic_types::{
     ingress::WasmResult,
     malicious_flags::MaliciousFlags,
@@ -32,9 +36,10 @@ use ic_types::{
         HttpQueryResponseReply, HttpRequest, HttpRequestEnvelope, HttpSignedQueryResponse,
         NodeSignature, Query, QueryResponseHash,
     },
-    CanisterId, NodeId,
+    CanisterId, NodeId, RegistryVersion, SubnetId, Time,
 };
 use ic_validator::HttpRequestVerifier;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::{
     convert::{Infallible, TryFrom},
@@ -43,10 +48,20 @@ use std::{
 use tokio::sync::watch;
 use tower::{util::BoxCloneService, ServiceBuilder, ServiceExt};
 
+/// The maximum number of queries in a request to /api/v3/canister/.../batch_query.
+const MAX_QUERIES_PER_BATCH: usize = 100;
+
+/// The maximum number of queries of a batch that are executed at the same
+/// time. A batch only counts as one request towards the concurrency limit of
+/// the query router, so this bounds how much more than a single query it can
+/// use of the query execution capacity.
+const MAX_CONCURRENT_QUERIES_PER_BATCH: usize = 4;
+
 #[derive(Clone)]
 pub struct QueryService {
     log: ReplicaLogger,
     node_id: NodeId,
+    subnet_id: SubnetId,
     signer: Arc<dyn BasicSigner<QueryResponseHash> + Send + Sync>,
     health_status: Arc<AtomicCell<ReplicaHealthStatus>>,
     delegation_from_nns: watch::Receiver<Option<CertificateDelegation>>,
@@ -59,6 +74,7 @@ pub struct QueryService {
 pub struct QueryServiceBuilder {
     log: ReplicaLogger,
     node_id: NodeId,
+    subnet_id: SubnetId,
     signer: Arc<dyn BasicSigner<QueryResponseHash> + Send + Sync>,
     health_status: Option<Arc<AtomicCell<ReplicaHealthStatus>>>,
     malicious_flags: Option<MaliciousFlags>,
@@ -73,12 +89,17 @@ impl QueryService {
     pub(crate) fn route() -> &'static str {
         "/api/v2/canister/{effective_canister_id}/query"
     }
+
+    pub(crate) fn batch_route() -> &'static str {
+        "/api/v3/canister/{effective_canister_id}/batch_query"
+    }
 }
 
 impl QueryServiceBuilder {
     pub fn builder(
         log: ReplicaLogger,
         node_id: NodeId,
+        subnet_id: SubnetId,
         signer: Arc<dyn BasicSigner<QueryResponseHash> + Send + Sync>,
         registry_client: Arc<dyn RegistryClient>,
         ingress_verifier: Arc<dyn IngressSigVerifier + Send + Sync>,
@@ -88,6 +109,7 @@ impl QueryServiceBuilder {
         Self {
             log,
             node_id,
+            subnet_id,
             signer,
             health_status: None,
             malicious_flags: None,
@@ -122,6 +144,7 @@ impl QueryServiceBuilder {
         let state = QueryService {
             log: log.clone(),
             node_id: self.node_id,
+            subnet_id: self.subnet_id,
             signer: self.signer,
             health_status: self
                 .health_status
@@ -132,12 +155,19 @@ impl QueryServiceBuilder {
             registry_client: self.registry_client,
             query_execution_service: Arc::new(Mutex::new(self.query_execution_service)),
         };
-        Router::new().route_service(
-            QueryService::route(),
-            axum::routing::post(query)
-                .with_state(state)
-                .layer(ServiceBuilder::new().layer(DefaultBodyLimit::disable())),
-        )
+        Router::new()
+            .route_service(
+                QueryService::route(),
+                axum::routing::post(query)
+                    .with_state(state.clone())
+                    .layer(ServiceBuilder::new().layer(DefaultBodyLimit::disable())),
+            )
+            .route_service(
+                QueryService::batch_route(),
+                axum::routing::post(batch_query)
+                    .with_state(state)
+                    .layer(ServiceBuilder::new().layer(DefaultBodyLimit::disable())),
+            )
     }
 
     pub fn build_service(self) -> BoxCloneService<Request<Body>, Response, Infallible> {
@@ -148,146 +178,334 @@ impl QueryServiceBuilder {
 
 pub(crate) async fn query(
     axum::extract::Path(effective_canister_id): axum::extract::Path<CanisterId>,
-    State(QueryService {
-        log,
-        node_id,
-        registry_client,
-        time_source,
-        validator,
-        health_status,
-        signer,
-        delegation_from_nns,
-        query_execution_service,
-    }): State<QueryService>,
+    State(service): State<QueryService>,
     WithTimeout(Cbor(request)): WithTimeout<Cbor<HttpRequestEnvelope<HttpQueryContent>>>,
 ) -> impl IntoResponse {
-    if health_status.load() != ReplicaHealthStatus::Healthy {
-        let status = StatusCode::SERVICE_UNAVAILABLE;
-        let text = format!(
-            "Replica is unhealthy: {:?}. Check the /api/v2/status for more information.",
-            health_status.load(),
-        );
-        return (status, text).into_response();
+    if let Err(HttpError { status, message }) = service.check_health() {
+        return (status, message).into_response();
     }
-    let delegation_from_nns = delegation_from_nns.borrow().clone();
-
-    let registry_version = registry_client.get_latest_version();
 
     // Convert the message to a strongly-typed struct, making structural validations
     // on the way.
-    let request = match HttpRequest::<Query>::try_from(request) {
-        Ok(request) => request,
-        Err(e) => {
-            let status = StatusCode::BAD_REQUEST;
-            let text = format!("Malformed request: {:?}", e);
-            return (status, text).into_response();
+    let request = parse_query(request).and_then(|request| {
+        let canister_id = request.content().canister_id();
+        if canister_id != CanisterId::ic_00() && canister_id != effective_canister_id {
+            return Err(HttpError {
+                status: StatusCode::BAD_REQUEST,
+                message: format!(
+                    "Specified CanisterId {} does not match effective canister id in URL {}",
+                    canister_id, effective_canister_id
+                ),
+            });
         }
-    };
-    let canister_id = request.content().canister_id();
-    if canister_id != CanisterId::ic_00() && canister_id != effective_canister_id {
+        Ok(request)
+    });
+
+    let response = service
+        .execute_queries(vec![request])
+        .await
+        .pop()
+        .expect("One response per query.");
+    match response {
+        Ok(signed_query_response) => Cbor(signed_query_response).into_response(),
+        Err(HttpError { status, message }) => (status, message).into_response(),
+    }
+}
+
+/// The body of a request to /api/v3/canister/.../batch_query.
+#[derive(Debug, Deserialize)]
+pub struct HttpBatchQueryRequest {
+    pub queries: Vec<HttpRequestEnvelope<HttpQueryContent>>,
+}
+
+/// The response to /api/v3/canister/.../batch_query, with one entry per query
+/// of the request, in the same order.
+#[derive(Debug, Serialize)]
+pub struct HttpBatchQueryResponse {
+    pub responses: Vec<HttpBatchQueryResponseEntry>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum HttpBatchQueryResponseEntry {
+    /// The signed response, as returned by /api/v2/canister/.../query.
+    Response(HttpSignedQueryResponse),
+    /// The query was not executed, e.g. because it failed validation. Carries
+    /// the HTTP status code and message a single query would have failed with.
+    Error {
+        status: &'static str,
+        error_status_code: u16,
+        error_message: String,
+    },
+}
+
+impl From<Result<HttpSignedQueryResponse, HttpError>> for HttpBatchQueryResponseEntry {
+    fn from(result: Result<HttpSignedQueryResponse, HttpError>) -> Self {
+        match result {
+            Ok(response) => Self::Response(response),
+            Err(HttpError { status, message }) => Self::Error {
+                status: "error",
+                error_status_code: status.as_u16(),
+                error_message: message,
+            },
+        }
+    }
+}
+
+/// Handles a batch of queries, to canisters on this node's subnet. The
+/// queries are validated, executed and signed independently of each other,
+/// so one of them failing does not affect the others.
+pub(crate) async fn batch_query(
+    axum::extract::Path(_effective_canister_id): axum::extract::Path<CanisterId>,
+    State(service): State<QueryService>,
+    WithTimeout(Cbor(request)): WithTimeout<Cbor<HttpBatchQueryRequest>>,
+) -> impl IntoResponse {
+    if let Err(HttpError { status, message }) = service.check_health() {
+        return (status, message).into_response();
+    }
+
+    let HttpBatchQueryRequest { queries } = request;
+    if queries.is_empty() || queries.len() > MAX_QUERIES_PER_BATCH {
         let status = StatusCode::BAD_REQUEST;
         let text = format!(
-            "Specified CanisterId {} does not match effective canister id in URL {}",
-            canister_id, effective_canister_id
+            "A batch must contain between 1 and {} queries, got {}.",
+            MAX_QUERIES_PER_BATCH,
+            queries.len()
         );
         return (status, text).into_response();
     }
 
-    let root_of_trust_provider =
-        RegistryRootOfTrustProvider::new(Arc::clone(&registry_client), registry_version);
-    // Since spawn blocking requires 'static we can't use any references
-    let request_c = request.clone();
-    match tokio::task::spawn_blocking(move || {
-        validator.validate_request(
-            &request_c,
-            time_source.get_relative_time(),
-            &root_of_trust_provider,
-        )
-    })
-    .await
-    {
-        Ok(Ok(_)) => {}
-        Ok(Err(err)) => {
-            let http_err = validation_error_to_http_error(&request, err, &log);
-            return (http_err.status, http_err.message).into_response();
-        }
-        Err(_) => {
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    // Since the effective canister id only determines the subnet the batch is
+    // routed to, all queries must be to canisters on this subnet.
+    let registry_version = service.registry_client.get_latest_version();
+    let routing_table = match service.registry_client.get_routing_table(registry_version) {
+        Ok(Some(routing_table)) => routing_table,
+        Ok(None) | Err(_) => {
+            error!(
+                service.log,
+                "Failed to get the routing table at registry version {}.", registry_version
+            );
+            let status = StatusCode::INTERNAL_SERVER_ERROR;
+            let text = "Failed to get the routing table.".to_string();
+            return (status, text).into_response();
         }
     };
+    let requests = queries
+        .into_iter()
+        .map(|query| -> Result<HttpRequest<Query>, HttpError> {
+            let request = parse_query(query)?;
+            let canister_id = request.content().canister_id();
+            if canister_id == CanisterId::ic_00()
+                || routing_table.route(canister_id.get()) != Some(service.subnet_id)
+            {
+                return Err(HttpError {
+                    status: StatusCode::BAD_REQUEST,
+                    message: format!(
+                        "Canister {} is not hosted on subnet {}.",
+                        canister_id, service.subnet_id
+                    ),
+                });
+            }
+            Ok(request)
+        })
+        .collect();
 
-    let user_query = request.take_content();
-
-    let query_execution_service = query_execution_service.lock().unwrap().clone();
-    let query_execution_response = query_execution_service
-        .oneshot((user_query.clone(), delegation_from_nns))
+    let responses = service
+        .execute_queries(requests)
         .await
-        .unwrap();
+        .into_iter()
+        .map(HttpBatchQueryResponseEntry::from)
+        .collect();
+    Cbor(HttpBatchQueryResponse { responses }).into_response()
+}
 
-    let (response, timestamp) = match query_execution_response {
-        Err(QueryExecutionError::CertifiedStateUnavailable) => {
-            let status = StatusCode::SERVICE_UNAVAILABLE;
-            let text = "Certified state unavailable. Please try again.".to_string();
-            return (status, text).into_response();
+/// Converts the envelope to a strongly-typed struct, making structural
+/// validations on the way.
+fn parse_query(
+    request: HttpRequestEnvelope<HttpQueryContent>,
+) -> Result<HttpRequest<Query>, HttpError> {
+    HttpRequest::<Query>::try_from(request).map_err(|e| HttpError {
+        status: StatusCode::BAD_REQUEST,
+        message: format!("Malformed request: {:?}", e),
+    })
+}
+
+impl QueryService {
+    fn check_health(&self) -> Result<(), HttpError> {
+        if self.health_status.load() != ReplicaHealthStatus::Healthy {
+            return Err(HttpError {
+                status: StatusCode::SERVICE_UNAVAILABLE,
+                message: format!(
+                    "Replica is unhealthy: {:?}. Check the /api/v2/status for more information.",
+                    self.health_status.load(),
+                ),
+            });
         }
-        Ok((response, time)) => (response, time),
-    };
+        Ok(())
+    }
+
+    /// Validates, executes and signs the given queries, returning one result
+    /// per query in the same order. The queries share the registry version,
+    /// the NNS delegation and the blocking tasks for signature verification
+    /// and signing, and at most [`MAX_CONCURRENT_QUERIES_PER_BATCH`] of them
+    /// are executed in parallel.
+    async fn execute_queries(
+        &self,
+        requests: Vec<Result<HttpRequest<Query>, HttpError>>,
+    ) -> Vec<Result<HttpSignedQueryResponse, HttpError>> {
+        let delegation_from_nns = self.delegation_from_nns.borrow().clone();
+        let registry_version = self.registry_client.get_latest_version();
+
+        let queries = self.validate_queries(requests, registry_version).await;
 
-    let query_response = match response {
-        Ok(res) => match res {
-            WasmResult::Reply(vec) => HttpQueryResponse::Replied {
-                reply: HttpQueryResponseReply { arg: Blob(vec) },
+        let responses = stream::iter(queries)
+            .map(|query| {
+                let delegation_from_nns = delegation_from_nns.clone();
+                async move {
+                    let user_query = query?;
+                    let (response, timestamp) = self
+                        .execute_query(user_query.clone(), delegation_from_nns)
+                        .await?;
+                    let response_hash = QueryResponseHash::new(&response, &user_query, timestamp);
+                    Ok::<_, HttpError>((response, response_hash, timestamp))
+                }
+            })
+            .buffered(MAX_CONCURRENT_QUERIES_PER_BATCH)
+            .collect()
+            .await;
+
+        self.sign_responses(responses, registry_version).await
+    }
+
+    async fn validate_queries(
+        &self,
+        requests: Vec<Result<HttpRequest<Query>, HttpError>>,
+        registry_version: RegistryVersion,
+    ) -> Vec<Result<Query, HttpError>> {
+        let log = self.log.clone();
+        let validator = self.validator.clone();
+        let time_source = self.time_source.clone();
+        let root_of_trust_provider =
+            RegistryRootOfTrustProvider::new(Arc::clone(&self.registry_client), registry_version);
+        let num_requests = requests.len();
+
+        // Since spawn blocking requires 'static we can't use any references
+        match tokio::task::spawn_blocking(move || {
+            let current_time = time_source.get_relative_time();
+            requests
+                .into_iter()
+                .map(|request| -> Result<Query, HttpError> {
+                    let request = request?;
+                    validator
+                        .validate_request(&request, current_time, &root_of_trust_provider)
+                        .map_err(|err| validation_error_to_http_error(&request, err, &log))?;
+                    Ok(request.take_content())
+                })
+                .collect()
+        })
+        .await
+        {
+            Ok(queries) => queries,
+            Err(_) => (0..num_requests)
+                .map(|_| {
+                    Err(HttpError {
+                        status: StatusCode::INTERNAL_SERVER_ERROR,
+                        message: "".to_string(),
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    async fn execute_query(
+        &self,
+        user_query: Query,
+        delegation_from_nns: Option<CertificateDelegation>,
+    ) -> Result<(HttpQueryResponse, Time), HttpError> {
+        let query_execution_service = self.query_execution_service.lock().unwrap().clone();
+        let query_execution_response = query_execution_service
+            .oneshot((user_query, delegation_from_nns))
+            .await
+            .unwrap();
+
+        let (response, timestamp) = match query_execution_response {
+            Err(QueryExecutionError::CertifiedStateUnavailable) => {
+                return Err(HttpError {
+                    status: StatusCode::SERVICE_UNAVAILABLE,
+                    message: "Certified state unavailable. Please try again.".to_string(),
+                });
+            }
+            Ok((response, time)) => (response, time),
+        };
+
+        let query_response = match response {
+            Ok(res) => match res {
+                WasmResult::Reply(vec) => HttpQueryResponse::Replied {
+                    reply: HttpQueryResponseReply { arg: Blob(vec) },
+                },
+                WasmResult::Reject(message) => HttpQueryResponse::Rejected {
+                    error_code: ErrorCode::CanisterRejectedMessage.to_string(),
+                    reject_code: RejectCode::CanisterReject as u64,
+                    reject_message: message,
+                },
             },
-            WasmResult::Reject(message) => HttpQueryResponse::Rejected {
-                error_code: ErrorCode::CanisterRejectedMessage.to_string(),
-                reject_code: RejectCode::CanisterReject as u64,
-                reject_message: message,
+
+            Err(user_error) => HttpQueryResponse::Rejected {
+                error_code: user_error.code().to_string(),
+                reject_code: user_error.reject_code() as u64,
+                reject_message: user_error.description().to_string(),
             },
-        },
+        };
+        Ok((query_response, timestamp))
+    }
 
-        Err(user_error) => HttpQueryResponse::Rejected {
-            error_code: user_error.code().to_string(),
-            reject_code: user_error.reject_code() as u64,
-            reject_message: user_error.description().to_string(),
-        },
-    };
+    async fn sign_responses(
+        &self,
+        responses: Vec<Result<(HttpQueryResponse, QueryResponseHash, Time), HttpError>>,
+        registry_version: RegistryVersion,
+    ) -> Vec<Result<HttpSignedQueryResponse, HttpError>> {
+        let log = self.log.clone();
+        let signer = self.signer.clone();
+        let node_id = self.node_id;
 
-    let response_hash = QueryResponseHash::new(&query_response, &user_query, timestamp);
+        // We wrap `sign_basic` into `spawn_blocking`, otherwise calling `sign_basic` will panic
+        // if called from the tokio runtime.
+        tokio::task::spawn_blocking(move || {
+            responses
+                .into_iter()
+                .map(|response| -> Result<HttpSignedQueryResponse, HttpError> {
+                    let (query_response, response_hash, timestamp) = response?;
+                    match signer.sign_basic(&response_hash, node_id, registry_version) {
+                        Ok(signature) => {
+                            let signature_bytes = signature.get().0;
+                            let signature_blob = Blob(signature_bytes);
 
-    // We wrap `sign_basic` into `spawn_blocking`, otherwise calling `sign_basic` will panic
-    // if called from the tokio runtime.
-    let signature = tokio::task::spawn_blocking(move || {
-        signer.sign_basic(&response_hash, node_id, registry_version)
-    })
-    .await
-    .expect("Panicked while attempting to sign the query response.");
-
-    match signature {
-        Ok(signature) => {
-            let signature_bytes = signature.get().0;
-            let signature_blob = Blob(signature_bytes);
-
-            let node_signature = NodeSignature {
-                signature: signature_blob,
-                timestamp,
-                identity: node_id,
-            };
-
-            let signed_query_response = HttpSignedQueryResponse {
-                response: query_response,
-                node_signature,
-            };
-
-            Cbor(signed_query_response).into_response()
-        }
-        Err(signing_error) => {
-            error!(
-                log,
-                "Failed to sign the Query response: `{:?}`.", signing_error
-            );
-            let status = StatusCode::INTERNAL_SERVER_ERROR;
-            let text = "Failed to sign the Query response.".to_string();
-            (status, text).into_response()
-        }
+                            let node_signature = NodeSignature {
+                                signature: signature_blob,
+                                timestamp,
+                                identity: node_id,
+                            };
+
+                            Ok(HttpSignedQueryResponse {
+                                response: query_response,
+                                node_signature,
+                            })
+                        }
+                        Err(signing_error) => {
+                            error!(
+                                log,
+                                "Failed to sign the Query response: `{:?}`.", signing_error
+                            );
+                            Err(HttpError {
+                                status: StatusCode::INTERNAL_SERVER_ERROR,
+                                message: "Failed to sign the Query response.".to_string(),
+                            })
+                        }
+                    }
+                })
+                .collect()
+        })
+        .await
+        .expect("Panicked while attempting to sign the query response.")
     }
 }
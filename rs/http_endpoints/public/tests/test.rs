@@ -4,8 +4,9 @@
 pub mod common;
 
 use crate::common::{
-    create_conn_and_send_request, default_get_latest_state, default_latest_certified_height,
-    default_read_certified_state, get_free_localhost_socket_addr, HttpEndpointBuilder,
+    basic_registry_client, create_conn_and_send_request, default_get_latest_state,
+    default_latest_certified_height, default_read_certified_state, get_free_localhost_socket_addr,
+    HttpEndpointBuilder,
 };
 use axum::body::{to_bytes, Body};
 use bytes::Bytes;
@@ -32,11 +33,13 @@ use ic_interfaces_mocks::consensus_pool::MockConsensusPoolCache;
 use ic_interfaces_registry_mocks::MockRegistryClient;
 use ic_interfaces_state_manager::CertifiedStateSnapshot;
 use ic_interfaces_state_manager_mocks::MockStateManager;
-use ic_protobuf::registry::crypto::v1::{
-    AlgorithmId as AlgorithmIdProto, PublicKey as PublicKeyProto,
+use ic_protobuf::registry::{
+    crypto::v1::{AlgorithmId as AlgorithmIdProto, PublicKey as PublicKeyProto},
+    routing_table::v1 as pb,
 };
 use ic_read_state_response_parser::parse_subnet_read_state_response;
-use ic_registry_keys::make_crypto_threshold_signing_pubkey_key;
+use ic_registry_keys::{make_crypto_threshold_signing_pubkey_key, make_routing_table_record_key};
+use ic_registry_routing_table::{CanisterIdRange, RoutingTable};
 use ic_replicated_state::ReplicatedState;
 use ic_test_utilities_state::ReplicatedStateBuilder;
 use ic_test_utilities_types::ids::{canister_test_id, subnet_test_id, user_test_id, NODE_1};
@@ -54,7 +57,7 @@ use ic_types::{
     messages::{Blob, Certificate, CertificateDelegation},
     signature::ThresholdSignature,
     time::current_time,
-    CryptoHashOfPartialState, Height, PrincipalId, RegistryVersion,
+    CanisterId, CryptoHashOfPartialState, Height, PrincipalId, RegistryVersion,
 };
 use prost::Message;
 use reqwest::header::CONTENT_TYPE;
@@ -209,6 +212,93 @@ fn test_unauthorized_controller() {
     });
 }
 
+/// Tests that the /v3/.../request_status_stream endpoint sends the current
+/// certified status of the requested message as the first event.
+#[test]
+fn test_request_status_stream_sends_current_status() {
+    let rt = Runtime::new().unwrap();
+    let addr = get_free_localhost_socket_addr();
+    let config = Config {
+        listen_addr: addr,
+        ..Default::default()
+    };
+
+    HttpEndpointBuilder::new(rt.handle().clone(), config).run();
+
+    let message_id = [7; 32];
+    let path: Path = vec!["request_status".into(), message_id.into()].into();
+
+    rt.block_on(async {
+        wait_for_status_healthy(&addr).await.unwrap();
+
+        let mut response = CanisterReadState::new(vec![path], PrincipalId::default())
+            .request_status_stream(addr)
+            .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!(
+            "text/event-stream",
+            response.headers().get(CONTENT_TYPE).unwrap()
+        );
+
+        let event = response
+            .chunk()
+            .await
+            .unwrap()
+            .expect("Stream has an event.");
+        let event = std::str::from_utf8(&event).unwrap();
+        assert!(event.starts_with("event: status\n"), "{}", event);
+        let data = event
+            .lines()
+            .find_map(|line| line.strip_prefix("data: "))
+            .expect("Event has data.");
+
+        let CBOR::Map(data) = serde_cbor::from_slice(&hex::decode(data).unwrap()).unwrap() else {
+            panic!("Expected a map.");
+        };
+        assert_eq!(
+            data.get(&CBOR::Text("request_id".to_string())),
+            Some(&CBOR::Bytes(message_id.to_vec()))
+        );
+        assert_eq!(
+            data.get(&CBOR::Text("status".to_string())),
+            Some(&CBOR::Text("unknown".to_string()))
+        );
+        let Some(CBOR::Bytes(certificate)) = data.get(&CBOR::Text("certificate".to_string()))
+        else {
+            panic!("Certificate is missing.");
+        };
+        let _: Certificate = serde_cbor::from_slice(certificate).expect("Valid certificate");
+    });
+}
+
+/// Tests that the /v3/.../request_status_stream endpoint only streams request
+/// statuses.
+#[test]
+fn test_request_status_stream_rejects_other_paths() {
+    let rt = Runtime::new().unwrap();
+    let addr = get_free_localhost_socket_addr();
+    let config = Config {
+        listen_addr: addr,
+        ..Default::default()
+    };
+
+    HttpEndpointBuilder::new(rt.handle().clone(), config).run();
+
+    rt.block_on(async {
+        wait_for_status_healthy(&addr).await.unwrap();
+
+        let response = CanisterReadState::new(
+            vec![Path::from(Label::from("time"))],
+            PrincipalId::default(),
+        )
+        .request_status_stream(addr)
+        .await;
+
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+    });
+}
+
 // Test that that http endpoint rejects queries with mismatch between canister id an effective canister id.
 #[test]
 fn test_unauthorized_query() {
@@ -271,6 +361,100 @@ fn test_unauthorized_query() {
     });
 }
 
+/// Tests that the batch query endpoint executes the queries to canisters on
+/// the node's subnet, and reports an error for the others.
+#[test]
+fn test_batch_query() {
+    let rt = Runtime::new().unwrap();
+    let addr = get_free_localhost_socket_addr();
+    let config = Config {
+        listen_addr: addr,
+        ..Default::default()
+    };
+
+    let canister1: PrincipalId = "223xb-saaaa-aaaaf-arlqa-cai".parse().unwrap();
+    let canister2: PrincipalId = "224lq-3aaaa-aaaaf-ase7a-cai".parse().unwrap();
+
+    let mut routing_table = RoutingTable::new();
+    let canister_id = CanisterId::unchecked_from_principal(canister1);
+    routing_table
+        .insert(
+            CanisterIdRange {
+                start: canister_id,
+                end: canister_id,
+            },
+            subnet_test_id(1),
+        )
+        .unwrap();
+    let mut registry_client = basic_registry_client();
+    registry_client
+        .expect_get_value()
+        .withf(|key, _| key == make_routing_table_record_key().as_str())
+        .return_const(Ok(Some(
+            pb::RoutingTable::from(routing_table).encode_to_vec(),
+        )));
+
+    let mut handlers = HttpEndpointBuilder::new(rt.handle().clone(), config)
+        .with_registry_client(registry_client)
+        .run();
+
+    // Query mock that returns empty Ok("success") response.
+    rt.spawn(async move {
+        loop {
+            let (_, resp) = handlers.query_execution.next_request().await.unwrap();
+            resp.send_response(Ok((
+                Ok(WasmResult::Reply("success".into())),
+                current_time(),
+            )))
+        }
+    });
+
+    rt.block_on(async {
+        wait_for_status_healthy(&addr).await.unwrap();
+
+        let response = Query::batch_query(
+            vec![
+                Query::new(canister1, canister1),
+                Query::new(canister2, canister2),
+                Query::new(canister1, canister1),
+            ],
+            canister1,
+            addr,
+        )
+        .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        let response_body = response.bytes().await.unwrap();
+        let response =
+            serde_cbor::from_slice::<CBOR>(&response_body).expect("Response is a valid CBOR.");
+        let CBOR::Map(response_map) = response else {
+            panic!("Expected a map, got {:?}", response);
+        };
+        let Some(CBOR::Array(responses)) = response_map.get(&CBOR::Text("responses".to_string()))
+        else {
+            panic!("Expected an array of responses, got {:?}", response_map);
+        };
+
+        let statuses: Vec<_> = responses
+            .iter()
+            .map(|response| {
+                let CBOR::Map(response) = response else {
+                    panic!("Expected a map, got {:?}", response);
+                };
+                response.get(&CBOR::Text("status".to_string())).cloned()
+            })
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                Some(CBOR::Text("replied".to_string())),
+                Some(CBOR::Text("error".to_string())),
+                Some(CBOR::Text("replied".to_string())),
+            ]
+        );
+    });
+}
+
 /// Tests that the HTTP endpoints accepts update calls to the management canister,
 /// regardless of the effective canister id.
 #[rstest]
@@ -9,7 +9,7 @@ use ic_types::{
 };
 use reqwest::{header::CONTENT_TYPE, StatusCode};
 use serde_cbor::Value as CBOR;
-use std::{net::SocketAddr, time::Duration};
+use std::{collections::BTreeMap, net::SocketAddr, time::Duration};
 
 const INGRESS_EXPIRY_DURATION: Duration = Duration::from_secs(300);
 const METHOD_NAME: &str = "test";
@@ -178,6 +178,45 @@ impl Query {
     }
 
     pub async fn query(self, addr: SocketAddr) -> reqwest::Response {
+        let body = serde_cbor::to_vec(&self.envelope()).unwrap();
+        let url = format!(
+            "http://{}/api/v2/canister/{}/query",
+            addr, self.effective_canister_id
+        );
+
+        reqwest::Client::new()
+            .post(url)
+            .body(body)
+            .header(CONTENT_TYPE, APPLICATION_CBOR)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    /// Sends the queries in one request to the batch query endpoint of
+    /// `effective_canister_id`.
+    pub async fn batch_query(
+        queries: Vec<Self>,
+        effective_canister_id: PrincipalId,
+        addr: SocketAddr,
+    ) -> reqwest::Response {
+        let envelopes: Vec<_> = queries.iter().map(Self::envelope).collect();
+        let body = serde_cbor::to_vec(&BTreeMap::from([("queries", envelopes)])).unwrap();
+        let url = format!(
+            "http://{}/api/v3/canister/{}/batch_query",
+            addr, effective_canister_id
+        );
+
+        reqwest::Client::new()
+            .post(url)
+            .body(body)
+            .header(CONTENT_TYPE, APPLICATION_CBOR)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    fn envelope(&self) -> HttpRequestEnvelope<HttpQueryContent> {
         let ingress_expiry = (current_time() + INGRESS_EXPIRY_DURATION).as_nanos_since_unix_epoch();
 
         let call_content = HttpQueryContent::Query {
@@ -191,26 +230,12 @@ impl Query {
             },
         };
 
-        let envelope = HttpRequestEnvelope {
+        HttpRequestEnvelope {
             content: call_content,
             sender_pubkey: None,
             sender_sig: None,
             sender_delegation: None,
-        };
-
-        let body = serde_cbor::to_vec(&envelope).unwrap();
-        let url = format!(
-            "http://{}/api/v2/canister/{}/query",
-            addr, self.effective_canister_id
-        );
-
-        reqwest::Client::new()
-            .post(url)
-            .body(body)
-            .header(CONTENT_TYPE, APPLICATION_CBOR)
-            .send()
-            .await
-            .unwrap()
+        }
     }
 }
 
@@ -237,6 +262,16 @@ impl CanisterReadState {
     }
 
     pub async fn read_state(self, addr: SocketAddr) -> reqwest::Response {
+        self.send(addr, "api/v2", "read_state").await
+    }
+
+    /// Subscribes to the status of the request ids in the `["request_status", <request id>]`
+    /// paths, which is streamed back as server-sent events.
+    pub async fn request_status_stream(self, addr: SocketAddr) -> reqwest::Response {
+        self.send(addr, "api/v3", "request_status_stream").await
+    }
+
+    async fn send(self, addr: SocketAddr, api: &str, endpoint: &str) -> reqwest::Response {
         let ingress_expiry = (current_time() + INGRESS_EXPIRY_DURATION).as_nanos_since_unix_epoch();
 
         let call_content = HttpReadStateContent::ReadState {
@@ -257,8 +292,8 @@ impl CanisterReadState {
 
         let body = serde_cbor::to_vec(&envelope).unwrap();
         let url = format!(
-            "http://{}/api/v2/canister/{}/read_state",
-            addr, self.effective_canister_id
+            "http://{}/{}/canister/{}/{}",
+            addr, api, self.effective_canister_id, endpoint
         );
 
         reqwest::Client::new()
@@ -24,6 +24,109 @@ use ic_types::{
     registry::RegistryClientError,
     Height, Time,
 };
+use serde::Serialize;
+
+/// A stage in the lifecycle of the block at a given height, as observed by
+/// the local replica.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum BlockLifecycleStage {
+    /// The local block maker proposed a block at this height. Only recorded on
+    /// replicas that are block makers at this height.
+    Proposed,
+    /// The local validator moved a block proposal for this height, made by
+    /// another replica, into the validated pool.
+    Validated,
+    /// The local notary signed a notarization share for a block proposal at
+    /// this height, i.e. it deemed it the best one to notarize. Only recorded
+    /// on replicas that are notaries at this height.
+    NotarizationShared,
+    /// A notarization for this height was aggregated or validated.
+    Notarized,
+    /// A finalization for this height was aggregated or validated.
+    Finalized,
+    /// The finalized block was delivered as a batch to message routing.
+    Delivered,
+    /// The batch was executed and the resulting state was committed to the
+    /// state manager.
+    Executed,
+    /// The certification of the state at this height was delivered to the
+    /// state manager.
+    Certified,
+}
+
+/// The times at which the block at a given height reached each stage of its
+/// lifecycle. Only the first occurrence of every stage is kept.
+#[derive(Clone, Eq, PartialEq, Debug, Default, Serialize)]
+pub struct BlockLifecycleRecord {
+    pub height: Height,
+    pub proposed: Option<Time>,
+    pub validated: Option<Time>,
+    pub notarization_shared: Option<Time>,
+    pub notarized: Option<Time>,
+    pub finalized: Option<Time>,
+    pub delivered: Option<Time>,
+    pub executed: Option<Time>,
+    pub certified: Option<Time>,
+}
+
+impl BlockLifecycleRecord {
+    pub fn new(height: Height) -> Self {
+        Self {
+            height,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the time at which the given stage was reached, if any.
+    pub fn get(&self, stage: BlockLifecycleStage) -> Option<Time> {
+        match stage {
+            BlockLifecycleStage::Proposed => self.proposed,
+            BlockLifecycleStage::Validated => self.validated,
+            BlockLifecycleStage::NotarizationShared => self.notarization_shared,
+            BlockLifecycleStage::Notarized => self.notarized,
+            BlockLifecycleStage::Finalized => self.finalized,
+            BlockLifecycleStage::Delivered => self.delivered,
+            BlockLifecycleStage::Executed => self.executed,
+            BlockLifecycleStage::Certified => self.certified,
+        }
+    }
+
+    /// Returns the time at which a block proposal for this height was first
+    /// seen locally, i.e. the earlier of its proposal and its validation.
+    pub fn first_seen(&self) -> Option<Time> {
+        match (self.proposed, self.validated) {
+            (Some(proposed), Some(validated)) => Some(proposed.min(validated)),
+            (proposed, validated) => proposed.or(validated),
+        }
+    }
+
+    /// Records the given stage at the given time, unless it was recorded before.
+    pub fn set(&mut self, stage: BlockLifecycleStage, time: Time) {
+        let slot = match stage {
+            BlockLifecycleStage::Proposed => &mut self.proposed,
+            BlockLifecycleStage::Validated => &mut self.validated,
+            BlockLifecycleStage::NotarizationShared => &mut self.notarization_shared,
+            BlockLifecycleStage::Notarized => &mut self.notarized,
+            BlockLifecycleStage::Finalized => &mut self.finalized,
+            BlockLifecycleStage::Delivered => &mut self.delivered,
+            BlockLifecycleStage::Executed => &mut self.executed,
+            BlockLifecycleStage::Certified => &mut self.certified,
+        };
+        slot.get_or_insert(time);
+    }
+}
+
+/// Collects per-height [`BlockLifecycleRecord`]s emitted by the consensus
+/// components and message routing, for debugging latency and stalls.
+pub trait BlockLifecycleTracer: Send + Sync {
+    /// Records that the block at `height` reached `stage` now. The time is
+    /// taken from the time source the tracer was created with, so that all
+    /// stages are measured against the same clock.
+    fn record(&self, height: Height, stage: BlockLifecycleStage);
+
+    /// Returns the retained records, ordered by height.
+    fn records(&self) -> Vec<BlockLifecycleRecord>;
+}
 
 /// The [`PayloadBuilder`] is responsible for creating and validating payload that
 /// is included in consensus blocks.
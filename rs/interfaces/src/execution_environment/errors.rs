@@ -69,6 +69,27 @@ impl std::fmt::Display for CanisterOutOfCyclesError {
     }
 }
 
+/// Error when a canister has consumed more cycles than its `cycles_burn_limit`
+/// allows within the current cycles burn window.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Deserialize, Serialize)]
+pub struct CyclesBurnLimitExceededError {
+    pub canister_id: CanisterId,
+    pub burned: Cycles,
+    pub limit: Cycles,
+}
+
+impl std::error::Error for CyclesBurnLimitExceededError {}
+
+impl std::fmt::Display for CyclesBurnLimitExceededError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Canister {} exceeded its cycles burn limit: burned {} cycles in the current window, limit is {} cycles",
+            self.canister_id, self.burned, self.limit
+        )
+    }
+}
+
 /// Backtrace coming from canister code. Suitable for displaying to users for
 /// assistance in debugging canisters.
 #[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
@@ -3,6 +3,7 @@ use crate::{routing, scheduling};
 use ic_config::execution_environment::{BitcoinConfig, Config as HypervisorConfig};
 use ic_config::message_routing::{MAX_STREAM_MESSAGES, TARGET_STREAM_SIZE_BYTES};
 use ic_cycles_account_manager::CyclesAccountManager;
+use ic_interfaces::consensus::{BlockLifecycleStage, BlockLifecycleTracer};
 use ic_interfaces::execution_environment::{
     IngressHistoryWriter, RegistryExecutionSettings, Scheduler,
 };
@@ -591,6 +592,7 @@ where
     registry: Arc<RegistryClient_>,
     bitcoin_config: BitcoinConfig,
     metrics: MessageRoutingMetrics,
+    block_lifecycle_tracer: Option<Arc<dyn BlockLifecycleTracer>>,
     log: ReplicaLogger,
     #[allow(dead_code)]
     malicious_flags: MaliciousFlags,
@@ -663,6 +665,7 @@ impl<RegistryClient_: RegistryClient> BatchProcessorImpl<RegistryClient_> {
         log: ReplicaLogger,
         registry: Arc<RegistryClient_>,
         malicious_flags: MaliciousFlags,
+        block_lifecycle_tracer: Option<Arc<dyn BlockLifecycleTracer>>,
     ) -> Self {
         let time_in_stream_metrics = Arc::new(Mutex::new(LatencyMetrics::new_time_in_stream(
             metrics_registry,
@@ -713,6 +716,7 @@ impl<RegistryClient_: RegistryClient> BatchProcessorImpl<RegistryClient_> {
             registry,
             bitcoin_config: hypervisor_config.bitcoin,
             metrics,
+            block_lifecycle_tracer,
             log,
             malicious_flags,
         }
@@ -1353,6 +1357,9 @@ impl<RegistryClient_: RegistryClient> BatchProcessor for BatchProcessorImpl<Regi
             batch_summary,
         );
         self.observe_phase_duration(PHASE_COMMIT, &phase_since);
+        if let Some(tracer) = &self.block_lifecycle_tracer {
+            tracer.record(commit_height, BlockLifecycleStage::Executed);
+        }
 
         self.metrics
             .process_batch_duration
@@ -1500,6 +1507,9 @@ impl MessageRoutingImpl {
 
     /// Creates a new `MessageRoutingImpl` for the given subnet using the
     /// provided `StateManager` and `ExecutionEnvironment`.
+    ///
+    /// If a `block_lifecycle_tracer` is given, the execution of every batch is
+    /// recorded in it.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         state_manager: Arc<dyn StateManager<State = ReplicatedState>>,
@@ -1513,6 +1523,7 @@ impl MessageRoutingImpl {
         log: ReplicaLogger,
         registry: Arc<impl RegistryClient + 'static>,
         malicious_flags: MaliciousFlags,
+        block_lifecycle_tracer: Option<Arc<dyn BlockLifecycleTracer>>,
     ) -> Self {
         let metrics = MessageRoutingMetrics::new(metrics_registry);
         let batch_processor = Box::new(BatchProcessorImpl::new(
@@ -1533,6 +1544,7 @@ impl MessageRoutingImpl {
             log.clone(),
             registry,
             malicious_flags,
+            block_lifecycle_tracer,
         ));
 
         Self::from_batch_processor(state_manager, batch_processor, metrics, log)
@@ -1677,6 +1689,7 @@ impl SyncMessageRouting {
             log.clone(),
             registry,
             malicious_flags,
+            None,
         );
         let batch_processor = Arc::new(Mutex::new(batch_processor));
 
@@ -676,6 +676,7 @@ fn make_batch_processor<RegistryClient_: RegistryClient + 'static>(
         registry,
         bitcoin_config: BitcoinConfig::default(),
         metrics: metrics.clone(),
+        block_lifecycle_tracer: None,
         log,
         malicious_flags: MaliciousFlags::default(),
     };
@@ -0,0 +1,24 @@
+use ic_management_canister_types_private::{
+    CanisterSnapshotResponse, LoadCanisterSnapshotArgs, TakeCanisterSnapshotArgs, IC_00,
+};
+use ic_nervous_system_runtime::Runtime;
+
+/// A wrapper call to the management canister `take_canister_snapshot` API.
+pub async fn take_canister_snapshot<Rt>(
+    args: TakeCanisterSnapshotArgs,
+) -> Result<CanisterSnapshotResponse, (i32, String)>
+where
+    Rt: Runtime,
+{
+    Rt::call_with_cleanup(IC_00, "take_canister_snapshot", (args,))
+        .await
+        .map(|response: (CanisterSnapshotResponse,)| response.0)
+}
+
+/// A wrapper call to the management canister `load_canister_snapshot` API.
+pub async fn load_canister_snapshot<Rt>(args: LoadCanisterSnapshotArgs) -> Result<(), (i32, String)>
+where
+    Rt: Runtime,
+{
+    Rt::call_with_cleanup(IC_00, "load_canister_snapshot", (args,)).await
+}
@@ -1,12 +1,15 @@
 use crate::{
     canister_id_record::CanisterIdRecord,
+    canister_snapshot::{load_canister_snapshot, take_canister_snapshot},
     canister_status::{canister_status, CanisterStatusResultFromManagementCanister},
     update_settings::{update_settings, UpdateSettings},
 };
 use async_trait::async_trait;
 use candid::Encode;
 use ic_error_types::RejectCode;
-use ic_management_canister_types_private::IC_00;
+use ic_management_canister_types_private::{
+    CanisterSnapshotResponse, LoadCanisterSnapshotArgs, TakeCanisterSnapshotArgs, IC_00,
+};
 use ic_nervous_system_proxied_canister_calls_tracker::ProxiedCanisterCallsTracker;
 use ic_nervous_system_runtime::Runtime;
 use std::{
@@ -32,6 +35,18 @@ pub trait ManagementCanisterClient {
     /// A call to the `update_settings` management canister endpoint.
     async fn update_settings(&self, settings: UpdateSettings) -> Result<(), (i32, String)>;
 
+    /// A call to the `take_canister_snapshot` management canister endpoint.
+    async fn take_canister_snapshot(
+        &self,
+        args: TakeCanisterSnapshotArgs,
+    ) -> Result<CanisterSnapshotResponse, (i32, String)>;
+
+    /// A call to the `load_canister_snapshot` management canister endpoint.
+    async fn load_canister_snapshot(
+        &self,
+        args: LoadCanisterSnapshotArgs,
+    ) -> Result<(), (i32, String)>;
+
     fn canister_version(&self) -> Option<u64>;
 }
 
@@ -92,6 +107,42 @@ impl<Rt: Runtime + Sync> ManagementCanisterClient for ManagementCanisterClientIm
         update_settings::<Rt>(settings).await
     }
 
+    async fn take_canister_snapshot(
+        &self,
+        args: TakeCanisterSnapshotArgs,
+    ) -> Result<CanisterSnapshotResponse, (i32, String)> {
+        let _tracker = self.proxied_canister_calls_tracker.map(|tracker| {
+            let args = Encode!(&args).unwrap_or_default();
+            ProxiedCanisterCallsTracker::start_tracking(
+                tracker,
+                dfn_core::api::caller(),
+                IC_00,
+                "take_canister_snapshot",
+                &args,
+            )
+        });
+
+        take_canister_snapshot::<Rt>(args).await
+    }
+
+    async fn load_canister_snapshot(
+        &self,
+        args: LoadCanisterSnapshotArgs,
+    ) -> Result<(), (i32, String)> {
+        let _tracker = self.proxied_canister_calls_tracker.map(|tracker| {
+            let args = Encode!(&args).unwrap_or_default();
+            ProxiedCanisterCallsTracker::start_tracking(
+                tracker,
+                dfn_core::api::caller(),
+                IC_00,
+                "load_canister_snapshot",
+                &args,
+            )
+        });
+
+        load_canister_snapshot::<Rt>(args).await
+    }
+
     fn canister_version(&self) -> Option<u64> {
         Some(Rt::canister_version())
     }
@@ -179,6 +230,22 @@ where
         self.inner.update_settings(settings).await
     }
 
+    async fn take_canister_snapshot(
+        &self,
+        args: TakeCanisterSnapshotArgs,
+    ) -> Result<CanisterSnapshotResponse, (i32, String)> {
+        let _loan = self.try_borrow_slot()?;
+        self.inner.take_canister_snapshot(args).await
+    }
+
+    async fn load_canister_snapshot(
+        &self,
+        args: LoadCanisterSnapshotArgs,
+    ) -> Result<(), (i32, String)> {
+        let _loan = self.try_borrow_slot()?;
+        self.inner.load_canister_snapshot(args).await
+    }
+
     fn canister_version(&self) -> Option<u64> {
         // This does not actually call the management canister. This implies a few things:
         //
@@ -237,6 +304,8 @@ impl MockManagementCanisterClient {
 pub enum MockManagementCanisterClientCall {
     CanisterStatus(CanisterIdRecord),
     UpdateSettings(UpdateSettings),
+    TakeCanisterSnapshot(TakeCanisterSnapshotArgs),
+    LoadCanisterSnapshot(LoadCanisterSnapshotArgs),
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -244,6 +313,8 @@ pub enum MockManagementCanisterClientCall {
 pub enum MockManagementCanisterClientReply {
     CanisterStatus(Result<CanisterStatusResultFromManagementCanister, (i32, String)>),
     UpdateSettings(Result<(), (i32, String)>),
+    TakeCanisterSnapshot(Result<CanisterSnapshotResponse, (i32, String)>),
+    LoadCanisterSnapshot(Result<(), (i32, String)>),
 }
 
 #[async_trait]
@@ -299,6 +370,58 @@ impl ManagementCanisterClient for MockManagementCanisterClient {
         }
     }
 
+    async fn take_canister_snapshot(
+        &self,
+        args: TakeCanisterSnapshotArgs,
+    ) -> Result<CanisterSnapshotResponse, (i32, String)> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push_back(MockManagementCanisterClientCall::TakeCanisterSnapshot(args));
+
+        let reply = self
+            .replies
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("Expected a MockManagementCanisterClientCall to be on the queue.");
+
+        match reply {
+            MockManagementCanisterClientReply::TakeCanisterSnapshot(response) => response,
+            err => panic!(
+                "Expected MockManagementCanisterClientReply::TakeCanisterSnapshot to be at \
+                the front of the queue. Had {:?}",
+                err
+            ),
+        }
+    }
+
+    async fn load_canister_snapshot(
+        &self,
+        args: LoadCanisterSnapshotArgs,
+    ) -> Result<(), (i32, String)> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push_back(MockManagementCanisterClientCall::LoadCanisterSnapshot(args));
+
+        let reply = self
+            .replies
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("Expected a MockManagementCanisterClientCall to be on the queue.");
+
+        match reply {
+            MockManagementCanisterClientReply::LoadCanisterSnapshot(response) => response,
+            err => panic!(
+                "Expected MockManagementCanisterClientReply::LoadCanisterSnapshot to be at \
+                the front of the queue. Had {:?}",
+                err
+            ),
+        }
+    }
+
     fn canister_version(&self) -> Option<u64> {
         None
     }
@@ -194,6 +194,7 @@ async fn upgrade_sns_controlled_canister_with_large_wasm() {
         canister_upgrade_arg,
         mode,
         chunked_canister_wasm,
+        take_snapshot_before_upgrade: _,
     }) = action
     else {
         panic!("unexpected proposal action {:?}", action);
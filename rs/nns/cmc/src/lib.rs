@@ -205,6 +205,9 @@ impl From<CanisterSettingsArgs> for Ic00CanisterSettingsArgs {
             wasm_memory_limit: settings.wasm_memory_limit,
             wasm_memory_threshold: settings.wasm_memory_threshold,
             environment_variables: None,
+            cycles_threshold: None,
+            cycles_burn_limit: None,
+            history_visibility: None,
         }
     }
 }
@@ -107,6 +107,8 @@ impl From<ProposalActionRequest> for Action {
             ProposalActionRequest::InstallCode(v) => Action::InstallCode(v.into()),
             ProposalActionRequest::StopOrStartCanister(v) => Action::StopOrStartCanister(v),
             ProposalActionRequest::UpdateCanisterSettings(v) => Action::UpdateCanisterSettings(v),
+            ProposalActionRequest::TakeCanisterSnapshot(v) => Action::TakeCanisterSnapshot(v),
+            ProposalActionRequest::LoadCanisterSnapshot(v) => Action::LoadCanisterSnapshot(v),
         }
     }
 }
@@ -617,6 +617,10 @@ pub mod proposal {
         StopOrStartCanister(super::StopOrStartCanister),
         /// Update the settings of a canister that is controlled by the NNS.
         UpdateCanisterSettings(super::UpdateCanisterSettings),
+        /// Take a snapshot of a canister that is controlled by the NNS.
+        TakeCanisterSnapshot(super::TakeCanisterSnapshot),
+        /// Load a snapshot into a canister that is controlled by the NNS.
+        LoadCanisterSnapshot(super::LoadCanisterSnapshot),
     }
 }
 /// Empty message to use in oneof fields that represent empty
@@ -1370,6 +1374,8 @@ pub enum ProposalActionRequest {
     InstallCode(InstallCodeRequest),
     StopOrStartCanister(StopOrStartCanister),
     UpdateCanisterSettings(UpdateCanisterSettings),
+    TakeCanisterSnapshot(TakeCanisterSnapshot),
+    LoadCanisterSnapshot(LoadCanisterSnapshot),
 }
 
 #[derive(
@@ -1823,6 +1829,22 @@ pub struct SwapParticipationLimits {
 )]
 pub struct DerivedProposalInformation {
     pub swap_background_information: Option<SwapBackgroundInformation>,
+    /// Set when a TakeCanisterSnapshot proposal is executed successfully.
+    pub taken_canister_snapshot: Option<TakenCanisterSnapshot>,
+}
+/// The snapshot taken by executing a TakeCanisterSnapshot proposal. Its ID can be used in a later
+/// LoadCanisterSnapshot proposal.
+#[derive(
+    candid::CandidType, candid::Deserialize, serde::Serialize, Clone, PartialEq, Debug, Default,
+)]
+pub struct TakenCanisterSnapshot {
+    /// The ID of the new snapshot.
+    #[serde(deserialize_with = "ic_utils::deserialize::deserialize_option_blob")]
+    pub snapshot_id: Option<Vec<u8>>,
+    /// When the snapshot was taken, in nanoseconds since the Unix epoch.
+    pub taken_at_timestamp: Option<u64>,
+    /// The size of the snapshot, in bytes.
+    pub total_size: Option<u64>,
 }
 /// Additional information about the SNS that's being "swapped".
 ///
@@ -2600,6 +2622,29 @@ pub mod update_canister_settings {
         }
     }
 }
+#[derive(
+    candid::CandidType, candid::Deserialize, serde::Serialize, Clone, PartialEq, Debug, Default,
+)]
+pub struct TakeCanisterSnapshot {
+    /// The target canister ID to call take_canister_snapshot on. The canister must be controlled by
+    /// NNS Root, which means that it cannot be NNS Root itself. Required.
+    pub canister_id: Option<PrincipalId>,
+    /// The ID of an existing snapshot of the canister to replace. Optional. When not set, a new
+    /// snapshot is added.
+    #[serde(deserialize_with = "ic_utils::deserialize::deserialize_option_blob")]
+    pub replace_snapshot: Option<Vec<u8>>,
+}
+#[derive(
+    candid::CandidType, candid::Deserialize, serde::Serialize, Clone, PartialEq, Debug, Default,
+)]
+pub struct LoadCanisterSnapshot {
+    /// The target canister ID to call load_canister_snapshot on. The canister must be controlled by
+    /// NNS Root, which means that it cannot be NNS Root itself. Required.
+    pub canister_id: Option<PrincipalId>,
+    /// The ID of the snapshot to load. It must be a snapshot of the target canister. Required.
+    #[serde(deserialize_with = "ic_utils::deserialize::deserialize_option_blob")]
+    pub snapshot_id: Option<Vec<u8>>,
+}
 /// This represents the whole NNS governance system. It contains all
 /// information about the NNS governance system that must be kept
 /// across upgrades of the NNS governance system.
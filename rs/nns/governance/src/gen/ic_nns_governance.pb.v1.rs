@@ -427,7 +427,7 @@ pub struct Proposal {
     /// take.
     #[prost(
         oneof = "proposal::Action",
-        tags = "10, 12, 13, 14, 15, 16, 17, 18, 19, 21, 22, 23, 24, 25, 26, 27"
+        tags = "10, 12, 13, 14, 15, 16, 17, 18, 19, 21, 22, 23, 24, 25, 26, 27, 28, 29"
     )]
     pub action: ::core::option::Option<proposal::Action>,
 }
@@ -527,6 +527,12 @@ pub mod proposal {
         /// Update the settings of a canister that is controlled by the NNS.
         #[prost(message, tag = "27")]
         UpdateCanisterSettings(super::UpdateCanisterSettings),
+        /// Take a snapshot of a canister that is controlled by the NNS.
+        #[prost(message, tag = "28")]
+        TakeCanisterSnapshot(super::TakeCanisterSnapshot),
+        /// Load a snapshot into a canister that is controlled by the NNS.
+        #[prost(message, tag = "29")]
+        LoadCanisterSnapshot(super::LoadCanisterSnapshot),
     }
 }
 /// Empty message to use in oneof fields that represent empty
@@ -1737,6 +1743,32 @@ pub struct SwapParticipationLimits {
 pub struct DerivedProposalInformation {
     #[prost(message, optional, tag = "1")]
     pub swap_background_information: ::core::option::Option<SwapBackgroundInformation>,
+    /// Set when a TakeCanisterSnapshot proposal is executed successfully.
+    #[prost(message, optional, tag = "2")]
+    pub taken_canister_snapshot: ::core::option::Option<TakenCanisterSnapshot>,
+}
+/// The snapshot taken by executing a TakeCanisterSnapshot proposal. Its ID can be used in a later
+/// LoadCanisterSnapshot proposal.
+#[derive(
+    candid::CandidType,
+    candid::Deserialize,
+    serde::Serialize,
+    comparable::Comparable,
+    Clone,
+    PartialEq,
+    ::prost::Message,
+)]
+pub struct TakenCanisterSnapshot {
+    /// The ID of the new snapshot.
+    #[prost(bytes = "vec", optional, tag = "1")]
+    #[serde(deserialize_with = "ic_utils::deserialize::deserialize_option_blob")]
+    pub snapshot_id: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    /// When the snapshot was taken, in nanoseconds since the Unix epoch.
+    #[prost(uint64, optional, tag = "2")]
+    pub taken_at_timestamp: ::core::option::Option<u64>,
+    /// The size of the snapshot, in bytes.
+    #[prost(uint64, optional, tag = "3")]
+    pub total_size: ::core::option::Option<u64>,
 }
 /// Additional information about the SNS that's being "swapped".
 ///
@@ -2733,6 +2765,45 @@ pub mod update_canister_settings {
         }
     }
 }
+#[derive(
+    candid::CandidType,
+    candid::Deserialize,
+    serde::Serialize,
+    comparable::Comparable,
+    Clone,
+    PartialEq,
+    ::prost::Message,
+)]
+pub struct TakeCanisterSnapshot {
+    /// The target canister ID to call take_canister_snapshot on. The canister must be controlled by
+    /// NNS Root, which means that it cannot be NNS Root itself. Required.
+    #[prost(message, optional, tag = "1")]
+    pub canister_id: ::core::option::Option<::ic_base_types::PrincipalId>,
+    /// The ID of an existing snapshot of the canister to replace. Optional. When not set, a new
+    /// snapshot is added.
+    #[prost(bytes = "vec", optional, tag = "2")]
+    #[serde(deserialize_with = "ic_utils::deserialize::deserialize_option_blob")]
+    pub replace_snapshot: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+}
+#[derive(
+    candid::CandidType,
+    candid::Deserialize,
+    serde::Serialize,
+    comparable::Comparable,
+    Clone,
+    PartialEq,
+    ::prost::Message,
+)]
+pub struct LoadCanisterSnapshot {
+    /// The target canister ID to call load_canister_snapshot on. The canister must be controlled by
+    /// NNS Root, which means that it cannot be NNS Root itself. Required.
+    #[prost(message, optional, tag = "1")]
+    pub canister_id: ::core::option::Option<::ic_base_types::PrincipalId>,
+    /// The ID of the snapshot to load. It must be a snapshot of the target canister. Required.
+    #[prost(bytes = "vec", optional, tag = "2")]
+    #[serde(deserialize_with = "ic_utils::deserialize::deserialize_option_blob")]
+    pub snapshot_id: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+}
 /// This represents the whole NNS governance system. It contains all
 /// information about the NNS governance system that must be kept
 /// across upgrades of the NNS governance system.
@@ -56,15 +56,16 @@ use crate::{
             CreateServiceNervousSystem, ExecuteNnsFunction, Followees,
             GetNeuronsFundAuditInfoRequest, GetNeuronsFundAuditInfoResponse,
             Governance as GovernanceProto, GovernanceError, InstallCode, KnownNeuron,
-            ListKnownNeuronsResponse, ListProposalInfo, ManageNeuron, MonthlyNodeProviderRewards,
-            Motion, NetworkEconomics, NeuronState, NeuronsFundAuditInfo, NeuronsFundData,
+            ListKnownNeuronsResponse, ListProposalInfo, LoadCanisterSnapshot, ManageNeuron,
+            MonthlyNodeProviderRewards, Motion, NetworkEconomics, NeuronState,
+            NeuronsFundAuditInfo, NeuronsFundData,
             NeuronsFundParticipation as NeuronsFundParticipationPb,
             NeuronsFundSnapshot as NeuronsFundSnapshotPb, NnsFunction, NodeProvider, Proposal,
             ProposalData, ProposalRewardStatus, ProposalStatus, RestoreAgingSummary, RewardEvent,
             RewardNodeProvider, RewardNodeProviders, SettleNeuronsFundParticipationRequest,
-            SettleNeuronsFundParticipationResponse, StopOrStartCanister, Tally, Topic,
-            UpdateCanisterSettings, UpdateNodeProvider, Vote, VotingPowerEconomics,
-            WaitForQuietState,
+            SettleNeuronsFundParticipationResponse, StopOrStartCanister, TakeCanisterSnapshot,
+            TakenCanisterSnapshot, Tally, Topic, UpdateCanisterSettings, UpdateNodeProvider, Vote,
+            VotingPowerEconomics, WaitForQuietState,
         },
     },
     proposals::{call_canister::CallCanister, sum_weighted_voting_power},
@@ -76,7 +77,7 @@ use cycles_minting_canister::{IcpXdrConversionRate, IcpXdrConversionRateCertifie
 use disburse_maturity::initiate_maturity_disbursement;
 #[cfg(not(target_arch = "wasm32"))]
 use futures::FutureExt;
-use ic_base_types::{CanisterId, PrincipalId};
+use ic_base_types::{CanisterId, PrincipalId, SnapshotId};
 use ic_cdk::println;
 #[cfg(target_arch = "wasm32")]
 use ic_cdk::spawn;
@@ -104,6 +105,7 @@ use ic_nns_governance_api::{
     CreateServiceNervousSystem as ApiCreateServiceNervousSystem, ListNeurons, ListNeuronsResponse,
     ListProposalInfoResponse, ManageNeuronResponse, NeuronInfo, ProposalInfo,
 };
+use ic_nns_handler_root_interface::TakeCanisterSnapshotOk;
 use ic_node_rewards_canister_api::monthly_rewards::{
     GetNodeProvidersMonthlyXdrRewardsRequest, GetNodeProvidersMonthlyXdrRewardsResponse,
 };
@@ -718,6 +720,22 @@ impl Proposal {
                         .valid_topic()
                         .unwrap_or(Topic::Unspecified)
                 }
+                Action::TakeCanisterSnapshot(take_canister_snapshot) => {
+                    // There should be a valid topic since the validation should be done when the
+                    // proposal is created. We avoid panicking here since `topic()` is called in a
+                    // lot of places.
+                    take_canister_snapshot
+                        .valid_topic()
+                        .unwrap_or(Topic::Unspecified)
+                }
+                Action::LoadCanisterSnapshot(load_canister_snapshot) => {
+                    // There should be a valid topic since the validation should be done when the
+                    // proposal is created. We avoid panicking here since `topic()` is called in a
+                    // lot of places.
+                    load_canister_snapshot
+                        .valid_topic()
+                        .unwrap_or(Topic::Unspecified)
+                }
             }
         } else {
             println!("{}ERROR: No action -> no topic.", LOG_PREFIX);
@@ -784,6 +802,8 @@ impl Action {
             Action::InstallCode(_) => "ACTION_CHANGE_CANISTER",
             Action::StopOrStartCanister(_) => "ACTION_STOP_OR_START_CANISTER",
             Action::UpdateCanisterSettings(_) => "ACTION_UPDATE_CANISTER_SETTINGS",
+            Action::TakeCanisterSnapshot(_) => "ACTION_TAKE_CANISTER_SNAPSHOT",
+            Action::LoadCanisterSnapshot(_) => "ACTION_LOAD_CANISTER_SNAPSHOT",
         }
     }
 
@@ -4374,6 +4394,14 @@ impl Governance {
                 self.perform_update_canister_settings(pid, update_settings)
                     .await;
             }
+            Action::TakeCanisterSnapshot(take_canister_snapshot) => {
+                self.perform_take_canister_snapshot(pid, take_canister_snapshot)
+                    .await;
+            }
+            Action::LoadCanisterSnapshot(load_canister_snapshot) => {
+                self.perform_load_canister_snapshot(pid, load_canister_snapshot)
+                    .await;
+            }
         }
     }
 
@@ -4448,11 +4476,87 @@ impl Governance {
         self.set_proposal_execution_status(proposal_id, result);
     }
 
+    async fn perform_take_canister_snapshot(
+        &mut self,
+        proposal_id: u64,
+        take_canister_snapshot: TakeCanisterSnapshot,
+    ) {
+        let canister_id = take_canister_snapshot.canister_id;
+        let result = self
+            .call_canister_for_reply(proposal_id, take_canister_snapshot)
+            .await
+            .and_then(|reply| TakeCanisterSnapshot::process_reply(&reply))
+            .map(|snapshot| {
+                let snapshot_id = SnapshotId::try_from(&snapshot.id)
+                    .map(|snapshot_id| snapshot_id.to_string())
+                    .unwrap_or_else(|_| format!("{:?}", snapshot.id));
+                println!(
+                    "{}Took snapshot {} of canister {:?} for proposal {}.",
+                    LOG_PREFIX, snapshot_id, canister_id, proposal_id
+                );
+                // The snapshot ID is needed to load the snapshot later, so it is recorded on the
+                // proposal, where it is shown as part of the proposal's derived information.
+                self.record_taken_canister_snapshot(proposal_id, snapshot);
+            });
+        self.set_proposal_execution_status(proposal_id, result);
+    }
+
+    fn record_taken_canister_snapshot(
+        &mut self,
+        proposal_id: u64,
+        snapshot: TakeCanisterSnapshotOk,
+    ) {
+        let Some(proposal_data) = self.heap_data.proposals.get_mut(&proposal_id) else {
+            println!(
+                "{}Proposal {} not found when recording the snapshot it took.",
+                LOG_PREFIX, proposal_id
+            );
+            return;
+        };
+        let TakeCanisterSnapshotOk {
+            id,
+            taken_at_timestamp,
+            total_size,
+        } = snapshot;
+        proposal_data
+            .derived_proposal_information
+            .get_or_insert_with(Default::default)
+            .taken_canister_snapshot = Some(TakenCanisterSnapshot {
+            snapshot_id: Some(id),
+            taken_at_timestamp: Some(taken_at_timestamp),
+            total_size: Some(total_size),
+        });
+    }
+
+    async fn perform_load_canister_snapshot(
+        &mut self,
+        proposal_id: u64,
+        load_canister_snapshot: LoadCanisterSnapshot,
+    ) {
+        let result = self
+            .call_canister_for_reply(proposal_id, load_canister_snapshot)
+            .await
+            .and_then(|reply| LoadCanisterSnapshot::process_reply(&reply));
+        self.set_proposal_execution_status(proposal_id, result);
+    }
+
     async fn perform_call_canister(
         &mut self,
         proposal_id: u64,
         call_canister: impl CallCanister,
     ) -> Result<(), GovernanceError> {
+        self.call_canister_for_reply(proposal_id, call_canister)
+            .await
+            .map(|_reply| ())
+    }
+
+    /// Like `perform_call_canister`, but returns the reply of the target canister, so that it can be
+    /// interpreted by the caller.
+    async fn call_canister_for_reply(
+        &mut self,
+        proposal_id: u64,
+        call_canister: impl CallCanister,
+    ) -> Result<Vec<u8>, GovernanceError> {
         let (canister_id, function) = call_canister.canister_and_function()?;
         let payload = call_canister.payload()?;
 
@@ -4462,7 +4566,7 @@ impl Governance {
             .await;
 
         match response {
-            Ok(_) => Ok(()),
+            Ok(reply) => Ok(reply),
             Err((code, message)) => Err(GovernanceError::new_with_message(
                 ErrorType::External,
                 format!(
@@ -4969,6 +5073,12 @@ impl Governance {
             Action::InstallCode(install_code) => install_code.validate(),
             Action::StopOrStartCanister(stop_or_start) => stop_or_start.validate(),
             Action::UpdateCanisterSettings(update_settings) => update_settings.validate(),
+            Action::TakeCanisterSnapshot(take_canister_snapshot) => {
+                take_canister_snapshot.validate()
+            }
+            Action::LoadCanisterSnapshot(load_canister_snapshot) => {
+                load_canister_snapshot.validate()
+            }
         }?;
 
         Ok(action.clone())
@@ -409,6 +409,12 @@ impl From<pb_api::proposal::Action> for pb::proposal::Action {
             pb_api::proposal::Action::UpdateCanisterSettings(v) => {
                 pb::proposal::Action::UpdateCanisterSettings(v.into())
             }
+            pb_api::proposal::Action::TakeCanisterSnapshot(v) => {
+                pb::proposal::Action::TakeCanisterSnapshot(v.into())
+            }
+            pb_api::proposal::Action::LoadCanisterSnapshot(v) => {
+                pb::proposal::Action::LoadCanisterSnapshot(v.into())
+            }
         }
     }
 }
@@ -452,6 +458,12 @@ impl From<pb_api::ProposalActionRequest> for pb::proposal::Action {
             pb_api::ProposalActionRequest::UpdateCanisterSettings(v) => {
                 pb::proposal::Action::UpdateCanisterSettings(v.into())
             }
+            pb_api::ProposalActionRequest::TakeCanisterSnapshot(v) => {
+                pb::proposal::Action::TakeCanisterSnapshot(v.into())
+            }
+            pb_api::ProposalActionRequest::LoadCanisterSnapshot(v) => {
+                pb::proposal::Action::LoadCanisterSnapshot(v.into())
+            }
         }
     }
 }
@@ -1702,6 +1714,7 @@ impl From<pb::DerivedProposalInformation> for pb_api::DerivedProposalInformation
     fn from(item: pb::DerivedProposalInformation) -> Self {
         Self {
             swap_background_information: item.swap_background_information.map(|x| x.into()),
+            taken_canister_snapshot: item.taken_canister_snapshot.map(|x| x.into()),
         }
     }
 }
@@ -1709,6 +1722,26 @@ impl From<pb_api::DerivedProposalInformation> for pb::DerivedProposalInformation
     fn from(item: pb_api::DerivedProposalInformation) -> Self {
         Self {
             swap_background_information: item.swap_background_information.map(|x| x.into()),
+            taken_canister_snapshot: item.taken_canister_snapshot.map(|x| x.into()),
+        }
+    }
+}
+
+impl From<pb::TakenCanisterSnapshot> for pb_api::TakenCanisterSnapshot {
+    fn from(item: pb::TakenCanisterSnapshot) -> Self {
+        Self {
+            snapshot_id: item.snapshot_id,
+            taken_at_timestamp: item.taken_at_timestamp,
+            total_size: item.total_size,
+        }
+    }
+}
+impl From<pb_api::TakenCanisterSnapshot> for pb::TakenCanisterSnapshot {
+    fn from(item: pb_api::TakenCanisterSnapshot) -> Self {
+        Self {
+            snapshot_id: item.snapshot_id,
+            taken_at_timestamp: item.taken_at_timestamp,
+            total_size: item.total_size,
         }
     }
 }
@@ -2481,6 +2514,42 @@ impl From<pb_api::StopOrStartCanister> for pb::StopOrStartCanister {
     }
 }
 
+impl From<pb::TakeCanisterSnapshot> for pb_api::TakeCanisterSnapshot {
+    fn from(item: pb::TakeCanisterSnapshot) -> Self {
+        Self {
+            canister_id: item.canister_id,
+            replace_snapshot: item.replace_snapshot,
+        }
+    }
+}
+
+impl From<pb_api::TakeCanisterSnapshot> for pb::TakeCanisterSnapshot {
+    fn from(item: pb_api::TakeCanisterSnapshot) -> Self {
+        Self {
+            canister_id: item.canister_id,
+            replace_snapshot: item.replace_snapshot,
+        }
+    }
+}
+
+impl From<pb::LoadCanisterSnapshot> for pb_api::LoadCanisterSnapshot {
+    fn from(item: pb::LoadCanisterSnapshot) -> Self {
+        Self {
+            canister_id: item.canister_id,
+            snapshot_id: item.snapshot_id,
+        }
+    }
+}
+
+impl From<pb_api::LoadCanisterSnapshot> for pb::LoadCanisterSnapshot {
+    fn from(item: pb_api::LoadCanisterSnapshot) -> Self {
+        Self {
+            canister_id: item.canister_id,
+            snapshot_id: item.snapshot_id,
+        }
+    }
+}
+
 impl From<pb::stop_or_start_canister::CanisterAction>
     for pb_api::stop_or_start_canister::CanisterAction
 {
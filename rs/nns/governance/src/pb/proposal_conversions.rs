@@ -174,6 +174,12 @@ fn convert_action(
         pb::proposal::Action::UpdateCanisterSettings(v) => {
             pb_api::proposal::Action::UpdateCanisterSettings(v.clone().into())
         }
+        pb::proposal::Action::TakeCanisterSnapshot(v) => {
+            pb_api::proposal::Action::TakeCanisterSnapshot(v.clone().into())
+        }
+        pb::proposal::Action::LoadCanisterSnapshot(v) => {
+            pb_api::proposal::Action::LoadCanisterSnapshot(v.clone().into())
+        }
 
         // The action types with potentially large fields need to be converted in a way that avoids
         // cloning the action first.
@@ -0,0 +1,206 @@
+use super::{
+    invalid_proposal_error, is_nns_controlled_canister, topic_to_manage_canister, valid_snapshot_id,
+};
+use crate::{
+    pb::v1::{governance_error::ErrorType, GovernanceError, LoadCanisterSnapshot, Topic},
+    proposals::call_canister::CallCanister,
+};
+
+use candid::{Decode, Encode};
+use ic_base_types::{CanisterId, SnapshotId};
+use ic_nns_constants::{GOVERNANCE_CANISTER_ID, ROOT_CANISTER_ID};
+use ic_nns_handler_root_interface::{LoadCanisterSnapshotRequest, LoadCanisterSnapshotResponse};
+
+impl LoadCanisterSnapshot {
+    pub fn validate(&self) -> Result<(), GovernanceError> {
+        let _ = self.valid_canister_id()?;
+        let _ = self.valid_snapshot_id()?;
+        let _ = self.valid_topic()?;
+
+        Ok(())
+    }
+
+    pub fn valid_topic(&self) -> Result<Topic, GovernanceError> {
+        let canister_id = self.valid_canister_id()?;
+        Ok(topic_to_manage_canister(&canister_id))
+    }
+
+    fn valid_canister_id(&self) -> Result<CanisterId, GovernanceError> {
+        let canister_principal_id = self
+            .canister_id
+            .ok_or(invalid_proposal_error("Canister ID is required"))?;
+        let canister_id = CanisterId::try_from(canister_principal_id)
+            .map_err(|_| invalid_proposal_error("Invalid canister ID"))?;
+
+        // NNS Root is controlled by Lifeline, not by itself, so it cannot load snapshots into
+        // itself.
+        if canister_id == ROOT_CANISTER_ID {
+            return Err(invalid_proposal_error(
+                "Canister is not controlled by NNS Root, so it cannot be restored from a snapshot",
+            ));
+        }
+        // Canisters outside of the NNS subnet and the protocol canisters are not controlled by NNS
+        // Root either.
+        if !is_nns_controlled_canister(&canister_id) {
+            return Err(invalid_proposal_error(
+                "Canister is not controlled by NNS Root, so it cannot be restored from a snapshot",
+            ));
+        }
+        // Governance is waiting for the reply of NNS Root while the proposal is being executed, so
+        // replacing its state underneath would lose track of the execution.
+        if canister_id == GOVERNANCE_CANISTER_ID {
+            return Err(invalid_proposal_error(
+                "Loading a snapshot into the governance canister is not allowed",
+            ));
+        }
+
+        Ok(canister_id)
+    }
+
+    fn valid_snapshot_id(&self) -> Result<SnapshotId, GovernanceError> {
+        let canister_id = self.valid_canister_id()?;
+        let snapshot_id = self
+            .snapshot_id
+            .as_ref()
+            .ok_or(invalid_proposal_error("Snapshot ID is required"))?;
+        valid_snapshot_id(canister_id, snapshot_id)
+    }
+
+    /// Interprets the reply of NNS Root's load_canister_snapshot method.
+    pub fn process_reply(reply: &[u8]) -> Result<(), GovernanceError> {
+        let response = Decode!(reply, LoadCanisterSnapshotResponse).map_err(|err| {
+            GovernanceError::new_with_message(
+                ErrorType::External,
+                format!("Failed to decode LoadCanisterSnapshotResponse: {}", err),
+            )
+        })?;
+
+        match response {
+            LoadCanisterSnapshotResponse::Ok(()) => Ok(()),
+            LoadCanisterSnapshotResponse::Err(err) => Err(GovernanceError::new_with_message(
+                ErrorType::External,
+                format!(
+                    "Failed to load canister snapshot. Code: {:?} description: {}",
+                    err.code, err.description
+                ),
+            )),
+        }
+    }
+}
+
+impl CallCanister for LoadCanisterSnapshot {
+    fn canister_and_function(&self) -> Result<(CanisterId, &str), GovernanceError> {
+        Ok((ROOT_CANISTER_ID, "load_canister_snapshot"))
+    }
+
+    fn payload(&self) -> Result<Vec<u8>, GovernanceError> {
+        let canister_id = self.valid_canister_id()?;
+        let snapshot_id = self.valid_snapshot_id()?;
+
+        Encode!(&LoadCanisterSnapshotRequest {
+            canister_id: canister_id.get(),
+            snapshot_id: snapshot_id.to_vec(),
+        })
+        .map_err(|e| invalid_proposal_error(&format!("Failed to encode payload: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ic_nns_constants::{CYCLES_MINTING_CANISTER_ID, SNS_WASM_CANISTER_ID};
+
+    #[test]
+    fn test_load_canister_snapshot() {
+        let snapshot_id = SnapshotId::from((SNS_WASM_CANISTER_ID, 7));
+        let load_canister_snapshot = LoadCanisterSnapshot {
+            canister_id: Some(SNS_WASM_CANISTER_ID.get()),
+            snapshot_id: Some(snapshot_id.to_vec()),
+        };
+
+        assert_eq!(load_canister_snapshot.validate(), Ok(()));
+        assert_eq!(
+            load_canister_snapshot.valid_topic(),
+            Ok(Topic::ServiceNervousSystemManagement)
+        );
+        assert_eq!(
+            load_canister_snapshot.canister_and_function(),
+            Ok((ROOT_CANISTER_ID, "load_canister_snapshot"))
+        );
+        let decoded_payload = Decode!(
+            &load_canister_snapshot.payload().unwrap(),
+            LoadCanisterSnapshotRequest
+        )
+        .unwrap();
+        assert_eq!(
+            decoded_payload,
+            LoadCanisterSnapshotRequest {
+                canister_id: SNS_WASM_CANISTER_ID.get(),
+                snapshot_id: snapshot_id.to_vec(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_load_canister_snapshot() {
+        let snapshot_id = SnapshotId::from((CYCLES_MINTING_CANISTER_ID, 7)).to_vec();
+        let is_invalid_proposal_with_keywords =
+            |load_canister_snapshot: LoadCanisterSnapshot, keywords: Vec<&str>| {
+                let error = load_canister_snapshot.validate().expect_err(&format!(
+                    "Expecting validation error for {load_canister_snapshot:?} but got Ok(())"
+                ));
+                assert_eq!(error.error_type, ErrorType::InvalidProposal as i32);
+                for keyword in keywords {
+                    let error_message = error.error_message.to_lowercase();
+                    assert!(
+                        error_message.contains(keyword),
+                        "{} not found in {:#?}",
+                        keyword,
+                        error_message
+                    );
+                }
+            };
+
+        is_invalid_proposal_with_keywords(
+            LoadCanisterSnapshot {
+                canister_id: Some(CYCLES_MINTING_CANISTER_ID.get()),
+                snapshot_id: None,
+            },
+            vec!["snapshot id", "required"],
+        );
+
+        is_invalid_proposal_with_keywords(
+            LoadCanisterSnapshot {
+                canister_id: Some(ROOT_CANISTER_ID.get()),
+                snapshot_id: Some(SnapshotId::from((ROOT_CANISTER_ID, 7)).to_vec()),
+            },
+            vec!["not controlled by nns root"],
+        );
+
+        let application_canister_id = CanisterId::from_u64(5 << 20);
+        is_invalid_proposal_with_keywords(
+            LoadCanisterSnapshot {
+                canister_id: Some(application_canister_id.get()),
+                snapshot_id: Some(SnapshotId::from((application_canister_id, 7)).to_vec()),
+            },
+            vec!["not controlled by nns root"],
+        );
+
+        is_invalid_proposal_with_keywords(
+            LoadCanisterSnapshot {
+                canister_id: Some(GOVERNANCE_CANISTER_ID.get()),
+                snapshot_id: Some(SnapshotId::from((GOVERNANCE_CANISTER_ID, 7)).to_vec()),
+            },
+            vec!["governance canister is not allowed"],
+        );
+
+        is_invalid_proposal_with_keywords(
+            LoadCanisterSnapshot {
+                canister_id: Some(SNS_WASM_CANISTER_ID.get()),
+                snapshot_id: Some(snapshot_id),
+            },
+            vec!["not a snapshot of canister"],
+        );
+    }
+}
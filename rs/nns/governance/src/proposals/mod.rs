@@ -2,7 +2,7 @@ use crate::{
     governance::LOG_PREFIX,
     pb::v1::{governance_error::ErrorType, GovernanceError, ProposalData, Topic, Vote},
 };
-use ic_base_types::CanisterId;
+use ic_base_types::{CanisterId, SnapshotId};
 use ic_cdk::println;
 use ic_nns_common::pb::v1::NeuronId;
 use ic_nns_constants::{PROTOCOL_CANISTER_IDS, SNS_AGGREGATOR_CANISTER_ID, SNS_WASM_CANISTER_ID};
@@ -11,12 +11,17 @@ use std::collections::HashMap;
 pub mod call_canister;
 pub mod create_service_nervous_system;
 pub mod install_code;
+pub mod load_canister_snapshot;
 pub mod stop_or_start_canister;
+pub mod take_canister_snapshot;
 pub mod update_canister_settings;
 
 const SNS_RELATED_CANISTER_IDS: [&CanisterId; 2] =
     [&SNS_WASM_CANISTER_ID, &SNS_AGGREGATOR_CANISTER_ID];
 
+/// The number of canister IDs allocated to the NNS subnet, starting from 0.
+const NNS_SUBNET_CANISTER_ID_COUNT: u64 = 1 << 20;
+
 pub(crate) fn topic_to_manage_canister(canister_id: &CanisterId) -> Topic {
     if PROTOCOL_CANISTER_IDS.contains(&canister_id) {
         Topic::ProtocolCanisterManagement
@@ -27,6 +32,23 @@ pub(crate) fn topic_to_manage_canister(canister_id: &CanisterId) -> Topic {
     }
 }
 
+/// Returns whether `canister_id` is controlled by the NNS, i.e. whether it was allocated on the
+/// NNS subnet, or is one of the protocol or SNS related canisters that the NNS controls on other
+/// subnets.
+pub(crate) fn is_nns_controlled_canister(canister_id: &CanisterId) -> bool {
+    if PROTOCOL_CANISTER_IDS.contains(&canister_id)
+        || SNS_RELATED_CANISTER_IDS.contains(&canister_id)
+    {
+        return true;
+    }
+    let Ok(canister_id) = CanisterId::try_from_principal_id(canister_id.get()) else {
+        return false;
+    };
+    let mut index = [0; 8];
+    index.copy_from_slice(&canister_id.get_ref().as_slice()[..8]);
+    u64::from_be_bytes(index) < NNS_SUBNET_CANISTER_ID_COUNT
+}
+
 pub(crate) fn invalid_proposal_error(reason: &str) -> GovernanceError {
     GovernanceError::new_with_message(
         ErrorType::InvalidProposal,
@@ -34,6 +56,22 @@ pub(crate) fn invalid_proposal_error(reason: &str) -> GovernanceError {
     )
 }
 
+/// Parses `snapshot_id`, and makes sure that it is a snapshot of `canister_id`.
+pub(crate) fn valid_snapshot_id(
+    canister_id: CanisterId,
+    snapshot_id: &[u8],
+) -> Result<SnapshotId, GovernanceError> {
+    let snapshot_id = SnapshotId::try_from(snapshot_id)
+        .map_err(|err| invalid_proposal_error(&format!("invalid snapshot ID: {}", err)))?;
+    if snapshot_id.get_canister_id() != canister_id {
+        return Err(invalid_proposal_error(&format!(
+            "snapshot ID {} is not a snapshot of canister {}",
+            snapshot_id, canister_id
+        )));
+    }
+    Ok(snapshot_id)
+}
+
 /// Weighted voting power is just voting power * the proposal's (topic's) weight.
 ///
 /// For example, suppose this returns ({42 => 3.14}, 99.9). This means that
@@ -0,0 +1,209 @@
+use super::{
+    invalid_proposal_error, is_nns_controlled_canister, topic_to_manage_canister, valid_snapshot_id,
+};
+use crate::{
+    pb::v1::{governance_error::ErrorType, GovernanceError, TakeCanisterSnapshot, Topic},
+    proposals::call_canister::CallCanister,
+};
+
+use candid::{Decode, Encode};
+use ic_base_types::{CanisterId, SnapshotId};
+use ic_nns_constants::ROOT_CANISTER_ID;
+use ic_nns_handler_root_interface::{
+    TakeCanisterSnapshotOk, TakeCanisterSnapshotRequest, TakeCanisterSnapshotResponse,
+};
+
+impl TakeCanisterSnapshot {
+    pub fn validate(&self) -> Result<(), GovernanceError> {
+        let _ = self.valid_canister_id()?;
+        let _ = self.valid_replace_snapshot()?;
+        let _ = self.valid_topic()?;
+
+        Ok(())
+    }
+
+    pub fn valid_topic(&self) -> Result<Topic, GovernanceError> {
+        let canister_id = self.valid_canister_id()?;
+        Ok(topic_to_manage_canister(&canister_id))
+    }
+
+    fn valid_canister_id(&self) -> Result<CanisterId, GovernanceError> {
+        let canister_principal_id = self
+            .canister_id
+            .ok_or(invalid_proposal_error("Canister ID is required"))?;
+        let canister_id = CanisterId::try_from(canister_principal_id)
+            .map_err(|_| invalid_proposal_error("Invalid canister ID"))?;
+
+        // NNS Root is controlled by Lifeline, not by itself, so it cannot take snapshots of itself.
+        if canister_id == ROOT_CANISTER_ID {
+            return Err(invalid_proposal_error(
+                "Canister is not controlled by NNS Root, so it cannot be snapshotted",
+            ));
+        }
+        // NNS Root can only act on canisters it controls, which are the ones on the NNS subnet and
+        // the protocol canisters hosted on other subnets.
+        if !is_nns_controlled_canister(&canister_id) {
+            return Err(invalid_proposal_error(
+                "Canister is not controlled by NNS Root, so it cannot be snapshotted",
+            ));
+        }
+
+        Ok(canister_id)
+    }
+
+    fn valid_replace_snapshot(&self) -> Result<Option<SnapshotId>, GovernanceError> {
+        let canister_id = self.valid_canister_id()?;
+        self.replace_snapshot
+            .as_ref()
+            .map(|snapshot_id| valid_snapshot_id(canister_id, snapshot_id))
+            .transpose()
+    }
+
+    /// Interprets the reply of NNS Root's take_canister_snapshot method.
+    pub fn process_reply(reply: &[u8]) -> Result<TakeCanisterSnapshotOk, GovernanceError> {
+        let response = Decode!(reply, TakeCanisterSnapshotResponse).map_err(|err| {
+            GovernanceError::new_with_message(
+                ErrorType::External,
+                format!("Failed to decode TakeCanisterSnapshotResponse: {}", err),
+            )
+        })?;
+
+        match response {
+            TakeCanisterSnapshotResponse::Ok(snapshot) => Ok(snapshot),
+            TakeCanisterSnapshotResponse::Err(err) => Err(GovernanceError::new_with_message(
+                ErrorType::External,
+                format!(
+                    "Failed to take canister snapshot. Code: {:?} description: {}",
+                    err.code, err.description
+                ),
+            )),
+        }
+    }
+}
+
+impl CallCanister for TakeCanisterSnapshot {
+    fn canister_and_function(&self) -> Result<(CanisterId, &str), GovernanceError> {
+        Ok((ROOT_CANISTER_ID, "take_canister_snapshot"))
+    }
+
+    fn payload(&self) -> Result<Vec<u8>, GovernanceError> {
+        let canister_id = self.valid_canister_id()?;
+        let replace_snapshot = self.valid_replace_snapshot()?;
+
+        Encode!(&TakeCanisterSnapshotRequest {
+            canister_id: canister_id.get(),
+            replace_snapshot: replace_snapshot.map(|snapshot_id| snapshot_id.to_vec()),
+        })
+        .map_err(|e| invalid_proposal_error(&format!("Failed to encode payload: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ic_nns_constants::{
+        CYCLES_MINTING_CANISTER_ID, EXCHANGE_RATE_CANISTER_ID, REGISTRY_CANISTER_ID,
+    };
+
+    #[test]
+    fn test_take_canister_snapshot() {
+        let replace_snapshot = SnapshotId::from((CYCLES_MINTING_CANISTER_ID, 1));
+        let take_canister_snapshot = TakeCanisterSnapshot {
+            canister_id: Some(CYCLES_MINTING_CANISTER_ID.get()),
+            replace_snapshot: Some(replace_snapshot.to_vec()),
+        };
+
+        assert_eq!(take_canister_snapshot.validate(), Ok(()));
+        assert_eq!(
+            take_canister_snapshot.valid_topic(),
+            Ok(Topic::ProtocolCanisterManagement)
+        );
+        assert_eq!(
+            take_canister_snapshot.canister_and_function(),
+            Ok((ROOT_CANISTER_ID, "take_canister_snapshot"))
+        );
+        let decoded_payload = Decode!(
+            &take_canister_snapshot.payload().unwrap(),
+            TakeCanisterSnapshotRequest
+        )
+        .unwrap();
+        assert_eq!(
+            decoded_payload,
+            TakeCanisterSnapshotRequest {
+                canister_id: CYCLES_MINTING_CANISTER_ID.get(),
+                replace_snapshot: Some(replace_snapshot.to_vec()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_take_canister_snapshot_of_protocol_canister_outside_nns_subnet() {
+        let take_canister_snapshot = TakeCanisterSnapshot {
+            canister_id: Some(EXCHANGE_RATE_CANISTER_ID.get()),
+            replace_snapshot: None,
+        };
+
+        assert_eq!(take_canister_snapshot.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_invalid_take_canister_snapshot() {
+        let is_invalid_proposal_with_keywords =
+            |take_canister_snapshot: TakeCanisterSnapshot, keywords: Vec<&str>| {
+                let error = take_canister_snapshot.validate().expect_err(&format!(
+                    "Expecting validation error for {take_canister_snapshot:?} but got Ok(())"
+                ));
+                assert_eq!(error.error_type, ErrorType::InvalidProposal as i32);
+                for keyword in keywords {
+                    let error_message = error.error_message.to_lowercase();
+                    assert!(
+                        error_message.contains(keyword),
+                        "{} not found in {:#?}",
+                        keyword,
+                        error_message
+                    );
+                }
+            };
+
+        is_invalid_proposal_with_keywords(
+            TakeCanisterSnapshot {
+                canister_id: None,
+                replace_snapshot: None,
+            },
+            vec!["canister id", "required"],
+        );
+
+        is_invalid_proposal_with_keywords(
+            TakeCanisterSnapshot {
+                canister_id: Some(ROOT_CANISTER_ID.get()),
+                replace_snapshot: None,
+            },
+            vec!["not controlled by nns root"],
+        );
+
+        is_invalid_proposal_with_keywords(
+            TakeCanisterSnapshot {
+                canister_id: Some(CanisterId::from_u64(5 << 20).get()),
+                replace_snapshot: None,
+            },
+            vec!["not controlled by nns root"],
+        );
+
+        is_invalid_proposal_with_keywords(
+            TakeCanisterSnapshot {
+                canister_id: Some(CYCLES_MINTING_CANISTER_ID.get()),
+                replace_snapshot: Some(vec![1, 2, 3]),
+            },
+            vec!["snapshot id"],
+        );
+
+        is_invalid_proposal_with_keywords(
+            TakeCanisterSnapshot {
+                canister_id: Some(CYCLES_MINTING_CANISTER_ID.get()),
+                replace_snapshot: Some(SnapshotId::from((REGISTRY_CANISTER_ID, 1)).to_vec()),
+            },
+            vec!["not a snapshot of canister"],
+        );
+    }
+}
@@ -31,7 +31,8 @@ use ic_nns_handler_root::{
 };
 use ic_nns_handler_root_interface::{
     ChangeCanisterControllersRequest, ChangeCanisterControllersResponse,
-    UpdateCanisterSettingsRequest, UpdateCanisterSettingsResponse,
+    LoadCanisterSnapshotRequest, LoadCanisterSnapshotResponse, TakeCanisterSnapshotRequest,
+    TakeCanisterSnapshotResponse, UpdateCanisterSettingsRequest, UpdateCanisterSettingsResponse,
 };
 use std::cell::RefCell;
 
@@ -231,6 +232,32 @@ async fn update_canister_settings(
     .await
 }
 
+/// Takes a snapshot of a canister controlled by NNS Root. Only callable by NNS Governance.
+#[update]
+async fn take_canister_snapshot(
+    take_canister_snapshot_request: TakeCanisterSnapshotRequest,
+) -> TakeCanisterSnapshotResponse {
+    check_caller_is_governance();
+    canister_management::take_canister_snapshot(
+        take_canister_snapshot_request,
+        &mut new_management_canister_client(),
+    )
+    .await
+}
+
+/// Loads a snapshot into a canister controlled by NNS Root. Only callable by NNS Governance.
+#[update]
+async fn load_canister_snapshot(
+    load_canister_snapshot_request: LoadCanisterSnapshotRequest,
+) -> LoadCanisterSnapshotResponse {
+    check_caller_is_governance();
+    canister_management::load_canister_snapshot(
+        load_canister_snapshot_request,
+        &mut new_management_canister_client(),
+    )
+    .await
+}
+
 /// Resources to serve for a given http_request
 /// Serve an HttpRequest made to this canister
 #[query(hidden = true, decoding_quota = 10000)]
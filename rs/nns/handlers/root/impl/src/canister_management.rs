@@ -1,11 +1,12 @@
 use crate::PROXIED_CANISTER_CALLS_TRACKER;
-use ic_base_types::{CanisterId, PrincipalId};
+use ic_base_types::{CanisterId, PrincipalId, SnapshotId};
 use ic_cdk::{
     api::call::{call_with_payment, RejectionCode},
     call, caller, print,
 };
 use ic_management_canister_types_private::{
     CanisterInstallMode::Install, CanisterSettingsArgsBuilder, CreateCanisterArgs, InstallCodeArgs,
+    LoadCanisterSnapshotArgs, TakeCanisterSnapshotArgs,
 };
 use ic_nervous_system_clients::{
     canister_id_record::CanisterIdRecord,
@@ -22,8 +23,10 @@ use ic_nns_common::{
     types::CallCanisterProposal,
 };
 use ic_nns_handler_root_interface::{
-    ChangeCanisterControllersRequest, ChangeCanisterControllersResponse,
-    UpdateCanisterSettingsError, UpdateCanisterSettingsRequest, UpdateCanisterSettingsResponse,
+    CanisterSnapshotError, ChangeCanisterControllersRequest, ChangeCanisterControllersResponse,
+    LoadCanisterSnapshotRequest, LoadCanisterSnapshotResponse, TakeCanisterSnapshotOk,
+    TakeCanisterSnapshotRequest, TakeCanisterSnapshotResponse, UpdateCanisterSettingsError,
+    UpdateCanisterSettingsRequest, UpdateCanisterSettingsResponse,
 };
 use ic_protobuf::{
     registry::nns::v1::{NnsCanisterRecord, NnsCanisterRecords},
@@ -250,3 +253,101 @@ pub async fn update_canister_settings(
         }
     }
 }
+
+/// Parses `snapshot_id`, and makes sure that it is a snapshot of `canister_id`.
+fn validate_snapshot_id(
+    canister_id: CanisterId,
+    snapshot_id: &[u8],
+) -> Result<SnapshotId, CanisterSnapshotError> {
+    let snapshot_id = SnapshotId::try_from(snapshot_id).map_err(|err| CanisterSnapshotError {
+        code: None,
+        description: format!("Invalid snapshot ID: {}", err),
+    })?;
+    if snapshot_id.get_canister_id() != canister_id {
+        return Err(CanisterSnapshotError {
+            code: None,
+            description: format!(
+                "Snapshot {} is not a snapshot of canister {}.",
+                snapshot_id, canister_id
+            ),
+        });
+    }
+    Ok(snapshot_id)
+}
+
+fn validate_canister_id(canister_id: PrincipalId) -> Result<CanisterId, CanisterSnapshotError> {
+    CanisterId::try_from(canister_id).map_err(|err| CanisterSnapshotError {
+        code: None,
+        description: format!("Invalid canister ID {}: {}", canister_id, err),
+    })
+}
+
+pub async fn take_canister_snapshot(
+    take_canister_snapshot_request: TakeCanisterSnapshotRequest,
+    management_canister_client: &mut impl ManagementCanisterClient,
+) -> TakeCanisterSnapshotResponse {
+    let TakeCanisterSnapshotRequest {
+        canister_id,
+        replace_snapshot,
+    } = take_canister_snapshot_request;
+
+    let args = validate_canister_id(canister_id).and_then(|canister_id| {
+        let replace_snapshot = replace_snapshot
+            .map(|snapshot_id| validate_snapshot_id(canister_id, &snapshot_id))
+            .transpose()?;
+        Ok(TakeCanisterSnapshotArgs::new(canister_id, replace_snapshot))
+    });
+    let args = match args {
+        Ok(args) => args,
+        Err(err) => return TakeCanisterSnapshotResponse::Err(err),
+    };
+
+    match management_canister_client
+        .take_canister_snapshot(args)
+        .await
+    {
+        Ok(snapshot) => TakeCanisterSnapshotResponse::Ok(TakeCanisterSnapshotOk {
+            id: snapshot.snapshot_id().to_vec(),
+            taken_at_timestamp: snapshot.taken_at_timestamp(),
+            total_size: snapshot.total_size(),
+        }),
+        Err((code, description)) => TakeCanisterSnapshotResponse::Err(CanisterSnapshotError {
+            code: Some(code),
+            description,
+        }),
+    }
+}
+
+pub async fn load_canister_snapshot(
+    load_canister_snapshot_request: LoadCanisterSnapshotRequest,
+    management_canister_client: &mut impl ManagementCanisterClient,
+) -> LoadCanisterSnapshotResponse {
+    let LoadCanisterSnapshotRequest {
+        canister_id,
+        snapshot_id,
+    } = load_canister_snapshot_request;
+
+    let args = validate_canister_id(canister_id).and_then(|canister_id| {
+        let snapshot_id = validate_snapshot_id(canister_id, &snapshot_id)?;
+        Ok(LoadCanisterSnapshotArgs::new(
+            canister_id,
+            snapshot_id,
+            management_canister_client.canister_version(),
+        ))
+    });
+    let args = match args {
+        Ok(args) => args,
+        Err(err) => return LoadCanisterSnapshotResponse::Err(err),
+    };
+
+    match management_canister_client
+        .load_canister_snapshot(args)
+        .await
+    {
+        Ok(()) => LoadCanisterSnapshotResponse::Ok(()),
+        Err((code, description)) => LoadCanisterSnapshotResponse::Err(CanisterSnapshotError {
+            code: Some(code),
+            description,
+        }),
+    }
+}
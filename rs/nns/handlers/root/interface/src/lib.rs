@@ -82,3 +82,46 @@ pub struct UpdateCanisterSettingsError {
     pub code: Option<i32>,
     pub description: String,
 }
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, CandidType, Deserialize)]
+pub struct TakeCanisterSnapshotRequest {
+    // The canister ID of the target canister.
+    pub canister_id: PrincipalId,
+    // The ID of an existing snapshot of the target canister to replace. If not set, a new snapshot
+    // is added.
+    pub replace_snapshot: Option<Vec<u8>>,
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, CandidType, Deserialize)]
+pub enum TakeCanisterSnapshotResponse {
+    Ok(TakeCanisterSnapshotOk),
+    Err(CanisterSnapshotError),
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, CandidType, Deserialize)]
+pub struct TakeCanisterSnapshotOk {
+    // The ID of the new snapshot.
+    pub id: Vec<u8>,
+    pub taken_at_timestamp: u64,
+    pub total_size: u64,
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, CandidType, Deserialize)]
+pub struct LoadCanisterSnapshotRequest {
+    // The canister ID of the target canister.
+    pub canister_id: PrincipalId,
+    // The ID of the snapshot to load. It must be a snapshot of the target canister.
+    pub snapshot_id: Vec<u8>,
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, CandidType, Deserialize)]
+pub enum LoadCanisterSnapshotResponse {
+    Ok(()),
+    Err(CanisterSnapshotError),
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, CandidType, Deserialize)]
+pub struct CanisterSnapshotError {
+    pub code: Option<i32>,
+    pub description: String,
+}
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use ic_http_utils::file_downloader::FileDownloader;
+use ic_http_utils::file_downloader::{check_file_hash, FileDownloader};
 use ic_logger::{error, info, warn, ReplicaLogger};
 use std::future::Future;
 use std::str::FromStr;
@@ -15,8 +15,10 @@ use tokio::sync::watch::Receiver;
 use tokio::time::error::Elapsed;
 
 use crate::error::{UpgradeError, UpgradeResult};
+use crate::peer_distribution::{PeerImageCache, PeerImageDownloader};
 
 pub mod error;
+pub mod peer_distribution;
 
 const REBOOT_TIME_FILENAME: &str = "reboot_time.txt";
 
@@ -112,6 +114,16 @@ pub trait ImageUpgrader<V: Clone + Debug + PartialEq + Eq + Send + Sync, R: Send
         &self,
         version: &V,
     ) -> UpgradeResult<(Vec<String>, Option<String>)>;
+    /// Return the cache in which downloaded release packages are published for
+    /// peers. Default is None, which disables peer-assisted downloads.
+    fn peer_image_cache(&self) -> Option<&PeerImageCache> {
+        None
+    }
+    /// Return the base URLs of the peers that may serve the release package of
+    /// the given version from their [`PeerImageCache`]. Default is empty.
+    fn get_peer_image_urls(&self, _version: &V) -> Vec<String> {
+        vec![]
+    }
 
     /// Calls a corresponding script to "confirm" that the base OS could boot
     /// successfully. Without a confirmation the image will be reverted on the next
@@ -146,6 +158,21 @@ pub trait ImageUpgrader<V: Clone + Debug + PartialEq + Eq + Send + Sync, R: Send
             )));
         }
 
+        if let (Some(cache), Some(hash)) = (self.peer_image_cache(), hash.as_ref()) {
+            match self
+                .download_release_package_from_peers(version, cache, hash)
+                .await
+            {
+                Ok(()) => return Ok(()),
+                Err(e) => warn!(
+                    self.log(),
+                    "Downloading image {:?} from peers failed, falling back to URLs: {}",
+                    version,
+                    e
+                ),
+            }
+        }
+
         // Load-balance, by making each node rotate the `release_package_urls` by some number.
         // Note that the order is the same for everyone; only the starting point is different.
         // This is okay because we do expect the first attempt to be successful.
@@ -172,6 +199,9 @@ pub trait ImageUpgrader<V: Clone + Debug + PartialEq + Eq + Send + Sync, R: Send
                 error = UpgradeError::from(e);
             } else {
                 info!(self.log(), "{} processed in {:?}", req, duration);
+                if let (Some(cache), Some(hash)) = (self.peer_image_cache(), hash) {
+                    self.publish_release_package(cache, hash).await;
+                }
                 return Ok(());
             }
         }
@@ -179,6 +209,60 @@ pub trait ImageUpgrader<V: Clone + Debug + PartialEq + Eq + Send + Sync, R: Send
         Err(error)
     }
 
+    /// Downloads the release package with the given hash chunk by chunk from
+    /// the peers returned by `get_peer_image_urls()`, see [`PeerImageDownloader`].
+    ///
+    /// If the release package was already downloaded, it is only published
+    /// for peers.
+    async fn download_release_package_from_peers(
+        &self,
+        version: &V,
+        cache: &PeerImageCache,
+        hash: &str,
+    ) -> UpgradeResult<()> {
+        if self.image_path().exists() && check_file_hash(self.image_path(), hash).is_ok() {
+            self.publish_release_package(cache, hash.to_string()).await;
+            return Ok(());
+        }
+
+        let mut peer_urls = self.get_peer_image_urls(version);
+        if peer_urls.is_empty() {
+            return Err(UpgradeError::GenericError(format!(
+                "No peers to download image {:?} from",
+                version
+            )));
+        }
+        // Load-balance in the same way as for the release package URLs.
+        let peer_count = peer_urls.len();
+        peer_urls.rotate_right(self.get_load_balance_number() % peer_count);
+
+        let start_time = std::time::Instant::now();
+        PeerImageDownloader::new(self.log().clone(), Duration::from_secs(60))
+            .download(&peer_urls, hash, cache, self.image_path())
+            .await?;
+        info!(
+            self.log(),
+            "Downloaded image {:?} from peers in {:?}",
+            version,
+            start_time.elapsed()
+        );
+        Ok(())
+    }
+
+    /// Publishes the downloaded release package in the given cache, so that
+    /// peers can fetch it from this node. Failures are only logged, as they do
+    /// not affect the upgrade of this node.
+    async fn publish_release_package(&self, cache: &PeerImageCache, hash: String) {
+        let cache = cache.clone();
+        let image_path = self.image_path().clone();
+        let result = tokio::task::spawn_blocking(move || cache.publish(&image_path, &hash)).await;
+        match result {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => warn!(self.log(), "Failed to publish image for peers: {}", e),
+            Err(e) => warn!(self.log(), "Failed to publish image for peers: {}", e),
+        }
+    }
+
     /// Downloads release package associated with the given version,
     /// calls the node script that extracts it and copies it to the boot partition.
     /// This function is automatically called by `execute_upgrade()` unless `version`
@@ -243,6 +327,16 @@ pub trait ImageUpgrader<V: Clone + Debug + PartialEq + Eq + Send + Sync, R: Send
         // We could successfully unpack the file above, so we do not need the image anymore.
         std::fs::remove_file(self.image_path())
             .map_err(|e| UpgradeError::IoError("Couldn't delete the image".to_string(), e))?;
+        // Peers upgrade at the same height, so they no longer need the chunks
+        // published by this node.
+        if let Some(cache) = self.peer_image_cache() {
+            if let Err(e) = cache.clear() {
+                warn!(
+                    self.log(),
+                    "Failed to remove the image published for peers: {}", e
+                );
+            }
+        }
 
         info!(self.log(), "Attempting to reboot");
         let script = self.binary_dir().join("manageboot.sh");
@@ -0,0 +1,689 @@
+//! Peer-assisted distribution of release packages.
+//!
+//! Once a node has obtained a release package, it splits the package into
+//! fixed-size chunks and publishes them, content-addressed by their SHA-256
+//! hash, in its [`PeerImageCache`]. The cache directory is laid out exactly as
+//! it is served to peers over HTTP:
+//!
+//! ```text
+//! <image sha256>/manifest.json
+//! <image sha256>/chunks/<chunk sha256>
+//! ```
+//!
+//! so that any static file server can stand in for a peer. Other nodes use a
+//! [`PeerImageDownloader`] to fetch the manifest and the chunks from peers that
+//! already have them. Every chunk is verified against the hash listed in the
+//! manifest and the reassembled package is verified against the hash from the
+//! registry, so the peers do not need to be trusted.
+
+use crate::error::{UpgradeError, UpgradeResult};
+use ic_crypto_sha2::Sha256;
+use ic_http_utils::file_downloader::check_file_hash;
+use ic_logger::{info, warn, ReplicaLogger};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::io::AsyncWriteExt;
+
+/// The default size of the chunks a release package is split into.
+pub const DEFAULT_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+/// The default time after which downloading from peers is given up on, so
+/// that the release package URLs are tried in time.
+pub const DEFAULT_TOTAL_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// The largest chunk accepted from peers.
+const MAX_CHUNK_SIZE: u64 = 64 * 1024 * 1024;
+/// The largest release package accepted from peers.
+const MAX_IMAGE_SIZE: u64 = 8 * 1024 * 1024 * 1024;
+/// The largest manifest accepted from peers.
+const MAX_MANIFEST_SIZE: u64 = 1024 * 1024;
+
+const MANIFEST_FILENAME: &str = "manifest.json";
+const CHUNKS_DIRNAME: &str = "chunks";
+
+/// Lists the chunks that make up a release package, in order.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageManifest {
+    pub image_sha256_hex: String,
+    pub chunks: Vec<ImageChunk>,
+}
+
+/// A chunk of a release package, identified by its SHA-256 hash.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageChunk {
+    pub sha256_hex: String,
+    pub size: u64,
+}
+
+/// A directory holding the chunks of the most recently obtained release
+/// package, from which they are served to peers.
+#[derive(Clone, Debug)]
+pub struct PeerImageCache {
+    root: PathBuf,
+    chunk_size: usize,
+}
+
+impl PeerImageCache {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    /// Sets the size of the chunks images are split into when published.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Returns the manifest of the given image, if the image is fully
+    /// available in the cache.
+    pub fn manifest(&self, image_sha256_hex: &str) -> Option<ImageManifest> {
+        if !is_sha256_hex(image_sha256_hex) {
+            return None;
+        }
+        let bytes = fs::read(self.image_dir(image_sha256_hex).join(MANIFEST_FILENAME)).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// Maps the path of an HTTP request from a peer to the file in the cache
+    /// that should be served, if any.
+    pub fn resolve_request_path(&self, request_path: &str) -> Option<PathBuf> {
+        let segments: Vec<&str> = request_path.trim_start_matches('/').split('/').collect();
+        let path = match segments.as_slice() {
+            [image, MANIFEST_FILENAME] if is_sha256_hex(image) => {
+                self.image_dir(image).join(MANIFEST_FILENAME)
+            }
+            [image, CHUNKS_DIRNAME, chunk] if is_sha256_hex(image) && is_sha256_hex(chunk) => {
+                self.chunk_path(image, chunk)
+            }
+            _ => return None,
+        };
+        path.is_file().then_some(path)
+    }
+
+    /// Splits the image at `image_path` into chunks and publishes them for
+    /// peers. Images published earlier are removed.
+    ///
+    /// The manifest is written last, so peers only ever see complete images.
+    pub fn publish(
+        &self,
+        image_path: &Path,
+        image_sha256_hex: &str,
+    ) -> UpgradeResult<ImageManifest> {
+        if !is_sha256_hex(image_sha256_hex) {
+            return Err(UpgradeError::GenericError(format!(
+                "Invalid image hash: {}",
+                image_sha256_hex
+            )));
+        }
+        if let Some(manifest) = self.manifest(image_sha256_hex) {
+            return Ok(manifest);
+        }
+        self.remove_other_images(image_sha256_hex)?;
+
+        let mut image = fs::File::open(image_path).map_err(|e| {
+            UpgradeError::IoError(format!("Failed to open image {}", image_path.display()), e)
+        })?;
+        let mut chunks = Vec::new();
+        loop {
+            let mut bytes = Vec::with_capacity(self.chunk_size);
+            (&mut image)
+                .take(self.chunk_size as u64)
+                .read_to_end(&mut bytes)
+                .map_err(|e| {
+                    UpgradeError::IoError(
+                        format!("Failed to read image {}", image_path.display()),
+                        e,
+                    )
+                })?;
+            if bytes.is_empty() {
+                break;
+            }
+            let chunk = ImageChunk {
+                sha256_hex: hex::encode(Sha256::hash(&bytes)),
+                size: bytes.len() as u64,
+            };
+            self.store_chunk(image_sha256_hex, &chunk, &bytes)?;
+            chunks.push(chunk);
+        }
+
+        let manifest = ImageManifest {
+            image_sha256_hex: image_sha256_hex.to_string(),
+            chunks,
+        };
+        self.store_manifest(&manifest)?;
+        Ok(manifest)
+    }
+
+    /// Removes all images from the cache, e.g. once the node has been
+    /// upgraded and its peers no longer need the image.
+    pub fn clear(&self) -> UpgradeResult<()> {
+        self.remove_other_images("")
+    }
+
+    /// Returns the content of the given chunk, if it is in the cache and
+    /// matches its hash. A cached chunk that does not match its hash is
+    /// removed.
+    fn read_chunk(&self, image_sha256_hex: &str, chunk: &ImageChunk) -> Option<Vec<u8>> {
+        let path = self.chunk_path(image_sha256_hex, &chunk.sha256_hex);
+        let bytes = fs::read(&path).ok()?;
+        if !chunk_matches(chunk, &bytes) {
+            let _ = fs::remove_file(&path);
+            return None;
+        }
+        Some(bytes)
+    }
+
+    fn store_chunk(
+        &self,
+        image_sha256_hex: &str,
+        chunk: &ImageChunk,
+        bytes: &[u8],
+    ) -> UpgradeResult<()> {
+        let path = self.chunk_path(image_sha256_hex, &chunk.sha256_hex);
+        write_using_tmp_file(&path, bytes)
+            .map_err(|e| UpgradeError::IoError(format!("Failed to store chunk {:?}", path), e))
+    }
+
+    fn store_manifest(&self, manifest: &ImageManifest) -> UpgradeResult<()> {
+        let path = self
+            .image_dir(&manifest.image_sha256_hex)
+            .join(MANIFEST_FILENAME);
+        let bytes = serde_json::to_vec(manifest).map_err(|e| {
+            UpgradeError::GenericError(format!("Failed to serialize manifest: {}", e))
+        })?;
+        write_using_tmp_file(&path, &bytes)
+            .map_err(|e| UpgradeError::IoError(format!("Failed to store manifest {:?}", path), e))
+    }
+
+    /// Removes the given image from the cache.
+    fn remove_image(&self, image_sha256_hex: &str) -> UpgradeResult<()> {
+        let path = self.image_dir(image_sha256_hex);
+        match fs::remove_dir_all(&path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(UpgradeError::IoError(
+                format!("Failed to remove {:?}", path),
+                e,
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Removes all images but the given one from the cache.
+    fn remove_other_images(&self, image_sha256_hex: &str) -> UpgradeResult<()> {
+        let entries = match fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => {
+                return Err(UpgradeError::IoError(
+                    format!("Failed to list {:?}", self.root),
+                    e,
+                ))
+            }
+        };
+        for entry in entries.flatten() {
+            if entry.file_name() != image_sha256_hex {
+                let path = entry.path();
+                fs::remove_dir_all(&path).map_err(|e| {
+                    UpgradeError::IoError(format!("Failed to remove {:?}", path), e)
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    fn image_dir(&self, image_sha256_hex: &str) -> PathBuf {
+        self.root.join(image_sha256_hex)
+    }
+
+    fn chunk_path(&self, image_sha256_hex: &str, chunk_sha256_hex: &str) -> PathBuf {
+        self.image_dir(image_sha256_hex)
+            .join(CHUNKS_DIRNAME)
+            .join(chunk_sha256_hex)
+    }
+}
+
+/// Downloads release packages chunk by chunk from the [`PeerImageCache`]s of
+/// peers.
+pub struct PeerImageDownloader {
+    client: Client,
+    total_timeout: Duration,
+    logger: ReplicaLogger,
+}
+
+impl PeerImageDownloader {
+    /// Creates a downloader whose requests to peers time out after `timeout`.
+    pub fn new(logger: ReplicaLogger, timeout: Duration) -> Self {
+        let client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("Failed to build the HTTP client");
+        Self {
+            client,
+            total_timeout: DEFAULT_TOTAL_TIMEOUT,
+            logger,
+        }
+    }
+
+    /// Sets the time after which a download is given up on, no matter how
+    /// many peers are left to try.
+    pub fn with_total_timeout(mut self, total_timeout: Duration) -> Self {
+        self.total_timeout = total_timeout;
+        self
+    }
+
+    /// Downloads the image with the given hash from the peers at `peer_urls`
+    /// to `target`.
+    ///
+    /// Chunks already present in `cache` are not downloaded again. Consecutive
+    /// chunks are requested from different peers to spread the load, and a
+    /// chunk that cannot be obtained from one peer is requested from the next.
+    /// Once the image is complete and matches its hash, it is published in
+    /// `cache` so that other peers can fetch it from this node. If the chunks
+    /// match the manifest but the image does not match its hash, the chunks
+    /// are removed from `cache`.
+    ///
+    /// The download fails if it takes longer than the total timeout.
+    pub async fn download(
+        &self,
+        peer_urls: &[String],
+        image_sha256_hex: &str,
+        cache: &PeerImageCache,
+        target: &Path,
+    ) -> UpgradeResult<()> {
+        let tmp_path = target.with_extension("peer_download");
+        let result = tokio::time::timeout(
+            self.total_timeout,
+            self.download_to(peer_urls, image_sha256_hex, cache, &tmp_path, target),
+        )
+        .await
+        .unwrap_or_else(|_| {
+            Err(UpgradeError::GenericError(format!(
+                "Downloading image {} from peers timed out after {:?}",
+                image_sha256_hex, self.total_timeout
+            )))
+        });
+        if result.is_err() {
+            let _ = fs::remove_file(&tmp_path);
+        }
+        result
+    }
+
+    async fn download_to(
+        &self,
+        peer_urls: &[String],
+        image_sha256_hex: &str,
+        cache: &PeerImageCache,
+        tmp_path: &Path,
+        target: &Path,
+    ) -> UpgradeResult<()> {
+        let manifest = self.fetch_manifest(peer_urls, image_sha256_hex).await?;
+        cache.remove_other_images(image_sha256_hex)?;
+
+        let mut file = tokio::fs::File::create(tmp_path)
+            .await
+            .map_err(|e| UpgradeError::IoError(format!("Failed to create {:?}", tmp_path), e))?;
+        for (index, chunk) in manifest.chunks.iter().enumerate() {
+            let bytes = match cache.read_chunk(image_sha256_hex, chunk) {
+                Some(bytes) => bytes,
+                None => {
+                    let bytes = self
+                        .fetch_chunk(peer_urls, index, image_sha256_hex, chunk)
+                        .await?;
+                    cache.store_chunk(image_sha256_hex, chunk, &bytes)?;
+                    bytes
+                }
+            };
+            file.write_all(&bytes)
+                .await
+                .map_err(|e| UpgradeError::IoError(format!("Failed to write {:?}", tmp_path), e))?;
+        }
+        file.sync_all()
+            .await
+            .map_err(|e| UpgradeError::IoError(format!("Failed to sync {:?}", tmp_path), e))?;
+        drop(file);
+
+        if let Err(e) = check_file_hash(tmp_path, image_sha256_hex) {
+            // The chunks match the manifest but not the image, so the peers
+            // served a bogus manifest and the chunks must not be reused.
+            cache.remove_image(image_sha256_hex)?;
+            return Err(e.into());
+        }
+        fs::rename(tmp_path, target).map_err(|e| {
+            UpgradeError::IoError(format!("Failed to move image to {:?}", target), e)
+        })?;
+        cache.store_manifest(&manifest)?;
+        info!(
+            self.logger,
+            "Downloaded image {} in {} chunks from peers",
+            image_sha256_hex,
+            manifest.chunks.len()
+        );
+        Ok(())
+    }
+
+    /// Returns the manifest of the given image from the first peer that serves
+    /// a well-formed one.
+    async fn fetch_manifest(
+        &self,
+        peer_urls: &[String],
+        image_sha256_hex: &str,
+    ) -> UpgradeResult<ImageManifest> {
+        for peer_url in peer_urls {
+            let url = format!(
+                "{}/{}/{}",
+                peer_url.trim_end_matches('/'),
+                image_sha256_hex,
+                MANIFEST_FILENAME
+            );
+            let manifest = match self.get(&url, MAX_MANIFEST_SIZE).await {
+                Ok(bytes) => serde_json::from_slice::<ImageManifest>(&bytes)
+                    .map_err(|e| format!("invalid manifest: {}", e)),
+                Err(e) => Err(e),
+            };
+            match manifest {
+                Ok(manifest) if is_valid_manifest(&manifest, image_sha256_hex) => {
+                    return Ok(manifest)
+                }
+                Ok(_) => warn!(self.logger, "Peer served a malformed manifest at {}", url),
+                Err(e) => warn!(self.logger, "Failed to fetch manifest from {}: {}", url, e),
+            }
+        }
+        Err(UpgradeError::GenericError(format!(
+            "No peer serves image {}",
+            image_sha256_hex
+        )))
+    }
+
+    /// Fetches a chunk and verifies it against the manifest, trying the peers
+    /// in turn starting with the one at position `index`.
+    async fn fetch_chunk(
+        &self,
+        peer_urls: &[String],
+        index: usize,
+        image_sha256_hex: &str,
+        chunk: &ImageChunk,
+    ) -> UpgradeResult<Vec<u8>> {
+        for peer_url in peer_urls.iter().cycle().skip(index).take(peer_urls.len()) {
+            let url = format!(
+                "{}/{}/{}/{}",
+                peer_url.trim_end_matches('/'),
+                image_sha256_hex,
+                CHUNKS_DIRNAME,
+                chunk.sha256_hex
+            );
+            match self.get(&url, chunk.size).await {
+                Ok(bytes) if chunk_matches(chunk, &bytes) => return Ok(bytes),
+                Ok(_) => warn!(self.logger, "Peer served a corrupted chunk at {}", url),
+                Err(e) => warn!(self.logger, "Failed to fetch chunk from {}: {}", url, e),
+            }
+        }
+        Err(UpgradeError::GenericError(format!(
+            "No peer serves chunk {} of image {}",
+            chunk.sha256_hex, image_sha256_hex
+        )))
+    }
+
+    /// Returns the body served at `url`, reading at most `max_size` bytes of
+    /// it.
+    async fn get(&self, url: &str, max_size: u64) -> Result<Vec<u8>, String> {
+        let mut response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| e.to_string())?;
+        if response.content_length().unwrap_or(0) > max_size {
+            return Err(format!("body exceeds {} bytes", max_size));
+        }
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+            if (bytes.len() + chunk.len()) as u64 > max_size {
+                return Err(format!("body exceeds {} bytes", max_size));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(bytes)
+    }
+}
+
+fn is_sha256_hex(s: &str) -> bool {
+    s.len() == 64 && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+fn is_valid_manifest(manifest: &ImageManifest, image_sha256_hex: &str) -> bool {
+    manifest.image_sha256_hex == image_sha256_hex
+        && manifest.chunks.iter().all(|chunk| {
+            is_sha256_hex(&chunk.sha256_hex) && (1..=MAX_CHUNK_SIZE).contains(&chunk.size)
+        })
+        && manifest
+            .chunks
+            .iter()
+            .try_fold(0_u64, |total, chunk| total.checked_add(chunk.size))
+            .is_some_and(|total| total <= MAX_IMAGE_SIZE)
+}
+
+fn chunk_matches(chunk: &ImageChunk, bytes: &[u8]) -> bool {
+    bytes.len() as u64 == chunk.size && hex::encode(Sha256::hash(bytes)) == chunk.sha256_hex
+}
+
+fn write_using_tmp_file(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, bytes)?;
+    fs::rename(&tmp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_logger::no_op_logger;
+    use mockito::{Server, ServerGuard};
+    use tempfile::tempdir;
+
+    const IMAGE: &[u8] = b"a release package split into several chunks";
+    const CHUNK_SIZE: usize = 8;
+
+    fn image_hash(bytes: &[u8]) -> String {
+        hex::encode(Sha256::hash(bytes))
+    }
+
+    /// Starts a file server that serves the given cache directory, standing
+    /// in for a peer.
+    async fn serve_cache(cache: &PeerImageCache, image_sha256_hex: &str) -> ServerGuard {
+        let mut server = Server::new_async().await;
+        let manifest = cache.manifest(image_sha256_hex).unwrap();
+        let manifest_path = format!("/{}/{}", image_sha256_hex, MANIFEST_FILENAME);
+        let mut paths = vec![manifest_path];
+        paths.extend(manifest.chunks.iter().map(|chunk| {
+            format!(
+                "/{}/{}/{}",
+                image_sha256_hex, CHUNKS_DIRNAME, chunk.sha256_hex
+            )
+        }));
+        for path in paths {
+            let body = fs::read(cache.resolve_request_path(&path).unwrap()).unwrap();
+            server
+                .mock("GET", path.as_str())
+                .with_body(body)
+                .create_async()
+                .await;
+        }
+        server
+    }
+
+    fn publish_image(image: &[u8]) -> (tempfile::TempDir, PeerImageCache, String) {
+        let dir = tempdir().unwrap();
+        let image_path = dir.path().join("image.bin");
+        fs::write(&image_path, image).unwrap();
+        let cache = PeerImageCache::new(dir.path().join("cache")).with_chunk_size(CHUNK_SIZE);
+        let hash = image_hash(image);
+        cache.publish(&image_path, &hash).unwrap();
+        (dir, cache, hash)
+    }
+
+    #[test]
+    fn publish_splits_image_into_content_addressed_chunks() {
+        let (_dir, cache, hash) = publish_image(IMAGE);
+
+        let manifest = cache.manifest(&hash).unwrap();
+        assert_eq!(manifest.image_sha256_hex, hash);
+        assert_eq!(manifest.chunks.len(), IMAGE.len().div_ceil(CHUNK_SIZE));
+        let reassembled: Vec<u8> = manifest
+            .chunks
+            .iter()
+            .flat_map(|chunk| cache.read_chunk(&hash, chunk).unwrap())
+            .collect();
+        assert_eq!(reassembled, IMAGE);
+
+        // Publishing another image replaces the previous one.
+        let (dir, _, _) = publish_image(b"another image");
+        let other_path = dir.path().join("image.bin");
+        let other_hash = image_hash(b"another image");
+        cache.publish(&other_path, &other_hash).unwrap();
+        assert!(cache.manifest(&other_hash).is_some());
+        assert!(cache.manifest(&hash).is_none());
+    }
+
+    #[test]
+    fn resolve_request_path_only_serves_cache_entries() {
+        let (_dir, cache, hash) = publish_image(IMAGE);
+        let chunk = &cache.manifest(&hash).unwrap().chunks[0];
+
+        assert!(cache
+            .resolve_request_path(&format!("/{}/{}", hash, MANIFEST_FILENAME))
+            .is_some());
+        assert!(cache
+            .resolve_request_path(&format!("/{}/chunks/{}", hash, chunk.sha256_hex))
+            .is_some());
+        assert!(cache
+            .resolve_request_path(&format!("/{}/chunks/{}", hash, "0".repeat(64)))
+            .is_none());
+        assert!(cache
+            .resolve_request_path(&format!("/../{}/{}", hash, MANIFEST_FILENAME))
+            .is_none());
+        assert!(cache.resolve_request_path("/").is_none());
+    }
+
+    #[tokio::test]
+    async fn download_reassembles_image_from_peers() {
+        let (_seed_dir, seed_cache, hash) = publish_image(IMAGE);
+        let peer = serve_cache(&seed_cache, &hash).await;
+        // A peer that does not have the image yet.
+        let empty_peer = Server::new_async().await;
+
+        let dir = tempdir().unwrap();
+        let cache = PeerImageCache::new(dir.path().join("cache"));
+        let target = dir.path().join("image.bin");
+        PeerImageDownloader::new(no_op_logger(), Duration::from_secs(10))
+            .download(&[empty_peer.url(), peer.url()], &hash, &cache, &target)
+            .await
+            .unwrap();
+
+        assert_eq!(fs::read(&target).unwrap(), IMAGE);
+        // The node now serves the image to other peers.
+        assert_eq!(cache.manifest(&hash), seed_cache.manifest(&hash));
+    }
+
+    #[tokio::test]
+    async fn download_rejects_image_not_matching_its_hash() {
+        let (_seed_dir, seed_cache, _) = publish_image(IMAGE);
+        // Serve the chunks of `IMAGE` under the hash of another image.
+        let hash = image_hash(b"the image the registry points to");
+        let mut manifest = seed_cache.manifest(&image_hash(IMAGE)).unwrap();
+        manifest.image_sha256_hex = hash.clone();
+        seed_cache.store_manifest(&manifest).unwrap();
+        for chunk in &manifest.chunks {
+            let bytes = seed_cache.read_chunk(&image_hash(IMAGE), chunk).unwrap();
+            seed_cache.store_chunk(&hash, chunk, &bytes).unwrap();
+        }
+        let peer = serve_cache(&seed_cache, &hash).await;
+
+        let dir = tempdir().unwrap();
+        let cache = PeerImageCache::new(dir.path().join("cache"));
+        let target = dir.path().join("image.bin");
+        let result = PeerImageDownloader::new(no_op_logger(), Duration::from_secs(10))
+            .download(&[peer.url()], &hash, &cache, &target)
+            .await;
+
+        assert!(matches!(result, Err(UpgradeError::FileDownloadError(_))));
+        assert!(!target.exists());
+        assert!(cache.manifest(&hash).is_none());
+        // The fetched chunks are not kept.
+        assert!(!cache.image_dir(&hash).exists());
+    }
+
+    #[tokio::test]
+    async fn download_gives_up_after_total_timeout() {
+        let (_seed_dir, seed_cache, hash) = publish_image(IMAGE);
+        let peer = serve_cache(&seed_cache, &hash).await;
+
+        let dir = tempdir().unwrap();
+        let cache = PeerImageCache::new(dir.path().join("cache"));
+        let target = dir.path().join("image.bin");
+        let result = PeerImageDownloader::new(no_op_logger(), Duration::from_secs(10))
+            .with_total_timeout(Duration::ZERO)
+            .download(&[peer.url()], &hash, &cache, &target)
+            .await;
+
+        assert!(matches!(result, Err(UpgradeError::GenericError(_))));
+        assert!(!target.exists());
+    }
+
+    #[tokio::test]
+    async fn get_rejects_body_exceeding_limit() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/body")
+            .with_body(IMAGE)
+            .create_async()
+            .await;
+        let url = format!("{}/body", server.url());
+        let downloader = PeerImageDownloader::new(no_op_logger(), Duration::from_secs(10));
+
+        assert_eq!(
+            downloader.get(&url, IMAGE.len() as u64).await.unwrap(),
+            IMAGE
+        );
+        assert!(downloader.get(&url, IMAGE.len() as u64 - 1).await.is_err());
+    }
+
+    #[test]
+    fn is_valid_manifest_bounds_chunk_and_image_size() {
+        let (_dir, cache, hash) = publish_image(IMAGE);
+        let manifest = cache.manifest(&hash).unwrap();
+        assert!(is_valid_manifest(&manifest, &hash));
+
+        let mut oversized_chunk = manifest.clone();
+        oversized_chunk.chunks[0].size = MAX_CHUNK_SIZE + 1;
+        assert!(!is_valid_manifest(&oversized_chunk, &hash));
+
+        let mut oversized_image = manifest;
+        let chunk = oversized_image.chunks[0].clone();
+        oversized_image.chunks = vec![
+            ImageChunk {
+                size: MAX_CHUNK_SIZE,
+                ..chunk
+            };
+            (MAX_IMAGE_SIZE / MAX_CHUNK_SIZE + 1) as usize
+        ];
+        assert!(!is_valid_manifest(&oversized_image, &hash));
+    }
+
+    #[test]
+    fn clear_removes_published_images() {
+        let (_dir, cache, hash) = publish_image(IMAGE);
+        cache.clear().unwrap();
+        assert!(cache.manifest(&hash).is_none());
+        assert!(!cache.image_dir(&hash).exists());
+    }
+}
@@ -0,0 +1,95 @@
+//! Serves the release packages published in the node's [`PeerImageCache`] to
+//! the other nodes of its subnet, so that they can download upgrade images
+//! from peers instead of the release package URLs. See
+//! [`ic_image_upgrader::peer_distribution`] for details.
+
+use http_body_util::Full;
+use hyper::{body::Bytes, server::conn::http1, service::service_fn, Method, Request, Response};
+use hyper_util::rt::TokioIo;
+use ic_image_upgrader::peer_distribution::PeerImageCache;
+use ic_logger::{info, warn, ReplicaLogger};
+use std::{
+    convert::Infallible,
+    net::{IpAddr, Ipv6Addr, SocketAddr},
+    sync::Arc,
+};
+use tokio::{net::TcpListener, sync::watch::Receiver};
+
+/// The port on which nodes serve their [`PeerImageCache`] to peers.
+pub(crate) const IMAGE_DISTRIBUTION_PORT: u16 = 7071;
+
+/// Listens to IMAGE_DISTRIBUTION_PORT and serves the manifests and chunks of
+/// the release packages in the node's [`PeerImageCache`].
+pub(crate) struct ImageDistributionServer {
+    cache: Arc<PeerImageCache>,
+    logger: ReplicaLogger,
+}
+
+impl ImageDistributionServer {
+    pub(crate) fn new(cache: PeerImageCache, logger: ReplicaLogger) -> Self {
+        Self {
+            cache: Arc::new(cache),
+            logger,
+        }
+    }
+
+    /// Serves requests until the exit signal changes.
+    pub(crate) async fn run(&self, mut exit_signal: Receiver<bool>) {
+        tokio::select! {
+            _ = self.serve_requests() => {}
+            _ = exit_signal.changed() => {}
+        };
+    }
+
+    async fn serve_requests(&self) {
+        let addr = SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), IMAGE_DISTRIBUTION_PORT);
+        let listener = match TcpListener::bind(addr).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                warn!(self.logger, "Failed to bind to socket {}: {}", addr, e);
+                return;
+            }
+        };
+        info!(self.logger, "Serving release package chunks on {}", addr);
+
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                continue;
+            };
+            let cache = Arc::clone(&self.cache);
+            let logger = self.logger.clone();
+            tokio::spawn(async move {
+                let service = service_fn(move |request| {
+                    let cache = Arc::clone(&cache);
+                    async move { Ok::<_, Infallible>(handle_request(&cache, request).await) }
+                });
+                if let Err(e) = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    warn!(logger, "Failed to serve peer connection: {}", e);
+                }
+            });
+        }
+    }
+}
+
+async fn handle_request<B>(cache: &PeerImageCache, request: Request<B>) -> Response<Full<Bytes>> {
+    if request.method() != Method::GET {
+        return response(405, Bytes::new());
+    }
+    let Some(path) = cache.resolve_request_path(request.uri().path()) else {
+        return response(404, Bytes::new());
+    };
+    match tokio::fs::read(path).await {
+        Ok(bytes) => response(200, Bytes::from(bytes)),
+        Err(_) => response(404, Bytes::new()),
+    }
+}
+
+fn response(status: u16, body: Bytes) -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(status)
+        .body(Full::new(body))
+        .expect("Failed to build response")
+}
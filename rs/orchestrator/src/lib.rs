@@ -39,6 +39,7 @@ mod dashboard;
 mod error;
 mod firewall;
 mod hostos_upgrade;
+mod image_distribution;
 mod ipv4_network;
 mod metrics;
 pub mod orchestrator;
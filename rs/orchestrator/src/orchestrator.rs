@@ -5,6 +5,7 @@ use crate::{
     dashboard::{Dashboard, OrchestratorDashboard},
     firewall::Firewall,
     hostos_upgrade::HostosUpgrader,
+    image_distribution::ImageDistributionServer,
     ipv4_network::Ipv4Configurator,
     metrics::OrchestratorMetrics,
     process_manager::ProcessManager,
@@ -19,7 +20,7 @@ use ic_config::metrics::{Config as MetricsConfig, Exporter};
 use ic_crypto::CryptoComponent;
 use ic_crypto_node_key_generation::{generate_node_keys_once, NodeKeyGenerationError};
 use ic_http_endpoints_metrics::MetricsHttpEndpoint;
-use ic_image_upgrader::ImageUpgrader;
+use ic_image_upgrader::{peer_distribution::PeerImageCache, ImageUpgrader};
 use ic_logger::{error, info, new_replica_logger_from_config, warn, ReplicaLogger};
 use ic_metrics::MetricsRegistry;
 use ic_registry_replicator::RegistryReplicator;
@@ -39,6 +40,9 @@ use std::{
 use tokio::{sync::watch::Receiver, task::JoinSet};
 
 const CHECK_INTERVAL_SECS: Duration = Duration::from_secs(10);
+/// Subdirectory of the orchestrator data directory holding the release
+/// package chunks served to peers.
+const PEER_IMAGE_CACHE_DIR: &str = "peer_image_cache";
 
 pub struct Orchestrator {
     pub logger: ReplicaLogger,
@@ -50,6 +54,7 @@ pub struct Orchestrator {
     firewall: Option<Firewall>,
     ssh_access_manager: Option<SshAccessManager>,
     orchestrator_dashboard: Option<OrchestratorDashboard>,
+    image_distribution_server: Option<ImageDistributionServer>,
     registration: Option<NodeRegistration>,
     // The subnet id of the node.
     subnet_id: Arc<RwLock<Option<SubnetId>>>,
@@ -234,6 +239,13 @@ impl Orchestrator {
             registration.register_node().await;
         }
 
+        let peer_image_cache =
+            PeerImageCache::new(args.orchestrator_data_directory.join(PEER_IMAGE_CACHE_DIR));
+        let image_distribution_server = Some(ImageDistributionServer::new(
+            peer_image_cache.clone(),
+            logger.clone(),
+        ));
+
         let upgrade = Some(
             Upgrade::new(
                 Arc::clone(&registry),
@@ -248,6 +260,7 @@ impl Orchestrator {
                 args.replica_binary_dir.clone(),
                 logger.clone(),
                 args.orchestrator_data_directory.clone(),
+                peer_image_cache,
             )
             .await,
         );
@@ -338,6 +351,7 @@ impl Orchestrator {
             firewall: Some(firewall),
             ssh_access_manager: Some(ssh_access_manager),
             orchestrator_dashboard,
+            image_distribution_server,
             registration: Some(registration),
             subnet_id,
             ipv4_configurator: Some(ipv4_configurator),
@@ -366,6 +380,9 @@ impl Orchestrator {
     /// 4. Fourth task checks if this node is part of a threshold signing subnet. If so,
     ///    and it is also time to rotate the iDKG encryption key, instruct crypto
     ///    to do the rotation and attempt to register the rotated key.
+    ///
+    /// 5. Fifth task serves the release packages downloaded by this node to
+    ///    its peers.
     pub async fn start_tasks(&mut self, exit_signal: Receiver<bool>) {
         async fn upgrade_checks(
             maybe_subnet_id: Arc<RwLock<Option<SubnetId>>>,
@@ -485,6 +502,13 @@ impl Orchestrator {
             dashboard.run(exit_signal).await;
         }
 
+        async fn serve_release_packages(
+            server: ImageDistributionServer,
+            exit_signal: Receiver<bool>,
+        ) {
+            server.run(exit_signal).await;
+        }
+
         if let Some(upgrade) = self.upgrade.take() {
             self.task_tracker.spawn(
                 "upgrade",
@@ -533,6 +557,13 @@ impl Orchestrator {
                 .spawn("dashboard", serve_dashboard(dashboard, exit_signal.clone()));
         }
 
+        if let Some(server) = self.image_distribution_server.take() {
+            self.task_tracker.spawn(
+                "image_distribution",
+                serve_release_packages(server, exit_signal.clone()),
+            );
+        }
+
         if let Some(registration) = self.registration.take() {
             self.task_tracker.spawn(
                 "key_rotation",
@@ -1,9 +1,11 @@
 use crate::{
     catch_up_package_provider::CatchUpPackageProvider,
     error::{OrchestratorError, OrchestratorResult},
+    image_distribution::IMAGE_DISTRIBUTION_PORT,
     metrics::OrchestratorMetrics,
     process_manager::{Process, ProcessManager},
     registry_helper::RegistryHelper,
+    utils::http_endpoint_to_url,
 };
 use async_trait::async_trait;
 use ic_consensus_dkg::get_vetkey_public_keys;
@@ -11,13 +13,17 @@ use ic_crypto::get_master_public_key_from_transcript;
 use ic_http_utils::file_downloader::FileDownloader;
 use ic_image_upgrader::{
     error::{UpgradeError, UpgradeResult},
+    peer_distribution::PeerImageCache,
     ImageUpgrader,
 };
 use ic_interfaces_registry::RegistryClient;
 use ic_logger::{error, info, warn, ReplicaLogger};
 use ic_management_canister_types_private::MasterPublicKeyId;
 use ic_protobuf::proxy::try_from_option_field;
-use ic_registry_client_helpers::{node::NodeRegistry, subnet::SubnetRegistry};
+use ic_registry_client_helpers::{
+    node::NodeRegistry,
+    subnet::{SubnetRegistry, SubnetTransportRegistry},
+};
 use ic_registry_local_store::LocalStoreImpl;
 use ic_registry_replicator::RegistryReplicator;
 use ic_types::{
@@ -84,6 +90,8 @@ pub(crate) struct Upgrade {
     /// The replica version that is prepared by 'prepare_upgrade' to upgrade to.
     pub prepared_upgrade_version: Option<ReplicaVersion>,
     pub orchestrator_data_directory: PathBuf,
+    /// Release packages downloaded by this node, served to its peers.
+    peer_image_cache: PeerImageCache,
 }
 
 impl Upgrade {
@@ -101,6 +109,7 @@ impl Upgrade {
         release_content_dir: PathBuf,
         logger: ReplicaLogger,
         orchestrator_data_directory: PathBuf,
+        peer_image_cache: PeerImageCache,
     ) -> Self {
         let value = Self {
             registry,
@@ -116,6 +125,7 @@ impl Upgrade {
             logger: logger.clone(),
             prepared_upgrade_version: None,
             orchestrator_data_directory,
+            peer_image_cache,
         };
         if let Err(e) = value.report_reboot_time() {
             warn!(logger, "Cannot report the reboot time: {}", e);
@@ -560,6 +570,34 @@ impl ImageUpgrader<ReplicaVersion, Option<SubnetId>> for Upgrade {
         ))
     }
 
+    fn peer_image_cache(&self) -> Option<&PeerImageCache> {
+        Some(&self.peer_image_cache)
+    }
+
+    fn get_peer_image_urls(&self, _version: &ReplicaVersion) -> Vec<String> {
+        let registry_version = self.registry.get_latest_version();
+        let Ok(Some(subnet_id)) = self
+            .registry
+            .get_subnet_id_from_node_id(self.node_id, registry_version)
+        else {
+            return vec![];
+        };
+        self.registry
+            .registry_client
+            .get_subnet_node_records(subnet_id, registry_version)
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(node_id, _)| *node_id != self.node_id)
+            .filter_map(|(_, node_record)| {
+                let mut url = http_endpoint_to_url(node_record.http.as_ref()?, &self.logger)?;
+                url.set_port(Some(IMAGE_DISTRIBUTION_PORT)).ok()?;
+                Some(url.to_string())
+            })
+            .collect()
+    }
+
     fn log(&self) -> &ReplicaLogger {
         &self.logger
     }
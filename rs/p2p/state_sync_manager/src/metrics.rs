@@ -8,6 +8,7 @@ use crate::ongoing::DownloadChunkError;
 
 const CHUNK_DOWNLOAD_STATUS_LABEL: &str = "status";
 const CHUNK_DOWNLOAD_STATUS_SUCCESS: &str = "success";
+const CHUNK_ENCODING_LABEL: &str = "encoding";
 
 #[derive(Clone, Debug)]
 pub(crate) struct StateSyncManagerMetrics {
@@ -44,6 +45,7 @@ impl StateSyncManagerMetrics {
 #[derive(Clone, Debug)]
 pub struct StateSyncManagerHandlerMetrics {
     pub compression_ratio: Histogram,
+    pub chunk_responses_total: IntCounterVec,
 }
 
 impl StateSyncManagerHandlerMetrics {
@@ -54,6 +56,11 @@ impl StateSyncManagerHandlerMetrics {
                 "State sync manager chunk compression ratio.",
                 vec![1.0, 1.25, 1.5, 2.0, 3.0, 5.0, 10.0],
             ),
+            chunk_responses_total: metrics_registry.int_counter_vec(
+                "state_sync_manager_chunk_responses_total",
+                "Chunks served, by the encoding used on the wire.",
+                &[CHUNK_ENCODING_LABEL],
+            ),
         }
     }
 }
@@ -62,7 +69,9 @@ pub(crate) struct OngoingStateSyncMetrics {
     pub download_task_monitor: TaskMonitor,
     pub allowed_parallel_downloads: IntGauge,
     pub chunk_size_compressed_total: IntCounter,
+    pub chunk_size_by_encoding_total: IntCounterVec,
     pub chunk_size_decompressed_total: IntCounter,
+    pub chunks_received_total: IntCounterVec,
     pub chunks_to_download_calls_total: IntCounter,
     pub chunks_to_download_total: IntCounter,
     pub peers_serving_state: IntGauge,
@@ -86,10 +95,20 @@ impl OngoingStateSyncMetrics {
                 "state_sync_manager_chunk_size_compressed_total",
                 "Sum of all chunks received from transport.",
             ),
+            chunk_size_by_encoding_total: metrics_registry.int_counter_vec(
+                "state_sync_manager_chunk_size_by_encoding_total",
+                "Sum of all chunks received from transport, by the encoding used on the wire.",
+                &[CHUNK_ENCODING_LABEL],
+            ),
             chunk_size_decompressed_total: metrics_registry.int_counter(
                 "state_sync_manager_chunk_size_decompressed_total",
                 "Sum of all chunks received after decompresssion.",
             ),
+            chunks_received_total: metrics_registry.int_counter_vec(
+                "state_sync_manager_chunks_received_total",
+                "Chunks received, by the encoding used on the wire.",
+                &[CHUNK_ENCODING_LABEL],
+            ),
             chunks_to_download_calls_total: metrics_registry.int_counter(
                 "state_sync_manager_chunks_to_download_calls_total",
                 "Number of times manager asked state sync for list of chunks to download.",
@@ -5,7 +5,10 @@ use crate::ongoing::DownloadChunkError;
 use axum::{
     body::Bytes,
     extract::State,
-    http::{Request, Response, StatusCode},
+    http::{
+        header::{ACCEPT_ENCODING, CONTENT_ENCODING},
+        HeaderMap, HeaderName, HeaderValue, Request, Response, StatusCode,
+    },
 };
 use bytes::BytesMut;
 use ic_interfaces::p2p::state_sync::{Chunk, ChunkId, StateSyncArtifactId, StateSyncClient};
@@ -18,6 +21,71 @@ pub const STATE_SYNC_CHUNK_PATH: &str = "/state-sync/chunk";
 /// State sync uses 1Mb chunks. To be safe we use 8Mib here same as transport.
 const MAX_CHUNK_SIZE: usize = 8 * 1024 * 1024;
 
+/// Encoding of a chunk response body on the wire.
+///
+/// The encoding is negotiated per chunk: the requester lists the encodings it
+/// supports in the `Accept-Encoding` header and the responder states the one it
+/// picked in the `Content-Encoding` header. Peers that do not send these headers
+/// predate the negotiation and only understand zstd.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum ChunkEncoding {
+    Zstd,
+    Identity,
+}
+
+impl ChunkEncoding {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ChunkEncoding::Zstd => "zstd",
+            ChunkEncoding::Identity => "identity",
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Self> {
+        // Strip optional parameters such as quality values (`zstd;q=1.0`).
+        let name = token.split(';').next().unwrap_or_default().trim();
+        if name.eq_ignore_ascii_case("zstd") {
+            Some(ChunkEncoding::Zstd)
+        } else if name.eq_ignore_ascii_case("identity") {
+            Some(ChunkEncoding::Identity)
+        } else {
+            None
+        }
+    }
+
+    /// Parses an `Accept-Encoding` entry. Entries with a quality value of zero
+    /// (`identity;q=0`) explicitly refuse the encoding and yield `None`.
+    fn from_accept_token(token: &str) -> Option<Self> {
+        let encoding = Self::from_token(token)?;
+        let refused = token
+            .split(';')
+            .skip(1)
+            .any(|param| match param.trim().split_once('=') {
+                Some((name, value)) if name.trim().eq_ignore_ascii_case("q") => value
+                    .trim()
+                    .parse::<f32>()
+                    .is_ok_and(|quality| quality == 0.0),
+                _ => false,
+            });
+        (!refused).then_some(encoding)
+    }
+
+    /// Returns the encodings accepted by the requester. A request without an
+    /// `Accept-Encoding` header only accepts zstd.
+    fn accepted(headers: &HeaderMap) -> Vec<Self> {
+        if !headers.contains_key(ACCEPT_ENCODING) {
+            return vec![ChunkEncoding::Zstd];
+        }
+        headers
+            .get_all(ACCEPT_ENCODING)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(Self::from_accept_token)
+            .collect()
+    }
+}
+
 pub(crate) struct StateSyncChunkHandler<T> {
     _log: ReplicaLogger,
     state_sync: Arc<dyn StateSyncClient<Message = T>>,
@@ -40,14 +108,20 @@ impl<T> StateSyncChunkHandler<T> {
 
 pub(crate) async fn state_sync_chunk_handler<T: 'static>(
     State(state): State<Arc<StateSyncChunkHandler<T>>>,
+    headers: HeaderMap,
     payload: Bytes,
-) -> Result<Bytes, StatusCode> {
+) -> Result<([(HeaderName, HeaderValue); 1], Bytes), StatusCode> {
     // Parse payload
     let pb::StateSyncChunkRequest { id, chunk_id } =
         pb::StateSyncChunkRequest::decode(payload).map_err(|_| StatusCode::BAD_REQUEST)?;
     let artifact_id: StateSyncArtifactId = id.map(From::from).ok_or(StatusCode::BAD_REQUEST)?;
     let chunk_id = ChunkId::from(chunk_id);
 
+    let accepted = ChunkEncoding::accepted(&headers);
+    if accepted.is_empty() {
+        return Err(StatusCode::NOT_ACCEPTABLE);
+    }
+
     let jh =
         tokio::task::spawn_blocking(
             move || match state.state_sync.chunk(&artifact_id, chunk_id) {
@@ -57,20 +131,45 @@ pub(crate) async fn state_sync_chunk_handler<T: 'static>(
                     pb_chunk.encode(&mut raw).expect("Allocated enough memory");
                     let raw = raw.freeze();
 
-                    let compressed = zstd::bulk::compress(&raw, zstd::DEFAULT_COMPRESSION_LEVEL)
-                        .expect("Compression failed");
+                    let (encoding, body) = if accepted.contains(&ChunkEncoding::Zstd) {
+                        let compressed =
+                            zstd::bulk::compress(&raw, zstd::DEFAULT_COMPRESSION_LEVEL)
+                                .expect("Compression failed");
+                        state
+                            .metrics
+                            .compression_ratio
+                            .observe(raw.len() as f64 / compressed.len() as f64);
+                        // Incompressible chunks (e.g. already compressed canister data)
+                        // are sent as is if the requester allows it.
+                        if compressed.len() >= raw.len()
+                            && accepted.contains(&ChunkEncoding::Identity)
+                        {
+                            (ChunkEncoding::Identity, raw)
+                        } else {
+                            (ChunkEncoding::Zstd, Bytes::from(compressed))
+                        }
+                    } else {
+                        (ChunkEncoding::Identity, raw)
+                    };
                     state
                         .metrics
-                        .compression_ratio
-                        .observe(raw.len() as f64 / compressed.len() as f64);
-                    Ok(compressed)
+                        .chunk_responses_total
+                        .with_label_values(&[encoding.as_str()])
+                        .inc();
+                    Ok((encoding, body))
                 }
                 None => Err(StatusCode::NO_CONTENT),
             },
         );
-    let data = jh.await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)??;
+    let (encoding, data) = jh.await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)??;
 
-    Ok(data.into())
+    Ok((
+        [(
+            CONTENT_ENCODING,
+            HeaderValue::from_static(encoding.as_str()),
+        )],
+        data,
+    ))
 }
 
 pub(crate) fn build_chunk_handler_request(
@@ -87,11 +186,15 @@ pub(crate) fn build_chunk_handler_request(
 
     Request::builder()
         .uri(STATE_SYNC_CHUNK_PATH)
+        .header(ACCEPT_ENCODING, HeaderValue::from_static("zstd, identity"))
         .body(raw.freeze())
         .expect("Building from typed values")
 }
 
 /// Transforms the http response received into typed responses expected from this handler.
+///
+/// The chunk is decoded here, before it is handed to state sync, so that
+/// chunk validation always operates on the raw chunk bytes.
 pub(crate) fn parse_chunk_handler_response(
     response: Response<Bytes>,
     chunk_id: ChunkId,
@@ -101,27 +204,63 @@ pub(crate) fn parse_chunk_handler_response(
 
     match parts.status {
         StatusCode::OK => {
+            // Responses without a `Content-Encoding` header come from peers
+            // that always compress with zstd.
+            let encoding = match parts.headers.get(CONTENT_ENCODING) {
+                None => ChunkEncoding::Zstd,
+                Some(value) => value
+                    .to_str()
+                    .ok()
+                    .and_then(ChunkEncoding::from_token)
+                    .ok_or_else(|| DownloadChunkError::RequestError {
+                        chunk_id,
+                        err: format!("Unsupported content encoding {:?}", value),
+                    })?,
+            };
+
             metrics
                 .chunk_size_compressed_total
                 .inc_by(body.len() as u64);
-            let decompressed = zstd::bulk::decompress(&body, MAX_CHUNK_SIZE).map_err(|e| {
-                DownloadChunkError::RequestError {
-                    chunk_id,
-                    err: e.to_string(),
+            metrics
+                .chunk_size_by_encoding_total
+                .with_label_values(&[encoding.as_str()])
+                .inc_by(body.len() as u64);
+            let decoded = match encoding {
+                ChunkEncoding::Zstd => {
+                    Bytes::from(zstd::bulk::decompress(&body, MAX_CHUNK_SIZE).map_err(|e| {
+                        DownloadChunkError::RequestError {
+                            chunk_id,
+                            err: e.to_string(),
+                        }
+                    })?)
                 }
-            })?;
+                ChunkEncoding::Identity if body.len() > MAX_CHUNK_SIZE => {
+                    return Err(DownloadChunkError::RequestError {
+                        chunk_id,
+                        err: format!(
+                            "Chunk of {} bytes exceeds the maximum size of {} bytes",
+                            body.len(),
+                            MAX_CHUNK_SIZE
+                        ),
+                    });
+                }
+                ChunkEncoding::Identity => body,
+            };
 
             metrics
                 .chunk_size_decompressed_total
-                .inc_by(decompressed.len() as u64);
+                .inc_by(decoded.len() as u64);
+            metrics
+                .chunks_received_total
+                .with_label_values(&[encoding.as_str()])
+                .inc();
 
-            let pb =
-                pb::StateSyncChunkResponse::decode(Bytes::from(decompressed)).map_err(|e| {
-                    DownloadChunkError::RequestError {
-                        chunk_id,
-                        err: e.to_string(),
-                    }
-                })?;
+            let pb = pb::StateSyncChunkResponse::decode(decoded).map_err(|e| {
+                DownloadChunkError::RequestError {
+                    chunk_id,
+                    err: e.to_string(),
+                }
+            })?;
 
             Ok(pb.data.into())
         }
@@ -134,3 +273,109 @@ pub(crate) fn parse_chunk_handler_response(
         }),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_metrics::MetricsRegistry;
+
+    fn encoded_chunk(data: &[u8]) -> Bytes {
+        let pb_chunk = pb::StateSyncChunkResponse {
+            data: data.to_vec(),
+        };
+        let mut raw = BytesMut::with_capacity(pb_chunk.encoded_len());
+        pb_chunk.encode(&mut raw).expect("Allocated enough memory");
+        raw.freeze()
+    }
+
+    fn parse(response: Response<Bytes>) -> Result<Chunk, DownloadChunkError> {
+        parse_chunk_handler_response(
+            response,
+            ChunkId::from(1),
+            OngoingStateSyncMetrics::new(&MetricsRegistry::new()),
+        )
+    }
+
+    #[test]
+    fn accepted_encodings_default_to_zstd() {
+        assert_eq!(
+            ChunkEncoding::accepted(&HeaderMap::new()),
+            vec![ChunkEncoding::Zstd]
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            ACCEPT_ENCODING,
+            HeaderValue::from_static("gzip, identity;q=0.5, ZSTD"),
+        );
+        assert_eq!(
+            ChunkEncoding::accepted(&headers),
+            vec![ChunkEncoding::Identity, ChunkEncoding::Zstd]
+        );
+    }
+
+    #[test]
+    fn zero_quality_refuses_encoding() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            ACCEPT_ENCODING,
+            HeaderValue::from_static("zstd;q=1.0, identity;q=0"),
+        );
+        assert_eq!(ChunkEncoding::accepted(&headers), vec![ChunkEncoding::Zstd]);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT_ENCODING, HeaderValue::from_static("zstd; Q=0.000"));
+        assert!(ChunkEncoding::accepted(&headers).is_empty());
+    }
+
+    #[test]
+    fn parse_response_without_content_encoding_as_zstd() {
+        let raw = encoded_chunk(&[7; 100]);
+        let compressed = zstd::bulk::compress(&raw, zstd::DEFAULT_COMPRESSION_LEVEL).unwrap();
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .body(Bytes::from(compressed))
+            .unwrap();
+
+        assert_eq!(parse(response).unwrap().as_bytes(), &[7; 100]);
+    }
+
+    #[test]
+    fn parse_identity_encoded_response() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .header(CONTENT_ENCODING, "identity")
+            .body(encoded_chunk(&[1, 2, 3]))
+            .unwrap();
+
+        assert_eq!(parse(response).unwrap().as_bytes(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn reject_oversized_identity_encoded_response() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .header(CONTENT_ENCODING, "identity")
+            .body(encoded_chunk(&vec![0; MAX_CHUNK_SIZE]))
+            .unwrap();
+
+        assert!(matches!(
+            parse(response),
+            Err(DownloadChunkError::RequestError { .. })
+        ));
+    }
+
+    #[test]
+    fn reject_unknown_content_encoding() {
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .header(CONTENT_ENCODING, "br")
+            .body(encoded_chunk(&[1, 2, 3]))
+            .unwrap();
+
+        assert!(matches!(
+            parse(response),
+            Err(DownloadChunkError::RequestError { .. })
+        ));
+    }
+}
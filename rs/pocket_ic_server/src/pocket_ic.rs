@@ -54,8 +54,9 @@ use ic_registry_routing_table::{
 };
 use ic_registry_subnet_type::SubnetType;
 use ic_state_machine_tests::{
-    add_global_registry_records, add_initial_registry_records, FakeVerifier, StateMachine,
-    StateMachineBuilder, StateMachineConfig, StateMachineStateDir, SubmitIngressError, Subnets,
+    add_global_registry_records, add_initial_registry_records, FakeVerifier, RecordedMessageKind,
+    StateMachine, StateMachineBuilder, StateMachineConfig, StateMachineStateDir,
+    SubmitIngressError, Subnets,
 };
 use ic_state_manager::StateManagerImpl;
 use ic_types::batch::BlockmakerMetrics;
@@ -80,10 +81,11 @@ use ic_types::{NumBytes, Time};
 use ic_validator_ingress_message::StandaloneIngressSigVerifier;
 use itertools::Itertools;
 use pocket_ic::common::rest::{
-    self, BinaryBlob, BlobCompression, CanisterHttpHeader, CanisterHttpMethod, CanisterHttpRequest,
-    CanisterHttpResponse, ExtendedSubnetConfigSet, IcpFeatures, MockCanisterHttpResponse,
-    RawAddCycles, RawCanisterCall, RawCanisterId, RawEffectivePrincipal, RawMessageId,
-    RawSetStableMemory, SubnetInstructionConfig, SubnetKind, TickConfigs, Topology,
+    self, BinaryBlob, BlobCompression, CallGraphMessage, CallGraphMessageKind, CanisterHttpHeader,
+    CanisterHttpMethod, CanisterHttpRequest, CanisterHttpResponse, ExtendedSubnetConfigSet,
+    IcpFeatures, MockCanisterHttpResponse, RawAddCycles, RawCanisterCall, RawCanisterId,
+    RawEffectivePrincipal, RawMessageId, RawSetStableMemory, SubnetInstructionConfig, SubnetKind,
+    TickConfigs, Topology,
 };
 use pocket_ic::{copy_dir, ErrorCode, RejectCode, RejectResponse};
 use registry_canister::init::RegistryCanisterInitPayload;
@@ -851,6 +853,9 @@ impl PocketIcSubnets {
                 wasm_memory_limit: Some(3_221_225_472_u64.into()),
                 wasm_memory_threshold: Some(0_u64.into()),
                 environment_variables: None,
+                cycles_threshold: None,
+                cycles_burn_limit: None,
+                history_visibility: None,
             };
             let canister_id = nns_subnet.state_machine.create_canister_with_cycles(
                 Some(REGISTRY_CANISTER_ID.get()),
@@ -1660,6 +1665,63 @@ impl Operation for GetCanisterHttp {
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub struct SetCallGraphRecording {
+    pub enabled: bool,
+}
+
+impl Operation for SetCallGraphRecording {
+    fn compute(&self, pic: &mut PocketIc) -> OpOut {
+        for subnet in pic.subnets.get_all() {
+            subnet.state_machine.set_call_graph_recording(self.enabled);
+        }
+        OpOut::NoOutput
+    }
+
+    fn id(&self) -> OpId {
+        OpId(format!("set_call_graph_recording({})", self.enabled))
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct GetCallGraph;
+
+impl Operation for GetCallGraph {
+    fn compute(&self, pic: &mut PocketIc) -> OpOut {
+        let mut res = vec![];
+        for subnet in pic.subnets.get_all() {
+            let subnet_id = subnet.get_subnet_id().get().0;
+            res.extend(
+                subnet
+                    .state_machine
+                    .call_graph()
+                    .into_iter()
+                    .map(|message| CallGraphMessage {
+                        subnet_id,
+                        height: message.height,
+                        kind: match message.kind {
+                            RecordedMessageKind::Request => CallGraphMessageKind::Request,
+                            RecordedMessageKind::Response => CallGraphMessageKind::Response,
+                        },
+                        caller: message.caller.0,
+                        callee: message.callee.0,
+                        method_name: message.method_name,
+                        callback_id: message.callback_id,
+                        payload_size_bytes: message.payload_size_bytes,
+                        cycles: message.cycles,
+                        reject_code: message.reject_code,
+                        deadline: message.deadline,
+                    }),
+            );
+        }
+        OpOut::CallGraph(res)
+    }
+
+    fn id(&self) -> OpId {
+        OpId("get_call_graph".into())
+    }
+}
+
 /// The operation `ProcessCanisterHttpInternal` changes the instance state in a non-deterministic way!
 /// It should only be used internally in auto-progress mode
 /// which changes the instance state in a non-deterministic way anyway.
@@ -2512,6 +2574,7 @@ impl Operation for QueryRequest {
                 let svc = QueryServiceBuilder::builder(
                     subnet.replica_logger.clone(),
                     node.node_id,
+                    subnet.get_subnet_id(),
                     Arc::new(PocketNodeSigner(node.node_signing_key.clone())),
                     subnet.registry_client.clone(),
                     Arc::new(StandaloneIngressSigVerifier),
@@ -37,8 +37,8 @@ use ic_gateway::{setup_router, Cli};
 use ic_types::{canister_http::CanisterHttpRequestId, CanisterId, NodeId, PrincipalId, SubnetId};
 use itertools::Itertools;
 use pocket_ic::common::rest::{
-    CanisterHttpRequest, HttpGatewayBackend, HttpGatewayConfig, HttpGatewayDetails,
-    HttpGatewayInfo, Topology,
+    CallGraphMessage, CanisterHttpRequest, HttpGatewayBackend, HttpGatewayConfig,
+    HttpGatewayDetails, HttpGatewayInfo, Topology,
 };
 use pocket_ic::RejectResponse;
 use reqwest::Url;
@@ -246,6 +246,7 @@ pub enum OpOut {
     MessageId((EffectivePrincipal, Vec<u8>)),
     Topology(Topology),
     CanisterHttp(Vec<CanisterHttpRequest>),
+    CallGraph(Vec<CallGraphMessage>),
 }
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Deserialize, Serialize)]
@@ -353,6 +354,7 @@ impl std::fmt::Debug for OpOut {
             OpOut::CanisterHttp(canister_http_reqeusts) => {
                 write!(f, "CanisterHttp({:?})", canister_http_reqeusts)
             }
+            OpOut::CallGraph(call_graph) => write!(f, "CallGraph({:?})", call_graph),
         }
     }
 }
@@ -158,6 +158,7 @@ pub mod wasm_method {
         CanisterHeartbeat = 6,
         CanisterGlobalTimer = 8,
         CanisterOnLowWasmMemory = 9,
+        CanisterOnLowCycles = 10,
     }
     impl SystemMethod {
         /// String value of the enum field names used in the ProtoBuf definition.
@@ -175,6 +176,7 @@ pub mod wasm_method {
                 Self::CanisterHeartbeat => "SYSTEM_METHOD_CANISTER_HEARTBEAT",
                 Self::CanisterGlobalTimer => "SYSTEM_METHOD_CANISTER_GLOBAL_TIMER",
                 Self::CanisterOnLowWasmMemory => "SYSTEM_METHOD_CANISTER_ON_LOW_WASM_MEMORY",
+                Self::CanisterOnLowCycles => "SYSTEM_METHOD_CANISTER_ON_LOW_CYCLES",
             }
         }
         /// Creates an enum from field names used in the ProtoBuf definition.
@@ -189,6 +191,7 @@ pub mod wasm_method {
                 "SYSTEM_METHOD_CANISTER_HEARTBEAT" => Some(Self::CanisterHeartbeat),
                 "SYSTEM_METHOD_CANISTER_GLOBAL_TIMER" => Some(Self::CanisterGlobalTimer),
                 "SYSTEM_METHOD_CANISTER_ON_LOW_WASM_MEMORY" => Some(Self::CanisterOnLowWasmMemory),
+                "SYSTEM_METHOD_CANISTER_ON_LOW_CYCLES" => Some(Self::CanisterOnLowCycles),
                 _ => None,
             }
         }
@@ -353,6 +356,7 @@ pub mod execution_task {
         Heartbeat = 1,
         Timer = 2,
         OnLowWasmMemory = 3,
+        OnLowCycles = 4,
     }
     impl CanisterTask {
         /// String value of the enum field names used in the ProtoBuf definition.
@@ -365,6 +369,7 @@ pub mod execution_task {
                 Self::Heartbeat => "CANISTER_TASK_HEARTBEAT",
                 Self::Timer => "CANISTER_TASK_TIMER",
                 Self::OnLowWasmMemory => "CANISTER_TASK_ON_LOW_WASM_MEMORY",
+                Self::OnLowCycles => "CANISTER_TASK_ON_LOW_CYCLES",
             }
         }
         /// Creates an enum from field names used in the ProtoBuf definition.
@@ -374,6 +379,7 @@ pub mod execution_task {
                 "CANISTER_TASK_HEARTBEAT" => Some(Self::Heartbeat),
                 "CANISTER_TASK_TIMER" => Some(Self::Timer),
                 "CANISTER_TASK_ON_LOW_WASM_MEMORY" => Some(Self::OnLowWasmMemory),
+                "CANISTER_TASK_ON_LOW_CYCLES" => Some(Self::OnLowCycles),
                 _ => None,
             }
         }
@@ -515,6 +521,13 @@ pub struct CanisterHistory {
     pub changes: ::prost::alloc::vec::Vec<CanisterChange>,
     #[prost(uint64, tag = "2")]
     pub total_num_changes: u64,
+    /// Hashes of the install arguments, one entry per element of `changes`.
+    /// The entry is empty for changes that are not code deployments.
+    #[prost(bytes = "vec", repeated, tag = "3")]
+    pub install_argument_hashes: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    /// Who may fetch the extended canister history.
+    #[prost(enumeration = "CanisterHistoryVisibility", tag = "4")]
+    pub visibility: i32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Unsigned128 {
@@ -596,6 +609,16 @@ pub struct TaskQueue {
     /// Queue of `Heartbeat` and `GlobalTimer` tasks.
     #[prost(message, repeated, tag = "3")]
     pub queue: ::prost::alloc::vec::Vec<ExecutionTask>,
+    /// Status of on_low_cycles hook execution.
+    #[prost(enumeration = "OnLowCyclesHookStatus", tag = "4")]
+    pub on_low_cycles_hook_status: i32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct CyclesBurnSample {
+    #[prost(uint64, tag = "1")]
+    pub time_nanos: u64,
+    #[prost(message, optional, tag = "2")]
+    pub consumed_cycles: ::core::option::Option<super::super::super::types::v1::NominalCycles>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CanisterStateBits {
@@ -706,6 +729,15 @@ pub struct CanisterStateBits {
         ::prost::alloc::string::String,
         ::prost::alloc::string::String,
     >,
+    /// Threshold used for activation of the on_low_cycles hook.
+    #[prost(message, optional, tag = "56")]
+    pub cycles_threshold: ::core::option::Option<super::super::queues::v1::Cycles>,
+    /// The user-specified upper limit on the cycles consumed within a window.
+    #[prost(message, optional, tag = "57")]
+    pub cycles_burn_limit: ::core::option::Option<super::super::queues::v1::Cycles>,
+    /// Samples of `consumed_cycles` covering the rolling cycles burn window.
+    #[prost(message, repeated, tag = "60")]
+    pub cycles_burn_samples: ::prost::alloc::vec::Vec<CyclesBurnSample>,
     #[prost(oneof = "canister_state_bits::CanisterStatus", tags = "11, 12, 13")]
     pub canister_status: ::core::option::Option<canister_state_bits::CanisterStatus>,
 }
@@ -856,6 +888,35 @@ impl CyclesUseCase {
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum CanisterHistoryVisibility {
+    Unspecified = 0,
+    Controllers = 1,
+    Public = 2,
+}
+impl CanisterHistoryVisibility {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "CANISTER_HISTORY_VISIBILITY_UNSPECIFIED",
+            Self::Controllers => "CANISTER_HISTORY_VISIBILITY_CONTROLLERS",
+            Self::Public => "CANISTER_HISTORY_VISIBILITY_PUBLIC",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CANISTER_HISTORY_VISIBILITY_UNSPECIFIED" => Some(Self::Unspecified),
+            "CANISTER_HISTORY_VISIBILITY_CONTROLLERS" => Some(Self::Controllers),
+            "CANISTER_HISTORY_VISIBILITY_PUBLIC" => Some(Self::Public),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum LongExecutionMode {
     Unspecified = 0,
     Opportunistic = 1,
@@ -917,3 +978,37 @@ impl OnLowWasmMemoryHookStatus {
         }
     }
 }
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum OnLowCyclesHookStatus {
+    Unspecified = 0,
+    ConditionNotSatisfied = 1,
+    Ready = 2,
+    Executed = 3,
+}
+impl OnLowCyclesHookStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "ON_LOW_CYCLES_HOOK_STATUS_UNSPECIFIED",
+            Self::ConditionNotSatisfied => "ON_LOW_CYCLES_HOOK_STATUS_CONDITION_NOT_SATISFIED",
+            Self::Ready => "ON_LOW_CYCLES_HOOK_STATUS_READY",
+            Self::Executed => "ON_LOW_CYCLES_HOOK_STATUS_EXECUTED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "ON_LOW_CYCLES_HOOK_STATUS_UNSPECIFIED" => Some(Self::Unspecified),
+            "ON_LOW_CYCLES_HOOK_STATUS_CONDITION_NOT_SATISFIED" => {
+                Some(Self::ConditionNotSatisfied)
+            }
+            "ON_LOW_CYCLES_HOOK_STATUS_READY" => Some(Self::Ready),
+            "ON_LOW_CYCLES_HOOK_STATUS_EXECUTED" => Some(Self::Executed),
+            _ => None,
+        }
+    }
+}
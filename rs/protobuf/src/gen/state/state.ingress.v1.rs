@@ -147,6 +147,7 @@ pub enum ErrorCode {
     CertifiedStateUnavailable = 208,
     CanisterInstallCodeRateLimited = 209,
     CanisterHeapDeltaRateLimited = 210,
+    CanisterCyclesBurnLimitExceeded = 211,
     /// 3xx -- `RejectCode::DestinationInvalid`
     CanisterNotFound = 301,
     CanisterSnapshotNotFound = 305,
@@ -217,6 +218,9 @@ impl ErrorCode {
             Self::CertifiedStateUnavailable => "ERROR_CODE_CERTIFIED_STATE_UNAVAILABLE",
             Self::CanisterInstallCodeRateLimited => "ERROR_CODE_CANISTER_INSTALL_CODE_RATE_LIMITED",
             Self::CanisterHeapDeltaRateLimited => "ERROR_CODE_CANISTER_HEAP_DELTA_RATE_LIMITED",
+            Self::CanisterCyclesBurnLimitExceeded => {
+                "ERROR_CODE_CANISTER_CYCLES_BURN_LIMIT_EXCEEDED"
+            }
             Self::CanisterNotFound => "ERROR_CODE_CANISTER_NOT_FOUND",
             Self::CanisterSnapshotNotFound => "ERROR_CODE_CANISTER_SNAPSHOT_NOT_FOUND",
             Self::InsufficientMemoryAllocation => "ERROR_CODE_INSUFFICIENT_MEMORY_ALLOCATION",
@@ -307,6 +311,9 @@ impl ErrorCode {
             "ERROR_CODE_CANISTER_HEAP_DELTA_RATE_LIMITED" => {
                 Some(Self::CanisterHeapDeltaRateLimited)
             }
+            "ERROR_CODE_CANISTER_CYCLES_BURN_LIMIT_EXCEEDED" => {
+                Some(Self::CanisterCyclesBurnLimitExceeded)
+            }
             "ERROR_CODE_CANISTER_NOT_FOUND" => Some(Self::CanisterNotFound),
             "ERROR_CODE_CANISTER_SNAPSHOT_NOT_FOUND" => Some(Self::CanisterSnapshotNotFound),
             "ERROR_CODE_INSUFFICIENT_MEMORY_ALLOCATION" => Some(Self::InsufficientMemoryAllocation),
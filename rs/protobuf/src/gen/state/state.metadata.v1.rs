@@ -335,6 +335,28 @@ pub struct StopCanisterCallTree {
     pub call: ::core::option::Option<StopCanisterCall>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScheduledManagementCall {
+    #[prost(message, optional, tag = "1")]
+    pub sender: ::core::option::Option<super::super::super::types::v1::PrincipalId>,
+    #[prost(message, optional, tag = "2")]
+    pub effective_canister_id: ::core::option::Option<super::super::super::types::v1::CanisterId>,
+    #[prost(string, tag = "3")]
+    pub method_name: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "4")]
+    pub method_payload: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "5")]
+    pub cycles: ::core::option::Option<super::super::queues::v1::Cycles>,
+    #[prost(message, optional, tag = "6")]
+    pub execute_at: ::core::option::Option<Time>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScheduledManagementCallTree {
+    #[prost(uint64, tag = "1")]
+    pub call_id: u64,
+    #[prost(message, optional, tag = "2")]
+    pub call: ::core::option::Option<ScheduledManagementCall>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RawRandContext {
     #[prost(message, optional, tag = "1")]
     pub request: ::core::option::Option<super::super::queues::v1::Request>,
@@ -373,6 +395,10 @@ pub struct SubnetCallContextManager {
     pub reshare_chain_key_contexts: ::prost::alloc::vec::Vec<ReshareChainKeyContextTree>,
     #[prost(message, repeated, tag = "18")]
     pub sign_with_threshold_contexts: ::prost::alloc::vec::Vec<SignWithThresholdContextTree>,
+    #[prost(uint64, tag = "19")]
+    pub next_scheduled_management_call_id: u64,
+    #[prost(message, repeated, tag = "20")]
+    pub scheduled_management_calls: ::prost::alloc::vec::Vec<ScheduledManagementCallTree>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SubnetMetrics {
@@ -28,6 +28,9 @@ pub mod v1 {
                 ErrorCodePublic::CanisterHeapDeltaRateLimited => {
                     ErrorCode::CanisterHeapDeltaRateLimited
                 }
+                ErrorCodePublic::CanisterCyclesBurnLimitExceeded => {
+                    ErrorCode::CanisterCyclesBurnLimitExceeded
+                }
                 ErrorCodePublic::CanisterNotFound => ErrorCode::CanisterNotFound,
                 ErrorCodePublic::CanisterSnapshotNotFound => ErrorCode::CanisterSnapshotNotFound,
                 ErrorCodePublic::CanisterSnapshotImmutable => ErrorCode::CanisterSnapshotImmutable,
@@ -140,6 +143,9 @@ pub mod v1 {
                 ErrorCode::CanisterHeapDeltaRateLimited => {
                     Ok(ErrorCodePublic::CanisterHeapDeltaRateLimited)
                 }
+                ErrorCode::CanisterCyclesBurnLimitExceeded => {
+                    Ok(ErrorCodePublic::CanisterCyclesBurnLimitExceeded)
+                }
                 ErrorCode::CanisterNotFound => Ok(ErrorCodePublic::CanisterNotFound),
                 ErrorCode::CanisterSnapshotNotFound => {
                     Ok(ErrorCodePublic::CanisterSnapshotNotFound)
@@ -5,7 +5,7 @@ use dfn_core::{
     over, over_async, stable,
 };
 use ic_base_types::{NodeId, PrincipalId};
-use ic_certified_map::{AsHashTree, HashTree};
+use ic_certified_map::HashTree;
 use ic_nervous_system_string::clamp_debug_len;
 use ic_nns_constants::{GOVERNANCE_CANISTER_ID, ROOT_CANISTER_ID};
 use ic_protobuf::registry::{
@@ -25,13 +25,17 @@ use ic_registry_transport::{
         HighCapacityRegistryGetChangesSinceResponse, HighCapacityRegistryGetValueResponse,
         HighCapacityRegistryValue, RegistryAtomicMutateResponse, RegistryError,
         RegistryGetChangesSinceRequest, RegistryGetLatestVersionResponse,
+        RegistryGetValuesByPrefixRequest, RegistryGetValuesByPrefixResponse, RegistryKeyValue,
     },
     serialize_atomic_mutate_response, serialize_get_changes_since_response,
     serialize_get_value_response,
 };
 use prost::Message;
 use registry_canister::{
-    certification::{current_version_tree, hash_tree_to_proto},
+    certification::{
+        hash_tree_to_proto, pruned_delta_tree, pruned_values_tree, registry_tree,
+        values_by_prefix_tree,
+    },
     common::LOG_PREFIX,
     init::RegistryCanisterInitPayload,
     mutations::{
@@ -270,7 +274,7 @@ fn get_certified_changes_since() {
     over(
         protobuf,
         |req: RegistryGetChangesSinceRequest| -> CertifiedResponse {
-            use ic_certified_map::{fork, labeled, labeled_hash};
+            use ic_certified_map::labeled;
             let latest_version = registry().latest_version();
             let from_version = EncodedVersion::from(req.version.saturating_add(1));
 
@@ -283,13 +287,14 @@ fn get_certified_changes_since() {
                 .changelog()
                 .value_range(from_version.as_ref(), to_version.as_ref());
 
-            let hash_tree = fork(
-                current_version_tree(latest_version),
+            let hash_tree = registry_tree(
+                registry(),
                 if req.version < latest_version {
                     labeled(b"delta", delta_tree)
                 } else {
-                    HashTree::Pruned(labeled_hash(b"delta", &registry().changelog().root_hash()))
+                    pruned_delta_tree(registry())
                 },
+                pruned_values_tree(registry()),
             );
 
             certified_response(hash_tree)
@@ -377,16 +382,85 @@ fn get_latest_version() {
 #[export_name = "canister_query get_certified_latest_version"]
 fn get_certified_latest_version() {
     over(protobuf, |_: Vec<u8>| -> CertifiedResponse {
-        use ic_certified_map::{fork, labeled_hash};
-        let latest_version = registry().latest_version();
-        let hash_tree = fork(
-            current_version_tree(latest_version),
-            HashTree::Pruned(labeled_hash(b"delta", &registry().changelog().root_hash())),
+        let hash_tree = registry_tree(
+            registry(),
+            pruned_delta_tree(registry()),
+            pruned_values_tree(registry()),
         );
         certified_response(hash_tree)
     });
 }
 
+#[export_name = "canister_query get_values_by_prefix"]
+fn get_values_by_prefix() {
+    over(
+        protobuf,
+        |req: RegistryGetValuesByPrefixRequest| -> RegistryGetValuesByPrefixResponse {
+            let registry = registry();
+            let latest_version = registry.latest_version();
+            let version = req.version.unwrap_or(latest_version);
+
+            let error = if version > latest_version {
+                Some(format!(
+                    "Version {} is greater than the latest version {}.",
+                    version, latest_version
+                ))
+            } else if req
+                .start_after
+                .as_ref()
+                .is_some_and(|start_after| !start_after.starts_with(&req.prefix))
+            {
+                Some("start_after does not start with the requested prefix.".to_string())
+            } else {
+                None
+            };
+            if let Some(reason) = error {
+                return RegistryGetValuesByPrefixResponse {
+                    error: Some(RegistryError {
+                        code: Code::MalformedMessage as i32,
+                        key: req.prefix,
+                        reason,
+                    }),
+                    version: latest_version,
+                    ..Default::default()
+                };
+            }
+
+            let page = registry.get_values_by_prefix(
+                &req.prefix,
+                version,
+                req.start_after.as_deref(),
+                MAX_REGISTRY_DELTAS_SIZE,
+            );
+            let hash_tree = registry_tree(
+                registry,
+                pruned_delta_tree(registry),
+                values_by_prefix_tree(
+                    registry,
+                    &req.prefix,
+                    req.start_after.as_deref(),
+                    page.last_key.as_deref(),
+                ),
+            );
+
+            RegistryGetValuesByPrefixResponse {
+                error: None,
+                version,
+                values: page
+                    .values
+                    .into_iter()
+                    .map(|(key, value)| RegistryKeyValue {
+                        key,
+                        value: Some(value),
+                    })
+                    .collect(),
+                next_start_after: page.last_key,
+                certified: Some(certified_response(hash_tree)),
+            }
+        },
+    )
+}
+
 #[export_name = "canister_update atomic_mutate"]
 fn atomic_mutate() {
     let caller = dfn_core::api::caller();
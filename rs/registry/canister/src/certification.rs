@@ -8,21 +8,30 @@
 //! |
 //! +-- current_version -- [ LEB128-encoded VERSION ]
 //! |
-//! `-- delta --+-- [ big-endian encoded 1u64    ] -- [ serialized protobuf ]
-//!             |
-//!             …
-//!             |
-//!             `-- [ big-endian encoded VERSION ] -- [ serialized protobuf ]
+//! +-- delta --+-- [ big-endian encoded 1u64    ] -- [ serialized protobuf ]
+//! |           |
+//! |           …
+//! |           |
+//! |           `-- [ big-endian encoded VERSION ] -- [ serialized protobuf ]
+//! |
+//! `-- values -+-- [ KEY ] --+-- [ big-endian encoded version ] -- [ SHA-256 of value ]
+//!             |             |
+//!             …             …
 //! ```
 //!
-//! where lebels under "delta" form contiguous range [1,VERSION].
+//! where lebels under "delta" form contiguous range [1,VERSION], and "values"
+//! holds, for every key, the hashes of all the values (encoded as
+//! `HighCapacityRegistryValue`) the key ever had, labeled by their version.
 
 #[cfg(target_arch = "wasm32")]
 use dfn_core::api::set_certified_data;
 #[cfg(all(not(target_arch = "wasm32"), test))]
 use ic_cdk::println;
-use ic_certified_map::{labeled, HashTree};
+use ic_certified_map::{fork, labeled, labeled_hash, AsHashTree, HashTree};
+use ic_crypto_sha2::Sha256;
 use ic_protobuf::messaging::xnet::v1 as pb;
+use ic_registry_transport::pb::v1::HighCapacityRegistryValue;
+use prost::Message;
 
 use crate::registry::{Registry, Version};
 
@@ -39,6 +48,72 @@ pub fn current_version_tree(v: Version) -> HashTree<'static> {
     )
 }
 
+/// Assembles the tree certified by the registry from the given `delta` and
+/// `values` subtrees, either of which may be pruned.
+pub fn registry_tree<'a>(
+    registry: &Registry,
+    delta: HashTree<'a>,
+    values: HashTree<'a>,
+) -> HashTree<'a> {
+    fork(
+        fork(current_version_tree(registry.latest_version()), delta),
+        values,
+    )
+}
+
+pub fn pruned_delta_tree(registry: &Registry) -> HashTree<'static> {
+    HashTree::Pruned(labeled_hash(b"delta", &registry.changelog().root_hash()))
+}
+
+pub fn pruned_values_tree(registry: &Registry) -> HashTree<'static> {
+    HashTree::Pruned(labeled_hash(b"values", &registry.key_index().root_hash()))
+}
+
+/// Returns the "values" subtree witnessing all the keys starting with
+/// `prefix` that are greater than `start_after` (if given) and not greater
+/// than `last_key`, or all of them if `last_key` is not given.
+pub fn values_by_prefix_tree<'a>(
+    registry: &'a Registry,
+    prefix: &[u8],
+    start_after: Option<&[u8]>,
+    last_key: Option<&[u8]>,
+) -> HashTree<'a> {
+    let first = match start_after {
+        // The smallest key greater than `start_after`.
+        Some(key) if key >= prefix => [key, &[0x00]].concat(),
+        _ => prefix.to_vec(),
+    };
+    let last = match last_key {
+        Some(key) => key.to_vec(),
+        // The witness of the greatest key starting with `prefix` includes the
+        // next key, which proves that no further key starts with `prefix`.
+        None => registry
+            .last_key_before(&first, prefix_successor(prefix).as_deref())
+            .map(|key| key.to_vec())
+            .unwrap_or_default(),
+    };
+    let last = last.max(first.clone());
+    labeled(b"values", registry.key_index().value_range(&first, &last))
+}
+
+/// Returns the smallest key that is greater than all the keys starting with
+/// `prefix`, or `None` if there is no such key.
+pub fn prefix_successor(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut successor = prefix.to_vec();
+    while let Some(byte) = successor.pop() {
+        if byte < u8::MAX {
+            successor.push(byte + 1);
+            return Some(successor);
+        }
+    }
+    None
+}
+
+/// Returns the hash under which `value` is recorded in the "values" subtree.
+pub fn value_hash(value: &HighCapacityRegistryValue) -> Vec<u8> {
+    Sha256::hash(&value.encode_to_vec()).to_vec()
+}
+
 /// Encodes a hash tree into the protobuf representation expected by
 /// the registry client.
 pub fn hash_tree_to_proto(tree: HashTree<'_>) -> pb::MixedHashTree {
@@ -67,12 +142,12 @@ pub fn hash_tree_to_proto(tree: HashTree<'_>) -> pb::MixedHashTree {
 #[cfg(target_arch = "wasm32")]
 /// Updates the certified data for the canister from the current registry state
 pub fn recertify_registry(registry: &Registry) {
-    use ic_certified_map::{fork_hash, labeled_hash, AsHashTree};
-
-    let root_hash = fork_hash(
-        &current_version_tree(registry.latest_version()).reconstruct(),
-        &labeled_hash(b"delta", &registry.changelog().root_hash()),
-    );
+    let root_hash = registry_tree(
+        registry,
+        pruned_delta_tree(registry),
+        pruned_values_tree(registry),
+    )
+    .reconstruct();
 
     set_certified_data(&root_hash);
 }
@@ -86,3 +161,119 @@ pub fn recertify_registry(_: &Registry) {
 pub fn recertify_registry(_: &Registry) {
     println!("recertify_registry called in test context");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_registry_transport::{delete, insert};
+    use std::collections::BTreeSet;
+
+    const KEYS: [&[u8]; 5] = [b"a", b"node_a", b"node_b", b"node_\xff\x01", b"subnet_a"];
+
+    fn registry_with_keys() -> Registry {
+        let mut registry = Registry::new();
+        registry.apply_mutations_for_test(KEYS.iter().map(|key| insert(key, "value")).collect());
+        registry.apply_mutations_for_test(vec![delete("node_b")]);
+        registry
+    }
+
+    /// Returns the keys whose values are revealed under the "values" label of
+    /// `tree`.
+    fn revealed_keys(tree: &HashTree<'_>) -> Vec<Vec<u8>> {
+        fn collect(tree: &HashTree<'_>, keys: &mut Vec<Vec<u8>>) {
+            match tree {
+                HashTree::Fork(children) => {
+                    collect(&children.0, keys);
+                    collect(&children.1, keys);
+                }
+                HashTree::Labeled(_, subtree) if matches!(**subtree, HashTree::Pruned(_)) => {}
+                HashTree::Labeled(key, _) => keys.push(key.to_vec()),
+                _ => {}
+            }
+        }
+        fn values(tree: &HashTree<'_>) -> Option<Vec<Vec<u8>>> {
+            match tree {
+                HashTree::Fork(children) => values(&children.0).or_else(|| values(&children.1)),
+                HashTree::Labeled(label, subtree) if label.to_vec() == b"values" => {
+                    let mut keys = vec![];
+                    collect(subtree, &mut keys);
+                    Some(keys)
+                }
+                _ => None,
+            }
+        }
+        values(tree).unwrap_or_default()
+    }
+
+    #[test]
+    fn values_by_prefix_tree_reconstructs_certified_data() {
+        let registry = registry_with_keys();
+        let certified_data = registry_tree(
+            &registry,
+            pruned_delta_tree(&registry),
+            pruned_values_tree(&registry),
+        )
+        .reconstruct();
+
+        for (prefix, start_after, last_key) in [
+            (&b"node_"[..], None, None),
+            (b"node_", None, Some(&b"node_a"[..])),
+            (b"node_", Some(&b"node_a"[..]), None),
+            (b"", None, None),
+            (b"missing", None, None),
+            (b"\xff", None, None),
+        ] {
+            let tree = registry_tree(
+                &registry,
+                pruned_delta_tree(&registry),
+                values_by_prefix_tree(&registry, prefix, start_after, last_key),
+            );
+            assert_eq!(tree.reconstruct(), certified_data);
+        }
+    }
+
+    #[test]
+    fn values_by_prefix_pages_reveal_all_keys_with_prefix() {
+        let registry = registry_with_keys();
+        let version = registry.latest_version();
+
+        let mut revealed = BTreeSet::new();
+        let mut start_after: Option<Vec<u8>> = None;
+        loop {
+            let page = registry.get_values_by_prefix(b"node_", version, start_after.as_deref(), 0);
+            let tree = values_by_prefix_tree(
+                &registry,
+                b"node_",
+                start_after.as_deref(),
+                page.last_key.as_deref(),
+            );
+            revealed.extend(
+                revealed_keys(&tree)
+                    .into_iter()
+                    .filter(|key| key.starts_with(b"node_")),
+            );
+            match page.last_key {
+                Some(last_key) => start_after = Some(last_key),
+                None => break,
+            }
+        }
+
+        // Deleted keys are revealed too, as are keys containing 0xFF bytes.
+        assert_eq!(
+            revealed,
+            BTreeSet::from([
+                b"node_a".to_vec(),
+                b"node_b".to_vec(),
+                b"node_\xff\x01".to_vec()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_prefix_successor() {
+        assert_eq!(prefix_successor(b"node_"), Some(b"node`".to_vec()));
+        assert_eq!(prefix_successor(b"a\xff\xff"), Some(b"b".to_vec()));
+        assert_eq!(prefix_successor(b"\xff"), None);
+        assert_eq!(prefix_successor(b""), None);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    certification::value_hash,
     common::LOG_PREFIX,
     pb::v1::{
         registry_stable_storage::Version as ReprVersion, ChangelogEntry, RegistryStableStorage,
@@ -23,6 +24,7 @@ use prost::Message;
 use std::{
     collections::{BTreeMap, VecDeque},
     fmt,
+    ops::Bound,
 };
 
 #[cfg(target_arch = "wasm32")]
@@ -35,6 +37,10 @@ use dfn_core::println;
 pub const MAX_REGISTRY_DELTAS_SIZE: usize =
     2 * MAX_INTER_CANISTER_PAYLOAD_IN_BYTES_U64 as usize / 3;
 
+/// The approximate number of bytes a single value of a key occupies in the
+/// witness returned with the values of the key.
+const KEY_INDEX_ENTRY_SIZE: usize = 64;
+
 pub type Version = u64;
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Default)]
 pub struct EncodedVersion([u8; std::mem::size_of::<Version>()]);
@@ -95,6 +101,23 @@ pub struct Registry {
     /// retained to ensure that hash trees stay the same even if the protobuf
     /// schema evolves.
     pub(crate) changelog: RbTree<EncodedVersion, Vec<u8>>,
+
+    /// For every key, the hashes of all the values it ever had, labeled by
+    /// their version.
+    ///
+    /// It is derived from the changelog and certified alongside it, which
+    /// allows proving the values of all keys in a range at any version.
+    pub(crate) key_index: RbTree<Vec<u8>, RbTree<EncodedVersion, Vec<u8>>>,
+}
+
+/// A page of the values of the keys starting with some prefix.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct ValuesByPrefixPage {
+    /// The keys of the page that existed at the requested version, with their
+    /// last value at or before that version (which may be a deletion).
+    pub values: Vec<(Vec<u8>, HighCapacityRegistryValue)>,
+    /// The last key covered by the page, if more keys start with the prefix.
+    pub last_key: Option<Vec<u8>>,
 }
 
 impl Registry {
@@ -163,6 +186,60 @@ impl Registry {
         Some(result)
     }
 
+    /// Returns the keys starting with `prefix` that are greater than
+    /// `start_after` (if given), in ascending order, together with their most
+    /// recent value that is not newer than `version`.
+    ///
+    /// Keys are added to the page until their values and histories exceed
+    /// `max_bytes`, but a page always covers at least one key.
+    pub fn get_values_by_prefix(
+        &self,
+        prefix: &[u8],
+        version: Version,
+        start_after: Option<&[u8]>,
+        max_bytes: usize,
+    ) -> ValuesByPrefixPage {
+        let lower_bound = match start_after {
+            Some(key) if key >= prefix => Bound::Excluded(key.to_vec()),
+            _ => Bound::Included(prefix.to_vec()),
+        };
+        let mut keys = self
+            .store
+            .range((lower_bound, Bound::Unbounded))
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .peekable();
+
+        let mut page = ValuesByPrefixPage::default();
+        let mut size = 0;
+        while let Some((key, values)) = keys.next() {
+            let value = values.iter().rev().find(|value| value.version <= version);
+            size += key.len() + values.len() * KEY_INDEX_ENTRY_SIZE;
+            size += value.map_or(0, |value| value.encoded_len());
+            if let Some(value) = value {
+                page.values.push((key.clone(), value.clone()));
+            }
+            if size > max_bytes && keys.peek().is_some() {
+                page.last_key = Some(key.clone());
+                break;
+            }
+        }
+        page
+    }
+
+    /// Returns the greatest key that is not smaller than `first` and smaller
+    /// than `end`, if given.
+    pub fn last_key_before(&self, first: &[u8], end: Option<&[u8]>) -> Option<&[u8]> {
+        let upper_bound = match end {
+            Some(end) if end <= first => return None,
+            Some(end) => Bound::Excluded(end.to_vec()),
+            None => Bound::Unbounded,
+        };
+        self.store
+            .range((Bound::Included(first.to_vec()), upper_bound))
+            .next_back()
+            .map(|(key, _)| key.as_slice())
+    }
+
     pub fn get_chunk(&self, request: GetChunkRequest) -> Result<Chunk, String> {
         let GetChunkRequest { content_sha256 } = request;
 
@@ -299,6 +376,7 @@ impl Registry {
                 timestamp_nanoseconds,
             };
 
+            self.key_index_insert(&key, &registry_value);
             self.store.entry(key).or_default().push_back(registry_value);
         }
 
@@ -415,6 +493,24 @@ impl Registry {
         &self.changelog
     }
 
+    pub fn key_index(&self) -> &RbTree<Vec<u8>, RbTree<EncodedVersion, Vec<u8>>> {
+        &self.key_index
+    }
+
+    /// Records the hash of a new value of `key` in the key index.
+    fn key_index_insert(&mut self, key: &[u8], value: &HighCapacityRegistryValue) {
+        let version = EncodedVersion::from(value.version);
+        let hash = value_hash(value);
+        if self.key_index.get(key).is_some() {
+            self.key_index
+                .modify(key, |values| values.insert(version, hash));
+        } else {
+            let mut values = RbTree::new();
+            values.insert(version, hash);
+            self.key_index.insert(key.to_vec(), values);
+        }
+    }
+
     /// Inserts a changelog entry at the given version, while enforcing the
     /// [`MAX_REGISTRY_DELTAS_SIZE`] limit.
     fn changelog_insert(&mut self, version: u64, req: HighCapacityRegistryAtomicMutateRequest) {
@@ -451,6 +547,7 @@ impl Registry {
     pub fn from_serializable_form(&mut self, stable_repr: RegistryStableStorage) {
         assert!(self.store.is_empty());
         assert!(self.changelog.is_empty());
+        assert!(self.key_index.is_empty());
         assert_eq!(self.version, 0);
 
         let repr_version = ReprVersion::try_from(stable_repr.version).unwrap_or_else(|_| {
@@ -634,6 +731,71 @@ mod tests {
         serialize_then_deserialize(registry);
     }
 
+    #[test]
+    fn test_get_values_by_prefix() {
+        let mut registry = Registry::new();
+        assert_empty!(apply_mutations_skip_invariant_checks(
+            &mut registry,
+            vec![
+                insert("node_a", "1"),
+                insert("node_b", "2"),
+                insert("node_c", "3"),
+                insert("subnet_a", "4"),
+            ]
+        ));
+        assert_empty!(apply_mutations_skip_invariant_checks(
+            &mut registry,
+            vec![update("node_a", "5"), delete("node_b")]
+        ));
+
+        let contents = |page: &ValuesByPrefixPage| {
+            page.values
+                .iter()
+                .map(|(key, value)| (key.clone(), value.content.clone()))
+                .collect::<Vec<_>>()
+        };
+        let value = |bytes: &str| {
+            Some(high_capacity_registry_value::Content::Value(
+                bytes.as_bytes().to_vec(),
+            ))
+        };
+
+        // At the latest version, deleted keys carry a deletion marker.
+        let page = registry.get_values_by_prefix(b"node_", 2, None, usize::MAX);
+        assert_eq!(
+            contents(&page),
+            vec![
+                (b"node_a".to_vec(), value("5")),
+                (b"node_b".to_vec(), DELETION_MARKER),
+                (b"node_c".to_vec(), value("3")),
+            ]
+        );
+        assert_eq!(page.last_key, None);
+
+        // Older versions are served too.
+        let page = registry.get_values_by_prefix(b"node_", 1, None, usize::MAX);
+        assert_eq!(
+            contents(&page),
+            vec![
+                (b"node_a".to_vec(), value("1")),
+                (b"node_b".to_vec(), value("2")),
+                (b"node_c".to_vec(), value("3")),
+            ]
+        );
+
+        // A zero byte limit yields pages of one key each.
+        let page = registry.get_values_by_prefix(b"node_", 2, None, 0);
+        assert_eq!(contents(&page), vec![(b"node_a".to_vec(), value("5"))]);
+        assert_eq!(page.last_key, Some(b"node_a".to_vec()));
+        let page = registry.get_values_by_prefix(b"node_", 2, Some(b"node_b"), 0);
+        assert_eq!(contents(&page), vec![(b"node_c".to_vec(), value("3"))]);
+        assert_eq!(page.last_key, None);
+
+        // Keys that did not exist yet at the requested version are skipped.
+        let page = registry.get_values_by_prefix(b"subnet_", 0, None, usize::MAX);
+        assert_eq!(contents(&page), vec![]);
+    }
+
     #[test]
     fn test_get_changes_since() {
         let mut registry = Registry::new();
@@ -1332,11 +1494,13 @@ mod tests {
                  inline Value (which is impossible, unless of course, bugs)."
             ),
         };
-        (*registry.store.entry(mutation.key).or_default()).push_back(HighCapacityRegistryValue {
+        let value = HighCapacityRegistryValue {
             version,
             content: Some(content),
             timestamp_nanoseconds: req.timestamp_nanoseconds,
-        });
+        };
+        registry.key_index_insert(&mutation.key, &value);
+        (*registry.store.entry(mutation.key).or_default()).push_back(value);
         registry.version = version;
 
         // Serialize.
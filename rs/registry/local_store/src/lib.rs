@@ -8,9 +8,21 @@ use ic_types::registry::RegistryDataProviderError;
 use ic_types::RegistryVersion;
 use prost::Message;
 use std::{
+    collections::BTreeMap,
+    fmt,
     io::{self},
     path::{Path, PathBuf},
 };
+
+/// Prefix of the name of the snapshot file into which
+/// [`LocalStoreImpl::compact`] folds old versions. The name ends with the
+/// hex-encoded latest folded version.
+const SNAPSHOT_FILE_PREFIX: &str = "snapshot_";
+
+/// The number of times a read is attempted while the store is being compacted
+/// concurrently, see [`LocalStoreImpl::get_changelog_since_version`].
+const MAX_READ_ATTEMPTS: usize = 10;
+
 pub trait LocalStore: LocalStoreWriter + LocalStoreReader {}
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
@@ -55,9 +67,44 @@ pub trait LocalStoreWriter: Send + Sync {
     fn clear(&self) -> io::Result<()>;
 }
 
+/// A discrepancy between the local store and a reference registry, as found
+/// by [`LocalStoreImpl::verify`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum VerificationError {
+    /// No delta is stored for a version below the latest stored version.
+    Missing(RegistryVersion),
+    /// The stored delta cannot be decoded.
+    Corrupted {
+        version: RegistryVersion,
+        reason: String,
+    },
+    /// The stored delta differs from the delta of the reference registry.
+    Mismatch(RegistryVersion),
+    /// The reference registry has no delta for a stored version.
+    NotInReference(RegistryVersion),
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(version) => write!(f, "Version {} is missing.", version),
+            Self::Corrupted { version, reason } => {
+                write!(f, "Version {} is corrupted: {}", version, reason)
+            }
+            Self::Mismatch(version) => {
+                write!(f, "Version {} differs from the reference.", version)
+            }
+            Self::NotInReference(version) => {
+                write!(f, "Version {} does not exist in the reference.", version)
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LocalStoreImpl {
-    /// Directory with one .pb file per registry version.
+    /// Directory with one .pb file per registry version, and possibly a
+    /// snapshot file holding all versions up to a given one.
     path: PathBuf,
 }
 
@@ -145,7 +192,16 @@ impl LocalStoreImpl {
         if version == 0 {
             panic!("Version must be > 0.")
         }
-        if version > 1 && !self.get_path(version - 1).exists() {
+        if version <= self.snapshot_version()? {
+            return Err(io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!(
+                    "Version {} is compacted and cannot be overwritten.",
+                    version
+                ),
+            ));
+        }
+        if version > 1 && !self.version_exists(version - 1)? {
             return Err(io::Error::new(
                 std::io::ErrorKind::NotFound,
                 format!("Version {} does not exist.", version - 1),
@@ -163,23 +219,281 @@ impl LocalStoreImpl {
     fn write_changelog_entry(&self, version: u64, pb: PbChangelogEntry) -> io::Result<()> {
         self.write_changelog_entry_(version, pb, |p, m| write_protobuf_using_tmp_file(p, &m))
     }
-}
 
-impl LocalStore for LocalStoreImpl {}
+    fn get_snapshot_path(&self, version: u64) -> PathBuf {
+        self.path
+            .join(format!("{}{:016x}.pb", SNAPSHOT_FILE_PREFIX, version))
+    }
 
-impl LocalStoreReader for LocalStoreImpl {
-    fn get_changelog_since_version(&self, version: RegistryVersion) -> io::Result<Changelog> {
-        let start = version.get() + 1;
+    /// Returns the latest version folded into a snapshot file, or 0 if the
+    /// store has not been compacted.
+    fn snapshot_version(&self) -> io::Result<u64> {
+        let entries = match std::fs::read_dir(self.path.as_path()) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e),
+        };
+        entries.try_fold(0, |version, de| {
+            let snapshot_version = de?.file_name().to_str().and_then(parse_snapshot_file_name);
+            Ok(version.max(snapshot_version.unwrap_or(0)))
+        })
+    }
+
+    /// Reads the changelog of versions 1 to `version` from the snapshot file
+    /// of `version`.
+    fn read_snapshot(&self, version: u64) -> io::Result<Changelog> {
+        let bytes = std::fs::read(self.get_snapshot_path(version))?;
+        let (_, changelog) = compact_delta_to_changelog(&bytes)?;
+        if changelog.len() as u64 != version {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Snapshot of version {} contains {} versions.",
+                    version,
+                    changelog.len()
+                ),
+            ));
+        }
+        Ok(changelog)
+    }
+
+    /// Reads the changelog since `version`, given that the latest compacted
+    /// version is `snapshot_version`.
+    fn read_changelog_since_version(
+        &self,
+        version: u64,
+        snapshot_version: u64,
+    ) -> io::Result<Changelog> {
+        let compacted = if version < snapshot_version {
+            self.read_snapshot(snapshot_version)?
+                .split_off(version as usize)
+        } else {
+            vec![]
+        };
+        let start = version.max(snapshot_version) + 1;
         (start..)
             .map(|i| self.get_path(i))
             .take_while(|p| p.exists())
-            .try_fold(vec![], |mut res, p| {
+            .try_fold(compacted, |mut res, p| {
                 res.push(changelog_entry_try_from_proto(Self::read_changelog_entry(
                     p,
                 )?)?);
                 Ok(res)
             })
     }
+
+    fn version_exists(&self, version: u64) -> io::Result<bool> {
+        Ok(version <= self.snapshot_version()? || self.get_path(version).exists())
+    }
+
+    /// Returns the paths of the files of all versions that are stored in their
+    /// own file, i.e. that have not been compacted.
+    fn version_files(&self) -> io::Result<BTreeMap<u64, PathBuf>> {
+        let mut files = BTreeMap::new();
+        let mut dirs = vec![(self.path.clone(), String::new())];
+        while let Some((dir, prefix)) = dirs.pop() {
+            let entries = match std::fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+            for de in entries {
+                let de = de?;
+                let Some(name) = de.file_name().to_str().map(String::from) else {
+                    continue;
+                };
+                let path = de.path();
+                if path.is_dir() {
+                    dirs.push((path, format!("{}{}", prefix, name)));
+                } else if let Some(name) = name.strip_suffix(".pb") {
+                    // The path of a version file is its hex-encoded version, see
+                    // `get_path`.
+                    let hex = format!("{}{}", prefix, name);
+                    if let (16, Ok(version)) = (hex.len(), u64::from_str_radix(&hex, 16)) {
+                        files.insert(version, path);
+                    }
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    /// Folds the deltas of all versions up to and including `version` into a
+    /// single snapshot file and removes their individual files. The folded
+    /// versions remain readable through the [`LocalStoreReader`].
+    ///
+    /// The snapshot is written atomically before any file is removed, so an
+    /// interrupted compaction leaves the store readable and can be repeated.
+    pub fn compact(&self, version: RegistryVersion) -> io::Result<()> {
+        let version = version.get();
+        let snapshot_version = self.snapshot_version()?;
+        if version > snapshot_version {
+            let mut changelog = match snapshot_version {
+                0 => vec![],
+                _ => self.read_snapshot(snapshot_version)?,
+            };
+            for v in (snapshot_version + 1)..=version {
+                let path = self.get_path(v);
+                if !path.exists() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("Version {} does not exist.", v),
+                    ));
+                }
+                changelog.push(changelog_entry_try_from_proto(Self::read_changelog_entry(
+                    path,
+                )?)?);
+            }
+            let delta = PbDelta {
+                registry_version: version,
+                changelog: changelog
+                    .into_iter()
+                    .map(changelog_entry_to_protobuf)
+                    .collect(),
+            };
+            write_protobuf_using_tmp_file(self.get_snapshot_path(version), &delta)?;
+        }
+        self.remove_compacted_files(version.max(snapshot_version))
+    }
+
+    /// Removes the files superseded by the snapshot of `version`, including
+    /// those left behind by an interrupted compaction.
+    fn remove_compacted_files(&self, version: u64) -> io::Result<()> {
+        for de in std::fs::read_dir(self.path.as_path())? {
+            let de = de?;
+            match de.file_name().to_str().and_then(parse_snapshot_file_name) {
+                Some(snapshot_version) if snapshot_version < version => {
+                    std::fs::remove_file(de.path())?
+                }
+                _ => {}
+            }
+        }
+        for (_, path) in self.version_files()?.range(..=version) {
+            std::fs::remove_file(path)?;
+            // Remove the directories left empty, up to the first non-empty one.
+            for dir in path
+                .ancestors()
+                .skip(1)
+                .take_while(|dir| *dir != self.path.as_path())
+            {
+                if std::fs::remove_dir(dir).is_err() {
+                    break;
+                }
+            }
+        }
+        sync_path(self.path.as_path())
+    }
+
+    /// Checks the deltas of all stored versions, including the compacted ones,
+    /// against the deltas served by `reference`, e.g. the certified changes
+    /// of the registry canister or another local store. Returns the
+    /// discrepancies found, which is empty if the store is intact.
+    pub fn verify(
+        &self,
+        reference: &dyn RegistryDataProvider,
+    ) -> io::Result<Vec<VerificationError>> {
+        let snapshot_version = self.snapshot_version()?;
+        let mut stored: BTreeMap<u64, Result<ChangelogEntry, String>> = BTreeMap::new();
+        if snapshot_version > 0 {
+            for (i, entry) in self
+                .read_snapshot(snapshot_version)?
+                .into_iter()
+                .enumerate()
+            {
+                stored.insert(i as u64 + 1, Ok(entry));
+            }
+        }
+        for (version, path) in self.version_files()?.split_off(&(snapshot_version + 1)) {
+            let entry = Self::read_changelog_entry(path)
+                .and_then(changelog_entry_try_from_proto)
+                .map_err(|e| e.to_string());
+            stored.insert(version, entry);
+        }
+
+        let latest_version = stored.keys().next_back().copied().unwrap_or(0);
+        let mut expected = get_reference_changelog(reference, latest_version)?;
+        let mut errors = vec![];
+        for v in 1..=latest_version {
+            let version = RegistryVersion::from(v);
+            match (stored.remove(&v), expected.remove(&v)) {
+                (None, _) => errors.push(VerificationError::Missing(version)),
+                (Some(Err(reason)), _) => {
+                    errors.push(VerificationError::Corrupted { version, reason })
+                }
+                (Some(Ok(_)), None) => errors.push(VerificationError::NotInReference(version)),
+                (Some(Ok(mut entry)), Some(mut expected_entry)) => {
+                    entry.sort();
+                    expected_entry.sort();
+                    if entry != expected_entry {
+                        errors.push(VerificationError::Mismatch(version));
+                    }
+                }
+            }
+        }
+        Ok(errors)
+    }
+}
+
+/// Returns the version of the snapshot file with the given name, if it is one.
+fn parse_snapshot_file_name(name: &str) -> Option<u64> {
+    let hex = name
+        .strip_prefix(SNAPSHOT_FILE_PREFIX)?
+        .strip_suffix(".pb")?;
+    u64::from_str_radix(hex, 16).ok()
+}
+
+/// Fetches the changelog of `reference` up to at least `latest_version`,
+/// indexed by version.
+fn get_reference_changelog(
+    reference: &dyn RegistryDataProvider,
+    latest_version: u64,
+) -> io::Result<BTreeMap<u64, ChangelogEntry>> {
+    let mut changelog: BTreeMap<u64, ChangelogEntry> = BTreeMap::new();
+    let mut version = RegistryVersion::from(0);
+    while version.get() < latest_version {
+        let records = reference
+            .get_updates_since(version)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        let Some(max_version) = records.iter().map(|r| r.version).max() else {
+            break;
+        };
+        for record in records {
+            changelog
+                .entry(record.version.get())
+                .or_default()
+                .push(KeyMutation {
+                    key: record.key,
+                    value: record.value,
+                });
+        }
+        version = max_version;
+    }
+    Ok(changelog)
+}
+
+impl LocalStore for LocalStoreImpl {}
+
+impl LocalStoreReader for LocalStoreImpl {
+    fn get_changelog_since_version(&self, version: RegistryVersion) -> io::Result<Changelog> {
+        // A concurrent `compact` writes its snapshot before removing the files
+        // it folded, so any file that disappears while reading has been
+        // folded into a newer snapshot. If the snapshot version changed, the
+        // read may be incomplete and is repeated from the new snapshot.
+        for _ in 0..MAX_READ_ATTEMPTS {
+            let snapshot_version = self.snapshot_version()?;
+            let result = self.read_changelog_since_version(version.get(), snapshot_version);
+            if self.snapshot_version()? == snapshot_version {
+                return result;
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::Interrupted,
+            format!(
+                "The local store was compacted during each of {} attempts to read it.",
+                MAX_READ_ATTEMPTS
+            ),
+        ))
+    }
 }
 
 impl LocalStoreWriter for LocalStoreImpl {
@@ -191,9 +505,17 @@ impl LocalStoreWriter for LocalStoreImpl {
 
     fn clear(&self) -> io::Result<()> {
         std::fs::read_dir(self.path.as_path())?.try_for_each(|de| {
-            let path = de?.path();
+            let de = de?;
+            let path = de.path();
             if path.is_dir() {
                 std::fs::remove_dir_all(path)
+            } else if de
+                .file_name()
+                .to_str()
+                .and_then(parse_snapshot_file_name)
+                .is_some()
+            {
+                std::fs::remove_file(path)
             } else {
                 Ok(())
             }
@@ -437,6 +759,160 @@ mod tests {
         }
     }
 
+    #[test]
+    fn compacted_versions_remain_readable() {
+        let tempdir = TempDir::new().unwrap();
+        let store = LocalStoreImpl::new(tempdir.path());
+        let mut rng = rand::thread_rng();
+
+        let mut changelog = get_random_changelog(300, &mut rng);
+        changelog.iter().enumerate().for_each(|(i, c)| {
+            store
+                .store(RegistryVersion::from((i + 1) as u64), c.clone())
+                .unwrap()
+        });
+
+        store.compact(RegistryVersion::from(100)).unwrap();
+        store.compact(RegistryVersion::from(250)).unwrap();
+        assert!(!store.get_path(1).exists());
+        assert!(!store.get_path(250).exists());
+        assert!(store.get_path(251).exists());
+        assert_eq!(store.snapshot_version().unwrap(), 250);
+
+        for i in (0..changelog.len()).step_by(10) {
+            let cl = store
+                .get_changelog_since_version(RegistryVersion::from(i as u64))
+                .unwrap();
+            assert_eq!(&changelog[i..], cl.as_slice());
+        }
+
+        // Compacted versions cannot be overwritten, but the store can be extended.
+        assert!(store
+            .store(RegistryVersion::from(250), changelog[0].clone())
+            .is_err());
+        let mut new_changelog = get_random_changelog(10, &mut rng);
+        new_changelog.iter().enumerate().for_each(|(i, c)| {
+            store
+                .store(RegistryVersion::from((i + 301) as u64), c.clone())
+                .unwrap()
+        });
+        changelog.append(&mut new_changelog);
+        store.compact(RegistryVersion::from(310)).unwrap();
+        assert_eq!(
+            store
+                .get_changelog_since_version(RegistryVersion::from(0))
+                .unwrap(),
+            changelog
+        );
+
+        store.clear().unwrap();
+        assert!(store
+            .get_changelog_since_version(RegistryVersion::from(0))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn reads_are_complete_during_concurrent_compaction() {
+        let tempdir = TempDir::new().unwrap();
+        let store = LocalStoreImpl::new(tempdir.path());
+        let mut rng = rand::thread_rng();
+        let changelog = get_random_changelog(500, &mut rng);
+        changelog.iter().enumerate().for_each(|(i, c)| {
+            store
+                .store(RegistryVersion::from((i + 1) as u64), c.clone())
+                .unwrap()
+        });
+
+        let compactor = {
+            let store = store.clone();
+            std::thread::spawn(move || {
+                // Fewer compactions than read attempts, so that no read can
+                // run out of attempts.
+                for version in (100..=500).step_by(100) {
+                    store.compact(RegistryVersion::from(version)).unwrap();
+                }
+            })
+        };
+        let mut num_reads = 0;
+        while !compactor.is_finished() || num_reads == 0 {
+            let since = (num_reads * 7) % changelog.len();
+            let cl = store
+                .get_changelog_since_version(RegistryVersion::from(since as u64))
+                .unwrap();
+            assert_eq!(&changelog[since..], cl.as_slice());
+            num_reads += 1;
+        }
+        compactor.join().unwrap();
+        assert_eq!(store.snapshot_version().unwrap(), 500);
+    }
+
+    #[test]
+    fn compacting_missing_version_fails() {
+        let tempdir = TempDir::new().unwrap();
+        let store = LocalStoreImpl::new(tempdir.path());
+        let mut rng = rand::thread_rng();
+        let changelog = get_random_changelog(5, &mut rng);
+        changelog.iter().enumerate().for_each(|(i, c)| {
+            store
+                .store(RegistryVersion::from((i + 1) as u64), c.clone())
+                .unwrap()
+        });
+
+        assert!(store.compact(RegistryVersion::from(6)).is_err());
+        assert_eq!(
+            store
+                .get_changelog_since_version(RegistryVersion::from(0))
+                .unwrap(),
+            changelog
+        );
+    }
+
+    #[test]
+    fn verification_detects_tampering_and_corruption() {
+        let mut rng = rand::thread_rng();
+        let changelog = get_random_changelog(50, &mut rng);
+        let reference_dir = TempDir::new().unwrap();
+        let reference =
+            LocalStoreImpl::from_changelog(changelog.clone(), reference_dir.path()).unwrap();
+        let tempdir = TempDir::new().unwrap();
+        let store = LocalStoreImpl::from_changelog(changelog.clone(), tempdir.path()).unwrap();
+        store.compact(RegistryVersion::from(20)).unwrap();
+
+        assert_eq!(store.verify(&reference).unwrap(), vec![]);
+
+        // Tamper with a stored delta.
+        let mut tampered = changelog[29].clone();
+        tampered[0].value = Some(b"tampered".to_vec());
+        let pb = changelog_entry_to_protobuf(tampered);
+        write_protobuf_using_tmp_file(store.get_path(30), &pb).unwrap();
+        // Corrupt another one.
+        std::fs::write(store.get_path(40), b"garbage").unwrap();
+
+        let errors = store.verify(&reference).unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0],
+            VerificationError::Mismatch(RegistryVersion::from(30))
+        );
+        assert!(matches!(
+            errors[1],
+            VerificationError::Corrupted { version, .. } if version == RegistryVersion::from(40)
+        ));
+
+        // Versions unknown to the reference are reported as well.
+        let short_reference_dir = TempDir::new().unwrap();
+        let short_reference =
+            LocalStoreImpl::from_changelog(changelog[..45].to_vec(), short_reference_dir.path())
+                .unwrap();
+        let errors = store.verify(&short_reference).unwrap();
+        assert!(
+            errors.contains(&VerificationError::NotInReference(RegistryVersion::from(
+                46
+            )))
+        );
+    }
+
     fn get_random_changelog(n: usize, rng: &mut ThreadRng) -> Changelog {
         // some pseudo random entries
         (0..n)
@@ -1,9 +1,13 @@
 use ic_certification::{verify_certified_data, CertificateValidationError};
-use ic_crypto_tree_hash::{LabeledTree, MixedHashTree};
+use ic_crypto_sha2::Sha256;
+use ic_crypto_tree_hash::{Label, LabeledTree, LookupStatus, MixedHashTree};
 use ic_interfaces_registry::RegistryRecord;
 use ic_registry_transport::{
-    dechunkify_mutation_value,
-    pb::v1::{CertifiedResponse, HighCapacityRegistryAtomicMutateRequest},
+    dechunkify_delta, dechunkify_mutation_value,
+    pb::v1::{
+        CertifiedResponse, HighCapacityRegistryAtomicMutateRequest, HighCapacityRegistryDelta,
+        RegistryGetValuesByPrefixResponse,
+    },
     GetChunk,
 };
 use ic_types::{
@@ -34,6 +38,10 @@ pub enum CertificationError {
     /// Parsing and signature verification was successful, but the list of
     /// deltas doesn't satisfy postconditions of the method.
     InvalidDeltas(String),
+    /// Parsing and signature verification was successful, but the values
+    /// don't match the hash tree, or the hash tree doesn't prove that no key
+    /// is missing.
+    InvalidValues(String),
     /// The hash tree in the response was not well-formed.
     MalformedHashTree(String),
     /// There are multiple delegation levels in the certification which is (currently) not allowed.
@@ -48,6 +56,11 @@ pub enum CertificationError {
     DechunkifyingFailed(ic_registry_transport::Error),
 }
 
+#[derive(Deserialize)]
+struct CertifiedVersion {
+    current_version: Leb128EncodedU64,
+}
+
 #[derive(Deserialize)]
 struct CertifiedPayload {
     current_version: Leb128EncodedU64,
@@ -189,7 +202,25 @@ pub(crate) async fn decode_certified_deltas(
         ))
     })?;
 
-    // Extract the hash trees from the canister response.
+    // Verify the authenticity of the root hash stored by the canister in the
+    // certified_data field, and get the time on the certificate.
+    let (mixed_hash_tree, time) =
+        verify_certified_response(canister_id, nns_pk, certified_response)?;
+
+    let (changes, current_version) =
+        decode_hash_tree(since_version, mixed_hash_tree, get_chunk).await?;
+
+    Ok((changes, current_version, time))
+}
+
+/// Extracts the hash tree from a certified response and verifies that its
+/// root hash is the certified data of the canister. Returns the hash tree
+/// together with the time on the certificate.
+fn verify_certified_response(
+    canister_id: &CanisterId,
+    nns_pk: &ThresholdSigPublicKey,
+    certified_response: CertifiedResponse,
+) -> Result<(MixedHashTree, Time), CertificationError> {
     let hash_tree = certified_response.hash_tree.ok_or_else(|| {
         CertificationError::MalformedHashTree(
             "certified response has an empty hash tree".to_string(),
@@ -201,9 +232,6 @@ pub(crate) async fn decode_certified_deltas(
             canister_id, err
         ))
     })?;
-
-    // Verify the authenticity of the root hash stored by the canister in the
-    // certified_data field, and get the time on the certificate.
     let time = verify_certified_data(
         &certified_response.certificate[..],
         canister_id,
@@ -211,11 +239,254 @@ pub(crate) async fn decode_certified_deltas(
         mixed_hash_tree.digest().as_bytes(),
     )
     .map_err(embed_certificate_error)?;
+    Ok((mixed_hash_tree, time))
+}
 
-    let (changes, current_version) =
-        decode_hash_tree(since_version, mixed_hash_tree, get_chunk).await?;
+/// The keys a page of a "get_values_by_prefix" response must cover: all the
+/// keys starting with `prefix` that are greater than `start_after` (if
+/// given) and not greater than `last_key` (if given).
+struct PrefixRange<'a> {
+    prefix: &'a [u8],
+    start_after: Option<&'a [u8]>,
+    last_key: Option<&'a [u8]>,
+}
 
-    Ok((changes, current_version, time))
+impl PrefixRange<'_> {
+    fn contains(&self, key: &[u8]) -> bool {
+        key.starts_with(self.prefix)
+            && self.start_after.is_none_or(|start_after| key > start_after)
+            && self.last_key.is_none_or(|last_key| key <= last_key)
+    }
+
+    /// Returns true if all keys smaller than `key` are below the range.
+    fn is_below(&self, key: &[u8]) -> bool {
+        key <= self.prefix
+            || self
+                .start_after
+                .is_some_and(|start_after| key <= start_after)
+    }
+
+    /// Returns true if all keys greater than `key` are above the range.
+    fn is_above(&self, key: &[u8]) -> bool {
+        match self.last_key {
+            Some(last_key) => key >= last_key,
+            None => key > self.prefix && !key.starts_with(self.prefix),
+        }
+    }
+}
+
+/// An element of the "values" subtree, in key order: either a key with the
+/// hash tree of its versions, or a pruned subtree hiding an unknown number of
+/// keys.
+enum ValuesTreeEntry<'a> {
+    Key(&'a Label, &'a MixedHashTree),
+    Pruned,
+}
+
+fn flatten_values_tree<'a>(
+    tree: &'a MixedHashTree,
+    entries: &mut Vec<ValuesTreeEntry<'a>>,
+) -> Result<(), CertificationError> {
+    match tree {
+        MixedHashTree::Empty => {}
+        MixedHashTree::Fork(children) => {
+            flatten_values_tree(&children.0, entries)?;
+            flatten_values_tree(&children.1, entries)?;
+        }
+        MixedHashTree::Labeled(label, subtree) => {
+            entries.push(ValuesTreeEntry::Key(label, subtree))
+        }
+        MixedHashTree::Pruned(_) => entries.push(ValuesTreeEntry::Pruned),
+        MixedHashTree::Leaf(_) => {
+            return Err(CertificationError::MalformedHashTree(
+                "unexpected leaf under the \"values\" label".to_string(),
+            ))
+        }
+    }
+    Ok(())
+}
+
+/// Returns the hash of the most recent value not newer than `version` from
+/// the hash tree of the versions of a key, if any.
+fn value_hash_at(
+    key: &[u8],
+    versions: &MixedHashTree,
+    version: u64,
+) -> Result<Option<(u64, Vec<u8>)>, CertificationError> {
+    let malformed = || {
+        CertificationError::InvalidValues(format!(
+            "the versions of key {:?} are not fully revealed",
+            String::from_utf8_lossy(key)
+        ))
+    };
+    let mut entries = vec![];
+    flatten_values_tree(versions, &mut entries)?;
+    let mut result = None;
+    for entry in entries {
+        let ValuesTreeEntry::Key(label, MixedHashTree::Leaf(hash)) = entry else {
+            return Err(malformed());
+        };
+        let value_version =
+            u64::from_be_bytes(label.as_bytes().try_into().map_err(|_| malformed())?);
+        if value_version <= version {
+            result = Some((value_version, hash.clone()));
+        }
+    }
+    Ok(result)
+}
+
+/// Parses a page of a response of the "get_values_by_prefix" registry method,
+/// requested with the given `prefix` and `start_after`, validates its
+/// integrity and authenticity, and checks that the hash tree proves that no
+/// key of the range covered by the page is missing.
+///
+/// Returns
+///   * The records of the keys of the page, at the version of the response.
+///     Keys deleted at that version have no value.
+///   * The key to pass as `start_after` to retrieve the next page, if any.
+///   * The time when the response was certified.
+pub(crate) async fn decode_certified_values_by_prefix(
+    prefix: &[u8],
+    start_after: Option<&[u8]>,
+    canister_id: &CanisterId,
+    nns_pk: &ThresholdSigPublicKey,
+    response: RegistryGetValuesByPrefixResponse,
+    get_chunk: &(impl GetChunk + Sync),
+) -> Result<(Vec<RegistryRecord>, Option<Vec<u8>>, Time), CertificationError> {
+    let RegistryGetValuesByPrefixResponse {
+        error: _,
+        version,
+        values,
+        next_start_after,
+        certified,
+    } = response;
+    let certified = certified.ok_or_else(|| {
+        CertificationError::MalformedHashTree("response is not certified".to_string())
+    })?;
+    let (hash_tree, time) = verify_certified_response(canister_id, nns_pk, certified)?;
+
+    let labeled_tree = LabeledTree::<Vec<u8>>::try_from(hash_tree.clone()).map_err(|err| {
+        CertificationError::MalformedHashTree(format!(
+            "failed to convert hash tree to labeled tree: {:?}",
+            err
+        ))
+    })?;
+    let current_version =
+        CertifiedVersion::deserialize(LabeledTreeDeserializer::new(&labeled_tree))
+            .map_err(|err| {
+                CertificationError::DeserError(format!(
+                    "failed to unpack the current version from the labeled tree: {}",
+                    err
+                ))
+            })?
+            .current_version
+            .0;
+    if version > current_version {
+        return Err(CertificationError::InvalidValues(format!(
+            "version {} is newer than the current version {}",
+            version, current_version
+        )));
+    }
+
+    // Every page must make progress, so that clients retrieving all pages
+    // terminate.
+    if let (Some(start_after), Some(next_start_after)) = (start_after, &next_start_after) {
+        if next_start_after.as_slice() <= start_after {
+            return Err(CertificationError::InvalidValues(format!(
+                "next_start_after {:?} does not follow start_after {:?}",
+                String::from_utf8_lossy(next_start_after),
+                String::from_utf8_lossy(start_after)
+            )));
+        }
+    }
+
+    let range = PrefixRange {
+        prefix,
+        start_after,
+        last_key: next_start_after.as_deref(),
+    };
+    let LookupStatus::Found(values_tree) = hash_tree.lookup(&[b"values"]) else {
+        return Err(CertificationError::MalformedHashTree(
+            "hash tree has no \"values\" label".to_string(),
+        ));
+    };
+    let mut entries = vec![];
+    flatten_values_tree(values_tree, &mut entries)?;
+
+    // Every pruned subtree must be next to a key outside of the range, on the
+    // side of the range, as it might otherwise hide keys of the range.
+    let mut expected = vec![];
+    for (index, entry) in entries.iter().enumerate() {
+        match entry {
+            ValuesTreeEntry::Pruned => {
+                let previous = entries[..index].iter().rev().find_map(|entry| match entry {
+                    ValuesTreeEntry::Key(label, _) => Some(label.as_bytes()),
+                    ValuesTreeEntry::Pruned => None,
+                });
+                let next = entries[index + 1..].iter().find_map(|entry| match entry {
+                    ValuesTreeEntry::Key(label, _) => Some(label.as_bytes()),
+                    ValuesTreeEntry::Pruned => None,
+                });
+                if !next.is_some_and(|key| range.is_below(key))
+                    && !previous.is_some_and(|key| range.is_above(key))
+                {
+                    return Err(CertificationError::InvalidValues(format!(
+                        "the hash tree may hide keys between {:?} and {:?}",
+                        previous.map(String::from_utf8_lossy),
+                        next.map(String::from_utf8_lossy),
+                    )));
+                }
+            }
+            ValuesTreeEntry::Key(label, versions) if range.contains(label.as_bytes()) => {
+                let key = label.as_bytes();
+                if let Some(value) = value_hash_at(key, versions, version)? {
+                    expected.push((key, value));
+                }
+            }
+            ValuesTreeEntry::Key(..) => {}
+        }
+    }
+
+    // The page must contain exactly the keys of the range that existed at the
+    // requested version, with their certified values.
+    if values.len() != expected.len() {
+        return Err(CertificationError::InvalidValues(format!(
+            "expected {} keys, got {}",
+            expected.len(),
+            values.len()
+        )));
+    }
+    let mut records = vec![];
+    for (key_value, (key, (value_version, hash))) in values.into_iter().zip(expected) {
+        let value = key_value.value.unwrap_or_default();
+        if key_value.key != key
+            || value.version != value_version
+            || Sha256::hash(&value.encode_to_vec()).as_slice() != hash.as_slice()
+        {
+            return Err(CertificationError::InvalidValues(format!(
+                "the value of key {:?} does not match the hash tree",
+                String::from_utf8_lossy(key)
+            )));
+        }
+        let delta = dechunkify_delta(
+            HighCapacityRegistryDelta {
+                key: key_value.key,
+                values: vec![value],
+            },
+            get_chunk,
+        )
+        .await
+        .map_err(CertificationError::DechunkifyingFailed)?;
+        for value in delta.values {
+            records.push(RegistryRecord {
+                key: String::from_utf8_lossy(&delta.key).to_string(),
+                value: (!value.deletion_marker).then_some(value.value),
+                version: RegistryVersion::from(value.version),
+            });
+        }
+    }
+
+    Ok((records, next_start_after, time))
 }
 
 /// An auxiliary type that instructs serde to deserialize blob as a protobuf
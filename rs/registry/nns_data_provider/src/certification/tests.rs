@@ -10,9 +10,10 @@ use ic_interfaces_registry::RegistryRecord;
 use ic_registry_transport::{
     delete,
     pb::v1::{
-        high_capacity_registry_mutation, registry_mutation, CertifiedResponse,
-        HighCapacityRegistryMutation, LargeValueChunkKeys, RegistryAtomicMutateRequest,
-        RegistryMutation,
+        high_capacity_registry_mutation, high_capacity_registry_value, registry_mutation,
+        CertifiedResponse, HighCapacityRegistryMutation, HighCapacityRegistryValue,
+        LargeValueChunkKeys, RegistryAtomicMutateRequest, RegistryGetValuesByPrefixResponse,
+        RegistryKeyValue, RegistryMutation,
     },
     upsert, MockGetChunk,
 };
@@ -461,3 +462,239 @@ fn test_evil_chunked() {
         _ => panic!("{:?}", result),
     }
 }
+
+/// The values of the keys `a`, `node_a`, `node_b`, `node_c` and `subnet_a`,
+/// where `node_b` is deleted at version 2.
+fn registry_values() -> BTreeMap<Vec<u8>, Vec<HighCapacityRegistryValue>> {
+    let value =
+        |version: u64, content: high_capacity_registry_value::Content| HighCapacityRegistryValue {
+            version,
+            content: Some(content),
+            timestamp_nanoseconds: 0,
+        };
+    let set = |version: u64, v: &str| {
+        value(
+            version,
+            high_capacity_registry_value::Content::Value(v.as_bytes().to_vec()),
+        )
+    };
+    let mut values = BTreeMap::new();
+    for key in ["a", "node_a", "node_b", "node_c", "subnet_a"] {
+        values.insert(key.as_bytes().to_vec(), vec![set(1, key)]);
+    }
+    values.get_mut(&b"node_b"[..]).unwrap().push(value(
+        2,
+        high_capacity_registry_value::Content::DeletionMarker(true),
+    ));
+    values
+}
+
+/// Certifies the given values under the "values" label, revealing the
+/// versions of the keys for which `reveal` returns true, and returns a
+/// response for a page with the given values at version 2.
+fn make_certified_values(
+    values: &BTreeMap<Vec<u8>, Vec<HighCapacityRegistryValue>>,
+    reveal: impl Fn(&[u8]) -> bool,
+    page: Vec<RegistryKeyValue>,
+    next_start_after: Option<&[u8]>,
+) -> (
+    CanisterId,
+    ThresholdSigPublicKey,
+    RegistryGetValuesByPrefixResponse,
+) {
+    let cid = CanisterId::from_u64(1);
+    let mut encoded_version = vec![];
+    leb128::write::unsigned(&mut encoded_version, 2).unwrap();
+
+    let mut b = HashTreeBuilderImpl::new();
+    let mut revealed = FlatMap::new();
+    b.start_subtree();
+    b.new_edge(Label::from("current_version"));
+    b.start_leaf();
+    b.write_leaf(&encoded_version[..]);
+    b.finish_leaf();
+    b.new_edge(Label::from("values"));
+    b.start_subtree();
+    for (key, key_values) in values {
+        b.new_edge(Label::from(key.clone()));
+        b.start_subtree();
+        let mut versions = FlatMap::new();
+        for value in key_values {
+            let label = Label::from(value.version.to_be_bytes());
+            let hash = Sha256::hash(&value.encode_to_vec()).to_vec();
+            b.new_edge(label.clone());
+            b.start_leaf();
+            b.write_leaf(&hash[..]);
+            b.finish_leaf();
+            versions.try_append(label, LabeledTree::Leaf(hash)).unwrap();
+        }
+        b.finish_subtree();
+        if reveal(key) {
+            revealed
+                .try_append(Label::from(key.clone()), LabeledTree::SubTree(versions))
+                .unwrap();
+        }
+    }
+    b.finish_subtree();
+    b.finish_subtree();
+
+    let witness_gen = b.witness_generator().unwrap();
+    let digest = witness_gen.hash_tree().digest().clone();
+    let data_tree = LabeledTree::SubTree(flatmap!(
+        Label::from("current_version") => LabeledTree::Leaf(encoded_version),
+        Label::from("values") => LabeledTree::SubTree(revealed),
+    ));
+    let mixed_hash_tree = witness_gen.mixed_hash_tree(&data_tree).unwrap();
+
+    let (_, pk, cbor) = CertificateBuilder::new(CertificateData::CanisterData {
+        canister_id: cid,
+        certified_data: digest,
+    })
+    .build();
+
+    let response = RegistryGetValuesByPrefixResponse {
+        error: None,
+        version: 2,
+        values: page,
+        next_start_after: next_start_after.map(<[u8]>::to_vec),
+        certified: Some(CertifiedResponse {
+            hash_tree: Some(mixed_hash_tree.into()),
+            certificate: cbor,
+        }),
+    };
+    (cid, pk, response)
+}
+
+/// Returns the latest values of the given keys as a page of a response.
+fn page_of(
+    values: &BTreeMap<Vec<u8>, Vec<HighCapacityRegistryValue>>,
+    keys: &[&str],
+) -> Vec<RegistryKeyValue> {
+    keys.iter()
+        .map(|key| RegistryKeyValue {
+            key: key.as_bytes().to_vec(),
+            value: values[key.as_bytes()].last().cloned(),
+        })
+        .collect()
+}
+
+fn decode_certified_values_by_prefix_no_chunks(
+    start_after: Option<&[u8]>,
+    (cid, pk, response): (
+        CanisterId,
+        ThresholdSigPublicKey,
+        RegistryGetValuesByPrefixResponse,
+    ),
+) -> Result<(Vec<RegistryRecord>, Option<Vec<u8>>, Time), CertificationError> {
+    decode_certified_values_by_prefix(
+        b"node_",
+        start_after,
+        &cid,
+        &pk,
+        response,
+        &MockGetChunk::new(),
+    )
+    .now_or_never()
+    .unwrap()
+}
+
+#[test]
+fn test_decode_values_by_prefix() {
+    let values = registry_values();
+    let response = make_certified_values(
+        &values,
+        |_| true,
+        page_of(&values, &["node_a", "node_b", "node_c"]),
+        None,
+    );
+
+    assert_eq!(
+        decode_certified_values_by_prefix_no_chunks(None, response).unwrap(),
+        (
+            vec![
+                set_key(1, "node_a", "node_a"),
+                rem_key(2, "node_b"),
+                set_key(1, "node_c", "node_c"),
+            ],
+            None,
+            Time::from_nanos_since_unix_epoch(REPLICA_TIME),
+        )
+    );
+}
+
+#[test]
+fn test_decode_values_by_prefix_page() {
+    let values = registry_values();
+    // The first page only covers `node_a`, so the other keys may be pruned.
+    let response = make_certified_values(
+        &values,
+        |key| key <= &b"node_b"[..],
+        page_of(&values, &["node_a"]),
+        Some(b"node_a"),
+    );
+    let (records, next_start_after, _) =
+        decode_certified_values_by_prefix_no_chunks(None, response).unwrap();
+    assert_eq!(records, vec![set_key(1, "node_a", "node_a")]);
+    assert_eq!(next_start_after, Some(b"node_a".to_vec()));
+
+    let response = make_certified_values(
+        &values,
+        |key| key >= &b"node_a"[..],
+        page_of(&values, &["node_b", "node_c"]),
+        None,
+    );
+    let (records, next_start_after, _) =
+        decode_certified_values_by_prefix_no_chunks(Some(b"node_a"), response).unwrap();
+    assert_eq!(
+        records,
+        vec![rem_key(2, "node_b"), set_key(1, "node_c", "node_c")]
+    );
+    assert_eq!(next_start_after, None);
+}
+
+#[test]
+fn test_decode_values_by_prefix_rejects_hidden_key() {
+    let values = registry_values();
+    let response = make_certified_values(
+        &values,
+        |key| key != b"node_b",
+        page_of(&values, &["node_a", "node_c"]),
+        None,
+    );
+
+    match decode_certified_values_by_prefix_no_chunks(None, response) {
+        Err(CertificationError::InvalidValues(_)) => (),
+        other => panic!("Expected InvalidValues error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_decode_values_by_prefix_rejects_omitted_key() {
+    let values = registry_values();
+    let response = make_certified_values(
+        &values,
+        |_| true,
+        page_of(&values, &["node_a", "node_b"]),
+        None,
+    );
+
+    match decode_certified_values_by_prefix_no_chunks(None, response) {
+        Err(CertificationError::InvalidValues(_)) => (),
+        other => panic!("Expected InvalidValues error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_decode_values_by_prefix_rejects_tampered_value() {
+    let values = registry_values();
+    let mut page = page_of(&values, &["node_a", "node_b", "node_c"]);
+    page[0].value.as_mut().unwrap().content = Some(high_capacity_registry_value::Content::Value(
+        b"tampered".to_vec(),
+    ));
+    let response = make_certified_values(&values, |_| true, page, None);
+
+    match decode_certified_values_by_prefix_no_chunks(None, response) {
+        Err(CertificationError::InvalidValues(_)) => (),
+        other => panic!("Expected InvalidValues error, got {:?}", other),
+    }
+}
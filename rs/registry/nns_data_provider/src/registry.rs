@@ -11,7 +11,10 @@ use ic_registry_canister_api::{Chunk, GetChunkRequest};
 use ic_registry_transport::{
     dechunkify_delta, dechunkify_get_value_response_content, deserialize_atomic_mutate_response,
     deserialize_get_changes_since_response, deserialize_get_value_response,
-    pb::v1::{Precondition, RegistryDelta, RegistryGetLatestVersionResponse, RegistryMutation},
+    pb::v1::{
+        Precondition, RegistryDelta, RegistryGetLatestVersionResponse,
+        RegistryGetValuesByPrefixRequest, RegistryGetValuesByPrefixResponse, RegistryMutation,
+    },
     serialize_atomic_mutate_request, serialize_get_changes_since_request,
     serialize_get_value_request, Error, GetChunk,
 };
@@ -206,6 +209,89 @@ impl RegistryCanister {
         .map_err(|err| Error::UnknownError(format!("{:?}", err)))
     }
 
+    /// Queries the registry for all the keys starting with `prefix` together
+    /// with their values at `version` (or the latest version if `None`),
+    /// using a certified endpoint. The keys are retrieved page by page, and
+    /// every page is checked to be certified and complete.
+    ///
+    /// Returns the registry records of the keys that exist at the version,
+    /// sorted by key; the version at which they were read; and the time when
+    /// the oldest page was certified.
+    pub async fn get_certified_values_by_prefix(
+        &self,
+        prefix: Vec<u8>,
+        version: Option<u64>,
+        nns_public_key: &ThresholdSigPublicKey,
+    ) -> Result<(Vec<RegistryRecord>, RegistryVersion, Time), Error> {
+        let mut records = vec![];
+        let mut version = version;
+        let mut start_after = None;
+        let mut time = None;
+        loop {
+            let request = RegistryGetValuesByPrefixRequest {
+                prefix: prefix.clone(),
+                version,
+                start_after: start_after.clone(),
+            };
+            let agent = self.choose_random_agent();
+            let response = agent
+                .execute_query(
+                    &self.canister_id,
+                    "get_values_by_prefix",
+                    request.encode_to_vec(),
+                )
+                .await
+                .map_err(|err| {
+                    Error::UnknownError(format!(
+                        "Failed to query get_values_by_prefix on canister {}: {}",
+                        self.canister_id, err,
+                    ))
+                })?
+                .ok_or_else(|| {
+                    Error::UnknownError(format!(
+                        "No response was received when queried get_values_by_prefix on {}",
+                        self.canister_id,
+                    ))
+                })?;
+            let response = RegistryGetValuesByPrefixResponse::decode(&response[..])
+                .map_err(|err| Error::MalformedMessage(err.to_string()))?;
+            if let Some(error) = response.error {
+                return Err(Error::UnknownError(format!(
+                    "get_values_by_prefix failed: {}",
+                    error.reason
+                )));
+            }
+            // All pages are read at the version of the first one.
+            version = Some(response.version);
+
+            let (page, next_start_after, page_time) =
+                crate::certification::decode_certified_values_by_prefix(
+                    &prefix,
+                    start_after.as_deref(),
+                    &self.canister_id,
+                    nns_public_key,
+                    response,
+                    &AgentBasedGetChunk {
+                        registry_canister_id: self.canister_id,
+                        agent,
+                    },
+                )
+                .await
+                .map_err(|err| Error::UnknownError(format!("{:?}", err)))?;
+            records.extend(page.into_iter().filter(|record| record.value.is_some()));
+            time = Some(time.map_or(page_time, |time: Time| time.min(page_time)));
+            match next_start_after {
+                Some(key) => start_after = Some(key),
+                None => break,
+            }
+        }
+        Ok((
+            records,
+            RegistryVersion::from(version.unwrap_or_default()),
+            time.expect("At least one page was retrieved"),
+        ))
+    }
+
     pub async fn get_latest_version(&self) -> Result<u64, Error> {
         let agent = self.choose_random_agent();
         match agent
@@ -84,6 +84,37 @@ pub enum CommandArg {
         /// Path to the local store (may not be specified together with --url).
         snapshot_file: PathBuf,
     },
+    Compact {
+        /// The latest registry version to fold into the snapshot file of the
+        /// local store. (default: latest available version.)
+        #[clap(short, long, allow_hyphen_values = true)]
+        version: Option<i64>,
+
+        /// Path to the local store.
+        local_store_path: PathBuf,
+    },
+    Verify {
+        /// Path to the local store.
+        local_store_path: PathBuf,
+
+        /// Path to the local store that serves as the reference.
+        reference_local_store_path: PathBuf,
+    },
+    CanisterVerify {
+        /// Url to a node hosting the registry canister.
+        #[clap(long)]
+        url: Url,
+
+        /// Path to the threshold public key of the root subnet (a.k.a. NNS
+        /// public key). The stored deltas are checked against the changes of
+        /// the registry canister certified with this key, and verification
+        /// fails if they cannot be certified.
+        #[clap(long)]
+        nns_public_key: PathBuf,
+
+        /// Path to the local store.
+        local_store_path: PathBuf,
+    },
     CanisterSnapshot {
         /// Url to a node hosting the registry canister (may not be specified
         /// together with --local-store).
@@ -192,6 +223,31 @@ impl CliArgs {
                     amend,
                 }
             }
+            CommandArg::Compact {
+                version,
+                local_store_path,
+            } => Command::Compact {
+                local_store_path: Self::is_dir(local_store_path)?,
+                version: version.into(),
+            },
+            CommandArg::Verify {
+                local_store_path,
+                reference_local_store_path,
+            } => Command::Verify {
+                local_store_path: Self::is_dir(local_store_path)?,
+                reference: SourceSpec::LocalStore(Self::is_dir(reference_local_store_path)?),
+            },
+            CommandArg::CanisterVerify {
+                url,
+                nns_public_key,
+                local_store_path,
+            } => {
+                let nns_key_material = get_key_material(Some(nns_public_key))?;
+                Command::Verify {
+                    local_store_path: Self::is_dir(local_store_path)?,
+                    reference: SourceSpec::Canister(url, nns_key_material),
+                }
+            }
             CommandArg::CanisterSnapshot {
                 url,
                 nns_public_key,
@@ -319,6 +375,14 @@ pub enum Command {
         snapshot: Value,
         amend: bool,
     },
+    Compact {
+        local_store_path: PathBuf,
+        version: VersionSpec,
+    },
+    Verify {
+        local_store_path: PathBuf,
+        reference: SourceSpec,
+    },
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
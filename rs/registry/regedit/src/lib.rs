@@ -8,7 +8,7 @@ mod snapshot;
 mod source;
 mod tests;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use args::{universal_projection, Command, RegistrySpec, SourceSpec, VersionSpec};
 use ic_base_types::RegistryVersion;
 use ic_registry_local_store::{
@@ -132,6 +132,45 @@ pub fn execute_command(cmd: Command) -> Result<Value> {
             local_store.store(v, changelog_entry)?;
             diff.0
         }
+        Command::Compact {
+            local_store_path,
+            version,
+        } => {
+            let (_, latest_version) =
+                source::get_changelog(SourceSpec::LocalStore(local_store_path.clone()))?;
+            let version = match version {
+                VersionSpec::RelativeToLatest(r) => latest_version
+                    .get()
+                    .checked_sub(r)
+                    .ok_or_else(|| anyhow!("relative version exceeds latest version"))?,
+                VersionSpec::Absolute(v) => v.get(),
+            };
+            LocalStoreImpl::new(&local_store_path).compact(RegistryVersion::from(version))?;
+            Value::String(format!(
+                "Successfully compacted versions up to {} of local store: {}",
+                version,
+                local_store_path.display()
+            ))
+        }
+        Command::Verify {
+            local_store_path,
+            reference,
+        } => {
+            let reference = source::source_to_dataprovider(reference);
+            let errors = LocalStoreImpl::new(&local_store_path).verify(reference.as_ref())?;
+            if !errors.is_empty() {
+                let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+                bail!(
+                    "local store {} does not match the reference:\n{}",
+                    local_store_path.display(),
+                    errors.join("\n")
+                );
+            }
+            Value::String(format!(
+                "All versions of local store {} match the reference.",
+                local_store_path.display()
+            ))
+        }
     };
     Ok(res)
 }
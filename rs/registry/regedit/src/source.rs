@@ -31,7 +31,7 @@ pub fn get_changelog(source_spec: SourceSpec) -> Result<Changelog> {
     Ok((records, version))
 }
 
-fn source_to_dataprovider(source_spec: SourceSpec) -> Arc<dyn RegistryDataProvider> {
+pub(crate) fn source_to_dataprovider(source_spec: SourceSpec) -> Arc<dyn RegistryDataProvider> {
     match source_spec {
         SourceSpec::LocalStore(path) => Arc::new(LocalStoreImpl::new(path)) as Arc<_>,
         SourceSpec::Canister(url, nns_pk) => {
@@ -5,12 +5,14 @@ use crate::{
     execute_command, normalization,
     snapshot::SPECIAL_FIELD_PREFIX,
 };
+use ic_base_types::RegistryVersion;
 use ic_prep_lib::{
     internet_computer::{IcConfig, TopologyConfig},
     node::{NodeConfiguration, NodeIndex},
     prep_state_directory::IcPrepStateDir,
     subnet_configuration::{SubnetConfig, SubnetRunningState},
 };
+use ic_registry_local_store::{LocalStoreImpl, LocalStoreReader};
 use ic_registry_provisional_whitelist::ProvisionalWhitelist;
 use ic_registry_subnet_type::SubnetType;
 use ic_types::ReplicaVersion;
@@ -111,6 +113,40 @@ fn adding_deleting_values_shows_up_in_diff() {
     assert_eq!(expected_snapshot.0, final_snapshot);
 }
 
+#[test]
+fn compacted_local_store_matches_original() {
+    let (_guard, ic_prep_dir) = run_ic_prep();
+    let original_path = ic_prep_dir.registry_local_store_path();
+    let changelog = LocalStoreImpl::new(&original_path)
+        .get_changelog_since_version(RegistryVersion::from(0))
+        .unwrap();
+    let compacted_dir = TempDir::new().unwrap();
+    LocalStoreImpl::from_changelog(changelog, compacted_dir.path()).unwrap();
+
+    execute_command(Command::Compact {
+        local_store_path: compacted_dir.path().to_path_buf(),
+        version: VersionSpec::RelativeToLatest(0),
+    })
+    .unwrap();
+    execute_command(Command::Verify {
+        local_store_path: compacted_dir.path().to_path_buf(),
+        reference: SourceSpec::LocalStore(original_path.clone()),
+    })
+    .unwrap();
+
+    let snapshot = |path: PathBuf| {
+        execute_command(Command::Snapshot {
+            registry_spec: local_store_latest_snapshot(path),
+            projection: universal_projection(),
+        })
+        .unwrap()
+    };
+    assert_eq!(
+        snapshot(compacted_dir.path().to_path_buf()),
+        snapshot(original_path)
+    );
+}
+
 pub fn local_store_latest_snapshot(path: PathBuf) -> RegistrySpec {
     let source = SourceSpec::LocalStore(path);
     let version = VersionSpec::RelativeToLatest(0);
@@ -274,6 +274,61 @@ pub struct RegistryGetLatestVersionResponse {
     #[prost(uint64, tag = "1")]
     pub version: u64,
 }
+/// Message to retrieve, one page at a time, all the keys starting with a
+/// prefix together with their values at some version.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RegistryGetValuesByPrefixRequest {
+    /// The prefix of the keys to retrieve.
+    /// Required.
+    #[prost(bytes = "vec", tag = "1")]
+    pub prefix: ::prost::alloc::vec::Vec<u8>,
+    /// The version at which to read the values.
+    /// Optional: If not set, the latest version is used.
+    #[prost(message, optional, tag = "2")]
+    pub version: ::core::option::Option<u64>,
+    /// Only keys greater than this one are returned. Set to the
+    /// next_start_after of the previous response to retrieve the next page.
+    /// Optional: If not set, the first page is returned.
+    #[prost(message, optional, tag = "3")]
+    pub start_after: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+}
+/// A registry key together with its value at some version.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RegistryKeyValue {
+    #[prost(bytes = "vec", tag = "1")]
+    pub key: ::prost::alloc::vec::Vec<u8>,
+    /// The last mutation of the key at or before the requested version. This may
+    /// be a deletion, so that clients can check the value against the
+    /// certificate.
+    #[prost(message, optional, tag = "2")]
+    pub value: ::core::option::Option<HighCapacityRegistryValue>,
+}
+/// Message corresponding to the response from the canister to a
+/// get_values_by_prefix() request.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RegistryGetValuesByPrefixResponse {
+    /// If anything went wrong, the registry canister
+    /// will set this error.
+    #[prost(message, optional, tag = "1")]
+    pub error: ::core::option::Option<RegistryError>,
+    /// The version at which the values were read.
+    #[prost(uint64, tag = "2")]
+    pub version: u64,
+    /// The keys of this page that existed at the requested version, in
+    /// ascending order.
+    #[prost(message, repeated, tag = "3")]
+    pub values: ::prost::alloc::vec::Vec<RegistryKeyValue>,
+    /// If set, further keys start with the prefix. Pass this as start_after to
+    /// retrieve the next page.
+    #[prost(message, optional, tag = "4")]
+    pub next_start_after: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    /// Certifies the values and proves that no key of the range covered by this
+    /// page is missing. Under the "values" label, the hash tree contains, for
+    /// every key in the range, the SHA-256 hashes of all its values (encoded as
+    /// HighCapacityRegistryValue) labeled by their big-endian encoded version.
+    #[prost(message, optional, tag = "5")]
+    pub certified: ::core::option::Option<CertifiedResponse>,
+}
 /// In the not too distant future, the `get_certified_changes_since` canister
 /// method will use this instead of RegistryMutation. However, there is no
 /// intention for the `atomic_mutate` canister method to ever use this. See the
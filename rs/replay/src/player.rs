@@ -337,6 +337,7 @@ impl Player {
             log.clone(),
             registry.clone(),
             MaliciousFlags::default(),
+            None,
         ));
         let certification_pool = consensus_pool.as_ref().map(|_| {
             CertificationPoolImpl::new(
@@ -722,6 +723,7 @@ impl Player {
                 &self.log,
                 replay_target_height,
                 None,
+                None,
             ) {
                 Ok(h) => break h,
                 Err(MessageRoutingError::QueueIsFull) => std::thread::sleep(WAIT_DURATION),
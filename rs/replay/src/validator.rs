@@ -10,8 +10,8 @@ use ic_consensus::consensus::{validator::Validator, ValidatorMetrics};
 use ic_consensus_certification::CertificationCrypto;
 use ic_consensus_dkg::DkgKeyManager;
 use ic_consensus_utils::{
-    active_high_threshold_nidkg_id, crypto::ConsensusCrypto, membership::Membership,
-    pool_reader::PoolReader, registry_version_at_height,
+    active_high_threshold_nidkg_id, block_lifecycle::BlockLifecycleTrace, crypto::ConsensusCrypto,
+    membership::Membership, pool_reader::PoolReader, registry_version_at_height,
 };
 use ic_interfaces::{
     certification::Verifier,
@@ -129,6 +129,7 @@ impl ReplayValidator {
             state_manager,
             message_routing,
             Arc::new(dkg_pool) as Arc<_>,
+            Arc::new(BlockLifecycleTrace::new(time_source.clone())),
             log.clone(),
             ValidatorMetrics::new(metrics_registry.clone()),
             time_source.clone(),
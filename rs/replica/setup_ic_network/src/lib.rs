@@ -27,12 +27,13 @@ use ic_https_outcalls_consensus::{
 use ic_ingress_manager::{bouncer::IngressBouncer, IngressManager, RandomStateKind};
 use ic_interfaces::{
     batch_payload::BatchPayloadBuilder,
+    consensus::BlockLifecycleTracer,
     consensus_pool::{ConsensusBlockCache, ConsensusPoolCache},
     execution_environment::IngressHistoryReader,
     messaging::{MessageRouting, XNetPayloadBuilder},
     p2p::{artifact_manager::JoinGuard, state_sync::StateSyncClient},
     self_validating_payload::SelfValidatingPayloadBuilder,
-    time_source::{SysTimeSource, TimeSource},
+    time_source::TimeSource,
 };
 use ic_interfaces_adapter_client::NonBlockingChannel;
 use ic_interfaces_registry::RegistryClient;
@@ -361,12 +362,13 @@ pub fn setup_consensus_and_p2p(
     canister_http_adapter_client: CanisterHttpAdapterClient,
     registry_poll_delay_duration_ms: u64,
     max_certified_height_tx: watch::Sender<Height>,
+    time_source: Arc<dyn TimeSource>,
+    block_lifecycle_tracer: Arc<dyn BlockLifecycleTracer>,
 ) -> (
     Arc<RwLock<IngressPoolImpl>>,
     UnboundedSender<UnvalidatedArtifactMutation<SignedIngress>>,
     Vec<Box<dyn JoinGuard>>,
 ) {
-    let time_source = Arc::new(SysTimeSource::new());
     let consensus_pool_cache = consensus_pool.read().unwrap().get_cache();
     let artifact_pools = ArtifactPools::new(
         log,
@@ -464,6 +466,7 @@ pub fn setup_consensus_and_p2p(
         registry_poll_delay_duration_ms,
         canister_http_adapter_client,
         max_certified_height_tx,
+        block_lifecycle_tracer,
         time_source,
     )
 }
@@ -497,6 +500,7 @@ fn start_consensus(
     registry_poll_delay_duration_ms: u64,
     canister_http_adapter_client: CanisterHttpAdapterClient,
     max_certified_height_tx: watch::Sender<Height>,
+    block_lifecycle_tracer: Arc<dyn BlockLifecycleTracer>,
     time_source: Arc<dyn TimeSource>,
 ) -> (
     Arc<RwLock<IngressPoolImpl>>,
@@ -575,6 +579,7 @@ fn start_consensus(
         Arc::clone(&time_source) as Arc<_>,
         registry_poll_delay_duration_ms,
         malicious_flags.clone(),
+        block_lifecycle_tracer.clone(),
         metrics_registry.clone(),
         log.clone(),
     );
@@ -607,6 +612,7 @@ fn start_consensus(
         metrics_registry.clone(),
         log.clone(),
         max_certified_height_tx,
+        block_lifecycle_tracer,
     );
     join_handles.push(create_artifact_handler(
         abortable_broadcast_channels.certifier,
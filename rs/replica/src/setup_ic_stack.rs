@@ -6,6 +6,7 @@ use ic_btc_adapter_client::{setup_bitcoin_adapter_clients, BitcoinAdapterClients
 use ic_btc_consensus::BitcoinPayloadBuilder;
 use ic_config::{artifact_pool::ArtifactPoolConfig, subnet_config::SubnetConfig, Config};
 use ic_consensus_certification::VerifierImpl;
+use ic_consensus_utils::block_lifecycle::BlockLifecycleTrace;
 use ic_crypto::CryptoComponent;
 use ic_cycles_account_manager::CyclesAccountManager;
 use ic_execution_environment::ExecutionServices;
@@ -206,6 +207,10 @@ pub fn construct_ic_stack(
         &state_manager.state_layout().tmp(),
     );
     // ---------- MESSAGE ROUTING DEPS FOLLOW ----------
+    // Consensus and the block lifecycle tracer share the same time source, so
+    // that all the stages of a block are measured against the same clock.
+    let time_source = Arc::new(SysTimeSource::new());
+    let block_lifecycle_tracer = Arc::new(BlockLifecycleTrace::new(time_source.clone()));
     let certified_stream_store = Arc::clone(&state_manager);
     let message_router = if config
         .malicious_behaviour
@@ -232,6 +237,7 @@ pub fn construct_ic_stack(
             log.clone(),
             registry.clone(),
             config.malicious_behaviour.malicious_flags.clone(),
+            Some(block_lifecycle_tracer.clone() as Arc<_>),
         )
     };
     let xnet_endpoint = XNetEndpoint::new(
@@ -338,6 +344,8 @@ pub fn construct_ic_stack(
         canister_http_adapter_client,
         config.nns_registry_replicator.poll_delay_duration_ms,
         max_certified_height_tx,
+        time_source,
+        block_lifecycle_tracer.clone(),
     );
 
     // ---------- PUBLIC ENDPOINT DEPS FOLLOW ----------
@@ -366,6 +374,7 @@ pub fn construct_ic_stack(
         tracing_handle,
         max_certified_height_rx,
         finalized_ingress_height_rx,
+        Some(block_lifecycle_tracer as Arc<_>),
         cancellation_token.child_token(),
     );
 
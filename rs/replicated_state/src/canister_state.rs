@@ -216,6 +216,7 @@ impl CanisterState {
             (Some(ExecutionTask::Heartbeat), _) => NextExecution::StartNew,
             (Some(ExecutionTask::GlobalTimer), _) => NextExecution::StartNew,
             (Some(ExecutionTask::OnLowWasmMemory), _) => NextExecution::StartNew,
+            (Some(ExecutionTask::OnLowCycles), _) => NextExecution::StartNew,
             (Some(ExecutionTask::AbortedExecution { .. }), _)
             | (Some(ExecutionTask::PausedExecution { .. }), _) => NextExecution::ContinueLong,
             (Some(ExecutionTask::AbortedInstallCode { .. }), _)
@@ -236,6 +237,7 @@ impl CanisterState {
             | Some(ExecutionTask::Heartbeat)
             | Some(ExecutionTask::GlobalTimer)
             | Some(ExecutionTask::OnLowWasmMemory)
+            | Some(ExecutionTask::OnLowCycles)
             | Some(ExecutionTask::PausedExecution { .. })
             | Some(ExecutionTask::PausedInstallCode(..))
             | Some(ExecutionTask::AbortedInstallCode { .. }) => false,
@@ -250,6 +252,7 @@ impl CanisterState {
             | Some(ExecutionTask::Heartbeat)
             | Some(ExecutionTask::GlobalTimer)
             | Some(ExecutionTask::OnLowWasmMemory)
+            | Some(ExecutionTask::OnLowCycles)
             | Some(ExecutionTask::PausedInstallCode(..))
             | Some(ExecutionTask::AbortedExecution { .. })
             | Some(ExecutionTask::AbortedInstallCode { .. }) => false,
@@ -264,6 +267,7 @@ impl CanisterState {
             | Some(ExecutionTask::Heartbeat)
             | Some(ExecutionTask::GlobalTimer)
             | Some(ExecutionTask::OnLowWasmMemory)
+            | Some(ExecutionTask::OnLowCycles)
             | Some(ExecutionTask::PausedExecution { .. })
             | Some(ExecutionTask::AbortedExecution { .. })
             | Some(ExecutionTask::AbortedInstallCode { .. }) => false,
@@ -278,6 +282,7 @@ impl CanisterState {
             | Some(ExecutionTask::Heartbeat)
             | Some(ExecutionTask::GlobalTimer)
             | Some(ExecutionTask::OnLowWasmMemory)
+            | Some(ExecutionTask::OnLowCycles)
             | Some(ExecutionTask::PausedExecution { .. })
             | Some(ExecutionTask::PausedInstallCode(..))
             | Some(ExecutionTask::AbortedExecution { .. }) => false,
@@ -512,6 +517,12 @@ impl CanisterState {
         self.exports_method(&WasmMethod::System(SystemMethod::CanisterOnLowWasmMemory))
     }
 
+    /// Returns true if the canister exports the `canister_on_low_cycles`
+    /// system method.
+    pub fn exports_on_low_cycles(&self) -> bool {
+        self.exports_method(&WasmMethod::System(SystemMethod::CanisterOnLowCycles))
+    }
+
     /// Returns true if the canister exports the given Wasm method.
     pub fn exports_method(&self, method: &WasmMethod) -> bool {
         match &self.execution_state {
@@ -611,6 +622,11 @@ impl CanisterState {
                 self.wasm_memory_usage(),
             )
     }
+
+    /// Updates status of `OnLowCycles` hook.
+    pub fn update_on_low_cycles_hook_condition(&mut self) {
+        self.system_state.update_on_low_cycles_hook_status();
+    }
 }
 
 /// The result of `next_execution()` function.
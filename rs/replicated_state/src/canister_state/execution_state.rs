@@ -73,6 +73,9 @@ pub struct ExportedFunctions {
 
     /// Cached info about exporting a on low Wasm memory to skip expensive BTreeSet lookup.
     exports_on_low_wasm_memory: bool,
+
+    /// Cached info about exporting a on low cycles to skip expensive BTreeSet lookup.
+    exports_on_low_cycles: bool,
 }
 
 impl ExportedFunctions {
@@ -83,11 +86,14 @@ impl ExportedFunctions {
             exported_functions.contains(&WasmMethod::System(SystemMethod::CanisterGlobalTimer));
         let exports_on_low_wasm_memory =
             exported_functions.contains(&WasmMethod::System(SystemMethod::CanisterOnLowWasmMemory));
+        let exports_on_low_cycles =
+            exported_functions.contains(&WasmMethod::System(SystemMethod::CanisterOnLowCycles));
         Self {
             exported_functions: Arc::new(exported_functions),
             exports_heartbeat,
             exports_global_timer,
             exports_on_low_wasm_memory,
+            exports_on_low_cycles,
         }
     }
 
@@ -99,6 +105,7 @@ impl ExportedFunctions {
             WasmMethod::System(SystemMethod::CanisterOnLowWasmMemory) => {
                 self.exports_on_low_wasm_memory
             }
+            WasmMethod::System(SystemMethod::CanisterOnLowCycles) => self.exports_on_low_cycles,
             // Expensive lookup.
             _ => self.exported_functions.contains(method),
         }
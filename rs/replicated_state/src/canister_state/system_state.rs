@@ -20,8 +20,8 @@ use ic_error_types::RejectCode;
 use ic_interfaces::execution_environment::HypervisorError;
 use ic_logger::{error, ReplicaLogger};
 use ic_management_canister_types_private::{
-    CanisterChange, CanisterChangeDetails, CanisterChangeOrigin, CanisterStatusType,
-    LogVisibilityV2,
+    CanisterChange, CanisterChangeDetails, CanisterChangeOrigin, CanisterHistoryVisibility,
+    CanisterStatusType, LogVisibilityV2, HASH_LENGTH,
 };
 use ic_protobuf::proxy::{try_from_option_field, ProxyDecodeError};
 use ic_protobuf::state::canister_state_bits::v1 as pb;
@@ -49,6 +49,7 @@ use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::convert::{TryFrom, TryInto};
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 use strum_macros::EnumIter;
 
 lazy_static! {
@@ -201,11 +202,96 @@ impl CanisterMetrics {
     }
 }
 
+/// Length of the rolling window over which the `cycles_burn_limit` of a
+/// canister is enforced.
+pub const CYCLES_BURN_LIMIT_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Minimum time between two samples of a [`CyclesBurnWindow`]. Cycles burned
+/// at the start of the window are forgotten at most this much later than they
+/// leave the window.
+pub const CYCLES_BURN_LIMIT_GRANULARITY: Duration = Duration::from_secs(60 * 60);
+
+/// The value of `CanisterMetrics::consumed_cycles` at a point in time.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct CyclesBurnSample {
+    pub time: Time,
+    pub consumed_cycles: NominalCycles,
+}
+
+/// Tracks the cycles consumed by a canister within a rolling window of
+/// `CYCLES_BURN_LIMIT_WINDOW`.
+///
+/// Samples of `CanisterMetrics::consumed_cycles` are taken at most once per
+/// `CYCLES_BURN_LIMIT_GRANULARITY`, and only the latest sample at or before
+/// the start of the window is kept out of the older ones. The cycles burned in
+/// the window are measured against that sample, so they are over-estimated by
+/// at most one granularity interval and never under-estimated. Refunds of
+/// prepaid cycles reduce the amount burned in the window.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct CyclesBurnWindow {
+    /// Samples ordered by time.
+    pub samples: VecDeque<CyclesBurnSample>,
+}
+
+impl CyclesBurnWindow {
+    /// Records a sample at `now` if the latest one is older than the
+    /// granularity, and drops samples that no longer affect the window.
+    fn update(&mut self, now: Time, consumed_cycles: NominalCycles) {
+        let needs_sample = self.samples.back().is_none_or(|sample| {
+            now.saturating_duration_since(sample.time) >= CYCLES_BURN_LIMIT_GRANULARITY
+        });
+        if needs_sample {
+            self.samples.push_back(CyclesBurnSample {
+                time: now,
+                consumed_cycles,
+            });
+        }
+        while self.samples.get(1).is_some_and(|sample| {
+            now.saturating_duration_since(sample.time) >= CYCLES_BURN_LIMIT_WINDOW
+        }) {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Returns the sample that burned cycles in the window ending at `now` are
+    /// measured against.
+    fn base(&self, now: Time) -> Option<&CyclesBurnSample> {
+        self.samples
+            .iter()
+            .take_while(|sample| {
+                now.saturating_duration_since(sample.time) >= CYCLES_BURN_LIMIT_WINDOW
+            })
+            .last()
+            .or_else(|| self.samples.front())
+    }
+}
+
 /// Computes the total byte size of the given canister changes. Requires `O(N)` time.
 pub fn compute_total_canister_change_size(changes: &VecDeque<Arc<CanisterChange>>) -> NumBytes {
     changes.iter().map(|c| c.count_bytes()).sum()
 }
 
+/// Returns the number of bytes taken by a retained install argument hash.
+fn install_argument_hash_size(install_argument_hash: &Option<[u8; HASH_LENGTH]>) -> NumBytes {
+    match install_argument_hash {
+        Some(hash) => NumBytes::new(hash.len() as u64),
+        None => NumBytes::new(0),
+    }
+}
+
+/// Computes the total byte size of the given canister changes and the hashes of
+/// their install arguments. Requires `O(N)` time.
+fn compute_canister_history_memory_usage(
+    changes: &VecDeque<Arc<CanisterChange>>,
+    install_argument_hashes: &VecDeque<Option<[u8; HASH_LENGTH]>>,
+) -> NumBytes {
+    compute_total_canister_change_size(changes)
+        + install_argument_hashes
+            .iter()
+            .map(install_argument_hash_size)
+            .sum()
+}
+
 /// The canister history consists of a list of canister changes
 /// with the oldest canister changes at lowest indices.
 /// The system can drop the oldest canister changes from the list to keep its length bounded
@@ -220,10 +306,16 @@ pub struct CanisterHistory {
     /// that have ever been recorded. In particular, if the system drops some canister changes,
     /// `total_num_changes` does not decrease.
     total_num_changes: u64,
-    /// Sum over `c.count_bytes()` for all canister changes `c`.
+    /// Sum over `c.count_bytes()` for all canister changes `c`, plus the size of
+    /// the retained install argument hashes.
     /// We pre-compute and store the sum in a field to optimize the running time
     /// of computing the sum as the canister history memory usage is requested frequently.
     canister_history_memory_usage: NumBytes,
+    /// The SHA-256 hashes of the install arguments, with one entry per element of `changes`.
+    /// The entry is `None` for changes that are not code deployments.
+    install_argument_hashes: Arc<VecDeque<Option<[u8; HASH_LENGTH]>>>,
+    /// Who may fetch the extended canister history. Kept when the history is cleared.
+    visibility: CanisterHistoryVisibility,
 }
 
 impl CanisterHistory {
@@ -231,11 +323,12 @@ impl CanisterHistory {
     /// but keeps the total number of changes recorded.
     pub fn clear(&mut self) {
         self.changes = Arc::new(Default::default());
+        self.install_argument_hashes = Arc::new(Default::default());
         self.canister_history_memory_usage = NumBytes::new(0);
 
         debug_assert_eq!(
             self.get_memory_usage(),
-            compute_total_canister_change_size(&self.changes),
+            compute_canister_history_memory_usage(&self.changes, &self.install_argument_hashes),
         );
     }
 
@@ -244,7 +337,18 @@ impl CanisterHistory {
     /// of canister changes does not exceed `MAX_CANISTER_HISTORY_CHANGES`
     /// by dropping the oldest entry if necessary.
     pub fn add_canister_change(&mut self, canister_change: CanisterChange) {
+        self.add_canister_change_with_argument_hash(canister_change, None);
+    }
+
+    /// Same as `add_canister_change`, but also records the hash of the install
+    /// argument of a code deployment.
+    pub fn add_canister_change_with_argument_hash(
+        &mut self,
+        canister_change: CanisterChange,
+        install_argument_hash: Option<[u8; HASH_LENGTH]>,
+    ) {
         let changes = Arc::make_mut(&mut self.changes);
+        let install_argument_hashes = Arc::make_mut(&mut self.install_argument_hashes);
         if changes.len() >= MAX_CANISTER_HISTORY_CHANGES as usize {
             let change_size = changes
                 .pop_front()
@@ -252,14 +356,22 @@ impl CanisterHistory {
                 .map(|c| c.count_bytes())
                 .unwrap_or_default();
             self.canister_history_memory_usage -= change_size;
+            let hash_size = install_argument_hashes
+                .pop_front()
+                .as_ref()
+                .map(install_argument_hash_size)
+                .unwrap_or_default();
+            self.canister_history_memory_usage -= hash_size;
         }
-        self.canister_history_memory_usage += canister_change.count_bytes();
+        self.canister_history_memory_usage +=
+            canister_change.count_bytes() + install_argument_hash_size(&install_argument_hash);
         changes.push_back(Arc::new(canister_change));
+        install_argument_hashes.push_back(install_argument_hash);
         self.total_num_changes += 1;
 
         debug_assert_eq!(
             self.get_memory_usage(),
-            compute_total_canister_change_size(&self.changes),
+            compute_canister_history_memory_usage(&self.changes, &self.install_argument_hashes),
         );
     }
 
@@ -276,6 +388,24 @@ impl CanisterHistory {
         self.changes.range((num_all_changes - num_changes)..)
     }
 
+    /// Returns an iterator over all canister changes in chronological order,
+    /// together with the hashes of their install arguments (if any).
+    pub fn get_changes_with_argument_hashes(
+        &self,
+    ) -> impl Iterator<Item = (&Arc<CanisterChange>, Option<&[u8; HASH_LENGTH]>)> {
+        self.changes
+            .iter()
+            .zip(self.install_argument_hashes.iter().map(Option::as_ref))
+    }
+
+    pub fn visibility(&self) -> CanisterHistoryVisibility {
+        self.visibility
+    }
+
+    pub fn set_visibility(&mut self, visibility: CanisterHistoryVisibility) {
+        self.visibility = visibility;
+    }
+
     pub fn get_total_num_changes(&self) -> u64 {
         self.total_num_changes
     }
@@ -311,6 +441,8 @@ pub struct SystemState {
     pub memory_allocation: MemoryAllocation,
     /// Threshold used for activation of canister_on_low_wasm_memory hook.
     pub wasm_memory_threshold: NumBytes,
+    /// Threshold used for activation of canister_on_low_cycles hook.
+    pub cycles_threshold: Cycles,
     pub freeze_threshold: NumSeconds,
     /// The status of the canister: `Running`, `Stopping`, or `Stopped`.
     /// Different statuses allow for different behaviors on the `SystemState`.
@@ -364,6 +496,16 @@ pub struct SystemState {
     /// fail if `reserved_balance + N` exceeds this limit if the limit is set.
     reserved_balance_limit: Option<Cycles>,
 
+    /// The user-specified upper limit on the cycles consumed by the canister
+    /// within a rolling `CYCLES_BURN_LIMIT_WINDOW`.
+    ///
+    /// Once the limit is reached, further calls and tasks of the canister are
+    /// rejected until enough of the burned cycles leave the window.
+    pub cycles_burn_limit: Option<Cycles>,
+
+    /// The cycles burn window used to enforce `cycles_burn_limit`.
+    pub cycles_burn_window: CyclesBurnWindow,
+
     /// Queue of tasks to be executed next. If a paused or aborted execution task is
     /// present, it must be executed before any other tasks or messages.
     pub task_queue: TaskQueue,
@@ -509,6 +651,10 @@ pub enum ExecutionTask {
     /// The task exists only within an execution round, it never gets serialized.
     OnLowWasmMemory,
 
+    /// On low cycles hook.
+    /// The task exists only within an execution round, it never gets serialized.
+    OnLowCycles,
+
     /// A paused execution task exists only within an epoch (between
     /// checkpoints). It is never serialized, and it turns into `AbortedExecution`
     /// before the checkpoint or when there are too many long-running executions.
@@ -552,7 +698,7 @@ pub enum ExecutionTask {
 impl ExecutionTask {
     pub fn is_hook(&self) -> bool {
         match self {
-            Self::OnLowWasmMemory => true,
+            Self::OnLowWasmMemory | Self::OnLowCycles => true,
             Self::Heartbeat
             | Self::GlobalTimer
             | Self::PausedExecution { .. }
@@ -569,6 +715,7 @@ impl From<&ExecutionTask> for pb::ExecutionTask {
             ExecutionTask::Heartbeat
             | ExecutionTask::GlobalTimer
             | ExecutionTask::OnLowWasmMemory
+            | ExecutionTask::OnLowCycles
             | ExecutionTask::PausedExecution { .. }
             | ExecutionTask::PausedInstallCode(_) => {
                 panic!("Attempt to serialize ephemeral task: {:?}.", item);
@@ -705,6 +852,12 @@ impl From<&CanisterHistory> for pb::CanisterHistory {
                 .map(|e| (&(**e)).into())
                 .collect::<Vec<pb::CanisterChange>>(),
             total_num_changes: item.total_num_changes,
+            install_argument_hashes: item
+                .install_argument_hashes
+                .iter()
+                .map(|hash| hash.map(|hash| hash.to_vec()).unwrap_or_default())
+                .collect(),
+            visibility: pb::CanisterHistoryVisibility::from(item.visibility).into(),
         }
     }
 }
@@ -718,11 +871,46 @@ impl TryFrom<pb::CanisterHistory> for CanisterHistory {
             .into_iter()
             .map(|e| Ok(Arc::new(e.try_into()?)))
             .collect::<Result<VecDeque<_>, Self::Error>>()?;
-        let canister_history_memory_usage = compute_total_canister_change_size(&changes);
+        // Histories recorded before install argument hashes were retained have no hashes.
+        let install_argument_hashes = if value.install_argument_hashes.is_empty() {
+            vec![None; changes.len()].into()
+        } else if value.install_argument_hashes.len() == changes.len() {
+            value
+                .install_argument_hashes
+                .into_iter()
+                .map(|hash| {
+                    if hash.is_empty() {
+                        return Ok(None);
+                    }
+                    <[u8; HASH_LENGTH]>::try_from(hash)
+                        .map(Some)
+                        .map_err(|hash| {
+                            ProxyDecodeError::Other(format!(
+                                "Expected install argument hash of {} bytes, got {}",
+                                HASH_LENGTH,
+                                hash.len()
+                            ))
+                        })
+                })
+                .collect::<Result<VecDeque<_>, Self::Error>>()?
+        } else {
+            return Err(ProxyDecodeError::Other(format!(
+                "Expected {} install argument hashes, got {}",
+                changes.len(),
+                value.install_argument_hashes.len()
+            )));
+        };
+        let canister_history_memory_usage =
+            compute_canister_history_memory_usage(&changes, &install_argument_hashes);
+        let visibility = pb::CanisterHistoryVisibility::try_from(value.visibility)
+            .unwrap_or_default()
+            .into();
         Ok(Self {
             changes: Arc::new(changes),
             total_num_changes: value.total_num_changes,
             canister_history_memory_usage,
+            install_argument_hashes: Arc::new(install_argument_hashes),
+            visibility,
         })
     }
 }
@@ -773,9 +961,12 @@ impl SystemState {
             ingress_induction_cycles_debit: Cycles::zero(),
             reserved_balance: Cycles::zero(),
             reserved_balance_limit: None,
+            cycles_burn_limit: None,
+            cycles_burn_window: CyclesBurnWindow::default(),
             memory_allocation: MemoryAllocation::BestEffort,
             environment_variables: Default::default(),
             wasm_memory_threshold: NumBytes::new(0),
+            cycles_threshold: Cycles::zero(),
             freeze_threshold,
             status,
             certified_data: Default::default(),
@@ -800,6 +991,7 @@ impl SystemState {
         queues: CanisterQueues,
         memory_allocation: MemoryAllocation,
         wasm_memory_threshold: NumBytes,
+        cycles_threshold: Cycles,
         freeze_threshold: NumSeconds,
         status: CanisterStatus,
         certified_data: Vec<u8>,
@@ -808,6 +1000,8 @@ impl SystemState {
         ingress_induction_cycles_debit: Cycles,
         reserved_balance: Cycles,
         reserved_balance_limit: Option<Cycles>,
+        cycles_burn_limit: Option<Cycles>,
+        cycles_burn_window: CyclesBurnWindow,
         task_queue: TaskQueue,
         global_timer: CanisterTimer,
         canister_version: u64,
@@ -828,6 +1022,7 @@ impl SystemState {
             queues,
             memory_allocation,
             wasm_memory_threshold,
+            cycles_threshold,
             freeze_threshold,
             status,
             certified_data,
@@ -836,6 +1031,8 @@ impl SystemState {
             ingress_induction_cycles_debit,
             reserved_balance,
             reserved_balance_limit,
+            cycles_burn_limit,
+            cycles_burn_window,
             task_queue,
             global_timer,
             canister_version,
@@ -1957,6 +2154,33 @@ impl SystemState {
         self.canister_history.add_canister_change(new_change);
     }
 
+    /// Adds a code deployment to canister history together with the hash of
+    /// its install argument.
+    pub fn add_code_deployment_change(
+        &mut self,
+        timestamp_nanos: Time,
+        change_origin: CanisterChangeOrigin,
+        change_details: CanisterChangeDetails,
+        install_argument_hash: [u8; HASH_LENGTH],
+    ) {
+        let new_change = CanisterChange::new(
+            timestamp_nanos.as_nanos_since_unix_epoch(),
+            self.canister_version,
+            change_origin,
+            change_details,
+        );
+        self.canister_history
+            .add_canister_change_with_argument_hash(new_change, Some(install_argument_hash));
+    }
+
+    pub fn canister_history_visibility(&self) -> CanisterHistoryVisibility {
+        self.canister_history.visibility()
+    }
+
+    pub fn set_canister_history_visibility(&mut self, visibility: CanisterHistoryVisibility) {
+        self.canister_history.set_visibility(visibility);
+    }
+
     /// Overwrite the `total_num_changes` of the canister history. This can happen in the context of canister migration.
     pub fn set_canister_history_total_num_changes(&mut self, total_num_changes: u64) {
         self.canister_history
@@ -2084,6 +2308,48 @@ impl SystemState {
             wasm_memory_threshold,
         )
     }
+
+    /// Enqueues or removes `OnLowCycles` task from `task_queue`
+    /// depending if the condition for `OnLowCyclesHook` is satisfied:
+    ///
+    ///    `debited_balance < cycles_threshold`
+    ///
+    /// Note: the condition never holds if `cycles_threshold` is zero.
+    pub fn update_on_low_cycles_hook_status(&mut self) {
+        if self.is_low_cycles_hook_condition_satisfied() {
+            self.task_queue.enqueue(ExecutionTask::OnLowCycles);
+        } else {
+            self.task_queue.remove(ExecutionTask::OnLowCycles);
+        }
+    }
+
+    /// Returns the `OnLowCycles` hook status without updating the `task_queue`.
+    pub fn is_low_cycles_hook_condition_satisfied(&self) -> bool {
+        self.debited_balance() < self.cycles_threshold
+    }
+
+    /// Advances the rolling cycles burn window to `now`. The window is only
+    /// tracked while a `cycles_burn_limit` is set.
+    pub fn update_cycles_burn_window(&mut self, now: Time) {
+        if self.cycles_burn_limit.is_none() {
+            self.cycles_burn_window.samples.clear();
+            return;
+        }
+        self.cycles_burn_window
+            .update(now, self.canister_metrics.consumed_cycles);
+    }
+
+    /// Returns the cycles consumed by the canister in the rolling window
+    /// ending at `now`, see [`CyclesBurnWindow`].
+    pub fn cycles_burned_in_window(&self, now: Time) -> Cycles {
+        match self.cycles_burn_window.base(now) {
+            // We rely on saturating operations of `NominalCycles` here.
+            Some(base) => {
+                Cycles::new((self.canister_metrics.consumed_cycles - base.consumed_cycles).get())
+            }
+            None => Cycles::zero(),
+        }
+    }
 }
 
 /// Implements memory limits verification for pushing a canister-to-canister
@@ -2337,6 +2603,7 @@ pub mod testing {
             queues: Default::default(),
             memory_allocation: Default::default(),
             wasm_memory_threshold: Default::default(),
+            cycles_threshold: Default::default(),
             freeze_threshold: Default::default(),
             status: CanisterStatus::Stopped,
             certified_data: Default::default(),
@@ -2345,6 +2612,8 @@ pub mod testing {
             ingress_induction_cycles_debit: Default::default(),
             reserved_balance: Default::default(),
             reserved_balance_limit: Default::default(),
+            cycles_burn_limit: Default::default(),
+            cycles_burn_window: Default::default(),
             task_queue: Default::default(),
             global_timer: CanisterTimer::Inactive,
             canister_version: Default::default(),
@@ -1,7 +1,7 @@
 use crate::ExecutionTask;
 use ic_config::flag_status::FlagStatus;
 use ic_interfaces::execution_environment::ExecutionRoundType;
-use ic_management_canister_types_private::OnLowWasmMemoryHookStatus;
+use ic_management_canister_types_private::{OnLowCyclesHookStatus, OnLowWasmMemoryHookStatus};
 use ic_protobuf::proxy::ProxyDecodeError;
 use ic_protobuf::state::canister_state_bits::v1 as pb;
 use ic_types::CanisterId;
@@ -13,7 +13,8 @@ use std::collections::VecDeque;
 ///
 /// 1. If there is a `Paused` or `Aborted` task it will be returned first.
 /// 2. If an `OnLowWasmMemoryHook` is ready to be executed, it will be returned next.
-/// 3. All other tasks will be returned based on the order in which they are added to the queue.
+/// 3. If an `OnLowCyclesHook` is ready to be executed, it will be returned next.
+/// 4. All other tasks will be returned based on the order in which they are added to the queue.
 #[derive(Clone, Eq, PartialEq, Debug, Default)]
 pub struct TaskQueue {
     /// Keeps `PausedExecution`, or `PausedInstallCode`, or `AbortedExecution`,
@@ -23,6 +24,9 @@ pub struct TaskQueue {
     /// Status of low_on_wasm_memory hook execution.
     on_low_wasm_memory_hook_status: OnLowWasmMemoryHookStatus,
 
+    /// Status of on_low_cycles hook execution.
+    on_low_cycles_hook_status: OnLowCyclesHookStatus,
+
     /// Queue of `Heartbeat` and `GlobalTimer` tasks.
     queue: VecDeque<ExecutionTask>,
 }
@@ -32,6 +36,8 @@ impl TaskQueue {
         self.paused_or_aborted_task.as_ref().or_else(|| {
             if self.on_low_wasm_memory_hook_status.is_ready() {
                 Some(&ExecutionTask::OnLowWasmMemory)
+            } else if self.on_low_cycles_hook_status.is_ready() {
+                Some(&ExecutionTask::OnLowCycles)
             } else {
                 self.queue.front()
             }
@@ -43,6 +49,9 @@ impl TaskQueue {
             if self.on_low_wasm_memory_hook_status.is_ready() {
                 self.on_low_wasm_memory_hook_status = OnLowWasmMemoryHookStatus::Executed;
                 Some(ExecutionTask::OnLowWasmMemory)
+            } else if self.on_low_cycles_hook_status.is_ready() {
+                self.on_low_cycles_hook_status = OnLowCyclesHookStatus::Executed;
+                Some(ExecutionTask::OnLowCycles)
             } else {
                 self.queue.pop_front()
             }
@@ -54,13 +63,16 @@ impl TaskQueue {
             ExecutionTask::OnLowWasmMemory => {
                 self.on_low_wasm_memory_hook_status.update(false);
             }
+            ExecutionTask::OnLowCycles => {
+                self.on_low_cycles_hook_status.update(false);
+            }
             ExecutionTask::Heartbeat
             | ExecutionTask::GlobalTimer
             | ExecutionTask::AbortedInstallCode { .. }
             | ExecutionTask::PausedExecution { .. }
             | ExecutionTask::PausedInstallCode(_)
             | ExecutionTask::AbortedExecution { .. } => unreachable!(
-                "Unsuccessful removal of the task {:?}. Removal of task from TaskQueue is only supported for OnLowWasmMemory type.", task
+                "Unsuccessful removal of the task {:?}. Removal of task from TaskQueue is only supported for OnLowWasmMemory and OnLowCycles types.", task
             ),
         };
     }
@@ -77,6 +89,9 @@ impl TaskQueue {
             ExecutionTask::OnLowWasmMemory => {
                 self.on_low_wasm_memory_hook_status.update(true);
             }
+            ExecutionTask::OnLowCycles => {
+                self.on_low_cycles_hook_status.update(true);
+            }
             ExecutionTask::Heartbeat | ExecutionTask::GlobalTimer => self.queue.push_front(task),
         };
     }
@@ -84,6 +99,7 @@ impl TaskQueue {
     pub fn is_empty(&self) -> bool {
         self.paused_or_aborted_task.is_none()
             && !self.on_low_wasm_memory_hook_status.is_ready()
+            && !self.on_low_cycles_hook_status.is_ready()
             && self.queue.is_empty()
     }
 
@@ -95,6 +111,11 @@ impl TaskQueue {
             } else {
                 0
             }
+            + if self.on_low_cycles_hook_status.is_ready() {
+                1
+            } else {
+                0
+            }
     }
 
     /// This function is used only in tests.
@@ -102,6 +123,11 @@ impl TaskQueue {
         self.on_low_wasm_memory_hook_status
     }
 
+    /// This function is used only in tests.
+    pub fn peek_on_low_cycles_hook_status(&self) -> OnLowCyclesHookStatus {
+        self.on_low_cycles_hook_status
+    }
+
     /// `check_dts_invariants` should only be called after round execution.
     ///
     /// It checks that the following properties are satisfied:
@@ -138,7 +164,8 @@ impl TaskQueue {
                 | ExecutionTask::AbortedInstallCode { .. } => {}
                 ExecutionTask::Heartbeat
                 | ExecutionTask::GlobalTimer
-                | ExecutionTask::OnLowWasmMemory => {
+                | ExecutionTask::OnLowWasmMemory
+                | ExecutionTask::OnLowCycles => {
                     unreachable!(
                         "Unexpected on task type {:?} in TaskQueue::paused_or_aborted_task in canister {:?} .", paused_or_aborted_task, id
                     )
@@ -161,6 +188,7 @@ impl TaskQueue {
                     );
                 }
                 ExecutionTask::OnLowWasmMemory
+                | ExecutionTask::OnLowCycles
                 | ExecutionTask::AbortedExecution { .. }
                 | ExecutionTask::AbortedInstallCode { .. }
                 | ExecutionTask::PausedExecution { .. }
@@ -204,7 +232,8 @@ impl TaskQueue {
                 | ExecutionTask::AbortedInstallCode { .. } => None,
                 ExecutionTask::Heartbeat
                 | ExecutionTask::GlobalTimer
-                | ExecutionTask::OnLowWasmMemory => unreachable!(
+                | ExecutionTask::OnLowWasmMemory
+                | ExecutionTask::OnLowCycles => unreachable!(
                     "Unexpected on task type in the in TaskQueue::paused_or_aborted_task."
                 ),
             }
@@ -238,6 +267,7 @@ impl TaskQueue {
             ExecutionTask::Heartbeat
             | ExecutionTask::GlobalTimer
             | ExecutionTask::OnLowWasmMemory
+            | ExecutionTask::OnLowCycles
             | ExecutionTask::PausedExecution { .. }
             | ExecutionTask::PausedInstallCode(_) => {
                 unreachable!(
@@ -260,6 +290,10 @@ impl From<&TaskQueue> for pb::TaskQueue {
             )
             .into(),
             queue: item.queue.iter().map(|task| task.into()).collect(),
+            on_low_cycles_hook_status: pb::OnLowCyclesHookStatus::from(
+                &item.on_low_cycles_hook_status,
+            )
+            .into(),
         }
     }
 }
@@ -279,6 +313,12 @@ impl TryFrom<pb::TaskQueue> for TaskQueue {
             .map_err(|e| ProxyDecodeError::Other(
                 format!("Error while trying to decode pb::TaskQueue::on_low_wasm_memory_hook_status, {:?}", e)))?
             .try_into()?,
+            on_low_cycles_hook_status: pb::OnLowCyclesHookStatus::try_from(
+                item.on_low_cycles_hook_status,
+            )
+            .map_err(|e| ProxyDecodeError::Other(
+                format!("Error while trying to decode pb::TaskQueue::on_low_cycles_hook_status, {:?}", e)))?
+            .into(),
             queue: item
                 .queue
                 .into_iter()
@@ -348,7 +388,7 @@ mod tests {
 
     use super::TaskQueue;
     use crate::canister_state::system_state::PausedExecutionId;
-    use ic_management_canister_types_private::OnLowWasmMemoryHookStatus;
+    use ic_management_canister_types_private::{OnLowCyclesHookStatus, OnLowWasmMemoryHookStatus};
     use ic_test_utilities_types::messages::IngressBuilder;
     use ic_types::{
         messages::{CanisterCall, CanisterMessageOrTask, CanisterTask},
@@ -593,4 +633,64 @@ mod tests {
         assert_eq!(task_queue.pop_front(), Some(ExecutionTask::GlobalTimer));
         assert_eq!(task_queue.pop_front(), Some(ExecutionTask::Heartbeat));
     }
+
+    #[test]
+    fn test_task_queue_pop_front_on_low_cycles() {
+        let mut task_queue = TaskQueue::default();
+
+        // `ExecutionTask::OnLowCycles` is added to queue.
+        task_queue.enqueue(ExecutionTask::OnLowCycles);
+        assert_eq!(task_queue.len(), 1);
+        assert_eq!(task_queue.front(), Some(&ExecutionTask::OnLowCycles));
+
+        assert_eq!(task_queue.pop_front(), Some(ExecutionTask::OnLowCycles));
+        assert!(task_queue.is_empty());
+        assert_eq!(
+            task_queue.peek_on_low_cycles_hook_status(),
+            OnLowCyclesHookStatus::Executed
+        );
+
+        // The hook is executed only once while the condition holds.
+        task_queue.enqueue(ExecutionTask::OnLowCycles);
+        assert!(task_queue.is_empty());
+
+        // Once the condition stops holding, the hook can be scheduled again.
+        task_queue.remove(ExecutionTask::OnLowCycles);
+        task_queue.enqueue(ExecutionTask::OnLowCycles);
+        assert_eq!(task_queue.len(), 1);
+        assert_eq!(task_queue.pop_front(), Some(ExecutionTask::OnLowCycles));
+    }
+
+    #[test]
+    fn test_task_queue_on_low_wasm_memory_before_on_low_cycles() {
+        let mut task_queue = TaskQueue::default();
+
+        task_queue.enqueue(ExecutionTask::Heartbeat);
+        task_queue.enqueue(ExecutionTask::OnLowCycles);
+        task_queue.enqueue(ExecutionTask::OnLowWasmMemory);
+        assert_eq!(task_queue.len(), 3);
+
+        assert_eq!(task_queue.pop_front(), Some(ExecutionTask::OnLowWasmMemory));
+        assert_eq!(task_queue.pop_front(), Some(ExecutionTask::OnLowCycles));
+        assert_eq!(task_queue.pop_front(), Some(ExecutionTask::Heartbeat));
+        assert!(task_queue.is_empty());
+    }
+
+    #[test]
+    fn test_task_queue_decodes_unspecified_on_low_cycles_hook_status() {
+        use ic_protobuf::state::canister_state_bits::v1 as pb;
+
+        let mut task_queue = TaskQueue::default();
+        task_queue.enqueue(ExecutionTask::OnLowWasmMemory);
+
+        let mut encoded = pb::TaskQueue::from(&task_queue);
+        encoded.on_low_cycles_hook_status = pb::OnLowCyclesHookStatus::Unspecified.into();
+
+        let decoded = TaskQueue::try_from(encoded).unwrap();
+        assert_eq!(decoded, task_queue);
+        assert_eq!(
+            decoded.peek_on_low_cycles_hook_status(),
+            OnLowCyclesHookStatus::ConditionNotSatisfied
+        );
+    }
 }
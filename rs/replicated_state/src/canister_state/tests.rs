@@ -20,7 +20,7 @@ use ic_logger::replica_logger::no_op_logger;
 use ic_management_canister_types_private::Global;
 use ic_management_canister_types_private::{
     BoundedAllowedViewers, CanisterChange, CanisterChangeDetails, CanisterChangeOrigin,
-    CanisterLogRecord, LogVisibilityV2,
+    CanisterHistoryVisibility, CanisterInstallMode, CanisterLogRecord, LogVisibilityV2,
 };
 use ic_metrics::MetricsRegistry;
 use ic_test_utilities_types::ids::{canister_test_id, message_test_id, user_test_id};
@@ -1105,7 +1105,7 @@ fn execution_state_test_partial_eq() {
 /// ```
 ///   debug_assert_eq!(
 ///       self.get_memory_usage(),
-///       compute_total_canister_change_size(&self.changes),
+///       compute_canister_history_memory_usage(&self.changes, &self.install_argument_hashes),
 ///   );
 /// ```
 /// in the functions `CanisterHistory::add_canister_change` and
@@ -1205,6 +1205,69 @@ fn canister_history_operations() {
     }
 }
 
+#[test]
+fn canister_history_keeps_install_argument_hashes_aligned_with_changes() {
+    use ic_protobuf::state::canister_state_bits::v1 as pb;
+
+    let mut canister_history = CanisterHistory::default();
+    canister_history.set_visibility(CanisterHistoryVisibility::Public);
+    for i in 0..(MAX_CANISTER_HISTORY_CHANGES + 3) {
+        let c = CanisterChange::new(
+            42,
+            i,
+            CanisterChangeOrigin::from_user(user_test_id(42).get()),
+            CanisterChangeDetails::code_deployment(CanisterInstallMode::Upgrade, [0; 32]),
+        );
+        canister_history.add_canister_change_with_argument_hash(c, Some([i as u8; 32]));
+    }
+    let expected_hashes: Vec<_> = (3..(MAX_CANISTER_HISTORY_CHANGES + 3))
+        .map(|i| Some([i as u8; 32]))
+        .collect();
+    let hashes = |history: &CanisterHistory| {
+        history
+            .get_changes_with_argument_hashes()
+            .map(|(change, hash)| {
+                assert_eq!(Some(change.canister_version() as u8), hash.map(|h| h[0]));
+                hash.copied()
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(hashes(&canister_history), expected_hashes);
+
+    // The retained hashes count towards the memory usage of the history.
+    assert_eq!(
+        canister_history.get_memory_usage(),
+        canister_history
+            .get_changes(MAX_CANISTER_HISTORY_CHANGES as usize)
+            .map(|c| c.count_bytes())
+            .sum::<NumBytes>()
+            + NumBytes::new(32 * MAX_CANISTER_HISTORY_CHANGES)
+    );
+
+    // The hashes and the visibility survive a protobuf round trip.
+    let round_trip =
+        CanisterHistory::try_from(pb::CanisterHistory::from(&canister_history)).unwrap();
+    assert_eq!(round_trip, canister_history);
+    assert_eq!(hashes(&round_trip), expected_hashes);
+    assert_eq!(round_trip.visibility(), CanisterHistoryVisibility::Public);
+    assert_eq!(
+        round_trip.get_memory_usage(),
+        canister_history.get_memory_usage()
+    );
+
+    // Clearing the history drops the hashes but keeps the visibility.
+    canister_history.clear();
+    assert_eq!(
+        canister_history.get_changes_with_argument_hashes().count(),
+        0
+    );
+    assert_eq!(canister_history.get_memory_usage(), NumBytes::new(0));
+    assert_eq!(
+        canister_history.visibility(),
+        CanisterHistoryVisibility::Public
+    );
+}
+
 #[test]
 fn drops_aborted_canister_install_after_split() {
     let mut canister_state = CanisterStateFixture::new().canister_state;
@@ -66,7 +66,8 @@ pub use canister_state::{
     num_bytes_try_from,
     system_state::{
         memory_usage_of_request, CallContext, CallContextAction, CallContextManager, CallOrigin,
-        CanisterMetrics, CanisterStatus, ExecutionTask, SystemState,
+        CanisterMetrics, CanisterStatus, CyclesBurnSample, CyclesBurnWindow, ExecutionTask,
+        SystemState, CYCLES_BURN_LIMIT_GRANULARITY, CYCLES_BURN_LIMIT_WINDOW,
     },
     CanisterQueues, CanisterState, EmbedderCache, ExecutionState, ExportedFunctions, NumWasmPages,
     SchedulerState,
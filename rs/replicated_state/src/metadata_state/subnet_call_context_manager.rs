@@ -1,4 +1,5 @@
 use ic_btc_replica_types::{GetSuccessorsRequestInitial, SendTransactionRequest};
+use ic_crypto_sha2::Sha256;
 use ic_logger::{info, ReplicaLogger};
 use ic_management_canister_types_private::{
     EcdsaKeyId, MasterPublicKeyId, SchnorrKeyId, VetKdKeyId,
@@ -13,9 +14,9 @@ use ic_types::{
     canister_http::CanisterHttpRequestContext,
     consensus::idkg::PreSigId,
     crypto::threshold_sig::ni_dkg::{id::ni_dkg_target_id, NiDkgId, NiDkgTargetId},
-    messages::{CallbackId, CanisterCall, Request, StopCanisterCallId},
-    node_id_into_protobuf, node_id_try_from_option, CanisterId, ExecutionRound, Height, NodeId,
-    RegistryVersion, Time,
+    messages::{CallbackId, CanisterCall, MessageId, Request, StopCanisterCallId},
+    node_id_into_protobuf, node_id_try_from_option, CanisterId, Cycles, ExecutionRound, Height,
+    NodeId, NumBytes, PrincipalId, RegistryVersion, SubnetId, Time,
 };
 use phantom_newtype::Id;
 use std::{
@@ -33,6 +34,9 @@ const PSEUDO_RANDOM_ID_SIZE: usize = 32;
 /// Threshold algorithm nonce size in bytes.
 const NONCE_SIZE: usize = 32;
 
+/// Domain separator for the message IDs of scheduled management calls.
+const SCHEDULED_MANAGEMENT_CALL_DOMAIN: &[u8] = b"\x1dic-scheduled-management-call";
+
 pub enum SubnetCallContext {
     SetupInitialDKG(SetupInitialDkgContext),
     CanisterHttpRequest(CanisterHttpRequestContext),
@@ -163,6 +167,75 @@ impl StopCanisterCallManager {
     }
 }
 
+pub struct ScheduledManagementCallIdTag;
+pub type ScheduledManagementCallId = Id<ScheduledManagementCallIdTag, u64>;
+
+/// Collection of management canister calls that controllers scheduled for
+/// execution by the system at a later time.
+///
+/// Calls are kept until they are due and are then handed over to execution,
+/// which enqueues them as messages on behalf of their sender. Calls whose
+/// target canister migrates in a subnet split stay here and fail at execution
+/// time, because the target canister can no longer be found.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+struct ScheduledManagementCallManager {
+    next_call_id: u64,
+    scheduled_management_calls: BTreeMap<ScheduledManagementCallId, ScheduledManagementCall>,
+}
+
+impl ScheduledManagementCallManager {
+    fn push_call(&mut self, call: ScheduledManagementCall) -> ScheduledManagementCallId {
+        let call_id = ScheduledManagementCallId::new(self.next_call_id);
+        self.next_call_id += 1;
+        self.scheduled_management_calls.insert(call_id, call);
+
+        call_id
+    }
+
+    fn remove_call(
+        &mut self,
+        call_id: ScheduledManagementCallId,
+    ) -> Option<ScheduledManagementCall> {
+        self.scheduled_management_calls.remove(&call_id)
+    }
+
+    /// Removes and returns all calls targeting `canister_id`.
+    fn remove_calls_for_canister(
+        &mut self,
+        canister_id: CanisterId,
+    ) -> Vec<ScheduledManagementCall> {
+        let mut removed = Vec::new();
+        self.scheduled_management_calls.retain(|_call_id, call| {
+            if call.effective_canister_id == canister_id {
+                removed.push(call.clone());
+                false
+            } else {
+                true
+            }
+        });
+        removed
+    }
+
+    /// Removes and returns all calls that are due at `time`, ordered by their
+    /// execution time and, for calls due at the same time, by their ID.
+    fn remove_due_calls(
+        &mut self,
+        time: Time,
+    ) -> Vec<(ScheduledManagementCallId, ScheduledManagementCall)> {
+        let mut due = Vec::new();
+        self.scheduled_management_calls.retain(|call_id, call| {
+            if call.execute_at <= time {
+                due.push((*call_id, call.clone()));
+                false
+            } else {
+                true
+            }
+        });
+        due.sort_by_key(|(call_id, call)| (call.execute_at, *call_id));
+        due
+    }
+}
+
 /// It is responsible for keeping track of all subnet messages that
 /// do not require work to be done by another IC layer and
 /// cannot finalize the execution in a single round.
@@ -170,6 +243,7 @@ impl StopCanisterCallManager {
 struct CanisterManagementCalls {
     install_code_call_manager: InstallCodeCallManager,
     stop_canister_call_manager: StopCanisterCallManager,
+    scheduled_management_call_manager: ScheduledManagementCallManager,
 }
 
 impl CanisterManagementCalls {
@@ -396,6 +470,84 @@ impl SubnetCallContextManager {
         self.canister_management_calls.stop_canister_calls_len()
     }
 
+    pub fn push_scheduled_management_call(
+        &mut self,
+        call: ScheduledManagementCall,
+    ) -> ScheduledManagementCallId {
+        self.canister_management_calls
+            .scheduled_management_call_manager
+            .push_call(call)
+    }
+
+    pub fn remove_scheduled_management_call(
+        &mut self,
+        call_id: ScheduledManagementCallId,
+    ) -> Option<ScheduledManagementCall> {
+        self.canister_management_calls
+            .scheduled_management_call_manager
+            .remove_call(call_id)
+    }
+
+    pub fn get_scheduled_management_call(
+        &self,
+        call_id: &ScheduledManagementCallId,
+    ) -> Option<&ScheduledManagementCall> {
+        self.canister_management_calls
+            .scheduled_management_call_manager
+            .scheduled_management_calls
+            .get(call_id)
+    }
+
+    /// Returns the scheduled calls targeting `canister_id`, ordered by ID.
+    pub fn scheduled_management_calls_for_canister(
+        &self,
+        canister_id: CanisterId,
+    ) -> impl Iterator<Item = (&ScheduledManagementCallId, &ScheduledManagementCall)> {
+        self.canister_management_calls
+            .scheduled_management_call_manager
+            .scheduled_management_calls
+            .iter()
+            .filter(move |(_, call)| call.effective_canister_id == canister_id)
+    }
+
+    /// Removes and returns all scheduled calls targeting `canister_id`, e.g.
+    /// when the canister is deleted.
+    pub fn remove_scheduled_management_calls_for_canister(
+        &mut self,
+        canister_id: CanisterId,
+    ) -> Vec<ScheduledManagementCall> {
+        self.canister_management_calls
+            .scheduled_management_call_manager
+            .remove_calls_for_canister(canister_id)
+    }
+
+    /// Removes and returns all scheduled calls that are due at `time`.
+    pub fn remove_due_scheduled_management_calls(
+        &mut self,
+        time: Time,
+    ) -> Vec<(ScheduledManagementCallId, ScheduledManagementCall)> {
+        self.canister_management_calls
+            .scheduled_management_call_manager
+            .remove_due_calls(time)
+    }
+
+    pub fn scheduled_management_calls_len(&self) -> usize {
+        self.canister_management_calls
+            .scheduled_management_call_manager
+            .scheduled_management_calls
+            .len()
+    }
+
+    /// Returns the memory taken by the scheduled calls that are not due yet.
+    pub fn scheduled_management_calls_memory_usage(&self) -> NumBytes {
+        self.canister_management_calls
+            .scheduled_management_call_manager
+            .scheduled_management_calls
+            .values()
+            .map(|call| call.size_bytes())
+            .sum()
+    }
+
     pub fn push_raw_rand_request(
         &mut self,
         request: Request,
@@ -553,6 +705,20 @@ impl From<&SubnetCallContextManager> for pb_metadata::SubnetCallContextManager {
                 .canister_management_calls
                 .stop_canister_call_manager
                 .next_call_id,
+            scheduled_management_calls: item
+                .canister_management_calls
+                .scheduled_management_call_manager
+                .scheduled_management_calls
+                .iter()
+                .map(|(call_id, call)| pb_metadata::ScheduledManagementCallTree {
+                    call_id: call_id.get(),
+                    call: Some(call.into()),
+                })
+                .collect(),
+            next_scheduled_management_call_id: item
+                .canister_management_calls
+                .scheduled_management_call_manager
+                .next_call_id,
             raw_rand_contexts: item
                 .raw_rand_contexts
                 .iter()
@@ -663,6 +829,18 @@ impl TryFrom<(Time, pb_metadata::SubnetCallContextManager)> for SubnetCallContex
             next_call_id: item.next_stop_canister_call_id,
             stop_canister_calls,
         };
+
+        let mut scheduled_management_calls =
+            BTreeMap::<ScheduledManagementCallId, ScheduledManagementCall>::new();
+        for entry in item.scheduled_management_calls {
+            let call: ScheduledManagementCall =
+                try_from_option_field(entry.call, "SystemMetadata::ScheduledManagementCall")?;
+            scheduled_management_calls.insert(ScheduledManagementCallId::new(entry.call_id), call);
+        }
+        let scheduled_management_call_manager = ScheduledManagementCallManager {
+            next_call_id: item.next_scheduled_management_call_id,
+            scheduled_management_calls,
+        };
         let mut raw_rand_contexts = VecDeque::<RawRandContext>::new();
         for pb_context in item.raw_rand_contexts {
             let context = RawRandContext::try_from((time, pb_context))?;
@@ -679,6 +857,7 @@ impl TryFrom<(Time, pb_metadata::SubnetCallContextManager)> for SubnetCallContex
             canister_management_calls: CanisterManagementCalls {
                 install_code_call_manager,
                 stop_canister_call_manager,
+                scheduled_management_call_manager,
             },
             raw_rand_contexts,
             reshare_chain_key_contexts,
@@ -1391,6 +1570,88 @@ impl TryFrom<(Time, pb_metadata::RawRandContext)> for RawRandContext {
     }
 }
 
+/// A management canister call scheduled by a controller of the targeted
+/// canister, to be executed by the system at `execute_at`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ScheduledManagementCall {
+    /// The principal that scheduled the call. The call is executed on its
+    /// behalf.
+    pub sender: PrincipalId,
+    /// The canister targeted by the call.
+    pub effective_canister_id: CanisterId,
+    pub method_name: String,
+    pub method_payload: Vec<u8>,
+    /// Cycles held on behalf of the call, e.g. for a scheduled `deposit_cycles`.
+    pub cycles: Cycles,
+    pub execute_at: Time,
+}
+
+impl ScheduledManagementCall {
+    /// Returns the ID under which the outcome of the call with the given ID is
+    /// recorded in the ingress history of `subnet_id`.
+    pub fn message_id(subnet_id: SubnetId, call_id: ScheduledManagementCallId) -> MessageId {
+        let mut hasher = Sha256::new();
+        hasher.write(SCHEDULED_MANAGEMENT_CALL_DOMAIN);
+        hasher.write(subnet_id.get_ref().as_slice());
+        hasher.write(&call_id.get().to_be_bytes());
+        MessageId::from(hasher.finish())
+    }
+
+    /// Returns the number of bytes taken by the method name and payload of
+    /// the call, which are counted against the subnet memory until the call
+    /// is due.
+    pub fn size_bytes(&self) -> NumBytes {
+        NumBytes::new((self.method_name.len() + self.method_payload.len()) as u64)
+    }
+}
+
+impl From<&ScheduledManagementCall> for pb_metadata::ScheduledManagementCall {
+    fn from(call: &ScheduledManagementCall) -> Self {
+        Self {
+            sender: Some(call.sender.into()),
+            effective_canister_id: Some(call.effective_canister_id.into()),
+            method_name: call.method_name.clone(),
+            method_payload: call.method_payload.clone(),
+            cycles: Some(call.cycles.into()),
+            execute_at: Some(pb_metadata::Time {
+                time_nanos: call.execute_at.as_nanos_since_unix_epoch(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<pb_metadata::ScheduledManagementCall> for ScheduledManagementCall {
+    type Error = ProxyDecodeError;
+    fn try_from(call: pb_metadata::ScheduledManagementCall) -> Result<Self, Self::Error> {
+        let sender: PrincipalId =
+            try_from_option_field(call.sender, "ScheduledManagementCall::sender")?;
+        let effective_canister_id: CanisterId = try_from_option_field(
+            call.effective_canister_id,
+            "ScheduledManagementCall::effective_canister_id",
+        )?;
+        let cycles = call
+            .cycles
+            .ok_or(ProxyDecodeError::MissingField(
+                "ScheduledManagementCall::cycles",
+            ))?
+            .into();
+        let execute_at = call
+            .execute_at
+            .map(|t| Time::from_nanos_since_unix_epoch(t.time_nanos))
+            .ok_or(ProxyDecodeError::MissingField(
+                "ScheduledManagementCall::execute_at",
+            ))?;
+        Ok(ScheduledManagementCall {
+            sender,
+            effective_canister_id,
+            method_name: call.method_name,
+            method_payload: call.method_payload,
+            cycles,
+            execute_at,
+        })
+    }
+}
+
 mod testing {
     use super::*;
 
@@ -1414,6 +1675,7 @@ mod testing {
         let canister_management_calls = CanisterManagementCalls {
             install_code_call_manager: Default::default(),
             stop_canister_call_manager: Default::default(),
+            scheduled_management_call_manager: Default::default(),
         };
         //
         // DO NOT MODIFY WITHOUT READING DOC COMMENT!
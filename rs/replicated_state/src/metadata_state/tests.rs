@@ -1,6 +1,7 @@
 use super::*;
 use crate::metadata_state::subnet_call_context_manager::{
-    InstallCodeCall, RawRandContext, StopCanisterCall, SubnetCallContext, SubnetCallContextManager,
+    InstallCodeCall, RawRandContext, ScheduledManagementCall, StopCanisterCall, SubnetCallContext,
+    SubnetCallContextManager,
 };
 use assert_matches::assert_matches;
 use ic_error_types::{ErrorCode, UserError};
@@ -680,6 +681,51 @@ fn subnet_call_contexts_deserialization() {
     )
 }
 
+#[test]
+fn scheduled_management_calls_round_trip_and_become_due_in_order() {
+    let mut subnet_call_context_manager = SubnetCallContextManager::default();
+    let scheduled_call = |execute_at_secs: u64, method_name: &str| ScheduledManagementCall {
+        sender: canister_test_id(1).get(),
+        effective_canister_id: canister_test_id(2),
+        method_name: method_name.to_string(),
+        method_payload: vec![1, 2, 3],
+        cycles: Cycles::new(7),
+        execute_at: UNIX_EPOCH + Duration::from_secs(execute_at_secs),
+    };
+    let late = subnet_call_context_manager
+        .push_scheduled_management_call(scheduled_call(20, "start_canister"));
+    let early = subnet_call_context_manager
+        .push_scheduled_management_call(scheduled_call(10, "deposit_cycles"));
+    let never = subnet_call_context_manager
+        .push_scheduled_management_call(scheduled_call(30, "stop_canister"));
+
+    // Encode and decode.
+    let proto: ic_protobuf::state::system_metadata::v1::SubnetCallContextManager =
+        (&subnet_call_context_manager).into();
+    let mut deserialized = SubnetCallContextManager::try_from((UNIX_EPOCH, proto)).unwrap();
+    assert_eq!(deserialized, subnet_call_context_manager);
+    assert_eq!(
+        deserialized
+            .scheduled_management_calls_for_canister(canister_test_id(2))
+            .count(),
+        3
+    );
+
+    // Calls become due in the order of their execution time.
+    let due =
+        deserialized.remove_due_scheduled_management_calls(UNIX_EPOCH + Duration::from_secs(20));
+    assert_eq!(
+        due.iter().map(|(call_id, _)| *call_id).collect::<Vec<_>>(),
+        vec![early, late]
+    );
+    assert_eq!(deserialized.scheduled_management_calls_len(), 1);
+    assert!(deserialized.get_scheduled_management_call(&never).is_some());
+
+    // IDs are not reused after the calls are removed.
+    let next = deserialized.push_scheduled_management_call(scheduled_call(40, "start_canister"));
+    assert!(next > never);
+}
+
 #[test]
 fn empty_network_topology() {
     let network_topology = NetworkTopology {
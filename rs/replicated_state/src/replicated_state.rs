@@ -340,7 +340,8 @@ pub struct MemoryTaken {
     /// Execution memory accounts for canister memory reservation where
     /// specified and the actual canister memory usage (including
     /// Wasm custom sections) where no explicit memory reservation
-    /// has been made.
+    /// has been made, as well as the management calls scheduled for
+    /// execution by the system.
     execution: NumBytes,
     /// Memory taken by guaranteed response canister messages or reservations.
     guaranteed_response_messages: NumBytes,
@@ -796,12 +797,17 @@ impl ReplicatedState {
             (self.subnet_queues.best_effort_message_memory_usage() as u64).into();
 
         let canister_snapshots_memory_taken = self.canister_snapshots.memory_taken();
+        let scheduled_management_calls_memory_taken = self
+            .metadata
+            .subnet_call_context_manager
+            .scheduled_management_calls_memory_usage();
 
         MemoryTaken {
             execution: raw_memory_taken
                 + canister_history_memory_taken
                 + wasm_chunk_store_memory_usage
-                + canister_snapshots_memory_taken,
+                + canister_snapshots_memory_taken
+                + scheduled_management_calls_memory_taken,
             guaranteed_response_messages: guaranteed_response_message_memory_taken,
             best_effort_messages: best_effort_message_memory_taken,
             wasm_custom_sections: wasm_custom_sections_memory_taken,
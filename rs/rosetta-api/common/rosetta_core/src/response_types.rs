@@ -370,3 +370,17 @@ impl CallResponse {
         CallResponse { result, idempotent }
     }
 }
+
+/// EventsBlocksResponse contains an ordered collection of BlockEvents and the
+/// max retrievable sequence.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct EventsBlocksResponse {
+    /// max_sequence is the maximum available sequence number to fetch.
+    pub max_sequence: i64,
+
+    /// events is an array of BlockEvents indicating the order to add and remove
+    /// blocks to maintain a canonical view of blockchain state. Lightweight
+    /// clients can use this event stream to update state without implementing
+    /// their own block syncing logic.
+    pub events: Vec<BlockEvent>,
+}
@@ -521,6 +521,13 @@ impl RosettaClient {
         self.call_endpoint("/search/transactions", request).await
     }
 
+    pub async fn events_blocks(
+        &self,
+        request: &EventsBlocksRequest,
+    ) -> Result<EventsBlocksResponse, Error> {
+        self.call_endpoint("/events/blocks", request).await
+    }
+
     pub async fn mempool(
         &self,
         network_identifier: NetworkIdentifier,
@@ -19,3 +19,4 @@ pub const MAX_TRANSACTIONS_PER_SEARCH_TRANSACTIONS_REQUEST: u64 = 10000;
 pub const INGRESS_INTERVAL_OVERLAP: Duration = Duration::from_secs(120);
 pub const STATUS_COMPLETED: &str = "COMPLETED";
 pub const MAX_BLOCKS_PER_QUERY_BLOCK_RANGE_REQUEST: u64 = 10000;
+pub const MAX_BLOCK_EVENTS_PER_EVENTS_BLOCKS_REQUEST: u64 = 10000;
@@ -26,11 +26,8 @@ pub fn get_state_from_network_id(
     network_identifier: &NetworkIdentifier,
     multitoken_state: &MultiTokenAppState,
 ) -> anyhow::Result<Arc<AppState>> {
-    let state = match multitoken_state
-        .token_states
-        .get(network_identifier.network.as_str())
-    {
-        Some(state) => state.clone(),
+    let state = match multitoken_state.get_token_state(network_identifier.network.as_str()) {
+        Some(state) => state,
         None => {
             bail!(
                 "Network Identifier {} not being tracked",
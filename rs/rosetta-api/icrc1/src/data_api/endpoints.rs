@@ -1,4 +1,4 @@
-use super::services::{self, initial_sync_is_completed};
+use super::services;
 use crate::{
     common::{types::Error, utils::utils::get_state_from_network_id},
     MultiTokenAppState,
@@ -12,11 +12,7 @@ use std::sync::Arc;
 // It returns Status Code 200 if an initial sync of the blockchain has been done
 // This means that no gaps in the blockchain exist and the genesis block has already been fetched
 pub async fn ready(State(state): State<Arc<MultiTokenAppState>>) -> (StatusCode, Json<()>) {
-    if state
-        .token_states
-        .values()
-        .all(|state| initial_sync_is_completed(&state.storage, state.synched.clone()))
-    {
+    if state.is_ready() {
         (StatusCode::OK, Json(()))
     } else {
         (StatusCode::SERVICE_UNAVAILABLE, Json(()))
@@ -33,8 +29,8 @@ pub async fn network_list(
 ) -> Json<NetworkListResponse> {
     let response = services::network_list(
         &state
-            .token_states
-            .values()
+            .token_states()
+            .iter()
             .map(|state| state.icrc1_agent.ledger_canister_id)
             .collect::<Vec<_>>(),
     );
@@ -137,6 +133,15 @@ pub async fn search_transactions(
     )?))
 }
 
+pub async fn events_blocks(
+    State(state): State<Arc<MultiTokenAppState>>,
+    Json(request): Json<EventsBlocksRequest>,
+) -> Result<Json<EventsBlocksResponse>> {
+    let state = get_state_from_network_id(&request.network_identifier, &state)
+        .map_err(|err| Error::invalid_network_id(&format!("{:?}", err)))?;
+    Ok(Json(services::events_blocks(&state.storage, &request)?))
+}
+
 pub async fn call(
     State(state): State<Arc<MultiTokenAppState>>,
     Json(request): Json<CallRequest>,
@@ -3,6 +3,7 @@ use std::sync::Mutex;
 
 use crate::common::constants::DEFAULT_BLOCKCHAIN;
 use crate::common::constants::MAX_BLOCKS_PER_QUERY_BLOCK_RANGE_REQUEST;
+use crate::common::constants::MAX_BLOCK_EVENTS_PER_EVENTS_BLOCKS_REQUEST;
 use crate::common::constants::MAX_TRANSACTIONS_PER_SEARCH_TRANSACTIONS_REQUEST;
 use crate::common::constants::STATUS_COMPLETED;
 use crate::common::types::OperationType;
@@ -24,6 +25,7 @@ use ic_ledger_core::tokens::Zero;
 use icrc_ledger_types::icrc1::account::Account;
 use num_bigint::{BigInt, BigUint};
 use rosetta_core::miscellaneous::OperationStatus;
+use rosetta_core::request_types::EventsBlocksRequest;
 use rosetta_core::request_types::SearchTransactionsRequest;
 use rosetta_core::response_types::SearchTransactionsResponse;
 use rosetta_core::{identifiers::*, miscellaneous::Version, objects::*, response_types::*};
@@ -526,6 +528,69 @@ pub fn search_transactions(
     })
 }
 
+/// Returns the block events of the ledger's blockchain. Since blocks of an ICRC-1 ledger are
+/// never removed, every event is a `block_added` event and the sequence number of an event is the
+/// index of the block it adds. This makes sequence numbers stable across restarts of Rosetta and
+/// independent per token in multi-token mode, as each token has its own storage.
+/// Only blocks for which account balances have been processed are returned, so that the event
+/// stream never contains gaps.
+pub fn events_blocks(
+    storage_client: &StorageClient,
+    request: &EventsBlocksRequest,
+) -> Result<EventsBlocksResponse, Error> {
+    let max_sequence = storage_client
+        .get_highest_block_idx_in_account_balance_table()
+        .map_err(|e| Error::unable_to_find_block(&e))?
+        .ok_or_else(|| {
+            Error::unable_to_find_block(&"Highest processed block not found".to_owned())
+        })?;
+
+    let offset = request
+        .offset
+        .map(|offset| {
+            u64::try_from(offset).map_err(|_| {
+                Error::request_processing_error(&format!(
+                    "Offset must be non-negative, but was {}",
+                    offset
+                ))
+            })
+        })
+        .transpose()?;
+    let limit = match request.limit {
+        Some(limit) => u64::try_from(limit).map_err(|_| {
+            Error::request_processing_error(&format!(
+                "Limit must be non-negative, but was {}",
+                limit
+            ))
+        })?,
+        None => MAX_BLOCK_EVENTS_PER_EVENTS_BLOCKS_REQUEST,
+    }
+    .min(MAX_BLOCK_EVENTS_PER_EVENTS_BLOCKS_REQUEST);
+
+    // Without an offset, the `limit` most recent events are returned.
+    let start_sequence = offset.unwrap_or_else(|| (max_sequence + 1).saturating_sub(limit));
+    let events = if limit == 0 || start_sequence > max_sequence {
+        vec![]
+    } else {
+        let end_sequence = std::cmp::min(start_sequence + (limit - 1), max_sequence);
+        storage_client
+            .get_blocks_by_index_range(start_sequence, end_sequence)
+            .map_err(|err| Error::unable_to_find_block(&err))?
+            .into_iter()
+            .map(|block| BlockEvent {
+                sequence: block.index as i64,
+                block_identifier: block.get_block_identifier(),
+                type_: BlockEventType::BlockAdded,
+            })
+            .collect()
+    };
+
+    Ok(EventsBlocksResponse {
+        max_sequence: max_sequence as i64,
+        events,
+    })
+}
+
 pub fn initial_sync_is_completed(
     storage_client: &StorageClient,
     sync_state: Arc<Mutex<Option<bool>>>,
@@ -2228,4 +2293,131 @@ mod test {
             println!("The DISTINCT clause in the aggregation SQL treats them as one account instead of two");
         }
     }
+
+    #[test]
+    fn test_events_blocks_service() {
+        let storage_client_memory = StorageClient::new_in_memory().unwrap();
+        let events_blocks_request = |offset: Option<i64>, limit: Option<i64>| EventsBlocksRequest {
+            network_identifier: NetworkIdentifier::new(
+                DEFAULT_BLOCKCHAIN.to_owned(),
+                Principal::anonymous().to_string(),
+            ),
+            offset,
+            limit,
+        };
+
+        // If no block has been processed yet the service should return an error
+        let err =
+            events_blocks(&storage_client_memory, &events_blocks_request(None, None)).unwrap_err();
+        assert!(err.0.message.contains("Unable to find block"));
+
+        let mut runner = TestRunner::new(TestRunnerConfig {
+            max_shrink_iters: 0,
+            cases: 1,
+            ..Default::default()
+        });
+        runner
+            .run(
+                &(valid_blockchain_strategy::<U256>(BLOCKCHAIN_LENGTH).no_shrink()),
+                |blockchain| {
+                    let rosetta_blocks: Vec<_> = blockchain
+                        .into_iter()
+                        // We only push Mint blocks since `update_account_balances` will
+                        // complain if we e.g., transfer from an account with no balance.
+                        .filter(|block| {
+                            matches!(
+                                block.transaction.operation,
+                                ic_icrc1::Operation::Mint { .. }
+                            )
+                        })
+                        .enumerate()
+                        .map(|(index, block)| {
+                            RosettaBlock::from_generic_block(
+                                encoded_block_to_generic_block(&block.encode()),
+                                index as u64,
+                            )
+                            .unwrap()
+                        })
+                        .collect();
+                    if rosetta_blocks.len() < 3 {
+                        return Ok(());
+                    }
+                    storage_client_memory
+                        .store_blocks(rosetta_blocks.clone())
+                        .unwrap();
+                    storage_client_memory.update_account_balances().unwrap();
+                    let max_sequence = rosetta_blocks.len() as i64 - 1;
+
+                    let expected_events = |start: usize, end: usize| -> Vec<BlockEvent> {
+                        rosetta_blocks[start..end]
+                            .iter()
+                            .map(|block| BlockEvent {
+                                sequence: block.index as i64,
+                                block_identifier: block.clone().get_block_identifier(),
+                                type_: BlockEventType::BlockAdded,
+                            })
+                            .collect()
+                    };
+
+                    // Events from an offset are returned in order and carry the index of the block they add
+                    let response = events_blocks(
+                        &storage_client_memory,
+                        &events_blocks_request(Some(1), Some(2)),
+                    )
+                    .unwrap();
+                    assert_eq!(
+                        response,
+                        EventsBlocksResponse {
+                            max_sequence,
+                            events: expected_events(1, 3),
+                        }
+                    );
+
+                    // Without an offset, the most recent events are returned
+                    let response = events_blocks(
+                        &storage_client_memory,
+                        &events_blocks_request(None, Some(2)),
+                    )
+                    .unwrap();
+                    assert_eq!(
+                        response.events,
+                        expected_events(rosetta_blocks.len() - 2, rosetta_blocks.len())
+                    );
+
+                    // The limit is capped by the tip of the blockchain
+                    let response = events_blocks(
+                        &storage_client_memory,
+                        &events_blocks_request(Some(max_sequence), Some(100)),
+                    )
+                    .unwrap();
+                    assert_eq!(
+                        response.events,
+                        expected_events(rosetta_blocks.len() - 1, rosetta_blocks.len())
+                    );
+
+                    // An offset past the tip yields no events
+                    let response = events_blocks(
+                        &storage_client_memory,
+                        &events_blocks_request(Some(max_sequence + 1), None),
+                    )
+                    .unwrap();
+                    assert!(response.events.is_empty());
+                    assert_eq!(response.max_sequence, max_sequence);
+
+                    // Negative offsets and limits are rejected
+                    assert!(events_blocks(
+                        &storage_client_memory,
+                        &events_blocks_request(Some(-1), None)
+                    )
+                    .is_err());
+                    assert!(events_blocks(
+                        &storage_client_memory,
+                        &events_blocks_request(None, Some(-1))
+                    )
+                    .is_err());
+                    Ok(())
+                },
+            )
+            .unwrap();
+    }
 }
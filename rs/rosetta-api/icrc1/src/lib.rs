@@ -3,6 +3,7 @@
 use anyhow::{bail, Context};
 use common::storage::storage_client::StorageClient;
 use common::storage::types::MetadataEntry;
+use data_api::services::initial_sync_is_completed;
 use ic_base_types::CanisterId;
 use icrc_ledger_agent::Icrc1Agent;
 use icrc_ledger_types::icrc::generic_metadata_value::MetadataValue;
@@ -10,14 +11,15 @@ use icrc_ledger_types::icrc3::archive::ArchiveInfo;
 use num_traits::ToPrimitive;
 use rosetta_core::objects::Currency;
 use std::{
-    collections::HashMap,
-    sync::{Arc, Mutex},
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex, RwLock},
 };
 use tokio::sync::Mutex as AsyncMutex;
 pub mod common;
 pub mod construction_api;
 pub mod data_api;
 pub mod ledger_blocks_synchronization;
+pub mod token_discovery;
 
 /**
  * The AppState struct is used to store the state of a single ledger within the application.
@@ -42,8 +44,67 @@ impl AppState {
  * The MultiTokenAppState struct is used to store the state of the application with all the ledgers.
  */
 pub struct MultiTokenAppState {
-    // A map from canister ids to their respective AppStates
-    pub token_states: HashMap<String, Arc<AppState>>,
+    // A map from canister ids to their respective AppStates.
+    // Ledgers found by token discovery are added while the server is running.
+    token_states: RwLock<HashMap<String, Arc<AppState>>>,
+    // The canister ids of the ledgers that were configured at startup.
+    configured_ledger_ids: HashSet<String>,
+}
+
+impl MultiTokenAppState {
+    /// Creates the state for the ledgers configured at startup.
+    pub fn new(token_states: HashMap<String, Arc<AppState>>) -> Self {
+        Self {
+            configured_ledger_ids: token_states.keys().cloned().collect(),
+            token_states: RwLock::new(token_states),
+        }
+    }
+
+    /// Returns whether Rosetta is ready to serve requests: every ledger configured at startup
+    /// has completed its initial sync and at least one ledger can be served. Discovered ledgers
+    /// do not hold back readiness, they only count once their initial sync is completed.
+    pub fn is_ready(&self) -> bool {
+        let mut num_synced = 0;
+        for (ledger_id, state) in self.token_states.read().unwrap().iter() {
+            if initial_sync_is_completed(&state.storage, state.synched.clone()) {
+                num_synced += 1;
+            } else if self.configured_ledger_ids.contains(ledger_id) {
+                return false;
+            }
+        }
+        num_synced > 0
+    }
+
+    /// Returns the state of the ledger with the given canister id, if it is being tracked.
+    pub fn get_token_state(&self, ledger_id: &str) -> Option<Arc<AppState>> {
+        self.token_states.read().unwrap().get(ledger_id).cloned()
+    }
+
+    /// Returns the states of all ledgers that are currently being tracked.
+    pub fn token_states(&self) -> Vec<Arc<AppState>> {
+        self.token_states
+            .read()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    pub fn contains_token(&self, ledger_id: &str) -> bool {
+        self.token_states.read().unwrap().contains_key(ledger_id)
+    }
+
+    /// Starts tracking the given ledger. Returns false if the ledger was already tracked,
+    /// in which case the existing state is kept.
+    pub fn add_token_state(&self, state: Arc<AppState>) -> bool {
+        let mut token_states = self.token_states.write().unwrap();
+        let ledger_id = state.ledger_id.to_string();
+        if token_states.contains_key(&ledger_id) {
+            return false;
+        }
+        token_states.insert(ledger_id, state);
+        true
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -1,11 +1,13 @@
 #![allow(clippy::disallowed_types)]
 use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
 use axum::{
     body::Body,
     extract::Request,
     routing::{get, post},
     Router,
 };
+use candid::Principal;
 use clap::{Parser, ValueEnum};
 use ic_agent::{identity::AnonymousIdentity, Agent};
 use ic_base_types::{CanisterId, PrincipalId};
@@ -18,6 +20,10 @@ use ic_icrc_rosetta::{
     ledger_blocks_synchronization::blocks_synchronizer::{
         start_synching_blocks, RecurrencyConfig, RecurrencyMode,
     },
+    token_discovery::{
+        run_token_discovery_round, validate_sources, TokenDiscoveryEnvironment,
+        TokenDiscoverySource,
+    },
     AppState, Metadata, MultiTokenAppState,
 };
 use ic_sys::fs::write_string_using_tmp_file;
@@ -33,7 +39,7 @@ use tokio::{net::TcpListener, sync::Mutex as AsyncMutex};
 use tower_http::classify::{ServerErrorsAsFailures, SharedClassifier};
 use tower_http::trace::TraceLayer;
 use tower_request_id::{RequestId, RequestIdLayer};
-use tracing::{debug, error, error_span, info, Level, Span};
+use tracing::{debug, error, error_span, info, warn, Level, Span};
 use tracing::{level_filters::LevelFilter, Instrument};
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::layer::SubscriberExt;
@@ -113,7 +119,7 @@ impl TokenDef {
     }
 }
 
-#[derive(Debug, Parser)]
+#[derive(Clone, Debug, Parser)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     #[arg(short, long)]
@@ -183,6 +189,23 @@ struct Args {
     /// Timeout in seconds for sync watchdog. If no synchronization is attempted within this time, the sync thread will be restarted.
     #[arg(long = "watchdog-timeout-seconds", default_value = "60")]
     watchdog_timeout_seconds: u64,
+
+    /// Discover the ledgers of all deployed SNSes by periodically querying this SNS-W canister.
+    #[arg(long)]
+    discover_tokens_from_sns_wasm: Option<CanisterId>,
+
+    /// Discover the ckERC20 ledgers by periodically querying this ledger suite orchestrator.
+    #[arg(long)]
+    discover_tokens_from_ledger_suite_orchestrator: Option<CanisterId>,
+
+    /// Discover the ledgers listed in this file, one ledger canister id per line.
+    /// The file is re-read periodically, so ledgers can be added while Rosetta is running.
+    #[arg(long)]
+    discover_tokens_from_file: Option<PathBuf>,
+
+    /// Interval in seconds between two token discovery rounds.
+    #[arg(long, default_value = "600", value_parser = clap::value_parser!(u64).range(1..))]
+    token_discovery_interval_seconds: u64,
 }
 
 impl Args {
@@ -201,6 +224,20 @@ impl Args {
         }
     }
 
+    fn token_discovery_sources(&self) -> Vec<TokenDiscoverySource> {
+        let mut sources = vec![];
+        if let Some(canister_id) = self.discover_tokens_from_sns_wasm {
+            sources.push(TokenDiscoverySource::SnsWasm(canister_id));
+        }
+        if let Some(canister_id) = self.discover_tokens_from_ledger_suite_orchestrator {
+            sources.push(TokenDiscoverySource::LedgerSuiteOrchestrator(canister_id));
+        }
+        if let Some(path) = &self.discover_tokens_from_file {
+            sources.push(TokenDiscoverySource::File(path.clone()));
+        }
+        sources
+    }
+
     fn effective_network_url(&self) -> String {
         self.network_url.clone().unwrap_or_else(|| {
             if self.is_mainnet() {
@@ -357,6 +394,10 @@ fn extract_token_defs(args: &Args) -> Result<Vec<TokenDef>> {
     // If no tokens are provided, use the legacy arguments
     if input_tokens.is_empty() {
         if args.ledger_id.is_none() {
+            // All tokens can be discovered at runtime.
+            if !args.token_discovery_sources().is_empty() {
+                return Ok(vec![]);
+            }
             bail!("No token definitions provided");
         }
 
@@ -391,101 +432,220 @@ fn extract_token_defs(args: &Args) -> Result<Vec<TokenDef>> {
     Ok(token_defs)
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let args = Args::parse();
+async fn build_ic_agent(args: &Args) -> Result<Agent> {
+    let network_url = args.effective_network_url();
 
-    let _guard = init_logs(args.log_level, &args.log_file)?;
+    let ic_agent = Agent::builder()
+        .with_identity(AnonymousIdentity)
+        .with_url(
+            Url::parse(&network_url)
+                .context(format!("Failed to parse URL {}", network_url.clone()))?,
+        )
+        .with_http_client(reqwest::Client::new())
+        .build()?;
 
-    // Initialize rosetta metrics with a default canister ID
-    // This will be updated for specific token operations but is required for middleware setup
-    let rosetta_metrics = RosettaMetrics::new("icrc1".to_string(), "icrc1_default".to_string());
+    // Only fetch root key if the network is not the mainnet
+    if !args.is_mainnet() {
+        debug!("Network type is not mainnet --> Trying to fetch root key");
+        ic_agent.fetch_root_key().await?;
+    }
 
-    let token_defs = extract_token_defs(&args)?;
-    let mut token_states = HashMap::new();
+    debug!("Rosetta connects to : {}", network_url);
 
-    for token_def in token_defs.iter() {
-        let network_url = args.effective_network_url();
+    debug!(
+        "Network status is : {:?}",
+        ic_agent.status().await?.replica_health_status
+    );
 
-        let ic_agent = Agent::builder()
-            .with_identity(AnonymousIdentity)
-            .with_url(
-                Url::parse(&network_url)
-                    .context(format!("Failed to parse URL {}", network_url.clone()))?,
-            )
-            .with_http_client(reqwest::Client::new())
-            .build()?;
+    Ok(ic_agent)
+}
+
+// Connects to the ledger of the given token, loads its metadata and opens its store.
+async fn initialize_token_state(args: &Args, token_def: &TokenDef) -> Result<Arc<AppState>> {
+    let icrc1_agent = Arc::new(Icrc1Agent {
+        agent: build_ic_agent(args).await?,
+        ledger_canister_id: token_def.ledger_id.into(),
+    });
 
-        // Only fetch root key if the network is not the mainnet
-        if !args.is_mainnet() {
-            debug!("Network type is not mainnet --> Trying to fetch root key");
-            ic_agent.fetch_root_key().await?;
+    let mut storage = match args.store_type {
+        StoreType::InMemory => StorageClient::new_in_memory()?,
+        StoreType::File => {
+            let mut path = args.multi_tokens_store_dir.clone();
+            path.push(format!("{}.db", PrincipalId::from(token_def.ledger_id)));
+            StorageClient::new_persistent(&path)
+                .with_context(|| format!("Failed to open the store at {}", path.display()))?
         }
+    };
 
-        debug!("Rosetta connects to : {}", network_url);
+    let metadata = load_metadata(token_def, &icrc1_agent, &storage, args.offline).await?;
 
-        debug!(
-            "Network status is : {:?}",
-            ic_agent.status().await?.replica_health_status
+    if token_def.icrc1_symbol.is_some()
+        && metadata.symbol != token_def.icrc1_symbol.clone().unwrap()
+    {
+        bail!(
+            "Provided symbol does not match symbol retrieved in online mode. Expected: {}, Got: {}",
+            metadata.symbol,
+            token_def.icrc1_symbol.clone().unwrap()
         );
+    }
 
-        let icrc1_agent = Arc::new(Icrc1Agent {
-            agent: ic_agent,
-            ledger_canister_id: token_def.ledger_id.into(),
-        });
+    info!(
+        "ICRC Rosetta is connected to the ICRC-1 ledger: {}",
+        token_def.ledger_id
+    );
+    info!(
+        "The token symbol of the ICRC-1 ledger is: {}",
+        metadata.symbol
+    );
+
+    let storage_metadata = metadata.clone();
+
+    storage.initialize(TokenInfo::new(
+        storage_metadata.symbol,
+        storage_metadata.decimals,
+        token_def.ledger_id,
+    ));
+
+    Ok(Arc::new(AppState {
+        icrc1_agent: icrc1_agent.clone(),
+        ledger_id: token_def.ledger_id,
+        synched: Arc::new(Mutex::new(None)),
+        storage: Arc::new(storage),
+        archive_canister_ids: Arc::new(AsyncMutex::new(vec![])),
+        metadata,
+    }))
+}
 
-        let mut storage = match args.store_type {
-            StoreType::InMemory => StorageClient::new_in_memory()?,
-            StoreType::File => {
-                let mut path = args.multi_tokens_store_dir.clone();
-                path.push(format!("{}.db", PrincipalId::from(token_def.ledger_id)));
-                StorageClient::new_persistent(&path).unwrap()
-            }
+// Token discovery against the IC that Rosetta is connected to.
+struct AgentTokenDiscoveryEnvironment {
+    args: Args,
+    agent: Agent,
+}
+
+#[async_trait]
+impl TokenDiscoveryEnvironment for AgentTokenDiscoveryEnvironment {
+    async fn query(
+        &self,
+        canister_id: CanisterId,
+        method_name: &str,
+        arg: Vec<u8>,
+    ) -> Result<Vec<u8>> {
+        Ok(self
+            .agent
+            .query(&Principal::from(canister_id), method_name)
+            .with_arg(arg)
+            .call()
+            .await?)
+    }
+
+    async fn initialize_token_state(&self, ledger_id: CanisterId) -> Result<Arc<AppState>> {
+        let token_def = TokenDef {
+            ledger_id,
+            icrc1_symbol: None,
+            icrc1_decimals: None,
         };
+        initialize_token_state(&self.args, &token_def).await
+    }
+}
 
-        let metadata = load_metadata(token_def, &icrc1_agent, &storage, args.offline).await?;
+// Runs a token discovery round, see [run_token_discovery_round].
+async fn discover_tokens(
+    args: &Args,
+    sources: &[TokenDiscoverySource],
+    token_app_states: &MultiTokenAppState,
+) -> Result<Vec<Arc<AppState>>> {
+    let env = AgentTokenDiscoveryEnvironment {
+        args: args.clone(),
+        agent: build_ic_agent(args).await?,
+    };
+    Ok(run_token_discovery_round(&env, sources, token_app_states).await)
+}
 
-        if token_def.icrc1_symbol.is_some()
-            && metadata.symbol != token_def.icrc1_symbol.clone().unwrap()
-        {
-            bail!(
-                    "Provided symbol does not match symbol retrieved in online mode. Expected: {}, Got: {}",
-                    metadata.symbol, token_def.icrc1_symbol.clone().unwrap()
-                );
+// Spawns a watchdog thread that repeatedly syncs the blocks of the given token.
+fn spawn_sync_thread(shared_state: Arc<AppState>, watchdog_timeout_seconds: u64) {
+    let token_name = shared_state.ledger_display_name();
+    let span = tracing::info_span!("sync", token = %token_name);
+    let span_watchdog = span.clone();
+
+    info!(
+        "Configuring watchdog for {} with timeout of {} seconds",
+        token_name, watchdog_timeout_seconds
+    );
+
+    tokio::spawn(
+        async move {
+            // First heartbeat might take hours until the ledger is initially synced,
+            // so we skip it to avoid the watchdog thread to restart the sync thread
+            // during the initial synchronization.
+            let skip_first_hearbeat = true;
+            let local_state = Arc::clone(&shared_state);
+            let mut watchdog = WatchdogThread::new(
+                Duration::from_secs(watchdog_timeout_seconds),
+                Some(Arc::new(move || {
+                    local_state.storage.get_metrics().inc_sync_thread_restarts();
+                    info!("Watchdog triggered restart for a sync thread");
+                })),
+                skip_first_hearbeat,
+                Some(span_watchdog.clone()),
+            );
+            let span_watchdog = span_watchdog.clone();
+            watchdog.start(move |heartbeat| {
+                let shared_state = Arc::clone(&shared_state);
+                let span_watchdog = span_watchdog.clone();
+                tokio::spawn(
+                    async move {
+                        if let Err(e) = start_synching_blocks(
+                            shared_state.icrc1_agent.clone(),
+                            shared_state.storage.clone(),
+                            *MAXIMUM_BLOCKS_PER_REQUEST,
+                            shared_state.archive_canister_ids.clone(),
+                            RecurrencyMode::Recurrent(RecurrencyConfig {
+                                min_recurrency_wait: Duration::from_secs(BLOCK_SYNC_WAIT_SECS),
+                                max_recurrency_wait: Duration::from_secs(MAX_BLOCK_SYNC_WAIT_SECS),
+                                backoff_factor: 2,
+                            }),
+                            Box::new(heartbeat),
+                        )
+                        .await
+                        {
+                            error!("Sync error for token {:?}: {:?}", shared_state.ledger_id, e);
+                        }
+                    }
+                    .instrument(span_watchdog),
+                )
+            });
         }
+        .instrument(span),
+    );
+}
 
-        info!(
-            "ICRC Rosetta is connected to the ICRC-1 ledger: {}",
-            token_def.ledger_id
-        );
-        info!(
-            "The token symbol of the ICRC-1 ledger is: {}",
-            metadata.symbol
-        );
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
 
-        let storage_metadata = metadata.clone();
-
-        storage.initialize(TokenInfo::new(
-            storage_metadata.symbol,
-            storage_metadata.decimals,
-            token_def.ledger_id,
-        ));
-
-        let shared_state = Arc::new(AppState {
-            icrc1_agent: icrc1_agent.clone(),
-            ledger_id: token_def.ledger_id,
-            synched: Arc::new(Mutex::new(None)),
-            storage: Arc::new(storage),
-            archive_canister_ids: Arc::new(AsyncMutex::new(vec![])),
-            metadata,
-        });
+    let _guard = init_logs(args.log_level, &args.log_file)?;
+
+    // Initialize rosetta metrics with a default canister ID
+    // This will be updated for specific token operations but is required for middleware setup
+    let rosetta_metrics = RosettaMetrics::new("icrc1".to_string(), "icrc1_default".to_string());
 
-        token_states.insert(token_def.ledger_id.to_string(), shared_state.clone());
+    let discovery_sources = args.token_discovery_sources();
+    validate_sources(&discovery_sources, args.offline)?;
+
+    let token_defs = extract_token_defs(&args)?;
+    let mut token_states = HashMap::new();
+
+    for token_def in token_defs.iter() {
+        let shared_state = initialize_token_state(&args, token_def).await?;
+        token_states.insert(token_def.ledger_id.to_string(), shared_state);
     }
 
-    let token_app_states = Arc::new(MultiTokenAppState {
-        token_states: token_states.clone(),
-    });
+    let token_app_states = Arc::new(MultiTokenAppState::new(token_states));
+
+    if !discovery_sources.is_empty() {
+        info!("Running initial token discovery");
+        discover_tokens(&args, &discovery_sources, &token_app_states).await?;
+    }
 
     if args.exit_on_sync {
         if args.offline {
@@ -493,20 +653,18 @@ async fn main() -> Result<()> {
         }
 
         info!("Starting to sync blocks");
-        let futures = token_app_states
-            .token_states
-            .values()
-            .map(|shared_state| async move {
-                start_synching_blocks(
-                    shared_state.icrc1_agent.clone(),
-                    shared_state.storage.clone(),
-                    *MAXIMUM_BLOCKS_PER_REQUEST,
-                    shared_state.archive_canister_ids.clone(),
-                    RecurrencyMode::OneShot,
-                    Box::new(|| {}), // <-- no-op heartbeat
-                )
-                .await
-            });
+        let token_states = token_app_states.token_states();
+        let futures = token_states.iter().map(|shared_state| async move {
+            start_synching_blocks(
+                shared_state.icrc1_agent.clone(),
+                shared_state.storage.clone(),
+                *MAXIMUM_BLOCKS_PER_REQUEST,
+                shared_state.archive_canister_ids.clone(),
+                RecurrencyMode::OneShot,
+                Box::new(|| {}), // <-- no-op heartbeat
+            )
+            .await
+        });
         let results = futures::future::join_all(futures).await;
 
         for result in results {
@@ -531,6 +689,7 @@ async fn main() -> Result<()> {
         .route("/account/balance", post(account_balance))
         .route("/block/transaction", post(block_transaction))
         .route("/search/transactions", post(search_transactions))
+        .route("/events/blocks", post(events_blocks))
         .route("/mempool", post(mempool))
         .route("/mempool/transaction", post(mempool_transaction))
         .route("/construction/derive", post(construction_derive))
@@ -555,7 +714,7 @@ async fn main() -> Result<()> {
     let rosetta_url = format!("0.0.0.0:{}", args.get_port());
     let tcp_listener = TcpListener::bind(rosetta_url.clone()).await?;
 
-    if let Some(port_file) = args.port_file {
+    if let Some(port_file) = &args.port_file {
         write_string_using_tmp_file(
             port_file,
             tcp_listener.local_addr()?.port().to_string().as_str(),
@@ -564,70 +723,35 @@ async fn main() -> Result<()> {
 
     if !args.offline {
         // For each token state, spawn a watchdog thread that repeatedly syncs blocks.
-        for shared_state in token_app_states.token_states.values() {
-            let token_name = shared_state.ledger_display_name();
-            let shared_state = Arc::clone(shared_state);
-            let span = tracing::info_span!("sync", token = %token_name);
-            let span_watchdog = span.clone();
-
-            info!(
-                "Configuring watchdog for {} with timeout of {} seconds",
-                token_name, args.watchdog_timeout_seconds
-            );
+        for shared_state in token_app_states.token_states() {
+            spawn_sync_thread(shared_state, args.watchdog_timeout_seconds);
+        }
+    }
 
-            tokio::spawn(
-                async move {
-                    // First heartbeat might take hours until the ledger is initially synced,
-                    // so we skip it to avoid the watchdog thread to restart the sync thread
-                    // during the initial synchronization.
-                    let skip_first_hearbeat = true;
-                    let local_state = Arc::clone(&shared_state);
-                    let mut watchdog = WatchdogThread::new(
-                        Duration::from_secs(args.watchdog_timeout_seconds),
-                        Some(Arc::new(move || {
-                            local_state.storage.get_metrics().inc_sync_thread_restarts();
-                            info!("Watchdog triggered restart for a sync thread");
-                        })),
-                        skip_first_hearbeat,
-                        Some(span_watchdog.clone()),
-                    );
-                    let span_watchdog = span_watchdog.clone();
-                    watchdog.start(move |heartbeat| {
-                        let shared_state = Arc::clone(&shared_state);
-                        let span_watchdog = span_watchdog.clone();
-                        tokio::spawn(
-                            async move {
-                                if let Err(e) = start_synching_blocks(
-                                    shared_state.icrc1_agent.clone(),
-                                    shared_state.storage.clone(),
-                                    *MAXIMUM_BLOCKS_PER_REQUEST,
-                                    shared_state.archive_canister_ids.clone(),
-                                    RecurrencyMode::Recurrent(RecurrencyConfig {
-                                        min_recurrency_wait: Duration::from_secs(
-                                            BLOCK_SYNC_WAIT_SECS,
-                                        ),
-                                        max_recurrency_wait: Duration::from_secs(
-                                            MAX_BLOCK_SYNC_WAIT_SECS,
-                                        ),
-                                        backoff_factor: 2,
-                                    }),
-                                    Box::new(heartbeat),
-                                )
-                                .await
-                                {
-                                    error!(
-                                        "Sync error for token {:?}: {:?}",
-                                        shared_state.ledger_id, e
-                                    );
-                                }
+    if !discovery_sources.is_empty() {
+        let token_app_states = token_app_states.clone();
+        let args = args.clone();
+        tokio::spawn(
+            async move {
+                let mut interval = tokio::time::interval(Duration::from_secs(
+                    args.token_discovery_interval_seconds,
+                ));
+                // The first tick completes immediately and the initial round already ran.
+                interval.tick().await;
+                loop {
+                    interval.tick().await;
+                    match discover_tokens(&args, &discovery_sources, &token_app_states).await {
+                        Ok(new_token_states) => {
+                            for shared_state in new_token_states {
+                                spawn_sync_thread(shared_state, args.watchdog_timeout_seconds);
                             }
-                            .instrument(span_watchdog),
-                        )
-                    });
+                        }
+                        Err(err) => warn!("Token discovery round failed: {:?}", err),
+                    }
                 }
-                .instrument(span),
-            );
-        }
+            }
+            .instrument(tracing::info_span!("token_discovery")),
+        );
     }
 
     info!("Starting Rosetta server");
@@ -0,0 +1,473 @@
+//! Discovery of ICRC-1 ledgers that are not configured at startup.
+//!
+//! In discovery mode, Rosetta periodically asks one or more sources for the ledgers they know
+//! about and starts syncing every ledger it is not tracking yet. The supported sources are the
+//! SNS-W canister (ledgers of deployed SNSes), the ledger suite orchestrator (ckERC20 ledgers)
+//! and a local file that lists ledger canister ids, which stands in for the canisters on test
+//! networks.
+
+use anyhow::{bail, Context};
+use async_trait::async_trait;
+use candid::{CandidType, Decode, Deserialize, Encode, Principal};
+use ic_base_types::{CanisterId, PrincipalId};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::{AppState, MultiTokenAppState};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TokenDiscoverySource {
+    /// The SNS-W canister, queried with `list_deployed_snses`.
+    SnsWasm(CanisterId),
+    /// The ledger suite orchestrator, queried with `get_orchestrator_info`.
+    LedgerSuiteOrchestrator(CanisterId),
+    /// A file with one ledger canister id per line. Empty lines and lines starting with `#`
+    /// are ignored. The file is re-read on every discovery round.
+    File(PathBuf),
+}
+
+impl std::fmt::Display for TokenDiscoverySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SnsWasm(canister_id) => write!(f, "SNS-W canister {}", canister_id),
+            Self::LedgerSuiteOrchestrator(canister_id) => {
+                write!(f, "ledger suite orchestrator {}", canister_id)
+            }
+            Self::File(path) => write!(f, "file {}", path.display()),
+        }
+    }
+}
+
+// The types below only contain the fields of the SNS-W and ledger suite orchestrator
+// responses that are needed to find ledgers. Candid ignores the remaining record fields
+// when decoding.
+
+#[derive(CandidType, Deserialize)]
+struct ListDeployedSnsesRequest {}
+
+#[derive(CandidType, Deserialize, Debug, Default)]
+pub struct ListDeployedSnsesResponse {
+    pub instances: Vec<DeployedSns>,
+}
+
+#[derive(CandidType, Deserialize, Debug, Default)]
+pub struct DeployedSns {
+    pub ledger_canister_id: Option<Principal>,
+}
+
+#[derive(CandidType, Deserialize, Debug, Default)]
+pub struct OrchestratorInfo {
+    pub managed_canisters: Vec<ManagedCanisters>,
+}
+
+#[derive(CandidType, Deserialize, Debug, Default)]
+pub struct ManagedCanisters {
+    pub ledger: Option<ManagedCanisterStatus>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum ManagedCanisterStatus {
+    Created {
+        canister_id: Principal,
+    },
+    Installed {
+        canister_id: Principal,
+        installed_wasm_hash: String,
+    },
+}
+
+/// Returns the ledgers of all deployed SNSes.
+pub fn ledger_ids_from_deployed_snses(
+    response: ListDeployedSnsesResponse,
+) -> anyhow::Result<Vec<CanisterId>> {
+    response
+        .instances
+        .into_iter()
+        .filter_map(|sns| sns.ledger_canister_id)
+        .map(|ledger_id| {
+            CanisterId::try_from_principal_id(PrincipalId::from(ledger_id))
+                .with_context(|| format!("Invalid SNS ledger canister id {}", ledger_id))
+        })
+        .collect()
+}
+
+/// Returns the ledgers managed by the orchestrator. Ledgers that were created but do not have
+/// their wasm module installed yet are skipped, as they cannot be synced.
+pub fn ledger_ids_from_orchestrator_info(
+    info: OrchestratorInfo,
+) -> anyhow::Result<Vec<CanisterId>> {
+    info.managed_canisters
+        .into_iter()
+        .filter_map(|managed| match managed.ledger {
+            Some(ManagedCanisterStatus::Installed { canister_id, .. }) => Some(canister_id),
+            Some(ManagedCanisterStatus::Created { .. }) | None => None,
+        })
+        .map(|ledger_id| {
+            CanisterId::try_from_principal_id(PrincipalId::from(ledger_id))
+                .with_context(|| format!("Invalid managed ledger canister id {}", ledger_id))
+        })
+        .collect()
+}
+
+/// Parses the contents of a token discovery file.
+pub fn parse_ledger_ids_file(contents: &str) -> anyhow::Result<Vec<CanisterId>> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let principal_id = PrincipalId::from_str(line)
+                .with_context(|| format!("Invalid ledger canister id {}", line))?;
+            CanisterId::try_from_principal_id(principal_id)
+                .with_context(|| format!("Invalid ledger canister id {}", line))
+        })
+        .collect()
+}
+
+/// The interactions of token discovery with the IC, which are mocked in tests.
+#[async_trait]
+pub trait TokenDiscoveryEnvironment: Send + Sync {
+    /// Sends a query call to the given canister and returns the reply.
+    async fn query(
+        &self,
+        canister_id: CanisterId,
+        method_name: &str,
+        arg: Vec<u8>,
+    ) -> anyhow::Result<Vec<u8>>;
+
+    /// Connects to the given ledger, loads its metadata and opens its store.
+    async fn initialize_token_state(&self, ledger_id: CanisterId) -> anyhow::Result<Arc<AppState>>;
+}
+
+/// Returns the ledgers known to the given source.
+pub async fn discover_ledger_ids(
+    env: &dyn TokenDiscoveryEnvironment,
+    source: &TokenDiscoverySource,
+) -> anyhow::Result<Vec<CanisterId>> {
+    match source {
+        TokenDiscoverySource::SnsWasm(canister_id) => {
+            let response = env
+                .query(
+                    *canister_id,
+                    "list_deployed_snses",
+                    Encode!(&ListDeployedSnsesRequest {})?,
+                )
+                .await
+                .with_context(|| format!("Failed to list deployed SNSes from {}", source))?;
+            ledger_ids_from_deployed_snses(Decode!(&response, ListDeployedSnsesResponse)?)
+        }
+        TokenDiscoverySource::LedgerSuiteOrchestrator(canister_id) => {
+            let response = env
+                .query(*canister_id, "get_orchestrator_info", Encode!()?)
+                .await
+                .with_context(|| format!("Failed to get orchestrator info from {}", source))?;
+            ledger_ids_from_orchestrator_info(Decode!(&response, OrchestratorInfo)?)
+        }
+        TokenDiscoverySource::File(path) => {
+            let contents = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", source))?;
+            parse_ledger_ids_file(&contents)
+        }
+    }
+}
+
+/// Queries all discovery sources and starts tracking the ledgers that are not tracked yet.
+/// Returns the states of the newly tracked ledgers. A source or ledger that cannot be reached
+/// is skipped and retried in the next round.
+pub async fn run_token_discovery_round(
+    env: &dyn TokenDiscoveryEnvironment,
+    sources: &[TokenDiscoverySource],
+    multitoken_state: &MultiTokenAppState,
+) -> Vec<Arc<AppState>> {
+    let mut discovered = vec![];
+    for source in sources {
+        match discover_ledger_ids(env, source).await {
+            Ok(ledger_ids) => discovered.extend(ledger_ids),
+            Err(err) => warn!("Token discovery from {} failed: {:?}", source, err),
+        }
+    }
+
+    let mut new_token_states = vec![];
+    for ledger_id in untracked_ledger_ids(discovered, multitoken_state) {
+        match env.initialize_token_state(ledger_id).await {
+            Ok(shared_state) => {
+                if multitoken_state.add_token_state(shared_state.clone()) {
+                    info!("Discovered new ICRC-1 ledger: {}", ledger_id);
+                    new_token_states.push(shared_state);
+                }
+            }
+            Err(err) => warn!(
+                "Failed to start tracking discovered ledger {}: {:?}",
+                ledger_id, err
+            ),
+        }
+    }
+    new_token_states
+}
+
+/// Returns the discovered ledgers that are not tracked yet, without duplicates and in the
+/// order in which they were discovered.
+pub fn untracked_ledger_ids(
+    discovered: Vec<CanisterId>,
+    multitoken_state: &MultiTokenAppState,
+) -> Vec<CanisterId> {
+    let mut seen = HashSet::new();
+    discovered
+        .into_iter()
+        .filter(|ledger_id| {
+            seen.insert(*ledger_id) && !multitoken_state.contains_token(&ledger_id.to_string())
+        })
+        .collect()
+}
+
+/// Checks that discovery is configured in a way that can work.
+pub fn validate_sources(sources: &[TokenDiscoverySource], offline: bool) -> anyhow::Result<()> {
+    if !sources.is_empty() && offline {
+        bail!("Token discovery cannot be used in offline mode.");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{common::storage::storage_client::StorageClient, Metadata};
+    use ic_agent::Agent;
+    use icrc_ledger_agent::Icrc1Agent;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use tokio::sync::Mutex as AsyncMutex;
+
+    fn canister_id(id: u64) -> CanisterId {
+        CanisterId::from_u64(id)
+    }
+
+    fn token_state(ledger_id: CanisterId, synced: bool) -> Arc<AppState> {
+        let agent = Agent::builder()
+            .with_url("http://localhost:8080")
+            .build()
+            .unwrap();
+        Arc::new(AppState {
+            icrc1_agent: Arc::new(Icrc1Agent {
+                agent,
+                ledger_canister_id: ledger_id.into(),
+            }),
+            ledger_id,
+            synched: Arc::new(Mutex::new(synced.then_some(true))),
+            storage: Arc::new(StorageClient::new_in_memory().unwrap()),
+            archive_canister_ids: Arc::new(AsyncMutex::new(vec![])),
+            metadata: Metadata::from_args("TKN".to_string(), 8),
+        })
+    }
+
+    // An SNS-W canister that lists the given SNS ledgers, whose ledgers can be initialized
+    // unless they are unreachable.
+    struct MockEnvironment {
+        sns_wasm_id: CanisterId,
+        sns_ledger_ids: Vec<CanisterId>,
+        unreachable_ledger_ids: Vec<CanisterId>,
+    }
+
+    #[async_trait]
+    impl TokenDiscoveryEnvironment for MockEnvironment {
+        async fn query(
+            &self,
+            canister_id: CanisterId,
+            method_name: &str,
+            arg: Vec<u8>,
+        ) -> anyhow::Result<Vec<u8>> {
+            if canister_id != self.sns_wasm_id || method_name != "list_deployed_snses" {
+                bail!(
+                    "Canister {} has no query method {}",
+                    canister_id,
+                    method_name
+                );
+            }
+            Decode!(&arg, ListDeployedSnsesRequest)?;
+            let response = ListDeployedSnsesResponse {
+                instances: self
+                    .sns_ledger_ids
+                    .iter()
+                    .map(|ledger_id| DeployedSns {
+                        ledger_canister_id: Some(ledger_id.get().0),
+                    })
+                    .collect(),
+            };
+            Ok(Encode!(&response)?)
+        }
+
+        async fn initialize_token_state(
+            &self,
+            ledger_id: CanisterId,
+        ) -> anyhow::Result<Arc<AppState>> {
+            if self.unreachable_ledger_ids.contains(&ledger_id) {
+                bail!("Ledger {} is unreachable", ledger_id);
+            }
+            Ok(token_state(ledger_id, false))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_discovery_round_does_not_hold_back_readiness() {
+        let sns_wasm_id = canister_id(100);
+        let configured_ledger_id = canister_id(1);
+        let multitoken_state = MultiTokenAppState::new(HashMap::from([(
+            configured_ledger_id.to_string(),
+            token_state(configured_ledger_id, true),
+        )]));
+        assert!(multitoken_state.is_ready());
+
+        let env = MockEnvironment {
+            sns_wasm_id,
+            sns_ledger_ids: vec![configured_ledger_id, canister_id(2), canister_id(3)],
+            unreachable_ledger_ids: vec![canister_id(3)],
+        };
+        let sources = vec![
+            TokenDiscoverySource::SnsWasm(sns_wasm_id),
+            TokenDiscoverySource::LedgerSuiteOrchestrator(canister_id(200)),
+        ];
+        let new_token_states = run_token_discovery_round(&env, &sources, &multitoken_state).await;
+
+        // The configured ledger is already tracked, the unreachable one is skipped and the
+        // failing source does not prevent the others from being queried.
+        assert_eq!(
+            new_token_states
+                .iter()
+                .map(|state| state.ledger_id)
+                .collect::<Vec<_>>(),
+            vec![canister_id(2)]
+        );
+        assert!(multitoken_state.contains_token(&canister_id(2).to_string()));
+        assert!(!multitoken_state.contains_token(&canister_id(3).to_string()));
+        // The discovered ledger has not synced yet, but it does not make Rosetta unready.
+        assert!(multitoken_state.is_ready());
+
+        // Tracked ledgers are not initialized again.
+        assert!(run_token_discovery_round(&env, &sources, &multitoken_state)
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_readiness_with_discovered_tokens_only() {
+        let sns_wasm_id = canister_id(100);
+        let multitoken_state = MultiTokenAppState::new(HashMap::new());
+        // There is no ledger to serve.
+        assert!(!multitoken_state.is_ready());
+
+        let env = MockEnvironment {
+            sns_wasm_id,
+            sns_ledger_ids: vec![canister_id(1)],
+            unreachable_ledger_ids: vec![],
+        };
+        let new_token_states = run_token_discovery_round(
+            &env,
+            &[TokenDiscoverySource::SnsWasm(sns_wasm_id)],
+            &multitoken_state,
+        )
+        .await;
+        assert_eq!(new_token_states.len(), 1);
+        // The discovered ledger only counts once its initial sync is completed.
+        assert!(!multitoken_state.is_ready());
+        *new_token_states[0].synched.lock().unwrap() = Some(true);
+        assert!(multitoken_state.is_ready());
+    }
+
+    #[test]
+    fn test_configured_tokens_must_be_synced() {
+        let multitoken_state = MultiTokenAppState::new(HashMap::from([
+            (
+                canister_id(1).to_string(),
+                token_state(canister_id(1), true),
+            ),
+            (
+                canister_id(2).to_string(),
+                token_state(canister_id(2), false),
+            ),
+        ]));
+        assert!(!multitoken_state.is_ready());
+    }
+
+    #[test]
+    fn test_parse_ledger_ids_file() {
+        let contents = format!(
+            "# ckERC20 ledgers\n{}\n\n   {}  \n# {}\n",
+            canister_id(1),
+            canister_id(2),
+            canister_id(3)
+        );
+        assert_eq!(
+            parse_ledger_ids_file(&contents).unwrap(),
+            vec![canister_id(1), canister_id(2)]
+        );
+        assert!(parse_ledger_ids_file("not-a-principal").is_err());
+    }
+
+    #[test]
+    fn test_ledger_ids_from_deployed_snses() {
+        let response = ListDeployedSnsesResponse {
+            instances: vec![
+                DeployedSns {
+                    ledger_canister_id: Some(canister_id(1).get().0),
+                },
+                DeployedSns {
+                    ledger_canister_id: None,
+                },
+                DeployedSns {
+                    ledger_canister_id: Some(canister_id(2).get().0),
+                },
+            ],
+        };
+        assert_eq!(
+            ledger_ids_from_deployed_snses(response).unwrap(),
+            vec![canister_id(1), canister_id(2)]
+        );
+    }
+
+    #[test]
+    fn test_ledger_ids_from_orchestrator_info_skips_uninstalled_ledgers() {
+        let info = OrchestratorInfo {
+            managed_canisters: vec![
+                ManagedCanisters {
+                    ledger: Some(ManagedCanisterStatus::Installed {
+                        canister_id: canister_id(1).get().0,
+                        installed_wasm_hash: "hash".to_string(),
+                    }),
+                },
+                ManagedCanisters {
+                    ledger: Some(ManagedCanisterStatus::Created {
+                        canister_id: canister_id(2).get().0,
+                    }),
+                },
+                ManagedCanisters { ledger: None },
+            ],
+        };
+        assert_eq!(
+            ledger_ids_from_orchestrator_info(info).unwrap(),
+            vec![canister_id(1)]
+        );
+    }
+
+    #[test]
+    fn test_untracked_ledger_ids_removes_duplicates() {
+        let multitoken_state = MultiTokenAppState::new(HashMap::new());
+        assert_eq!(
+            untracked_ledger_ids(
+                vec![canister_id(2), canister_id(1), canister_id(2)],
+                &multitoken_state
+            ),
+            vec![canister_id(2), canister_id(1)]
+        );
+    }
+
+    #[test]
+    fn test_validate_sources() {
+        let sources = vec![TokenDiscoverySource::SnsWasm(canister_id(1))];
+        assert!(validate_sources(&sources, false).is_ok());
+        assert!(validate_sources(&sources, true).is_err());
+        assert!(validate_sources(&[], true).is_ok());
+    }
+}
@@ -475,6 +475,7 @@ pub async fn exec<C: CallCanisters>(
                     store_canister_id: Some(store_canister_id.get()),
                     chunk_hashes_list,
                 }),
+                take_snapshot_before_upgrade: None,
             },
         )),
     };
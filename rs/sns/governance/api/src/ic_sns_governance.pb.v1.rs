@@ -370,6 +370,10 @@ pub struct UpgradeSnsControlledCanister {
     /// If the entire WASM does not fit into the 2 MiB ingress limit, then `new_canister_wasm` should be
     /// an empty, and this field should be set instead.
     pub chunked_canister_wasm: ::core::option::Option<ChunkedCanisterWasm>,
+    /// If true, a snapshot of the canister is taken right before it is upgraded, so that the
+    /// upgrade can be rolled back with a LoadDappCanisterSnapshot proposal. If taking the snapshot
+    /// fails, the canister is not upgraded.
+    pub take_snapshot_before_upgrade: Option<bool>,
 }
 /// A proposal to transfer SNS treasury funds to (optionally a Subaccount of) the
 /// target principal.
@@ -430,6 +434,70 @@ pub mod transfer_sns_treasury_funds {
         }
     }
 }
+/// A proposal to create a stream of recurring payments from one of the SNS treasuries.
+///
+/// Once the proposal is executed, Governance makes the payments by itself, without further
+/// proposals. Like TransferSnsTreasuryFunds, each payment counts towards the 7 day upper bound
+/// of treasury transfers. A payment that would exceed that bound is postponed. The ID of the
+/// stream is the ID of the proposal that created it.
+#[derive(Default, candid::CandidType, candid::Deserialize, Debug, Clone, PartialEq)]
+pub struct CreateTreasuryPaymentStream {
+    /// Whether the payments are made in ICP or in SNS tokens.
+    pub from_treasury: i32,
+    /// The amount paid at the end of each period, in e8s.
+    pub amount_per_period_e8s: Option<u64>,
+    /// The length of a period, in seconds.
+    pub period_seconds: Option<u64>,
+    /// The total number of payments.
+    pub number_of_periods: Option<u64>,
+    /// If set, no payment is made until this many seconds have passed since the stream was
+    /// created. The payments of periods that end during the cliff are made right after the cliff.
+    pub cliff_seconds: Option<u64>,
+    /// An optional memo to use for each payment.
+    pub memo: Option<u64>,
+    /// The principal to pay.
+    pub to_principal: Option<::ic_base_types::PrincipalId>,
+    /// An (optional) Subaccount of the principal to pay.
+    pub to_subaccount: Option<Subaccount>,
+}
+/// A proposal to stop a treasury payment stream. Payments already made are not affected.
+#[derive(Default, candid::CandidType, candid::Deserialize, Debug, Clone, PartialEq)]
+pub struct CancelTreasuryPaymentStream {
+    /// The ID of the stream to cancel, which is the ID of the proposal that created it.
+    pub payment_stream_id: Option<u64>,
+}
+/// The state of a stream of payments created by a CreateTreasuryPaymentStream proposal.
+#[derive(Default, candid::CandidType, candid::Deserialize, Debug, Clone, PartialEq)]
+pub struct TreasuryPaymentStream {
+    /// The ID of the proposal that created the stream.
+    pub id: Option<u64>,
+    /// What the stream pays, and to whom.
+    pub schedule: Option<CreateTreasuryPaymentStream>,
+    /// When the stream was created (seconds since UNIX epoch). Periods are counted from here.
+    pub start_timestamp_seconds: Option<u64>,
+    /// The treasury valuation at proposal submission time. Payments are checked against the 7 day
+    /// upper bound based on a fresh valuation taken when they are made.
+    pub valuation: Option<Valuation>,
+    /// The payments made so far, one per period, oldest first.
+    pub payments: Vec<treasury_payment_stream::Payment>,
+    /// When the stream was cancelled, if it was.
+    pub cancelled_timestamp_seconds: Option<u64>,
+    /// Why the last payment attempt did not succeed, if it did not.
+    pub last_failure_reason: Option<String>,
+    /// When the last payment attempt failed, if it did. Together with
+    /// consecutive_failure_count, this determines when the payment is retried.
+    pub last_failure_timestamp_seconds: Option<u64>,
+    /// The number of payment attempts that failed in a row.
+    pub consecutive_failure_count: Option<u64>,
+}
+/// Nested message and enum types in `TreasuryPaymentStream`.
+pub mod treasury_payment_stream {
+    #[derive(Default, candid::CandidType, candid::Deserialize, Debug, Clone, PartialEq)]
+    pub struct Payment {
+        pub timestamp_seconds: Option<u64>,
+        pub amount_e8s: Option<u64>,
+    }
+}
 /// A proposal function that changes the ledger's parameters.
 /// Fields with None values will remain unchanged.
 #[derive(Default, candid::CandidType, candid::Deserialize, Debug, Clone, PartialEq)]
@@ -533,6 +601,49 @@ pub struct ManageDappCanisterSettings {
     pub wasm_memory_limit: Option<u64>,
     pub wasm_memory_threshold: Option<u64>,
 }
+/// A proposal to take a snapshot of a dapp canister.
+#[derive(Default, candid::CandidType, candid::Deserialize, Debug, Clone, PartialEq)]
+pub struct TakeDappCanisterSnapshot {
+    /// The canister ID of the dapp canister to take a snapshot of.
+    pub canister_id: Option<::ic_base_types::PrincipalId>,
+    /// If set, this existing snapshot of the canister is replaced by the new snapshot.
+    #[serde(deserialize_with = "ic_utils::deserialize::deserialize_option_blob")]
+    pub replace_snapshot: Option<Vec<u8>>,
+}
+/// A proposal to load a snapshot into the dapp canister that it was taken of. This restores the
+/// canister's Wasm module and state to what they were when the snapshot was taken.
+#[derive(Default, candid::CandidType, candid::Deserialize, Debug, Clone, PartialEq)]
+pub struct LoadDappCanisterSnapshot {
+    /// The canister ID of the dapp canister to load the snapshot into.
+    pub canister_id: Option<::ic_base_types::PrincipalId>,
+    /// The ID of the snapshot to load.
+    #[serde(deserialize_with = "ic_utils::deserialize::deserialize_option_blob")]
+    pub snapshot_id: Option<Vec<u8>>,
+}
+/// A proposal to open a follow-on swap round, in which SNS tokens from the SNS token treasury are
+/// sold for ICP after the decentralization swap. Participation, refunds, finalization and the
+/// creation of the participants' neuron baskets work the same way as in the decentralization swap.
+/// The round starts as soon as the proposal is executed. If the round is aborted, the SNS tokens
+/// are returned to the treasury.
+#[derive(Default, candid::CandidType, candid::Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct OpenFollowOnSwapRound {
+    /// The number of SNS tokens (in e8s) from the treasury to offer in the round.
+    pub sns_token_e8s: Option<u64>,
+    /// The minimum and maximum amount of ICP (in e8s) that the round aims to raise.
+    pub min_direct_participation_icp_e8s: Option<u64>,
+    pub max_direct_participation_icp_e8s: Option<u64>,
+    /// The minimum and maximum amount of ICP (in e8s) that each participant may contribute.
+    pub min_participant_icp_e8s: Option<u64>,
+    pub max_participant_icp_e8s: Option<u64>,
+    /// The minimum number of participants for the round to succeed.
+    pub min_participants: Option<u32>,
+    /// How long the round accepts participation, starting when the proposal is executed.
+    pub duration_seconds: Option<u64>,
+    /// The number of neurons each participant receives.
+    pub neuron_basket_count: Option<u64>,
+    /// The amount of additional time it takes for the next neuron in a basket to dissolve.
+    pub neuron_basket_dissolve_delay_interval_seconds: Option<u64>,
+}
 /// Unlike `Governance.Version`, this message has optional fields and is the recommended one
 /// to use in APIs that can evolve. For example, the SNS Governance could eventually support
 /// a shorthand notation for SNS versions, enabling clients to specify SNS versions without having
@@ -682,6 +793,26 @@ pub mod proposal {
         ///
         /// Id = 17.
         RegisterExtension(super::RegisterExtension),
+        /// Create a stream of recurring payments from the treasury.
+        ///
+        /// Id = 18.
+        CreateTreasuryPaymentStream(super::CreateTreasuryPaymentStream),
+        /// Cancel a stream of recurring payments from the treasury.
+        ///
+        /// Id = 19.
+        CancelTreasuryPaymentStream(super::CancelTreasuryPaymentStream),
+        /// Take a snapshot of a dapp canister.
+        ///
+        /// Id = 20.
+        TakeDappCanisterSnapshot(super::TakeDappCanisterSnapshot),
+        /// Load a snapshot into a dapp canister.
+        ///
+        /// Id = 21.
+        LoadDappCanisterSnapshot(super::LoadDappCanisterSnapshot),
+        /// Open a follow-on swap round that sells SNS tokens from the treasury.
+        ///
+        /// Id = 22.
+        OpenFollowOnSwapRound(super::OpenFollowOnSwapRound),
     }
 }
 #[derive(Default, candid::CandidType, candid::Deserialize, Debug, Clone, PartialEq)]
@@ -993,6 +1124,10 @@ pub struct ProposalData {
     pub action_auxiliary: Option<proposal_data::ActionAuxiliary>,
     /// This proposal's topic.
     pub topic: Option<topics::Topic>,
+    /// The ID of the canister snapshot taken while executing this proposal, if any. This is set by
+    /// TakeDappCanisterSnapshot proposals, and by UpgradeSnsControlledCanister proposals with
+    /// take_snapshot_before_upgrade, so that a later LoadDappCanisterSnapshot proposal can refer to it.
+    pub taken_snapshot_id: Option<Vec<u8>>,
 }
 /// Nested message and enum types in `ProposalData`.
 pub mod proposal_data {
@@ -1005,6 +1140,10 @@ pub mod proposal_data {
         pub valuation: Option<super::Valuation>,
     }
     #[derive(Default, candid::CandidType, candid::Deserialize, Debug, Clone, PartialEq)]
+    pub struct CreateTreasuryPaymentStreamActionAuxiliary {
+        pub valuation: Option<super::Valuation>,
+    }
+    #[derive(Default, candid::CandidType, candid::Deserialize, Debug, Clone, PartialEq)]
     pub struct AdvanceSnsTargetVersionActionAuxiliary {
         /// Corresponds to the Some(target_version) from an AdvanceSnsTargetVersion proposal, or
         /// to the last SNS version known to this SNS at the time of AdvanceSnsTargetVersion creation.
@@ -1017,6 +1156,7 @@ pub mod proposal_data {
         TransferSnsTreasuryFunds(TransferSnsTreasuryFundsActionAuxiliary),
         MintSnsTokens(MintSnsTokensActionAuxiliary),
         AdvanceSnsTargetVersion(AdvanceSnsTargetVersionActionAuxiliary),
+        CreateTreasuryPaymentStream(CreateTreasuryPaymentStreamActionAuxiliary),
     }
 }
 #[derive(Default, candid::CandidType, candid::Deserialize, Debug, Clone, PartialEq)]
@@ -1408,6 +1548,8 @@ pub struct Governance {
     /// Information about the timers that perform periodic tasks of this Governance canister.
     pub timers: Option<::ic_nervous_system_proto::pb::v1::Timers>,
     pub upgrade_journal: Option<UpgradeJournal>,
+    /// Streams of treasury payments created by CreateTreasuryPaymentStream proposals, by ID.
+    pub treasury_payment_streams: BTreeMap<u64, TreasuryPaymentStream>,
 }
 /// Nested message and enum types in `Governance`.
 pub mod governance {
@@ -471,6 +471,11 @@ pub struct UpgradeSnsControlledCanister {
     /// empty, and this field should be set instead.
     #[prost(message, optional, tag = "5")]
     pub chunked_canister_wasm: ::core::option::Option<ChunkedCanisterWasm>,
+    /// If true, a snapshot of the canister is taken right before it is upgraded, so that the
+    /// upgrade can be rolled back with a LoadDappCanisterSnapshot proposal. If taking the snapshot
+    /// fails, the canister is not upgraded.
+    #[prost(bool, optional, tag = "6")]
+    pub take_snapshot_before_upgrade: ::core::option::Option<bool>,
 }
 /// A proposal to transfer SNS treasury funds to (optionally a Subaccount of) the
 /// target principal.
@@ -545,6 +550,118 @@ pub mod transfer_sns_treasury_funds {
         }
     }
 }
+/// A proposal to create a stream of recurring payments from one of the SNS treasuries.
+///
+/// Once the proposal is executed, Governance makes the payments by itself, without further
+/// proposals. Like TransferSnsTreasuryFunds, each payment counts towards the 7 day upper bound
+/// of treasury transfers. A payment that would exceed that bound is postponed. The ID of the
+/// stream is the ID of the proposal that created it.
+#[derive(
+    candid::CandidType,
+    candid::Deserialize,
+    comparable::Comparable,
+    Clone,
+    PartialEq,
+    ::prost::Message,
+)]
+pub struct CreateTreasuryPaymentStream {
+    /// Whether the payments are made in ICP or in SNS tokens.
+    #[prost(enumeration = "transfer_sns_treasury_funds::TransferFrom", tag = "1")]
+    pub from_treasury: i32,
+    /// The amount paid at the end of each period, in e8s.
+    #[prost(uint64, optional, tag = "2")]
+    pub amount_per_period_e8s: ::core::option::Option<u64>,
+    /// The length of a period, in seconds.
+    #[prost(uint64, optional, tag = "3")]
+    pub period_seconds: ::core::option::Option<u64>,
+    /// The total number of payments.
+    #[prost(uint64, optional, tag = "4")]
+    pub number_of_periods: ::core::option::Option<u64>,
+    /// If set, no payment is made until this many seconds have passed since the stream was
+    /// created. The payments of periods that end during the cliff are made right after the cliff.
+    #[prost(uint64, optional, tag = "5")]
+    pub cliff_seconds: ::core::option::Option<u64>,
+    /// An optional memo to use for each payment.
+    #[prost(uint64, optional, tag = "6")]
+    pub memo: ::core::option::Option<u64>,
+    /// The principal to pay.
+    #[prost(message, optional, tag = "7")]
+    pub to_principal: ::core::option::Option<::ic_base_types::PrincipalId>,
+    /// An (optional) Subaccount of the principal to pay.
+    #[prost(message, optional, tag = "8")]
+    pub to_subaccount: ::core::option::Option<Subaccount>,
+}
+/// A proposal to stop a treasury payment stream. Payments already made are not affected.
+#[derive(
+    candid::CandidType,
+    candid::Deserialize,
+    comparable::Comparable,
+    Clone,
+    PartialEq,
+    ::prost::Message,
+)]
+pub struct CancelTreasuryPaymentStream {
+    /// The ID of the stream to cancel, which is the ID of the proposal that created it.
+    #[prost(uint64, optional, tag = "1")]
+    pub payment_stream_id: ::core::option::Option<u64>,
+}
+/// The state of a stream of payments created by a CreateTreasuryPaymentStream proposal.
+#[derive(
+    candid::CandidType,
+    candid::Deserialize,
+    comparable::Comparable,
+    Clone,
+    PartialEq,
+    ::prost::Message,
+)]
+pub struct TreasuryPaymentStream {
+    /// The ID of the proposal that created the stream.
+    #[prost(uint64, optional, tag = "1")]
+    pub id: ::core::option::Option<u64>,
+    /// What the stream pays, and to whom.
+    #[prost(message, optional, tag = "2")]
+    pub schedule: ::core::option::Option<CreateTreasuryPaymentStream>,
+    /// When the stream was created (seconds since UNIX epoch). Periods are counted from here.
+    #[prost(uint64, optional, tag = "3")]
+    pub start_timestamp_seconds: ::core::option::Option<u64>,
+    /// The treasury valuation at proposal submission time. Payments are checked against the 7 day
+    /// upper bound based on a fresh valuation taken when they are made.
+    #[prost(message, optional, tag = "4")]
+    pub valuation: ::core::option::Option<Valuation>,
+    /// The payments made so far, one per period, oldest first.
+    #[prost(message, repeated, tag = "5")]
+    pub payments: ::prost::alloc::vec::Vec<treasury_payment_stream::Payment>,
+    /// When the stream was cancelled, if it was.
+    #[prost(uint64, optional, tag = "6")]
+    pub cancelled_timestamp_seconds: ::core::option::Option<u64>,
+    /// Why the last payment attempt did not succeed, if it did not.
+    #[prost(string, optional, tag = "7")]
+    pub last_failure_reason: ::core::option::Option<::prost::alloc::string::String>,
+    /// When the last payment attempt failed, if it did. Together with
+    /// consecutive_failure_count, this determines when the payment is retried.
+    #[prost(uint64, optional, tag = "8")]
+    pub last_failure_timestamp_seconds: ::core::option::Option<u64>,
+    /// The number of payment attempts that failed in a row.
+    #[prost(uint64, optional, tag = "9")]
+    pub consecutive_failure_count: ::core::option::Option<u64>,
+}
+/// Nested message and enum types in `TreasuryPaymentStream`.
+pub mod treasury_payment_stream {
+    #[derive(
+        candid::CandidType,
+        candid::Deserialize,
+        comparable::Comparable,
+        Clone,
+        PartialEq,
+        ::prost::Message,
+    )]
+    pub struct Payment {
+        #[prost(uint64, optional, tag = "1")]
+        pub timestamp_seconds: ::core::option::Option<u64>,
+        #[prost(uint64, optional, tag = "2")]
+        pub amount_e8s: ::core::option::Option<u64>,
+    }
+}
 /// A proposal function that changes the ledger's parameters.
 /// Fields with None values will remain unchanged.
 #[derive(
@@ -780,6 +897,84 @@ pub struct ManageDappCanisterSettings {
     #[prost(uint64, optional, tag = "8")]
     pub wasm_memory_threshold: ::core::option::Option<u64>,
 }
+/// A proposal to take a snapshot of a dapp canister.
+#[derive(
+    candid::CandidType,
+    candid::Deserialize,
+    comparable::Comparable,
+    Clone,
+    PartialEq,
+    ::prost::Message,
+)]
+pub struct TakeDappCanisterSnapshot {
+    /// The canister ID of the dapp canister to take a snapshot of.
+    #[prost(message, optional, tag = "1")]
+    pub canister_id: ::core::option::Option<::ic_base_types::PrincipalId>,
+    /// If set, this existing snapshot of the canister is replaced by the new snapshot.
+    #[prost(bytes = "vec", optional, tag = "2")]
+    #[serde(deserialize_with = "ic_utils::deserialize::deserialize_option_blob")]
+    pub replace_snapshot: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+}
+/// A proposal to load a snapshot into the dapp canister that it was taken of. This restores the
+/// canister's Wasm module and state to what they were when the snapshot was taken.
+#[derive(
+    candid::CandidType,
+    candid::Deserialize,
+    comparable::Comparable,
+    Clone,
+    PartialEq,
+    ::prost::Message,
+)]
+pub struct LoadDappCanisterSnapshot {
+    /// The canister ID of the dapp canister to load the snapshot into.
+    #[prost(message, optional, tag = "1")]
+    pub canister_id: ::core::option::Option<::ic_base_types::PrincipalId>,
+    /// The ID of the snapshot to load.
+    #[prost(bytes = "vec", optional, tag = "2")]
+    #[serde(deserialize_with = "ic_utils::deserialize::deserialize_option_blob")]
+    pub snapshot_id: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+}
+/// A proposal to open a follow-on swap round, in which SNS tokens from the SNS token treasury are
+/// sold for ICP after the decentralization swap. Participation, refunds, finalization and the
+/// creation of the participants' neuron baskets work the same way as in the decentralization swap.
+/// The round starts as soon as the proposal is executed. If the round is aborted, the SNS tokens
+/// are returned to the treasury.
+#[derive(
+    candid::CandidType,
+    candid::Deserialize,
+    comparable::Comparable,
+    Clone,
+    Copy,
+    PartialEq,
+    ::prost::Message,
+)]
+pub struct OpenFollowOnSwapRound {
+    /// The number of SNS tokens (in e8s) from the treasury to offer in the round.
+    #[prost(uint64, optional, tag = "1")]
+    pub sns_token_e8s: ::core::option::Option<u64>,
+    /// The minimum and maximum amount of ICP (in e8s) that the round aims to raise.
+    #[prost(uint64, optional, tag = "2")]
+    pub min_direct_participation_icp_e8s: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "3")]
+    pub max_direct_participation_icp_e8s: ::core::option::Option<u64>,
+    /// The minimum and maximum amount of ICP (in e8s) that each participant may contribute.
+    #[prost(uint64, optional, tag = "4")]
+    pub min_participant_icp_e8s: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "5")]
+    pub max_participant_icp_e8s: ::core::option::Option<u64>,
+    /// The minimum number of participants for the round to succeed.
+    #[prost(uint32, optional, tag = "6")]
+    pub min_participants: ::core::option::Option<u32>,
+    /// How long the round accepts participation, starting when the proposal is executed.
+    #[prost(uint64, optional, tag = "7")]
+    pub duration_seconds: ::core::option::Option<u64>,
+    /// The number of neurons each participant receives.
+    #[prost(uint64, optional, tag = "8")]
+    pub neuron_basket_count: ::core::option::Option<u64>,
+    /// The amount of additional time it takes for the next neuron in a basket to dissolve.
+    #[prost(uint64, optional, tag = "9")]
+    pub neuron_basket_dissolve_delay_interval_seconds: ::core::option::Option<u64>,
+}
 /// Unlike `Governance.Version`, this message has optional fields and is the recommended one
 /// to be used in APIs that can evolve. For example, the SNS Governance could eventually support
 /// a shorthand notation for SNS versions, enabling clients to specify SNS versions without having
@@ -865,7 +1060,7 @@ pub struct Proposal {
     /// of this mapping.
     #[prost(
         oneof = "proposal::Action",
-        tags = "4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21"
+        tags = "4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26"
     )]
     pub action: ::core::option::Option<proposal::Action>,
 }
@@ -985,6 +1180,31 @@ pub mod proposal {
         /// Id = 17.
         #[prost(message, tag = "21")]
         RegisterExtension(super::RegisterExtension),
+        /// Create a stream of recurring payments from the treasury.
+        ///
+        /// Id = 18.
+        #[prost(message, tag = "22")]
+        CreateTreasuryPaymentStream(super::CreateTreasuryPaymentStream),
+        /// Cancel a stream of recurring payments from the treasury.
+        ///
+        /// Id = 19.
+        #[prost(message, tag = "23")]
+        CancelTreasuryPaymentStream(super::CancelTreasuryPaymentStream),
+        /// Take a snapshot of a dapp canister.
+        ///
+        /// Id = 20.
+        #[prost(message, tag = "24")]
+        TakeDappCanisterSnapshot(super::TakeDappCanisterSnapshot),
+        /// Load a snapshot into a dapp canister.
+        ///
+        /// Id = 21.
+        #[prost(message, tag = "25")]
+        LoadDappCanisterSnapshot(super::LoadDappCanisterSnapshot),
+        /// Open a follow-on swap round that sells SNS tokens from the treasury.
+        ///
+        /// Id = 22.
+        #[prost(message, tag = "26")]
+        OpenFollowOnSwapRound(super::OpenFollowOnSwapRound),
     }
 }
 #[derive(candid::CandidType, candid::Deserialize, comparable::Comparable)]
@@ -1356,9 +1576,14 @@ pub struct ProposalData {
     /// This proposal's topic.
     #[prost(enumeration = "Topic", optional, tag = "25")]
     pub topic: ::core::option::Option<i32>,
+    /// The ID of the canister snapshot taken while executing this proposal, if any. This is set by
+    /// TakeDappCanisterSnapshot proposals, and by UpgradeSnsControlledCanister proposals with
+    /// take_snapshot_before_upgrade, so that a later LoadDappCanisterSnapshot proposal can refer to it.
+    #[prost(bytes = "vec", optional, tag = "27")]
+    pub taken_snapshot_id: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
     /// In general, this holds data retrieved at proposal submission/creation time and used later
     /// during execution. This varies based on the action of the proposal.
-    #[prost(oneof = "proposal_data::ActionAuxiliary", tags = "22, 23, 24")]
+    #[prost(oneof = "proposal_data::ActionAuxiliary", tags = "22, 23, 24, 26")]
     pub action_auxiliary: ::core::option::Option<proposal_data::ActionAuxiliary>,
 }
 /// Nested message and enum types in `ProposalData`.
@@ -1395,6 +1620,18 @@ pub mod proposal_data {
         PartialEq,
         ::prost::Message,
     )]
+    pub struct CreateTreasuryPaymentStreamActionAuxiliary {
+        #[prost(message, optional, tag = "1")]
+        pub valuation: ::core::option::Option<super::Valuation>,
+    }
+    #[derive(
+        candid::CandidType,
+        candid::Deserialize,
+        comparable::Comparable,
+        Clone,
+        PartialEq,
+        ::prost::Message,
+    )]
     pub struct AdvanceSnsTargetVersionActionAuxiliary {
         /// Corresponds to the Some(target_version) from an AdvanceSnsTargetVersion proposal, or
         /// to the last SNS version known to this SNS at the time of AdvanceSnsTargetVersion creation.
@@ -1418,6 +1655,8 @@ pub mod proposal_data {
         MintSnsTokens(MintSnsTokensActionAuxiliary),
         #[prost(message, tag = "24")]
         AdvanceSnsTargetVersion(AdvanceSnsTargetVersionActionAuxiliary),
+        #[prost(message, tag = "26")]
+        CreateTreasuryPaymentStream(CreateTreasuryPaymentStreamActionAuxiliary),
     }
 }
 #[derive(
@@ -1930,6 +2169,9 @@ pub struct Governance {
     pub timers: ::core::option::Option<::ic_nervous_system_proto::pb::v1::Timers>,
     #[prost(message, optional, tag = "32")]
     pub upgrade_journal: ::core::option::Option<UpgradeJournal>,
+    /// Streams of treasury payments created by CreateTreasuryPaymentStream proposals, by ID.
+    #[prost(btree_map = "uint64, message", tag = "33")]
+    pub treasury_payment_streams: ::prost::alloc::collections::BTreeMap<u64, TreasuryPaymentStream>,
 }
 /// Nested message and enum types in `Governance`.
 pub mod governance {
@@ -21,9 +21,15 @@ use crate::{
     },
     pb::{
         sns_root_types::{
+            LoadDappCanisterSnapshotRequest, LoadDappCanisterSnapshotResponse,
             ManageDappCanisterSettingsRequest, ManageDappCanisterSettingsResponse,
             RegisterDappCanistersRequest, RegisterDappCanistersResponse, SetDappControllersRequest,
-            SetDappControllersResponse,
+            SetDappControllersResponse, TakeDappCanisterSnapshotRequest,
+            TakeDappCanisterSnapshotResponse,
+        },
+        sns_swap_types::{
+            open_follow_on_round_response, NeuronBasketConstructionParameters,
+            OpenFollowOnRoundRequest, OpenFollowOnRoundResponse,
         },
         v1::{
             claim_swap_neurons_response::SwapNeuron,
@@ -50,31 +56,34 @@ use crate::{
             transfer_sns_treasury_funds::TransferFrom,
             upgrade_journal_entry, Account as AccountProto, AddMaturityRequest,
             AddMaturityResponse, AdvanceTargetVersionRequest, AdvanceTargetVersionResponse, Ballot,
-            ClaimSwapNeuronsError, ClaimSwapNeuronsRequest, ClaimSwapNeuronsResponse,
-            ClaimedSwapNeuronStatus, DefaultFollowees, DeregisterDappCanisters,
-            DisburseMaturityInProgress, Empty, ExecuteGenericNervousSystemFunction,
-            FailStuckUpgradeInProgressRequest, FailStuckUpgradeInProgressResponse,
-            GetMaturityModulationRequest, GetMaturityModulationResponse, GetMetadataRequest,
-            GetMetadataResponse, GetMetricsRequest, GetMode, GetModeResponse, GetNeuron,
-            GetNeuronResponse, GetProposal, GetProposalResponse,
-            GetSnsInitializationParametersRequest, GetSnsInitializationParametersResponse,
-            Governance as GovernanceProto, GovernanceError, ListNervousSystemFunctionsResponse,
-            ListNeurons, ListNeuronsResponse, ListProposals, ListProposalsResponse,
-            ManageDappCanisterSettings, ManageLedgerParameters, ManageNeuron, ManageNeuronResponse,
-            ManageSnsMetadata, MintSnsTokens, MintTokensRequest, MintTokensResponse,
-            NervousSystemFunction, NervousSystemParameters, Neuron, NeuronId, NeuronPermission,
-            NeuronPermissionList, NeuronPermissionType, Proposal, ProposalData,
+            CancelTreasuryPaymentStream, ClaimSwapNeuronsError, ClaimSwapNeuronsRequest,
+            ClaimSwapNeuronsResponse, ClaimedSwapNeuronStatus, CreateTreasuryPaymentStream,
+            DefaultFollowees, DeregisterDappCanisters, DisburseMaturityInProgress, Empty,
+            ExecuteGenericNervousSystemFunction, FailStuckUpgradeInProgressRequest,
+            FailStuckUpgradeInProgressResponse, GetMaturityModulationRequest,
+            GetMaturityModulationResponse, GetMetadataRequest, GetMetadataResponse,
+            GetMetricsRequest, GetMode, GetModeResponse, GetNeuron, GetNeuronResponse, GetProposal,
+            GetProposalResponse, GetSnsInitializationParametersRequest,
+            GetSnsInitializationParametersResponse, Governance as GovernanceProto, GovernanceError,
+            ListNervousSystemFunctionsResponse, ListNeurons, ListNeuronsResponse, ListProposals,
+            ListProposalsResponse, LoadDappCanisterSnapshot, ManageDappCanisterSettings,
+            ManageLedgerParameters, ManageNeuron, ManageNeuronResponse, ManageSnsMetadata,
+            MintSnsTokens, MintTokensRequest, MintTokensResponse, NervousSystemFunction,
+            NervousSystemParameters, Neuron, NeuronId, NeuronPermission, NeuronPermissionList,
+            NeuronPermissionType, OpenFollowOnSwapRound, Proposal, ProposalData,
             ProposalDecisionStatus, ProposalId, ProposalRewardStatus, RegisterDappCanisters,
             RewardEvent, SetTopicsForCustomProposals, Tally, Topic, TransferSnsTreasuryFunds,
-            UpgradeSnsControlledCanister, Vote, WaitForQuietState,
+            TreasuryPaymentStream, UpgradeSnsControlledCanister, Valuation as ValuationPb, Vote,
+            WaitForQuietState,
         },
     },
     proposal::{
         get_action_auxiliary,
         transfer_sns_treasury_funds_amount_is_small_enough_at_execution_time_or_err,
-        validate_and_render_proposal, validate_and_render_set_topics_for_custom_proposals,
-        ValidGenericNervousSystemFunction, MAX_LIST_PROPOSAL_RESULTS,
-        MAX_NUMBER_OF_PROPOSALS_WITH_BALLOTS,
+        treasury_amount_is_small_enough_at_execution_time_or_err,
+        treasury_payment_stream_valuation, validate_and_render_proposal,
+        validate_and_render_set_topics_for_custom_proposals, ValidGenericNervousSystemFunction,
+        MAX_LIST_PROPOSAL_RESULTS, MAX_NUMBER_OF_PROPOSALS_WITH_BALLOTS,
     },
     sns_upgrade::{
         canister_type_and_wasm_hash_for_upgrade, get_all_sns_canisters, get_canisters_to_upgrade,
@@ -2174,6 +2183,35 @@ impl Governance {
             Action::SetTopicsForCustomProposals(set_topics_for_custom_proposals) => {
                 self.perform_set_topics_for_custom_proposals(set_topics_for_custom_proposals)
             }
+            Action::CreateTreasuryPaymentStream(create) => {
+                let valuation =
+                    get_action_auxiliary(&self.proto.proposals, ProposalId { id: proposal_id })
+                        .and_then(|action_auxiliary| {
+                            action_auxiliary.unwrap_create_treasury_payment_stream_or_err()
+                        });
+                self.perform_create_treasury_payment_stream(proposal_id, valuation, create)
+            }
+            Action::CancelTreasuryPaymentStream(cancel) => {
+                self.perform_cancel_treasury_payment_stream(cancel)
+            }
+            Action::TakeDappCanisterSnapshot(take_dapp_canister_snapshot) => {
+                let snapshot_id = self
+                    .take_dapp_canister_snapshot(TakeDappCanisterSnapshotRequest {
+                        canister_id: take_dapp_canister_snapshot.canister_id,
+                        replace_snapshot: take_dapp_canister_snapshot.replace_snapshot,
+                    })
+                    .await?;
+                self.record_taken_snapshot_id(proposal_id, snapshot_id);
+                Ok(())
+            }
+            Action::LoadDappCanisterSnapshot(load_dapp_canister_snapshot) => {
+                self.perform_load_dapp_canister_snapshot(load_dapp_canister_snapshot)
+                    .await
+            }
+            Action::OpenFollowOnSwapRound(open_follow_on_swap_round) => {
+                self.perform_open_follow_on_swap_round(proposal_id, open_follow_on_swap_round)
+                    .await
+            }
             // This should not be possible, because Proposal validation is performed when
             // a proposal is first made.
             Action::Unspecified(_) => Err(GovernanceError::new_with_message(
@@ -2554,6 +2592,30 @@ impl Governance {
         let wasm = Wasm::try_from(&upgrade)
             .map_err(|err| GovernanceError::new_with_message(ErrorType::InvalidCommand, err))?;
 
+        if upgrade.take_snapshot_before_upgrade.unwrap_or_default() {
+            // Each pre-upgrade snapshot replaces the previous one, so that repeated upgrades do
+            // not exhaust the canister's snapshot slots.
+            let replace_snapshot =
+                self.latest_pre_upgrade_snapshot_id(proposal_id, target_canister_id);
+            let snapshot_id = self
+                .take_dapp_canister_snapshot(TakeDappCanisterSnapshotRequest {
+                    canister_id: Some(target_canister_id.get()),
+                    replace_snapshot,
+                })
+                .await
+                .map_err(|err| {
+                    GovernanceError::new_with_message(
+                        err.error_type(),
+                        format!(
+                            "Not upgrading canister {target_canister_id}, because the snapshot \
+                             requested by take_snapshot_before_upgrade could not be taken: {}",
+                            err.error_message
+                        ),
+                    )
+                })?;
+            self.record_taken_snapshot_id(proposal_id, snapshot_id);
+        }
+
         self.upgrade_non_root_canister(
             target_canister_id,
             wasm,
@@ -2843,17 +2905,15 @@ impl Governance {
         valuation: Result<Valuation, GovernanceError>,
         transfer: &TransferSnsTreasuryFunds,
     ) -> Result<(), GovernanceError> {
-        // Only execute one proposal of this type at a time.
-        thread_local! {
-            static IN_PROGRESS_PROPOSAL_ID: RefCell<Option<u64>> = const { RefCell::new(None) };
-        }
-        let release_on_drop = acquire(&IN_PROGRESS_PROPOSAL_ID, proposal_id);
-        if let Err(already_in_progress_proposal_id) = release_on_drop {
+        // Only execute one treasury transfer at a time.
+        let release_on_drop = acquire(&TREASURY_TRANSFER_IN_PROGRESS_ID, proposal_id);
+        if let Err(already_in_progress_id) = release_on_drop {
             return Err(GovernanceError::new_with_message(
                 ErrorType::PreconditionFailed,
                 format!(
-                    "Another TransferSnsTreasuryFunds proposal (ID = {}) is already in progress.",
-                    already_in_progress_proposal_id,
+                    "Another treasury transfer (TransferSnsTreasuryFunds proposal or payment \
+                     stream ID = {}) is already in progress.",
+                    already_in_progress_id,
                 ),
             ));
         }
@@ -2862,6 +2922,7 @@ impl Governance {
             transfer,
             valuation?,
             self.proto.proposals.values(),
+            self.proto.treasury_payment_streams.values(),
             self.env.now(),
         )?;
 
@@ -2875,15 +2936,33 @@ impl Governance {
                     .expect("Couldn't transform transfer.subaccount to Subaccount")
             }),
         };
-        match transfer.from_treasury() {
+        self.transfer_from_treasury(
+            transfer.from_treasury(),
+            transfer.amount_e8s,
+            to,
+            transfer.memo.unwrap_or(0),
+        )
+        .await
+    }
+
+    /// Transfers `amount_e8s` from the ICP or SNS token treasury to `to`. Callers are
+    /// responsible for checking that the amount is within the treasury transfer limits.
+    async fn transfer_from_treasury(
+        &self,
+        from_treasury: TransferFrom,
+        amount_e8s: u64,
+        to: Account,
+        memo: u64,
+    ) -> Result<(), GovernanceError> {
+        match from_treasury {
             TransferFrom::IcpTreasury => self
                 .nns_ledger
                 .transfer_funds(
-                    transfer.amount_e8s,
+                    amount_e8s,
                     NNS_DEFAULT_TRANSFER_FEE.get_e8s(),
                     None,
                     to,
-                    transfer.memo.unwrap_or(0),
+                    memo,
                 )
                 .await
                 .map(|_| ())
@@ -2902,11 +2981,11 @@ impl Governance {
                 );
                 self.ledger
                     .transfer_funds(
-                        transfer.amount_e8s,
+                        amount_e8s,
                         transaction_fee_e8s,
                         Some(treasury_subaccount),
                         to,
-                        transfer.memo.unwrap_or(0),
+                        memo,
                     )
                     .await
                     .map(|_| ())
@@ -2924,6 +3003,76 @@ impl Governance {
         }
     }
 
+    /// Creates the stream of payments described by a CreateTreasuryPaymentStream proposal.
+    /// The payments themselves are made by run_periodic_tasks.
+    fn perform_create_treasury_payment_stream(
+        &mut self,
+        proposal_id: u64,
+        valuation: Result<Valuation, GovernanceError>,
+        schedule: CreateTreasuryPaymentStream,
+    ) -> Result<(), GovernanceError> {
+        let valuation = ValuationPb::try_from(valuation?).map_err(|err| {
+            GovernanceError::new_with_message(ErrorType::InconsistentInternalData, err)
+        })?;
+
+        let payment_stream = TreasuryPaymentStream {
+            id: Some(proposal_id),
+            schedule: Some(schedule),
+            start_timestamp_seconds: Some(self.env.now()),
+            valuation: Some(valuation),
+            payments: vec![],
+            cancelled_timestamp_seconds: None,
+            last_failure_reason: None,
+            last_failure_timestamp_seconds: None,
+            consecutive_failure_count: None,
+        };
+        self.proto
+            .treasury_payment_streams
+            .insert(proposal_id, payment_stream);
+
+        Ok(())
+    }
+
+    fn perform_cancel_treasury_payment_stream(
+        &mut self,
+        cancel: CancelTreasuryPaymentStream,
+    ) -> Result<(), GovernanceError> {
+        let now = self.env.now();
+        let payment_stream_id = cancel.payment_stream_id.ok_or_else(|| {
+            GovernanceError::new_with_message(
+                ErrorType::InvalidProposal,
+                "CancelTreasuryPaymentStream.payment_stream_id must be set.",
+            )
+        })?;
+
+        let payment_stream = self
+            .proto
+            .treasury_payment_streams
+            .get_mut(&payment_stream_id)
+            .ok_or_else(|| {
+                GovernanceError::new_with_message(
+                    ErrorType::NotFound,
+                    format!(
+                        "There is no treasury payment stream with ID {}.",
+                        payment_stream_id
+                    ),
+                )
+            })?;
+
+        if payment_stream.is_finished() {
+            return Err(GovernanceError::new_with_message(
+                ErrorType::PreconditionFailed,
+                format!(
+                    "Treasury payment stream {} is already cancelled or complete.",
+                    payment_stream_id
+                ),
+            ));
+        }
+
+        payment_stream.cancelled_timestamp_seconds = Some(now);
+        Ok(())
+    }
+
     async fn perform_mint_sns_tokens(
         &mut self,
         mint: MintSnsTokens,
@@ -3133,6 +3282,291 @@ impl Governance {
             )
     }
 
+    /// Asks SNS Root to take a snapshot of a dapp canister, and returns the ID of the new snapshot.
+    async fn take_dapp_canister_snapshot(
+        &self,
+        request: TakeDappCanisterSnapshotRequest,
+    ) -> Result<Vec<u8>, GovernanceError> {
+        let canister_id = request.canister_id;
+        let payload = candid::Encode!(&request).map_err(|err| {
+            GovernanceError::new_with_message(
+                ErrorType::InvalidProposal,
+                format!("Could not encode TakeDappCanisterSnapshotRequest: {err:?}"),
+            )
+        })?;
+        let reply = self
+            .env
+            .call_canister(
+                self.proto.root_canister_id_or_panic(),
+                "take_dapp_canister_snapshot",
+                payload,
+            )
+            .await
+            .map_err(|err| {
+                GovernanceError::new_with_message(
+                    ErrorType::External,
+                    format!("Canister method call failed: {err:?}"),
+                )
+            })?;
+        let TakeDappCanisterSnapshotResponse {
+            failure_reason,
+            snapshot_id,
+            taken_at_timestamp: _,
+        } = candid::Decode!(&reply, TakeDappCanisterSnapshotResponse).map_err(|err| {
+            GovernanceError::new_with_message(
+                ErrorType::External,
+                format!("Could not decode TakeDappCanisterSnapshotResponse: {err}"),
+            )
+        })?;
+
+        if let Some(failure_reason) = failure_reason {
+            return Err(GovernanceError::new_with_message(
+                ErrorType::External,
+                format!("Failed to take dapp canister snapshot: {failure_reason}"),
+            ));
+        }
+        let snapshot_id = snapshot_id.ok_or_else(|| {
+            GovernanceError::new_with_message(
+                ErrorType::External,
+                "SNS Root did not return the ID of the new snapshot.",
+            )
+        })?;
+
+        // The snapshot ID is needed to load the snapshot later, e.g. to roll back an upgrade.
+        log!(
+            INFO,
+            "{}Took snapshot {} of dapp canister {:?}.",
+            log_prefix(),
+            hex::encode(&snapshot_id),
+            canister_id,
+        );
+        Ok(snapshot_id)
+    }
+
+    /// Records on the given proposal the ID of the snapshot taken while executing it, so that
+    /// a later LoadDappCanisterSnapshot proposal can refer to it.
+    fn record_taken_snapshot_id(&mut self, proposal_id: u64, snapshot_id: Vec<u8>) {
+        match self.proto.proposals.get_mut(&proposal_id) {
+            Some(proposal_data) => proposal_data.taken_snapshot_id = Some(snapshot_id),
+            None => log!(
+                ERROR,
+                "{}Proposal {} not found when recording snapshot {}.",
+                log_prefix(),
+                proposal_id,
+                hex::encode(&snapshot_id),
+            ),
+        }
+    }
+
+    /// Returns the ID of the most recent snapshot of `canister_id` taken before an upgrade by an
+    /// UpgradeSnsControlledCanister proposal other than `proposal_id`, unless that snapshot has
+    /// since been replaced by a TakeDappCanisterSnapshot proposal.
+    fn latest_pre_upgrade_snapshot_id(
+        &self,
+        proposal_id: u64,
+        canister_id: CanisterId,
+    ) -> Option<Vec<u8>> {
+        let canister_id = canister_id.get();
+        let mut replaced_snapshot_ids = BTreeSet::new();
+        for (id, proposal_data) in self.proto.proposals.iter().rev() {
+            if *id == proposal_id {
+                continue;
+            }
+            let Some(taken_snapshot_id) = &proposal_data.taken_snapshot_id else {
+                continue;
+            };
+            match proposal_data
+                .proposal
+                .as_ref()
+                .and_then(|p| p.action.as_ref())
+            {
+                Some(Action::TakeDappCanisterSnapshot(take))
+                    if take.canister_id == Some(canister_id) =>
+                {
+                    if let Some(replace_snapshot) = &take.replace_snapshot {
+                        replaced_snapshot_ids.insert(replace_snapshot.clone());
+                    }
+                }
+                Some(Action::UpgradeSnsControlledCanister(upgrade))
+                    if upgrade.canister_id == Some(canister_id) =>
+                {
+                    if replaced_snapshot_ids.contains(taken_snapshot_id) {
+                        return None;
+                    }
+                    return Some(taken_snapshot_id.clone());
+                }
+                _ => (),
+            }
+        }
+        None
+    }
+
+    async fn perform_load_dapp_canister_snapshot(
+        &self,
+        load_dapp_canister_snapshot: LoadDappCanisterSnapshot,
+    ) -> Result<(), GovernanceError> {
+        let request = LoadDappCanisterSnapshotRequest {
+            canister_id: load_dapp_canister_snapshot.canister_id,
+            snapshot_id: load_dapp_canister_snapshot.snapshot_id.unwrap_or_default(),
+        };
+        let payload = candid::Encode!(&request).map_err(|err| {
+            GovernanceError::new_with_message(
+                ErrorType::InvalidProposal,
+                format!("Could not encode LoadDappCanisterSnapshotRequest: {err:?}"),
+            )
+        })?;
+        self.env
+            .call_canister(
+                self.proto.root_canister_id_or_panic(),
+                "load_dapp_canister_snapshot",
+                payload,
+            )
+            .await
+            .map_err(|err| {
+                GovernanceError::new_with_message(
+                    ErrorType::External,
+                    format!("Canister method call failed: {err:?}"),
+                )
+            })
+            .and_then(
+                |reply| match candid::Decode!(&reply, LoadDappCanisterSnapshotResponse) {
+                    Ok(LoadDappCanisterSnapshotResponse { failure_reason }) => failure_reason
+                        .map_or(Ok(()), |failure_reason| {
+                            Err(GovernanceError::new_with_message(
+                                ErrorType::External,
+                                format!("Failed to load dapp canister snapshot: {failure_reason}"),
+                            ))
+                        }),
+                    Err(error) => Err(GovernanceError::new_with_message(
+                        ErrorType::External,
+                        format!("Could not decode LoadDappCanisterSnapshotResponse: {error}"),
+                    )),
+                },
+            )
+    }
+
+    /// Funds the swap canister with SNS tokens from the treasury, and asks it to open a
+    /// follow-on round that sells them. SNS tokens that are already in the swap canister's
+    /// account (e.g. because an earlier attempt transferred them but could not open the round)
+    /// count towards the amount offered, so that retrying does not take more from the treasury.
+    async fn perform_open_follow_on_swap_round(
+        &self,
+        proposal_id: u64,
+        open_follow_on_swap_round: OpenFollowOnSwapRound,
+    ) -> Result<(), GovernanceError> {
+        // Only execute one treasury transfer at a time.
+        let release_on_drop = acquire(&TREASURY_TRANSFER_IN_PROGRESS_ID, proposal_id);
+        if let Err(already_in_progress_id) = release_on_drop {
+            return Err(GovernanceError::new_with_message(
+                ErrorType::PreconditionFailed,
+                format!(
+                    "Another treasury transfer (TransferSnsTreasuryFunds proposal or payment \
+                     stream ID = {}) is already in progress.",
+                    already_in_progress_id,
+                ),
+            ));
+        }
+
+        let OpenFollowOnSwapRound {
+            sns_token_e8s,
+            min_direct_participation_icp_e8s,
+            max_direct_participation_icp_e8s,
+            min_participant_icp_e8s,
+            max_participant_icp_e8s,
+            min_participants,
+            duration_seconds,
+            neuron_basket_count,
+            neuron_basket_dissolve_delay_interval_seconds,
+        } = open_follow_on_swap_round;
+        let sns_token_e8s = sns_token_e8s.unwrap_or_default();
+
+        let swap_canister_id = self.proto.swap_canister_id_or_panic();
+        let swap_account = Account {
+            owner: swap_canister_id.get().0,
+            subaccount: None,
+        };
+        let swap_balance_e8s = self.ledger.account_balance(swap_account).await?.get_e8s();
+        if swap_balance_e8s < sns_token_e8s {
+            self.transfer_from_treasury(
+                TransferFrom::SnsTokenTreasury,
+                sns_token_e8s - swap_balance_e8s,
+                swap_account,
+                proposal_id,
+            )
+            .await?;
+        }
+
+        let now = self.env.now();
+        let request = OpenFollowOnRoundRequest {
+            sns_proposal_id: Some(proposal_id),
+            sns_token_e8s: Some(sns_token_e8s),
+            min_direct_participation_icp_e8s,
+            max_direct_participation_icp_e8s,
+            min_participant_icp_e8s,
+            max_participant_icp_e8s,
+            min_participants,
+            swap_start_timestamp_seconds: Some(now),
+            swap_due_timestamp_seconds: Some(
+                now.saturating_add(duration_seconds.unwrap_or_default()),
+            ),
+            neuron_basket_construction_parameters: Some(NeuronBasketConstructionParameters {
+                count: neuron_basket_count.unwrap_or_default(),
+                dissolve_delay_interval_seconds: neuron_basket_dissolve_delay_interval_seconds
+                    .unwrap_or_default(),
+            }),
+            transaction_fee_e8s: Some(self.transaction_fee_e8s_or_panic()),
+            neuron_minimum_stake_e8s: Some(self.neuron_minimum_stake_e8s_or_panic()),
+        };
+        let payload = Encode!(&request).map_err(|err| {
+            GovernanceError::new_with_message(
+                ErrorType::InvalidProposal,
+                format!("Could not encode OpenFollowOnRoundRequest: {err:?}"),
+            )
+        })?;
+        let reply = self
+            .env
+            .call_canister(swap_canister_id, "open_follow_on_round", payload)
+            .await
+            .map_err(|err| {
+                GovernanceError::new_with_message(
+                    ErrorType::External,
+                    format!("Canister method call failed: {err:?}"),
+                )
+            })?;
+        let response = Decode!(&reply, OpenFollowOnRoundResponse).map_err(|err| {
+            GovernanceError::new_with_message(
+                ErrorType::External,
+                format!("Could not decode OpenFollowOnRoundResponse: {err}"),
+            )
+        })?;
+
+        match response.result {
+            Some(open_follow_on_round_response::Result::Ok(ok)) => {
+                log!(
+                    INFO,
+                    "Opened follow-on swap round {} offering {} SNS e8s (proposal {}).",
+                    ok.round_number.unwrap_or_default(),
+                    sns_token_e8s,
+                    proposal_id,
+                );
+                Ok(())
+            }
+            Some(open_follow_on_round_response::Result::Err(err)) => {
+                Err(GovernanceError::new_with_message(
+                    ErrorType::External,
+                    format!(
+                        "The swap canister did not open the follow-on round: {}",
+                        err.description.unwrap_or_default()
+                    ),
+                ))
+            }
+            None => Err(GovernanceError::new_with_message(
+                ErrorType::External,
+                "The swap canister returned an empty OpenFollowOnRoundResponse.",
+            )),
+        }
+    }
+
     fn perform_advance_target_version(
         &mut self,
         new_target: Version,
@@ -3569,6 +4003,8 @@ impl Governance {
             is_eligible_for_rewards: true,
             action_auxiliary,
             topic: Some(i32::from(proposal_topic)),
+            // Set when the proposal is executed, if executing it takes a canister snapshot.
+            taken_snapshot_id: None,
         };
 
         proposal_data.wait_for_quiet_state = Some(WaitForQuietState {
@@ -5004,6 +5440,156 @@ impl Governance {
         self.proto.is_finalizing_disburse_maturity = None;
     }
 
+    /// Makes the next due payment of a treasury payment stream, if any. Only one payment is
+    /// made per call, so that a stream that is behind (e.g. after its cliff) catches up over
+    /// several calls, and each payment is checked against the 7 day upper bound on its own.
+    ///
+    /// The upper bound is based on the current value of the treasury. A stream whose payment
+    /// fails is retried with exponential backoff (see TreasuryPaymentStream::retry_delay_seconds).
+    ///
+    /// Finished streams are kept, so that their payments remain visible.
+    async fn maybe_make_treasury_payment_stream_payment(&mut self) {
+        let now = self.env.now();
+
+        let due_payments = self
+            .proto
+            .treasury_payment_streams
+            .iter()
+            .filter(|(_, payment_stream)| payment_stream.is_retry_due(now))
+            .filter_map(|(id, payment_stream)| {
+                let amount_e8s = payment_stream.next_payment_amount_e8s(now)?;
+                let schedule = payment_stream.schedule.clone().unwrap_or_default();
+                Some((*id, schedule, amount_e8s))
+            })
+            .collect::<Vec<_>>();
+        if due_payments.is_empty() {
+            return;
+        }
+
+        let sns_ledger_canister_id = self.proto.ledger_canister_id_or_panic();
+        let swap_canister_id = self.proto.swap_canister_id_or_panic();
+
+        // Make the first due payment that fits into the 7 day upper bound. Streams whose payment
+        // does not fit are skipped, so that they do not block other streams.
+        for (id, schedule, amount_e8s) in due_payments {
+            // Only execute one treasury transfer at a time. If another one is in progress, the
+            // payment is made in a later call.
+            let Ok(_release_on_drop) = acquire(&TREASURY_TRANSFER_IN_PROGRESS_ID, id) else {
+                return;
+            };
+
+            let valuation = match treasury_payment_stream_valuation(
+                &*self.env,
+                sns_ledger_canister_id,
+                swap_canister_id,
+                &schedule,
+            )
+            .await
+            {
+                Ok(valuation) => valuation,
+                Err(err) => {
+                    self.record_treasury_payment_stream_failure(id, err);
+                    continue;
+                }
+            };
+
+            let now = self.env.now();
+            let result = treasury_amount_is_small_enough_at_execution_time_or_err(
+                "this payment",
+                schedule.from_treasury(),
+                amount_e8s,
+                valuation,
+                self.proto.proposals.values(),
+                self.proto.treasury_payment_streams.values(),
+                now,
+            )
+            .and_then(|()| {
+                let subaccount = schedule
+                    .to_subaccount
+                    .as_ref()
+                    .map(|s| bytes_to_subaccount(&s.subaccount[..]))
+                    .transpose()?;
+                let owner = schedule.to_principal.ok_or_else(|| {
+                    GovernanceError::new_with_message(
+                        ErrorType::InconsistentInternalData,
+                        "Treasury payment stream has no target principal.",
+                    )
+                })?;
+                Ok(Account {
+                    owner: owner.0,
+                    subaccount,
+                })
+            });
+            let to = match result {
+                Ok(to) => to,
+                Err(err) => {
+                    log!(
+                        INFO,
+                        "{}Postponing payment of treasury payment stream {}: {}",
+                        log_prefix(),
+                        id,
+                        err.error_message,
+                    );
+                    continue;
+                }
+            };
+
+            let result = self
+                .transfer_from_treasury(
+                    schedule.from_treasury(),
+                    amount_e8s,
+                    to,
+                    schedule.memo.unwrap_or(0),
+                )
+                .await;
+
+            match result {
+                Ok(()) => {
+                    let now = self.env.now();
+                    // Streams are never removed, so the stream is still there.
+                    if let Some(payment_stream) = self.proto.treasury_payment_streams.get_mut(&id) {
+                        payment_stream.record_payment(now, amount_e8s);
+                    }
+                }
+                Err(err) => self.record_treasury_payment_stream_failure(id, err.error_message),
+            }
+            return;
+        }
+    }
+
+    /// Records that an attempt to make a payment of the given treasury payment stream failed.
+    /// Only the first of a series of consecutive failures is logged as an error, because the
+    /// payment is retried (with backoff) until it succeeds.
+    fn record_treasury_payment_stream_failure(&mut self, id: u64, reason: String) {
+        let now = self.env.now();
+        let Some(payment_stream) = self.proto.treasury_payment_streams.get_mut(&id) else {
+            return;
+        };
+        let failures = payment_stream.record_payment_failure(now, reason.clone());
+        let retry_delay_seconds = payment_stream.retry_delay_seconds();
+        if failures == 1 {
+            log!(
+                ERROR,
+                "{}Payment of treasury payment stream {} failed, retrying in {} seconds: {}",
+                log_prefix(),
+                id,
+                retry_delay_seconds,
+                reason,
+            );
+        } else {
+            log!(
+                INFO,
+                "{}Payment of treasury payment stream {} failed {} times in a row, \
+                 retrying in {} seconds: {}",
+                log_prefix(),
+                id,
+                failures,
+                retry_delay_seconds,
+                reason,
+            );
+        }
+    }
+
     /// When a neuron is finally dissolved, if there is any staked maturity it is moved to regular maturity
     /// which can be spawned.
     pub(crate) fn maybe_move_staked_maturity(&mut self) {
@@ -5159,6 +5745,8 @@ impl Governance {
 
         self.maybe_finalize_disburse_maturity().await;
 
+        self.maybe_make_treasury_payment_stream_payment().await;
+
         self.maybe_move_staked_maturity();
 
         self.maybe_gc();
@@ -6198,6 +6786,12 @@ impl Governance {
     }
 }
 
+thread_local! {
+    // The ID of the TransferSnsTreasuryFunds proposal or treasury payment stream whose transfer
+    // is in progress, if any.
+    static TREASURY_TRANSFER_IN_PROGRESS_ID: RefCell<Option<u64>> = const { RefCell::new(None) };
+}
+
 // TODO(NNS1-2835): Remove this const after changes published.
 thread_local! {
     static ATTEMPTED_FIXING_MEMORY_ALLOCATIONS: RefCell<bool> = const { RefCell::new(false) };
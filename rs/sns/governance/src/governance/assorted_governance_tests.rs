@@ -16,8 +16,9 @@ use crate::{
         manage_neuron_response,
         nervous_system_function::{FunctionType, GenericNervousSystemFunction},
         neuron, Account as AccountProto, Motion, NervousSystemFunction, NeuronPermissionType,
-        ProposalData, ProposalId, Tally, UpgradeJournalEntry, UpgradeSnsControlledCanister,
-        UpgradeSnsToNextVersion, VotingRewardsParameters, WaitForQuietState,
+        ProposalData, ProposalId, TakeDappCanisterSnapshot, Tally, UpgradeJournalEntry,
+        UpgradeSnsControlledCanister, UpgradeSnsToNextVersion, VotingRewardsParameters,
+        WaitForQuietState,
     },
     reward,
     sns_upgrade::{
@@ -3020,6 +3021,7 @@ fn test_sns_controlled_canister_upgrade_only_upgrades_dapp_canisters() {
             canister_upgrade_arg: None,
             mode: Some(CanisterInstallModeProto::Upgrade.into()),
             chunked_canister_wasm: None,
+            take_snapshot_before_upgrade: None,
         });
 
         // Upgrade Proposal
@@ -3371,6 +3373,92 @@ fn test_allow_canister_upgrades_proposal_does_not_block_itself_but_does_block_ot
     }
 }
 
+#[test]
+fn test_pre_upgrade_snapshot_replaces_the_previous_one() {
+    // Step 1: Prepare the world.
+    let dapp_canister_id = TEST_DAPP_CANISTER_IDS[0];
+    let other_canister_id = canister_test_id(2000);
+    let proposal_data =
+        |id: u64, action: Action, taken_snapshot_id: Option<Vec<u8>>| ProposalData {
+            action: (&action).into(),
+            id: Some(id.into()),
+            proposal: Some(Proposal {
+                action: Some(action),
+                ..Default::default()
+            }),
+            taken_snapshot_id,
+            ..Default::default()
+        };
+    let upgrade = |canister_id: CanisterId| {
+        Action::UpgradeSnsControlledCanister(UpgradeSnsControlledCanister {
+            canister_id: Some(canister_id.get()),
+            take_snapshot_before_upgrade: Some(true),
+            ..Default::default()
+        })
+    };
+    let take_snapshot = |canister_id: CanisterId, replace_snapshot: Option<Vec<u8>>| {
+        Action::TakeDappCanisterSnapshot(TakeDappCanisterSnapshot {
+            canister_id: Some(canister_id.get()),
+            replace_snapshot,
+        })
+    };
+
+    let mut governance = Governance::new(
+        GovernanceProto {
+            proposals: btreemap! {
+                1 => proposal_data(1, upgrade(dapp_canister_id), Some(vec![1])),
+                2 => proposal_data(2, upgrade(dapp_canister_id), Some(vec![2])),
+                3 => proposal_data(3, upgrade(other_canister_id), Some(vec![3])),
+                4 => proposal_data(4, upgrade(dapp_canister_id), None),
+            },
+            ..basic_governance_proto()
+        }
+        .try_into()
+        .unwrap(),
+        Box::<NativeEnvironment>::default(),
+        Box::new(DoNothingLedger {}),
+        Box::new(DoNothingLedger {}),
+        Box::new(FakeCmc::new()),
+    );
+
+    // Step 2 & 3: Run code under test and inspect results.
+
+    // The most recent pre-upgrade snapshot of the same canister is replaced.
+    assert_eq!(
+        governance.latest_pre_upgrade_snapshot_id(4, dapp_canister_id),
+        Some(vec![2])
+    );
+    assert_eq!(
+        governance.latest_pre_upgrade_snapshot_id(4, other_canister_id),
+        Some(vec![3])
+    );
+
+    // Once a TakeDappCanisterSnapshot proposal replaced that snapshot, there is nothing to replace.
+    governance.proto.proposals.insert(
+        5,
+        proposal_data(
+            5,
+            take_snapshot(dapp_canister_id, Some(vec![2])),
+            Some(vec![5]),
+        ),
+    );
+    assert_eq!(
+        governance.latest_pre_upgrade_snapshot_id(6, dapp_canister_id),
+        None
+    );
+
+    // The snapshot taken by an upgrade is recorded on its proposal.
+    governance.record_taken_snapshot_id(4, vec![4]);
+    assert_eq!(
+        governance.proto.proposals[&4].taken_snapshot_id,
+        Some(vec![4])
+    );
+    assert_eq!(
+        governance.latest_pre_upgrade_snapshot_id(6, dapp_canister_id),
+        Some(vec![4])
+    );
+}
+
 #[test]
 fn test_upgrade_proposals_blocked_by_pending_upgrade() {
     // Step 1: Prepare the world.
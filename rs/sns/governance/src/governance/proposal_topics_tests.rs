@@ -127,6 +127,20 @@ fn test_all_topics() {
                 ProposalCriticality::Normal,
             )),
         ),
+        (
+            pb::proposal::Action::TakeDappCanisterSnapshot(Default::default()),
+            Ok((
+                Some(pb::Topic::DappCanisterManagement),
+                ProposalCriticality::Normal,
+            )),
+        ),
+        (
+            pb::proposal::Action::LoadDappCanisterSnapshot(Default::default()),
+            Ok((
+                Some(pb::Topic::DappCanisterManagement),
+                ProposalCriticality::Normal,
+            )),
+        ),
         // ApplicationBusinessLogic - skipped, since this topic is for custom proposals.
 
         // Governance
@@ -149,6 +163,27 @@ fn test_all_topics() {
                 ProposalCriticality::Critical,
             )),
         ),
+        (
+            pb::proposal::Action::CreateTreasuryPaymentStream(Default::default()),
+            Ok((
+                Some(pb::Topic::TreasuryAssetManagement),
+                ProposalCriticality::Critical,
+            )),
+        ),
+        (
+            pb::proposal::Action::CancelTreasuryPaymentStream(Default::default()),
+            Ok((
+                Some(pb::Topic::TreasuryAssetManagement),
+                ProposalCriticality::Critical,
+            )),
+        ),
+        (
+            pb::proposal::Action::OpenFollowOnSwapRound(Default::default()),
+            Ok((
+                Some(pb::Topic::TreasuryAssetManagement),
+                ProposalCriticality::Critical,
+            )),
+        ),
         // CriticalDappOperations
         (
             pb::proposal::Action::DeregisterDappCanisters(Default::default()),
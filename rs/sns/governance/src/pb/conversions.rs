@@ -398,6 +398,7 @@ impl From<pb::UpgradeSnsControlledCanister> for pb_api::UpgradeSnsControlledCani
             chunked_canister_wasm: item
                 .chunked_canister_wasm
                 .map(pb_api::ChunkedCanisterWasm::from),
+            take_snapshot_before_upgrade: item.take_snapshot_before_upgrade,
         }
     }
 }
@@ -432,6 +433,7 @@ impl From<pb_api::UpgradeSnsControlledCanister> for pb::UpgradeSnsControlledCani
             chunked_canister_wasm: item
                 .chunked_canister_wasm
                 .map(pb::ChunkedCanisterWasm::from),
+            take_snapshot_before_upgrade: item.take_snapshot_before_upgrade,
         }
     }
 }
@@ -459,6 +461,165 @@ impl From<pb_api::TransferSnsTreasuryFunds> for pb::TransferSnsTreasuryFunds {
     }
 }
 
+impl From<pb::CreateTreasuryPaymentStream> for pb_api::CreateTreasuryPaymentStream {
+    fn from(item: pb::CreateTreasuryPaymentStream) -> Self {
+        Self {
+            from_treasury: item.from_treasury,
+            amount_per_period_e8s: item.amount_per_period_e8s,
+            period_seconds: item.period_seconds,
+            number_of_periods: item.number_of_periods,
+            cliff_seconds: item.cliff_seconds,
+            memo: item.memo,
+            to_principal: item.to_principal,
+            to_subaccount: item.to_subaccount.map(|x| x.into()),
+        }
+    }
+}
+impl From<pb_api::CreateTreasuryPaymentStream> for pb::CreateTreasuryPaymentStream {
+    fn from(item: pb_api::CreateTreasuryPaymentStream) -> Self {
+        Self {
+            from_treasury: item.from_treasury,
+            amount_per_period_e8s: item.amount_per_period_e8s,
+            period_seconds: item.period_seconds,
+            number_of_periods: item.number_of_periods,
+            cliff_seconds: item.cliff_seconds,
+            memo: item.memo,
+            to_principal: item.to_principal,
+            to_subaccount: item.to_subaccount.map(|x| x.into()),
+        }
+    }
+}
+
+impl From<pb::TakeDappCanisterSnapshot> for pb_api::TakeDappCanisterSnapshot {
+    fn from(item: pb::TakeDappCanisterSnapshot) -> Self {
+        Self {
+            canister_id: item.canister_id,
+            replace_snapshot: item.replace_snapshot,
+        }
+    }
+}
+impl From<pb_api::TakeDappCanisterSnapshot> for pb::TakeDappCanisterSnapshot {
+    fn from(item: pb_api::TakeDappCanisterSnapshot) -> Self {
+        Self {
+            canister_id: item.canister_id,
+            replace_snapshot: item.replace_snapshot,
+        }
+    }
+}
+
+impl From<pb::LoadDappCanisterSnapshot> for pb_api::LoadDappCanisterSnapshot {
+    fn from(item: pb::LoadDappCanisterSnapshot) -> Self {
+        Self {
+            canister_id: item.canister_id,
+            snapshot_id: item.snapshot_id,
+        }
+    }
+}
+impl From<pb_api::LoadDappCanisterSnapshot> for pb::LoadDappCanisterSnapshot {
+    fn from(item: pb_api::LoadDappCanisterSnapshot) -> Self {
+        Self {
+            canister_id: item.canister_id,
+            snapshot_id: item.snapshot_id,
+        }
+    }
+}
+
+impl From<pb::OpenFollowOnSwapRound> for pb_api::OpenFollowOnSwapRound {
+    fn from(item: pb::OpenFollowOnSwapRound) -> Self {
+        Self {
+            sns_token_e8s: item.sns_token_e8s,
+            min_direct_participation_icp_e8s: item.min_direct_participation_icp_e8s,
+            max_direct_participation_icp_e8s: item.max_direct_participation_icp_e8s,
+            min_participant_icp_e8s: item.min_participant_icp_e8s,
+            max_participant_icp_e8s: item.max_participant_icp_e8s,
+            min_participants: item.min_participants,
+            duration_seconds: item.duration_seconds,
+            neuron_basket_count: item.neuron_basket_count,
+            neuron_basket_dissolve_delay_interval_seconds: item
+                .neuron_basket_dissolve_delay_interval_seconds,
+        }
+    }
+}
+impl From<pb_api::OpenFollowOnSwapRound> for pb::OpenFollowOnSwapRound {
+    fn from(item: pb_api::OpenFollowOnSwapRound) -> Self {
+        Self {
+            sns_token_e8s: item.sns_token_e8s,
+            min_direct_participation_icp_e8s: item.min_direct_participation_icp_e8s,
+            max_direct_participation_icp_e8s: item.max_direct_participation_icp_e8s,
+            min_participant_icp_e8s: item.min_participant_icp_e8s,
+            max_participant_icp_e8s: item.max_participant_icp_e8s,
+            min_participants: item.min_participants,
+            duration_seconds: item.duration_seconds,
+            neuron_basket_count: item.neuron_basket_count,
+            neuron_basket_dissolve_delay_interval_seconds: item
+                .neuron_basket_dissolve_delay_interval_seconds,
+        }
+    }
+}
+
+impl From<pb::CancelTreasuryPaymentStream> for pb_api::CancelTreasuryPaymentStream {
+    fn from(item: pb::CancelTreasuryPaymentStream) -> Self {
+        Self {
+            payment_stream_id: item.payment_stream_id,
+        }
+    }
+}
+impl From<pb_api::CancelTreasuryPaymentStream> for pb::CancelTreasuryPaymentStream {
+    fn from(item: pb_api::CancelTreasuryPaymentStream) -> Self {
+        Self {
+            payment_stream_id: item.payment_stream_id,
+        }
+    }
+}
+
+impl From<pb::TreasuryPaymentStream> for pb_api::TreasuryPaymentStream {
+    fn from(item: pb::TreasuryPaymentStream) -> Self {
+        Self {
+            id: item.id,
+            schedule: item.schedule.map(|x| x.into()),
+            start_timestamp_seconds: item.start_timestamp_seconds,
+            valuation: item.valuation.map(|x| x.into()),
+            payments: item.payments.into_iter().map(|x| x.into()).collect(),
+            cancelled_timestamp_seconds: item.cancelled_timestamp_seconds,
+            last_failure_reason: item.last_failure_reason,
+            last_failure_timestamp_seconds: item.last_failure_timestamp_seconds,
+            consecutive_failure_count: item.consecutive_failure_count,
+        }
+    }
+}
+impl From<pb_api::TreasuryPaymentStream> for pb::TreasuryPaymentStream {
+    fn from(item: pb_api::TreasuryPaymentStream) -> Self {
+        Self {
+            id: item.id,
+            schedule: item.schedule.map(|x| x.into()),
+            start_timestamp_seconds: item.start_timestamp_seconds,
+            valuation: item.valuation.map(|x| x.into()),
+            payments: item.payments.into_iter().map(|x| x.into()).collect(),
+            cancelled_timestamp_seconds: item.cancelled_timestamp_seconds,
+            last_failure_reason: item.last_failure_reason,
+            last_failure_timestamp_seconds: item.last_failure_timestamp_seconds,
+            consecutive_failure_count: item.consecutive_failure_count,
+        }
+    }
+}
+
+impl From<pb::treasury_payment_stream::Payment> for pb_api::treasury_payment_stream::Payment {
+    fn from(item: pb::treasury_payment_stream::Payment) -> Self {
+        Self {
+            timestamp_seconds: item.timestamp_seconds,
+            amount_e8s: item.amount_e8s,
+        }
+    }
+}
+impl From<pb_api::treasury_payment_stream::Payment> for pb::treasury_payment_stream::Payment {
+    fn from(item: pb_api::treasury_payment_stream::Payment) -> Self {
+        Self {
+            timestamp_seconds: item.timestamp_seconds,
+            amount_e8s: item.amount_e8s,
+        }
+    }
+}
+
 impl From<pb::transfer_sns_treasury_funds::TransferFrom>
     for pb_api::transfer_sns_treasury_funds::TransferFrom
 {
@@ -893,6 +1054,21 @@ impl From<pb::proposal::Action> for pb_api::proposal::Action {
             pb::proposal::Action::SetTopicsForCustomProposals(v) => {
                 pb_api::proposal::Action::SetTopicsForCustomProposals(v.into())
             }
+            pb::proposal::Action::CreateTreasuryPaymentStream(v) => {
+                pb_api::proposal::Action::CreateTreasuryPaymentStream(v.into())
+            }
+            pb::proposal::Action::CancelTreasuryPaymentStream(v) => {
+                pb_api::proposal::Action::CancelTreasuryPaymentStream(v.into())
+            }
+            pb::proposal::Action::TakeDappCanisterSnapshot(v) => {
+                pb_api::proposal::Action::TakeDappCanisterSnapshot(v.into())
+            }
+            pb::proposal::Action::LoadDappCanisterSnapshot(v) => {
+                pb_api::proposal::Action::LoadDappCanisterSnapshot(v.into())
+            }
+            pb::proposal::Action::OpenFollowOnSwapRound(v) => {
+                pb_api::proposal::Action::OpenFollowOnSwapRound(v.into())
+            }
         }
     }
 }
@@ -949,6 +1125,21 @@ impl From<pb_api::proposal::Action> for pb::proposal::Action {
             pb_api::proposal::Action::SetTopicsForCustomProposals(v) => {
                 pb::proposal::Action::SetTopicsForCustomProposals(v.into())
             }
+            pb_api::proposal::Action::CreateTreasuryPaymentStream(v) => {
+                pb::proposal::Action::CreateTreasuryPaymentStream(v.into())
+            }
+            pb_api::proposal::Action::CancelTreasuryPaymentStream(v) => {
+                pb::proposal::Action::CancelTreasuryPaymentStream(v.into())
+            }
+            pb_api::proposal::Action::TakeDappCanisterSnapshot(v) => {
+                pb::proposal::Action::TakeDappCanisterSnapshot(v.into())
+            }
+            pb_api::proposal::Action::LoadDappCanisterSnapshot(v) => {
+                pb::proposal::Action::LoadDappCanisterSnapshot(v.into())
+            }
+            pb_api::proposal::Action::OpenFollowOnSwapRound(v) => {
+                pb::proposal::Action::OpenFollowOnSwapRound(v.into())
+            }
         }
     }
 }
@@ -1182,6 +1373,7 @@ impl From<pb::ProposalData> for pb_api::ProposalData {
             minimum_yes_proportion_of_exercised: item.minimum_yes_proportion_of_exercised,
             action_auxiliary: item.action_auxiliary.map(|x| x.into()),
             topic: item.topic.and_then(topic_id_to_api),
+            taken_snapshot_id: item.taken_snapshot_id,
         }
     }
 }
@@ -1215,6 +1407,7 @@ impl From<pb_api::ProposalData> for pb::ProposalData {
             minimum_yes_proportion_of_exercised: item.minimum_yes_proportion_of_exercised,
             action_auxiliary: item.action_auxiliary.map(|x| x.into()),
             topic: item.topic.map(|topic| i32::from(pb::Topic::from(topic))),
+            taken_snapshot_id: item.taken_snapshot_id,
         }
     }
 }
@@ -1257,6 +1450,25 @@ impl From<pb_api::proposal_data::MintSnsTokensActionAuxiliary>
     }
 }
 
+impl From<pb::proposal_data::CreateTreasuryPaymentStreamActionAuxiliary>
+    for pb_api::proposal_data::CreateTreasuryPaymentStreamActionAuxiliary
+{
+    fn from(item: pb::proposal_data::CreateTreasuryPaymentStreamActionAuxiliary) -> Self {
+        Self {
+            valuation: item.valuation.map(|x| x.into()),
+        }
+    }
+}
+impl From<pb_api::proposal_data::CreateTreasuryPaymentStreamActionAuxiliary>
+    for pb::proposal_data::CreateTreasuryPaymentStreamActionAuxiliary
+{
+    fn from(item: pb_api::proposal_data::CreateTreasuryPaymentStreamActionAuxiliary) -> Self {
+        Self {
+            valuation: item.valuation.map(|x| x.into()),
+        }
+    }
+}
+
 impl From<pb::proposal_data::AdvanceSnsTargetVersionActionAuxiliary>
     for pb_api::proposal_data::AdvanceSnsTargetVersionActionAuxiliary
 {
@@ -1288,6 +1500,9 @@ impl From<pb::proposal_data::ActionAuxiliary> for pb_api::proposal_data::ActionA
             pb::proposal_data::ActionAuxiliary::AdvanceSnsTargetVersion(v) => {
                 pb_api::proposal_data::ActionAuxiliary::AdvanceSnsTargetVersion(v.into())
             }
+            pb::proposal_data::ActionAuxiliary::CreateTreasuryPaymentStream(v) => {
+                pb_api::proposal_data::ActionAuxiliary::CreateTreasuryPaymentStream(v.into())
+            }
         }
     }
 }
@@ -1304,6 +1519,9 @@ impl From<pb_api::proposal_data::ActionAuxiliary> for pb::proposal_data::ActionA
             pb_api::proposal_data::ActionAuxiliary::AdvanceSnsTargetVersion(v) => {
                 pb::proposal_data::ActionAuxiliary::AdvanceSnsTargetVersion(v.into())
             }
+            pb_api::proposal_data::ActionAuxiliary::CreateTreasuryPaymentStream(v) => {
+                pb::proposal_data::ActionAuxiliary::CreateTreasuryPaymentStream(v.into())
+            }
         }
     }
 }
@@ -1559,6 +1777,11 @@ impl From<pb::Governance> for pb_api::Governance {
             cached_upgrade_steps: item.cached_upgrade_steps.map(|x| x.into()),
             timers: item.timers,
             upgrade_journal: item.upgrade_journal.map(|x| x.into()),
+            treasury_payment_streams: item
+                .treasury_payment_streams
+                .into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
         }
     }
 }
@@ -1603,6 +1826,11 @@ impl From<pb_api::Governance> for pb::Governance {
             cached_upgrade_steps: item.cached_upgrade_steps.map(|x| x.into()),
             timers: item.timers,
             upgrade_journal: item.upgrade_journal.map(|x| x.into()),
+            treasury_payment_streams: item
+                .treasury_payment_streams
+                .into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
         }
     }
 }
@@ -16,14 +16,17 @@ use crate::{
         proposal::Action,
         proposal_data::{
             self, ActionAuxiliary as ActionAuxiliaryPb, AdvanceSnsTargetVersionActionAuxiliary,
-            MintSnsTokensActionAuxiliary, TransferSnsTreasuryFundsActionAuxiliary,
+            CreateTreasuryPaymentStreamActionAuxiliary, MintSnsTokensActionAuxiliary,
+            TransferSnsTreasuryFundsActionAuxiliary,
         },
         transfer_sns_treasury_funds::TransferFrom,
-        DeregisterDappCanisters, ExecuteGenericNervousSystemFunction, Governance, GovernanceError,
+        CancelTreasuryPaymentStream, CreateTreasuryPaymentStream, DeregisterDappCanisters,
+        ExecuteGenericNervousSystemFunction, Governance, GovernanceError, LoadDappCanisterSnapshot,
         LogVisibility, ManageDappCanisterSettings, ManageLedgerParameters, ManageSnsMetadata,
-        MintSnsTokens, Motion, NervousSystemFunction, NervousSystemParameters, Proposal,
-        ProposalData, ProposalDecisionStatus, ProposalId, ProposalRewardStatus,
-        RegisterDappCanisters, SnsVersion, Tally, Topic as TopicPb, TransferSnsTreasuryFunds,
+        MintSnsTokens, Motion, NervousSystemFunction, NervousSystemParameters,
+        OpenFollowOnSwapRound, Proposal, ProposalData, ProposalDecisionStatus, ProposalId,
+        ProposalRewardStatus, RegisterDappCanisters, SnsVersion, TakeDappCanisterSnapshot, Tally,
+        Topic as TopicPb, TransferSnsTreasuryFunds, TreasuryPaymentStream,
         UpgradeSnsControlledCanister, UpgradeSnsToNextVersion, Valuation as ValuationPb, Vote,
     },
     sns_upgrade::{get_proposal_id_that_added_wasm, get_upgrade_params, UpgradeSnsParams},
@@ -31,7 +34,7 @@ use crate::{
     validate_chars_count, validate_len, validate_required_field,
 };
 use candid::Principal;
-use ic_base_types::{CanisterId, PrincipalId};
+use ic_base_types::{CanisterId, PrincipalId, SnapshotId};
 use ic_canister_log::log;
 use ic_crypto_sha2::Sha256;
 use ic_nervous_system_common::{
@@ -73,6 +76,18 @@ pub const MAX_LIST_PROPOSAL_RESULTS: u32 = 100;
 /// The maximum number of unsettled proposals (proposals for which ballots are still stored).
 pub const MAX_NUMBER_OF_PROPOSALS_WITH_BALLOTS: usize = 700;
 
+/// The shortest period allowed in a CreateTreasuryPaymentStream proposal.
+pub const MIN_TREASURY_PAYMENT_STREAM_PERIOD_SECONDS: u64 = ONE_DAY_SECONDS;
+
+/// The largest number of payments allowed in a CreateTreasuryPaymentStream proposal. Each payment
+/// is recorded in the stream, so this also bounds the size of a stream.
+pub const MAX_TREASURY_PAYMENT_STREAM_NUMBER_OF_PERIODS: u64 = 120;
+
+/// The shortest and longest duration allowed in an OpenFollowOnSwapRound proposal. These match
+/// the bounds that the swap canister enforces on the duration of a swap.
+pub const MIN_FOLLOW_ON_SWAP_ROUND_DURATION_SECONDS: u64 = ONE_DAY_SECONDS;
+pub const MAX_FOLLOW_ON_SWAP_ROUND_DURATION_SECONDS: u64 = 14 * ONE_DAY_SECONDS;
+
 /// The maximum number of GenericNervousSystemFunctions the system allows.
 pub const MAX_NUMBER_OF_GENERIC_NERVOUS_SYSTEM_FUNCTIONS: usize = 200_000;
 
@@ -174,6 +189,7 @@ pub(crate) enum ActionAuxiliary {
     TransferSnsTreasuryFunds(Valuation),
     MintSnsTokens(Valuation),
     AdvanceSnsTargetVersion(Version),
+    CreateTreasuryPaymentStream(Valuation),
     None,
 }
 
@@ -193,6 +209,23 @@ impl ActionAuxiliary {
         }
     }
 
+    pub fn unwrap_create_treasury_payment_stream_or_err(
+        self,
+    ) -> Result<Valuation, GovernanceError> {
+        match self {
+            Self::CreateTreasuryPaymentStream(valuation) => Ok(valuation),
+
+            wrong => Err(GovernanceError::new_with_message(
+                ErrorType::InconsistentInternalData,
+                format!(
+                    "Missing supporting information. Specifically, \
+                     no treasury valuation factors: {:#?}",
+                    wrong,
+                ),
+            )),
+        }
+    }
+
     pub fn unwrap_advance_sns_target_version_or_err(self) -> Result<Version, GovernanceError> {
         match self {
             Self::AdvanceSnsTargetVersion(new_target) => Ok(new_target),
@@ -239,6 +272,14 @@ impl TryFrom<ActionAuxiliary> for Option<ActionAuxiliaryPb> {
                     },
                 ))
             }
+
+            ActionAuxiliary::CreateTreasuryPaymentStream(valuation) => {
+                Some(ActionAuxiliaryPb::CreateTreasuryPaymentStream(
+                    CreateTreasuryPaymentStreamActionAuxiliary {
+                        valuation: Some(ValuationPb::try_from(valuation)?),
+                    },
+                ))
+            }
         };
 
         Ok(result)
@@ -284,6 +325,14 @@ impl TryFrom<&Option<ActionAuxiliaryPb>> for ActionAuxiliary {
 
                 ActionAuxiliary::AdvanceSnsTargetVersion(target_version)
             }
+            Some(ActionAuxiliaryPb::CreateTreasuryPaymentStream(action_auxiliary)) => {
+                let CreateTreasuryPaymentStreamActionAuxiliary { valuation } = action_auxiliary;
+
+                let valuation = Valuation::try_from(valuation.as_ref().unwrap_or_default())
+                    .map_err(|err| format!("Invalid ActionAuxiliaryPb {:?}: {}", src, err))?;
+
+                ActionAuxiliary::CreateTreasuryPaymentStream(valuation)
+            }
         };
 
         Ok(result)
@@ -398,6 +447,7 @@ pub(crate) async fn validate_and_render_action(
     let swap_canister_id = governance_proto.swap_canister_id_or_panic();
     let sns_ledger_canister_id = governance_proto.ledger_canister_id_or_panic();
     let proposals = governance_proto.proposals.values();
+    let payment_streams = governance_proto.treasury_payment_streams.values();
 
     match action {
         proposal::Action::Unspecified(_unspecified) => {
@@ -468,6 +518,7 @@ pub(crate) async fn validate_and_render_action(
                 swap_canister_id,
                 sns_ledger_canister_id,
                 proposals,
+                payment_streams,
             )
             .await;
         }
@@ -479,6 +530,7 @@ pub(crate) async fn validate_and_render_action(
                 swap_canister_id,
                 sns_ledger_canister_id,
                 proposals,
+                payment_streams,
             )
             .await;
         }
@@ -501,6 +553,39 @@ pub(crate) async fn validate_and_render_action(
                 &governance_proto.custom_functions_to_topics(),
             )
         }
+        proposal::Action::CreateTreasuryPaymentStream(create) => {
+            return validate_and_render_create_treasury_payment_stream(
+                create,
+                sns_transfer_fee_e8s,
+                env,
+                swap_canister_id,
+                sns_ledger_canister_id,
+                proposals,
+                payment_streams,
+            )
+            .await;
+        }
+        proposal::Action::CancelTreasuryPaymentStream(cancel) => {
+            validate_and_render_cancel_treasury_payment_stream(
+                cancel,
+                &governance_proto.treasury_payment_streams,
+            )
+        }
+        proposal::Action::TakeDappCanisterSnapshot(take_dapp_canister_snapshot) => {
+            validate_and_render_take_dapp_canister_snapshot(
+                take_dapp_canister_snapshot,
+                &disallowed_target_canister_ids,
+            )
+        }
+        proposal::Action::LoadDappCanisterSnapshot(load_dapp_canister_snapshot) => {
+            validate_and_render_load_dapp_canister_snapshot(
+                load_dapp_canister_snapshot,
+                &disallowed_target_canister_ids,
+            )
+        }
+        proposal::Action::OpenFollowOnSwapRound(open_follow_on_swap_round) => {
+            validate_and_render_open_follow_on_swap_round(open_follow_on_swap_round)
+        }
     }
     .map(|rendering| (rendering, ActionAuxiliary::None))
 }
@@ -550,13 +635,14 @@ fn validate_and_render_manage_nervous_system_parameters(
 /// Validates and render TransferSnsTreasuryFunds proposal
 ///
 /// Returns ActionAuxiliary::TransferSnsTreasuryFunds.
-async fn validate_and_render_transfer_sns_treasury_funds(
+async fn validate_and_render_transfer_sns_treasury_funds<'a>(
     transfer: &TransferSnsTreasuryFunds,
     sns_transfer_fee_e8s: u64,
     env: &dyn Environment,
     swap_canister_id: CanisterId,
     sns_ledger_canister_id: CanisterId,
-    proposals: impl Iterator<Item = &ProposalData>,
+    proposals: impl Iterator<Item = &'a ProposalData>,
+    payment_streams: impl Iterator<Item = &'a TreasuryPaymentStream>,
 ) -> Result<
     (
         String, // Rendering.
@@ -572,6 +658,7 @@ async fn validate_and_render_transfer_sns_treasury_funds(
         sns_ledger_canister_id,
         swap_canister_id,
         proposals,
+        payment_streams,
         transfer,
     )
     .await;
@@ -700,6 +787,317 @@ fn locally_validate_and_render_transfer_sns_treasury_funds(
     ))
 }
 
+/// Validates and renders a CreateTreasuryPaymentStream proposal.
+///
+/// Returns ActionAuxiliary::CreateTreasuryPaymentStream.
+async fn validate_and_render_create_treasury_payment_stream<'a>(
+    create: &CreateTreasuryPaymentStream,
+    sns_transfer_fee_e8s: u64,
+    env: &dyn Environment,
+    swap_canister_id: CanisterId,
+    sns_ledger_canister_id: CanisterId,
+    proposals: impl Iterator<Item = &'a ProposalData>,
+    payment_streams: impl Iterator<Item = &'a TreasuryPaymentStream>,
+) -> Result<
+    (
+        String, // Rendering.
+        ActionAuxiliary,
+    ),
+    String,
+> {
+    let mut defects = vec![];
+
+    // Like for TransferSnsTreasuryFunds, the amount that the stream can pay within 7 days must
+    // not exceed the upper bound. This requires calling CMC and the swap canister; hence, await.
+    let valuation = treasury_valuation_if_proposal_amount_is_small_enough_or_err(
+        env,
+        sns_ledger_canister_id,
+        swap_canister_id,
+        proposals,
+        payment_streams,
+        create,
+    )
+    .await;
+    let valuation = match valuation {
+        Ok(ok) => Some(ok),
+        Err(err) => {
+            defects.push(err);
+            None
+        }
+    };
+
+    locally_validate_and_render_create_treasury_payment_stream(
+        create,
+        sns_transfer_fee_e8s,
+        defects,
+    )
+    .and_then(|rendering| match valuation {
+        Some(valuation) => Ok((
+            rendering,
+            ActionAuxiliary::CreateTreasuryPaymentStream(valuation),
+        )),
+        // Same reasoning as in validate_and_render_transfer_sns_treasury_funds.
+        None => Err(
+            "There seems to be a bug in the amount validator. Somehow, no valuation, \
+             even though a rendering was generated."
+                .to_string(),
+        ),
+    })
+}
+
+/// Performs all the validation on a CreateTreasuryPaymentStream that does not require fetching
+/// information from other canisters.
+fn locally_validate_and_render_create_treasury_payment_stream(
+    create: &CreateTreasuryPaymentStream,
+    sns_transfer_fee_e8s: u64,
+    mut defects: Vec<String>,
+) -> Result<String, String> {
+    let (from, unit, minimum_transaction) = match create.from_treasury() {
+        TransferFrom::IcpTreasury => (
+            "ICP Treasury (ICP Ledger)",
+            "ICP",
+            NNS_DEFAULT_TRANSFER_FEE.get_e8s(),
+        ),
+        TransferFrom::SnsTokenTreasury => (
+            "SNS Token Treasury (SNS Ledger)",
+            "SNS Tokens",
+            sns_transfer_fee_e8s,
+        ),
+        TransferFrom::Unspecified => {
+            defects.push(
+                "Must specify a treasury from which to make the payments (ICP/SNS Token)."
+                    .to_string(),
+            );
+            ("", "", 0)
+        }
+    };
+
+    let amount_per_period_e8s = create.amount_per_period_e8s.unwrap_or_default();
+    if amount_per_period_e8s < minimum_transaction {
+        defects.push(format!(
+            "For payments from {}, the fee and minimum transaction is {} e8s",
+            from, minimum_transaction
+        ));
+    }
+
+    let period_seconds = create.period_seconds.unwrap_or_default();
+    if period_seconds < MIN_TREASURY_PAYMENT_STREAM_PERIOD_SECONDS {
+        defects.push(format!(
+            "period_seconds must be at least {} seconds.",
+            MIN_TREASURY_PAYMENT_STREAM_PERIOD_SECONDS,
+        ));
+    }
+
+    let number_of_periods = create.number_of_periods.unwrap_or_default();
+    if !(1..=MAX_TREASURY_PAYMENT_STREAM_NUMBER_OF_PERIODS).contains(&number_of_periods) {
+        defects.push(format!(
+            "number_of_periods must be between 1 and {}.",
+            MAX_TREASURY_PAYMENT_STREAM_NUMBER_OF_PERIODS,
+        ));
+    }
+
+    let cliff_seconds = create.cliff_seconds.unwrap_or_default();
+    let duration_seconds = period_seconds.saturating_mul(number_of_periods);
+    if cliff_seconds > duration_seconds {
+        defects.push(format!(
+            "cliff_seconds ({}) must not be longer than the stream ({} seconds).",
+            cliff_seconds, duration_seconds,
+        ));
+    }
+
+    let to_principal = match create.to_principal {
+        Some(to_principal) if to_principal == PrincipalId::new_anonymous() => {
+            defects.push("to_principal must not be anonymous.".to_string());
+            to_principal
+        }
+        Some(to_principal) => to_principal,
+        None => {
+            defects.push("Must specify a principal to make the payments to.".to_string());
+            PrincipalId::new_anonymous()
+        }
+    };
+
+    let to_subaccount = match &create.to_subaccount {
+        None => None,
+        Some(s) => match bytes_to_subaccount(&s.subaccount[..]) {
+            Ok(s) => Some(s),
+            Err(e) => {
+                defects.push(e.error_message);
+                None
+            }
+        },
+    };
+    let to_account = Account {
+        owner: to_principal.0,
+        subaccount: to_subaccount,
+    };
+
+    if !defects.is_empty() {
+        return Err(format!(
+            "CreateTreasuryPaymentStream proposal was invalid for the following reason(s):\n{}",
+            defects.join("\n"),
+        ));
+    }
+
+    let display_amount_tokens = i2d(amount_per_period_e8s) / i2d(E8);
+    let display_total_tokens = display_amount_tokens * i2d(number_of_periods);
+    Ok(format!(
+        r"# Proposal to create a treasury payment stream:
+## Source treasury: {from}
+## Amount per period: {display_amount_tokens:.8} {unit}
+## Amount per period (e8s): {amount_per_period_e8s}
+## Period: {period}
+## Number of periods: {number_of_periods}
+## Cliff: {cliff}
+## Total amount: {display_total_tokens:.8} {unit}
+## Target principal: {to_principal}
+## Target account: {to_account}
+## Memo: {memo}",
+        period = format_duration_seconds(period_seconds),
+        cliff = format_duration_seconds(cliff_seconds),
+        memo = create.memo.unwrap_or(0),
+    ))
+}
+
+fn format_duration_seconds(seconds: u64) -> String {
+    format!(
+        "{} seconds ({:.2} days)",
+        seconds,
+        seconds as f64 / ONE_DAY_SECONDS as f64
+    )
+}
+
+/// Validates and renders a CancelTreasuryPaymentStream proposal. The stream must exist, and
+/// must still have payments to make.
+fn validate_and_render_cancel_treasury_payment_stream(
+    cancel: &CancelTreasuryPaymentStream,
+    payment_streams: &BTreeMap<u64, TreasuryPaymentStream>,
+) -> Result<String, String> {
+    let Some(payment_stream_id) = cancel.payment_stream_id else {
+        return Err("CancelTreasuryPaymentStream.payment_stream_id must be set.".to_string());
+    };
+
+    let Some(payment_stream) = payment_streams.get(&payment_stream_id) else {
+        return Err(format!(
+            "There is no treasury payment stream with ID {}.",
+            payment_stream_id
+        ));
+    };
+
+    if payment_stream.is_finished() {
+        return Err(format!(
+            "Treasury payment stream {} is already cancelled or complete.",
+            payment_stream_id
+        ));
+    }
+
+    let schedule = payment_stream.schedule.clone().unwrap_or_default();
+    Ok(format!(
+        r"# Proposal to cancel treasury payment stream {payment_stream_id}:
+## Payments made: {payments_made} out of {number_of_periods}
+## Amount per period (e8s): {amount_per_period_e8s}
+## Target principal: {to_principal}",
+        payments_made = payment_stream.payments.len(),
+        number_of_periods = schedule.number_of_periods.unwrap_or_default(),
+        amount_per_period_e8s = schedule.amount_per_period_e8s.unwrap_or_default(),
+        to_principal = schedule
+            .to_principal
+            .map(|principal| principal.to_string())
+            .unwrap_or_default(),
+    ))
+}
+
+/// Validates and renders a proposal with action OpenFollowOnSwapRound.
+///
+/// Only the proposal's own consistency is checked here. The swap canister performs the full
+/// validation of the round's parameters when the proposal is executed.
+fn validate_and_render_open_follow_on_swap_round(
+    open: &OpenFollowOnSwapRound,
+) -> Result<String, String> {
+    let OpenFollowOnSwapRound {
+        sns_token_e8s,
+        min_direct_participation_icp_e8s,
+        max_direct_participation_icp_e8s,
+        min_participant_icp_e8s,
+        max_participant_icp_e8s,
+        min_participants,
+        duration_seconds,
+        neuron_basket_count,
+        neuron_basket_dissolve_delay_interval_seconds,
+    } = *open;
+
+    let required = |field_name: &str, value: Option<u64>| -> Result<u64, String> {
+        match value {
+            Some(value) if value > 0 => Ok(value),
+            _ => Err(format!(
+                "OpenFollowOnSwapRound.{} must be set to a positive value.",
+                field_name
+            )),
+        }
+    };
+
+    let sns_token_e8s = required("sns_token_e8s", sns_token_e8s)?;
+    let min_direct_participation_icp_e8s = required(
+        "min_direct_participation_icp_e8s",
+        min_direct_participation_icp_e8s,
+    )?;
+    let max_direct_participation_icp_e8s = required(
+        "max_direct_participation_icp_e8s",
+        max_direct_participation_icp_e8s,
+    )?;
+    let min_participant_icp_e8s = required("min_participant_icp_e8s", min_participant_icp_e8s)?;
+    let max_participant_icp_e8s = required("max_participant_icp_e8s", max_participant_icp_e8s)?;
+    let min_participants = required("min_participants", min_participants.map(u64::from))?;
+    let duration_seconds = required("duration_seconds", duration_seconds)?;
+    let neuron_basket_count = required("neuron_basket_count", neuron_basket_count)?;
+    let neuron_basket_dissolve_delay_interval_seconds =
+        neuron_basket_dissolve_delay_interval_seconds.ok_or_else(|| {
+            "OpenFollowOnSwapRound.neuron_basket_dissolve_delay_interval_seconds must be set."
+                .to_string()
+        })?;
+
+    if min_direct_participation_icp_e8s > max_direct_participation_icp_e8s {
+        return Err(format!(
+            "min_direct_participation_icp_e8s ({}) must be at most \
+             max_direct_participation_icp_e8s ({}).",
+            min_direct_participation_icp_e8s, max_direct_participation_icp_e8s,
+        ));
+    }
+    if min_participant_icp_e8s > max_participant_icp_e8s {
+        return Err(format!(
+            "min_participant_icp_e8s ({}) must be at most max_participant_icp_e8s ({}).",
+            min_participant_icp_e8s, max_participant_icp_e8s,
+        ));
+    }
+    if max_participant_icp_e8s > max_direct_participation_icp_e8s {
+        return Err(format!(
+            "max_participant_icp_e8s ({}) must be at most \
+             max_direct_participation_icp_e8s ({}).",
+            max_participant_icp_e8s, max_direct_participation_icp_e8s,
+        ));
+    }
+    if !(MIN_FOLLOW_ON_SWAP_ROUND_DURATION_SECONDS..=MAX_FOLLOW_ON_SWAP_ROUND_DURATION_SECONDS)
+        .contains(&duration_seconds)
+    {
+        return Err(format!(
+            "duration_seconds ({}) must be between {} and {}.",
+            duration_seconds,
+            MIN_FOLLOW_ON_SWAP_ROUND_DURATION_SECONDS,
+            MAX_FOLLOW_ON_SWAP_ROUND_DURATION_SECONDS,
+        ));
+    }
+
+    Ok(format!(
+        r"# Proposal to open a follow-on swap round:
+## SNS tokens offered (e8s): {sns_token_e8s}
+## Direct participation (ICP e8s): {min_direct_participation_icp_e8s} to {max_direct_participation_icp_e8s}
+## Participation per participant (ICP e8s): {min_participant_icp_e8s} to {max_participant_icp_e8s}
+## Minimum number of participants: {min_participants}
+## Duration (seconds): {duration_seconds}
+## Neuron basket: {neuron_basket_count} neurons, dissolve delay interval {neuron_basket_dissolve_delay_interval_seconds} seconds"
+    ))
+}
+
 /// The only thing that implements this is Token.
 // treasury_account could be moved to impl Token if TREASURY_SUBACCOUNT_NONCE where defined in
 // another crate instead of this one.
@@ -743,10 +1141,12 @@ trait TokenProposalAction {
 
     /// First, this filters proposals for those like self that have been executed in the "recent"
     /// past (where "recent" is defined by Self). Then, this adds up the amounts in those
-    /// proposals.
+    /// proposals, and in the payments of treasury payment streams that are subject to the same
+    /// upper bound.
     fn recent_amount_total_tokens<'a>(
         &self,
         proposals: impl Iterator<Item = &'a ProposalData>,
+        payment_streams: impl Iterator<Item = &'a TreasuryPaymentStream>,
         now_timestamp_seconds: u64,
     ) -> Result<Decimal, String>;
 
@@ -767,25 +1167,27 @@ trait TokenProposalAction {
 //     }
 // }
 // ```
-async fn treasury_valuation_if_proposal_amount_is_small_enough_or_err<MyTokenProposalAction>(
+async fn treasury_valuation_if_proposal_amount_is_small_enough_or_err<'a, MyTokenProposalAction>(
     env: &dyn Environment,
     sns_ledger_canister_id: CanisterId,
     swap_canister_id: CanisterId,
-    proposals: impl Iterator<Item = &ProposalData>,
+    proposals: impl Iterator<Item = &'a ProposalData>,
+    payment_streams: impl Iterator<Item = &'a TreasuryPaymentStream>,
     action: &MyTokenProposalAction,
 ) -> Result<Valuation, String>
 where
     MyTokenProposalAction: TokenProposalAction,
 {
-    let spent_tokens = action.recent_amount_total_tokens(proposals, env.now())?;
+    let spent_tokens = action.recent_amount_total_tokens(proposals, payment_streams, env.now())?;
 
     // Get valuation of the tokens in the treasury.
-    let token = action.token()?;
-    let treasury_account = token.treasury_account(env.canister_id())?;
-    let valuation = token
-        .assess_balance(sns_ledger_canister_id, swap_canister_id, treasury_account)
-        .await
-        .map_err(|valuation_error| format!("Unable to validate amount: {:?}", valuation_error))?;
+    let valuation = token_valuation(
+        env,
+        sns_ledger_canister_id,
+        swap_canister_id,
+        action.token()?,
+    )
+    .await?;
 
     // From valuation, determine limit on the total from the past 7 days.
     let max_tokens = MyTokenProposalAction::recent_amount_total_upper_bound_tokens(&valuation)
@@ -818,6 +1220,38 @@ where
     Ok(valuation)
 }
 
+/// Assesses the current value of the tokens in the treasury of `token`.
+async fn token_valuation(
+    env: &dyn Environment,
+    sns_ledger_canister_id: CanisterId,
+    swap_canister_id: CanisterId,
+    token: Token,
+) -> Result<Valuation, String> {
+    let treasury_account = token.treasury_account(env.canister_id())?;
+    token
+        .assess_balance(sns_ledger_canister_id, swap_canister_id, treasury_account)
+        .await
+        .map_err(|valuation_error| format!("Unable to validate amount: {:?}", valuation_error))
+}
+
+/// Assesses the current value of the tokens in the treasury that a payment stream pays from.
+/// Payments are checked against this, rather than against the valuation at proposal time,
+/// because a stream can run for much longer than the valuation remains accurate.
+pub(crate) async fn treasury_payment_stream_valuation(
+    env: &dyn Environment,
+    sns_ledger_canister_id: CanisterId,
+    swap_canister_id: CanisterId,
+    schedule: &CreateTreasuryPaymentStream,
+) -> Result<Valuation, String> {
+    token_valuation(
+        env,
+        sns_ledger_canister_id,
+        swap_canister_id,
+        schedule.token()?,
+    )
+    .await
+}
+
 impl TokenProposalAction for TransferSnsTreasuryFunds {
     fn token(&self) -> Result<Token, String> {
         let transfer_from = TransferFrom::try_from(self.from_treasury).map_err(|err| {
@@ -854,10 +1288,12 @@ impl TokenProposalAction for TransferSnsTreasuryFunds {
     fn recent_amount_total_tokens<'a>(
         &self,
         proposals: impl Iterator<Item = &'a ProposalData>,
+        payment_streams: impl Iterator<Item = &'a TreasuryPaymentStream>,
         now_timestamp_seconds: u64,
     ) -> Result<Decimal, String> {
-        total_treasury_transfer_amount_tokens(
+        total_treasury_amount_tokens(
             proposals,
+            payment_streams,
             self.from_treasury(),
             now_timestamp_seconds - 7 * ONE_DAY_SECONDS,
         )
@@ -872,16 +1308,67 @@ impl TokenProposalAction for TransferSnsTreasuryFunds {
     }
 }
 
+impl TokenProposalAction for CreateTreasuryPaymentStream {
+    fn token(&self) -> Result<Token, String> {
+        match self.from_treasury() {
+            TransferFrom::IcpTreasury => Ok(Token::Icp),
+            TransferFrom::SnsTokenTreasury => Ok(Token::SnsToken),
+            TransferFrom::Unspecified => Err(format!(
+                "Invalid CreateTreasuryPaymentStream: \
+                 The `from_treasury` field does not hold a supported value: {:#?}",
+                self,
+            )),
+        }
+    }
+
+    /// The most that the stream pays within 7 days, if payments are made on time.
+    fn proposal_amount_tokens(&self) -> Result<Decimal, String> {
+        let period_seconds = self.period_seconds.unwrap_or_default().max(1);
+        let periods_per_7_days = (7 * ONE_DAY_SECONDS).div_ceil(period_seconds);
+        let periods = periods_per_7_days.min(self.number_of_periods.unwrap_or_default());
+        let amount_e8s = self
+            .amount_per_period_e8s
+            .unwrap_or_default()
+            .saturating_mul(periods);
+
+        denominations_to_tokens(amount_e8s, E8).ok_or_else(|| {
+            format!(
+                "Unable to convert proposal amount {} e8s to tokens.",
+                amount_e8s,
+            )
+        })
+    }
+
+    fn recent_amount_total_tokens<'a>(
+        &self,
+        proposals: impl Iterator<Item = &'a ProposalData>,
+        payment_streams: impl Iterator<Item = &'a TreasuryPaymentStream>,
+        now_timestamp_seconds: u64,
+    ) -> Result<Decimal, String> {
+        total_treasury_amount_tokens(
+            proposals,
+            payment_streams,
+            self.from_treasury(),
+            now_timestamp_seconds - 7 * ONE_DAY_SECONDS,
+        )
+    }
+
+    fn recent_amount_total_upper_bound_tokens(valuation: &Valuation) -> Result<Decimal, String> {
+        TransferSnsTreasuryFunds::recent_amount_total_upper_bound_tokens(valuation)
+    }
+}
+
 /// Validates and render MintSnsTokens proposal.
 ///
 /// Returns ActionAuxiliary::MintSnsTokens.
-async fn validate_and_render_mint_sns_tokens(
+async fn validate_and_render_mint_sns_tokens<'a>(
     mint_sns_tokens: &MintSnsTokens,
     sns_transfer_fee_e8s: u64,
     env: &dyn Environment,
     swap_canister_id: CanisterId,
     sns_ledger_canister_id: CanisterId,
-    proposals: impl Iterator<Item = &ProposalData>,
+    proposals: impl Iterator<Item = &'a ProposalData>,
+    payment_streams: impl Iterator<Item = &'a TreasuryPaymentStream>,
 ) -> Result<
     (
         String, // Rendering.
@@ -897,6 +1384,7 @@ async fn validate_and_render_mint_sns_tokens(
         sns_ledger_canister_id,
         swap_canister_id,
         proposals,
+        payment_streams,
         mint_sns_tokens,
     )
     .await;
@@ -1023,6 +1511,8 @@ impl TokenProposalAction for MintSnsTokens {
     fn recent_amount_total_tokens<'a>(
         &self,
         proposals: impl Iterator<Item = &'a ProposalData>,
+        // Payment streams pay from the treasury, they do not mint.
+        _payment_streams: impl Iterator<Item = &'a TreasuryPaymentStream>,
         now_timestamp_seconds: u64,
     ) -> Result<Decimal, String> {
         total_minting_amount_tokens(proposals, now_timestamp_seconds - 7 * ONE_DAY_SECONDS)
@@ -1062,6 +1552,7 @@ async fn validate_and_render_upgrade_sns_controlled_canister(
         // The WASM-related fields are extracted separately.
         chunked_canister_wasm: _,
         new_canister_wasm: _,
+        take_snapshot_before_upgrade,
     } = upgrade;
 
     // Make sure `mode` is not None, and not an invalid/unknown value.
@@ -1141,6 +1632,12 @@ async fn validate_and_render_upgrade_sns_controlled_canister(
         })
         .unwrap_or_else(|| "No upgrade argument.".to_string());
 
+    let snapshot_info = if take_snapshot_before_upgrade.unwrap_or_default() {
+        "\n\n## A snapshot of the canister is taken before the upgrade."
+    } else {
+        ""
+    };
+
     Ok(format!(
         r"# Proposal to Upgrade an SNS Controlled Canister
 
@@ -1154,7 +1651,7 @@ async fn validate_and_render_upgrade_sns_controlled_canister(
 
 ## Argument info
 
-{args_info}",
+{args_info}{snapshot_info}",
     ))
 }
 
@@ -1766,6 +2263,97 @@ fn validate_and_render_manage_dapp_canister_settings(
     }
 }
 
+/// Validates the canister ID of a TakeDappCanisterSnapshot or LoadDappCanisterSnapshot proposal.
+/// Whether the canister is a registered dapp canister is checked by SNS Root at execution time.
+fn validate_dapp_canister_snapshot_target(
+    action_name: &str,
+    canister_id: &Option<PrincipalId>,
+    disallowed_target_canister_ids: &HashSet<CanisterId>,
+) -> Result<CanisterId, String> {
+    let canister_id = validate_required_field("canister_id", canister_id)
+        .map_err(|err| format!("{action_name} is invalid: {err}"))?;
+    let canister_id = CanisterId::try_from_principal_id(*canister_id)
+        .map_err(|err| format!("{action_name}.canister_id is invalid: {err:?}"))?;
+    if disallowed_target_canister_ids.contains(&canister_id) {
+        return Err(format!(
+            "{action_name}.canister_id must be a dapp canister, but {canister_id} is an SNS \
+             canister."
+        ));
+    }
+    Ok(canister_id)
+}
+
+/// Parses a snapshot ID of a TakeDappCanisterSnapshot or LoadDappCanisterSnapshot proposal, and
+/// makes sure that it is a snapshot of `canister_id`.
+fn validate_dapp_canister_snapshot_id(
+    action_name: &str,
+    canister_id: CanisterId,
+    snapshot_id: &[u8],
+) -> Result<SnapshotId, String> {
+    let snapshot_id = SnapshotId::try_from(snapshot_id)
+        .map_err(|err| format!("{action_name} has an invalid snapshot ID: {err}"))?;
+    if snapshot_id.get_canister_id() != canister_id {
+        return Err(format!(
+            "{action_name} refers to snapshot {snapshot_id}, which is not a snapshot of \
+             canister {canister_id}."
+        ));
+    }
+    Ok(snapshot_id)
+}
+
+fn validate_and_render_take_dapp_canister_snapshot(
+    take_dapp_canister_snapshot: &TakeDappCanisterSnapshot,
+    disallowed_target_canister_ids: &HashSet<CanisterId>,
+) -> Result<String, String> {
+    const ACTION_NAME: &str = "TakeDappCanisterSnapshot";
+
+    let canister_id = validate_dapp_canister_snapshot_target(
+        ACTION_NAME,
+        &take_dapp_canister_snapshot.canister_id,
+        disallowed_target_canister_ids,
+    )?;
+    let replace_snapshot = take_dapp_canister_snapshot
+        .replace_snapshot
+        .as_ref()
+        .map(|snapshot_id| {
+            validate_dapp_canister_snapshot_id(ACTION_NAME, canister_id, snapshot_id)
+        })
+        .transpose()?;
+
+    let replace_snapshot = replace_snapshot
+        .map(|snapshot_id| format!("{snapshot_id}"))
+        .unwrap_or_else(|| "None (a new snapshot is added)".to_string());
+    Ok(format!(
+        "# Proposal to take a snapshot of a dapp canister\n\n\
+         ## Canister id: {canister_id}\n\n\
+         ## Replaced snapshot: {replace_snapshot}"
+    ))
+}
+
+fn validate_and_render_load_dapp_canister_snapshot(
+    load_dapp_canister_snapshot: &LoadDappCanisterSnapshot,
+    disallowed_target_canister_ids: &HashSet<CanisterId>,
+) -> Result<String, String> {
+    const ACTION_NAME: &str = "LoadDappCanisterSnapshot";
+
+    let canister_id = validate_dapp_canister_snapshot_target(
+        ACTION_NAME,
+        &load_dapp_canister_snapshot.canister_id,
+        disallowed_target_canister_ids,
+    )?;
+    let snapshot_id =
+        validate_required_field("snapshot_id", &load_dapp_canister_snapshot.snapshot_id)
+            .map_err(|err| format!("{ACTION_NAME} is invalid: {err}"))?;
+    let snapshot_id = validate_dapp_canister_snapshot_id(ACTION_NAME, canister_id, snapshot_id)?;
+
+    Ok(format!(
+        "# Proposal to load a snapshot into a dapp canister\n\n\
+         ## Canister id: {canister_id}\n\n\
+         ## Snapshot id: {snapshot_id}\n\n\
+         The canister's Wasm module and state will be replaced by the ones in the snapshot."
+    ))
+}
+
 /// Attempts to validate an `AdvanceSnsTargetVersion` action and render its human-readable text.
 /// Invalidates the action in the following cases:
 /// - There are no pending upgrades.
@@ -2409,6 +2997,7 @@ impl ProposalData {
             minimum_yes_proportion_of_exercised,
             action_auxiliary,
             topic,
+            taken_snapshot_id,
         } = self;
 
         let limited_ballots: BTreeMap<_, _> = ballots
@@ -2439,6 +3028,7 @@ impl ProposalData {
             minimum_yes_proportion_of_exercised: *minimum_yes_proportion_of_exercised,
             action_auxiliary: action_auxiliary.clone(),
             topic: *topic,
+            taken_snapshot_id: taken_snapshot_id.clone(),
 
             // The following fields are truncated:
             payload_text_rendering: None,
@@ -2497,6 +3087,32 @@ pub(crate) fn transfer_sns_treasury_funds_amount_is_small_enough_at_execution_ti
     transfer: &TransferSnsTreasuryFunds,
     valuation: Valuation,
     proposals: impl Iterator<Item = &'a ProposalData>,
+    payment_streams: impl Iterator<Item = &'a TreasuryPaymentStream>,
+    now_timestamp_seconds: u64,
+) -> Result<(), GovernanceError> {
+    treasury_amount_is_small_enough_at_execution_time_or_err(
+        "this proposal",
+        transfer.from_treasury(),
+        transfer.amount_e8s,
+        valuation,
+        proposals,
+        payment_streams,
+        now_timestamp_seconds,
+    )
+}
+
+/// Returns Err if taking `amount_e8s` out of the `from_treasury` treasury would cause the 7 day
+/// upper bound to be exceeded. Both TransferSnsTreasuryFunds proposals and the payments of
+/// treasury payment streams count towards that bound.
+///
+/// `what` describes the transfer in the error message (e.g. "this proposal").
+pub(crate) fn treasury_amount_is_small_enough_at_execution_time_or_err<'a>(
+    what: &str,
+    from_treasury: TransferFrom,
+    amount_e8s: u64,
+    valuation: Valuation,
+    proposals: impl Iterator<Item = &'a ProposalData>,
+    payment_streams: impl Iterator<Item = &'a TreasuryPaymentStream>,
     now_timestamp_seconds: u64,
 ) -> Result<(), GovernanceError> {
     let allowance_tokens = transfer_sns_treasury_funds_7_day_total_upper_bound_tokens(valuation)
@@ -2515,10 +3131,11 @@ pub(crate) fn transfer_sns_treasury_funds_amount_is_small_enough_at_execution_ti
 
     // The total calculated here _could_ be different from what was calculated at proposal
     // submission/creation time. A difference would result from the execution of (another)
-    // TransferSnsTreasuryFunds proposal between now and then.
-    let spent_tokens = total_treasury_transfer_amount_tokens(
+    // TransferSnsTreasuryFunds proposal, or from a payment stream payment, between now and then.
+    let spent_tokens = total_treasury_amount_tokens(
         proposals,
-        transfer.from_treasury(),
+        payment_streams,
+        from_treasury,
         now_timestamp_seconds - 7 * ONE_DAY_SECONDS,
     )
     .map_err(|message| {
@@ -2526,29 +3143,26 @@ pub(crate) fn transfer_sns_treasury_funds_amount_is_small_enough_at_execution_ti
     })?;
 
     let remainder_tokens = allowance_tokens - spent_tokens;
-    let transfer_amount_tokens = denominations_to_tokens(transfer.amount_e8s, E8)
+    let transfer_amount_tokens = denominations_to_tokens(amount_e8s, E8)
         // This Err cannot be provoked, because we are dividing a u64 (amount_e8s) by a positive
         // integer (E8).
         .ok_or_else(|| {
             GovernanceError::new_with_message(
                 ErrorType::UnreachableCode,
-                format!(
-                    "Unable to convert proposals amount {} e8s to tokens.",
-                    transfer.amount_e8s,
-                ),
+                format!("Unable to convert amount {} e8s to tokens.", amount_e8s),
             )
         })?;
     if transfer_amount_tokens > remainder_tokens {
         return Err(GovernanceError::new_with_message(
             ErrorType::PreconditionFailed,
             format!(
-                "Executing this proposal is not allowed at this time, because doing \
+                "Executing {} is not allowed at this time, because doing \
                  so would cause the 7 day upper bound of {} tokens to be exceeded. \
                  Maybe, try again later? The total amount transferred in the past \
-                 7 days stands at {} tokens, and the amount in this proposal is {} \
+                 7 days stands at {} tokens, and the amount in {} is {} \
                  tokens. The upper bound is based on treasury valuation factors at \
                  the time of proposal submission: {:?}",
-                allowance_tokens, spent_tokens, transfer_amount_tokens, valuation,
+                what, allowance_tokens, spent_tokens, what, transfer_amount_tokens, valuation,
             ),
         ));
     }
@@ -2556,6 +3170,80 @@ pub(crate) fn transfer_sns_treasury_funds_amount_is_small_enough_at_execution_ti
     Ok(())
 }
 
+/// Returns the total amount (in tokens) taken out of the `from_treasury` treasury since
+/// `min_timestamp_seconds`, both by TransferSnsTreasuryFunds proposals and by the payments of
+/// treasury payment streams. Both count towards the same 7 day upper bound.
+fn total_treasury_amount_tokens<'a>(
+    proposals: impl Iterator<Item = &'a ProposalData>,
+    payment_streams: impl Iterator<Item = &'a TreasuryPaymentStream>,
+    from_treasury: TransferFrom,
+    min_timestamp_seconds: u64,
+) -> Result<Decimal, String> {
+    let proposals_tokens =
+        total_treasury_transfer_amount_tokens(proposals, from_treasury, min_timestamp_seconds)?;
+    let payment_streams_tokens = total_treasury_payment_stream_amount_tokens(
+        payment_streams,
+        from_treasury,
+        min_timestamp_seconds,
+    )?;
+    proposals_tokens
+        .checked_add(payment_streams_tokens)
+        .ok_or_else(|| {
+            format!(
+                "Overflow while performing {} + {}.",
+                proposals_tokens, payment_streams_tokens,
+            )
+        })
+}
+
+/// Returns the total amount (in tokens) paid from the treasury by treasury payment streams since
+/// `min_timestamp_seconds`. This is the payment stream analogue of
+/// total_treasury_transfer_amount_tokens.
+fn total_treasury_payment_stream_amount_tokens<'a>(
+    payment_streams: impl Iterator<Item = &'a TreasuryPaymentStream>,
+    filter_from_treasury: TransferFrom,
+    min_timestamp_seconds: u64,
+) -> Result<Decimal, String> {
+    let mut total_tokens = Decimal::from(0);
+
+    for payment_stream in payment_streams {
+        let Some(schedule) = &payment_stream.schedule else {
+            return Err(format!(
+                "TreasuryPaymentStream {:?} is invalid, because its `schedule` field is empty!",
+                payment_stream.id,
+            ));
+        };
+        if schedule.from_treasury() != filter_from_treasury {
+            continue;
+        }
+
+        for payment in &payment_stream.payments {
+            if payment.timestamp_seconds.unwrap_or_default() < min_timestamp_seconds {
+                continue;
+            }
+
+            let amount_e8s = payment.amount_e8s.unwrap_or_default();
+            // This Err is impossible, because we are dividing a u64 by a positive number.
+            let amount_tokens = denominations_to_tokens(amount_e8s, E8).ok_or_else(|| {
+                format!(
+                    "Unable to convert amount {} e8s to whole tokens in payment stream {:?}.",
+                    amount_e8s, payment_stream.id,
+                )
+            })?;
+
+            total_tokens = total_tokens.checked_add(amount_tokens).ok_or_else(|| {
+                format!(
+                    "Failed to total recent treasury payment stream payments: \
+                     overflow while performing {} + {}.",
+                    total_tokens, amount_tokens,
+                )
+            })?;
+        }
+    }
+
+    Ok(total_tokens)
+}
+
 /// Returns the total amount (in e8s) that was transfered from the treasury via
 /// TransferSnsTreasuryFunds proposals, or None if there was an overflow.
 ///
@@ -2693,6 +3381,9 @@ mod treasury_tests;
 #[cfg(test)]
 mod minting_tests;
 
+#[cfg(test)]
+mod follow_on_swap_tests;
+
 #[cfg(test)]
 mod advance_sns_target_version;
 
@@ -2771,6 +3462,7 @@ mod tests {
             target_version: None,
             timers: None,
             upgrade_journal: None,
+            treasury_payment_streams: Default::default(),
         }
     }
 
@@ -2935,6 +3627,7 @@ mod tests {
             canister_upgrade_arg: None,
             mode: Some(CanisterInstallModeProto::Upgrade.into()),
             chunked_canister_wasm: None,
+            take_snapshot_before_upgrade: None,
         };
         let env = setup_for_upgrade_sns_controlled_canister_tests(&upgrade);
         let text = validate_and_render_upgrade_sns_controlled_canister(
@@ -2976,6 +3669,7 @@ No upgrade argument."#
                 store_canister_id: Some(canister_test_id(111).get()),
                 chunk_hashes_list: vec![vec![1, 1, 1], vec![2, 2, 2], vec![3, 3, 3]],
             }),
+            take_snapshot_before_upgrade: None,
         };
         let env = setup_for_upgrade_sns_controlled_canister_tests(&upgrade);
         let text = validate_and_render_upgrade_sns_controlled_canister(
@@ -3023,6 +3717,7 @@ No upgrade argument."#
             canister_upgrade_arg: None,
             mode: Some(CanisterInstallModeProto::Upgrade.into()),
             chunked_canister_wasm: Some(chunked_canister_wasm.clone()),
+            take_snapshot_before_upgrade: None,
         };
 
         let env = setup_for_upgrade_sns_controlled_canister_tests(&upgrade);
@@ -3059,6 +3754,7 @@ No upgrade argument."#
             canister_upgrade_arg: None,
             mode: Some(CanisterInstallModeProto::Upgrade.into()),
             chunked_canister_wasm: Some(chunked_canister_wasm.clone()),
+            take_snapshot_before_upgrade: None,
         };
 
         let env = setup_for_upgrade_sns_controlled_canister_tests(&upgrade);
@@ -3097,6 +3793,7 @@ No upgrade argument."#
             canister_upgrade_arg: None,
             mode: Some(CanisterInstallModeProto::Upgrade.into()),
             chunked_canister_wasm: Some(chunked_canister_wasm.clone()),
+            take_snapshot_before_upgrade: None,
         };
 
         let env = setup_for_upgrade_sns_controlled_canister_tests(&upgrade);
@@ -3131,6 +3828,7 @@ No upgrade argument."#
             canister_upgrade_arg: None,
             mode: Some(CanisterInstallModeProto::Upgrade.into()),
             chunked_canister_wasm: Some(chunked_canister_wasm.clone()),
+            take_snapshot_before_upgrade: None,
         };
 
         let env = setup_for_upgrade_sns_controlled_canister_tests(&upgrade);
@@ -3160,6 +3858,7 @@ No upgrade argument."#
             canister_upgrade_arg: Some(vec![10, 20, 30, 40, 50, 60, 70, 80]),
             mode: Some(CanisterInstallModeProto::Upgrade.into()),
             chunked_canister_wasm: None,
+            take_snapshot_before_upgrade: None,
         };
         let env = setup_for_upgrade_sns_controlled_canister_tests(&upgrade);
         let text = validate_and_render_upgrade_sns_controlled_canister(
@@ -3197,6 +3896,7 @@ Upgrade argument with 8 bytes and SHA256 `0a141e28323c4650`."#
             canister_upgrade_arg: None,
             mode: Some(100), // 100 is not a valid mode
             chunked_canister_wasm: None,
+            take_snapshot_before_upgrade: None,
         };
         let env = setup_for_upgrade_sns_controlled_canister_tests(&upgrade);
         let text = validate_and_render_upgrade_sns_controlled_canister(
@@ -3216,6 +3916,7 @@ Upgrade argument with 8 bytes and SHA256 `0a141e28323c4650`."#
             canister_upgrade_arg: None,
             mode: Some(CanisterInstallModeProto::Upgrade.into()),
             chunked_canister_wasm: None,
+            take_snapshot_before_upgrade: None,
         };
         let result = validate_and_render_upgrade_sns_controlled_canister(
             &upgrade,
@@ -4753,6 +5454,7 @@ Version {
             executed_timestamp_seconds: 0,
             action_auxiliary: None,
             topic: Some(Topic::Governance as i32),
+            taken_snapshot_id: None,
         };
     }
 
@@ -5169,6 +5871,74 @@ Payload rendering here"#
         );
     }
 
+    #[test]
+    fn validate_and_render_take_dapp_canister_snapshot_succeeds() {
+        let canister_id = canister_test_id(1);
+        let replace_snapshot = SnapshotId::from((canister_id, 3));
+
+        let render = validate_and_render_take_dapp_canister_snapshot(
+            &TakeDappCanisterSnapshot {
+                canister_id: Some(canister_id.get()),
+                replace_snapshot: Some(replace_snapshot.to_vec()),
+            },
+            &HashSet::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            render,
+            format!(
+                "# Proposal to take a snapshot of a dapp canister\n\n\
+                 ## Canister id: {canister_id}\n\n\
+                 ## Replaced snapshot: {replace_snapshot}"
+            )
+        );
+    }
+
+    #[test]
+    fn validate_and_render_take_dapp_canister_snapshot_rejects_sns_canisters() {
+        let canister_id = canister_test_id(1);
+
+        let err = validate_and_render_take_dapp_canister_snapshot(
+            &TakeDappCanisterSnapshot {
+                canister_id: Some(canister_id.get()),
+                replace_snapshot: None,
+            },
+            &hashset! { canister_id },
+        )
+        .unwrap_err();
+
+        assert!(err.contains("is an SNS canister"), "{err}");
+    }
+
+    #[test]
+    fn validate_and_render_load_dapp_canister_snapshot_rejects_snapshot_of_other_canister() {
+        let canister_id = canister_test_id(1);
+        let other_canister_id = canister_test_id(2);
+
+        let err = validate_and_render_load_dapp_canister_snapshot(
+            &LoadDappCanisterSnapshot {
+                canister_id: Some(canister_id.get()),
+                snapshot_id: Some(SnapshotId::from((other_canister_id, 0)).to_vec()),
+            },
+            &HashSet::new(),
+        )
+        .unwrap_err();
+
+        assert!(err.contains("which is not a snapshot of canister"), "{err}");
+
+        let err = validate_and_render_load_dapp_canister_snapshot(
+            &LoadDappCanisterSnapshot {
+                canister_id: Some(canister_id.get()),
+                snapshot_id: None,
+            },
+            &HashSet::new(),
+        )
+        .unwrap_err();
+
+        assert!(err.contains("snapshot_id"), "{err}");
+    }
+
     #[test]
     fn limited_proposal_data_for_list_proposals_retain_ballots_by_caller() {
         let original_proposal_data = ProposalData {
@@ -5315,6 +6085,7 @@ Payload rendering here"#
                         canister_upgrade_arg: Some(vec![4, 5, 6, 7]),
                         mode: Some(1),
                         chunked_canister_wasm: None,
+                        take_snapshot_before_upgrade: None,
                     },
                 )),
                 ..Default::default()
@@ -5336,6 +6107,7 @@ Payload rendering here"#
                             canister_upgrade_arg: Some(vec![4, 5, 6, 7]),
                             mode: Some(1),
                             chunked_canister_wasm: None,
+                            take_snapshot_before_upgrade: None,
                         },
                     )),
                     ..Default::default()
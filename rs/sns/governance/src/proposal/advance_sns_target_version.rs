@@ -62,6 +62,7 @@ fn standard_governance_proto_for_tests(deployed_version: Option<Version>) -> Gov
         target_version: None,
         timers: None,
         upgrade_journal: None,
+        treasury_payment_streams: Default::default(),
         cached_upgrade_steps: None,
     }
 }
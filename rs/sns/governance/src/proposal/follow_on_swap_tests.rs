@@ -0,0 +1,85 @@
+use super::*;
+
+#[test]
+fn test_validate_and_render_open_follow_on_swap_round() {
+    let open = OpenFollowOnSwapRound {
+        sns_token_e8s: Some(1_000_000 * E8),
+        min_direct_participation_icp_e8s: Some(100 * E8),
+        max_direct_participation_icp_e8s: Some(10_000 * E8),
+        min_participant_icp_e8s: Some(E8),
+        max_participant_icp_e8s: Some(1_000 * E8),
+        min_participants: Some(10),
+        duration_seconds: Some(7 * ONE_DAY_SECONDS),
+        neuron_basket_count: Some(3),
+        neuron_basket_dissolve_delay_interval_seconds: Some(30 * ONE_DAY_SECONDS),
+    };
+
+    let rendering = validate_and_render_open_follow_on_swap_round(&open).unwrap();
+    assert!(
+        rendering.contains("## SNS tokens offered (e8s): 100000000000000"),
+        "{}",
+        rendering
+    );
+
+    for (open, expected_defect) in [
+        (
+            OpenFollowOnSwapRound {
+                sns_token_e8s: None,
+                ..open
+            },
+            "sns_token_e8s must be set",
+        ),
+        (
+            OpenFollowOnSwapRound {
+                min_participants: Some(0),
+                ..open
+            },
+            "min_participants must be set to a positive value",
+        ),
+        (
+            OpenFollowOnSwapRound {
+                neuron_basket_dissolve_delay_interval_seconds: None,
+                ..open
+            },
+            "neuron_basket_dissolve_delay_interval_seconds must be set",
+        ),
+        (
+            OpenFollowOnSwapRound {
+                min_direct_participation_icp_e8s: Some(20_000 * E8),
+                ..open
+            },
+            "must be at most max_direct_participation_icp_e8s",
+        ),
+        (
+            OpenFollowOnSwapRound {
+                min_participant_icp_e8s: Some(2_000 * E8),
+                ..open
+            },
+            "must be at most max_participant_icp_e8s",
+        ),
+        (
+            OpenFollowOnSwapRound {
+                max_participant_icp_e8s: Some(20_000 * E8),
+                ..open
+            },
+            "must be at most max_direct_participation_icp_e8s",
+        ),
+        (
+            OpenFollowOnSwapRound {
+                duration_seconds: Some(ONE_DAY_SECONDS - 1),
+                ..open
+            },
+            "duration_seconds",
+        ),
+        (
+            OpenFollowOnSwapRound {
+                duration_seconds: Some(15 * ONE_DAY_SECONDS),
+                ..open
+            },
+            "duration_seconds",
+        ),
+    ] {
+        let err = validate_and_render_open_follow_on_swap_round(&open).unwrap_err();
+        assert!(err.contains(expected_defect), "{:?}: {}", open, err);
+    }
+}
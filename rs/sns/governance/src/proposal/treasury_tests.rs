@@ -1,4 +1,9 @@
 use super::*;
+use crate::pb::v1::treasury_payment_stream;
+use crate::treasury::{
+    TREASURY_PAYMENT_STREAM_MAX_RETRY_DELAY_SECONDS,
+    TREASURY_PAYMENT_STREAM_MIN_RETRY_DELAY_SECONDS,
+};
 
 #[test]
 fn test_can_be_purged_retain_recent_transfer_sns_treasury_funds() {
@@ -286,3 +291,384 @@ fn test_total_treasury_transfer_amount_tokens() {
         Ok((Decimal::from(u64::MAX) + Decimal::from(1)) / Decimal::from(E8)),
     );
 }
+
+fn new_treasury_payment_stream(cliff_seconds: u64) -> TreasuryPaymentStream {
+    TreasuryPaymentStream {
+        id: Some(42),
+        schedule: Some(CreateTreasuryPaymentStream {
+            from_treasury: TransferFrom::IcpTreasury as i32,
+            amount_per_period_e8s: Some(100 * E8),
+            period_seconds: Some(30 * ONE_DAY_SECONDS),
+            number_of_periods: Some(3),
+            cliff_seconds: Some(cliff_seconds),
+            memo: None,
+            to_principal: Some(PrincipalId::new_user_test_id(42)),
+            to_subaccount: None,
+        }),
+        start_timestamp_seconds: Some(1_000_000),
+        valuation: None,
+        payments: vec![],
+        cancelled_timestamp_seconds: None,
+        last_failure_reason: None,
+        last_failure_timestamp_seconds: None,
+        consecutive_failure_count: None,
+    }
+}
+
+#[test]
+fn test_treasury_payment_stream_next_payment() {
+    let start = 1_000_000;
+    let period = 30 * ONE_DAY_SECONDS;
+    let mut payment_stream = new_treasury_payment_stream(0);
+
+    // Nothing is due before the end of the first period.
+    assert_eq!(payment_stream.due_payment_count(start), 0);
+    assert_eq!(payment_stream.due_payment_count(start + period - 1), 0);
+    assert_eq!(
+        payment_stream.next_payment_amount_e8s(start + period - 1),
+        None
+    );
+
+    // One payment per period, up to number_of_periods.
+    assert_eq!(payment_stream.due_payment_count(start + period), 1);
+    assert_eq!(payment_stream.due_payment_count(start + 2 * period + 1), 2);
+    assert_eq!(payment_stream.due_payment_count(start + 100 * period), 3);
+    assert_eq!(
+        payment_stream.next_payment_amount_e8s(start + period),
+        Some(100 * E8)
+    );
+
+    // Once the payment is made, the next one is not due until the end of the next period.
+    payment_stream
+        .payments
+        .push(treasury_payment_stream::Payment {
+            timestamp_seconds: Some(start + period),
+            amount_e8s: Some(100 * E8),
+        });
+    assert_eq!(payment_stream.next_payment_amount_e8s(start + period), None);
+    assert_eq!(
+        payment_stream.next_payment_amount_e8s(start + 2 * period),
+        Some(100 * E8)
+    );
+    assert!(!payment_stream.is_finished());
+
+    // Cancelled streams make no further payments, not even ones that are already due.
+    let cancelled_payment_stream = TreasuryPaymentStream {
+        cancelled_timestamp_seconds: Some(start + period + 1),
+        ..payment_stream.clone()
+    };
+    assert!(cancelled_payment_stream.is_finished());
+    assert_eq!(
+        cancelled_payment_stream.next_payment_amount_e8s(start + 2 * period),
+        None
+    );
+
+    // Streams are finished once all payments are made.
+    for i in 2..=3 {
+        payment_stream
+            .payments
+            .push(treasury_payment_stream::Payment {
+                timestamp_seconds: Some(start + i * period),
+                amount_e8s: Some(100 * E8),
+            });
+    }
+    assert!(payment_stream.is_finished());
+    assert_eq!(
+        payment_stream.next_payment_amount_e8s(start + 100 * period),
+        None
+    );
+}
+
+#[test]
+fn test_treasury_payment_stream_cliff() {
+    let start = 1_000_000;
+    let period = 30 * ONE_DAY_SECONDS;
+    let payment_stream = new_treasury_payment_stream(2 * period + 1);
+
+    // Nothing is due during the cliff.
+    assert_eq!(payment_stream.due_payment_count(start + 2 * period), 0);
+
+    // At the end of the cliff, the payments of all periods that have ended so far are due.
+    assert_eq!(payment_stream.due_payment_count(start + 2 * period + 1), 2);
+    assert_eq!(payment_stream.due_payment_count(start + 3 * period), 3);
+}
+
+#[test]
+fn test_total_treasury_payment_stream_amount_tokens() {
+    let min_timestamp_seconds = 123_456_789;
+
+    let payment = |timestamp_seconds: u64, amount_e8s: u64| treasury_payment_stream::Payment {
+        timestamp_seconds: Some(timestamp_seconds),
+        amount_e8s: Some(amount_e8s),
+    };
+
+    let icp_payment_stream = TreasuryPaymentStream {
+        payments: vec![
+            // Skip because too old.
+            payment(min_timestamp_seconds - 1, 999_000_000),
+            // Ok.
+            payment(min_timestamp_seconds, 1),
+            payment(min_timestamp_seconds + 1, 20),
+        ],
+        ..new_treasury_payment_stream(0)
+    };
+
+    // Payments of cancelled streams still count.
+    let cancelled_icp_payment_stream = TreasuryPaymentStream {
+        payments: vec![payment(min_timestamp_seconds + 123_456, 300)],
+        cancelled_timestamp_seconds: Some(min_timestamp_seconds + 123_457),
+        ..new_treasury_payment_stream(0)
+    };
+
+    // Skip because wrong type of token (SNS instead of ICP).
+    let mut sns_payment_stream = TreasuryPaymentStream {
+        payments: vec![payment(min_timestamp_seconds, 4000)],
+        ..new_treasury_payment_stream(0)
+    };
+    sns_payment_stream.schedule.as_mut().unwrap().from_treasury =
+        TransferFrom::SnsTokenTreasury as i32;
+
+    let payment_streams = [
+        icp_payment_stream,
+        cancelled_icp_payment_stream,
+        sns_payment_stream,
+    ];
+
+    assert_eq!(
+        total_treasury_payment_stream_amount_tokens(
+            payment_streams.iter(),
+            TransferFrom::IcpTreasury,
+            min_timestamp_seconds,
+        ),
+        Ok(Decimal::from(321) / Decimal::from(E8)),
+    );
+    assert_eq!(
+        total_treasury_payment_stream_amount_tokens(
+            payment_streams.iter(),
+            TransferFrom::IcpTreasury,
+            0,
+        ),
+        Ok(Decimal::from(999_000_321) / Decimal::from(E8)),
+    );
+    assert_eq!(
+        total_treasury_payment_stream_amount_tokens(
+            payment_streams.iter(),
+            TransferFrom::SnsTokenTreasury,
+            min_timestamp_seconds,
+        ),
+        Ok(Decimal::from(4000) / Decimal::from(E8)),
+    );
+}
+
+#[test]
+fn test_recent_amount_total_tokens_includes_payment_streams() {
+    let now = 1_000 * ONE_DAY_SECONDS;
+    let payment_stream = TreasuryPaymentStream {
+        payments: vec![treasury_payment_stream::Payment {
+            timestamp_seconds: Some(now - ONE_DAY_SECONDS),
+            amount_e8s: Some(5 * E8),
+        }],
+        ..new_treasury_payment_stream(0)
+    };
+    let payment_streams = [payment_stream];
+
+    let transfer = TransferSnsTreasuryFunds {
+        from_treasury: TransferFrom::IcpTreasury as i32,
+        amount_e8s: E8,
+        ..Default::default()
+    };
+    assert_eq!(
+        transfer.recent_amount_total_tokens(std::iter::empty(), payment_streams.iter(), now),
+        Ok(Decimal::from(5)),
+    );
+
+    let create = new_treasury_payment_stream(0).schedule.unwrap();
+    assert_eq!(
+        create.recent_amount_total_tokens(std::iter::empty(), payment_streams.iter(), now),
+        Ok(Decimal::from(5)),
+    );
+
+    // Payments older than 7 days do not count.
+    assert_eq!(
+        transfer.recent_amount_total_tokens(
+            std::iter::empty(),
+            payment_streams.iter(),
+            now + 7 * ONE_DAY_SECONDS,
+        ),
+        Ok(Decimal::from(0)),
+    );
+}
+
+#[test]
+fn test_treasury_payment_stream_retry_backoff() {
+    let now = 1_000_000;
+    let mut payment_stream = new_treasury_payment_stream(0);
+    assert!(payment_stream.is_retry_due(now));
+
+    // The delay doubles with each consecutive failure.
+    assert_eq!(
+        payment_stream.record_payment_failure(now, "boom".to_string()),
+        1
+    );
+    assert!(!payment_stream.is_retry_due(now + TREASURY_PAYMENT_STREAM_MIN_RETRY_DELAY_SECONDS - 1));
+    assert!(payment_stream.is_retry_due(now + TREASURY_PAYMENT_STREAM_MIN_RETRY_DELAY_SECONDS));
+
+    assert_eq!(
+        payment_stream.record_payment_failure(now, "boom".to_string()),
+        2
+    );
+    assert_eq!(
+        payment_stream.retry_delay_seconds(),
+        2 * TREASURY_PAYMENT_STREAM_MIN_RETRY_DELAY_SECONDS
+    );
+
+    // The delay is capped.
+    for _ in 0..100 {
+        payment_stream.record_payment_failure(now, "boom".to_string());
+    }
+    assert_eq!(
+        payment_stream.retry_delay_seconds(),
+        TREASURY_PAYMENT_STREAM_MAX_RETRY_DELAY_SECONDS
+    );
+    assert_eq!(payment_stream.last_failure_reason, Some("boom".to_string()));
+
+    // A successful payment resets the backoff.
+    payment_stream.record_payment(now, 100 * E8);
+    assert!(payment_stream.is_retry_due(now));
+    assert_eq!(payment_stream.consecutive_failure_count, None);
+    assert_eq!(payment_stream.last_failure_reason, None);
+    assert_eq!(payment_stream.payments.len(), 1);
+}
+
+#[test]
+fn test_locally_validate_and_render_create_treasury_payment_stream() {
+    let sns_transfer_fee_e8s = 10_000;
+    let valid = new_treasury_payment_stream(0).schedule.unwrap();
+
+    let result = locally_validate_and_render_create_treasury_payment_stream(
+        &valid,
+        sns_transfer_fee_e8s,
+        vec![],
+    );
+    let rendering = result.unwrap();
+    assert!(
+        rendering.contains("## Number of periods: 3"),
+        "{}",
+        rendering
+    );
+    assert!(
+        rendering.contains("## Total amount: 300.00000000 ICP"),
+        "{}",
+        rendering
+    );
+
+    let invalid_cases = [
+        (
+            CreateTreasuryPaymentStream {
+                from_treasury: TransferFrom::Unspecified as i32,
+                ..valid.clone()
+            },
+            "Must specify a treasury",
+        ),
+        (
+            CreateTreasuryPaymentStream {
+                amount_per_period_e8s: Some(1),
+                ..valid.clone()
+            },
+            "minimum transaction",
+        ),
+        (
+            CreateTreasuryPaymentStream {
+                period_seconds: Some(ONE_DAY_SECONDS - 1),
+                ..valid.clone()
+            },
+            "period_seconds",
+        ),
+        (
+            CreateTreasuryPaymentStream {
+                number_of_periods: Some(0),
+                ..valid.clone()
+            },
+            "number_of_periods",
+        ),
+        (
+            CreateTreasuryPaymentStream {
+                number_of_periods: Some(MAX_TREASURY_PAYMENT_STREAM_NUMBER_OF_PERIODS + 1),
+                ..valid.clone()
+            },
+            "number_of_periods",
+        ),
+        (
+            CreateTreasuryPaymentStream {
+                cliff_seconds: Some(90 * ONE_DAY_SECONDS + 1),
+                ..valid.clone()
+            },
+            "cliff_seconds",
+        ),
+        (
+            CreateTreasuryPaymentStream {
+                to_principal: None,
+                ..valid.clone()
+            },
+            "Must specify a principal",
+        ),
+        (
+            CreateTreasuryPaymentStream {
+                to_principal: Some(PrincipalId::new_anonymous()),
+                ..valid.clone()
+            },
+            "anonymous",
+        ),
+    ];
+
+    for (create, expected_defect) in invalid_cases {
+        let result = locally_validate_and_render_create_treasury_payment_stream(
+            &create,
+            sns_transfer_fee_e8s,
+            vec![],
+        );
+        let err = result.unwrap_err();
+        assert!(err.contains(expected_defect), "{:?}: {}", create, err);
+    }
+}
+
+#[test]
+fn test_validate_and_render_cancel_treasury_payment_stream() {
+    let payment_streams = BTreeMap::from([
+        (42, new_treasury_payment_stream(0)),
+        (
+            43,
+            TreasuryPaymentStream {
+                id: Some(43),
+                cancelled_timestamp_seconds: Some(1_000_001),
+                ..new_treasury_payment_stream(0)
+            },
+        ),
+    ]);
+
+    let cancel = |payment_stream_id| CancelTreasuryPaymentStream { payment_stream_id };
+
+    assert!(validate_and_render_cancel_treasury_payment_stream(
+        &cancel(Some(42)),
+        &payment_streams
+    )
+    .is_ok());
+
+    let err = validate_and_render_cancel_treasury_payment_stream(&cancel(None), &payment_streams)
+        .unwrap_err();
+    assert!(err.contains("must be set"), "{}", err);
+
+    let err =
+        validate_and_render_cancel_treasury_payment_stream(&cancel(Some(1)), &payment_streams)
+            .unwrap_err();
+    assert!(
+        err.contains("There is no treasury payment stream"),
+        "{}",
+        err
+    );
+
+    let err =
+        validate_and_render_cancel_treasury_payment_stream(&cancel(Some(43)), &payment_streams)
+            .unwrap_err();
+    assert!(err.contains("already cancelled or complete"), "{}", err);
+}
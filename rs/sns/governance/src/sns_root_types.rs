@@ -196,3 +196,45 @@ pub struct ManageDappCanisterSettingsResponse {
     #[prost(string, optional, tag = "1")]
     pub failure_reason: ::core::option::Option<::prost::alloc::string::String>,
 }
+
+#[derive(candid::CandidType, candid::Deserialize, comparable::Comparable)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TakeDappCanisterSnapshotRequest {
+    #[prost(message, optional, tag = "1")]
+    pub canister_id: ::core::option::Option<::ic_base_types::PrincipalId>,
+    #[prost(bytes = "vec", optional, tag = "2")]
+    pub replace_snapshot: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+}
+
+#[derive(candid::CandidType, candid::Deserialize, comparable::Comparable)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TakeDappCanisterSnapshotResponse {
+    /// Absence of failure_reason indicates success.
+    #[prost(string, optional, tag = "1")]
+    pub failure_reason: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bytes = "vec", optional, tag = "2")]
+    pub snapshot_id: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    #[prost(uint64, optional, tag = "3")]
+    pub taken_at_timestamp: ::core::option::Option<u64>,
+}
+
+#[derive(candid::CandidType, candid::Deserialize, comparable::Comparable)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LoadDappCanisterSnapshotRequest {
+    #[prost(message, optional, tag = "1")]
+    pub canister_id: ::core::option::Option<::ic_base_types::PrincipalId>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub snapshot_id: ::prost::alloc::vec::Vec<u8>,
+}
+
+#[derive(candid::CandidType, candid::Deserialize, comparable::Comparable)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LoadDappCanisterSnapshotResponse {
+    /// Absence of failure_reason indicates success.
+    #[prost(string, optional, tag = "1")]
+    pub failure_reason: ::core::option::Option<::prost::alloc::string::String>,
+}
@@ -0,0 +1,81 @@
+// NOTE: This file's types are all from the swap canister, where a current dependency cycle
+// prevents including them directly.
+// TODO(NNS1-1589): Remove all these types after dependency cycle is fixed.
+
+#[derive(candid::CandidType, candid::Deserialize, comparable::Comparable)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct NeuronBasketConstructionParameters {
+    #[prost(uint64, tag = "1")]
+    pub count: u64,
+    #[prost(uint64, tag = "2")]
+    pub dissolve_delay_interval_seconds: u64,
+}
+
+#[derive(candid::CandidType, candid::Deserialize, comparable::Comparable)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct OpenFollowOnRoundRequest {
+    #[prost(uint64, optional, tag = "1")]
+    pub sns_proposal_id: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "2")]
+    pub sns_token_e8s: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "3")]
+    pub min_direct_participation_icp_e8s: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "4")]
+    pub max_direct_participation_icp_e8s: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "5")]
+    pub min_participant_icp_e8s: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "6")]
+    pub max_participant_icp_e8s: ::core::option::Option<u64>,
+    #[prost(uint32, optional, tag = "7")]
+    pub min_participants: ::core::option::Option<u32>,
+    #[prost(uint64, optional, tag = "8")]
+    pub swap_start_timestamp_seconds: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "9")]
+    pub swap_due_timestamp_seconds: ::core::option::Option<u64>,
+    #[prost(message, optional, tag = "10")]
+    pub neuron_basket_construction_parameters:
+        ::core::option::Option<NeuronBasketConstructionParameters>,
+    #[prost(uint64, optional, tag = "11")]
+    pub transaction_fee_e8s: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "12")]
+    pub neuron_minimum_stake_e8s: ::core::option::Option<u64>,
+}
+
+#[derive(candid::CandidType, candid::Deserialize, comparable::Comparable)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OpenFollowOnRoundResponse {
+    #[prost(oneof = "open_follow_on_round_response::Result", tags = "1, 2")]
+    pub result: ::core::option::Option<open_follow_on_round_response::Result>,
+}
+
+/// Nested message and enum types in `OpenFollowOnRoundResponse`.
+pub mod open_follow_on_round_response {
+    #[derive(candid::CandidType, candid::Deserialize, comparable::Comparable)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, Copy, PartialEq, ::prost::Message)]
+    pub struct Ok {
+        #[prost(uint64, optional, tag = "1")]
+        pub round_number: ::core::option::Option<u64>,
+    }
+
+    #[derive(candid::CandidType, candid::Deserialize, comparable::Comparable)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Err {
+        #[prost(string, optional, tag = "1")]
+        pub description: ::core::option::Option<::prost::alloc::string::String>,
+    }
+
+    #[derive(candid::CandidType, candid::Deserialize, comparable::Comparable)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag = "1")]
+        Ok(Ok),
+        #[prost(message, tag = "2")]
+        Err(Err),
+    }
+}
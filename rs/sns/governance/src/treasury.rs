@@ -1,10 +1,11 @@
 use crate::pb::v1::{
+    treasury_payment_stream,
     valuation::{Token as TokenPb, ValuationFactors as ValuationFactorsPb},
-    Account as AccountPb, Valuation as ValuationPb,
+    Account as AccountPb, TreasuryPaymentStream, Valuation as ValuationPb,
 };
 use candid::Principal;
 use ic_base_types::PrincipalId;
-use ic_nervous_system_common::E8;
+use ic_nervous_system_common::{E8, ONE_DAY_SECONDS};
 use ic_nervous_system_proto::pb::v1::{Decimal as DecimalPb, Tokens};
 use ic_sns_governance_token_valuation::{Token, Valuation, ValuationFactors};
 use icrc_ledger_types::icrc1::account::Account;
@@ -277,3 +278,108 @@ impl<'a> Default for &'a ValuationFactorsPb {
         &DEFAULT
     }
 }
+
+/// How long to wait before retrying a treasury payment stream payment that failed. Each further
+/// consecutive failure doubles the wait, up to TREASURY_PAYMENT_STREAM_MAX_RETRY_DELAY_SECONDS.
+pub(crate) const TREASURY_PAYMENT_STREAM_MIN_RETRY_DELAY_SECONDS: u64 = 10 * 60;
+
+/// The longest wait between two attempts to make a treasury payment stream payment.
+pub(crate) const TREASURY_PAYMENT_STREAM_MAX_RETRY_DELAY_SECONDS: u64 = ONE_DAY_SECONDS;
+
+impl TreasuryPaymentStream {
+    /// Whether the stream will not make any more payments, because it was cancelled, or because
+    /// all of its payments have been made.
+    pub(crate) fn is_finished(&self) -> bool {
+        let number_of_periods = self
+            .schedule
+            .as_ref()
+            .and_then(|schedule| schedule.number_of_periods)
+            .unwrap_or_default();
+
+        self.cancelled_timestamp_seconds.is_some()
+            || self.payments.len() as u64 >= number_of_periods
+    }
+
+    /// The number of payments that should have been made by `now_timestamp_seconds`: one for each
+    /// period that has ended, except that nothing is due before the end of the cliff.
+    pub(crate) fn due_payment_count(&self, now_timestamp_seconds: u64) -> u64 {
+        let Some(schedule) = &self.schedule else {
+            return 0;
+        };
+        let period_seconds = schedule.period_seconds.unwrap_or_default();
+        if period_seconds == 0 {
+            return 0;
+        }
+
+        let elapsed_seconds =
+            now_timestamp_seconds.saturating_sub(self.start_timestamp_seconds.unwrap_or_default());
+        if elapsed_seconds < schedule.cliff_seconds.unwrap_or_default() {
+            return 0;
+        }
+
+        (elapsed_seconds / period_seconds).min(schedule.number_of_periods.unwrap_or_default())
+    }
+
+    /// Returns the amount of the next payment, if it is due. Cancelled streams make no payments.
+    pub(crate) fn next_payment_amount_e8s(&self, now_timestamp_seconds: u64) -> Option<u64> {
+        if self.is_finished() {
+            return None;
+        }
+        if (self.payments.len() as u64) >= self.due_payment_count(now_timestamp_seconds) {
+            return None;
+        }
+
+        self.schedule
+            .as_ref()
+            .and_then(|schedule| schedule.amount_per_period_e8s)
+    }
+
+    /// How long to wait after the last failed payment attempt before trying again.
+    pub(crate) fn retry_delay_seconds(&self) -> u64 {
+        let failures = self.consecutive_failure_count.unwrap_or_default();
+        if failures == 0 {
+            return 0;
+        }
+
+        let doublings = (failures - 1).min(u32::BITS as u64) as u32;
+        TREASURY_PAYMENT_STREAM_MIN_RETRY_DELAY_SECONDS
+            .saturating_mul(2_u64.saturating_pow(doublings))
+            .min(TREASURY_PAYMENT_STREAM_MAX_RETRY_DELAY_SECONDS)
+    }
+
+    /// Whether a payment may be attempted at `now_timestamp_seconds`, i.e. the retry delay after
+    /// the last failed attempt (if any) has passed.
+    pub(crate) fn is_retry_due(&self, now_timestamp_seconds: u64) -> bool {
+        match self.last_failure_timestamp_seconds {
+            None => true,
+            Some(last_failure_timestamp_seconds) => {
+                now_timestamp_seconds
+                    >= last_failure_timestamp_seconds.saturating_add(self.retry_delay_seconds())
+            }
+        }
+    }
+
+    /// Records a successful payment, and forgets about previous failures.
+    pub(crate) fn record_payment(&mut self, now_timestamp_seconds: u64, amount_e8s: u64) {
+        self.payments.push(treasury_payment_stream::Payment {
+            timestamp_seconds: Some(now_timestamp_seconds),
+            amount_e8s: Some(amount_e8s),
+        });
+        self.last_failure_reason = None;
+        self.last_failure_timestamp_seconds = None;
+        self.consecutive_failure_count = None;
+    }
+
+    /// Records a failed payment attempt. Returns the number of attempts that failed in a row.
+    pub(crate) fn record_payment_failure(
+        &mut self,
+        now_timestamp_seconds: u64,
+        reason: String,
+    ) -> u64 {
+        let failures = self.consecutive_failure_count.unwrap_or_default() + 1;
+        self.last_failure_reason = Some(reason);
+        self.last_failure_timestamp_seconds = Some(now_timestamp_seconds);
+        self.consecutive_failure_count = Some(failures);
+        failures
+    }
+}
@@ -140,6 +140,21 @@ pub mod native_action_ids {
     /// RegisterExtension Action.
     pub const REGISTER_EXTENSION: u64 = 17;
 
+    /// CreateTreasuryPaymentStream Action.
+    pub const CREATE_TREASURY_PAYMENT_STREAM: u64 = 18;
+
+    /// CancelTreasuryPaymentStream Action.
+    pub const CANCEL_TREASURY_PAYMENT_STREAM: u64 = 19;
+
+    /// TakeDappCanisterSnapshot Action.
+    pub const TAKE_DAPP_CANISTER_SNAPSHOT: u64 = 20;
+
+    /// LoadDappCanisterSnapshot Action.
+    pub const LOAD_DAPP_CANISTER_SNAPSHOT: u64 = 21;
+
+    /// OpenFollowOnSwapRound Action.
+    pub const OPEN_FOLLOW_ON_SWAP_ROUND: u64 = 22;
+
     // When adding something to this list, make sure to update the below function.
     pub fn nervous_system_functions() -> Vec<NervousSystemFunction> {
         vec![
@@ -160,6 +175,11 @@ pub mod native_action_ids {
             NervousSystemFunction::advance_sns_target_version(),
             NervousSystemFunction::set_topics_for_custom_proposals(),
             NervousSystemFunction::register_extension(),
+            NervousSystemFunction::create_treasury_payment_stream(),
+            NervousSystemFunction::cancel_treasury_payment_stream(),
+            NervousSystemFunction::take_dapp_canister_snapshot(),
+            NervousSystemFunction::load_dapp_canister_snapshot(),
+            NervousSystemFunction::open_follow_on_swap_round(),
         ]
     }
 }
@@ -1265,6 +1285,67 @@ impl NervousSystemFunction {
             function_type: Some(FunctionType::NativeNervousSystemFunction(Empty {})),
         }
     }
+
+    fn create_treasury_payment_stream() -> NervousSystemFunction {
+        NervousSystemFunction {
+            id: native_action_ids::CREATE_TREASURY_PAYMENT_STREAM,
+            name: "Create treasury payment stream".to_string(),
+            description: Some(
+                "Proposal to make recurring payments from an SNS Governance controlled treasury \
+                 account."
+                    .to_string(),
+            ),
+            function_type: Some(FunctionType::NativeNervousSystemFunction(Empty {})),
+        }
+    }
+
+    fn cancel_treasury_payment_stream() -> NervousSystemFunction {
+        NervousSystemFunction {
+            id: native_action_ids::CANCEL_TREASURY_PAYMENT_STREAM,
+            name: "Cancel treasury payment stream".to_string(),
+            description: Some(
+                "Proposal to stop the recurring payments of a treasury payment stream.".to_string(),
+            ),
+            function_type: Some(FunctionType::NativeNervousSystemFunction(Empty {})),
+        }
+    }
+
+    fn take_dapp_canister_snapshot() -> NervousSystemFunction {
+        NervousSystemFunction {
+            id: native_action_ids::TAKE_DAPP_CANISTER_SNAPSHOT,
+            name: "Take dapp canister snapshot".to_string(),
+            description: Some(
+                "Proposal to take a snapshot of a dapp canister controlled by the SNS.".to_string(),
+            ),
+            function_type: Some(FunctionType::NativeNervousSystemFunction(Empty {})),
+        }
+    }
+
+    fn load_dapp_canister_snapshot() -> NervousSystemFunction {
+        NervousSystemFunction {
+            id: native_action_ids::LOAD_DAPP_CANISTER_SNAPSHOT,
+            name: "Load dapp canister snapshot".to_string(),
+            description: Some(
+                "Proposal to restore a dapp canister controlled by the SNS from one of its \
+                 snapshots."
+                    .to_string(),
+            ),
+            function_type: Some(FunctionType::NativeNervousSystemFunction(Empty {})),
+        }
+    }
+
+    fn open_follow_on_swap_round() -> NervousSystemFunction {
+        NervousSystemFunction {
+            id: native_action_ids::OPEN_FOLLOW_ON_SWAP_ROUND,
+            name: "Open follow-on swap round".to_string(),
+            description: Some(
+                "Proposal to sell SNS tokens from the treasury for ICP in a follow-on round of \
+                 the swap."
+                    .to_string(),
+            ),
+            function_type: Some(FunctionType::NativeNervousSystemFunction(Empty {})),
+        }
+    }
 }
 
 impl From<Action> for NervousSystemFunction {
@@ -1312,6 +1393,19 @@ impl From<Action> for NervousSystemFunction {
             Action::SetTopicsForCustomProposals(_) => {
                 NervousSystemFunction::set_topics_for_custom_proposals()
             }
+            Action::CreateTreasuryPaymentStream(_) => {
+                NervousSystemFunction::create_treasury_payment_stream()
+            }
+            Action::CancelTreasuryPaymentStream(_) => {
+                NervousSystemFunction::cancel_treasury_payment_stream()
+            }
+            Action::TakeDappCanisterSnapshot(_) => {
+                NervousSystemFunction::take_dapp_canister_snapshot()
+            }
+            Action::LoadDappCanisterSnapshot(_) => {
+                NervousSystemFunction::load_dapp_canister_snapshot()
+            }
+            Action::OpenFollowOnSwapRound(_) => NervousSystemFunction::open_follow_on_swap_round(),
         }
     }
 }
@@ -1896,6 +1990,15 @@ impl From<&Action> for u64 {
             Action::SetTopicsForCustomProposals(_) => {
                 native_action_ids::SET_TOPICS_FOR_CUSTOM_PROPOSALS_ACTION
             }
+            Action::CreateTreasuryPaymentStream(_) => {
+                native_action_ids::CREATE_TREASURY_PAYMENT_STREAM
+            }
+            Action::CancelTreasuryPaymentStream(_) => {
+                native_action_ids::CANCEL_TREASURY_PAYMENT_STREAM
+            }
+            Action::TakeDappCanisterSnapshot(_) => native_action_ids::TAKE_DAPP_CANISTER_SNAPSHOT,
+            Action::LoadDappCanisterSnapshot(_) => native_action_ids::LOAD_DAPP_CANISTER_SNAPSHOT,
+            Action::OpenFollowOnSwapRound(_) => native_action_ids::OPEN_FOLLOW_ON_SWAP_ROUND,
         }
     }
 }
@@ -138,6 +138,7 @@ fn test_upgrade_canister_proposal_is_successful() {
                 // mode: None corresponds to CanisterInstallModeProto::Upgrade
                 mode: None,
                 chunked_canister_wasm: None,
+                take_snapshot_before_upgrade: None,
             },
         )),
         ..Default::default()
@@ -265,6 +266,7 @@ fn test_upgrade_canister_proposal_reinstall() {
                     canister_upgrade_arg: Some(wasm().build()),
                     mode: Some(CanisterInstallModeProto::Reinstall.into()),
                     chunked_canister_wasm: None,
+                    take_snapshot_before_upgrade: None,
                 },
             )),
             ..Default::default()
@@ -423,6 +425,7 @@ fn test_upgrade_canister_proposal_execution_fail() {
                     canister_upgrade_arg: None,
                     mode: Some(CanisterInstallModeProto::Upgrade.into()),
                     chunked_canister_wasm: None,
+                    take_snapshot_before_upgrade: None,
                 },
             )),
             ..Default::default()
@@ -526,6 +529,7 @@ fn test_upgrade_canister_proposal_too_large() {
                 // mode: None corresponds to CanisterInstallModeProto::Upgrade
                 mode: None,
                 chunked_canister_wasm: None,
+                take_snapshot_before_upgrade: None,
             },
         )),
         ..Default::default()
@@ -648,6 +652,7 @@ fn test_upgrade_after_state_shrink() {
                     canister_upgrade_arg: None,
                     mode: Some(CanisterInstallModeProto::Upgrade.into()),
                     chunked_canister_wasm: None,
+                    take_snapshot_before_upgrade: None,
                 },
             )),
             ..Default::default()
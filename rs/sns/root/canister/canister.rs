@@ -23,10 +23,11 @@ use ic_sns_root::{
     logs::{ERROR, INFO},
     pb::v1::{
         CanisterCallError, ListSnsCanistersRequest, ListSnsCanistersResponse,
+        LoadDappCanisterSnapshotRequest, LoadDappCanisterSnapshotResponse,
         ManageDappCanisterSettingsRequest, ManageDappCanisterSettingsResponse,
         RegisterDappCanisterRequest, RegisterDappCanisterResponse, RegisterDappCanistersRequest,
         RegisterDappCanistersResponse, SetDappControllersRequest, SetDappControllersResponse,
-        SnsRootCanister,
+        SnsRootCanister, TakeDappCanisterSnapshotRequest, TakeDappCanisterSnapshotResponse,
     },
     types::Environment,
     GetSnsCanistersSummaryRequest, GetSnsCanistersSummaryResponse, LedgerCanisterClient,
@@ -356,6 +357,38 @@ async fn manage_dapp_canister_settings(
     })
 }
 
+#[candid_method(update)]
+#[update]
+async fn take_dapp_canister_snapshot(
+    request: TakeDappCanisterSnapshotRequest,
+) -> TakeDappCanisterSnapshotResponse {
+    log!(INFO, "take_dapp_canister_snapshot");
+    assert_eq_governance_canister_id(PrincipalId(ic_cdk::api::caller()));
+
+    SnsRootCanister::take_dapp_canister_snapshot(
+        &STATE,
+        request,
+        &ManagementCanisterClientImpl::<CanisterRuntime>::new(None),
+    )
+    .await
+}
+
+#[candid_method(update)]
+#[update]
+async fn load_dapp_canister_snapshot(
+    request: LoadDappCanisterSnapshotRequest,
+) -> LoadDappCanisterSnapshotResponse {
+    log!(INFO, "load_dapp_canister_snapshot");
+    assert_eq_governance_canister_id(PrincipalId(ic_cdk::api::caller()));
+
+    SnsRootCanister::load_dapp_canister_snapshot(
+        &STATE,
+        request,
+        &ManagementCanisterClientImpl::<CanisterRuntime>::new(None),
+    )
+    .await
+}
+
 fn assert_state_is_valid(state: &SnsRootCanister) {
     assert!(state.governance_canister_id.is_some());
     assert!(state.ledger_canister_id.is_some());
@@ -2,17 +2,20 @@ use crate::{
     logs::{ERROR, INFO},
     pb::v1::{
         set_dapp_controllers_response, CanisterCallError, ListSnsCanistersResponse,
+        LoadDappCanisterSnapshotRequest, LoadDappCanisterSnapshotResponse,
         ManageDappCanisterSettingsRequest, ManageDappCanisterSettingsResponse,
         RegisterDappCanistersRequest, RegisterDappCanistersResponse, SetDappControllersRequest,
-        SetDappControllersResponse, SnsRootCanister,
+        SetDappControllersResponse, SnsRootCanister, TakeDappCanisterSnapshotRequest,
+        TakeDappCanisterSnapshotResponse,
     },
     types::Environment,
 };
 use async_trait::async_trait;
 use candid::{Decode, Encode, Nat};
 use futures::{future::join_all, join};
-use ic_base_types::{CanisterId, PrincipalId};
+use ic_base_types::{CanisterId, PrincipalId, SnapshotId};
 use ic_canister_log::log;
+use ic_management_canister_types_private::{LoadCanisterSnapshotArgs, TakeCanisterSnapshotArgs};
 use ic_nervous_system_clients::{
     canister_id_record::CanisterIdRecord,
     canister_status::CanisterStatusResultV2,
@@ -731,6 +734,128 @@ impl SnsRootCanister {
         }
     }
 
+    /// Returns the ID of a registered dapp canister, or an error if `canister_id` is not one.
+    fn validate_dapp_canister_id(
+        &self,
+        canister_id: Option<PrincipalId>,
+    ) -> Result<CanisterId, String> {
+        let canister_id = canister_id.ok_or_else(|| "canister_id must be set.".to_string())?;
+        if !self.dapp_canister_ids.contains(&canister_id) {
+            return Err(format!(
+                "Canister {canister_id} is not a registered dapp canister."
+            ));
+        }
+        CanisterId::try_from(canister_id).map_err(|err| format!("Invalid canister_id: {err:?}"))
+    }
+
+    /// Parses a snapshot ID, and makes sure that it belongs to `canister_id`.
+    fn validate_snapshot_id(
+        canister_id: CanisterId,
+        snapshot_id: &[u8],
+    ) -> Result<SnapshotId, String> {
+        let snapshot_id = SnapshotId::try_from(snapshot_id).map_err(|err| err.to_string())?;
+        if snapshot_id.get_canister_id() != canister_id {
+            return Err(format!(
+                "Snapshot {snapshot_id} does not belong to canister {canister_id}."
+            ));
+        }
+        Ok(snapshot_id)
+    }
+
+    /// Takes a snapshot of a dapp canister, optionally replacing an existing snapshot of it.
+    /// * Does NOT take snapshots of SNS canisters because those cannot be dapp canisters.
+    pub async fn take_dapp_canister_snapshot(
+        self_ref: &'static LocalKey<RefCell<Self>>,
+        request: TakeDappCanisterSnapshotRequest,
+        management_canister_client: &impl ManagementCanisterClient,
+    ) -> TakeDappCanisterSnapshotResponse {
+        let failure = |failure_reason: String| TakeDappCanisterSnapshotResponse {
+            failure_reason: Some(failure_reason),
+            snapshot_id: None,
+            taken_at_timestamp: None,
+        };
+
+        let validated = self_ref.with_borrow(|state| {
+            let canister_id = state.validate_dapp_canister_id(request.canister_id)?;
+            let replace_snapshot = request
+                .replace_snapshot
+                .as_ref()
+                .map(|snapshot_id| Self::validate_snapshot_id(canister_id, snapshot_id))
+                .transpose()?;
+            Ok::<_, String>((canister_id, replace_snapshot))
+        });
+        let (canister_id, replace_snapshot) = match validated {
+            Ok(validated) => validated,
+            Err(failure_reason) => return failure(failure_reason),
+        };
+
+        match management_canister_client
+            .take_canister_snapshot(TakeCanisterSnapshotArgs::new(canister_id, replace_snapshot))
+            .await
+        {
+            Ok(response) => {
+                let snapshot_id = response.snapshot_id();
+                log!(
+                    INFO,
+                    "Took snapshot {snapshot_id} of canister {canister_id}"
+                );
+                TakeDappCanisterSnapshotResponse {
+                    failure_reason: None,
+                    snapshot_id: Some(snapshot_id.to_vec()),
+                    taken_at_timestamp: Some(response.taken_at_timestamp),
+                }
+            }
+            Err(err) => {
+                log!(
+                    ERROR,
+                    "Failed to take a snapshot of canister {canister_id}: {err:?}"
+                );
+                failure(format!(
+                    "Failed to take a snapshot of canister {canister_id}: {err:?}"
+                ))
+            }
+        }
+    }
+
+    /// Loads a snapshot into the dapp canister that the snapshot was taken of.
+    pub async fn load_dapp_canister_snapshot(
+        self_ref: &'static LocalKey<RefCell<Self>>,
+        request: LoadDappCanisterSnapshotRequest,
+        management_canister_client: &impl ManagementCanisterClient,
+    ) -> LoadDappCanisterSnapshotResponse {
+        let validated = self_ref.with_borrow(|state| {
+            let canister_id = state.validate_dapp_canister_id(request.canister_id)?;
+            let snapshot_id = Self::validate_snapshot_id(canister_id, &request.snapshot_id)?;
+            Ok::<_, String>((canister_id, snapshot_id))
+        });
+        let (canister_id, snapshot_id) = match validated {
+            Ok(validated) => validated,
+            Err(failure_reason) => {
+                return LoadDappCanisterSnapshotResponse {
+                    failure_reason: Some(failure_reason),
+                }
+            }
+        };
+
+        let failure_reason = management_canister_client
+            .load_canister_snapshot(LoadCanisterSnapshotArgs::new(
+                canister_id,
+                snapshot_id,
+                management_canister_client.canister_version(),
+            ))
+            .await
+            .err()
+            .map(|err| {
+                log!(
+                    ERROR,
+                    "Failed to load snapshot into canister {canister_id}: {err:?}"
+                );
+                format!("Failed to load snapshot into canister {canister_id}: {err:?}")
+            });
+
+        LoadDappCanisterSnapshotResponse { failure_reason }
+    }
+
     /// Polls for new archives canisters from the ledger canister.
     pub async fn poll_for_new_archive_canisters(
         self_ref: &'static LocalKey<RefCell<Self>>,
@@ -879,6 +1004,7 @@ async fn get_owned_canister_summary(
 mod tests {
     use super::*;
     use crate::pb::v1::{set_dapp_controllers_request::CanisterIds, ListSnsCanistersResponse};
+    use ic_management_canister_types_private::CanisterSnapshotResponse;
     use ic_nervous_system_clients::{
         canister_status::CanisterStatusResultFromManagementCanister,
         management_canister_client::{
@@ -3402,4 +3528,164 @@ mod tests {
             expected_management_canister_calls
         );
     }
+
+    #[tokio::test]
+    async fn test_take_dapp_canister_snapshot() {
+        // Step 1: Prepare the world.
+        thread_local! {
+            static STATE: RefCell<SnsRootCanister> = RefCell::new(SnsRootCanister {
+                dapp_canister_ids: vec![CanisterId::from_u64(10).get()],
+                ..build_test_sns_root_canister(false)
+            });
+        }
+        let dapp_canister_id = CanisterId::from_u64(10);
+        let old_snapshot_id = SnapshotId::from((dapp_canister_id, 1));
+        let new_snapshot_id = SnapshotId::from((dapp_canister_id, 2));
+
+        let management_canister_client = MockManagementCanisterClient::new(vec![
+            MockManagementCanisterClientReply::TakeCanisterSnapshot(Ok(CanisterSnapshotResponse {
+                id: new_snapshot_id,
+                taken_at_timestamp: NOW,
+                total_size: 42,
+            })),
+        ]);
+
+        // Step 2: Run code under test.
+        let response = SnsRootCanister::take_dapp_canister_snapshot(
+            &STATE,
+            TakeDappCanisterSnapshotRequest {
+                canister_id: Some(dapp_canister_id.get()),
+                replace_snapshot: Some(old_snapshot_id.to_vec()),
+            },
+            &management_canister_client,
+        )
+        .await;
+
+        // Step 3: Inspect results.
+        assert_eq!(
+            response,
+            TakeDappCanisterSnapshotResponse {
+                failure_reason: None,
+                snapshot_id: Some(new_snapshot_id.to_vec()),
+                taken_at_timestamp: Some(NOW),
+            }
+        );
+        assert_eq!(
+            management_canister_client.get_calls_snapshot(),
+            vec![MockManagementCanisterClientCall::TakeCanisterSnapshot(
+                TakeCanisterSnapshotArgs::new(dapp_canister_id, Some(old_snapshot_id))
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_take_dapp_canister_snapshot_invalid() {
+        thread_local! {
+            static STATE: RefCell<SnsRootCanister> = RefCell::new(SnsRootCanister {
+                dapp_canister_ids: vec![CanisterId::from_u64(10).get()],
+                ..build_test_sns_root_canister(false)
+            });
+        }
+        let dapp_canister_id = CanisterId::from_u64(10);
+        let other_canister_id = CanisterId::from_u64(11);
+        // No calls are expected to be made to the management canister.
+        let management_canister_client = MockManagementCanisterClient::new(vec![]);
+
+        for (request, expected_failure_reason) in [
+            (
+                TakeDappCanisterSnapshotRequest {
+                    canister_id: None,
+                    replace_snapshot: None,
+                },
+                "canister_id must be set",
+            ),
+            (
+                TakeDappCanisterSnapshotRequest {
+                    canister_id: Some(other_canister_id.get()),
+                    replace_snapshot: None,
+                },
+                "is not a registered dapp canister",
+            ),
+            (
+                TakeDappCanisterSnapshotRequest {
+                    canister_id: Some(dapp_canister_id.get()),
+                    replace_snapshot: Some(SnapshotId::from((other_canister_id, 1)).to_vec()),
+                },
+                "does not belong to canister",
+            ),
+        ] {
+            let response = SnsRootCanister::take_dapp_canister_snapshot(
+                &STATE,
+                request,
+                &management_canister_client,
+            )
+            .await;
+            let failure_reason = response.failure_reason.unwrap();
+            assert!(
+                failure_reason.contains(expected_failure_reason),
+                "{failure_reason}"
+            );
+            assert_eq!(response.snapshot_id, None);
+        }
+        assert_eq!(management_canister_client.get_calls_snapshot(), vec![]);
+    }
+
+    #[tokio::test]
+    async fn test_load_dapp_canister_snapshot() {
+        // Step 1: Prepare the world.
+        thread_local! {
+            static STATE: RefCell<SnsRootCanister> = RefCell::new(SnsRootCanister {
+                dapp_canister_ids: vec![CanisterId::from_u64(10).get()],
+                ..build_test_sns_root_canister(false)
+            });
+        }
+        let dapp_canister_id = CanisterId::from_u64(10);
+        let snapshot_id = SnapshotId::from((dapp_canister_id, 1));
+
+        let management_canister_client = MockManagementCanisterClient::new(vec![
+            MockManagementCanisterClientReply::LoadCanisterSnapshot(Ok(())),
+            MockManagementCanisterClientReply::LoadCanisterSnapshot(Err((
+                3,
+                "Snapshot not found".to_string(),
+            ))),
+        ]);
+        let request = LoadDappCanisterSnapshotRequest {
+            canister_id: Some(dapp_canister_id.get()),
+            snapshot_id: snapshot_id.to_vec(),
+        };
+
+        // Step 2 & 3: Run code under test, and inspect results.
+        let response = SnsRootCanister::load_dapp_canister_snapshot(
+            &STATE,
+            request.clone(),
+            &management_canister_client,
+        )
+        .await;
+        assert_eq!(
+            response,
+            LoadDappCanisterSnapshotResponse {
+                failure_reason: None
+            }
+        );
+
+        let response = SnsRootCanister::load_dapp_canister_snapshot(
+            &STATE,
+            request,
+            &management_canister_client,
+        )
+        .await;
+        let failure_reason = response.failure_reason.unwrap();
+        assert!(
+            failure_reason.contains("Snapshot not found"),
+            "{failure_reason}"
+        );
+
+        let expected_call = MockManagementCanisterClientCall::LoadCanisterSnapshot(
+            LoadCanisterSnapshotArgs::new(dapp_canister_id, snapshot_id, None),
+        );
+        assert_eq!(
+            management_canister_client.get_calls_snapshot(),
+            vec![expected_call.clone(), expected_call]
+        );
+    }
 }
@@ -10,6 +10,7 @@ use ic_nervous_system_canisters::ledger::IcpLedgerCanister;
 use ic_nervous_system_clients::{
     canister_id_record::CanisterIdRecord,
     canister_status::CanisterStatusResultV2,
+    ledger_client::LedgerCanister,
     management_canister_client::{ManagementCanisterClient, ManagementCanisterClientImpl},
 };
 use ic_nervous_system_common::{serve_logs, serve_logs_v2, serve_metrics};
@@ -21,17 +22,18 @@ use ic_sns_swap::{
     logs::{ERROR, INFO},
     memory::UPGRADES_MEMORY,
     pb::v1::{
-        ErrorRefundIcpRequest, ErrorRefundIcpResponse, FinalizeSwapRequest, FinalizeSwapResponse,
-        GetAutoFinalizationStatusRequest, GetAutoFinalizationStatusResponse, GetBuyerStateRequest,
-        GetBuyerStateResponse, GetBuyersTotalRequest, GetBuyersTotalResponse,
-        GetCanisterStatusRequest, GetDerivedStateRequest, GetDerivedStateResponse, GetInitRequest,
-        GetInitResponse, GetLifecycleRequest, GetLifecycleResponse, GetOpenTicketRequest,
-        GetOpenTicketResponse, GetSaleParametersRequest, GetSaleParametersResponse,
-        GetStateRequest, GetStateResponse, Init, ListCommunityFundParticipantsRequest,
-        ListCommunityFundParticipantsResponse, ListDirectParticipantsRequest,
-        ListDirectParticipantsResponse, ListSnsNeuronRecipesRequest, ListSnsNeuronRecipesResponse,
-        NewSaleTicketRequest, NewSaleTicketResponse, NotifyPaymentFailureRequest,
-        NotifyPaymentFailureResponse, RefreshBuyerTokensRequest, RefreshBuyerTokensResponse, Swap,
+        open_follow_on_round_response, ErrorRefundIcpRequest, ErrorRefundIcpResponse,
+        FinalizeSwapRequest, FinalizeSwapResponse, GetAutoFinalizationStatusRequest,
+        GetAutoFinalizationStatusResponse, GetBuyerStateRequest, GetBuyerStateResponse,
+        GetBuyersTotalRequest, GetBuyersTotalResponse, GetCanisterStatusRequest,
+        GetDerivedStateRequest, GetDerivedStateResponse, GetInitRequest, GetInitResponse,
+        GetLifecycleRequest, GetLifecycleResponse, GetOpenTicketRequest, GetOpenTicketResponse,
+        GetSaleParametersRequest, GetSaleParametersResponse, GetStateRequest, GetStateResponse,
+        Init, ListCommunityFundParticipantsRequest, ListCommunityFundParticipantsResponse,
+        ListDirectParticipantsRequest, ListDirectParticipantsResponse, ListSnsNeuronRecipesRequest,
+        ListSnsNeuronRecipesResponse, NewSaleTicketRequest, NewSaleTicketResponse,
+        NotifyPaymentFailureRequest, NotifyPaymentFailureResponse, OpenFollowOnRoundRequest,
+        OpenFollowOnRoundResponse, RefreshBuyerTokensRequest, RefreshBuyerTokensResponse, Swap,
     },
 };
 use ic_stable_structures::{writer::Writer, Memory};
@@ -157,6 +159,30 @@ async fn finalize_swap(_arg: FinalizeSwapRequest) -> FinalizeSwapResponse {
     swap_mut().finalize(now_fn, &mut clients).await
 }
 
+/// See Swap.open_follow_on_round. Only SNS governance may call this, as part of
+/// executing a proposal.
+#[update]
+async fn open_follow_on_round(request: OpenFollowOnRoundRequest) -> OpenFollowOnRoundResponse {
+    log!(INFO, "open_follow_on_round");
+    let init = swap().init_or_panic();
+    if caller_principal_id() != init.sns_governance_or_panic().get() {
+        panic!("Only SNS governance can open a follow-on round.");
+    }
+    let sns_ledger = LedgerCanister::new(init.sns_ledger_or_panic());
+
+    let response = swap_mut()
+        .open_follow_on_round_with_ledger(&request, now_fn, this_canister_id(), &sns_ledger)
+        .await;
+
+    // The periodic tasks stop once a round is over, so they need to be
+    // restarted to drive the new round through its lifecycle.
+    if let Some(open_follow_on_round_response::Result::Ok(_)) = response.result {
+        init_timers();
+    }
+
+    response
+}
+
 #[update]
 async fn error_refund_icp(request: ErrorRefundIcpRequest) -> ErrorRefundIcpResponse {
     let icp_ledger = create_real_icp_ledger(swap().init_or_panic().icp_ledger_or_panic());
@@ -239,6 +239,81 @@ pub struct Swap {
     /// Information about the timers that perform periodic tasks of this Swap canister.
     #[prost(message, optional, tag = "22")]
     pub timers: ::core::option::Option<::ic_nervous_system_proto::pb::v1::Timers>,
+    /// Set when SNS governance reopens the swap for a follow-on round (see
+    /// `OpenFollowOnRoundRequest`). Unset during the initial decentralization swap.
+    #[prost(message, optional, tag = "23")]
+    pub follow_on_round: ::core::option::Option<FollowOnRound>,
+    /// Summaries of all rounds that were completed before the current one, in
+    /// chronological order. The initial decentralization swap is round 0.
+    #[prost(message, repeated, tag = "24")]
+    pub completed_rounds: ::prost::alloc::vec::Vec<SwapRoundSummary>,
+}
+/// Describes the follow-on round that the swap is currently running.
+///
+/// A follow-on round sells SNS tokens from the SNS treasury to direct
+/// participants. It reuses the participation, refund and finalization logic of
+/// the initial decentralization swap, but without the Neurons' Fund, and
+/// without touching the control of the dapp canisters or the mode of SNS
+/// governance.
+#[derive(
+    candid::CandidType,
+    candid::Deserialize,
+    serde::Serialize,
+    comparable::Comparable,
+    Clone,
+    Copy,
+    PartialEq,
+    ::prost::Message,
+)]
+pub struct FollowOnRound {
+    /// The number of this round. The first follow-on round has number 1.
+    #[prost(uint64, optional, tag = "1")]
+    pub round_number: ::core::option::Option<u64>,
+    /// The ID of the SNS proposal that opened this round.
+    #[prost(uint64, optional, tag = "2")]
+    pub sns_proposal_id: ::core::option::Option<u64>,
+    /// Tracks the return of the offered SNS tokens to the SNS treasury, which
+    /// happens when the round is aborted.
+    #[prost(message, optional, tag = "3")]
+    pub sns_tokens_returned: ::core::option::Option<TransferableAmount>,
+}
+/// A summary of a completed swap round, kept for historical purposes after the
+/// state of the round has been cleared to make room for the next round.
+#[derive(
+    candid::CandidType,
+    candid::Deserialize,
+    serde::Serialize,
+    comparable::Comparable,
+    Clone,
+    Copy,
+    PartialEq,
+    ::prost::Message,
+)]
+pub struct SwapRoundSummary {
+    /// The number of the round. The initial decentralization swap has number 0.
+    #[prost(uint64, optional, tag = "1")]
+    pub round_number: ::core::option::Option<u64>,
+    /// The terminal lifecycle of the round (COMMITTED or ABORTED).
+    #[prost(enumeration = "Lifecycle", optional, tag = "2")]
+    pub lifecycle: ::core::option::Option<i32>,
+    /// The number of SNS tokens offered in the round.
+    #[prost(uint64, optional, tag = "3")]
+    pub sns_token_e8s: ::core::option::Option<u64>,
+    /// The total amount of ICP contributed by direct participants.
+    #[prost(uint64, optional, tag = "4")]
+    pub direct_participation_icp_e8s: ::core::option::Option<u64>,
+    /// The total amount of ICP contributed by the Neurons' Fund.
+    #[prost(uint64, optional, tag = "5")]
+    pub neurons_fund_participation_icp_e8s: ::core::option::Option<u64>,
+    /// The number of direct participants.
+    #[prost(uint64, optional, tag = "6")]
+    pub direct_participant_count: ::core::option::Option<u64>,
+    /// The timestamp at which the round opened.
+    #[prost(uint64, optional, tag = "7")]
+    pub open_timestamp_seconds: ::core::option::Option<u64>,
+    /// The timestamp at which the round was committed or aborted.
+    #[prost(uint64, optional, tag = "8")]
+    pub termination_timestamp_seconds: ::core::option::Option<u64>,
 }
 /// The initialisation data of the canister. Always specified on
 /// canister creation, and cannot be modified afterwards.
@@ -2784,6 +2859,119 @@ pub struct NotifyPaymentFailureResponse {
     #[prost(message, optional, tag = "1")]
     pub ticket: ::core::option::Option<Ticket>,
 }
+/// Request for the method `open_follow_on_round`. Can only be called by SNS
+/// governance, as part of executing a proposal.
+///
+/// Before calling this method, SNS governance is expected to have transferred
+/// (at least) `sns_token_e8s` SNS tokens to the default account of the Swap
+/// canister.
+#[derive(
+    candid::CandidType,
+    candid::Deserialize,
+    serde::Serialize,
+    comparable::Comparable,
+    Clone,
+    Copy,
+    PartialEq,
+    ::prost::Message,
+)]
+pub struct OpenFollowOnRoundRequest {
+    /// The ID of the SNS proposal that is opening the round.
+    #[prost(uint64, optional, tag = "1")]
+    pub sns_proposal_id: ::core::option::Option<u64>,
+    /// The number of SNS tokens offered in the round.
+    #[prost(uint64, optional, tag = "2")]
+    pub sns_token_e8s: ::core::option::Option<u64>,
+    /// The minimum and maximum amount of ICP that the round aims to raise.
+    #[prost(uint64, optional, tag = "3")]
+    pub min_direct_participation_icp_e8s: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "4")]
+    pub max_direct_participation_icp_e8s: ::core::option::Option<u64>,
+    /// The minimum and maximum amount of ICP that each participant may contribute.
+    #[prost(uint64, optional, tag = "5")]
+    pub min_participant_icp_e8s: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "6")]
+    pub max_participant_icp_e8s: ::core::option::Option<u64>,
+    /// The minimum number of participants for the round to succeed.
+    #[prost(uint32, optional, tag = "7")]
+    pub min_participants: ::core::option::Option<u32>,
+    /// The time window during which the round accepts participation.
+    #[prost(uint64, optional, tag = "8")]
+    pub swap_start_timestamp_seconds: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "9")]
+    pub swap_due_timestamp_seconds: ::core::option::Option<u64>,
+    /// How the SNS tokens bought by each participant are split into neurons.
+    #[prost(message, optional, tag = "10")]
+    pub neuron_basket_construction_parameters:
+        ::core::option::Option<NeuronBasketConstructionParameters>,
+    /// The current values of the corresponding SNS ledger and governance
+    /// parameters, which may have changed since the initial swap.
+    #[prost(uint64, optional, tag = "11")]
+    pub transaction_fee_e8s: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "12")]
+    pub neuron_minimum_stake_e8s: ::core::option::Option<u64>,
+}
+#[derive(
+    candid::CandidType,
+    candid::Deserialize,
+    serde::Serialize,
+    comparable::Comparable,
+    Clone,
+    PartialEq,
+    ::prost::Message,
+)]
+pub struct OpenFollowOnRoundResponse {
+    #[prost(oneof = "open_follow_on_round_response::Result", tags = "1, 2")]
+    pub result: ::core::option::Option<open_follow_on_round_response::Result>,
+}
+/// Nested message and enum types in `OpenFollowOnRoundResponse`.
+pub mod open_follow_on_round_response {
+    /// The round was opened. It will start accepting participation at
+    /// `swap_start_timestamp_seconds`.
+    #[derive(
+        candid::CandidType,
+        candid::Deserialize,
+        serde::Serialize,
+        comparable::Comparable,
+        Clone,
+        Copy,
+        PartialEq,
+        ::prost::Message,
+    )]
+    pub struct Ok {
+        #[prost(uint64, optional, tag = "1")]
+        pub round_number: ::core::option::Option<u64>,
+    }
+    /// The round could not be opened, and the state of the swap is unchanged.
+    #[derive(
+        candid::CandidType,
+        candid::Deserialize,
+        serde::Serialize,
+        comparable::Comparable,
+        Clone,
+        PartialEq,
+        ::prost::Message,
+    )]
+    pub struct Err {
+        #[prost(string, optional, tag = "1")]
+        pub description: ::core::option::Option<::prost::alloc::string::String>,
+    }
+    #[derive(
+        candid::CandidType,
+        candid::Deserialize,
+        serde::Serialize,
+        comparable::Comparable,
+        Clone,
+        PartialEq,
+        ::prost::Oneof,
+    )]
+    pub enum Result {
+        #[prost(message, tag = "1")]
+        Ok(Ok),
+        #[prost(message, tag = "2")]
+        Err(Err),
+    }
+}
 /// Lifecycle states of the swap canister. The details of their meanings
 /// are provided in the documentation of the `Swap` message.
 #[derive(
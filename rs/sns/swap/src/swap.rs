@@ -4,26 +4,27 @@ use crate::{
     logs::{ERROR, INFO},
     memory,
     pb::v1::{
-        get_open_ticket_response, new_sale_ticket_response, set_dapp_controllers_call_result,
-        set_mode_call_result,
+        get_open_ticket_response, new_sale_ticket_response, open_follow_on_round_response,
+        set_dapp_controllers_call_result, set_mode_call_result,
         set_mode_call_result::SetModeResult,
         settle_neurons_fund_participation_request, settle_neurons_fund_participation_response,
         sns_neuron_recipe::{ClaimedStatus, Investor, NeuronAttributes},
         BuyerState, CanisterCallError, CfInvestment, CfNeuron, CfParticipant, DerivedState,
         DirectInvestment, ErrorRefundIcpRequest, ErrorRefundIcpResponse, FinalizeSwapResponse,
-        GetAutoFinalizationStatusRequest, GetAutoFinalizationStatusResponse, GetBuyerStateRequest,
-        GetBuyerStateResponse, GetBuyersTotalResponse, GetDerivedStateResponse, GetInitRequest,
-        GetInitResponse, GetLifecycleRequest, GetLifecycleResponse, GetOpenTicketRequest,
-        GetOpenTicketResponse, GetSaleParametersRequest, GetSaleParametersResponse,
-        GetStateResponse, Icrc1Account, Init, Lifecycle, ListCommunityFundParticipantsRequest,
-        ListCommunityFundParticipantsResponse, ListDirectParticipantsRequest,
-        ListDirectParticipantsResponse, ListSnsNeuronRecipesRequest, ListSnsNeuronRecipesResponse,
-        NeuronBasketConstructionParameters, NeuronId as SwapNeuronId, NewSaleTicketRequest,
-        NewSaleTicketResponse, NotifyPaymentFailureResponse, Params, Participant,
+        FollowOnRound, GetAutoFinalizationStatusRequest, GetAutoFinalizationStatusResponse,
+        GetBuyerStateRequest, GetBuyerStateResponse, GetBuyersTotalResponse,
+        GetDerivedStateResponse, GetInitRequest, GetInitResponse, GetLifecycleRequest,
+        GetLifecycleResponse, GetOpenTicketRequest, GetOpenTicketResponse,
+        GetSaleParametersRequest, GetSaleParametersResponse, GetStateResponse, Icrc1Account, Init,
+        Lifecycle, ListCommunityFundParticipantsRequest, ListCommunityFundParticipantsResponse,
+        ListDirectParticipantsRequest, ListDirectParticipantsResponse, ListSnsNeuronRecipesRequest,
+        ListSnsNeuronRecipesResponse, NeuronBasketConstructionParameters, NeuronId as SwapNeuronId,
+        NewSaleTicketRequest, NewSaleTicketResponse, NotifyPaymentFailureResponse,
+        OpenFollowOnRoundRequest, OpenFollowOnRoundResponse, Params, Participant,
         RefreshBuyerTokensResponse, SetDappControllersCallResult, SetDappControllersRequest,
         SetDappControllersResponse, SetModeCallResult, SettleNeuronsFundParticipationRequest,
         SettleNeuronsFundParticipationResponse, SettleNeuronsFundParticipationResult,
-        SnsNeuronRecipe, Swap, SweepResult, Ticket, TransferableAmount,
+        SnsNeuronRecipe, Swap, SwapRoundSummary, SweepResult, Ticket, TransferableAmount,
     },
     types::{NeuronsFundNeuron, ScheduledVestingEvent, TransferResult},
 };
@@ -33,15 +34,20 @@ use ic_cdk::api::call::RejectionCode;
 use ic_ledger_core::Tokens;
 use ic_nervous_system_clients::ledger_client::ICRC1Ledger;
 use ic_nervous_system_common::{
-    i2d, ledger::compute_neuron_staking_subaccount_bytes, MAX_NEURONS_FOR_DIRECT_PARTICIPANTS,
+    i2d,
+    ledger::{compute_distribution_subaccount_bytes, compute_neuron_staking_subaccount_bytes},
+    MAX_NEURONS_FOR_DIRECT_PARTICIPANTS,
 };
 use ic_nervous_system_proto::pb::v1::Principals;
 use ic_neurons_fund::{MatchedParticipationFunction, PolynomialNeuronsFundParticipation};
-use ic_sns_governance::pb::v1::{
-    claim_swap_neurons_request::{neuron_recipe, NeuronRecipe, NeuronRecipes},
-    claim_swap_neurons_response::{ClaimSwapNeuronsResult, SwapNeuron},
-    governance, ClaimSwapNeuronsError, ClaimSwapNeuronsRequest, ClaimedSwapNeuronStatus, NeuronId,
-    NeuronIds, SetMode, SetModeResponse,
+use ic_sns_governance::{
+    governance::TREASURY_SUBACCOUNT_NONCE,
+    pb::v1::{
+        claim_swap_neurons_request::{neuron_recipe, NeuronRecipe, NeuronRecipes},
+        claim_swap_neurons_response::{ClaimSwapNeuronsResult, SwapNeuron},
+        governance, ClaimSwapNeuronsError, ClaimSwapNeuronsRequest, ClaimedSwapNeuronStatus,
+        NeuronId, NeuronIds, SetMode, SetModeResponse,
+    },
 };
 use ic_stable_structures::{
     storable::{Blob, Bound},
@@ -88,6 +94,12 @@ const DEFAULT_LIST_SNS_NEURON_RECIPES_LIMIT: u32 = 10_000;
 pub const NEURON_BASKET_MEMO_RANGE_START: u64 = 1_000_000;
 pub const SALE_NEURON_MEMO_RANGE_END: u64 = 10_000_000;
 
+/// The neuron basket memos of direct participants in follow-on round `n` start at
+/// `NEURON_BASKET_MEMO_RANGE_START + n * FOLLOW_ON_ROUND_MEMO_STRIDE`. This way, a principal that
+/// participates in several rounds gets distinct SNS neurons in each of them. It also bounds the
+/// neuron basket count of follow-on rounds.
+pub const FOLLOW_ON_ROUND_MEMO_STRIDE: u64 = 1_000;
+
 /// The principal with all bytes set to zero. The main property
 /// of this principal is that for any principal p, the following condition holds:
 /// (p != FIRST_PRINCIPAL_BYTES) ==> FIRST_PRINCIPAL_BYTES.as_slice() < p.as_slice()
@@ -430,6 +442,8 @@ impl Swap {
             direct_participation_icp_e8s: Some(0),
             neurons_fund_participation_icp_e8s: Some(0),
             timers: None,
+            follow_on_round: None,
+            completed_rounds: vec![],
         };
         if init.validate_swap_init_for_one_proposal_flow().is_ok() {
             // Automatically fill out the fields that the (legacy) open request
@@ -666,6 +680,30 @@ impl Swap {
             .count() as u64
     }
 
+    /// The number of the round that the Swap is running, or has run last. The
+    /// initial decentralization swap is round 0.
+    pub fn current_round_number(&self) -> u64 {
+        self.follow_on_round
+            .as_ref()
+            .and_then(|follow_on_round| follow_on_round.round_number)
+            .unwrap_or(0)
+    }
+
+    /// Whether the Swap is running (or has run last) a follow-on round, as
+    /// opposed to the initial decentralization swap.
+    pub fn is_follow_on_round(&self) -> bool {
+        self.follow_on_round.is_some()
+    }
+
+    /// The memo of the first neuron in the neuron basket of each direct
+    /// participant of the current round.
+    fn direct_participant_memo_offset(&self) -> u64 {
+        NEURON_BASKET_MEMO_RANGE_START.saturating_add(
+            self.current_round_number()
+                .saturating_mul(FOLLOW_ON_ROUND_MEMO_STRIDE),
+        )
+    }
+
     /// Determines if the Swap is in it's terminal state
     /// based on it's lifecycle.
     fn lifecycle_is_terminal(&self) -> bool {
@@ -816,6 +854,8 @@ impl Swap {
         // The following methods are safe to call since we validated Init in the above block
         let nns_governance_canister_id = init.nns_governance_or_panic();
 
+        let direct_participant_memo_offset = self.direct_participant_memo_offset();
+
         let mut sweep_result = SweepResult::default();
 
         // We are selling SNS tokens for the base token (ICP), or, in
@@ -868,7 +908,7 @@ impl Swap {
                 &buyer_principal,
                 amount_sns_e8s,
                 neuron_basket_construction_parameters,
-                NEURON_BASKET_MEMO_RANGE_START,
+                direct_participant_memo_offset,
             ) {
                 Ok(direct_participant_sns_neuron_recipes) => {
                     self.neuron_recipes
@@ -1010,6 +1050,276 @@ impl Swap {
         true
     }
 
+    /// Handles an `open_follow_on_round` request from SNS governance: looks up the
+    /// SNS token balance of the Swap canister, and then calls
+    /// `Self::open_follow_on_round`.
+    pub async fn open_follow_on_round_with_ledger(
+        &mut self,
+        request: &OpenFollowOnRoundRequest,
+        now_fn: fn(bool) -> u64,
+        this_canister: CanisterId,
+        sns_ledger: &dyn ICRC1Ledger,
+    ) -> OpenFollowOnRoundResponse {
+        let account = Account {
+            owner: this_canister.get().0,
+            subaccount: None,
+        };
+        let sns_token_balance_e8s = match sns_ledger.account_balance(account).await {
+            Ok(balance) => balance.get_e8s(),
+            Err(err) => {
+                return OpenFollowOnRoundResponse::err(format!(
+                    "Unable to get the SNS token balance of the Swap canister: {}",
+                    err
+                ));
+            }
+        };
+
+        match self.open_follow_on_round(request, now_fn(true), sns_token_balance_e8s) {
+            Ok(round_number) => OpenFollowOnRoundResponse::ok(round_number),
+            Err(err) => {
+                log!(ERROR, "Unable to open a follow-on round: {}", err);
+                OpenFollowOnRoundResponse::err(err)
+            }
+        }
+    }
+
+    /// Reopens the Swap for a follow-on round, which sells `request.sns_token_e8s`
+    /// SNS tokens (taken from the SNS treasury) to direct participants.
+    ///
+    /// The current round must have been committed or aborted, and fully
+    /// finalized. Its state is summarized in `completed_rounds` and then cleared,
+    /// `init` and `params` are updated with the parameters of the new round, and
+    /// the lifecycle goes back to `Lifecycle::Adopted`. From there on, the round
+    /// goes through the same lifecycle as the initial decentralization swap.
+    ///
+    /// `sns_token_balance_e8s` is the current SNS token balance of the Swap
+    /// canister, which must cover the tokens offered in the round.
+    ///
+    /// Returns the number of the new round. If an error is returned, the state
+    /// of the Swap is unchanged.
+    pub fn open_follow_on_round(
+        &mut self,
+        request: &OpenFollowOnRoundRequest,
+        now_seconds: u64,
+        sns_token_balance_e8s: u64,
+    ) -> Result<u64, String> {
+        if !self.lifecycle_is_terminal() {
+            return Err(format!(
+                "A follow-on round can only be opened once the current round is COMMITTED \
+                 or ABORTED. Current lifecycle is {:?}.",
+                self.lifecycle()
+            ));
+        }
+        if !self.is_follow_on_round() && self.lifecycle() == Lifecycle::Aborted {
+            return Err(
+                "The decentralization swap was aborted, so there is no SNS to open a \
+                 follow-on round for."
+                    .to_string(),
+            );
+        }
+        if self.is_finalize_swap_locked() {
+            return Err("The current round is being finalized.".to_string());
+        }
+        self.validate_current_round_is_settled()?;
+
+        let round_number = self.current_round_number().saturating_add(1);
+        let max_round_number = (SALE_NEURON_MEMO_RANGE_END - NEURON_BASKET_MEMO_RANGE_START)
+            / FOLLOW_ON_ROUND_MEMO_STRIDE
+            - 1;
+        if round_number > max_round_number {
+            return Err(format!(
+                "At most {} follow-on rounds can be opened.",
+                max_round_number
+            ));
+        }
+
+        let init = self.follow_on_round_init(request)?;
+        let params = Params::try_from(&init)?;
+        params.validate(&init)?;
+        if params
+            .neuron_basket_construction_parameters
+            .as_ref()
+            .is_some_and(|basket| basket.count > FOLLOW_ON_ROUND_MEMO_STRIDE)
+        {
+            return Err(format!(
+                "neuron_basket_construction_parameters.count must be at most {}.",
+                FOLLOW_ON_ROUND_MEMO_STRIDE
+            ));
+        }
+        // The round opens at the requested time, or right away if that time has
+        // already passed.
+        let open_timestamp_seconds = init
+            .swap_start_timestamp_seconds
+            .unwrap_or(now_seconds)
+            .max(now_seconds);
+        params.is_valid_if_initiated_at(open_timestamp_seconds)?;
+
+        if sns_token_balance_e8s < params.sns_token_e8s {
+            return Err(format!(
+                "The Swap canister holds {} SNS e8s, which is less than the {} SNS e8s \
+                 offered in the round.",
+                sns_token_balance_e8s, params.sns_token_e8s
+            ));
+        }
+
+        // All checks passed. Archive the current round and start the new one.
+        let summary = self.current_round_summary();
+        self.completed_rounds.push(summary);
+
+        self.buyers.clear();
+        self.cf_participants.clear();
+        self.neuron_recipes.clear();
+        clear_open_tickets_and_buyers_list_index();
+
+        self.init = Some(init);
+        self.params = Some(params);
+        self.follow_on_round = Some(FollowOnRound {
+            round_number: Some(round_number),
+            sns_proposal_id: request.sns_proposal_id,
+            sns_tokens_returned: None,
+        });
+        self.decentralization_sale_open_timestamp_seconds = Some(open_timestamp_seconds);
+        self.decentralization_swap_termination_timestamp_seconds = None;
+        self.purge_old_tickets_next_principal = Some(FIRST_PRINCIPAL_BYTES.to_vec());
+        self.already_tried_to_auto_finalize = Some(false);
+        self.auto_finalize_swap_response = None;
+        self.direct_participation_icp_e8s = Some(0);
+        self.neurons_fund_participation_icp_e8s = Some(0);
+        self.set_lifecycle(Lifecycle::Adopted);
+
+        log!(
+            INFO,
+            "Opened follow-on round {} (SNS proposal {:?}), offering {} SNS e8s from \
+             timestamp {} to {}.",
+            round_number,
+            request.sns_proposal_id,
+            self.sns_token_e8s().unwrap_or_default(),
+            open_timestamp_seconds,
+            self.init_or_panic()
+                .swap_due_timestamp_seconds
+                .unwrap_or_default(),
+        );
+
+        Ok(round_number)
+    }
+
+    /// Builds the `Init` of a follow-on round from the `Init` of the current
+    /// round and the parameters in `request`. The Neurons' Fund does not
+    /// participate in follow-on rounds.
+    fn follow_on_round_init(&self, request: &OpenFollowOnRoundRequest) -> Result<Init, String> {
+        let current_init = self.init()?;
+        let init = Init {
+            min_participants: request.min_participants,
+            min_direct_participation_icp_e8s: request.min_direct_participation_icp_e8s,
+            max_direct_participation_icp_e8s: request.max_direct_participation_icp_e8s,
+            min_participant_icp_e8s: request.min_participant_icp_e8s,
+            max_participant_icp_e8s: request.max_participant_icp_e8s,
+            swap_start_timestamp_seconds: request.swap_start_timestamp_seconds,
+            swap_due_timestamp_seconds: request.swap_due_timestamp_seconds,
+            sns_token_e8s: request.sns_token_e8s,
+            neuron_basket_construction_parameters: request.neuron_basket_construction_parameters,
+            transaction_fee_e8s: request
+                .transaction_fee_e8s
+                .or(current_init.transaction_fee_e8s),
+            neuron_minimum_stake_e8s: request
+                .neuron_minimum_stake_e8s
+                .or(current_init.neuron_minimum_stake_e8s),
+            neurons_fund_participation: Some(false),
+            neurons_fund_participation_constraints: None,
+            ..current_init.clone()
+        };
+        init.validate()?;
+        Ok(init)
+    }
+
+    /// Returns an error if some of the tokens of the current (terminal) round
+    /// have not reached their destination yet, i.e., if finalizing the round
+    /// has not completed successfully.
+    fn validate_current_round_is_settled(&self) -> Result<(), String> {
+        let unsettled_buyer_count = self
+            .buyers
+            .values()
+            .filter(|buyer_state| {
+                buyer_state
+                    .icp
+                    .as_ref()
+                    .is_none_or(|icp| icp.transfer_success_timestamp_seconds == 0)
+            })
+            .count();
+        if unsettled_buyer_count > 0 {
+            return Err(format!(
+                "The ICP of {} participants of the current round has not been swept yet. \
+                 Please finalize the current round first.",
+                unsettled_buyer_count
+            ));
+        }
+
+        match self.lifecycle() {
+            Lifecycle::Committed => {
+                let unsettled_recipe_count = self
+                    .neuron_recipes
+                    .iter()
+                    .filter(|recipe| {
+                        recipe.claimed_status != Some(ClaimedStatus::Success as i32)
+                            || recipe
+                                .sns
+                                .as_ref()
+                                .is_none_or(|sns| sns.transfer_success_timestamp_seconds == 0)
+                    })
+                    .count();
+                let buyers_without_recipes = self
+                    .buyers
+                    .values()
+                    .filter(|buyer_state| buyer_state.has_created_neuron_recipes != Some(true))
+                    .count();
+                if unsettled_recipe_count > 0 || buyers_without_recipes > 0 {
+                    return Err(format!(
+                        "The SNS neurons of the current round have not all been created yet \
+                         ({} unsettled neuron recipes, {} participants without neuron \
+                         recipes). Please finalize the current round first.",
+                        unsettled_recipe_count, buyers_without_recipes
+                    ));
+                }
+            }
+            Lifecycle::Aborted => {
+                let sns_tokens_returned = self
+                    .follow_on_round
+                    .as_ref()
+                    .and_then(|follow_on_round| follow_on_round.sns_tokens_returned.as_ref())
+                    .is_some_and(|sns| sns.transfer_success_timestamp_seconds > 0);
+                if !sns_tokens_returned {
+                    return Err(
+                        "The SNS tokens of the current round have not been returned to the \
+                         SNS treasury yet. Please finalize the current round first."
+                            .to_string(),
+                    );
+                }
+            }
+            lifecycle => {
+                return Err(format!(
+                    "The current round is not over (lifecycle {:?}).",
+                    lifecycle
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Summarizes the current round, for archival in `completed_rounds`.
+    fn current_round_summary(&self) -> SwapRoundSummary {
+        SwapRoundSummary {
+            round_number: Some(self.current_round_number()),
+            lifecycle: Some(self.lifecycle),
+            sns_token_e8s: self.sns_token_e8s().ok(),
+            direct_participation_icp_e8s: Some(self.current_direct_participation_e8s()),
+            neurons_fund_participation_icp_e8s: Some(self.current_neurons_fund_participation_e8s()),
+            direct_participant_count: Some(self.buyers.len() as u64),
+            open_timestamp_seconds: self.decentralization_sale_open_timestamp_seconds,
+            termination_timestamp_seconds: self.decentralization_swap_termination_timestamp_seconds,
+        }
+    }
+
     //
     // --- state modifying methods ---------------------------------------------
     //
@@ -1355,8 +1665,11 @@ impl Swap {
     /// Determines if the conditions have been met in order to
     /// restore the dapp canisters to the fallback controller ids.
     /// The lifecycle MUST be set to Aborted via the commit method.
+    ///
+    /// The dapp canisters are never handed back after a follow-on round, as the
+    /// SNS has already been decentralized by then.
     pub fn should_restore_dapp_control(&self) -> bool {
-        self.lifecycle() == Lifecycle::Aborted
+        self.lifecycle() == Lifecycle::Aborted && !self.is_follow_on_round()
     }
 
     /// Calls SNS Root's set_dapp_controllers with the Swap canister's configured
@@ -1579,6 +1892,16 @@ impl Swap {
             return finalize_swap_response;
         }
 
+        if self.lifecycle() == Lifecycle::Aborted && self.is_follow_on_round() {
+            // The SNS tokens offered in a follow-on round come from the SNS
+            // treasury, so they go back there if the round is aborted.
+            finalize_swap_response.set_sweep_sns_result(
+                self.return_sns_tokens_to_treasury(now_fn, environment.sns_ledger())
+                    .await,
+            );
+            return finalize_swap_response;
+        }
+
         if self.should_restore_dapp_control() {
             // Restore controllers of dapp canisters to their original
             // owners (i.e. self.init.fallback_controller_principal_ids).
@@ -1617,6 +1940,12 @@ impl Swap {
             return finalize_swap_response;
         }
 
+        // By the time of a follow-on round, SNS governance is already in normal
+        // mode, and SNS root is already the sole controller of the dapp canisters.
+        if self.is_follow_on_round() {
+            return finalize_swap_response;
+        }
+
         finalize_swap_response.set_set_mode_call_result(
             Self::set_sns_governance_to_normal_mode(environment.sns_governance_mut()).await,
         );
@@ -2312,6 +2641,105 @@ impl Swap {
         sweep_result
     }
 
+    /// In state ABORTED of a follow-on round. Returns the SNS tokens that were
+    /// offered in the round to the SNS treasury (a subaccount of SNS governance),
+    /// where they were taken from when the round was opened.
+    ///
+    /// Like `sweep_sns`, this is idempotent: the tokens are only transferred once.
+    pub async fn return_sns_tokens_to_treasury(
+        &mut self,
+        now_fn: fn(bool) -> u64,
+        sns_ledger: &dyn ICRC1Ledger,
+    ) -> SweepResult {
+        if self.lifecycle() != Lifecycle::Aborted || !self.is_follow_on_round() {
+            log!(
+                ERROR,
+                "Halting return_sns_tokens_to_treasury(). SNS tokens can only be returned \
+                to the treasury in an ABORTED follow-on round. Current Lifecycle: {:?}",
+                self.lifecycle()
+            );
+            return SweepResult::new_with_global_failures(1);
+        }
+
+        let (sns_governance, sns_transaction_fee_tokens) = match self.init_and_validate() {
+            Ok(init) => (
+                init.sns_governance_or_panic(),
+                Tokens::from_e8s(init.transaction_fee_e8s_or_panic()),
+            ),
+            Err(error_message) => {
+                log!(
+                    ERROR,
+                    "Halting return_sns_tokens_to_treasury(). State is missing or corrupted: {:?}",
+                    error_message
+                );
+                return SweepResult::new_with_global_failures(1);
+            }
+        };
+        let sns_token_e8s = match self.sns_token_e8s() {
+            Ok(sns_token_e8s) => sns_token_e8s,
+            Err(error_message) => {
+                log!(
+                    ERROR,
+                    "Halting return_sns_tokens_to_treasury(). {}",
+                    error_message
+                );
+                return SweepResult::new_with_global_failures(1);
+            }
+        };
+
+        // See ic_sns_init::distributions::FractionalDeveloperVotingPower.insert_treasury_accounts
+        let dst = Account {
+            owner: sns_governance.get().0,
+            subaccount: Some(compute_distribution_subaccount_bytes(
+                sns_governance.get(),
+                TREASURY_SUBACCOUNT_NONCE,
+            )),
+        };
+
+        let Some(follow_on_round) = self.follow_on_round.as_mut() else {
+            return SweepResult::new_with_global_failures(1);
+        };
+        let sns_transferable_amount =
+            follow_on_round
+                .sns_tokens_returned
+                .get_or_insert_with(|| TransferableAmount {
+                    amount_e8s: sns_token_e8s,
+                    ..Default::default()
+                });
+
+        let mut sweep_result = SweepResult::default();
+        let result = sns_transferable_amount
+            .transfer_helper(
+                now_fn,
+                sns_transaction_fee_tokens,
+                /* src_subaccount= */ None,
+                &dst,
+                sns_ledger,
+            )
+            .await;
+        match result {
+            TransferResult::AmountTooSmall => {
+                sweep_result.invalid += 1;
+            }
+            TransferResult::AlreadyStarted => {
+                sweep_result.skipped += 1;
+            }
+            TransferResult::Success(_) => {
+                let fee_e8s = sns_transaction_fee_tokens.get_e8s();
+                sns_transferable_amount.transfer_fee_paid_e8s = Some(fee_e8s);
+                sns_transferable_amount.amount_transferred_e8s =
+                    Some(sns_transferable_amount.amount_e8s - fee_e8s);
+
+                sweep_result.success += 1;
+            }
+            TransferResult::Failure(_) => {
+                sweep_result.failure += 1;
+            }
+        }
+
+        sweep_result
+    }
+
     /// Requests the NNS Governance canister to settle the Neurons' Fund
     /// participation in the Swap. If the Swap is committed, ICP will be
     /// minted to the Swap canister's ICP account, and the returned NfParticipants
@@ -3682,6 +4110,28 @@ impl GetOpenTicketResponse {
     }
 }
 
+impl OpenFollowOnRoundResponse {
+    pub fn ok(round_number: u64) -> Self {
+        Self {
+            result: Some(open_follow_on_round_response::Result::Ok(
+                open_follow_on_round_response::Ok {
+                    round_number: Some(round_number),
+                },
+            )),
+        }
+    }
+
+    pub fn err(description: String) -> Self {
+        Self {
+            result: Some(open_follow_on_round_response::Result::Err(
+                open_follow_on_round_response::Err {
+                    description: Some(description),
+                },
+            )),
+        }
+    }
+}
+
 impl NewSaleTicketResponse {
     pub fn ok(ticket: Ticket) -> Self {
         Self {
@@ -3769,6 +4219,26 @@ fn insert_buyer_into_buyers_list_index(buyer_principal_id: PrincipalId) -> Resul
         .with(|buyer_list| buyer_list.borrow_mut().push(&buyer_principal_id.into()))
 }
 
+/// Removes all open tickets and empties the buyers list index, so that the next
+/// round starts from a clean slate.
+fn clear_open_tickets_and_buyers_list_index() {
+    memory::OPEN_TICKETS_MEMORY.with(|tickets| {
+        let principals = tickets
+            .borrow()
+            .iter()
+            .map(|(principal, _ticket)| principal)
+            .collect::<Vec<_>>();
+        let mut tickets = tickets.borrow_mut();
+        for principal in principals {
+            tickets.remove(&principal);
+        }
+    });
+    memory::BUYERS_LIST_INDEX.with(|buyer_list| {
+        let buyer_list = buyer_list.borrow_mut();
+        while buyer_list.pop().is_some() {}
+    });
+}
+
 /// A version of Swap that implements a shorter version of Debug, suitable for
 /// logs. Potentially large collection fields are summarized and/or decimated.
 struct SwapDigest<'a> {
@@ -3797,6 +4267,7 @@ impl fmt::Debug for SwapDigest<'_> {
             already_tried_to_auto_finalize,
             auto_finalize_swap_response,
             timers,
+            follow_on_round,
 
             // These are (potentially large) collections. To avoid an
             // overwhelmingly large log message, we need summarize and/or
@@ -3806,6 +4277,7 @@ impl fmt::Debug for SwapDigest<'_> {
             neuron_recipes,
             direct_participation_icp_e8s,
             neurons_fund_participation_icp_e8s,
+            completed_rounds,
         } = self.swap;
 
         formatter
@@ -3856,6 +4328,11 @@ impl fmt::Debug for SwapDigest<'_> {
                 neurons_fund_participation_icp_e8s,
             )
             .field("timers", timers)
+            .field("follow_on_round", follow_on_round)
+            .field(
+                "completed_rounds",
+                &format!("<len={}>", completed_rounds.len()),
+            )
             .finish()
     }
 }
@@ -5003,4 +5480,289 @@ mod tests {
 
         assert_eq!(7, swap.cf_neuron_count());
     }
+
+    const FOLLOW_ON_ROUND_START_TIMESTAMP_SECONDS: u64 = 10_000_000;
+
+    fn settled_buyer_state(amount_icp_e8s: u64) -> BuyerState {
+        BuyerState {
+            icp: Some(TransferableAmount {
+                amount_e8s: amount_icp_e8s,
+                transfer_start_timestamp_seconds: 1,
+                transfer_success_timestamp_seconds: 2,
+                amount_transferred_e8s: Some(amount_icp_e8s),
+                transfer_fee_paid_e8s: Some(0),
+            }),
+            has_created_neuron_recipes: Some(true),
+        }
+    }
+
+    fn settled_neuron_recipe(buyer_principal: PrincipalId) -> SnsNeuronRecipe {
+        SnsNeuronRecipe {
+            sns: Some(TransferableAmount {
+                amount_e8s: 100,
+                transfer_start_timestamp_seconds: 1,
+                transfer_success_timestamp_seconds: 2,
+                ..Default::default()
+            }),
+            investor: Some(Investor::Direct(DirectInvestment {
+                buyer_principal: buyer_principal.to_string(),
+            })),
+            claimed_status: Some(ClaimedStatus::Success as i32),
+            ..Default::default()
+        }
+    }
+
+    /// A swap whose initial round was committed and fully finalized.
+    fn finalized_committed_swap() -> Swap {
+        let buyer_principal = PrincipalId::new_user_test_id(1000);
+        let mut swap = SwapBuilder::new()
+            .with_lifecycle(Lifecycle::Committed)
+            .with_buyers(btreemap! {
+                buyer_principal.to_string() => settled_buyer_state(15),
+            })
+            .with_neuron_recipes(vec![
+                settled_neuron_recipe(buyer_principal),
+                settled_neuron_recipe(buyer_principal),
+            ])
+            .build();
+        swap.decentralization_swap_termination_timestamp_seconds = Some(1234567);
+        swap.already_tried_to_auto_finalize = Some(true);
+        swap.update_derived_fields();
+        swap
+    }
+
+    fn follow_on_round_request() -> OpenFollowOnRoundRequest {
+        OpenFollowOnRoundRequest {
+            sns_proposal_id: Some(42),
+            sns_token_e8s: Some(500),
+            min_direct_participation_icp_e8s: Some(20),
+            max_direct_participation_icp_e8s: Some(200),
+            min_participant_icp_e8s: Some(20),
+            max_participant_icp_e8s: Some(50),
+            min_participants: Some(1),
+            swap_start_timestamp_seconds: Some(FOLLOW_ON_ROUND_START_TIMESTAMP_SECONDS),
+            swap_due_timestamp_seconds: Some(
+                FOLLOW_ON_ROUND_START_TIMESTAMP_SECONDS + 7 * ONE_DAY_SECONDS,
+            ),
+            neuron_basket_construction_parameters: Some(NeuronBasketConstructionParameters {
+                count: 3,
+                dissolve_delay_interval_seconds: 1000,
+            }),
+            transaction_fee_e8s: Some(0),
+            neuron_minimum_stake_e8s: Some(0),
+        }
+    }
+
+    #[test]
+    fn test_open_follow_on_round() {
+        let mut swap = finalized_committed_swap();
+
+        let result = swap.open_follow_on_round(
+            &follow_on_round_request(),
+            FOLLOW_ON_ROUND_START_TIMESTAMP_SECONDS - 60,
+            500,
+        );
+
+        assert_eq!(result, Ok(1));
+        assert_eq!(swap.lifecycle(), Lifecycle::Adopted);
+        assert_eq!(swap.current_round_number(), 1);
+        assert_eq!(
+            swap.follow_on_round,
+            Some(FollowOnRound {
+                round_number: Some(1),
+                sns_proposal_id: Some(42),
+                sns_tokens_returned: None,
+            })
+        );
+        assert_eq!(
+            swap.completed_rounds,
+            vec![SwapRoundSummary {
+                round_number: Some(0),
+                lifecycle: Some(Lifecycle::Committed as i32),
+                sns_token_e8s: Some(1000),
+                direct_participation_icp_e8s: Some(15),
+                neurons_fund_participation_icp_e8s: Some(0),
+                direct_participant_count: Some(1),
+                open_timestamp_seconds: None,
+                termination_timestamp_seconds: Some(1234567),
+            }]
+        );
+
+        // The state of the previous round has been cleared.
+        assert!(swap.buyers.is_empty());
+        assert!(swap.neuron_recipes.is_empty());
+        assert_eq!(swap.current_direct_participation_e8s(), 0);
+        assert_eq!(swap.already_tried_to_auto_finalize, Some(false));
+        assert_eq!(
+            swap.decentralization_swap_termination_timestamp_seconds,
+            None
+        );
+        assert!(swap.requires_periodic_tasks());
+
+        // The new round uses the requested parameters, without the Neurons' Fund.
+        let init = swap.init_or_panic();
+        assert_eq!(init.sns_token_e8s, Some(500));
+        assert_eq!(init.max_direct_participation_icp_e8s, Some(200));
+        assert_eq!(init.neurons_fund_participation, Some(false));
+        assert_eq!(init.nns_proposal_id, Some(101));
+        assert_eq!(swap.sns_token_e8s(), Ok(500));
+        assert_eq!(swap.max_direct_participation_e8s(), 200);
+        assert_eq!(
+            swap.decentralization_sale_open_timestamp_seconds,
+            Some(FOLLOW_ON_ROUND_START_TIMESTAMP_SECONDS)
+        );
+
+        // The round opens at the requested time.
+        assert!(!swap.try_open(FOLLOW_ON_ROUND_START_TIMESTAMP_SECONDS - 1));
+        assert!(swap.try_open(FOLLOW_ON_ROUND_START_TIMESTAMP_SECONDS));
+        assert_eq!(swap.lifecycle(), Lifecycle::Open);
+    }
+
+    #[test]
+    fn test_open_follow_on_round_rejects_swap_that_is_not_over() {
+        for lifecycle in [Lifecycle::Adopted, Lifecycle::Open] {
+            let mut swap = SwapBuilder::new().with_lifecycle(lifecycle).build();
+            let swap_before = swap.clone();
+
+            let result = swap.open_follow_on_round(
+                &follow_on_round_request(),
+                FOLLOW_ON_ROUND_START_TIMESTAMP_SECONDS,
+                500,
+            );
+
+            assert!(result.unwrap_err().contains("COMMITTED or ABORTED"));
+            assert_eq!(swap, swap_before);
+        }
+    }
+
+    #[test]
+    fn test_open_follow_on_round_rejects_aborted_decentralization_swap() {
+        let mut swap = SwapBuilder::new()
+            .with_lifecycle(Lifecycle::Aborted)
+            .build();
+
+        let result = swap.open_follow_on_round(
+            &follow_on_round_request(),
+            FOLLOW_ON_ROUND_START_TIMESTAMP_SECONDS,
+            500,
+        );
+
+        assert!(result
+            .unwrap_err()
+            .contains("decentralization swap was aborted"));
+        assert_eq!(swap.lifecycle(), Lifecycle::Aborted);
+    }
+
+    #[test]
+    fn test_open_follow_on_round_rejects_unsettled_round() {
+        let mut swap = finalized_committed_swap();
+        swap.neuron_recipes[0].claimed_status = Some(ClaimedStatus::Failed as i32);
+        let swap_before = swap.clone();
+
+        let result = swap.open_follow_on_round(
+            &follow_on_round_request(),
+            FOLLOW_ON_ROUND_START_TIMESTAMP_SECONDS,
+            500,
+        );
+
+        assert!(result.unwrap_err().contains("1 unsettled neuron recipes"));
+        assert_eq!(swap, swap_before);
+    }
+
+    #[test]
+    fn test_open_follow_on_round_rejects_insufficient_sns_balance() {
+        let mut swap = finalized_committed_swap();
+        let swap_before = swap.clone();
+
+        let result = swap.open_follow_on_round(
+            &follow_on_round_request(),
+            FOLLOW_ON_ROUND_START_TIMESTAMP_SECONDS,
+            499,
+        );
+
+        assert!(result.unwrap_err().contains("less than the 500 SNS e8s"));
+        assert_eq!(swap, swap_before);
+    }
+
+    #[test]
+    fn test_open_follow_on_round_rejects_invalid_parameters() {
+        let mut swap = finalized_committed_swap();
+        let swap_before = swap.clone();
+
+        // Too short.
+        let request = OpenFollowOnRoundRequest {
+            swap_due_timestamp_seconds: Some(FOLLOW_ON_ROUND_START_TIMESTAMP_SECONDS + 60),
+            ..follow_on_round_request()
+        };
+        let result =
+            swap.open_follow_on_round(&request, FOLLOW_ON_ROUND_START_TIMESTAMP_SECONDS, 500);
+        assert!(result.unwrap_err().contains("MIN_SALE_DURATION_SECONDS"));
+
+        // Too many neurons per basket.
+        let request = OpenFollowOnRoundRequest {
+            neuron_basket_construction_parameters: Some(NeuronBasketConstructionParameters {
+                count: FOLLOW_ON_ROUND_MEMO_STRIDE + 1,
+                dissolve_delay_interval_seconds: 1,
+            }),
+            ..follow_on_round_request()
+        };
+        let result =
+            swap.open_follow_on_round(&request, FOLLOW_ON_ROUND_START_TIMESTAMP_SECONDS, 500);
+        assert!(result
+            .unwrap_err()
+            .contains("neuron_basket_construction_parameters.count"));
+
+        assert_eq!(swap, swap_before);
+    }
+
+    #[test]
+    fn test_follow_on_round_neuron_memos_do_not_collide_with_previous_rounds() {
+        let buyer_principal = PrincipalId::new_user_test_id(1000);
+        let mut swap = SwapBuilder::new()
+            .with_lifecycle(Lifecycle::Committed)
+            .with_buyers(btreemap! {
+                buyer_principal.to_string() => BuyerState::new(15),
+            })
+            .with_follow_on_round(FollowOnRound {
+                round_number: Some(2),
+                sns_proposal_id: Some(42),
+                sns_tokens_returned: None,
+            })
+            .build();
+        swap.update_derived_fields();
+
+        let sweep_result = swap.create_sns_neuron_recipes();
+
+        assert_eq!(sweep_result.success, 2);
+        let memos = swap
+            .neuron_recipes
+            .iter()
+            .map(|recipe| recipe.neuron_attributes.as_ref().unwrap().memo)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            memos,
+            vec![
+                NEURON_BASKET_MEMO_RANGE_START + 2 * FOLLOW_ON_ROUND_MEMO_STRIDE,
+                NEURON_BASKET_MEMO_RANGE_START + 2 * FOLLOW_ON_ROUND_MEMO_STRIDE + 1,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_aborted_follow_on_round_does_not_restore_dapp_control() {
+        let swap = SwapBuilder::new()
+            .with_lifecycle(Lifecycle::Aborted)
+            .with_follow_on_round(FollowOnRound {
+                round_number: Some(1),
+                sns_proposal_id: Some(42),
+                sns_tokens_returned: None,
+            })
+            .build();
+        assert!(!swap.should_restore_dapp_control());
+
+        let swap = SwapBuilder::new()
+            .with_lifecycle(Lifecycle::Aborted)
+            .build();
+        assert!(swap.should_restore_dapp_control());
+    }
 }
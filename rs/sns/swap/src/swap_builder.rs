@@ -1,8 +1,8 @@
 use self::pb::v1::{Init, Lifecycle, Swap};
 use super::*;
 use crate::pb::v1::{
-    BuyerState, NeuronBasketConstructionParameters, NeuronsFundParticipationConstraints,
-    SnsNeuronRecipe,
+    BuyerState, FollowOnRound, NeuronBasketConstructionParameters,
+    NeuronsFundParticipationConstraints, SnsNeuronRecipe,
 };
 use ic_base_types::{CanisterId, PrincipalId};
 use ic_nervous_system_proto::pb::v1::Countries;
@@ -38,6 +38,7 @@ pub struct SwapBuilder {
     neurons_fund_participation_constraints: Option<NeuronsFundParticipationConstraints>,
     neurons_fund_participation: Option<bool>,
     neuron_recipes: Vec<SnsNeuronRecipe>,
+    follow_on_round: Option<FollowOnRound>,
     // The following fields are deprecated and thus don't need to be represented here.
     // min_icp_e8s,
     // max_icp_e8s,
@@ -79,6 +80,7 @@ impl Default for SwapBuilder {
             neurons_fund_participation_constraints: None,
             neurons_fund_participation: None,
             neuron_recipes: vec![],
+            follow_on_round: None,
         }
     }
 }
@@ -211,6 +213,11 @@ impl SwapBuilder {
         self
     }
 
+    pub fn with_follow_on_round(mut self, follow_on_round: FollowOnRound) -> Self {
+        self.follow_on_round = Some(follow_on_round);
+        self
+    }
+
     pub fn build(self) -> Swap {
         let init = Init {
             nns_governance_canister_id: self.nns_governance_canister_id.to_string(),
@@ -252,6 +259,7 @@ impl SwapBuilder {
             lifecycle: self.lifecycle as i32,
             buyers: self.buyers,
             neuron_recipes: self.neuron_recipes,
+            follow_on_round: self.follow_on_round,
             ..swap
         }
     }
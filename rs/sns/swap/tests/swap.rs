@@ -22,7 +22,8 @@ use futures::{channel::mpsc, future::FutureExt, StreamExt};
 use ic_base_types::{CanisterId, PrincipalId};
 use ic_ledger_core::Tokens;
 use ic_nervous_system_common::{
-    assert_is_err, assert_is_ok, ledger::compute_neuron_staking_subaccount_bytes,
+    assert_is_err, assert_is_ok,
+    ledger::{compute_distribution_subaccount_bytes, compute_neuron_staking_subaccount_bytes},
     NervousSystemError, E8, ONE_DAY_SECONDS, ONE_MONTH_SECONDS, START_OF_2022_TIMESTAMP_SECONDS,
 };
 use ic_nervous_system_common_test_keys::{
@@ -37,11 +38,14 @@ use ic_neurons_fund::{
     InvertibleFunction, MatchingFunction, NeuronsFundParticipationLimits,
     PolynomialMatchingFunction, SerializableFunction,
 };
-use ic_sns_governance::pb::v1::{
-    claim_swap_neurons_request::{neuron_recipe, NeuronRecipe, NeuronRecipes},
-    claim_swap_neurons_response::ClaimSwapNeuronsResult,
-    governance, ClaimSwapNeuronsRequest, ClaimSwapNeuronsResponse, NeuronId, NeuronIds, SetMode,
-    SetModeResponse,
+use ic_sns_governance::{
+    governance::TREASURY_SUBACCOUNT_NONCE,
+    pb::v1::{
+        claim_swap_neurons_request::{neuron_recipe, NeuronRecipe, NeuronRecipes},
+        claim_swap_neurons_response::ClaimSwapNeuronsResult,
+        governance, ClaimSwapNeuronsRequest, ClaimSwapNeuronsResponse, NeuronId, NeuronIds,
+        SetMode, SetModeResponse,
+    },
 };
 use ic_sns_swap::{
     environment::CanisterClients,
@@ -218,6 +222,8 @@ fn create_generic_committed_swap() -> Swap {
         direct_participation_icp_e8s: Some(50 * E8),
         neurons_fund_participation_icp_e8s: None,
         timers: None,
+        follow_on_round: None,
+        completed_rounds: vec![],
     }
 }
 
@@ -6003,3 +6009,81 @@ async fn test_finalize_swap_abort_sets_amount_transferred_and_fees_correctly() {
         },
     );
 }
+
+/// Assert that finalizing an aborted follow-on round refunds the direct participants and
+/// returns the SNS tokens offered in the round to the SNS treasury, without touching the
+/// controllers of the dapp canisters.
+#[tokio::test]
+async fn test_finalize_aborted_follow_on_round_returns_sns_tokens_to_treasury() {
+    let buyer = PrincipalId::new_user_test_id(1);
+    let mut swap = Swap {
+        lifecycle: Aborted as i32,
+        init: Some(init_without_neurons_fund_funding()),
+        params: Some(params()),
+        buyers: btreemap! {
+            Principal::from(buyer).to_text() => BuyerState::new(50 * E8),
+        },
+        follow_on_round: Some(FollowOnRound {
+            round_number: Some(1),
+            sns_proposal_id: Some(42),
+            sns_tokens_returned: None,
+        }),
+        ..Default::default()
+    };
+
+    let mut clients = CanisterClients {
+        icp_ledger: SpyLedger::new(vec![LedgerReply::TransferFunds(Ok(1000))]),
+        sns_ledger: SpyLedger::new(vec![LedgerReply::TransferFunds(Ok(1001))]),
+        ..spy_clients()
+    };
+
+    let response = swap.finalize(now_fn, &mut clients).await;
+
+    assert_eq!(response.error_message, None);
+    assert_eq!(
+        response.sweep_icp_result,
+        Some(SweepResult {
+            success: 1,
+            ..Default::default()
+        })
+    );
+    assert_eq!(
+        response.sweep_sns_result,
+        Some(SweepResult {
+            success: 1,
+            ..Default::default()
+        })
+    );
+    assert_eq!(response.set_dapp_controllers_call_result, None);
+    assert!(clients.sns_root.observed_calls.is_empty());
+
+    let sns_transaction_fee_e8s = init().transaction_fee_e8s();
+    let treasury = Account {
+        owner: SNS_GOVERNANCE_CANISTER_ID.get().0,
+        subaccount: Some(compute_distribution_subaccount_bytes(
+            SNS_GOVERNANCE_CANISTER_ID.get(),
+            TREASURY_SUBACCOUNT_NONCE,
+        )),
+    };
+    assert_eq!(
+        clients.sns_ledger.get_calls_snapshot(),
+        vec![LedgerCall::TransferFundsICRC1 {
+            amount_e8s: params().sns_token_e8s - sns_transaction_fee_e8s,
+            fee_e8s: sns_transaction_fee_e8s,
+            from_subaccount: None,
+            to: treasury,
+            memo: 0,
+        }]
+    );
+
+    // Finalizing again must not return the SNS tokens a second time.
+    let response = swap.finalize(now_fn, &mut clients).await;
+    assert_eq!(
+        response.sweep_sns_result,
+        Some(SweepResult {
+            skipped: 1,
+            ..Default::default()
+        })
+    );
+    assert_eq!(clients.sns_ledger.get_calls_snapshot().len(), 1);
+}
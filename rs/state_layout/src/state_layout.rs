@@ -20,7 +20,8 @@ use ic_replicated_state::{
         },
     },
     page_map::{Shard, StorageLayout, StorageResult},
-    CallContextManager, CanisterStatus, ExportedFunctions, NumWasmPages,
+    CallContextManager, CanisterStatus, CyclesBurnSample, CyclesBurnWindow, ExportedFunctions,
+    NumWasmPages,
 };
 use ic_sys::{fs::sync_path, mmap::ScopedMmap};
 use ic_types::{
@@ -154,11 +155,14 @@ pub struct CanisterStateBits {
     pub execution_state_bits: Option<ExecutionStateBits>,
     pub memory_allocation: MemoryAllocation,
     pub wasm_memory_threshold: NumBytes,
+    pub cycles_threshold: Cycles,
     pub freeze_threshold: NumSeconds,
     pub cycles_balance: Cycles,
     pub cycles_debit: Cycles,
     pub reserved_balance: Cycles,
     pub reserved_balance_limit: Option<Cycles>,
+    pub cycles_burn_limit: Option<Cycles>,
+    pub cycles_burn_window: CyclesBurnWindow,
     pub status: CanisterStatus,
     pub scheduled_as_first: u64,
     pub skipped_round_due_to_no_messages: u64,
@@ -2706,6 +2710,17 @@ impl From<CanisterStateBits> for pb_canister_state_bits::CanisterStateBits {
             snapshots_memory_usage: item.snapshots_memory_usage.get(),
             tasks: Some((&item.task_queue).into()),
             environment_variables: item.environment_variables.into_iter().collect(),
+            cycles_threshold: Some(item.cycles_threshold.into()),
+            cycles_burn_limit: item.cycles_burn_limit.map(|v| v.into()),
+            cycles_burn_samples: item
+                .cycles_burn_window
+                .samples
+                .iter()
+                .map(|sample| pb_canister_state_bits::CyclesBurnSample {
+                    time_nanos: sample.time.as_nanos_since_unix_epoch(),
+                    consumed_cycles: Some((&sample.consumed_cycles).into()),
+                })
+                .collect(),
         }
     }
 }
@@ -2760,6 +2775,18 @@ impl TryFrom<pb_canister_state_bits::CanisterStateBits> for CanisterStateBits {
             );
         }
 
+        let mut cycles_burn_window = CyclesBurnWindow::default();
+        for sample in value.cycles_burn_samples.into_iter() {
+            cycles_burn_window.samples.push_back(CyclesBurnSample {
+                time: Time::from_nanos_since_unix_epoch(sample.time_nanos),
+                consumed_cycles: try_from_option_field(
+                    sample.consumed_cycles,
+                    "CyclesBurnSample::consumed_cycles",
+                )
+                .unwrap_or_default(),
+            });
+        }
+
         let tasks: pb_canister_state_bits::TaskQueue =
             try_from_option_field(value.tasks, "CanisterStateBits::tasks").unwrap_or_default();
 
@@ -2789,11 +2816,17 @@ impl TryFrom<pb_canister_state_bits::CanisterStateBits> for CanisterStateBits {
                     err: format!("{:?}", e),
                 })?,
             wasm_memory_threshold: NumBytes::new(value.wasm_memory_threshold.unwrap_or(0)),
+            cycles_threshold: value
+                .cycles_threshold
+                .map(|v| v.into())
+                .unwrap_or_else(Cycles::zero),
             freeze_threshold: NumSeconds::from(value.freeze_threshold),
             cycles_balance,
             cycles_debit,
             reserved_balance,
             reserved_balance_limit: value.reserved_balance_limit.map(|v| v.into()),
+            cycles_burn_limit: value.cycles_burn_limit.map(|v| v.into()),
+            cycles_burn_window,
             status: try_from_option_field(
                 value.canister_status,
                 "CanisterStateBits::canister_status",
@@ -32,11 +32,14 @@ fn default_canister_state_bits() -> CanisterStateBits {
         execution_state_bits: None,
         memory_allocation: MemoryAllocation::default(),
         wasm_memory_threshold: NumBytes::new(0),
+        cycles_threshold: Cycles::zero(),
         freeze_threshold: NumSeconds::from(0),
         cycles_balance: Cycles::zero(),
         cycles_debit: Cycles::zero(),
         reserved_balance: Cycles::zero(),
         reserved_balance_limit: None,
+        cycles_burn_limit: None,
+        cycles_burn_window: CyclesBurnWindow::default(),
         status: CanisterStatus::Stopped,
         scheduled_as_first: 0,
         skipped_round_due_to_no_messages: 0,
@@ -290,6 +293,34 @@ fn test_encode_decode_non_empty_environment_variables() {
     );
 }
 
+#[test]
+fn test_encode_decode_cycles_burn_limit_and_window() {
+    let cycles_burn_window = CyclesBurnWindow {
+        samples: [(1_000_000, 42), (2_000_000, 50)]
+            .into_iter()
+            .map(|(time, consumed_cycles)| CyclesBurnSample {
+                time: Time::from_nanos_since_unix_epoch(time),
+                consumed_cycles: NominalCycles::from(consumed_cycles),
+            })
+            .collect(),
+    };
+    let canister_state_bits = CanisterStateBits {
+        cycles_burn_limit: Some(Cycles::new(1_000_000)),
+        cycles_burn_window,
+        ..default_canister_state_bits()
+    };
+    let pb_bits = pb_canister_state_bits::CanisterStateBits::from(canister_state_bits);
+    let decoded_canister_state_bits = CanisterStateBits::try_from(pb_bits).unwrap();
+    assert_eq!(
+        decoded_canister_state_bits.cycles_burn_limit,
+        Some(Cycles::new(1_000_000))
+    );
+    assert_eq!(
+        decoded_canister_state_bits.cycles_burn_window,
+        cycles_burn_window
+    );
+}
+
 #[test]
 fn test_encode_decode_task_queue() {
     let ingress = Arc::new(IngressBuilder::new().method_name("test_ingress").build());
@@ -107,6 +107,7 @@ use ic_replicated_state::{
     canister_state::{system_state::CyclesUseCase, NumWasmPages, WASM_PAGE_SIZE_IN_BYTES},
     metadata_state::subnet_call_context_manager::{SignWithThresholdContext, ThresholdArguments},
     page_map::Buffer,
+    testing::ReplicatedStateTesting,
     CheckpointLoadingMetrics, Memory, PageMap, ReplicatedState,
 };
 use ic_state_layout::{CheckpointLayout, ReadOnly};
@@ -143,13 +144,14 @@ use ic_types::{
     },
     malicious_flags::MaliciousFlags,
     messages::{
-        extract_effective_canister_id, Blob, Certificate, CertificateDelegation, HttpCallContent,
-        HttpCanisterUpdate, HttpRequestContent, HttpRequestEnvelope, Payload as MsgPayload, Query,
-        QuerySource, RejectContext, SignedIngress, EXPECTED_MESSAGE_ID_LENGTH,
+        extract_effective_canister_id, Blob, CanisterMessage, Certificate, CertificateDelegation,
+        HttpCallContent, HttpCanisterUpdate, HttpRequestContent, HttpRequestEnvelope,
+        Payload as MsgPayload, Query, QuerySource, RejectContext, RequestOrResponse, SignedIngress,
+        EXPECTED_MESSAGE_ID_LENGTH,
     },
     signature::ThresholdSignature,
     time::GENESIS,
-    xnet::{CertifiedStreamSlice, StreamIndex},
+    xnet::{CertifiedStreamSlice, RejectReason, StreamIndex},
     CanisterLog, CountBytes, CryptoHashOfPartialState, Height, NodeId, Randomness, RegistryVersion,
     ReplicaVersion, SnapshotId,
 };
@@ -184,7 +186,10 @@ use std::{
     path::{Path, PathBuf},
     str::FromStr,
     string::ToString,
-    sync::{atomic::AtomicU64, Arc, Mutex, RwLock},
+    sync::{
+        atomic::{AtomicBool, AtomicU64},
+        Arc, Mutex, RwLock,
+    },
     time::{Duration, Instant, SystemTime},
 };
 use tempfile::TempDir;
@@ -706,6 +711,259 @@ impl PocketXNetImpl {
     }
 }
 
+/// A fault injected into the XNet stream from a remote subnet into a `StateMachine`.
+///
+/// Faults are applied by the receiving `StateMachine` whenever it executes a block,
+/// see `StateMachine::inject_xnet_fault`. Messages that are not affected by a fault are
+/// inducted by message routing as usual.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum XNetFault {
+    /// Nothing from the stream is inducted until the fault is cleared. This includes the
+    /// signals in the stream header, so the reverse stream is not garbage collected either.
+    Pause,
+    /// Nothing from the stream is inducted before the given time of the receiving subnet.
+    /// The fault clears itself once that time is reached.
+    DelayUntil(Time),
+    /// Best-effort requests and responses are dropped instead of being inducted,
+    /// so the respective callbacks on the sending subnet eventually time out.
+    DropBestEffortMessages,
+    /// Requests are rejected with reject signals with the given reason instead of
+    /// being inducted. Responses are inducted as usual.
+    RejectRequests(RejectReason),
+}
+
+/// Whether a message recorded by the call-graph recorder is a request or a response.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum RecordedMessageKind {
+    Request,
+    Response,
+}
+
+/// An inter-canister message recorded by the call-graph recorder,
+/// see `StateMachine::set_call_graph_recording`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RecordedMessage {
+    /// Height of the block after which the message was first observed.
+    pub height: u64,
+    pub kind: RecordedMessageKind,
+    /// The canister that made the call.
+    pub caller: PrincipalId,
+    /// The canister that was called.
+    pub callee: PrincipalId,
+    /// The called method. Unknown for responses to requests that were sent or
+    /// received before the recording was enabled.
+    pub method_name: Option<String>,
+    pub callback_id: u64,
+    pub payload_size_bytes: u64,
+    /// Cycles attached to a request or refunded with a response.
+    pub cycles: u128,
+    /// The reject code of a reject response.
+    pub reject_code: Option<u64>,
+    /// The deadline of a best-effort message, in seconds since the Unix epoch.
+    pub deadline: Option<u32>,
+}
+
+/// Identifies a message by its kind, the canister that made the call and the
+/// callback ID, which is unique among the calls made by that canister.
+type MessageKey = (RecordedMessageKind, PrincipalId, u64);
+
+fn message_key(msg: &RequestOrResponse) -> MessageKey {
+    match msg {
+        RequestOrResponse::Request(request) => (
+            RecordedMessageKind::Request,
+            request.sender.get(),
+            request.sender_reply_callback.get(),
+        ),
+        RequestOrResponse::Response(response) => (
+            RecordedMessageKind::Response,
+            response.originator.get(),
+            response.originator_reply_callback.get(),
+        ),
+    }
+}
+
+/// Records the messages sent by the canisters of a `StateMachine` to other canisters
+/// by comparing the messages in the canister queues and streams after every round
+/// with those after the previous round.
+///
+/// Messages that are both sent and consumed within the same round, e.g., a call
+/// between two canisters on the same subnet that completes within one round, are
+/// never observed and therefore not recorded. Setting
+/// `SchedulerConfig::max_heap_delta_per_iteration` to zero routes all such messages
+/// through the loopback stream, so that every one of them is recorded.
+#[derive(Default)]
+struct CallGraphRecorder {
+    messages: Vec<RecordedMessage>,
+    /// The messages in the canister queues and streams after the previous round.
+    pending: BTreeSet<MessageKey>,
+    /// Height of the block that is currently being executed.
+    height: Height,
+    /// The methods called by the requests sent or received by this subnet, by caller
+    /// and callback ID, to look up the method of the respective response.
+    method_names: BTreeMap<(PrincipalId, u64), String>,
+}
+
+impl CallGraphRecorder {
+    /// Creates a recorder that only records messages sent after `state`.
+    fn new(state: &ReplicatedState) -> Self {
+        Self {
+            pending: Self::pending_messages(state).into_keys().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Prepares for recording the messages of the block at `height`, given the
+    /// messages that the block inducts from other subnets.
+    fn start_block<'a>(
+        &mut self,
+        height: Height,
+        incoming_messages: impl Iterator<Item = &'a RequestOrResponse>,
+    ) {
+        self.height = height;
+        for msg in incoming_messages {
+            if let RequestOrResponse::Request(request) = msg {
+                self.method_names.insert(
+                    (request.sender.get(), request.sender_reply_callback.get()),
+                    request.method_name.clone(),
+                );
+            }
+        }
+    }
+
+    /// Records the messages sent by the canisters of this subnet that have shown
+    /// up in a canister queue or stream since the previous round.
+    fn record_round(&mut self, state: &ReplicatedState) {
+        let pending = Self::pending_messages(state);
+        for (key, msg) in pending.iter() {
+            if !self.pending.contains(key) {
+                self.record_message(msg);
+            }
+        }
+        self.pending = pending.into_keys().collect();
+    }
+
+    /// Returns the messages sent by the canisters of this subnet that are in an
+    /// output queue, a stream or, unless sent to another subnet, an input queue.
+    fn pending_messages(state: &ReplicatedState) -> BTreeMap<MessageKey, RequestOrResponse> {
+        let mut messages = BTreeMap::new();
+        for (_, stream) in state.metadata.streams().iter() {
+            for (_, msg) in stream.messages().iter() {
+                messages.insert(message_key(msg), msg.clone());
+            }
+        }
+        for canister in state.canisters_iter() {
+            // Popping is the only way to iterate over the queues, so do it on a copy.
+            let mut canister = canister.clone();
+            for msg in canister.output_into_iter() {
+                messages.insert(message_key(&msg), msg);
+            }
+            while let Some(msg) = canister.pop_input() {
+                let msg = match msg {
+                    CanisterMessage::Request(request) => RequestOrResponse::Request(request),
+                    CanisterMessage::Response(response) => RequestOrResponse::Response(response),
+                    CanisterMessage::Ingress(_) => continue,
+                };
+                if state.canister_state(&msg.sender()).is_some() {
+                    messages.insert(message_key(&msg), msg);
+                }
+            }
+        }
+        messages
+    }
+
+    fn record_message(&mut self, msg: &RequestOrResponse) {
+        let message = match msg {
+            RequestOrResponse::Request(request) => {
+                self.method_names.insert(
+                    (request.sender.get(), request.sender_reply_callback.get()),
+                    request.method_name.clone(),
+                );
+                RecordedMessage {
+                    height: self.height.get(),
+                    kind: RecordedMessageKind::Request,
+                    caller: request.sender.get(),
+                    callee: request.receiver.get(),
+                    method_name: Some(request.method_name.clone()),
+                    callback_id: request.sender_reply_callback.get(),
+                    payload_size_bytes: request.payload_size_bytes().get(),
+                    cycles: request.payment.get(),
+                    reject_code: None,
+                    deadline: request
+                        .is_best_effort()
+                        .then(|| request.deadline.as_secs_since_unix_epoch()),
+                }
+            }
+            RequestOrResponse::Response(response) => RecordedMessage {
+                height: self.height.get(),
+                kind: RecordedMessageKind::Response,
+                caller: response.originator.get(),
+                callee: response.respondent.get(),
+                method_name: self.method_names.remove(&(
+                    response.originator.get(),
+                    response.originator_reply_callback.get(),
+                )),
+                callback_id: response.originator_reply_callback.get(),
+                payload_size_bytes: response.payload_size_bytes().get(),
+                cycles: response.refund.get(),
+                reject_code: match &response.response_payload {
+                    MsgPayload::Data(_) => None,
+                    MsgPayload::Reject(context) => Some(context.code() as u64),
+                },
+                deadline: response
+                    .is_best_effort()
+                    .then(|| response.deadline.as_secs_since_unix_epoch()),
+            },
+        };
+        self.messages.push(message);
+    }
+}
+
+/// Escapes a string for use within a double-quoted DOT string, e.g., a label.
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Renders the given recorded messages as a Graphviz digraph with one node per canister
+/// and one edge per message. Responses are drawn as dashed edges.
+pub fn call_graph_to_dot(messages: &[RecordedMessage]) -> String {
+    let mut dot = String::from("digraph call_graph {\n");
+    for (i, message) in messages.iter().enumerate() {
+        let method_name = message.method_name.as_deref().unwrap_or("?");
+        let (from, to, label, style) = match message.kind {
+            RecordedMessageKind::Request => (
+                message.caller,
+                message.callee,
+                format!(
+                    "#{} {} ({} bytes, {} cycles)",
+                    i, method_name, message.payload_size_bytes, message.cycles
+                ),
+                "solid",
+            ),
+            RecordedMessageKind::Response => (
+                message.callee,
+                message.caller,
+                match message.reject_code {
+                    Some(code) => format!("#{} {} reject {}", i, method_name, code),
+                    None => format!(
+                        "#{} {} reply ({} bytes)",
+                        i, method_name, message.payload_size_bytes
+                    ),
+                },
+                "dashed",
+            ),
+        };
+        dot.push_str(&format!(
+            "  \"{}\" -> \"{}\" [label=\"{}\", style={}];\n",
+            from,
+            to,
+            escape_dot(&label),
+            style
+        ));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
 /// A custom `QueryStatsPayloadBuilderImpl` that uses a single
 /// `QueryStatsPayloadBuilderImpl` to retrieve total query stats
 /// and turns them into a collection of fractional query stats
@@ -906,6 +1164,12 @@ pub struct StateMachine {
     vetkd_payload_builder: Arc<dyn BatchPayloadBuilder>,
     remove_old_states: bool,
     cycles_account_manager: Arc<CyclesAccountManager>,
+    /// Faults injected into the XNet streams from remote subnets into this subnet.
+    xnet_faults: RwLock<BTreeMap<SubnetId, XNetFault>>,
+    /// A stalled subnet does not execute any blocks.
+    stalled: AtomicBool,
+    /// Records inter-canister messages if call-graph recording is enabled.
+    call_graph_recorder: Mutex<Option<CallGraphRecorder>>,
     // This field must be the last one so that the temporary directory is deleted at the very end.
     state_dir: Box<dyn StateMachineStateDir>,
     // DO NOT PUT ANY FIELDS AFTER `state_dir`!!!
@@ -2011,6 +2275,9 @@ impl StateMachine {
             vetkd_payload_builder,
             remove_old_states,
             cycles_account_manager,
+            xnet_faults: RwLock::new(BTreeMap::new()),
+            stalled: AtomicBool::new(false),
+            call_graph_recorder: Mutex::new(None),
         }
     }
 
@@ -2599,6 +2866,11 @@ impl StateMachine {
     /// Advances time by 1ns (to make sure time is strictly monotone)
     /// and triggers a single round of execution with block payload as an input.
     pub fn execute_payload(&self, payload: PayloadBuilder) -> Height {
+        if self.stalled.load(Ordering::Relaxed) {
+            return self.state_manager.latest_state_height();
+        }
+        let payload = self.apply_xnet_faults(payload);
+
         let batch_number = self.message_routing.expected_batch_height();
 
         let mut seed = [0u8; 32];
@@ -2629,6 +2901,26 @@ impl StateMachine {
             .blockmaker_metrics
             .unwrap_or(BlockmakerMetrics::new_for_test());
 
+        if let Some(recorder) = self.call_graph_recorder.lock().unwrap().as_mut() {
+            let incoming_slices: Vec<_> = payload
+                .xnet_payload
+                .stream_slices
+                .values()
+                .map(|slice| {
+                    self.state_manager
+                        .decode_valid_certified_stream_slice(slice)
+                        .expect("failed to decode certified stream slice")
+                })
+                .collect();
+            recorder.start_block(
+                batch_number,
+                incoming_slices
+                    .iter()
+                    .filter_map(|slice| slice.messages())
+                    .flat_map(|messages| messages.iter().map(|(_, msg)| msg)),
+            );
+        }
+
         let batch = Batch {
             batch_number,
             batch_summary,
@@ -2670,12 +2962,183 @@ impl StateMachine {
 
         self.check_critical_errors();
 
+        if let Some(recorder) = self.call_graph_recorder.lock().unwrap().as_mut() {
+            recorder.record_round(&self.get_latest_state());
+        }
+
         self.set_time(time_of_next_round.into());
         *self.time_of_last_round.write().unwrap() = time_of_next_round;
 
         batch_number
     }
 
+    /// Injects a fault into the XNet stream from the given remote subnet into this subnet,
+    /// replacing any fault previously injected for that stream.
+    ///
+    /// Dropping or rejecting messages requires this `StateMachine` to be built with
+    /// `StateMachineBuilder::build_with_subnets`.
+    pub fn inject_xnet_fault(&self, remote_subnet_id: SubnetId, fault: XNetFault) {
+        self.xnet_faults
+            .write()
+            .unwrap()
+            .insert(remote_subnet_id, fault);
+    }
+
+    /// Removes the fault injected into the XNet stream from the given remote subnet,
+    /// so that the stream is inducted as usual from the next block on.
+    pub fn clear_xnet_fault(&self, remote_subnet_id: SubnetId) {
+        self.xnet_faults.write().unwrap().remove(&remote_subnet_id);
+    }
+
+    /// Simulates a stalled subnet: while stalled, executing a block (e.g. as part of
+    /// `tick` or `execute_round`) is a no-op. Streams certified before the subnet
+    /// stalled can still be inducted by other subnets.
+    pub fn set_stalled(&self, stalled: bool) {
+        self.stalled.store(stalled, Ordering::Relaxed);
+    }
+
+    /// Applies the injected faults to the stream slices in the given payload: paused or delayed
+    /// streams are removed from the payload, while for streams whose messages are dropped or
+    /// rejected, the slice is replaced by one that only contains unaffected messages.
+    fn apply_xnet_faults(&self, mut payload: PayloadBuilder) -> PayloadBuilder {
+        let faults = self.xnet_faults.read().unwrap().clone();
+        for (remote_subnet_id, fault) in faults {
+            match fault {
+                XNetFault::DelayUntil(time) if time <= self.get_time() => {
+                    self.clear_xnet_fault(remote_subnet_id);
+                }
+                XNetFault::Pause | XNetFault::DelayUntil(_) => {
+                    payload.xnet_payload.stream_slices.remove(&remote_subnet_id);
+                }
+                XNetFault::DropBestEffortMessages | XNetFault::RejectRequests(_) => {
+                    match self.stream_slice_with_fault(remote_subnet_id, &fault) {
+                        Some(slice) => payload
+                            .xnet_payload
+                            .stream_slices
+                            .insert(remote_subnet_id, slice),
+                        None => payload.xnet_payload.stream_slices.remove(&remote_subnet_id),
+                    };
+                }
+            }
+        }
+        payload
+    }
+
+    /// Applies the given fault to the messages at the beginning of the stream from the given
+    /// remote subnet that are affected by it, by appending the respective signals to the reverse
+    /// stream. Returns a certified slice of the messages that follow, up to the next affected
+    /// message, to be inducted by message routing as usual.
+    ///
+    /// Signals are appended to the reverse stream the same way other helpers modify the state,
+    /// i.e. by committing a new state. A certified slice cannot be altered, so this is the only
+    /// way to skip messages in it.
+    fn stream_slice_with_fault(
+        &self,
+        remote_subnet_id: SubnetId,
+        fault: &XNetFault,
+    ) -> Option<CertifiedStreamSlice> {
+        let is_affected = |msg: &RequestOrResponse| match (fault, msg) {
+            (XNetFault::DropBestEffortMessages, msg) => msg.is_best_effort(),
+            (XNetFault::RejectRequests(_), RequestOrResponse::Request(_)) => true,
+            _ => false,
+        };
+
+        let remote_env = self
+            .pocket_xnet
+            .read()
+            .unwrap()
+            .as_ref()
+            .expect("Dropping or rejecting XNet messages requires a `StateMachine` built with `StateMachineBuilder::build_with_subnets`")
+            .subnets
+            .get(remote_subnet_id)
+            .unwrap();
+        remote_env.certify_latest_state();
+        let remote_state = remote_env.get_latest_state();
+        let stream = remote_state.get_stream(&self.subnet_id)?;
+
+        let signals_end = self
+            .get_latest_state()
+            .get_stream(&remote_subnet_id)
+            .map(|reverse_stream| reverse_stream.signals_end())
+            .unwrap_or_else(|| stream.messages_begin());
+        let mut messages = stream
+            .messages()
+            .iter()
+            .skip_while(|(index, _)| *index < signals_end)
+            .peekable();
+
+        let mut affected_messages = 0_u64;
+        while messages.next_if(|(_, msg)| is_affected(msg)).is_some() {
+            affected_messages += 1;
+        }
+        if affected_messages > 0 {
+            let (height, mut state) = self.state_manager.take_tip();
+            state.modify_streams(|streams| {
+                let reverse_stream = streams.entry(remote_subnet_id).or_default();
+                for _ in 0..affected_messages {
+                    match fault {
+                        XNetFault::RejectRequests(reason) => {
+                            reverse_stream.push_reject_signal(*reason)
+                        }
+                        // Like a subnet shedding load, the receiver accepts the message but
+                        // drops it.
+                        _ => reverse_stream.push_accept_signal(),
+                    }
+                }
+            });
+            self.state_manager.commit_and_certify(
+                state,
+                height.increment(),
+                CertificationScope::Metadata,
+                None,
+            );
+        }
+
+        // The slice always contains the stream header, so that the signals in it are inducted.
+        let msg_begin = signals_end + StreamIndex::new(affected_messages);
+        let msg_limit = messages.take_while(|(_, msg)| !is_affected(msg)).count();
+        remote_env
+            .generate_certified_stream_slice(
+                self.subnet_id,
+                Some(msg_begin),
+                Some(msg_begin),
+                Some(msg_limit),
+                None,
+            )
+            .ok()
+    }
+
+    /// Enables or disables recording of the inter-canister messages sent by the canisters
+    /// on this subnet. Enabling the recording discards any previously recorded messages.
+    ///
+    /// Messages are recorded between rounds, see `CallGraphRecorder` for the messages that
+    /// are missed this way.
+    pub fn set_call_graph_recording(&self, enabled: bool) {
+        *self.call_graph_recorder.lock().unwrap() =
+            enabled.then(|| CallGraphRecorder::new(&self.get_latest_state()));
+    }
+
+    /// Returns the messages recorded since call-graph recording was enabled,
+    /// in the order in which they were recorded.
+    pub fn call_graph(&self) -> Vec<RecordedMessage> {
+        self.call_graph_recorder
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|recorder| recorder.messages.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the recorded call graph as JSON.
+    pub fn call_graph_json(&self) -> String {
+        serde_json::to_string_pretty(&self.call_graph()).unwrap()
+    }
+
+    /// Returns the recorded call graph in the Graphviz DOT format.
+    pub fn call_graph_dot(&self) -> String {
+        call_graph_to_dot(&self.call_graph())
+    }
+
     pub fn execute_block_with_xnet_payload(&self, xnet_payload: XNetPayload) {
         self.execute_payload(PayloadBuilder::new().xnet_payload(xnet_payload));
     }
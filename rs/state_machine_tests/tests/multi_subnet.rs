@@ -1,10 +1,17 @@
-use ic_state_machine_tests::two_subnets_simple;
+use ic_config::{execution_environment::Config as HypervisorConfig, subnet_config::SubnetConfig};
+use ic_registry_subnet_type::SubnetType;
+use ic_state_machine_tests::{
+    call_graph_to_dot, two_subnets_simple, RecordedMessageKind, StateMachineBuilder,
+    StateMachineConfig, XNetFault,
+};
 use ic_test_utilities_types::ids::user_test_id;
 use ic_types::{
-    ingress::{IngressStatus, WasmResult},
-    Cycles,
+    ingress::{IngressState, IngressStatus, WasmResult},
+    xnet::RejectReason,
+    Cycles, NumBytes,
 };
 use ic_universal_canister::{wasm, CallArgs, UNIVERSAL_CANISTER_WASM};
+use std::time::Duration;
 
 const INITIAL_CYCLES_BALANCE: Cycles = Cycles::new(100_000_000_000_000);
 
@@ -309,3 +316,400 @@ fn counter_canister_call_test() {
         _ => panic!("unreachable"),
     };
 }
+
+#[test]
+fn xnet_fault_injection_test() {
+    const MAX_TICKS: usize = 100;
+    let user_id = user_test_id(1).get();
+
+    let (env1, env2) = two_subnets_simple();
+
+    let canister_id1 = env1
+        .install_canister_with_cycles(
+            UNIVERSAL_CANISTER_WASM.to_vec(),
+            vec![],
+            None,
+            INITIAL_CYCLES_BALANCE,
+        )
+        .unwrap();
+    let canister_id2 = env2
+        .install_canister_with_cycles(
+            UNIVERSAL_CANISTER_WASM.to_vec(),
+            vec![],
+            None,
+            INITIAL_CYCLES_BALANCE,
+        )
+        .unwrap();
+    let call_2nd_subnet = || {
+        env1.submit_ingress_as(
+            user_id,
+            canister_id1,
+            "update",
+            wasm()
+                .inter_update(
+                    canister_id2,
+                    CallArgs::default().other_side(wasm().reply_data(b"pong")),
+                )
+                .build(),
+        )
+        .unwrap()
+    };
+
+    // Pause the stream from the 1st into the 2nd subnet: the downstream call is not delivered.
+    env2.inject_xnet_fault(env1.get_subnet_id(), XNetFault::Pause);
+    let msg1_id = call_2nd_subnet();
+    for _ in 0..5 {
+        env1.execute_round();
+        env2.execute_round();
+    }
+    assert!(matches!(
+        env1.ingress_status(&msg1_id),
+        IngressStatus::Known {
+            state: IngressState::Processing,
+            ..
+        }
+    ));
+
+    // Once the stream is resumed, the call completes.
+    env2.clear_xnet_fault(env1.get_subnet_id());
+    env2.execute_round();
+    env1.execute_round();
+    let wasm_result = env1.await_ingress(msg1_id, MAX_TICKS).unwrap();
+    match wasm_result {
+        WasmResult::Reply(bytes) => assert_eq!(bytes, b"pong".to_vec()),
+        _ => panic!("unreachable"),
+    };
+
+    // Reject requests from the 1st subnet with reject signals.
+    env2.inject_xnet_fault(
+        env1.get_subnet_id(),
+        XNetFault::RejectRequests(RejectReason::QueueFull),
+    );
+    let msg2_id = call_2nd_subnet();
+    env1.execute_round();
+    env2.execute_round();
+    env1.execute_round();
+    let wasm_result = env1.await_ingress(msg2_id, MAX_TICKS).unwrap();
+    match wasm_result {
+        // The call fails with SYS_TRANSIENT reject code (2).
+        WasmResult::Reject(reject) => assert_eq!(reject.as_bytes(), 2_u32.to_le_bytes().to_vec()),
+        _ => panic!("unreachable"),
+    };
+}
+
+#[test]
+fn xnet_fault_drop_best_effort_messages_test() {
+    const MAX_TICKS: usize = 100;
+    const TIMEOUT_SECONDS: u32 = 10;
+    let user_id = user_test_id(1).get();
+
+    let (env1, env2) = two_subnets_simple();
+
+    let canister_id1 = env1
+        .install_canister_with_cycles(
+            UNIVERSAL_CANISTER_WASM.to_vec(),
+            vec![],
+            None,
+            INITIAL_CYCLES_BALANCE,
+        )
+        .unwrap();
+    let canister_id2 = env2
+        .install_canister_with_cycles(
+            UNIVERSAL_CANISTER_WASM.to_vec(),
+            vec![],
+            None,
+            INITIAL_CYCLES_BALANCE,
+        )
+        .unwrap();
+
+    env2.inject_xnet_fault(env1.get_subnet_id(), XNetFault::DropBestEffortMessages);
+
+    // A call with a guaranteed response is not affected by the fault.
+    let msg1_id = env1
+        .submit_ingress_as(
+            user_id,
+            canister_id1,
+            "update",
+            wasm()
+                .inter_update(
+                    canister_id2,
+                    CallArgs::default().other_side(wasm().reply_data(b"pong")),
+                )
+                .build(),
+        )
+        .unwrap();
+    env1.execute_round();
+    env2.execute_round();
+    let wasm_result = env1.await_ingress(msg1_id, MAX_TICKS).unwrap();
+    match wasm_result {
+        WasmResult::Reply(bytes) => assert_eq!(bytes, b"pong".to_vec()),
+        _ => panic!("unreachable"),
+    };
+
+    // A best-effort call is dropped, so it is pending until it times out.
+    let msg2_id = env1
+        .submit_ingress_as(
+            user_id,
+            canister_id1,
+            "update",
+            wasm()
+                .call_simple_with_cycles_and_best_effort_response(
+                    canister_id2,
+                    "update",
+                    CallArgs::default().other_side(wasm().reply_data(b"pong")),
+                    0_u128,
+                    TIMEOUT_SECONDS,
+                )
+                .build(),
+        )
+        .unwrap();
+    for _ in 0..5 {
+        env1.execute_round();
+        env2.execute_round();
+    }
+    assert!(matches!(
+        env1.ingress_status(&msg2_id),
+        IngressStatus::Known {
+            state: IngressState::Processing,
+            ..
+        }
+    ));
+
+    env1.advance_time(Duration::from_secs(TIMEOUT_SECONDS as u64 + 1));
+    let wasm_result = env1.await_ingress(msg2_id, MAX_TICKS).unwrap();
+    match wasm_result {
+        // The call fails with SYS_UNKNOWN reject code (6).
+        WasmResult::Reject(reject) => assert_eq!(reject.as_bytes(), 6_u32.to_le_bytes().to_vec()),
+        _ => panic!("unreachable"),
+    };
+}
+
+#[test]
+fn xnet_fault_delay_and_stalled_subnet_test() {
+    const MAX_TICKS: usize = 100;
+    let user_id = user_test_id(1).get();
+
+    let (env1, env2) = two_subnets_simple();
+
+    let canister_id1 = env1
+        .install_canister_with_cycles(
+            UNIVERSAL_CANISTER_WASM.to_vec(),
+            vec![],
+            None,
+            INITIAL_CYCLES_BALANCE,
+        )
+        .unwrap();
+    let canister_id2 = env2
+        .install_canister_with_cycles(
+            UNIVERSAL_CANISTER_WASM.to_vec(),
+            vec![],
+            None,
+            INITIAL_CYCLES_BALANCE,
+        )
+        .unwrap();
+    let call_2nd_subnet = || {
+        env1.submit_ingress_as(
+            user_id,
+            canister_id1,
+            "update",
+            wasm()
+                .inter_update(
+                    canister_id2,
+                    CallArgs::default().other_side(wasm().reply_data(b"pong")),
+                )
+                .build(),
+        )
+        .unwrap()
+    };
+    let assert_processing = |msg_id| {
+        assert!(matches!(
+            env1.ingress_status(msg_id),
+            IngressStatus::Known {
+                state: IngressState::Processing,
+                ..
+            }
+        ));
+    };
+
+    // Delay the stream from the 1st into the 2nd subnet by a minute.
+    env2.inject_xnet_fault(
+        env1.get_subnet_id(),
+        XNetFault::DelayUntil(env2.get_time() + Duration::from_secs(60)),
+    );
+    let msg1_id = call_2nd_subnet();
+    for _ in 0..5 {
+        env1.execute_round();
+        env2.execute_round();
+    }
+    assert_processing(&msg1_id);
+
+    // Once the time is reached, the call completes without clearing the fault.
+    env2.advance_time(Duration::from_secs(60));
+    env2.execute_round();
+    let wasm_result = env1.await_ingress(msg1_id, MAX_TICKS).unwrap();
+    match wasm_result {
+        WasmResult::Reply(bytes) => assert_eq!(bytes, b"pong".to_vec()),
+        _ => panic!("unreachable"),
+    };
+
+    // A stalled subnet does not execute the downstream call.
+    env2.set_stalled(true);
+    let msg2_id = call_2nd_subnet();
+    for _ in 0..5 {
+        env1.execute_round();
+        env2.execute_round();
+    }
+    assert_processing(&msg2_id);
+
+    // Once the subnet resumes, the call completes.
+    env2.set_stalled(false);
+    env2.execute_round();
+    let wasm_result = env1.await_ingress(msg2_id, MAX_TICKS).unwrap();
+    match wasm_result {
+        WasmResult::Reply(bytes) => assert_eq!(bytes, b"pong".to_vec()),
+        _ => panic!("unreachable"),
+    };
+}
+
+#[test]
+fn call_graph_recording_test() {
+    const MAX_TICKS: usize = 100;
+    let user_id = user_test_id(1).get();
+
+    let (env1, env2) = two_subnets_simple();
+
+    let canister_id1 = env1
+        .install_canister_with_cycles(
+            UNIVERSAL_CANISTER_WASM.to_vec(),
+            vec![],
+            None,
+            INITIAL_CYCLES_BALANCE,
+        )
+        .unwrap();
+    let canister_id2 = env2
+        .install_canister_with_cycles(
+            UNIVERSAL_CANISTER_WASM.to_vec(),
+            vec![],
+            None,
+            INITIAL_CYCLES_BALANCE,
+        )
+        .unwrap();
+
+    env1.set_call_graph_recording(true);
+    env2.set_call_graph_recording(true);
+
+    let msg_id = env1
+        .submit_ingress_as(
+            user_id,
+            canister_id1,
+            "update",
+            wasm()
+                .inter_update(
+                    canister_id2,
+                    CallArgs::default().other_side(wasm().reply_data(b"pong")),
+                )
+                .build(),
+        )
+        .unwrap();
+    env1.execute_round();
+    env2.execute_round();
+    env1.execute_round();
+    env1.await_ingress(msg_id, MAX_TICKS).unwrap();
+
+    // The request is routed by the 1st subnet, the response by the 2nd one.
+    let requests = env1.call_graph();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].kind, RecordedMessageKind::Request);
+    assert_eq!(requests[0].caller, canister_id1.get());
+    assert_eq!(requests[0].callee, canister_id2.get());
+    assert_eq!(requests[0].method_name.as_deref(), Some("update"));
+    assert_eq!(requests[0].reject_code, None);
+
+    let responses = env2.call_graph();
+    assert_eq!(responses.len(), 1);
+    assert_eq!(responses[0].kind, RecordedMessageKind::Response);
+    assert_eq!(responses[0].caller, canister_id1.get());
+    assert_eq!(responses[0].callee, canister_id2.get());
+    assert_eq!(responses[0].callback_id, requests[0].callback_id);
+    assert_eq!(responses[0].method_name.as_deref(), Some("update"));
+    assert_eq!(responses[0].reject_code, None);
+
+    assert!(env1.call_graph_dot().contains(&format!(
+        "\"{}\" -> \"{}\"",
+        canister_id1.get(),
+        canister_id2.get()
+    )));
+
+    // Disabling the recording discards the recorded messages.
+    env1.set_call_graph_recording(false);
+    assert!(env1.call_graph().is_empty());
+}
+
+#[test]
+fn call_graph_recording_same_subnet_test() {
+    const MAX_TICKS: usize = 100;
+    let user_id = user_test_id(1).get();
+
+    // Messages between canisters on the same subnet are only recorded if they are
+    // still in a queue or stream at the end of a round, which is guaranteed if they
+    // are never inducted within the round that they were sent in.
+    let mut subnet_config = SubnetConfig::new(SubnetType::Application);
+    subnet_config.scheduler_config.max_heap_delta_per_iteration = NumBytes::new(0);
+    let env1 = StateMachineBuilder::new()
+        .with_config(Some(StateMachineConfig::new(
+            subnet_config,
+            HypervisorConfig::default(),
+        )))
+        .build();
+
+    let canister_id1 = env1
+        .install_canister_with_cycles(
+            UNIVERSAL_CANISTER_WASM.to_vec(),
+            vec![],
+            None,
+            INITIAL_CYCLES_BALANCE,
+        )
+        .unwrap();
+    let canister_id2 = env1
+        .install_canister_with_cycles(
+            UNIVERSAL_CANISTER_WASM.to_vec(),
+            vec![],
+            None,
+            INITIAL_CYCLES_BALANCE,
+        )
+        .unwrap();
+
+    env1.set_call_graph_recording(true);
+
+    let msg_id = env1
+        .submit_ingress_as(
+            user_id,
+            canister_id1,
+            "update",
+            wasm()
+                .inter_update(
+                    canister_id2,
+                    CallArgs::default().other_side(wasm().reply_data(b"pong")),
+                )
+                .build(),
+        )
+        .unwrap();
+    env1.await_ingress(msg_id, MAX_TICKS).unwrap();
+
+    // Both the request and the response are inducted on the same subnet.
+    let messages = env1.call_graph();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0].kind, RecordedMessageKind::Request);
+    assert_eq!(messages[1].kind, RecordedMessageKind::Response);
+    for message in &messages {
+        assert_eq!(message.caller, canister_id1.get());
+        assert_eq!(message.callee, canister_id2.get());
+        assert_eq!(message.method_name.as_deref(), Some("update"));
+        assert_eq!(message.callback_id, messages[0].callback_id);
+    }
+
+    // Labels are escaped in the DOT output.
+    let mut message = messages[0].clone();
+    message.method_name = Some("a\"b\\c".to_string());
+    assert!(call_graph_to_dot(&[message]).contains("#0 a\\\"b\\\\c ("));
+}
@@ -874,6 +874,7 @@ pub fn load_canister_state(
         queues,
         canister_state_bits.memory_allocation,
         canister_state_bits.wasm_memory_threshold,
+        canister_state_bits.cycles_threshold,
         canister_state_bits.freeze_threshold,
         canister_state_bits.status,
         canister_state_bits.certified_data,
@@ -882,6 +883,8 @@ pub fn load_canister_state(
         canister_state_bits.cycles_debit,
         canister_state_bits.reserved_balance,
         canister_state_bits.reserved_balance_limit,
+        canister_state_bits.cycles_burn_limit,
+        canister_state_bits.cycles_burn_window,
         canister_state_bits.task_queue,
         CanisterTimer::from_nanos_since_unix_epoch(canister_state_bits.global_timer_nanos),
         canister_state_bits.canister_version,
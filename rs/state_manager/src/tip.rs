@@ -1256,11 +1256,14 @@ fn serialize_canister_protos_to_checkpoint_readwrite(
             accumulated_priority: canister_state.scheduler_state.accumulated_priority,
             memory_allocation: canister_state.system_state.memory_allocation,
             wasm_memory_threshold: canister_state.system_state.wasm_memory_threshold,
+            cycles_threshold: canister_state.system_state.cycles_threshold,
             freeze_threshold: canister_state.system_state.freeze_threshold,
             cycles_balance: canister_state.system_state.balance(),
             cycles_debit: canister_state.system_state.ingress_induction_cycles_debit(),
             reserved_balance: canister_state.system_state.reserved_balance(),
             reserved_balance_limit: canister_state.system_state.reserved_balance_limit(),
+            cycles_burn_limit: canister_state.system_state.cycles_burn_limit,
+            cycles_burn_window: canister_state.system_state.cycles_burn_window.clone(),
             execution_state_bits,
             status: canister_state.system_state.get_status().clone(),
             scheduled_as_first: canister_state
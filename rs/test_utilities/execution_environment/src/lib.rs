@@ -638,6 +638,39 @@ impl ExecutionTest {
         self.subnet_message(Method::UpdateSettings, payload)
     }
 
+    /// Updates the cycles threshold of the canister.
+    pub fn canister_update_cycles_threshold(
+        &mut self,
+        canister_id: CanisterId,
+        cycles_threshold: Cycles,
+    ) -> Result<WasmResult, UserError> {
+        let payload = UpdateSettingsArgs {
+            canister_id: canister_id.into(),
+            settings: CanisterSettingsArgsBuilder::new()
+                .with_cycles_threshold(cycles_threshold.get())
+                .build(),
+            sender_canister_version: None,
+        }
+        .encode();
+        self.subnet_message(Method::UpdateSettings, payload)
+    }
+
+    pub fn canister_update_cycles_burn_limit(
+        &mut self,
+        canister_id: CanisterId,
+        cycles_burn_limit: Cycles,
+    ) -> Result<WasmResult, UserError> {
+        let payload = UpdateSettingsArgs {
+            canister_id: canister_id.into(),
+            settings: CanisterSettingsArgsBuilder::new()
+                .with_cycles_burn_limit(cycles_burn_limit.get())
+                .build(),
+            sender_canister_version: None,
+        }
+        .encode();
+        self.subnet_message(Method::UpdateSettings, payload)
+    }
+
     pub fn canister_update_wasm_memory_limit(
         &mut self,
         canister_id: CanisterId,
@@ -738,6 +771,13 @@ impl ExecutionTest {
         self.state = Some(state);
     }
 
+    pub fn enqueue_due_scheduled_management_calls(&mut self) {
+        let mut state = self.state.take().unwrap();
+        self.exec_env
+            .enqueue_due_scheduled_management_calls(&mut state);
+        self.state = Some(state);
+    }
+
     /// Returns the canister status by canister id.
     pub fn canister_status(&mut self, canister_id: CanisterId) -> Result<WasmResult, UserError> {
         let payload = CanisterIdRecord::from(canister_id).encode();
@@ -1071,6 +1111,16 @@ impl ExecutionTest {
                     .task_queue
                     .enqueue(ExecutionTask::OnLowWasmMemory);
             }
+            CanisterTask::OnLowCycles => {
+                canister
+                    .system_state
+                    .task_queue
+                    .remove(ExecutionTask::OnLowCycles);
+                canister
+                    .system_state
+                    .task_queue
+                    .enqueue(ExecutionTask::OnLowCycles);
+            }
         }
         let result = execute_canister(
             &self.exec_env,
@@ -114,6 +114,7 @@ pub enum Method {
     SubnetInfo,
 
     FetchCanisterLogs,
+    FetchCanisterHistory,
 
     // These methods are only available on test IC instances where there is a
     // need to fabricate cycles without burning ICP first.
@@ -139,6 +140,12 @@ pub enum Method {
 
     // Support for canister migration
     RenameCanister,
+
+    // Support for management calls scheduled for execution by the system.
+    ScheduleManagementCall,
+    ListScheduledManagementCalls,
+    CancelScheduledManagementCall,
+    ScheduledManagementCallStatus,
 }
 
 fn candid_error_to_user_error(err: candid::Error) -> UserError {
@@ -1095,6 +1102,48 @@ impl TryFrom<pb_canister_state_bits::LogVisibilityV2> for LogVisibilityV2 {
     }
 }
 
+/// Visibility of the extended canister history returned by
+/// `fetch_canister_history`.
+/// ```text
+/// variant {
+///    controllers;
+///    public;
+/// }
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, CandidType, Deserialize, EnumIter)]
+pub enum CanisterHistoryVisibility {
+    #[default]
+    #[serde(rename = "controllers")]
+    Controllers,
+    #[serde(rename = "public")]
+    Public,
+}
+
+impl Payload<'_> for CanisterHistoryVisibility {}
+
+impl From<CanisterHistoryVisibility> for pb_canister_state_bits::CanisterHistoryVisibility {
+    fn from(item: CanisterHistoryVisibility) -> Self {
+        match item {
+            CanisterHistoryVisibility::Controllers => {
+                pb_canister_state_bits::CanisterHistoryVisibility::Controllers
+            }
+            CanisterHistoryVisibility::Public => {
+                pb_canister_state_bits::CanisterHistoryVisibility::Public
+            }
+        }
+    }
+}
+
+impl From<pb_canister_state_bits::CanisterHistoryVisibility> for CanisterHistoryVisibility {
+    fn from(item: pb_canister_state_bits::CanisterHistoryVisibility) -> Self {
+        match item {
+            pb_canister_state_bits::CanisterHistoryVisibility::Unspecified
+            | pb_canister_state_bits::CanisterHistoryVisibility::Controllers => Self::Controllers,
+            pb_canister_state_bits::CanisterHistoryVisibility::Public => Self::Public,
+        }
+    }
+}
+
 /// Struct used for encoding/decoding
 /// `(record {
 ///     controller : principal;
@@ -1105,6 +1154,9 @@ impl TryFrom<pb_canister_state_bits::LogVisibilityV2> for LogVisibilityV2 {
 ///     log_visibility: log_visibility;
 ///     wasm_memory_limit: nat;
 ///     wasm_memory_threshold: nat;
+///     cycles_threshold: nat;
+///     cycles_burn_limit: nat;
+///     history_visibility: canister_history_visibility;
 /// })`
 #[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
 pub struct DefiniteCanisterSettingsArgs {
@@ -1117,6 +1169,9 @@ pub struct DefiniteCanisterSettingsArgs {
     log_visibility: LogVisibilityV2,
     wasm_memory_limit: candid::Nat,
     wasm_memory_threshold: candid::Nat,
+    cycles_threshold: candid::Nat,
+    cycles_burn_limit: candid::Nat,
+    history_visibility: CanisterHistoryVisibility,
 }
 
 impl DefiniteCanisterSettingsArgs {
@@ -1130,10 +1185,14 @@ impl DefiniteCanisterSettingsArgs {
         log_visibility: LogVisibilityV2,
         wasm_memory_limit: Option<u64>,
         wasm_memory_threshold: u64,
+        cycles_threshold: u128,
+        cycles_burn_limit: Option<u128>,
+        history_visibility: CanisterHistoryVisibility,
     ) -> Self {
         let memory_allocation = candid::Nat::from(memory_allocation.unwrap_or(0));
         let reserved_cycles_limit = candid::Nat::from(reserved_cycles_limit.unwrap_or(0));
         let wasm_memory_limit = candid::Nat::from(wasm_memory_limit.unwrap_or(0));
+        let cycles_burn_limit = candid::Nat::from(cycles_burn_limit.unwrap_or(0));
         Self {
             controller,
             controllers,
@@ -1144,6 +1203,9 @@ impl DefiniteCanisterSettingsArgs {
             log_visibility,
             wasm_memory_limit,
             wasm_memory_threshold: candid::Nat::from(wasm_memory_threshold),
+            cycles_threshold: candid::Nat::from(cycles_threshold),
+            cycles_burn_limit,
+            history_visibility,
         }
     }
 
@@ -1167,6 +1229,18 @@ impl DefiniteCanisterSettingsArgs {
         self.wasm_memory_threshold.clone()
     }
 
+    pub fn cycles_threshold(&self) -> candid::Nat {
+        self.cycles_threshold.clone()
+    }
+
+    pub fn cycles_burn_limit(&self) -> candid::Nat {
+        self.cycles_burn_limit.clone()
+    }
+
+    pub fn history_visibility(&self) -> CanisterHistoryVisibility {
+        self.history_visibility
+    }
+
     pub fn compute_allocation(&self) -> candid::Nat {
         self.compute_allocation.clone()
     }
@@ -1216,7 +1290,8 @@ pub struct QueryStats {
 ///         num_instructions: nat;
 ///         ingress_payload_size: nat;
 ///         egress_payload_size: nat;
-///     }
+///     };
+///     cycles_burned_in_window: nat;
 /// })`
 #[derive(Eq, PartialEq, Debug, CandidType, Deserialize)]
 pub struct CanisterStatusResultV2 {
@@ -1233,6 +1308,7 @@ pub struct CanisterStatusResultV2 {
     idle_cycles_burned_per_day: candid::Nat,
     reserved_cycles: candid::Nat,
     query_stats: QueryStats,
+    cycles_burned_in_window: candid::Nat,
 }
 
 #[derive(Eq, PartialEq, Debug, CandidType, Deserialize)]
@@ -1277,6 +1353,10 @@ impl CanisterStatusResultV2 {
         query_egress_payload_size: u128,
         wasm_memory_limit: Option<u64>,
         wasm_memory_threshold: u64,
+        cycles_threshold: u128,
+        cycles_burn_limit: Option<u128>,
+        history_visibility: CanisterHistoryVisibility,
+        cycles_burned_in_window: u128,
     ) -> Self {
         Self {
             status,
@@ -1307,6 +1387,9 @@ impl CanisterStatusResultV2 {
                 log_visibility,
                 wasm_memory_limit,
                 wasm_memory_threshold,
+                cycles_threshold,
+                cycles_burn_limit,
+                history_visibility,
             ),
             freezing_threshold: candid::Nat::from(freezing_threshold),
             idle_cycles_burned_per_day: candid::Nat::from(idle_cycles_burned_per_day),
@@ -1317,6 +1400,7 @@ impl CanisterStatusResultV2 {
                 request_payload_bytes_total: candid::Nat::from(query_ingress_payload_size),
                 response_payload_bytes_total: candid::Nat::from(query_egress_payload_size),
             },
+            cycles_burned_in_window: candid::Nat::from(cycles_burned_in_window),
         }
     }
 
@@ -1408,6 +1492,10 @@ impl CanisterStatusResultV2 {
         self.reserved_cycles.0.to_u128().unwrap()
     }
 
+    pub fn cycles_burned_in_window(&self) -> u128 {
+        self.cycles_burned_in_window.0.to_u128().unwrap()
+    }
+
     pub fn settings(&self) -> DefiniteCanisterSettingsArgs {
         self.settings.clone()
     }
@@ -1978,6 +2066,9 @@ pub struct EnvironmentVariable {
 ///     wasm_memory_limit: opt nat;
 ///     wasm_memory_threshold: opt nat;
 ///     environment_variables: opt vec environment_variable;
+///     cycles_threshold: opt nat;
+///     cycles_burn_limit: opt nat;
+///     history_visibility: opt canister_history_visibility;
 /// })`
 #[derive(Clone, Eq, PartialEq, Debug, Default, CandidType, Deserialize)]
 pub struct CanisterSettingsArgs {
@@ -1990,6 +2081,9 @@ pub struct CanisterSettingsArgs {
     pub wasm_memory_limit: Option<candid::Nat>,
     pub wasm_memory_threshold: Option<candid::Nat>,
     pub environment_variables: Option<Vec<EnvironmentVariable>>,
+    pub cycles_threshold: Option<candid::Nat>,
+    pub cycles_burn_limit: Option<candid::Nat>,
+    pub history_visibility: Option<CanisterHistoryVisibility>,
 }
 
 impl Payload<'_> for CanisterSettingsArgs {}
@@ -2008,6 +2102,9 @@ impl CanisterSettingsArgs {
             wasm_memory_limit: None,
             wasm_memory_threshold: None,
             environment_variables: None,
+            cycles_threshold: None,
+            cycles_burn_limit: None,
+            history_visibility: None,
         }
     }
 }
@@ -2023,6 +2120,9 @@ pub struct CanisterSettingsArgsBuilder {
     wasm_memory_limit: Option<candid::Nat>,
     wasm_memory_threshold: Option<candid::Nat>,
     environment_variables: Option<BTreeMap<String, String>>,
+    cycles_threshold: Option<candid::Nat>,
+    cycles_burn_limit: Option<candid::Nat>,
+    history_visibility: Option<CanisterHistoryVisibility>,
 }
 
 #[allow(dead_code)]
@@ -2050,6 +2150,9 @@ impl CanisterSettingsArgsBuilder {
                     })
                     .collect::<Vec<_>>()
             }),
+            cycles_threshold: self.cycles_threshold,
+            cycles_burn_limit: self.cycles_burn_limit,
+            history_visibility: self.history_visibility,
         }
     }
 
@@ -2144,6 +2247,32 @@ impl CanisterSettingsArgsBuilder {
             ..self
         }
     }
+
+    /// Sets the cycles threshold below which the `canister_on_low_cycles`
+    /// hook is triggered.
+    pub fn with_cycles_threshold(self, cycles_threshold: u128) -> Self {
+        Self {
+            cycles_threshold: Some(candid::Nat::from(cycles_threshold)),
+            ..self
+        }
+    }
+
+    /// Sets the maximum amount of cycles the canister may burn within
+    /// a single cycles burn limit window.
+    pub fn with_cycles_burn_limit(self, cycles_burn_limit: u128) -> Self {
+        Self {
+            cycles_burn_limit: Some(candid::Nat::from(cycles_burn_limit)),
+            ..self
+        }
+    }
+
+    /// Sets who may fetch the extended canister history.
+    pub fn with_history_visibility(self, history_visibility: CanisterHistoryVisibility) -> Self {
+        Self {
+            history_visibility: Some(history_visibility),
+            ..self
+        }
+    }
 }
 
 /// Struct used for encoding/decoding
@@ -3241,6 +3370,8 @@ impl Payload<'_> for BitcoinSendTransactionInternalArgs {}
 #[strum(serialize_all = "snake_case")]
 pub enum QueryMethod {
     FetchCanisterLogs,
+    FetchCanisterHistory,
+    SimulateUpdateCall,
 }
 
 /// `CandidType` for `SubnetInfoArgs`
@@ -3408,6 +3539,206 @@ pub struct FetchCanisterLogsResponse {
 
 impl Payload<'_> for FetchCanisterLogsResponse {}
 
+/// `CandidType` for `FetchCanisterHistoryRequest`
+/// ```text
+/// record {
+///     canister_id: principal;
+///     num_requested_modules: opt nat64;
+/// }
+/// ```
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct FetchCanisterHistoryRequest {
+    pub canister_id: PrincipalId,
+    /// Number of most recent code deployments for which the Wasm module
+    /// should be returned. Past modules are not retained by the canister
+    /// history, so a module is only returned while it is still installed or
+    /// stored in one of the canister's snapshots.
+    pub num_requested_modules: Option<u64>,
+}
+
+impl Payload<'_> for FetchCanisterHistoryRequest {}
+
+impl FetchCanisterHistoryRequest {
+    pub fn new(canister_id: CanisterId, num_requested_modules: Option<u64>) -> Self {
+        Self {
+            canister_id: canister_id.into(),
+            num_requested_modules,
+        }
+    }
+
+    pub fn get_canister_id(&self) -> CanisterId {
+        CanisterId::unchecked_from_principal(self.canister_id)
+    }
+}
+
+/// `CandidType` for `CanisterHistoryEntry`
+/// ```text
+/// record {
+///     change: change;
+///     arg_hash: opt blob;
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub struct CanisterHistoryEntry {
+    pub change: CanisterChange,
+    /// SHA-256 hash of the install argument of a code deployment. Not set for
+    /// other changes and for code deployments recorded before argument hashes
+    /// were retained.
+    pub arg_hash: Option<Vec<u8>>,
+}
+
+/// `CandidType` for `CanisterHistoryModule`
+/// ```text
+/// record {
+///     canister_version: nat64;
+///     module_hash: blob;
+///     wasm_module: opt blob;
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub struct CanisterHistoryModule {
+    /// The canister version of the code deployment that installed the module.
+    pub canister_version: u64,
+    pub module_hash: Vec<u8>,
+    /// The Wasm module, if it is still installed or stored in one of the
+    /// canister's snapshots. Not set for modules that have since been
+    /// replaced, as the canister history only retains module hashes.
+    pub wasm_module: Option<Vec<u8>>,
+}
+
+/// `CandidType` for `FetchCanisterHistoryResponse`
+/// ```text
+/// record {
+///     total_num_changes: nat64;
+///     changes: vec canister_history_entry;
+///     modules: vec canister_history_module;
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub struct FetchCanisterHistoryResponse {
+    pub total_num_changes: u64,
+    /// All changes retained in the canister history, from the oldest to the
+    /// most recent.
+    pub changes: Vec<CanisterHistoryEntry>,
+    /// The modules of the requested most recent code deployments, from the
+    /// oldest to the most recent.
+    pub modules: Vec<CanisterHistoryModule>,
+}
+
+impl Payload<'_> for FetchCanisterHistoryResponse {}
+
+/// `CandidType` for `SimulateUpdateCallArgs`
+/// ```text
+/// record {
+///     canister_id: principal;
+///     method_name: text;
+///     arg: blob;
+/// }
+/// ```
+///
+/// Only controllers of the canister may simulate its update methods, since
+/// `canister_inspect_message` is not run for the simulated call.
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct SimulateUpdateCallArgs {
+    pub canister_id: PrincipalId,
+    pub method_name: String,
+    #[serde(with = "serde_bytes")]
+    pub arg: Vec<u8>,
+}
+
+impl Payload<'_> for SimulateUpdateCallArgs {}
+
+impl SimulateUpdateCallArgs {
+    pub fn new(canister_id: CanisterId, method_name: String, arg: Vec<u8>) -> Self {
+        Self {
+            canister_id: canister_id.into(),
+            method_name,
+            arg,
+        }
+    }
+
+    pub fn get_canister_id(&self) -> CanisterId {
+        CanisterId::unchecked_from_principal(self.canister_id)
+    }
+}
+
+/// `CandidType` for `SimulatedCallReject`
+/// ```text
+/// record {
+///     reject_code: nat32;
+///     reject_message: text;
+///     error_code: text;
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub struct SimulatedCallReject {
+    pub reject_code: u32,
+    pub reject_message: String,
+    pub error_code: String,
+}
+
+/// `CandidType` for `SimulatedCallResult`
+/// ```text
+/// variant {
+///     reply: blob;
+///     reject: simulated_call_reject;
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub enum SimulatedCallResult {
+    #[serde(rename = "reply")]
+    Reply(#[serde(with = "serde_bytes")] Vec<u8>),
+    #[serde(rename = "reject")]
+    Reject(SimulatedCallReject),
+}
+
+/// `CandidType` for `SimulatedOutgoingCall`
+/// ```text
+/// record {
+///     callee: principal;
+///     method_name: text;
+///     payment: nat;
+///     arg: blob;
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub struct SimulatedOutgoingCall {
+    pub callee: PrincipalId,
+    pub method_name: String,
+    /// The cycles attached to the call.
+    pub payment: candid::Nat,
+    #[serde(with = "serde_bytes")]
+    pub arg: Vec<u8>,
+}
+
+/// `CandidType` for `SimulateUpdateCallResponse`
+/// ```text
+/// record {
+///     result: opt simulated_call_result;
+///     instructions_used: nat64;
+///     cycles_charged: nat;
+///     outgoing_calls: vec simulated_outgoing_call;
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub struct SimulateUpdateCallResponse {
+    /// The reply or reject of the call, or `None` if the call is still
+    /// awaiting the responses to its outgoing calls.
+    pub result: Option<SimulatedCallResult>,
+    /// The instructions executed by the call. The call runs under the query
+    /// instruction limit, so a call that needs more instructions is rejected
+    /// with `CanisterInstructionLimitExceeded` instead of being measured.
+    pub instructions_used: u64,
+    /// The cycles the canister would be charged for inducting and executing
+    /// the call and for making its outgoing calls, excluding the cycles
+    /// attached to the outgoing calls.
+    pub cycles_charged: candid::Nat,
+    /// The calls made by the method, which were not sent.
+    pub outgoing_calls: Vec<SimulatedOutgoingCall>,
+}
+
+impl Payload<'_> for SimulateUpdateCallResponse {}
+
 /// Struct used for encoding/decoding
 /// `(record {
 ///     canister_id: principal;
@@ -4119,6 +4450,61 @@ impl TryFrom<pb_canister_state_bits::OnLowWasmMemoryHookStatus> for OnLowWasmMem
     }
 }
 
+/// A wrapper around the different statuses of `OnLowCycles` hook execution.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default, EnumIter)]
+pub enum OnLowCyclesHookStatus {
+    #[default]
+    ConditionNotSatisfied,
+    Ready,
+    Executed,
+}
+
+impl OnLowCyclesHookStatus {
+    pub fn update(&mut self, is_hook_condition_satisfied: bool) {
+        *self = if is_hook_condition_satisfied {
+            match *self {
+                Self::ConditionNotSatisfied | Self::Ready => Self::Ready,
+                Self::Executed => Self::Executed,
+            }
+        } else {
+            Self::ConditionNotSatisfied
+        };
+    }
+
+    pub fn is_ready(&self) -> bool {
+        *self == Self::Ready
+    }
+}
+
+impl From<&OnLowCyclesHookStatus> for pb_canister_state_bits::OnLowCyclesHookStatus {
+    fn from(item: &OnLowCyclesHookStatus) -> Self {
+        use OnLowCyclesHookStatus::*;
+
+        match *item {
+            ConditionNotSatisfied => Self::ConditionNotSatisfied,
+            Ready => Self::Ready,
+            Executed => Self::Executed,
+        }
+    }
+}
+
+impl From<pb_canister_state_bits::OnLowCyclesHookStatus> for OnLowCyclesHookStatus {
+    /// Checkpoints written before the `OnLowCycles` hook was introduced do not
+    /// carry its status, so an unspecified value is decoded as the default.
+    fn from(value: pb_canister_state_bits::OnLowCyclesHookStatus) -> Self {
+        match value {
+            pb_canister_state_bits::OnLowCyclesHookStatus::Unspecified
+            | pb_canister_state_bits::OnLowCyclesHookStatus::ConditionNotSatisfied => {
+                OnLowCyclesHookStatus::ConditionNotSatisfied
+            }
+            pb_canister_state_bits::OnLowCyclesHookStatus::Ready => OnLowCyclesHookStatus::Ready,
+            pb_canister_state_bits::OnLowCyclesHookStatus::Executed => {
+                OnLowCyclesHookStatus::Executed
+            }
+        }
+    }
+}
+
 /// Struct for encoding/decoding
 /// (record {
 ///  canister_id : principal;
@@ -4418,6 +4804,225 @@ impl RenameToArgs {
     }
 }
 
+/// Struct used for encoding/decoding
+/// `(record {
+///     canister_id: principal;
+///     method_name: text;
+///     arg: blob;
+///     execute_at_nanos: nat64;
+/// })`
+#[derive(Clone, Eq, PartialEq, Debug, Default, CandidType, Deserialize)]
+pub struct ScheduleManagementCallArgs {
+    /// The canister targeted by the scheduled call. The caller must be one of
+    /// its controllers. The canister pays for storing the call until it is due.
+    pub canister_id: PrincipalId,
+    /// The management canister method to call, e.g. `start_canister`.
+    ///
+    /// The call is executed on behalf of the caller and, if the caller is a
+    /// canister, is recorded with a canister origin in the canister history.
+    /// A scheduling canister retrieves the outcome of the call with
+    /// `scheduled_management_call_status`, users from the ingress history.
+    pub method_name: String,
+    /// The Candid-encoded argument of the scheduled call. The method name and
+    /// argument can take at most 64 KiB.
+    #[serde(with = "serde_bytes")]
+    pub arg: Vec<u8>,
+    /// The time (in nanoseconds since the Unix epoch) at which the call is due,
+    /// at most 30 days in the future.
+    pub execute_at_nanos: u64,
+}
+
+impl Payload<'_> for ScheduleManagementCallArgs {}
+
+impl ScheduleManagementCallArgs {
+    pub fn new(
+        canister_id: CanisterId,
+        method: Method,
+        arg: Vec<u8>,
+        execute_at_nanos: u64,
+    ) -> Self {
+        Self {
+            canister_id: canister_id.get(),
+            method_name: method.to_string(),
+            arg,
+            execute_at_nanos,
+        }
+    }
+
+    pub fn get_canister_id(&self) -> CanisterId {
+        CanisterId::unchecked_from_principal(self.canister_id)
+    }
+}
+
+/// Struct used for encoding/decoding
+/// `(record {
+///     call_id: nat64;
+///     request_id: blob;
+/// })`
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub struct ScheduleManagementCallResult {
+    pub call_id: u64,
+    /// The ID under which the outcome of the call is recorded in the ingress
+    /// history once the call is executed.
+    #[serde(with = "serde_bytes")]
+    pub request_id: Vec<u8>,
+}
+
+impl Payload<'_> for ScheduleManagementCallResult {}
+
+/// Struct used for encoding/decoding
+/// `(record {
+///     canister_id: principal;
+/// })`
+#[derive(Clone, Eq, PartialEq, Debug, Default, CandidType, Deserialize)]
+pub struct ListScheduledManagementCallsArgs {
+    canister_id: PrincipalId,
+}
+
+impl ListScheduledManagementCallsArgs {
+    pub fn new(canister_id: CanisterId) -> Self {
+        Self {
+            canister_id: canister_id.get(),
+        }
+    }
+
+    pub fn get_canister_id(&self) -> CanisterId {
+        CanisterId::unchecked_from_principal(self.canister_id)
+    }
+}
+
+impl Payload<'_> for ListScheduledManagementCallsArgs {}
+
+/// Struct used for encoding/decoding
+/// `(record {
+///     call_id: nat64;
+///     request_id: blob;
+///     sender: principal;
+///     method_name: text;
+///     execute_at_nanos: nat64;
+///     cycles: nat;
+/// })`
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub struct ScheduledManagementCallRecord {
+    pub call_id: u64,
+    #[serde(with = "serde_bytes")]
+    pub request_id: Vec<u8>,
+    pub sender: PrincipalId,
+    pub method_name: String,
+    pub execute_at_nanos: u64,
+    pub cycles: candid::Nat,
+}
+
+/// Struct used for encoding/decoding
+/// `(vec scheduled_management_call)`
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub struct ListScheduledManagementCallsResponse(pub Vec<ScheduledManagementCallRecord>);
+
+impl Payload<'_> for ListScheduledManagementCallsResponse {}
+
+/// Struct used for encoding/decoding
+/// `(record {
+///     canister_id: principal;
+///     call_id: nat64;
+/// })`
+#[derive(Clone, Eq, PartialEq, Debug, Default, CandidType, Deserialize)]
+pub struct CancelScheduledManagementCallArgs {
+    canister_id: PrincipalId,
+    call_id: u64,
+}
+
+impl CancelScheduledManagementCallArgs {
+    pub fn new(canister_id: CanisterId, call_id: u64) -> Self {
+        Self {
+            canister_id: canister_id.get(),
+            call_id,
+        }
+    }
+
+    pub fn get_canister_id(&self) -> CanisterId {
+        CanisterId::unchecked_from_principal(self.canister_id)
+    }
+
+    pub fn get_call_id(&self) -> u64 {
+        self.call_id
+    }
+}
+
+impl Payload<'_> for CancelScheduledManagementCallArgs {}
+
+/// Struct used for encoding/decoding
+/// `(record {
+///     canister_id: principal;
+///     call_id: nat64;
+/// })`
+#[derive(Clone, Eq, PartialEq, Debug, Default, CandidType, Deserialize)]
+pub struct ScheduledManagementCallStatusArgs {
+    canister_id: PrincipalId,
+    call_id: u64,
+}
+
+impl ScheduledManagementCallStatusArgs {
+    pub fn new(canister_id: CanisterId, call_id: u64) -> Self {
+        Self {
+            canister_id: canister_id.get(),
+            call_id,
+        }
+    }
+
+    pub fn get_canister_id(&self) -> CanisterId {
+        CanisterId::unchecked_from_principal(self.canister_id)
+    }
+
+    pub fn get_call_id(&self) -> u64 {
+        self.call_id
+    }
+}
+
+impl Payload<'_> for ScheduledManagementCallStatusArgs {}
+
+/// The status of a scheduled management call.
+///
+/// ```text
+/// variant {
+///     scheduled;
+///     processing;
+///     replied : blob;
+///     rejected : record {
+///         reject_code : nat32;
+///         reject_message : text;
+///     };
+///     done;
+///     unknown;
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub enum ScheduledManagementCallStatusResponse {
+    /// The call is not due yet.
+    #[serde(rename = "scheduled")]
+    Scheduled,
+    /// The call is due and is being executed.
+    #[serde(rename = "processing")]
+    Processing,
+    /// The call succeeded with the given Candid-encoded reply.
+    #[serde(rename = "replied")]
+    Replied(#[serde(with = "serde_bytes")] Vec<u8>),
+    /// The call failed.
+    #[serde(rename = "rejected")]
+    Rejected {
+        reject_code: u32,
+        reject_message: String,
+    },
+    /// The call was executed, but its outcome is no longer retained.
+    #[serde(rename = "done")]
+    Done,
+    /// No call with the given ID was scheduled for the canister, or the call
+    /// was cancelled.
+    #[serde(rename = "unknown")]
+    Unknown,
+}
+
+impl Payload<'_> for ScheduledManagementCallStatusResponse {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
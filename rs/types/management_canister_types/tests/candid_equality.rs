@@ -32,6 +32,11 @@ type ListCanisterSnapshotsArgs = CanisterIdRecord;
 type ListCanisterSnapshotsResult = Vec<CanisterSnapshotResponse>;
 type FetchCanisterLogsArgs = FetchCanisterLogsRequest;
 type FetchCanisterLogsResult = FetchCanisterLogsResponse;
+type FetchCanisterHistoryArgs = FetchCanisterHistoryRequest;
+type FetchCanisterHistoryResult = FetchCanisterHistoryResponse;
+type SimulateUpdateCallResult = SimulateUpdateCallResponse;
+type ListScheduledManagementCallsResult = ListScheduledManagementCallsResponse;
+type ScheduledManagementCallStatusResult = ScheduledManagementCallStatusResponse;
 
 #[candid_method(update)]
 fn create_canister(_: CreateCanisterArgs) -> CreateCanisterResult {
@@ -180,6 +185,16 @@ fn fetch_canister_logs(_: FetchCanisterLogsArgs) -> FetchCanisterLogsResult {
     unreachable!()
 }
 
+#[candid_method(query)]
+fn fetch_canister_history(_: FetchCanisterHistoryArgs) -> FetchCanisterHistoryResult {
+    unreachable!()
+}
+
+#[candid_method(query)]
+fn simulate_update_call(_: SimulateUpdateCallArgs) -> SimulateUpdateCallResult {
+    unreachable!()
+}
+
 #[candid_method(update)]
 fn read_canister_snapshot_metadata(
     _: ReadCanisterSnapshotMetadataArgs,
@@ -211,6 +226,30 @@ fn rename_canister(_: RenameCanisterArgs) {
     unreachable!()
 }
 
+#[candid_method(update)]
+fn schedule_management_call(_: ScheduleManagementCallArgs) -> ScheduleManagementCallResult {
+    unreachable!()
+}
+
+#[candid_method(update)]
+fn list_scheduled_management_calls(
+    _: ListScheduledManagementCallsArgs,
+) -> ListScheduledManagementCallsResult {
+    unreachable!()
+}
+
+#[candid_method(update)]
+fn cancel_scheduled_management_call(_: CancelScheduledManagementCallArgs) {
+    unreachable!()
+}
+
+#[candid_method(update)]
+fn scheduled_management_call_status(
+    _: ScheduledManagementCallStatusArgs,
+) -> ScheduledManagementCallStatusResult {
+    unreachable!()
+}
+
 #[cfg(test)]
 mod test {
     use crate::*;
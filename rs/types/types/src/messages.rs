@@ -413,9 +413,23 @@ impl CanisterCall {
         }
     }
 
+    /// Returns the origin recorded in the canister history for changes made
+    /// by this message.
+    ///
+    /// Users cannot send ingress messages with a canister ID as their source.
+    /// Such messages are enqueued by the system on behalf of the canister,
+    /// e.g. for management calls the canister scheduled, and are recorded
+    /// with a canister origin.
     pub fn canister_change_origin(&self, canister_version: Option<u64>) -> CanisterChangeOrigin {
         match self {
-            CanisterCall::Ingress(msg) => CanisterChangeOrigin::from_user(msg.source.get()),
+            CanisterCall::Ingress(msg) => {
+                match CanisterId::try_from_principal_id(msg.source.get()) {
+                    Ok(canister_id) => {
+                        CanisterChangeOrigin::from_canister(canister_id.get(), canister_version)
+                    }
+                    Err(_) => CanisterChangeOrigin::from_user(msg.source.get()),
+                }
+            }
             CanisterCall::Request(msg) => {
                 CanisterChangeOrigin::from_canister(msg.sender.into(), canister_version)
             }
@@ -451,6 +465,7 @@ pub enum CanisterTask {
     Heartbeat = 1,
     GlobalTimer = 2,
     OnLowWasmMemory = 3,
+    OnLowCycles = 4,
 }
 
 impl From<CanisterTask> for SystemMethod {
@@ -459,6 +474,7 @@ impl From<CanisterTask> for SystemMethod {
             CanisterTask::Heartbeat => SystemMethod::CanisterHeartbeat,
             CanisterTask::GlobalTimer => SystemMethod::CanisterGlobalTimer,
             CanisterTask::OnLowWasmMemory => SystemMethod::CanisterOnLowWasmMemory,
+            CanisterTask::OnLowCycles => SystemMethod::CanisterOnLowCycles,
         }
     }
 }
@@ -469,6 +485,7 @@ impl Display for CanisterTask {
             Self::Heartbeat => write!(f, "Heartbeat task"),
             Self::GlobalTimer => write!(f, "Global timer task"),
             Self::OnLowWasmMemory => write!(f, "On low Wasm memory task"),
+            Self::OnLowCycles => write!(f, "On low cycles task"),
         }
     }
 }
@@ -479,6 +496,7 @@ impl From<&CanisterTask> for pb::execution_task::CanisterTask {
             CanisterTask::Heartbeat => pb::execution_task::CanisterTask::Heartbeat,
             CanisterTask::GlobalTimer => pb::execution_task::CanisterTask::Timer,
             CanisterTask::OnLowWasmMemory => pb::execution_task::CanisterTask::OnLowWasmMemory,
+            CanisterTask::OnLowCycles => pb::execution_task::CanisterTask::OnLowCycles,
         }
     }
 }
@@ -497,6 +515,7 @@ impl TryFrom<pb::execution_task::CanisterTask> for CanisterTask {
             pb::execution_task::CanisterTask::Heartbeat => Ok(CanisterTask::Heartbeat),
             pb::execution_task::CanisterTask::Timer => Ok(CanisterTask::GlobalTimer),
             pb::execution_task::CanisterTask::OnLowWasmMemory => Ok(CanisterTask::OnLowWasmMemory),
+            pb::execution_task::CanisterTask::OnLowCycles => Ok(CanisterTask::OnLowCycles),
         }
     }
 }
@@ -7,12 +7,14 @@ use ic_error_types::{RejectCode, UserError};
 #[cfg(test)]
 use ic_exhaustive_derive::ExhaustiveSet;
 use ic_management_canister_types_private::{
-    CanisterIdRecord, CanisterInfoRequest, ClearChunkStoreArgs, DeleteCanisterSnapshotArgs,
-    InstallChunkedCodeArgs, InstallCodeArgsV2, ListCanisterSnapshotArgs, LoadCanisterSnapshotArgs,
-    Method, Payload as _, ProvisionalTopUpCanisterArgs, ReadCanisterSnapshotDataArgs,
-    ReadCanisterSnapshotMetadataArgs, RenameCanisterArgs, StoredChunksArgs,
-    TakeCanisterSnapshotArgs, UpdateSettingsArgs, UploadCanisterSnapshotDataArgs,
-    UploadCanisterSnapshotMetadataArgs, UploadChunkArgs,
+    CancelScheduledManagementCallArgs, CanisterIdRecord, CanisterInfoRequest, ClearChunkStoreArgs,
+    DeleteCanisterSnapshotArgs, InstallChunkedCodeArgs, InstallCodeArgsV2,
+    ListCanisterSnapshotArgs, ListScheduledManagementCallsArgs, LoadCanisterSnapshotArgs, Method,
+    Payload as _, ProvisionalTopUpCanisterArgs, ReadCanisterSnapshotDataArgs,
+    ReadCanisterSnapshotMetadataArgs, RenameCanisterArgs, ScheduleManagementCallArgs,
+    ScheduledManagementCallStatusArgs, StoredChunksArgs, TakeCanisterSnapshotArgs,
+    UpdateSettingsArgs, UploadCanisterSnapshotDataArgs, UploadCanisterSnapshotMetadataArgs,
+    UploadChunkArgs,
 };
 use ic_protobuf::{
     proxy::{try_from_option_field, ProxyDecodeError},
@@ -248,6 +250,30 @@ impl Request {
                 Ok(record) => Some(record.get_canister_id()),
                 Err(_) => None,
             },
+            Ok(Method::ScheduleManagementCall) => {
+                match ScheduleManagementCallArgs::decode(&self.method_payload) {
+                    Ok(record) => Some(record.get_canister_id()),
+                    Err(_) => None,
+                }
+            }
+            Ok(Method::ListScheduledManagementCalls) => {
+                match ListScheduledManagementCallsArgs::decode(&self.method_payload) {
+                    Ok(record) => Some(record.get_canister_id()),
+                    Err(_) => None,
+                }
+            }
+            Ok(Method::CancelScheduledManagementCall) => {
+                match CancelScheduledManagementCallArgs::decode(&self.method_payload) {
+                    Ok(record) => Some(record.get_canister_id()),
+                    Err(_) => None,
+                }
+            }
+            Ok(Method::ScheduledManagementCallStatus) => {
+                match ScheduledManagementCallStatusArgs::decode(&self.method_payload) {
+                    Ok(record) => Some(record.get_canister_id()),
+                    Err(_) => None,
+                }
+            }
             Ok(Method::CreateCanister)
             | Ok(Method::SetupInitialDKG)
             | Ok(Method::HttpRequest)
@@ -272,10 +298,11 @@ impl Request {
                 // No effective canister id.
                 None
             }
-            // `FetchCanisterLogs` method is only allowed for messages sent by
-            // end users in non-replicated mode, so we should never reach this point.
-            // If we do, we return `None` (which should be no-op) to avoid panicking.
-            Ok(Method::FetchCanisterLogs) => None,
+            // `FetchCanisterLogs` and `FetchCanisterHistory` methods are only allowed
+            // for messages sent by end users in non-replicated mode, so we should never
+            // reach this point. If we do, we return `None` (which should be no-op) to
+            // avoid panicking.
+            Ok(Method::FetchCanisterLogs) | Ok(Method::FetchCanisterHistory) => None,
             Err(_) => None,
         }
     }